@@ -22,6 +22,7 @@ use integration::http_client::HttpClientFactory;
 use integration::quic_client::QuicClientFactory;
 use integration::tcp_client::TcpClientFactory;
 use integration::test_server::{ClientFactory, Transport};
+use integration::websocket_client::WebSocketClientFactory;
 use std::sync::Arc;
 
 pub fn create_client_factory(args: &IggyBenchArgs) -> Arc<dyn ClientFactory> {
@@ -47,5 +48,8 @@ pub fn create_client_factory(args: &IggyBenchArgs) -> Arc<dyn ClientFactory> {
         Transport::Quic => Arc::new(QuicClientFactory {
             server_addr: args.server_address().to_owned(),
         }),
+        Transport::WebSocket => Arc::new(WebSocketClientFactory {
+            server_addr: args.server_address().to_owned(),
+        }),
     }
 }
@@ -117,6 +117,7 @@ pub fn params_from_args_and_metrics(
         Transport::Tcp => BenchmarkTransport::Tcp,
         Transport::Quic => BenchmarkTransport::Quic,
         Transport::Http => BenchmarkTransport::Http,
+        Transport::WebSocket => BenchmarkTransport::WebSocket,
     };
     let server_address = args.server_address().to_string();
     let remark = args.remark();
@@ -96,7 +96,7 @@ async fn evaluate_server_start_condition(args: &IggyBenchArgs) -> (bool, HashMap
         toml::from_str(include_str!("../../../configs/server.toml")).unwrap();
 
     match &args.transport() {
-        Transport::Http => {
+        Transport::Http | Transport::WebSocket => {
             let args_http_address = args.server_address().parse::<SocketAddr>().unwrap();
             let config_http_address = default_config.http.address.parse::<SocketAddr>().unwrap();
             let envs = HashMap::from([
@@ -54,6 +54,7 @@ impl CreateTopicCmd {
                 message_expiry,
                 max_topic_size,
                 replication_factor: Some(replication_factor),
+                overrides: Default::default(),
             },
             message_expiry,
             max_topic_size,
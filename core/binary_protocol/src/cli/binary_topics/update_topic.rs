@@ -51,6 +51,7 @@ impl UpdateTopicCmd {
                 message_expiry,
                 max_topic_size,
                 replication_factor: Some(replication_factor),
+                overrides: None,
             },
             message_expiry,
             max_topic_size,
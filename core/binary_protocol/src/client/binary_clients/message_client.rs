@@ -17,7 +17,8 @@
  */
 use async_trait::async_trait;
 use iggy_common::{
-    Consumer, Identifier, IggyError, IggyMessage, Partitioning, PolledMessages, PollingStrategy,
+    Consumer, Identifier, IggyDuration, IggyError, IggyMessage, Partitioning, PolledMessages,
+    PollingStrategy, ProducerSequence,
 };
 
 /// This trait defines the methods to interact with the messaging module.
@@ -38,6 +39,24 @@ pub trait MessageClient {
         auto_commit: bool,
     ) -> Result<PolledMessages, IggyError>;
 
+    /// Poll given amount of messages using the specified consumer and strategy from the specified stream and topic by unique IDs or names.
+    /// If there are no messages available, the server holds the request until the new messages are appended to the topic or the timeout elapses,
+    /// in which case the empty result is returned.
+    ///
+    /// Authentication is required, and the permission to poll the messages.
+    #[allow(clippy::too_many_arguments)]
+    async fn long_poll_messages(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: Option<u32>,
+        consumer: &Consumer,
+        strategy: &PollingStrategy,
+        count: u32,
+        auto_commit: bool,
+        timeout: &IggyDuration,
+    ) -> Result<PolledMessages, IggyError>;
+
     /// Send messages using specified partitioning strategy to the given stream and topic by unique IDs or names.
     ///
     /// Authentication is required, and the permission to send the messages.
@@ -49,6 +68,21 @@ pub trait MessageClient {
         messages: &mut [IggyMessage],
     ) -> Result<(), IggyError>;
 
+    /// Send messages to the specified partition of the given stream and topic by unique IDs or names, as a part of the producer sequence.
+    /// The server keeps track of the last sequence number of each producer in the partition,
+    /// thus the messages which have been already appended are discarded, which makes the retries safe,
+    /// while the messages with a gap in the sequence are rejected with `ProducerSequenceOutOfOrder` error.
+    ///
+    /// Authentication is required, and the permission to send the messages.
+    async fn send_messages_with_sequence(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: u32,
+        producer_sequence: &ProducerSequence,
+        messages: &mut [IggyMessage],
+    ) -> Result<(), IggyError>;
+
     /// Force flush of the `unsaved_messages` buffer to disk, optionally fsyncing the data.
     #[allow(clippy::too_many_arguments)]
     async fn flush_unsaved_buffer(
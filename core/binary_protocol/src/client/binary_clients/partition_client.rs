@@ -44,4 +44,35 @@ pub trait PartitionClient {
         topic_id: &Identifier,
         partitions_count: u32,
     ) -> Result<(), IggyError>;
+    /// Move the partition to another data directory without stopping the server.
+    ///
+    /// The directory must be either the system path or one of the configured partition directories.
+    /// The messages and offsets are preserved, and the partition keeps serving the requests during the move.
+    ///
+    /// Authentication is required, and the permission to manage the partitions.
+    async fn move_partition(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: u32,
+        directory: &str,
+    ) -> Result<(), IggyError>;
+    /// Safely remove the last N partitions from the topic.
+    ///
+    /// By default, the removed partitions stop accepting new messages and are deleted once all the consumer groups and consumers have consumed them.
+    /// With `copy_unconsumed` enabled, the messages not yet consumed by all the consumer groups are copied into the remaining partitions,
+    /// distributed by the value of the `key_header` user header if provided, and the partitions are deleted right away.
+    /// The key is hashed the same way as for the `MessagesKey` partitioning, or `ConsistentMessagesKey` with `consistent_hashing` enabled,
+    /// which should match the partitioning the messages were sent with.
+    ///
+    /// Authentication is required, and the permission to manage the partitions.
+    async fn shrink_partitions(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partitions_count: u32,
+        copy_unconsumed: bool,
+        key_header: Option<&str>,
+        consistent_hashing: bool,
+    ) -> Result<(), IggyError>;
 }
@@ -18,8 +18,8 @@
 
 use async_trait::async_trait;
 use iggy_common::{
-    ClientInfo, ClientInfoDetails, IggyDuration, IggyError, Snapshot, SnapshotCompression, Stats,
-    SystemSnapshotType,
+    ClientInfo, ClientInfoDetails, DataSnapshotTarget, IggyDuration, IggyError, Snapshot,
+    SnapshotCompression, Stats, SystemSnapshotType,
 };
 
 /// This trait defines the methods to interact with the system module.
@@ -52,4 +52,14 @@ pub trait SystemClient {
         compression: SnapshotCompression,
         snapshot_types: Vec<SystemSnapshotType>,
     ) -> Result<Snapshot, IggyError>;
+    /// Create a point-in-time backup of the selected streams and topics (all of them if no targets are specified),
+    /// as a tar archive compressed with ZStandard, which contains the segments, indexes, consumer offsets and state entries.
+    ///
+    /// Authentication is required, and the permission to poll the messages from the topics.
+    async fn data_snapshot(&self, targets: Vec<DataSnapshotTarget>) -> Result<Snapshot, IggyError>;
+    /// Restore the streams and topics from the data snapshot. The existing streams with the same ID and name are reused,
+    /// while the topics must not exist yet.
+    ///
+    /// Authentication is required, and the permission to manage the streams and topics.
+    async fn restore_data_snapshot(&self, snapshot: &Snapshot) -> Result<(), IggyError>;
 }
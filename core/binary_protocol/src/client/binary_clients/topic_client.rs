@@ -18,7 +18,8 @@
 
 use async_trait::async_trait;
 use iggy_common::{
-    CompressionAlgorithm, Identifier, IggyError, IggyExpiry, MaxTopicSize, Topic, TopicDetails,
+    CompressionAlgorithm, Identifier, IggyDuration, IggyError, IggyExpiry, MaxTopicSize, Topic,
+    TopicDetails, TopicOverrides,
 };
 
 /// This trait defines the methods to interact with the topic module.
@@ -52,6 +53,22 @@ pub trait TopicClient {
         message_expiry: IggyExpiry,
         max_topic_size: MaxTopicSize,
     ) -> Result<TopicDetails, IggyError>;
+    /// Create a new topic with the per-topic overrides of the server settings (e.g. segment size or fsync).
+    ///
+    /// Authentication is required, and the permission to manage the topics.
+    #[allow(clippy::too_many_arguments)]
+    async fn create_topic_with_overrides(
+        &self,
+        stream_id: &Identifier,
+        name: &str,
+        partitions_count: u32,
+        compression_algorithm: CompressionAlgorithm,
+        replication_factor: Option<u8>,
+        topic_id: Option<u32>,
+        message_expiry: IggyExpiry,
+        max_topic_size: MaxTopicSize,
+        overrides: TopicOverrides,
+    ) -> Result<TopicDetails, IggyError>;
     /// Update a topic by unique ID or name.
     ///
     /// Authentication is required, and the permission to manage the topics.
@@ -65,6 +82,22 @@ pub trait TopicClient {
         message_expiry: IggyExpiry,
         max_topic_size: MaxTopicSize,
     ) -> Result<(), IggyError>;
+    /// Update a topic by unique ID or name, including the per-topic overrides of the server settings.
+    /// The overrides replace the current ones, the settings which are not overridden fall back to the server config.
+    ///
+    /// Authentication is required, and the permission to manage the topics.
+    #[allow(clippy::too_many_arguments)]
+    async fn update_topic_with_overrides(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        name: &str,
+        compression_algorithm: CompressionAlgorithm,
+        replication_factor: Option<u8>,
+        message_expiry: IggyExpiry,
+        max_topic_size: MaxTopicSize,
+        overrides: TopicOverrides,
+    ) -> Result<(), IggyError>;
     /// Delete a topic by unique ID or name.
     ///
     /// Authentication is required, and the permission to manage the topics.
@@ -81,4 +114,10 @@ pub trait TopicClient {
         stream_id: &Identifier,
         topic_id: &Identifier,
     ) -> Result<(), IggyError>;
+    /// Wait for the changes of the topics, i.e. created or deleted topics and streams, and return the current topics version.
+    /// If the provided version (`0` if unknown) differs from the current one, it's returned immediately,
+    /// otherwise the server holds the request until the topics change or the timeout elapses.
+    ///
+    /// Authentication is required.
+    async fn watch_topics(&self, version: u64, timeout: &IggyDuration) -> Result<u64, IggyError>;
 }
@@ -18,9 +18,10 @@
 use crate::utils::auth::fail_if_not_authenticated;
 use crate::{BinaryClient, MessageClient};
 use iggy_common::{
-    BytesSerializable, Consumer, FlushUnsavedBuffer, Identifier, IggyError, IggyMessage,
-    POLL_MESSAGES_CODE, Partitioning, PollMessages, PolledMessages, PollingStrategy,
-    SEND_MESSAGES_CODE, SendMessages,
+    BytesSerializable, Consumer, FlushUnsavedBuffer, Identifier, IggyDuration, IggyError,
+    IggyMessage, LONG_POLL_MESSAGES_CODE, LongPollMessages, POLL_MESSAGES_CODE, Partitioning,
+    PollMessages, PolledMessages, PollingStrategy, ProducerSequence, SEND_MESSAGES_CODE,
+    SendMessages,
 };
 
 #[async_trait::async_trait]
@@ -53,6 +54,36 @@ impl<B: BinaryClient> MessageClient for B {
         PolledMessages::from_bytes(response)
     }
 
+    async fn long_poll_messages(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: Option<u32>,
+        consumer: &Consumer,
+        strategy: &PollingStrategy,
+        count: u32,
+        auto_commit: bool,
+        timeout: &IggyDuration,
+    ) -> Result<PolledMessages, IggyError> {
+        fail_if_not_authenticated(self).await?;
+        let response = self
+            .send_raw_with_response(
+                LONG_POLL_MESSAGES_CODE,
+                LongPollMessages::bytes(
+                    stream_id,
+                    topic_id,
+                    partition_id,
+                    consumer,
+                    strategy,
+                    count,
+                    auto_commit,
+                    *timeout,
+                ),
+            )
+            .await?;
+        PolledMessages::from_bytes(response)
+    }
+
     async fn send_messages(
         &self,
         stream_id: &Identifier,
@@ -63,7 +94,30 @@ impl<B: BinaryClient> MessageClient for B {
         fail_if_not_authenticated(self).await?;
         self.send_raw_with_response(
             SEND_MESSAGES_CODE,
-            SendMessages::bytes(stream_id, topic_id, partitioning, messages),
+            SendMessages::bytes(stream_id, topic_id, partitioning, None, messages),
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn send_messages_with_sequence(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: u32,
+        producer_sequence: &ProducerSequence,
+        messages: &mut [IggyMessage],
+    ) -> Result<(), IggyError> {
+        fail_if_not_authenticated(self).await?;
+        self.send_raw_with_response(
+            SEND_MESSAGES_CODE,
+            SendMessages::bytes(
+                stream_id,
+                topic_id,
+                &Partitioning::partition_id(partition_id),
+                Some(producer_sequence),
+                messages,
+            ),
         )
         .await?;
         Ok(())
@@ -20,6 +20,8 @@ use crate::utils::auth::fail_if_not_authenticated;
 use crate::{BinaryClient, PartitionClient};
 use iggy_common::create_partitions::CreatePartitions;
 use iggy_common::delete_partitions::DeletePartitions;
+use iggy_common::move_partition::MovePartition;
+use iggy_common::shrink_partitions::ShrinkPartitions;
 use iggy_common::{Identifier, IggyError};
 
 #[async_trait::async_trait]
@@ -55,4 +57,44 @@ impl<B: BinaryClient> PartitionClient for B {
         .await?;
         Ok(())
     }
+
+    async fn move_partition(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: u32,
+        directory: &str,
+    ) -> Result<(), IggyError> {
+        fail_if_not_authenticated(self).await?;
+        self.send_with_response(&MovePartition {
+            stream_id: stream_id.clone(),
+            topic_id: topic_id.clone(),
+            partition_id,
+            directory: directory.to_string(),
+        })
+        .await?;
+        Ok(())
+    }
+
+    async fn shrink_partitions(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partitions_count: u32,
+        copy_unconsumed: bool,
+        key_header: Option<&str>,
+        consistent_hashing: bool,
+    ) -> Result<(), IggyError> {
+        fail_if_not_authenticated(self).await?;
+        self.send_with_response(&ShrinkPartitions {
+            stream_id: stream_id.clone(),
+            topic_id: topic_id.clone(),
+            partitions_count,
+            copy_unconsumed,
+            key_header: key_header.map(|key_header| key_header.to_string()),
+            consistent_hashing,
+        })
+        .await?;
+        Ok(())
+    }
 }
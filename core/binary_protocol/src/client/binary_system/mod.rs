@@ -21,13 +21,15 @@ use crate::utils::mapper;
 use crate::{BinaryClient, SystemClient};
 use iggy_common::get_client::GetClient;
 use iggy_common::get_clients::GetClients;
+use iggy_common::get_data_snapshot::GetDataSnapshot;
 use iggy_common::get_me::GetMe;
 use iggy_common::get_snapshot::GetSnapshot;
 use iggy_common::get_stats::GetStats;
 use iggy_common::ping::Ping;
+use iggy_common::restore_data_snapshot::RestoreDataSnapshot;
 use iggy_common::{
-    ClientInfo, ClientInfoDetails, IggyDuration, IggyError, Snapshot, SnapshotCompression, Stats,
-    SystemSnapshotType,
+    ClientInfo, ClientInfoDetails, DataSnapshotTarget, IggyDuration, IggyError, Snapshot,
+    SnapshotCompression, Stats, SystemSnapshotType,
 };
 
 #[async_trait::async_trait]
@@ -83,4 +85,22 @@ impl<B: BinaryClient> SystemClient for B {
         let snapshot = Snapshot::new(response.to_vec());
         Ok(snapshot)
     }
+
+    async fn data_snapshot(&self, targets: Vec<DataSnapshotTarget>) -> Result<Snapshot, IggyError> {
+        fail_if_not_authenticated(self).await?;
+        let response = self
+            .send_with_response(&GetDataSnapshot { targets })
+            .await?;
+        let snapshot = Snapshot::new(response.to_vec());
+        Ok(snapshot)
+    }
+
+    async fn restore_data_snapshot(&self, snapshot: &Snapshot) -> Result<(), IggyError> {
+        fail_if_not_authenticated(self).await?;
+        self.send_with_response(&RestoreDataSnapshot {
+            snapshot: snapshot.0.clone(),
+        })
+        .await?;
+        Ok(())
+    }
 }
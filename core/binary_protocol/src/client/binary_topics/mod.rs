@@ -25,8 +25,10 @@ use iggy_common::get_topic::GetTopic;
 use iggy_common::get_topics::GetTopics;
 use iggy_common::purge_topic::PurgeTopic;
 use iggy_common::update_topic::UpdateTopic;
+use iggy_common::watch_topics::WatchTopics;
 use iggy_common::{
-    CompressionAlgorithm, Identifier, IggyError, IggyExpiry, MaxTopicSize, Topic, TopicDetails,
+    CompressionAlgorithm, Identifier, IggyDuration, IggyError, IggyExpiry, MaxTopicSize, Topic,
+    TopicDetails, TopicOverrides,
 };
 
 #[async_trait::async_trait]
@@ -70,6 +72,32 @@ impl<B: BinaryClient> TopicClient for B {
         topic_id: Option<u32>,
         message_expiry: IggyExpiry,
         max_topic_size: MaxTopicSize,
+    ) -> Result<TopicDetails, IggyError> {
+        self.create_topic_with_overrides(
+            stream_id,
+            name,
+            partitions_count,
+            compression_algorithm,
+            replication_factor,
+            topic_id,
+            message_expiry,
+            max_topic_size,
+            TopicOverrides::default(),
+        )
+        .await
+    }
+
+    async fn create_topic_with_overrides(
+        &self,
+        stream_id: &Identifier,
+        name: &str,
+        partitions_count: u32,
+        compression_algorithm: CompressionAlgorithm,
+        replication_factor: Option<u8>,
+        topic_id: Option<u32>,
+        message_expiry: IggyExpiry,
+        max_topic_size: MaxTopicSize,
+        overrides: TopicOverrides,
     ) -> Result<TopicDetails, IggyError> {
         fail_if_not_authenticated(self).await?;
         let response = self
@@ -82,6 +110,7 @@ impl<B: BinaryClient> TopicClient for B {
                 topic_id,
                 message_expiry,
                 max_topic_size,
+                overrides,
             })
             .await?;
         mapper::map_topic(response)
@@ -106,6 +135,33 @@ impl<B: BinaryClient> TopicClient for B {
             replication_factor,
             message_expiry,
             max_topic_size,
+            overrides: None,
+        })
+        .await?;
+        Ok(())
+    }
+
+    async fn update_topic_with_overrides(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        name: &str,
+        compression_algorithm: CompressionAlgorithm,
+        replication_factor: Option<u8>,
+        message_expiry: IggyExpiry,
+        max_topic_size: MaxTopicSize,
+        overrides: TopicOverrides,
+    ) -> Result<(), IggyError> {
+        fail_if_not_authenticated(self).await?;
+        self.send_with_response(&UpdateTopic {
+            stream_id: stream_id.clone(),
+            topic_id: topic_id.clone(),
+            name: name.to_string(),
+            compression_algorithm,
+            replication_factor,
+            message_expiry,
+            max_topic_size,
+            overrides: Some(overrides),
         })
         .await?;
         Ok(())
@@ -138,4 +194,20 @@ impl<B: BinaryClient> TopicClient for B {
         .await?;
         Ok(())
     }
+
+    async fn watch_topics(&self, version: u64, timeout: &IggyDuration) -> Result<u64, IggyError> {
+        fail_if_not_authenticated(self).await?;
+        let response = self
+            .send_with_response(&WatchTopics {
+                version,
+                timeout: *timeout,
+            })
+            .await?;
+        let version = response
+            .get(..8)
+            .ok_or(IggyError::InvalidNumberEncoding)?
+            .try_into()
+            .map_err(|_| IggyError::InvalidNumberEncoding)?;
+        Ok(u64::from_le_bytes(version))
+    }
 }
@@ -906,6 +906,10 @@ fn map_to_client_info(
     let transport = match transport {
         1 => "TCP",
         2 => "QUIC",
+        3 => "WebSocket",
+        4 => "HTTP",
+        5 => "Kafka",
+        6 => "MQTT",
         _ => "Unknown",
     }
     .to_string();
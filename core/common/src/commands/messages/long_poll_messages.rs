@@ -0,0 +1,188 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::error::IggyError;
+use crate::{
+    BytesSerializable, Identifier, IggyDuration, PollMessages, PollingStrategy, Validatable,
+};
+use crate::{Command, Consumer, LONG_POLL_MESSAGES_CODE};
+use bytes::{BufMut, Bytes, BytesMut};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::time::Duration;
+
+/// The maximum time the server is allowed to hold a long polling request.
+pub const MAX_LONG_POLLING_TIMEOUT: IggyDuration = IggyDuration::new(Duration::from_secs(60));
+
+/// `LongPollMessages` command is used to poll messages from a topic in a stream,
+/// waiting on the server until new messages are appended or the timeout elapses.
+/// It has the same payload as `PollMessages` with the additional field:
+/// - `timeout` - maximum time to wait for new messages, if there are none available at the time of the request.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct LongPollMessages {
+    /// Poll messages command which will be repeated until any messages are returned.
+    #[serde(flatten)]
+    pub poll: PollMessages,
+    /// Maximum time to wait for new messages.
+    pub timeout: IggyDuration,
+}
+
+impl LongPollMessages {
+    #[allow(clippy::too_many_arguments)]
+    pub fn bytes(
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: Option<u32>,
+        consumer: &Consumer,
+        strategy: &PollingStrategy,
+        count: u32,
+        auto_commit: bool,
+        timeout: IggyDuration,
+    ) -> Bytes {
+        let poll_bytes = PollMessages::bytes(
+            stream_id,
+            topic_id,
+            partition_id,
+            consumer,
+            strategy,
+            count,
+            auto_commit,
+        );
+        let mut bytes = BytesMut::with_capacity(poll_bytes.len() + 8);
+        bytes.put_slice(&poll_bytes);
+        bytes.put_u64_le(timeout.as_micros());
+        bytes.freeze()
+    }
+}
+
+impl Default for LongPollMessages {
+    fn default() -> Self {
+        Self {
+            poll: PollMessages::default(),
+            timeout: IggyDuration::ONE_SECOND,
+        }
+    }
+}
+
+impl Command for LongPollMessages {
+    fn code(&self) -> u32 {
+        LONG_POLL_MESSAGES_CODE
+    }
+}
+
+impl LongPollMessages {
+    /// Ensures that the timeout doesn't exceed `MAX_LONG_POLLING_TIMEOUT`.
+    /// Unlike the regular validation, it's invoked by the server when handling the command,
+    /// so that the error can be returned to the client without closing the connection.
+    pub fn validate_timeout(&self) -> Result<(), IggyError> {
+        if self.timeout.as_micros() > MAX_LONG_POLLING_TIMEOUT.as_micros() {
+            return Err(IggyError::InvalidLongPollingTimeout(
+                self.timeout.as_micros(),
+                MAX_LONG_POLLING_TIMEOUT.as_micros(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Validatable<IggyError> for LongPollMessages {
+    fn validate(&self) -> Result<(), IggyError> {
+        self.poll.validate()
+    }
+}
+
+impl BytesSerializable for LongPollMessages {
+    fn to_bytes(&self) -> Bytes {
+        LongPollMessages::bytes(
+            &self.poll.stream_id,
+            &self.poll.topic_id,
+            self.poll.partition_id,
+            &self.poll.consumer,
+            &self.poll.strategy,
+            self.poll.count,
+            self.poll.auto_commit,
+            self.timeout,
+        )
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<Self, IggyError> {
+        if bytes.len() < 37 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let position = bytes.len() - 8;
+        let poll = PollMessages::from_bytes(bytes.slice(..position))?;
+        let timeout = u64::from_le_bytes(
+            bytes[position..]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        Ok(LongPollMessages {
+            poll,
+            timeout: timeout.into(),
+        })
+    }
+}
+
+impl Display for LongPollMessages {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}|{}", self.poll, self.timeout.as_micros())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_be_serialized_as_bytes_and_deserialized() {
+        let command = LongPollMessages {
+            poll: PollMessages {
+                consumer: Consumer::new(Identifier::numeric(1).unwrap()),
+                stream_id: Identifier::numeric(2).unwrap(),
+                topic_id: Identifier::named("test").unwrap(),
+                partition_id: Some(4),
+                strategy: PollingStrategy::offset(2),
+                count: 3,
+                auto_commit: true,
+            },
+            timeout: IggyDuration::new_from_secs(5),
+        };
+
+        let bytes = command.to_bytes();
+        let timeout = u64::from_le_bytes(bytes[bytes.len() - 8..].try_into().unwrap());
+        assert_eq!(timeout, 5_000_000);
+
+        let deserialized = LongPollMessages::from_bytes(bytes).unwrap();
+        assert_eq!(deserialized, command);
+    }
+
+    #[test]
+    fn timeout_above_maximum_should_be_invalid() {
+        let command = LongPollMessages {
+            poll: PollMessages::default(),
+            timeout: IggyDuration::new_from_secs(MAX_LONG_POLLING_TIMEOUT.as_secs() as u64 + 1),
+        };
+
+        assert!(matches!(
+            command.validate_timeout(),
+            Err(IggyError::InvalidLongPollingTimeout(_, _))
+        ));
+    }
+}
@@ -16,5 +16,6 @@
 // under the License.
 
 pub mod flush_unsaved_buffer;
+pub mod long_poll_messages;
 pub mod poll_messages;
 pub mod send_messages;
@@ -20,6 +20,7 @@ use crate::BytesSerializable;
 use crate::Identifier;
 use crate::IggyMessageView;
 use crate::PartitioningKind;
+use crate::ProducerSequence;
 use crate::Sizeable;
 use crate::Validatable;
 use crate::error::IggyError;
@@ -39,10 +40,11 @@ use std::fmt::{Display, Formatter};
 /// - `stream_id` - unique stream ID (numeric or name).
 /// - `topic_id` - unique topic ID (numeric or name).
 /// - `partitioning` - to which partition the messages should be sent - either provided by the client or calculated by the server.
+/// - `producer_sequence` - optional producer ID and sequence number of the first message, used by the idempotent producer.
 /// - `batch` - collection of messages to be sent.
 #[derive(Debug, PartialEq)]
 pub struct SendMessages {
-    /// Length of stream_id, topic_id, partitioning and messages_count (4 bytes)
+    /// Length of stream_id, topic_id, partitioning, messages_count (4 bytes) and the optional producer_sequence (16 bytes)
     pub metadata_length: u32,
     /// Unique stream ID (numeric or name).
     pub stream_id: Identifier,
@@ -50,6 +52,8 @@ pub struct SendMessages {
     pub topic_id: Identifier,
     /// To which partition the messages should be sent - either provided by the client or calculated by the server.
     pub partitioning: Partitioning,
+    /// Producer ID and sequence number of the first message, if sent by the idempotent producer.
+    pub producer_sequence: Option<ProducerSequence>,
     /// Messages collection
     pub batch: IggyMessagesBatch,
 }
@@ -59,6 +63,7 @@ impl SendMessages {
         stream_id: &Identifier,
         topic_id: &Identifier,
         partitioning: &Partitioning,
+        producer_sequence: Option<&ProducerSequence>,
         messages: &[IggyMessage],
     ) -> Bytes {
         let stream_id_field_size = stream_id.get_buffer_size();
@@ -67,10 +72,14 @@ impl SendMessages {
         let metadata_length_field_size = size_of::<u32>();
         let messages_count = messages.len();
         let messages_count_field_size = size_of::<u32>();
+        let producer_sequence_field_size = producer_sequence
+            .map(|sequence| sequence.get_buffer_size())
+            .unwrap_or(0);
         let metadata_length = stream_id_field_size
             + topic_id_field_size
             + partitioning_field_size
-            + messages_count_field_size;
+            + messages_count_field_size
+            + producer_sequence_field_size;
         let indexes_size = messages_count * INDEX_SIZE;
         let messages_size = messages
             .iter()
@@ -82,6 +91,7 @@ impl SendMessages {
             + topic_id_field_size
             + partitioning_field_size
             + messages_count_field_size
+            + producer_sequence_field_size
             + indexes_size
             + messages_size;
 
@@ -92,6 +102,10 @@ impl SendMessages {
         topic_id.write_to_buffer(&mut bytes);
         partitioning.write_to_buffer(&mut bytes);
         bytes.put_u32_le(messages_count as u32);
+        // The producer sequence is placed at the end of metadata, so that it's simply skipped by the servers not supporting it.
+        if let Some(producer_sequence) = producer_sequence {
+            producer_sequence.write_to_buffer(&mut bytes);
+        }
 
         let mut current_position = bytes.len();
 
@@ -134,6 +148,7 @@ impl Default for SendMessages {
             stream_id: Identifier::default(),
             topic_id: Identifier::default(),
             partitioning: Partitioning::default(),
+            producer_sequence: None,
             batch: IggyMessagesBatch::empty(),
         }
     }
@@ -189,6 +204,7 @@ impl Serialize for SendMessages {
     {
         // In HTTP API, we expose:
         // - partitioning (kind, value)
+        // - optional producer_sequence (producer_id, base_sequence)
         // - messages as an array of {id, payload, headers}
         // We don't expose stream_id and topic_id via JSON as they're in URL path
 
@@ -210,8 +226,12 @@ impl Serialize for SendMessages {
             })
             .collect();
 
-        let mut state = serializer.serialize_struct("SendMessages", 2)?;
+        let field_count = 2 + self.producer_sequence.is_some() as usize;
+        let mut state = serializer.serialize_struct("SendMessages", field_count)?;
         state.serialize_field("partitioning", &self.partitioning)?;
+        if let Some(producer_sequence) = &self.producer_sequence {
+            state.serialize_field("producer_sequence", producer_sequence)?;
+        }
         state.serialize_field("messages", &messages)?;
         state.end()
     }
@@ -224,6 +244,7 @@ impl<'de> Deserialize<'de> for SendMessages {
     {
         enum Field {
             Partitioning,
+            ProducerSequence,
             Messages,
         }
 
@@ -238,7 +259,7 @@ impl<'de> Deserialize<'de> for SendMessages {
                     type Value = Field;
 
                     fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
-                        formatter.write_str("`partitioning` or `messages`")
+                        formatter.write_str("`partitioning`, `producer_sequence` or `messages`")
                     }
 
                     fn visit_str<E>(self, value: &str) -> Result<Field, E>
@@ -247,10 +268,11 @@ impl<'de> Deserialize<'de> for SendMessages {
                     {
                         match value {
                             "partitioning" => Ok(Field::Partitioning),
+                            "producer_sequence" => Ok(Field::ProducerSequence),
                             "messages" => Ok(Field::Messages),
                             _ => Err(de::Error::unknown_field(
                                 value,
-                                &["partitioning", "messages"],
+                                &["partitioning", "producer_sequence", "messages"],
                             )),
                         }
                     }
@@ -274,6 +296,7 @@ impl<'de> Deserialize<'de> for SendMessages {
                 V: MapAccess<'de>,
             {
                 let mut partitioning = None;
+                let mut producer_sequence = None;
                 let mut messages = None;
 
                 while let Some(key) = map.next_key()? {
@@ -284,6 +307,12 @@ impl<'de> Deserialize<'de> for SendMessages {
                             }
                             partitioning = Some(map.next_value()?);
                         }
+                        Field::ProducerSequence => {
+                            if producer_sequence.is_some() {
+                                return Err(de::Error::duplicate_field("producer_sequence"));
+                            }
+                            producer_sequence = map.next_value()?;
+                        }
                         Field::Messages => {
                             if messages.is_some() {
                                 return Err(de::Error::duplicate_field("messages"));
@@ -358,6 +387,7 @@ impl<'de> Deserialize<'de> for SendMessages {
                     stream_id: Identifier::default(),
                     topic_id: Identifier::default(),
                     partitioning,
+                    producer_sequence,
                     batch,
                 })
             }
@@ -365,7 +395,7 @@ impl<'de> Deserialize<'de> for SendMessages {
 
         deserializer.deserialize_struct(
             "SendMessages",
-            &["partitioning", "messages"],
+            &["partitioning", "producer_sequence", "messages"],
             SendMessagesVisitor,
         )
     }
@@ -413,6 +443,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn key_of_type_consistent_messages_key_should_have_value_of_dynamic_length() {
+        let messages_key = "hello world";
+        let key = Partitioning::consistent_messages_key_str(messages_key).unwrap();
+        assert_eq!(key.kind, PartitioningKind::ConsistentMessagesKey);
+        assert_eq!(key.length, messages_key.len() as u8);
+        assert_eq!(key.value, messages_key.as_bytes());
+        assert_eq!(
+            PartitioningKind::from_code(4).unwrap(),
+            PartitioningKind::ConsistentMessagesKey
+        );
+    }
+
     #[test]
     fn key_of_type_messages_key_that_has_length_0_should_fail() {
         let messages_key = "";
@@ -18,5 +18,7 @@
 
 pub mod create_partitions;
 pub mod delete_partitions;
+pub mod move_partition;
+pub mod shrink_partitions;
 
 const MAX_PARTITIONS_COUNT: u32 = 1000;
@@ -0,0 +1,213 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::BytesSerializable;
+use crate::Identifier;
+use crate::Sizeable;
+use crate::Validatable;
+use crate::error::IggyError;
+use crate::{Command, MOVE_PARTITION_CODE};
+use bytes::{BufMut, Bytes, BytesMut};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::str::from_utf8;
+
+const MAX_DIRECTORY_LENGTH: usize = 255;
+
+/// `MovePartition` command is used to move the partition to another data directory, without stopping the server.
+/// It has additional payload:
+/// - `stream_id` - unique stream ID (numeric or name).
+/// - `topic_id` - unique topic ID (numeric or name).
+/// - `partition_id` - unique partition ID.
+/// - `directory` - target data directory, it must be either the system path or one of the configured partition directories.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct MovePartition {
+    /// Unique stream ID (numeric or name).
+    #[serde(skip)]
+    pub stream_id: Identifier,
+    /// Unique topic ID (numeric or name).
+    #[serde(skip)]
+    pub topic_id: Identifier,
+    /// Unique partition ID.
+    #[serde(skip)]
+    pub partition_id: u32,
+    /// Target data directory, max length is 255 characters.
+    pub directory: String,
+}
+
+impl Command for MovePartition {
+    fn code(&self) -> u32 {
+        MOVE_PARTITION_CODE
+    }
+}
+
+impl Default for MovePartition {
+    fn default() -> Self {
+        MovePartition {
+            stream_id: Identifier::default(),
+            topic_id: Identifier::default(),
+            partition_id: 1,
+            directory: "local_data".to_string(),
+        }
+    }
+}
+
+impl Validatable<IggyError> for MovePartition {
+    fn validate(&self) -> Result<(), IggyError> {
+        if self.partition_id == 0 {
+            return Err(IggyError::InvalidPartitionId);
+        }
+
+        if self.directory.is_empty() || self.directory.len() > MAX_DIRECTORY_LENGTH {
+            return Err(IggyError::InvalidPartitionDirectory(self.directory.clone()));
+        }
+
+        Ok(())
+    }
+}
+
+impl BytesSerializable for MovePartition {
+    fn to_bytes(&self) -> Bytes {
+        let stream_id_bytes = self.stream_id.to_bytes();
+        let topic_id_bytes = self.topic_id.to_bytes();
+        let mut bytes = BytesMut::with_capacity(
+            5 + stream_id_bytes.len() + topic_id_bytes.len() + self.directory.len(),
+        );
+        bytes.put_slice(&stream_id_bytes);
+        bytes.put_slice(&topic_id_bytes);
+        bytes.put_u32_le(self.partition_id);
+        #[allow(clippy::cast_possible_truncation)]
+        bytes.put_u8(self.directory.len() as u8);
+        bytes.put_slice(self.directory.as_bytes());
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> std::result::Result<MovePartition, IggyError> {
+        if bytes.len() < 12 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let mut position = 0;
+        let stream_id = Identifier::from_bytes(bytes.clone())?;
+        position += stream_id.get_size_bytes().as_bytes_usize();
+        let topic_id = Identifier::from_bytes(bytes.slice(position..))?;
+        position += topic_id.get_size_bytes().as_bytes_usize();
+        if bytes.len() < position + 5 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let partition_id = u32::from_le_bytes(
+            bytes[position..position + 4]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let directory_length = bytes[position + 4] as usize;
+        position += 5;
+        if bytes.len() < position + directory_length {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let directory = from_utf8(&bytes[position..position + directory_length])
+            .map_err(|_| IggyError::InvalidUtf8)?
+            .to_string();
+        let command = MovePartition {
+            stream_id,
+            topic_id,
+            partition_id,
+            directory,
+        };
+        Ok(command)
+    }
+}
+
+impl Display for MovePartition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}|{}|{}|{}",
+            self.stream_id, self.topic_id, self.partition_id, self.directory
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_be_serialized_as_bytes() {
+        let command = MovePartition {
+            stream_id: Identifier::numeric(1).unwrap(),
+            topic_id: Identifier::numeric(2).unwrap(),
+            partition_id: 3,
+            directory: "/mnt/nvme1/iggy".to_string(),
+        };
+
+        let bytes = command.to_bytes();
+        let mut position = 0;
+        let stream_id = Identifier::from_bytes(bytes.clone()).unwrap();
+        position += stream_id.get_size_bytes().as_bytes_usize();
+        let topic_id = Identifier::from_bytes(bytes.slice(position..)).unwrap();
+        position += topic_id.get_size_bytes().as_bytes_usize();
+        let partition_id = u32::from_le_bytes(bytes[position..position + 4].try_into().unwrap());
+        let directory_length = bytes[position + 4] as usize;
+        let directory = from_utf8(&bytes[position + 5..position + 5 + directory_length]).unwrap();
+
+        assert!(!bytes.is_empty());
+        assert_eq!(stream_id, command.stream_id);
+        assert_eq!(topic_id, command.topic_id);
+        assert_eq!(partition_id, command.partition_id);
+        assert_eq!(directory, command.directory);
+    }
+
+    #[test]
+    fn should_be_deserialized_from_bytes() {
+        let stream_id = Identifier::numeric(1).unwrap();
+        let topic_id = Identifier::numeric(2).unwrap();
+        let partition_id = 3u32;
+        let directory = "/mnt/nvme1/iggy";
+        let stream_id_bytes = stream_id.to_bytes();
+        let topic_id_bytes = topic_id.to_bytes();
+        let mut bytes = BytesMut::with_capacity(
+            5 + stream_id_bytes.len() + topic_id_bytes.len() + directory.len(),
+        );
+        bytes.put_slice(&stream_id_bytes);
+        bytes.put_slice(&topic_id_bytes);
+        bytes.put_u32_le(partition_id);
+        bytes.put_u8(directory.len() as u8);
+        bytes.put_slice(directory.as_bytes());
+        let command = MovePartition::from_bytes(bytes.freeze());
+        assert!(command.is_ok());
+
+        let command = command.unwrap();
+        assert_eq!(command.stream_id, stream_id);
+        assert_eq!(command.topic_id, topic_id);
+        assert_eq!(command.partition_id, partition_id);
+        assert_eq!(command.directory, directory);
+    }
+
+    #[test]
+    fn should_not_be_valid_given_empty_directory() {
+        let command = MovePartition {
+            directory: String::new(),
+            ..Default::default()
+        };
+
+        assert!(command.validate().is_err());
+    }
+}
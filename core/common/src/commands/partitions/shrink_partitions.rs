@@ -0,0 +1,263 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use super::MAX_PARTITIONS_COUNT;
+use crate::BytesSerializable;
+use crate::Identifier;
+use crate::Sizeable;
+use crate::Validatable;
+use crate::error::IggyError;
+use crate::{Command, SHRINK_PARTITIONS_CODE};
+use bytes::{BufMut, Bytes, BytesMut};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::str::from_utf8;
+
+const MAX_KEY_HEADER_LENGTH: usize = 255;
+
+/// `ShrinkPartitions` command is used to safely reduce the number of partitions in a topic.
+/// Unlike `DeletePartitions`, the last N partitions are not dropped immediately:
+/// - by default, they stop accepting new messages and are removed once all the consumer groups have consumed them.
+/// - with `copy_unconsumed` enabled, the messages not yet consumed by all the consumer groups
+///   are copied into the remaining partitions, and the partitions are removed right away.
+///
+/// The consumers with the stored offsets are taken into account as well. When the partition has neither
+/// consumer groups nor consumers, it's removed once the draining retention configured on the server elapses,
+/// and with `copy_unconsumed` enabled all its messages are copied.
+///
+/// While the partitions are being drained, no partitions can be created or deleted in the topic.
+///
+/// It has additional payload:
+/// - `stream_id` - unique stream ID (numeric or name).
+/// - `topic_id` - unique topic ID (numeric or name).
+/// - `partitions_count` - number of partitions in the topic to remove, max value is 1000.
+/// - `copy_unconsumed` - whether to copy the unconsumed messages into the remaining partitions.
+/// - `key_header` - optional name of the user header holding the messages key. When set, the copied messages are
+///   distributed by the key, the same way as the messages partitioned by the messages key, which preserves the per-key ordering.
+///   Otherwise, all the messages of the removed partition are copied to a single remaining partition.
+/// - `consistent_hashing` - whether the key is hashed the same way as for the consistent messages key partitioning,
+///   instead of the messages key one. It should match the partitioning the messages were sent with.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct ShrinkPartitions {
+    /// Unique stream ID (numeric or name).
+    #[serde(skip)]
+    pub stream_id: Identifier,
+    /// Unique topic ID (numeric or name).
+    #[serde(skip)]
+    pub topic_id: Identifier,
+    /// Number of partitions in the topic to remove, max value is 1000.
+    pub partitions_count: u32,
+    /// Whether to copy the unconsumed messages into the remaining partitions.
+    #[serde(default)]
+    pub copy_unconsumed: bool,
+    /// Optional name of the user header holding the messages key, max length is 255 characters.
+    #[serde(default)]
+    pub key_header: Option<String>,
+    /// Whether the messages key is hashed the same way as for the consistent messages key partitioning.
+    #[serde(default)]
+    pub consistent_hashing: bool,
+}
+
+impl Command for ShrinkPartitions {
+    fn code(&self) -> u32 {
+        SHRINK_PARTITIONS_CODE
+    }
+}
+
+impl Default for ShrinkPartitions {
+    fn default() -> Self {
+        ShrinkPartitions {
+            stream_id: Identifier::default(),
+            topic_id: Identifier::default(),
+            partitions_count: 1,
+            copy_unconsumed: false,
+            key_header: None,
+            consistent_hashing: false,
+        }
+    }
+}
+
+impl Validatable<IggyError> for ShrinkPartitions {
+    fn validate(&self) -> Result<(), IggyError> {
+        if !(1..=MAX_PARTITIONS_COUNT).contains(&self.partitions_count) {
+            return Err(IggyError::TooManyPartitions);
+        }
+
+        if self.key_header.as_ref().is_some_and(|key_header| {
+            key_header.is_empty() || key_header.len() > MAX_KEY_HEADER_LENGTH
+        }) {
+            return Err(IggyError::InvalidHeaderKey);
+        }
+
+        Ok(())
+    }
+}
+
+impl BytesSerializable for ShrinkPartitions {
+    fn to_bytes(&self) -> Bytes {
+        let stream_id_bytes = self.stream_id.to_bytes();
+        let topic_id_bytes = self.topic_id.to_bytes();
+        let key_header = self.key_header.as_deref().unwrap_or_default();
+        let mut bytes = BytesMut::with_capacity(
+            7 + stream_id_bytes.len() + topic_id_bytes.len() + key_header.len(),
+        );
+        bytes.put_slice(&stream_id_bytes);
+        bytes.put_slice(&topic_id_bytes);
+        bytes.put_u32_le(self.partitions_count);
+        bytes.put_u8(if self.copy_unconsumed { 1 } else { 0 });
+        bytes.put_u8(if self.consistent_hashing { 1 } else { 0 });
+        #[allow(clippy::cast_possible_truncation)]
+        bytes.put_u8(key_header.len() as u8);
+        bytes.put_slice(key_header.as_bytes());
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> std::result::Result<ShrinkPartitions, IggyError> {
+        if bytes.len() < 13 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let mut position = 0;
+        let stream_id = Identifier::from_bytes(bytes.clone())?;
+        position += stream_id.get_size_bytes().as_bytes_usize();
+        let topic_id = Identifier::from_bytes(bytes.slice(position..))?;
+        position += topic_id.get_size_bytes().as_bytes_usize();
+        if bytes.len() < position + 7 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let partitions_count = u32::from_le_bytes(
+            bytes[position..position + 4]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let copy_unconsumed = match bytes[position + 4] {
+            0 => false,
+            1 => true,
+            _ => return Err(IggyError::InvalidCommand),
+        };
+        let consistent_hashing = match bytes[position + 5] {
+            0 => false,
+            1 => true,
+            _ => return Err(IggyError::InvalidCommand),
+        };
+        let key_header_length = bytes[position + 6] as usize;
+        position += 7;
+        if bytes.len() < position + key_header_length {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let key_header = if key_header_length == 0 {
+            None
+        } else {
+            Some(
+                from_utf8(&bytes[position..position + key_header_length])
+                    .map_err(|_| IggyError::InvalidUtf8)?
+                    .to_string(),
+            )
+        };
+        let command = ShrinkPartitions {
+            stream_id,
+            topic_id,
+            partitions_count,
+            copy_unconsumed,
+            key_header,
+            consistent_hashing,
+        };
+        Ok(command)
+    }
+}
+
+impl Display for ShrinkPartitions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}|{}|{}|{}|{}|{}",
+            self.stream_id,
+            self.topic_id,
+            self.partitions_count,
+            self.copy_unconsumed,
+            self.key_header.as_deref().unwrap_or_default(),
+            self.consistent_hashing
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_be_serialized_as_bytes() {
+        let command = ShrinkPartitions {
+            stream_id: Identifier::numeric(1).unwrap(),
+            topic_id: Identifier::numeric(2).unwrap(),
+            partitions_count: 3,
+            copy_unconsumed: true,
+            key_header: Some("tenant".to_string()),
+            consistent_hashing: true,
+        };
+
+        let bytes = command.to_bytes();
+        let mut position = 0;
+        let stream_id = Identifier::from_bytes(bytes.clone()).unwrap();
+        position += stream_id.get_size_bytes().as_bytes_usize();
+        let topic_id = Identifier::from_bytes(bytes.slice(position..)).unwrap();
+        position += topic_id.get_size_bytes().as_bytes_usize();
+        let partitions_count =
+            u32::from_le_bytes(bytes[position..position + 4].try_into().unwrap());
+        let copy_unconsumed = bytes[position + 4] == 1;
+        let consistent_hashing = bytes[position + 5] == 1;
+        let key_header_length = bytes[position + 6] as usize;
+        let key_header = from_utf8(&bytes[position + 7..position + 7 + key_header_length]).unwrap();
+
+        assert!(!bytes.is_empty());
+        assert_eq!(stream_id, command.stream_id);
+        assert_eq!(topic_id, command.topic_id);
+        assert_eq!(partitions_count, command.partitions_count);
+        assert_eq!(copy_unconsumed, command.copy_unconsumed);
+        assert_eq!(consistent_hashing, command.consistent_hashing);
+        assert_eq!(Some(key_header), command.key_header.as_deref());
+    }
+
+    #[test]
+    fn should_be_deserialized_from_bytes() {
+        let stream_id = Identifier::numeric(1).unwrap();
+        let topic_id = Identifier::numeric(2).unwrap();
+        let partitions_count = 3u32;
+        let stream_id_bytes = stream_id.to_bytes();
+        let topic_id_bytes = topic_id.to_bytes();
+        let mut bytes = BytesMut::with_capacity(7 + stream_id_bytes.len() + topic_id_bytes.len());
+        bytes.put_slice(&stream_id_bytes);
+        bytes.put_slice(&topic_id_bytes);
+        bytes.put_u32_le(partitions_count);
+        bytes.put_u8(0);
+        bytes.put_u8(1);
+        bytes.put_u8(0);
+        let command = ShrinkPartitions::from_bytes(bytes.freeze());
+        assert!(command.is_ok());
+
+        let command = command.unwrap();
+        assert_eq!(command.stream_id, stream_id);
+        assert_eq!(command.topic_id, topic_id);
+        assert_eq!(command.partitions_count, partitions_count);
+        assert!(!command.copy_unconsumed);
+        assert!(command.consistent_hashing);
+        assert!(command.key_header.is_none());
+    }
+}
@@ -0,0 +1,175 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::BytesSerializable;
+use crate::Identifier;
+use crate::Sizeable;
+use crate::Validatable;
+use crate::error::IggyError;
+use crate::{Command, DataSnapshotTarget, GET_DATA_SNAPSHOT_CODE};
+use bytes::{BufMut, Bytes, BytesMut};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// `GetDataSnapshot` command is used to get a point-in-time backup of the selected streams and topics.
+/// The snapshot is a tar archive compressed with ZStandard, containing the segments, indexes and consumer offsets
+/// of the partitions, along with the state entries required to recreate the streams, topics and consumer groups.
+/// It can be restored with the `RestoreDataSnapshot` command.
+///
+/// It has additional payload:
+/// - `targets` - streams or topics to be included in the snapshot, all the streams are included if empty.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct GetDataSnapshot {
+    /// Streams or topics to be included in the snapshot, all the streams are included if empty.
+    #[serde(default)]
+    pub targets: Vec<DataSnapshotTarget>,
+}
+
+impl Command for GetDataSnapshot {
+    fn code(&self) -> u32 {
+        GET_DATA_SNAPSHOT_CODE
+    }
+}
+
+impl Validatable<IggyError> for GetDataSnapshot {
+    fn validate(&self) -> Result<(), IggyError> {
+        Ok(())
+    }
+}
+
+impl BytesSerializable for GetDataSnapshot {
+    fn to_bytes(&self) -> Bytes {
+        let mut bytes = BytesMut::new();
+        bytes.put_u32_le(self.targets.len() as u32);
+        for target in &self.targets {
+            bytes.put_slice(&target.stream_id.to_bytes());
+            match &target.topic_id {
+                Some(topic_id) => {
+                    bytes.put_u8(1);
+                    bytes.put_slice(&topic_id.to_bytes());
+                }
+                None => bytes.put_u8(0),
+            }
+        }
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<GetDataSnapshot, IggyError> {
+        if bytes.len() < 4 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let targets_count = u32::from_le_bytes(
+            bytes[0..4]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let mut position = 4;
+        let mut targets = Vec::new();
+        for _ in 0..targets_count {
+            let stream_id = Identifier::from_bytes(bytes.slice(position..))?;
+            position += stream_id.get_size_bytes().as_bytes_usize();
+            let has_topic = *bytes.get(position).ok_or(IggyError::InvalidCommand)?;
+            position += 1;
+            let topic_id = match has_topic {
+                0 => None,
+                1 => {
+                    let topic_id = Identifier::from_bytes(bytes.slice(position..))?;
+                    position += topic_id.get_size_bytes().as_bytes_usize();
+                    Some(topic_id)
+                }
+                _ => return Err(IggyError::InvalidCommand),
+            };
+            targets.push(DataSnapshotTarget {
+                stream_id,
+                topic_id,
+            });
+        }
+
+        Ok(GetDataSnapshot { targets })
+    }
+}
+
+impl Display for GetDataSnapshot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let targets = self
+            .targets
+            .iter()
+            .map(|target| target.to_string())
+            .collect::<Vec<_>>();
+        write!(f, "{}", targets.join(","))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_be_serialized_as_bytes() {
+        let command = GetDataSnapshot {
+            targets: vec![
+                DataSnapshotTarget::stream(Identifier::numeric(1).unwrap()),
+                DataSnapshotTarget::topic(
+                    Identifier::named("stream").unwrap(),
+                    Identifier::numeric(2).unwrap(),
+                ),
+            ],
+        };
+
+        let bytes = command.to_bytes();
+        let targets_count = u32::from_le_bytes(bytes[0..4].try_into().unwrap());
+        let mut position = 4;
+        let stream_id = Identifier::from_bytes(bytes.slice(position..)).unwrap();
+        position += stream_id.get_size_bytes().as_bytes_usize();
+        let has_topic = bytes[position];
+        position += 1;
+        let second_stream_id = Identifier::from_bytes(bytes.slice(position..)).unwrap();
+        position += second_stream_id.get_size_bytes().as_bytes_usize();
+        let second_has_topic = bytes[position];
+        position += 1;
+        let topic_id = Identifier::from_bytes(bytes.slice(position..)).unwrap();
+
+        assert!(!bytes.is_empty());
+        assert_eq!(targets_count, 2);
+        assert_eq!(stream_id, command.targets[0].stream_id);
+        assert_eq!(has_topic, 0);
+        assert_eq!(second_stream_id, command.targets[1].stream_id);
+        assert_eq!(second_has_topic, 1);
+        assert_eq!(Some(topic_id), command.targets[1].topic_id);
+    }
+
+    #[test]
+    fn should_be_deserialized_from_bytes() {
+        let stream_id = Identifier::numeric(1).unwrap();
+        let topic_id = Identifier::named("topic").unwrap();
+        let mut bytes = BytesMut::new();
+        bytes.put_u32_le(1);
+        bytes.put_slice(&stream_id.to_bytes());
+        bytes.put_u8(1);
+        bytes.put_slice(&topic_id.to_bytes());
+
+        let command = GetDataSnapshot::from_bytes(bytes.freeze());
+        assert!(command.is_ok());
+
+        let command = command.unwrap();
+        assert_eq!(command.targets.len(), 1);
+        assert_eq!(command.targets[0].stream_id, stream_id);
+        assert_eq!(command.targets[0].topic_id, Some(topic_id));
+    }
+}
@@ -18,7 +18,9 @@
 
 pub mod get_client;
 pub mod get_clients;
+pub mod get_data_snapshot;
 pub mod get_me;
 pub mod get_snapshot;
 pub mod get_stats;
 pub mod ping;
+pub mod restore_data_snapshot;
@@ -0,0 +1,102 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::BytesSerializable;
+use crate::Validatable;
+use crate::error::IggyError;
+use crate::{Command, RESTORE_DATA_SNAPSHOT_CODE};
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+use serde_with::base64::Base64;
+use serde_with::serde_as;
+use std::fmt::Display;
+
+/// `RestoreDataSnapshot` command is used to restore the streams and topics from the snapshot
+/// created with the `GetDataSnapshot` command.
+/// The streams are reused if they already exist with the same ID and name, while the topics must not exist,
+/// and are recreated with their original IDs, partitions, consumer groups, messages and consumer offsets.
+///
+/// It has additional payload:
+/// - `snapshot` - the data snapshot archive.
+#[serde_as]
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct RestoreDataSnapshot {
+    /// The data snapshot archive.
+    #[serde_as(as = "Base64")]
+    pub snapshot: Vec<u8>,
+}
+
+impl Command for RestoreDataSnapshot {
+    fn code(&self) -> u32 {
+        RESTORE_DATA_SNAPSHOT_CODE
+    }
+}
+
+impl Validatable<IggyError> for RestoreDataSnapshot {
+    fn validate(&self) -> Result<(), IggyError> {
+        if self.snapshot.is_empty() {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        Ok(())
+    }
+}
+
+impl BytesSerializable for RestoreDataSnapshot {
+    fn to_bytes(&self) -> Bytes {
+        Bytes::copy_from_slice(&self.snapshot)
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<RestoreDataSnapshot, IggyError> {
+        if bytes.is_empty() {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        Ok(RestoreDataSnapshot {
+            snapshot: bytes.to_vec(),
+        })
+    }
+}
+
+impl Display for RestoreDataSnapshot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} bytes", self.snapshot.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_be_serialized_as_bytes() {
+        let command = RestoreDataSnapshot {
+            snapshot: vec![1, 2, 3],
+        };
+
+        let bytes = command.to_bytes();
+
+        assert_eq!(bytes.as_ref(), &[1, 2, 3]);
+    }
+
+    #[test]
+    fn should_not_be_deserialized_from_empty_bytes() {
+        let command = RestoreDataSnapshot::from_bytes(Bytes::new());
+        assert!(command.is_err());
+    }
+}
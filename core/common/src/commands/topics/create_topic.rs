@@ -21,6 +21,7 @@ use crate::BytesSerializable;
 use crate::CompressionAlgorithm;
 use crate::Identifier;
 use crate::Sizeable;
+use crate::TopicOverrides;
 use crate::Validatable;
 use crate::error::IggyError;
 use crate::utils::expiry::IggyExpiry;
@@ -41,6 +42,7 @@ use std::str::from_utf8;
 ///   Can't be lower than segment size in the config.
 /// - `replication_factor` - replication factor for the topic.
 /// - `name` - unique topic name, max length is 255 characters.
+/// - `overrides` - per-topic overrides of the server settings, optional and appended after the name.
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub struct CreateTopic {
     /// Unique stream ID (numeric or name).
@@ -61,6 +63,9 @@ pub struct CreateTopic {
     pub replication_factor: Option<u8>,
     /// Unique topic name, max length is 255 characters.
     pub name: String,
+    /// Per-topic overrides of the server settings, e.g. segment size or fsync.
+    #[serde(default)]
+    pub overrides: TopicOverrides,
 }
 
 impl Command for CreateTopic {
@@ -80,6 +85,7 @@ impl Default for CreateTopic {
             max_topic_size: MaxTopicSize::ServerDefault,
             replication_factor: None,
             name: "topic".to_string(),
+            overrides: TopicOverrides::default(),
         }
     }
 }
@@ -106,14 +112,16 @@ impl Validatable<IggyError> for CreateTopic {
             }
         }
 
-        Ok(())
+        self.overrides.validate()
     }
 }
 
 impl BytesSerializable for CreateTopic {
     fn to_bytes(&self) -> Bytes {
         let stream_id_bytes = self.stream_id.to_bytes();
-        let mut bytes = BytesMut::with_capacity(23 + stream_id_bytes.len() + self.name.len());
+        let mut bytes = BytesMut::with_capacity(
+            23 + stream_id_bytes.len() + self.name.len() + TopicOverrides::get_size_bytes(),
+        );
         bytes.put_slice(&stream_id_bytes);
         bytes.put_u32_le(self.topic_id.unwrap_or(0));
         bytes.put_u32_le(self.partitions_count);
@@ -127,6 +135,7 @@ impl BytesSerializable for CreateTopic {
         #[allow(clippy::cast_possible_truncation)]
         bytes.put_u8(self.name.len() as u8);
         bytes.put_slice(self.name.as_bytes());
+        bytes.put_slice(&self.overrides.to_bytes());
         bytes.freeze()
     }
 
@@ -172,6 +181,12 @@ impl BytesSerializable for CreateTopic {
         if name.len() != name_length as usize {
             return Err(IggyError::InvalidCommand);
         }
+        position += 27 + name_length as usize;
+        let overrides = if bytes.len() > position {
+            TopicOverrides::from_bytes(bytes.slice(position..))?
+        } else {
+            TopicOverrides::default()
+        };
         let command = CreateTopic {
             stream_id,
             topic_id,
@@ -181,6 +196,7 @@ impl BytesSerializable for CreateTopic {
             max_topic_size,
             replication_factor,
             name,
+            overrides,
         };
         Ok(command)
     }
@@ -190,14 +206,15 @@ impl Display for CreateTopic {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{}|{}|{}|{}|{}|{}|{}",
+            "{}|{}|{}|{}|{}|{}|{}|{}",
             self.stream_id,
             self.topic_id.unwrap_or(0),
             self.partitions_count,
             self.message_expiry,
             self.max_topic_size,
             self.replication_factor.unwrap_or(0),
-            self.name
+            self.name,
+            self.overrides
         )
     }
 }
@@ -218,6 +235,10 @@ mod tests {
             max_topic_size: MaxTopicSize::ServerDefault,
             replication_factor: Some(1),
             name: "test".to_string(),
+            overrides: TopicOverrides {
+                enforce_fsync: Some(true),
+                ..Default::default()
+            },
         };
         let bytes = command.to_bytes();
         let mut position = 0;
@@ -238,6 +259,9 @@ mod tests {
         let name = from_utf8(&bytes[position + 27..(position + 27 + name_length as usize)])
             .unwrap()
             .to_string();
+        let overrides =
+            TopicOverrides::from_bytes(bytes.slice(position + 27 + name_length as usize..))
+                .unwrap();
 
         assert!(!bytes.is_empty());
         assert_eq!(stream_id, command.stream_id);
@@ -249,6 +273,7 @@ mod tests {
         assert_eq!(replication_factor, command.replication_factor.unwrap());
         assert_eq!(name.len() as u8, command.name.len() as u8);
         assert_eq!(name, command.name);
+        assert_eq!(overrides, command.overrides);
     }
 
     #[test]
@@ -287,5 +312,6 @@ mod tests {
         assert_eq!(command.max_topic_size, max_topic_size);
         assert_eq!(command.replication_factor.unwrap(), replication_factor);
         assert_eq!(command.partitions_count, partitions_count);
+        assert!(command.overrides.is_empty());
     }
 }
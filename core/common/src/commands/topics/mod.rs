@@ -22,6 +22,7 @@ pub mod get_topic;
 pub mod get_topics;
 pub mod purge_topic;
 pub mod update_topic;
+pub mod watch_topics;
 
 const MAX_NAME_LENGTH: usize = 255;
 const MAX_PARTITIONS_COUNT: u32 = 1000;
@@ -21,6 +21,7 @@ use crate::BytesSerializable;
 use crate::CompressionAlgorithm;
 use crate::Identifier;
 use crate::Sizeable;
+use crate::TopicOverrides;
 use crate::Validatable;
 use crate::error::IggyError;
 use crate::utils::expiry::IggyExpiry;
@@ -40,6 +41,7 @@ use std::str::from_utf8;
 ///   Can't be lower than segment size in the config.
 /// - `replication_factor` - replication factor for the topic.
 /// - `name` - unique topic name, max length is 255 characters.
+/// - `overrides` - per-topic overrides of the server settings, if not provided then the current ones are kept.
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub struct UpdateTopic {
     /// Unique stream ID (numeric or name).
@@ -59,6 +61,9 @@ pub struct UpdateTopic {
     pub replication_factor: Option<u8>,
     /// Unique topic name, max length is 255 characters.
     pub name: String,
+    /// Per-topic overrides of the server settings, if `None` then the current ones are kept.
+    #[serde(default)]
+    pub overrides: Option<TopicOverrides>,
 }
 
 impl Command for UpdateTopic {
@@ -77,6 +82,7 @@ impl Default for UpdateTopic {
             max_topic_size: MaxTopicSize::ServerDefault,
             replication_factor: None,
             name: "topic".to_string(),
+            overrides: None,
         }
     }
 }
@@ -93,7 +99,10 @@ impl Validatable<IggyError> for UpdateTopic {
             }
         }
 
-        Ok(())
+        match &self.overrides {
+            Some(overrides) => overrides.validate(),
+            None => Ok(()),
+        }
     }
 }
 
@@ -102,7 +111,10 @@ impl BytesSerializable for UpdateTopic {
         let stream_id_bytes = self.stream_id.to_bytes();
         let topic_id_bytes = self.topic_id.to_bytes();
         let mut bytes = BytesMut::with_capacity(
-            19 + stream_id_bytes.len() + topic_id_bytes.len() + self.name.len(),
+            19 + stream_id_bytes.len()
+                + topic_id_bytes.len()
+                + self.name.len()
+                + TopicOverrides::get_size_bytes(),
         );
         bytes.put_slice(&stream_id_bytes.clone());
         bytes.put_slice(&topic_id_bytes.clone());
@@ -116,6 +128,9 @@ impl BytesSerializable for UpdateTopic {
         #[allow(clippy::cast_possible_truncation)]
         bytes.put_u8(self.name.len() as u8);
         bytes.put_slice(self.name.as_bytes());
+        if let Some(overrides) = &self.overrides {
+            bytes.put_slice(&overrides.to_bytes());
+        }
         bytes.freeze()
     }
 
@@ -153,6 +168,12 @@ impl BytesSerializable for UpdateTopic {
         if name.len() != name_length as usize {
             return Err(IggyError::InvalidCommand);
         }
+        position += 18 + name_length as usize;
+        let overrides = if bytes.len() > position {
+            Some(TopicOverrides::from_bytes(bytes.slice(position..))?)
+        } else {
+            None
+        };
         let command = UpdateTopic {
             stream_id,
             topic_id,
@@ -161,6 +182,7 @@ impl BytesSerializable for UpdateTopic {
             max_topic_size,
             replication_factor,
             name,
+            overrides,
         };
         Ok(command)
     }
@@ -197,6 +219,10 @@ mod tests {
             max_topic_size: MaxTopicSize::ServerDefault,
             replication_factor: Some(1),
             name: "test".to_string(),
+            overrides: Some(TopicOverrides {
+                messages_required_to_save: Some(1),
+                ..Default::default()
+            }),
         };
 
         let bytes = command.to_bytes();
@@ -217,6 +243,9 @@ mod tests {
         let name = from_utf8(&bytes[position + 18..position + 18 + name_length as usize])
             .unwrap()
             .to_string();
+        let overrides =
+            TopicOverrides::from_bytes(bytes.slice(position + 18 + name_length as usize..))
+                .unwrap();
 
         assert!(!bytes.is_empty());
         assert_eq!(stream_id, command.stream_id);
@@ -227,6 +256,7 @@ mod tests {
         assert_eq!(replication_factor, command.replication_factor.unwrap());
         assert_eq!(name.len() as u8, command.name.len() as u8);
         assert_eq!(name, command.name);
+        assert_eq!(Some(overrides), command.overrides);
     }
 
     #[test]
@@ -265,5 +295,6 @@ mod tests {
         assert_eq!(command.max_topic_size, max_topic_size);
         assert_eq!(command.replication_factor, Some(replication_factor));
         assert_eq!(command.name, name);
+        assert!(command.overrides.is_none());
     }
 }
@@ -0,0 +1,143 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::BytesSerializable;
+use crate::Validatable;
+use crate::error::IggyError;
+use crate::{Command, IggyDuration, MAX_LONG_POLLING_TIMEOUT, WATCH_TOPICS_CODE};
+use bytes::{BufMut, Bytes, BytesMut};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// `WatchTopics` command is used to wait for the changes of the topics (created or deleted topics and streams).
+/// The server responds with its current topics version immediately if it differs from the provided one,
+/// otherwise it holds the request until the topics change or the timeout elapses.
+/// It has additional payload:
+/// - `version` - the last topics version known to the client, `0` to get the current one immediately.
+/// - `timeout` - maximum time to wait for the changes.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct WatchTopics {
+    /// The last topics version known to the client.
+    pub version: u64,
+    /// Maximum time to wait for the changes.
+    pub timeout: IggyDuration,
+}
+
+impl WatchTopics {
+    /// Ensures that the timeout doesn't exceed `MAX_LONG_POLLING_TIMEOUT`.
+    pub fn validate_timeout(&self) -> Result<(), IggyError> {
+        if self.timeout.as_micros() > MAX_LONG_POLLING_TIMEOUT.as_micros() {
+            return Err(IggyError::InvalidLongPollingTimeout(
+                self.timeout.as_micros(),
+                MAX_LONG_POLLING_TIMEOUT.as_micros(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for WatchTopics {
+    fn default() -> Self {
+        Self {
+            version: 0,
+            timeout: IggyDuration::ONE_SECOND,
+        }
+    }
+}
+
+impl Command for WatchTopics {
+    fn code(&self) -> u32 {
+        WATCH_TOPICS_CODE
+    }
+}
+
+impl Validatable<IggyError> for WatchTopics {
+    fn validate(&self) -> Result<(), IggyError> {
+        Ok(())
+    }
+}
+
+impl BytesSerializable for WatchTopics {
+    fn to_bytes(&self) -> Bytes {
+        let mut bytes = BytesMut::with_capacity(16);
+        bytes.put_u64_le(self.version);
+        bytes.put_u64_le(self.timeout.as_micros());
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> std::result::Result<WatchTopics, IggyError> {
+        if bytes.len() != 16 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let version = u64::from_le_bytes(
+            bytes[..8]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let timeout = u64::from_le_bytes(
+            bytes[8..]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        Ok(WatchTopics {
+            version,
+            timeout: timeout.into(),
+        })
+    }
+}
+
+impl Display for WatchTopics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}|{}", self.version, self.timeout.as_micros())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_be_serialized_as_bytes_and_deserialized() {
+        let command = WatchTopics {
+            version: 7,
+            timeout: IggyDuration::new_from_secs(5),
+        };
+
+        let bytes = command.to_bytes();
+        assert_eq!(bytes.len(), 16);
+        assert_eq!(u64::from_le_bytes(bytes[..8].try_into().unwrap()), 7);
+
+        let deserialized = WatchTopics::from_bytes(bytes).unwrap();
+        assert_eq!(deserialized, command);
+    }
+
+    #[test]
+    fn timeout_above_maximum_should_be_invalid() {
+        let command = WatchTopics {
+            version: 1,
+            timeout: IggyDuration::new_from_secs(MAX_LONG_POLLING_TIMEOUT.as_secs() as u64 + 1),
+        };
+
+        assert!(matches!(
+            command.validate_timeout(),
+            Err(IggyError::InvalidLongPollingTimeout(_, _))
+        ));
+    }
+}
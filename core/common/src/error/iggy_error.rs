@@ -80,6 +80,8 @@ pub enum IggyError {
     InvalidClientAddress = 34,
     #[error("Invalid IP address: {0}:{1}")]
     InvalidIpAddress(String, String) = 35,
+    #[error("WebSocket error")]
+    WebSocketError = 36,
     #[error("Unauthenticated")]
     Unauthenticated = 40,
     #[error("Unauthorized")]
@@ -248,6 +250,8 @@ pub enum IggyError {
     CannotReadTopics(u32) = 2017,
     #[error("Invalid replication factor")]
     InvalidReplicationFactor = 2018,
+    #[error("Invalid topic overrides")]
+    InvalidTopicOverrides = 2019,
     #[error("Cannot create partition with ID: {0} for stream with ID: {1} and topic with ID: {2}")]
     CannotCreatePartition(u32, u32, u32) = 3000,
     #[error(
@@ -286,6 +290,28 @@ pub enum IggyError {
     CannotReadConsumerOffsets(String) = 3020,
     #[error("Consumer offset for consumer with ID: {0} was not found.")]
     ConsumerOffsetNotFound(u32) = 3021,
+    #[error("Failed to read producer states from path: {0}")]
+    CannotReadProducerStates(String) = 3022,
+    #[error("Invalid partition ID")]
+    InvalidPartitionId = 3023,
+    #[error("Invalid partition data directory: {0}")]
+    InvalidPartitionDirectory(String) = 3024,
+    #[error(
+        "Failed to move partition with ID: {0} for topic with ID: {1} for stream with ID: {2} to directory: {3}"
+    )]
+    CannotMovePartition(u32, u32, u32, String) = 3025,
+    #[error(
+        "Partition with ID: {0} for topic with ID: {1} for stream with ID: {2} is being drained and does not accept new messages."
+    )]
+    PartitionDraining(u32, u32, u32) = 3026,
+    #[error(
+        "Cannot remove {0} partition(s) from topic with ID: {1} for stream with ID: {2}, at least one partition must remain."
+    )]
+    CannotShrinkPartitions(u32, u32, u32) = 3027,
+    #[error("Partitions of topic with ID: {0} for stream with ID: {1} are being drained.")]
+    PartitionsDraining(u32, u32) = 3028,
+    #[error("Failed to read copied offsets from path: {0}")]
+    CannotReadCopiedOffsets(String) = 3029,
     #[error("Segment not found")]
     SegmentNotFound = 4000,
     #[error("Segment with start offset: {0} and partition with ID: {1} is closed")]
@@ -362,6 +388,10 @@ pub enum IggyError {
     InvalidMessagesSize(u32, u32) = 4036,
     #[error("Too small message: {0}B, expected: {1}B")]
     TooSmallMessage(u32, u32) = 4037,
+    #[error("Invalid long polling timeout: {0} us, maximum allowed: {1} us")]
+    InvalidLongPollingTimeout(u64, u64) = 4038,
+    #[error("Producer with ID: {0} has sent messages with sequence: {1}, expected: {2}")]
+    ProducerSequenceOutOfOrder(u64, u64, u64) = 4039,
     #[error("Cannot sed messages due to client disconnection")]
     CannotSendMessagesDueToClientDisconnection = 4050,
     #[error("Background send error")]
@@ -433,6 +463,8 @@ pub enum IggyError {
     InvalidConnectionString = 8000,
     #[error("Snapshot file completion failed")]
     SnapshotFileCompletionFailed = 9000,
+    #[error("Invalid data snapshot")]
+    InvalidDataSnapshot = 9001,
     #[error("Cannot serialize resource")]
     CannotSerializeResource = 10000,
     #[error("Cannot deserialize resource")]
@@ -64,6 +64,8 @@ pub use types::configuration::tcp_config::tcp_client_config::*;
 pub use types::configuration::tcp_config::tcp_client_config_builder::*;
 pub use types::configuration::tcp_config::tcp_client_reconnection_config::*;
 pub use types::configuration::tcp_config::tcp_connection_string_options::*;
+pub use types::configuration::websocket_config::websocket_client_config::*;
+pub use types::configuration::websocket_config::websocket_client_config_builder::*;
 pub use types::confirmation::*;
 pub use types::consumer::consumer_group::*;
 pub use types::consumer::consumer_kind::*;
@@ -81,10 +83,12 @@ pub use types::topic::*;
 pub use types::user::user_identity_info::*;
 pub use types::user::user_info::*;
 pub use types::user::user_status::*;
+pub use types::websocket::websocket_frame::*;
 // Utils
 pub use certificates::generate_self_signed_certificate;
 pub use utils::byte_size::IggyByteSize;
 pub use utils::checksum::*;
+pub use utils::consistent_hash::calculate_partition_id_by_consistent_hash;
 pub use utils::crypto::*;
 pub use utils::duration::{IggyDuration, SEC_IN_MICRO};
 pub use utils::expiry::IggyExpiry;
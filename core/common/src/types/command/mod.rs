@@ -31,6 +31,10 @@ pub const GET_STATS: &str = "stats";
 pub const GET_STATS_CODE: u32 = 10;
 pub const GET_SNAPSHOT_FILE: &str = "snapshot";
 pub const GET_SNAPSHOT_FILE_CODE: u32 = 11;
+pub const GET_DATA_SNAPSHOT: &str = "data_snapshot.get";
+pub const GET_DATA_SNAPSHOT_CODE: u32 = 12;
+pub const RESTORE_DATA_SNAPSHOT: &str = "data_snapshot.restore";
+pub const RESTORE_DATA_SNAPSHOT_CODE: u32 = 13;
 pub const GET_ME: &str = "me";
 pub const GET_ME_CODE: u32 = 20;
 pub const GET_CLIENT: &str = "client.get";
@@ -69,6 +73,8 @@ pub const SEND_MESSAGES: &str = "message.send";
 pub const SEND_MESSAGES_CODE: u32 = 101;
 pub const FLUSH_UNSAVED_BUFFER: &str = "message.flush_unsaved_buffer";
 pub const FLUSH_UNSAVED_BUFFER_CODE: u32 = 102;
+pub const LONG_POLL_MESSAGES: &str = "message.long_poll";
+pub const LONG_POLL_MESSAGES_CODE: u32 = 103;
 pub const GET_CONSUMER_OFFSET: &str = "consumer_offset.get";
 pub const GET_CONSUMER_OFFSET_CODE: u32 = 120;
 pub const STORE_CONSUMER_OFFSET: &str = "consumer_offset.store";
@@ -99,10 +105,18 @@ pub const UPDATE_TOPIC: &str = "topic.update";
 pub const UPDATE_TOPIC_CODE: u32 = 304;
 pub const PURGE_TOPIC: &str = "topic.purge";
 pub const PURGE_TOPIC_CODE: u32 = 305;
+pub const WATCH_TOPICS: &str = "topic.watch";
+pub const WATCH_TOPICS_CODE: u32 = 306;
 pub const CREATE_PARTITIONS: &str = "partition.create";
 pub const CREATE_PARTITIONS_CODE: u32 = 402;
 pub const DELETE_PARTITIONS: &str = "partition.delete";
 pub const DELETE_PARTITIONS_CODE: u32 = 403;
+pub const MOVE_PARTITION: &str = "partition.move";
+pub const MOVE_PARTITION_CODE: u32 = 404;
+pub const SHRINK_PARTITIONS: &str = "partition.shrink";
+pub const SHRINK_PARTITIONS_CODE: u32 = 405;
+pub const DELETE_DRAINED_PARTITIONS: &str = "partition.delete_drained";
+pub const DELETE_DRAINED_PARTITIONS_CODE: u32 = 406;
 pub const DELETE_SEGMENTS: &str = "segment.delete";
 pub const DELETE_SEGMENTS_CODE: u32 = 503;
 pub const GET_CONSUMER_GROUP: &str = "consumer_group.get";
@@ -141,6 +155,7 @@ pub fn get_name_from_code(code: u32) -> Result<&'static str, IggyError> {
         SEND_MESSAGES_CODE => Ok(SEND_MESSAGES),
         POLL_MESSAGES_CODE => Ok(POLL_MESSAGES),
         FLUSH_UNSAVED_BUFFER_CODE => Ok(FLUSH_UNSAVED_BUFFER),
+        LONG_POLL_MESSAGES_CODE => Ok(LONG_POLL_MESSAGES),
         STORE_CONSUMER_OFFSET_CODE => Ok(STORE_CONSUMER_OFFSET),
         GET_CONSUMER_OFFSET_CODE => Ok(GET_CONSUMER_OFFSET),
         GET_STREAM_CODE => Ok(GET_STREAM),
@@ -155,8 +170,12 @@ pub fn get_name_from_code(code: u32) -> Result<&'static str, IggyError> {
         DELETE_TOPIC_CODE => Ok(DELETE_TOPIC),
         UPDATE_TOPIC_CODE => Ok(UPDATE_TOPIC),
         PURGE_TOPIC_CODE => Ok(PURGE_TOPIC),
+        WATCH_TOPICS_CODE => Ok(WATCH_TOPICS),
         CREATE_PARTITIONS_CODE => Ok(CREATE_PARTITIONS),
         DELETE_PARTITIONS_CODE => Ok(DELETE_PARTITIONS),
+        MOVE_PARTITION_CODE => Ok(MOVE_PARTITION),
+        SHRINK_PARTITIONS_CODE => Ok(SHRINK_PARTITIONS),
+        DELETE_DRAINED_PARTITIONS_CODE => Ok(DELETE_DRAINED_PARTITIONS),
         DELETE_SEGMENTS_CODE => Ok(DELETE_SEGMENTS),
         GET_CONSUMER_GROUP_CODE => Ok(GET_CONSUMER_GROUP),
         GET_CONSUMER_GROUPS_CODE => Ok(GET_CONSUMER_GROUPS),
@@ -165,6 +184,8 @@ pub fn get_name_from_code(code: u32) -> Result<&'static str, IggyError> {
         JOIN_CONSUMER_GROUP_CODE => Ok(JOIN_CONSUMER_GROUP),
         LEAVE_CONSUMER_GROUP_CODE => Ok(LEAVE_CONSUMER_GROUP),
         GET_SNAPSHOT_FILE_CODE => Ok(GET_SNAPSHOT_FILE),
+        GET_DATA_SNAPSHOT_CODE => Ok(GET_DATA_SNAPSHOT),
+        RESTORE_DATA_SNAPSHOT_CODE => Ok(RESTORE_DATA_SNAPSHOT),
         _ => Err(IggyError::InvalidCommand),
     }
 }
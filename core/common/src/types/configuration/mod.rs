@@ -19,3 +19,4 @@ pub(crate) mod auth_config;
 pub(crate) mod http_config;
 pub(crate) mod quick_config;
 pub(crate) mod tcp_config;
+pub(crate) mod websocket_config;
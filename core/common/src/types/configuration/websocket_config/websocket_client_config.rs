@@ -0,0 +1,44 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::{AutoLogin, IggyDuration};
+use std::str::FromStr;
+
+/// Configuration for the WebSocket client.
+#[derive(Debug, Clone)]
+pub struct WebSocketClientConfig {
+    /// The URL of the WebSocket endpoint exposed by the HTTP API.
+    pub server_url: String,
+    /// The optional JWT access token sent in the `Authorization` header during the upgrade.
+    pub access_token: Option<String>,
+    /// Whether to automatically login user after establishing connection.
+    pub auto_login: AutoLogin,
+    /// Interval of heartbeats sent by the client
+    pub heartbeat_interval: IggyDuration,
+}
+
+impl Default for WebSocketClientConfig {
+    fn default() -> WebSocketClientConfig {
+        WebSocketClientConfig {
+            server_url: "ws://127.0.0.1:3000/ws".to_string(),
+            access_token: None,
+            auto_login: AutoLogin::Disabled,
+            heartbeat_interval: IggyDuration::from_str("5s").unwrap(),
+        }
+    }
+}
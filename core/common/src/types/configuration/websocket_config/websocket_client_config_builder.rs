@@ -0,0 +1,66 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::{AutoLogin, IggyDuration, WebSocketClientConfig};
+
+/// The builder for the `WebSocketClientConfig` configuration.
+/// Allows configuring the WebSocket client with custom settings or using defaults:
+/// - `server_url`: Default is "ws://127.0.0.1:3000/ws"
+/// - `access_token`: Default is None.
+/// - `auto_login`: Default is AutoLogin::Disabled.
+/// - `heartbeat_interval`: Default is 5 seconds.
+#[derive(Debug, Default)]
+pub struct WebSocketClientConfigBuilder {
+    config: WebSocketClientConfig,
+}
+
+impl WebSocketClientConfigBuilder {
+    /// Create a new `WebSocketClientConfigBuilder` with default settings.
+    pub fn new() -> Self {
+        WebSocketClientConfigBuilder::default()
+    }
+
+    /// Sets the WebSocket endpoint URL for the client.
+    pub fn with_server_url(mut self, url: String) -> Self {
+        self.config.server_url = url;
+        self
+    }
+
+    /// Sets the JWT access token used to authenticate the upgrade request.
+    pub fn with_access_token(mut self, access_token: String) -> Self {
+        self.config.access_token = Some(access_token);
+        self
+    }
+
+    /// Sets the auto sign in during connection.
+    pub fn with_auto_sign_in(mut self, auto_sign_in: AutoLogin) -> Self {
+        self.config.auto_login = auto_sign_in;
+        self
+    }
+
+    /// Sets the interval of heartbeats sent by the client.
+    pub fn with_heartbeat_interval(mut self, interval: IggyDuration) -> Self {
+        self.config.heartbeat_interval = interval;
+        self
+    }
+
+    /// Builds the `WebSocketClientConfig` instance.
+    pub fn build(self) -> WebSocketClientConfig {
+        self.config
+    }
+}
@@ -22,6 +22,7 @@ use crate::BytesSerializable;
 use crate::Sizeable;
 use crate::error::IggyError;
 use crate::utils::byte_size::IggyByteSize;
+use crate::utils::duration::IggyDuration;
 use crate::utils::timestamp::IggyTimestamp;
 use crate::{HeaderKey, HeaderValue};
 use bon::bon;
@@ -59,6 +60,13 @@ pub const MAX_PAYLOAD_SIZE: u32 = 10 * 1000 * 1000;
 ///
 pub const MAX_USER_HEADERS_SIZE: u32 = 100 * 1000;
 
+/// Name of the reserved user header holding the time to live of the message in microseconds (as `u64`).
+///
+/// The message expires once its TTL has elapsed since it was appended by the server (the message timestamp).
+/// Expired messages are no longer returned when polling, and the segments containing only the expired messages
+/// are deleted by the messages cleaner, regardless of the topic message expiry.
+pub const MESSAGE_TTL_HEADER: &str = "iggy_ttl";
+
 /// A message stored in the Iggy messaging system.
 ///
 /// `IggyMessage` represents a single message that can be sent to or received from
@@ -105,7 +113,7 @@ pub const MAX_USER_HEADERS_SIZE: u32 = 100 * 1000;
 ///     .build()
 ///     .unwrap();
 /// ```
-#[derive(Debug, PartialEq, Default)]
+#[derive(Debug, Clone, PartialEq, Default)]
 pub struct IggyMessage {
     /// Message metadata
     pub header: IggyMessageHeader,
@@ -126,6 +134,7 @@ impl IggyMessage {
     /// * `id` - Optional message ID (defaults to 0 if None)
     /// * `payload` - The message content
     /// * `user_headers` - Optional user-defined headers
+    /// * `ttl` - Optional time to live of the message, stored as the `MESSAGE_TTL_HEADER` user header
     ///
     /// # Returns
     ///
@@ -161,12 +170,20 @@ impl IggyMessage {
     ///     .user_headers(user_headers)
     ///     .build()
     ///     .unwrap();
+    ///
+    /// // Message expiring after 30 seconds
+    /// let msg = IggyMessage::builder()
+    ///     .payload("Hello".into())
+    ///     .ttl(IggyDuration::from_str("30s").unwrap())
+    ///     .build()
+    ///     .unwrap();
     /// ```
     #[builder]
     pub fn new(
         id: Option<u128>,
         payload: Bytes,
         user_headers: Option<HashMap<HeaderKey, HeaderValue>>,
+        ttl: Option<IggyDuration>,
     ) -> Result<Self, IggyError> {
         if payload.is_empty() {
             return Err(IggyError::InvalidMessagePayloadLength);
@@ -176,6 +193,18 @@ impl IggyMessage {
             return Err(IggyError::TooBigMessagePayload);
         }
 
+        let user_headers = match ttl {
+            Some(ttl) => {
+                let mut user_headers = user_headers.unwrap_or_default();
+                user_headers.insert(
+                    HeaderKey::new(MESSAGE_TTL_HEADER)?,
+                    HeaderValue::from_uint64(ttl.as_micros())?,
+                );
+                Some(user_headers)
+            }
+            None => user_headers,
+        };
+
         let user_headers_length = get_user_headers_size(&user_headers).unwrap_or(0);
 
         if user_headers_length > MAX_USER_HEADERS_SIZE {
@@ -641,6 +670,7 @@ impl<'de> Deserialize<'de> for IggyMessage {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::IggyMessageView;
 
     #[test]
     fn test_create_simple_message() {
@@ -685,6 +715,64 @@ mod tests {
         assert!(headers_map.contains_key(&HeaderKey::new("content-type").unwrap()));
     }
 
+    #[test]
+    fn test_create_with_ttl() {
+        let message = IggyMessage::builder()
+            .payload(Bytes::from("test with ttl"))
+            .ttl(IggyDuration::from_str("30s").unwrap())
+            .build()
+            .expect("String conversion should not fail");
+
+        let ttl = message
+            .get_user_header(&HeaderKey::new(MESSAGE_TTL_HEADER).unwrap())
+            .unwrap()
+            .unwrap();
+        assert_eq!(ttl.as_uint64().unwrap(), 30_000_000);
+
+        let bytes = message.to_bytes();
+        let view = IggyMessageView::new(&bytes);
+        assert_eq!(view.expiry_timestamp(), Some(30_000_000));
+    }
+
+    #[test]
+    fn test_expiry_timestamp_with_other_headers() {
+        let mut headers = HashMap::new();
+        headers.insert(
+            HeaderKey::new("content-type").unwrap(),
+            HeaderValue::from_str("text/plain").unwrap(),
+        );
+        headers.insert(
+            HeaderKey::new("iggy_ttl_suffix").unwrap(),
+            HeaderValue::from_uint64(1).unwrap(),
+        );
+        let message = IggyMessage::builder()
+            .payload(Bytes::from("test with headers and ttl"))
+            .user_headers(headers.clone())
+            .ttl(IggyDuration::from_str("1s").unwrap())
+            .build()
+            .unwrap();
+        let bytes = message.to_bytes();
+        assert_eq!(
+            IggyMessageView::new(&bytes).expiry_timestamp(),
+            Some(1_000_000)
+        );
+
+        let message = IggyMessage::builder()
+            .payload(Bytes::from("test with headers"))
+            .user_headers(headers)
+            .build()
+            .unwrap();
+        let bytes = message.to_bytes();
+        assert_eq!(IggyMessageView::new(&bytes).expiry_timestamp(), None);
+
+        let message = IggyMessage::builder()
+            .payload(Bytes::from("test without headers"))
+            .build()
+            .unwrap();
+        let bytes = message.to_bytes();
+        assert_eq!(IggyMessageView::new(&bytes).expiry_timestamp(), None);
+    }
+
     #[test]
     fn test_empty_payload() {
         let message = IggyMessage::builder().payload(Bytes::new()).build();
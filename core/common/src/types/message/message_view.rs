@@ -16,8 +16,9 @@
  * under the License.
  */
 
-use super::HeaderValue;
+use super::iggy_message::MESSAGE_TTL_HEADER;
 use super::message_header::*;
+use super::{HeaderKind, HeaderValue};
 use crate::BytesSerializable;
 use crate::IggyByteSize;
 use crate::Sizeable;
@@ -98,6 +99,36 @@ impl<'a> IggyMessageView<'a> {
         }
     }
 
+    /// Returns the timestamp (in microseconds) at which the message expires, if it has the `MESSAGE_TTL_HEADER` user header.
+    /// It's checked for every polled message, so the raw user headers are scanned for the key, without parsing them into the map.
+    pub fn expiry_timestamp(&self) -> Option<u64> {
+        let mut headers = self.user_headers()?;
+        while !headers.is_empty() {
+            let key_length = u32::from_le_bytes(headers.get(..4)?.try_into().ok()?) as usize;
+            let key = headers.get(4..4 + key_length)?;
+            let kind = *headers.get(4 + key_length)?;
+            let value_offset = 4 + key_length + 1 + 4;
+            let value_length = u32::from_le_bytes(
+                headers
+                    .get(value_offset - 4..value_offset)?
+                    .try_into()
+                    .ok()?,
+            ) as usize;
+            let value = headers.get(value_offset..value_offset + value_length)?;
+            if key == MESSAGE_TTL_HEADER.as_bytes() {
+                if kind != HeaderKind::Uint64.as_code() {
+                    return None;
+                }
+
+                let ttl = u64::from_le_bytes(value.try_into().ok()?);
+                return Some(self.header().timestamp().saturating_add(ttl));
+            }
+
+            headers = &headers[value_offset + value_length..];
+        }
+        None
+    }
+
     /// Returns the size of the entire message.
     pub fn size(&self) -> usize {
         let header_view = self.header();
@@ -29,14 +29,18 @@ pub mod partitioning_kind;
 pub mod polled_messages;
 pub mod polling_kind;
 pub mod polling_strategy;
+pub mod producer_sequence;
 mod user_headers;
 
 pub const INDEX_SIZE: usize = 16;
 
 pub use crate::commands::messages::flush_unsaved_buffer::FlushUnsavedBuffer;
+pub use crate::commands::messages::long_poll_messages::{
+    LongPollMessages, MAX_LONG_POLLING_TIMEOUT,
+};
 pub use crate::commands::messages::poll_messages::PollMessages;
 pub use crate::commands::messages::send_messages::SendMessages;
-pub use iggy_message::{IggyMessage, MAX_PAYLOAD_SIZE, MAX_USER_HEADERS_SIZE};
+pub use iggy_message::{IggyMessage, MAX_PAYLOAD_SIZE, MAX_USER_HEADERS_SIZE, MESSAGE_TTL_HEADER};
 pub use index::IggyIndex;
 pub use index_view::IggyIndexView;
 pub use indexes::IggyIndexes;
@@ -55,4 +59,5 @@ pub use partitioning_kind::PartitioningKind;
 pub use polled_messages::PolledMessages;
 pub use polling_kind::PollingKind;
 pub use polling_strategy::PollingStrategy;
+pub use producer_sequence::{PRODUCER_SEQUENCE_SIZE, ProducerSequence};
 pub use user_headers::{HeaderKey, HeaderKind, HeaderValue};
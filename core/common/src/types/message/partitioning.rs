@@ -32,6 +32,8 @@ use std::{
 /// - `Balanced` - the partition ID is calculated by the server using the round-robin algorithm.
 /// - `PartitionId` - the partition ID is provided by the client.
 /// - `MessagesKey` - the partition ID is calculated by the server using the hash of the provided messages key.
+/// - `ConsistentMessagesKey` - the partition ID is calculated by the server using the jump consistent hash of the provided messages key,
+///   which keeps most of the keys in the same partitions when the partitions count changes.
 #[serde_as]
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
 pub struct Partitioning {
@@ -55,7 +57,7 @@ impl Display for Partitioning {
                 self.kind,
                 u32::from_le_bytes(self.value[..4].try_into().unwrap())
             ),
-            PartitioningKind::MessagesKey => {
+            PartitioningKind::MessagesKey | PartitioningKind::ConsistentMessagesKey => {
                 write!(f, "{}|{}", self.kind, String::from_utf8_lossy(&self.value))
             }
         }
@@ -128,6 +130,27 @@ impl Partitioning {
         }
     }
 
+    /// Partition the messages using the jump consistent hash of the provided messages key.
+    pub fn consistent_messages_key(value: &[u8]) -> Result<Self, IggyError> {
+        let mut partitioning = Self::messages_key(value)?;
+        partitioning.kind = PartitioningKind::ConsistentMessagesKey;
+        Ok(partitioning)
+    }
+
+    /// Partition the messages using the jump consistent hash of the provided messages key as str.
+    pub fn consistent_messages_key_str(value: &str) -> Result<Self, IggyError> {
+        Self::consistent_messages_key(value.as_bytes())
+    }
+
+    /// Partition the messages using the jump consistent hash of the provided messages key as u64.
+    pub fn consistent_messages_key_u64(value: u64) -> Self {
+        Partitioning {
+            kind: PartitioningKind::ConsistentMessagesKey,
+            length: 8,
+            value: value.to_le_bytes().to_vec(),
+        }
+    }
+
     /// Create the partitioning from the provided partitioning.
     pub fn from_partitioning(partitioning: &Partitioning) -> Self {
         Partitioning {
@@ -34,6 +34,9 @@ pub enum PartitioningKind {
     PartitionId,
     /// The partition ID is calculated by the server using the hash of the provided messages key.
     MessagesKey,
+    /// The partition ID is calculated by the server using the jump consistent hash of the provided messages key,
+    /// so changing the partitions count moves only a minimal fraction of the keys to other partitions.
+    ConsistentMessagesKey,
 }
 
 impl Hash for PartitioningKind {
@@ -49,6 +52,7 @@ impl PartitioningKind {
             PartitioningKind::Balanced => 1,
             PartitioningKind::PartitionId => 2,
             PartitioningKind::MessagesKey => 3,
+            PartitioningKind::ConsistentMessagesKey => 4,
         }
     }
 
@@ -58,6 +62,7 @@ impl PartitioningKind {
             1 => Ok(PartitioningKind::Balanced),
             2 => Ok(PartitioningKind::PartitionId),
             3 => Ok(PartitioningKind::MessagesKey),
+            4 => Ok(PartitioningKind::ConsistentMessagesKey),
             _ => Err(IggyError::InvalidCommand),
         }
     }
@@ -69,6 +74,7 @@ impl Display for PartitioningKind {
             PartitioningKind::Balanced => write!(f, "balanced"),
             PartitioningKind::PartitionId => write!(f, "partition_id"),
             PartitioningKind::MessagesKey => write!(f, "messages_key"),
+            PartitioningKind::ConsistentMessagesKey => write!(f, "consistent_messages_key"),
         }
     }
 }
@@ -0,0 +1,130 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::{BytesSerializable, IggyByteSize, Sizeable, error::IggyError};
+use bytes::{BufMut, Bytes, BytesMut};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+pub const PRODUCER_SEQUENCE_SIZE: usize = 16;
+
+/// `ProducerSequence` is used by the idempotent producer to make the sending of messages safe to retry.
+/// It has the following fields:
+/// - `producer_id` - unique ID of the producer, assigned by the client.
+/// - `base_sequence` - sequence number of the first message in the batch, the following messages have consecutive numbers.
+///
+/// The sequence numbers are tracked by the server separately for each partition,
+/// thus the batch which has been already appended is discarded, and the batch with a gap in the sequence is rejected.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Default)]
+pub struct ProducerSequence {
+    /// Unique ID of the producer.
+    pub producer_id: u64,
+    /// Sequence number of the first message in the batch.
+    pub base_sequence: u64,
+}
+
+impl ProducerSequence {
+    pub fn new(producer_id: u64, base_sequence: u64) -> Self {
+        Self {
+            producer_id,
+            base_sequence,
+        }
+    }
+
+    /// Returns the sequence number of the last message in the batch of the given size.
+    pub fn last_sequence(&self, messages_count: u32) -> u64 {
+        self.base_sequence + (messages_count.max(1) as u64 - 1)
+    }
+
+    pub fn from_raw_bytes(bytes: &[u8]) -> Result<Self, IggyError> {
+        if bytes.len() < PRODUCER_SEQUENCE_SIZE {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let producer_id = u64::from_le_bytes(
+            bytes[..8]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let base_sequence = u64::from_le_bytes(
+            bytes[8..PRODUCER_SEQUENCE_SIZE]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        Ok(ProducerSequence {
+            producer_id,
+            base_sequence,
+        })
+    }
+}
+
+impl Display for ProducerSequence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}|{}", self.producer_id, self.base_sequence)
+    }
+}
+
+impl Sizeable for ProducerSequence {
+    fn get_size_bytes(&self) -> IggyByteSize {
+        IggyByteSize::from(PRODUCER_SEQUENCE_SIZE as u64)
+    }
+}
+
+impl BytesSerializable for ProducerSequence {
+    fn to_bytes(&self) -> Bytes {
+        let mut bytes = BytesMut::with_capacity(PRODUCER_SEQUENCE_SIZE);
+        self.write_to_buffer(&mut bytes);
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<Self, IggyError>
+    where
+        Self: Sized,
+    {
+        ProducerSequence::from_raw_bytes(&bytes)
+    }
+
+    fn write_to_buffer(&self, bytes: &mut BytesMut) {
+        bytes.put_u64_le(self.producer_id);
+        bytes.put_u64_le(self.base_sequence);
+    }
+
+    fn get_buffer_size(&self) -> usize {
+        PRODUCER_SEQUENCE_SIZE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_be_serialized_as_bytes_and_deserialized() {
+        let sequence = ProducerSequence::new(123, 456);
+        let bytes = sequence.to_bytes();
+        assert_eq!(bytes.len(), PRODUCER_SEQUENCE_SIZE);
+        assert_eq!(ProducerSequence::from_bytes(bytes).unwrap(), sequence);
+    }
+
+    #[test]
+    fn last_sequence_should_include_all_messages_in_batch() {
+        let sequence = ProducerSequence::new(1, 10);
+        assert_eq!(sequence.last_sequence(1), 10);
+        assert_eq!(sequence.last_sequence(5), 14);
+    }
+}
@@ -33,3 +33,4 @@ pub(crate) mod stats;
 pub(crate) mod stream;
 pub(crate) mod topic;
 pub(crate) mod user;
+pub(crate) mod websocket;
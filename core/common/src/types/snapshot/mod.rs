@@ -18,9 +18,11 @@
 
 use std::{fmt, str::FromStr};
 
+use crate::Identifier;
 use crate::error::IggyError;
 
 use serde::{Deserialize, Serialize};
+use serde_with::{DisplayFromStr, serde_as};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Snapshot(pub Vec<u8>);
@@ -31,6 +33,45 @@ impl Snapshot {
     }
 }
 
+/// Stream or topic to be included in the data snapshot.
+/// When the topic isn't specified, all the topics of the stream are included.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DataSnapshotTarget {
+    /// Unique stream ID (numeric or name).
+    #[serde_as(as = "DisplayFromStr")]
+    pub stream_id: Identifier,
+    /// Optional unique topic ID (numeric or name).
+    #[serde(default)]
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub topic_id: Option<Identifier>,
+}
+
+impl DataSnapshotTarget {
+    pub fn stream(stream_id: Identifier) -> Self {
+        Self {
+            stream_id,
+            topic_id: None,
+        }
+    }
+
+    pub fn topic(stream_id: Identifier, topic_id: Identifier) -> Self {
+        Self {
+            stream_id,
+            topic_id: Some(topic_id),
+        }
+    }
+}
+
+impl fmt::Display for DataSnapshotTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.topic_id {
+            Some(topic_id) => write!(f, "{}/{}", self.stream_id, topic_id),
+            None => write!(f, "{}", self.stream_id),
+        }
+    }
+}
+
 /// Enum representing the different types of system snapshots that can be taken.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub enum SystemSnapshotType {
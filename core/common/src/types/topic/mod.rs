@@ -16,6 +16,8 @@
  * under the License.
  */
 
+mod topic_overrides;
+
 use crate::CompressionAlgorithm;
 use crate::Partition;
 use crate::utils::byte_size::IggyByteSize;
@@ -24,6 +26,8 @@ use crate::utils::timestamp::IggyTimestamp;
 use crate::utils::topic_size::MaxTopicSize;
 use serde::{Deserialize, Serialize};
 
+pub use topic_overrides::TopicOverrides;
+
 /// `Topic` represents the medium level of logical separation of data as it's a part of the stream.
 /// It consists of the following fields:
 /// - `id`: the unique identifier (numeric) of the topic.
@@ -0,0 +1,258 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::BytesSerializable;
+use crate::Confirmation;
+use crate::Validatable;
+use crate::error::IggyError;
+use crate::utils::byte_size::IggyByteSize;
+use crate::utils::duration::IggyDuration;
+use bytes::{BufMut, Bytes, BytesMut};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// The segment size (u64), messages required to save (u32), enforce fsync, server confirmation and deduplication enabled (u8 each),
+/// deduplication max entries and expiry (u64 each), where 0 stands for the server default.
+const TOPIC_OVERRIDES_SIZE: usize = 31;
+
+/// `TopicOverrides` holds the per-topic overrides of the server-wide `[system]` settings.
+/// Each setting which is not provided (`None`) falls back to the server configuration.
+/// It consists of the following fields:
+/// - `segment_size`: the maximum size of a single segment (`segment.size`).
+/// - `enforce_fsync`: whether each persisted batch is followed by fsync (`partition.enforce_fsync`).
+/// - `messages_required_to_save`: the number of buffered messages which triggers saving them on disk (`partition.messages_required_to_save`).
+/// - `server_confirmation`: whether the messages are written to disk before the response is sent (`segment.server_confirmation`).
+/// - `deduplication_enabled`: whether the messages with duplicated IDs are skipped (`message_deduplication.enabled`).
+/// - `deduplication_max_entries`: the maximum number of IDs in the deduplication cache (`message_deduplication.max_entries`).
+/// - `deduplication_expiry`: the maximum age of IDs in the deduplication cache (`message_deduplication.expiry`).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TopicOverrides {
+    /// The maximum size of a single segment.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub segment_size: Option<IggyByteSize>,
+    /// Whether each persisted batch of messages is followed by fsync.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enforce_fsync: Option<bool>,
+    /// The number of buffered messages which triggers saving them on disk.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub messages_required_to_save: Option<u32>,
+    /// Whether the messages are written to disk before the response is sent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub server_confirmation: Option<Confirmation>,
+    /// Whether the messages with duplicated IDs are skipped.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deduplication_enabled: Option<bool>,
+    /// The maximum number of IDs in the deduplication cache.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deduplication_max_entries: Option<u64>,
+    /// The maximum age of IDs in the deduplication cache.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deduplication_expiry: Option<IggyDuration>,
+}
+
+impl TopicOverrides {
+    /// Returns `true` if none of the settings is overridden.
+    pub fn is_empty(&self) -> bool {
+        self.segment_size.is_none()
+            && self.enforce_fsync.is_none()
+            && self.messages_required_to_save.is_none()
+            && self.server_confirmation.is_none()
+            && self.deduplication_enabled.is_none()
+            && self.deduplication_max_entries.is_none()
+            && self.deduplication_expiry.is_none()
+    }
+
+    /// Returns the size of the serialized overrides in bytes.
+    pub fn get_size_bytes() -> usize {
+        TOPIC_OVERRIDES_SIZE
+    }
+}
+
+impl Validatable<IggyError> for TopicOverrides {
+    fn validate(&self) -> Result<(), IggyError> {
+        if self
+            .segment_size
+            .is_some_and(|size| size.as_bytes_u64() == 0)
+        {
+            return Err(IggyError::InvalidTopicOverrides);
+        }
+
+        if self.messages_required_to_save == Some(0) || self.deduplication_max_entries == Some(0) {
+            return Err(IggyError::InvalidTopicOverrides);
+        }
+
+        if self
+            .deduplication_expiry
+            .is_some_and(|expiry| expiry.is_zero())
+        {
+            return Err(IggyError::InvalidTopicOverrides);
+        }
+
+        Ok(())
+    }
+}
+
+impl BytesSerializable for TopicOverrides {
+    fn to_bytes(&self) -> Bytes {
+        let mut bytes = BytesMut::with_capacity(TOPIC_OVERRIDES_SIZE);
+        bytes.put_u64_le(self.segment_size.map_or(0, |size| size.as_bytes_u64()));
+        bytes.put_u32_le(self.messages_required_to_save.unwrap_or(0));
+        bytes.put_u8(match self.enforce_fsync {
+            None => 0,
+            Some(false) => 1,
+            Some(true) => 2,
+        });
+        bytes.put_u8(match self.server_confirmation {
+            None => 0,
+            Some(Confirmation::Wait) => 1,
+            Some(Confirmation::NoWait) => 2,
+        });
+        bytes.put_u8(match self.deduplication_enabled {
+            None => 0,
+            Some(false) => 1,
+            Some(true) => 2,
+        });
+        bytes.put_u64_le(self.deduplication_max_entries.unwrap_or(0));
+        bytes.put_u64_le(
+            self.deduplication_expiry
+                .map_or(0, |expiry| expiry.as_micros()),
+        );
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<TopicOverrides, IggyError> {
+        if bytes.len() != TOPIC_OVERRIDES_SIZE {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let segment_size = u64::from_le_bytes(
+            bytes[0..8]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let messages_required_to_save = u32::from_le_bytes(
+            bytes[8..12]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let enforce_fsync = match bytes[12] {
+            0 => None,
+            1 => Some(false),
+            2 => Some(true),
+            _ => return Err(IggyError::InvalidCommand),
+        };
+        let server_confirmation = match bytes[13] {
+            0 => None,
+            1 => Some(Confirmation::Wait),
+            2 => Some(Confirmation::NoWait),
+            _ => return Err(IggyError::InvalidCommand),
+        };
+        let deduplication_enabled = match bytes[14] {
+            0 => None,
+            1 => Some(false),
+            2 => Some(true),
+            _ => return Err(IggyError::InvalidCommand),
+        };
+        let deduplication_max_entries = u64::from_le_bytes(
+            bytes[15..23]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let deduplication_expiry = u64::from_le_bytes(
+            bytes[23..31]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+
+        Ok(TopicOverrides {
+            segment_size: (segment_size > 0).then(|| IggyByteSize::from(segment_size)),
+            enforce_fsync,
+            messages_required_to_save: (messages_required_to_save > 0)
+                .then_some(messages_required_to_save),
+            server_confirmation,
+            deduplication_enabled,
+            deduplication_max_entries: (deduplication_max_entries > 0)
+                .then_some(deduplication_max_entries),
+            deduplication_expiry: (deduplication_expiry > 0)
+                .then(|| IggyDuration::from(deduplication_expiry)),
+        })
+    }
+}
+
+impl Display for TopicOverrides {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fn display<T: Display>(value: &Option<T>) -> String {
+            value
+                .as_ref()
+                .map_or_else(|| "server_default".to_owned(), |value| value.to_string())
+        }
+
+        write!(
+            f,
+            "segment_size: {}, enforce_fsync: {}, messages_required_to_save: {}, server_confirmation: {}, deduplication_enabled: {}, deduplication_max_entries: {}, deduplication_expiry: {}",
+            display(&self.segment_size),
+            display(&self.enforce_fsync),
+            display(&self.messages_required_to_save),
+            display(&self.server_confirmation),
+            display(&self.deduplication_enabled),
+            display(&self.deduplication_max_entries),
+            display(&self.deduplication_expiry)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn should_be_serialized_and_deserialized_from_bytes() {
+        let overrides = TopicOverrides {
+            segment_size: Some(IggyByteSize::from_str("16 MiB").unwrap()),
+            enforce_fsync: Some(true),
+            messages_required_to_save: None,
+            server_confirmation: Some(Confirmation::NoWait),
+            deduplication_enabled: Some(true),
+            deduplication_max_entries: None,
+            deduplication_expiry: Some(IggyDuration::from_str("1h").unwrap()),
+        };
+
+        let bytes = overrides.to_bytes();
+
+        assert_eq!(bytes.len(), TOPIC_OVERRIDES_SIZE);
+        assert_eq!(TopicOverrides::from_bytes(bytes).unwrap(), overrides);
+    }
+
+    #[test]
+    fn empty_overrides_should_be_serialized_as_zeros() {
+        let bytes = TopicOverrides::default().to_bytes();
+
+        assert!(bytes.iter().all(|byte| *byte == 0));
+        assert!(TopicOverrides::from_bytes(bytes).unwrap().is_empty());
+    }
+
+    #[test]
+    fn overrides_of_invalid_size_should_not_be_deserialized() {
+        let bytes = TopicOverrides::default()
+            .to_bytes()
+            .slice(..TOPIC_OVERRIDES_SIZE - 1);
+
+        assert!(TopicOverrides::from_bytes(bytes).is_err());
+    }
+}
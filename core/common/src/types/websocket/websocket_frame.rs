@@ -0,0 +1,227 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::error::IggyError;
+use crate::{
+    Consumer, ConsumerKind, Identifier, PollMessages, PolledMessages, PollingKind, PollingStrategy,
+    Validatable,
+};
+use serde::{Deserialize, Serialize};
+use serde_with::{DisplayFromStr, serde_as};
+
+/// The JSON control frame sent by the client as a WebSocket text message.
+/// Binary messages carry the regular binary protocol, while text messages are used for server push:
+/// - `subscribe`: start receiving the messages from the given stream and topic without polling.
+/// - `unsubscribe`: stop the previously created subscription.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WebSocketClientFrame {
+    Subscribe(MessagesSubscription),
+    Unsubscribe { subscription_id: u32 },
+}
+
+/// The JSON frame sent by the server as a WebSocket text message.
+/// - `subscribed`: the subscription has been created.
+/// - `unsubscribed`: the subscription has been removed.
+/// - `messages`: the batch of messages pushed for the subscription.
+/// - `error`: the subscription (or the frame itself, if there is no subscription ID) has failed.
+///
+/// The fields are nested under `data`, as the internally tagged representation
+/// doesn't support deserializing `u128` values (e.g. the message ID).
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum WebSocketServerFrame {
+    Subscribed {
+        subscription_id: u32,
+    },
+    Unsubscribed {
+        subscription_id: u32,
+    },
+    Messages {
+        subscription_id: u32,
+        messages: PolledMessages,
+    },
+    Error {
+        subscription_id: Option<u32>,
+        code: u32,
+        reason: String,
+    },
+}
+
+/// `MessagesSubscription` describes the messages to be pushed by the server over the WebSocket connection.
+/// It consists of the following fields:
+/// - `subscription_id`: unique (per connection) identifier of the subscription chosen by the client.
+/// - `stream_id`: unique stream ID (numeric or name).
+/// - `topic_id`: unique topic ID (numeric or name).
+/// - `partition_id`: optional partition ID, if not provided, it will be resolved like for the regular polling.
+/// - `consumer_kind`: the kind of consumer, either `consumer` or `consumer_group`.
+/// - `consumer_id`: unique consumer ID (numeric or name).
+/// - `strategy`: the strategy used to start the subscription.
+/// - `count`: the maximum number of messages pushed in a single frame.
+/// - `auto_commit`: whether to store the offset of the pushed messages on the server.
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct MessagesSubscription {
+    /// Unique (per connection) identifier of the subscription chosen by the client.
+    pub subscription_id: u32,
+    /// Unique stream ID (numeric or name).
+    #[serde_as(as = "DisplayFromStr")]
+    pub stream_id: Identifier,
+    /// Unique topic ID (numeric or name).
+    #[serde_as(as = "DisplayFromStr")]
+    pub topic_id: Identifier,
+    /// Partition ID from which messages will be pushed.
+    #[serde(default)]
+    pub partition_id: Option<u32>,
+    /// The kind of consumer.
+    #[serde(default)]
+    pub consumer_kind: ConsumerKind,
+    /// Unique consumer ID (numeric or name).
+    #[serde_as(as = "DisplayFromStr")]
+    pub consumer_id: Identifier,
+    /// The strategy used to start the subscription.
+    #[serde(default = "PollingStrategy::default")]
+    pub strategy: PollingStrategy,
+    /// The maximum number of messages pushed in a single frame.
+    #[serde(default = "PollMessages::default_number_of_messages_to_poll")]
+    pub count: u32,
+    /// Whether to store the offset of the pushed messages on the server.
+    #[serde(default)]
+    pub auto_commit: bool,
+}
+
+impl MessagesSubscription {
+    /// Returns the consumer used by the subscription.
+    pub fn consumer(&self) -> Consumer {
+        Consumer {
+            kind: self.consumer_kind,
+            id: self.consumer_id.clone(),
+        }
+    }
+}
+
+impl Validatable<IggyError> for MessagesSubscription {
+    fn validate(&self) -> Result<(), IggyError> {
+        if self.count == 0 {
+            return Err(IggyError::InvalidMessagesCount);
+        }
+
+        // Without storing the offsets, the `next` strategy would keep pushing the same messages.
+        if self.strategy.kind == PollingKind::Next && !self.auto_commit {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        // Consumer group members switch between the assigned partitions, so only the stored offsets can be followed.
+        if self.consumer_kind == ConsumerKind::ConsumerGroup
+            && self.partition_id.is_none()
+            && self.strategy.kind != PollingKind::Next
+        {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IggyMessage;
+
+    #[test]
+    fn subscribe_frame_should_be_deserialized_with_defaults() {
+        let json = r#"{"type":"subscribe","subscription_id":1,"stream_id":"orders","topic_id":"2","consumer_id":"3","auto_commit":true}"#;
+        let frame: WebSocketClientFrame = serde_json::from_str(json).unwrap();
+        let WebSocketClientFrame::Subscribe(subscription) = frame else {
+            panic!("Expected subscribe frame");
+        };
+
+        assert_eq!(subscription.subscription_id, 1);
+        assert_eq!(subscription.stream_id, Identifier::named("orders").unwrap());
+        assert_eq!(subscription.topic_id, Identifier::numeric(2).unwrap());
+        assert_eq!(subscription.consumer().kind, ConsumerKind::Consumer);
+        assert_eq!(subscription.consumer().id, Identifier::numeric(3).unwrap());
+        assert_eq!(subscription.partition_id, None);
+        assert_eq!(subscription.strategy, PollingStrategy::default());
+        assert_eq!(
+            subscription.count,
+            PollMessages::default_number_of_messages_to_poll()
+        );
+        assert!(subscription.validate().is_ok());
+    }
+
+    #[test]
+    fn subscription_with_next_strategy_should_require_auto_commit() {
+        let subscription = MessagesSubscription {
+            subscription_id: 1,
+            stream_id: Identifier::numeric(1).unwrap(),
+            topic_id: Identifier::numeric(1).unwrap(),
+            partition_id: Some(1),
+            consumer_kind: ConsumerKind::Consumer,
+            consumer_id: Identifier::numeric(1).unwrap(),
+            strategy: PollingStrategy::next(),
+            count: 10,
+            auto_commit: false,
+        };
+        assert!(subscription.validate().is_err());
+    }
+
+    #[test]
+    fn messages_frame_should_be_serialized_with_type_tag() {
+        let frame = WebSocketServerFrame::Messages {
+            subscription_id: 7,
+            messages: PolledMessages::empty(),
+        };
+        let json = serde_json::to_value(&frame).unwrap();
+        assert_eq!(json["type"], "messages");
+        assert_eq!(json["data"]["subscription_id"], 7);
+        assert_eq!(json["data"]["messages"]["count"], 0);
+    }
+
+    #[test]
+    fn messages_frame_should_be_deserialized() {
+        let message = IggyMessage::builder()
+            .id(u128::MAX)
+            .payload("hello".into())
+            .build()
+            .unwrap();
+        let frame = WebSocketServerFrame::Messages {
+            subscription_id: 7,
+            messages: PolledMessages {
+                partition_id: 1,
+                current_offset: 0,
+                count: 1,
+                messages: vec![message],
+            },
+        };
+        let json = serde_json::to_string(&frame).unwrap();
+        let frame: WebSocketServerFrame = serde_json::from_str(&json).unwrap();
+        let WebSocketServerFrame::Messages {
+            subscription_id,
+            messages,
+        } = frame
+        else {
+            panic!("Expected messages frame");
+        };
+
+        assert_eq!(subscription_id, 7);
+        assert_eq!(messages.messages.len(), 1);
+        assert_eq!(messages.messages[0].header.id, u128::MAX);
+        assert_eq!(messages.messages[0].payload, "hello");
+    }
+}
@@ -0,0 +1,72 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+const JUMP_MULTIPLIER: u64 = 2862933555777941757;
+
+/// Calculates the partition ID (starting from 1) for the provided key hash using the jump consistent hash algorithm.
+/// Unlike the modulo of the partitions count, growing the topic from N to N+1 partitions moves only 1/(N+1) of the keys,
+/// all of them to the new partition, and removing the last partition moves only the keys which were assigned to it.
+pub fn calculate_partition_id_by_consistent_hash(key_hash: u64, partitions_count: u32) -> u32 {
+    if partitions_count == 0 {
+        return 0;
+    }
+
+    let mut key = key_hash;
+    let mut bucket: i64 = -1;
+    let mut next_bucket: i64 = 0;
+    while next_bucket < partitions_count as i64 {
+        bucket = next_bucket;
+        key = key.wrapping_mul(JUMP_MULTIPLIER).wrapping_add(1);
+        next_bucket =
+            ((bucket + 1) as f64 * ((1u64 << 31) as f64 / ((key >> 33) + 1) as f64)) as i64;
+    }
+    bucket as u32 + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn partition_id_should_be_in_range() {
+        for key_hash in 0..1000 {
+            let partition_id = calculate_partition_id_by_consistent_hash(key_hash, 7);
+            assert!((1..=7).contains(&partition_id));
+        }
+        assert_eq!(calculate_partition_id_by_consistent_hash(123, 1), 1);
+        assert_eq!(calculate_partition_id_by_consistent_hash(123, 0), 0);
+    }
+
+    #[test]
+    fn growing_partitions_should_move_keys_only_to_the_new_partition() {
+        let keys_count = 10000;
+        let mut moved_keys = 0;
+        for key_hash in 0..keys_count {
+            let key_hash = key_hash * 2654435761;
+            let partition_id = calculate_partition_id_by_consistent_hash(key_hash, 10);
+            let new_partition_id = calculate_partition_id_by_consistent_hash(key_hash, 11);
+            if partition_id != new_partition_id {
+                assert_eq!(new_partition_id, 11);
+                moved_keys += 1;
+            }
+        }
+
+        // Roughly 1/11 of the keys are expected to move.
+        assert!(moved_keys > keys_count / 20);
+        assert!(moved_keys < keys_count / 5);
+    }
+}
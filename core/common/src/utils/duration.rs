@@ -41,7 +41,7 @@ impl IggyDuration {
 }
 
 impl IggyDuration {
-    pub fn new(duration: Duration) -> IggyDuration {
+    pub const fn new(duration: Duration) -> IggyDuration {
         IggyDuration { duration }
     }
 
@@ -28,7 +28,7 @@ use std::sync::Arc;
 
 const API_KEY_HEADER: &str = "api-key";
 
-const PUBLIC_PATHS: &[&str] = &["/", "/health"];
+const PUBLIC_PATHS: &[&str] = &["/", "/health", "/metrics"];
 
 pub async fn resolve_api_key(
     State(context): State<Arc<RuntimeContext>>,
@@ -0,0 +1,41 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+use crate::{context::RuntimeContext, manager::health::ConnectorStatus, metrics::METRICS};
+use axum::{Router, extract::State, routing::get};
+use std::sync::Arc;
+
+pub fn router(state: Arc<RuntimeContext>) -> Router {
+    Router::new()
+        .route("/metrics", get(get_metrics))
+        .with_state(state)
+}
+
+async fn get_metrics(State(context): State<Arc<RuntimeContext>>) -> String {
+    for sink in context.sinks.get_all_details().await {
+        let sink = sink.lock().await;
+        sink.metrics
+            .set_running(sink.health.status() == ConnectorStatus::Running);
+    }
+    for source in context.sources.get_all_details().await {
+        let source = source.lock().await;
+        source
+            .metrics
+            .set_running(source.health.status() == ConnectorStatus::Running);
+    }
+    METRICS.get_formatted_output()
+}
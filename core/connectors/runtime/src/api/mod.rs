@@ -28,7 +28,9 @@ use tracing::{error, info};
 mod auth;
 pub mod config;
 mod error;
+mod metrics;
 mod models;
+mod reload;
 mod sink;
 mod source;
 
@@ -46,6 +48,8 @@ pub async fn init(config: &HttpApiConfig, context: Arc<RuntimeContext>) {
             "/health",
             get(|| async { Json(serde_json::json!({ "status": "healthy" })) }),
         )
+        .merge(metrics::router(context.clone()))
+        .merge(reload::router(context.clone()))
         .merge(sink::router(context.clone()))
         .merge(source::router(context.clone()));
 
@@ -18,7 +18,12 @@
 
 use crate::{
     configs::{ConfigFormat, StreamConsumerConfig, StreamProducerConfig},
-    manager::{sink::SinkInfo, source::SourceInfo},
+    manager::{
+        health::{ConnectorError, ConnectorHealthReport, ConnectorStatus},
+        sink::SinkInfo,
+        source::SourceInfo,
+    },
+    reload::{ConfigChanges, ReloadResult},
 };
 use iggy_connector_sdk::transforms::TransformType;
 use serde::{Deserialize, Serialize};
@@ -31,6 +36,7 @@ pub struct SinkInfoResponse {
     pub path: String,
     pub enabled: bool,
     pub running: bool,
+    pub status: ConnectorStatus,
     pub config_format: Option<ConfigFormat>,
 }
 
@@ -49,6 +55,7 @@ pub struct SourceInfoResponse {
     pub path: String,
     pub enabled: bool,
     pub running: bool,
+    pub status: ConnectorStatus,
     pub config_format: Option<ConfigFormat>,
 }
 
@@ -65,30 +72,89 @@ pub struct TransformResponse {
     pub config: serde_json::Value,
 }
 
-impl From<SinkInfo> for SinkInfoResponse {
-    fn from(sink: SinkInfo) -> Self {
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConnectorHealthResponse {
+    pub status: ConnectorStatus,
+    pub messages: u64,
+    pub batches: u64,
+    pub errors: u64,
+    pub throughput: f64,
+    pub uptime_secs: u64,
+    pub last_error: Option<ConnectorError>,
+}
+
+impl From<(SinkInfo, ConnectorStatus)> for SinkInfoResponse {
+    fn from((sink, status): (SinkInfo, ConnectorStatus)) -> Self {
         SinkInfoResponse {
             id: sink.id,
             key: sink.key,
             name: sink.name,
             path: sink.path,
             enabled: sink.enabled,
-            running: sink.running,
+            running: status == ConnectorStatus::Running,
+            status,
             config_format: sink.config_format,
         }
     }
 }
 
-impl From<SourceInfo> for SourceInfoResponse {
-    fn from(source: SourceInfo) -> Self {
+impl From<(SourceInfo, ConnectorStatus)> for SourceInfoResponse {
+    fn from((source, status): (SourceInfo, ConnectorStatus)) -> Self {
         SourceInfoResponse {
             id: source.id,
             key: source.key,
             name: source.name,
             path: source.path,
             enabled: source.enabled,
-            running: source.running,
+            running: status == ConnectorStatus::Running,
+            status,
             config_format: source.config_format,
         }
     }
 }
+
+impl From<ConnectorHealthReport> for ConnectorHealthResponse {
+    fn from(report: ConnectorHealthReport) -> Self {
+        ConnectorHealthResponse {
+            status: report.status,
+            messages: report.messages,
+            batches: report.batches,
+            errors: report.errors,
+            throughput: report.throughput,
+            uptime_secs: report.uptime_secs,
+            last_error: report.last_error,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConfigChangesResponse {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub updated: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConfigReloadResponse {
+    pub sinks: ConfigChangesResponse,
+    pub sources: ConfigChangesResponse,
+}
+
+impl From<ConfigChanges> for ConfigChangesResponse {
+    fn from(changes: ConfigChanges) -> Self {
+        Self {
+            added: changes.added,
+            removed: changes.removed,
+            updated: changes.updated,
+        }
+    }
+}
+
+impl From<ReloadResult> for ConfigReloadResponse {
+    fn from(result: ReloadResult) -> Self {
+        Self {
+            sinks: result.sinks.into(),
+            sources: result.sources.into(),
+        }
+    }
+}
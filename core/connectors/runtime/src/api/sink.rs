@@ -19,15 +19,21 @@
 use super::{
     config::map_connector_config,
     error::ApiError,
-    models::{SinkDetailsResponse, SinkInfoResponse, TransformResponse},
+    models::{ConnectorHealthResponse, SinkDetailsResponse, SinkInfoResponse, TransformResponse},
+};
+use crate::{
+    configs::{ConfigFormat, TransformsConfig},
+    context::RuntimeContext,
+    error::RuntimeError,
+    manager::health::ConnectorStatus,
+    sink, transform,
 };
-use crate::{configs::ConfigFormat, context::RuntimeContext, error::RuntimeError};
 use axum::{
     Json, Router,
     extract::{Path, Query, State},
     http::{HeaderMap, StatusCode, header},
     response::IntoResponse,
-    routing::get,
+    routing::{get, post},
 };
 use serde::Deserialize;
 use std::sync::Arc;
@@ -36,8 +42,20 @@ pub fn router(state: Arc<RuntimeContext>) -> Router {
     Router::new()
         .route("/sinks", get(get_sinks))
         .route("/sinks/{key}", get(get_sink))
-        .route("/sinks/{key}/config", get(get_sink_config))
-        .route("/sinks/{key}/transforms", get(get_sink_transforms))
+        .route(
+            "/sinks/{key}/config",
+            get(get_sink_config).put(update_sink_config),
+        )
+        .route(
+            "/sinks/{key}/transforms",
+            get(get_sink_transforms).put(update_sink_transforms),
+        )
+        .route("/sinks/{key}/health", get(get_sink_health))
+        .route("/sinks/{key}/start", post(start_sink))
+        .route("/sinks/{key}/stop", post(stop_sink))
+        .route("/sinks/{key}/pause", post(pause_sink))
+        .route("/sinks/{key}/resume", post(resume_sink))
+        .route("/sinks/{key}/restart", post(restart_sink))
         .with_state(state)
 }
 
@@ -63,8 +81,8 @@ async fn get_sink(
     };
     let sink = sink.lock().await;
     Ok(Json(SinkDetailsResponse {
-        info: sink.info.clone().into(),
-        streams: sink.streams.to_vec(),
+        info: (sink.info.clone(), sink.health.status()).into(),
+        streams: sink.config.streams.to_vec(),
     }))
 }
 
@@ -77,7 +95,7 @@ async fn get_sink_config(
         return Err(ApiError::Error(RuntimeError::SinkNotFound(key)));
     };
     let sink = sink.lock().await;
-    let Some(config) = sink.config.as_ref() else {
+    let Some(config) = sink.config.config.as_ref() else {
         return Ok(StatusCode::NOT_FOUND.into_response());
     };
 
@@ -103,7 +121,7 @@ async fn get_sink_transforms(
         return Err(ApiError::Error(RuntimeError::SinkNotFound(key)));
     };
     let sink = sink.lock().await;
-    let Some(transforms) = sink.transforms.as_ref() else {
+    let Some(transforms) = sink.config.transforms.as_ref() else {
         return Ok(Json(vec![]));
     };
 
@@ -118,3 +136,107 @@ async fn get_sink_transforms(
             .collect(),
     ))
 }
+
+async fn get_sink_health(
+    State(context): State<Arc<RuntimeContext>>,
+    Path(key): Path<String>,
+) -> Result<Json<ConnectorHealthResponse>, ApiError> {
+    let Some(sink) = context.sinks.get(&key).await else {
+        return Err(ApiError::Error(RuntimeError::SinkNotFound(key)));
+    };
+    let sink = sink.lock().await;
+    Ok(Json(sink.health.report().into()))
+}
+
+async fn start_sink(
+    State(context): State<Arc<RuntimeContext>>,
+    Path(key): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let Some(sink) = context.sinks.get(&key).await else {
+        return Err(ApiError::Error(RuntimeError::SinkNotFound(key)));
+    };
+    let mut sink = sink.lock().await;
+    sink::start(&mut sink, &context.iggy_clients).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn stop_sink(
+    State(context): State<Arc<RuntimeContext>>,
+    Path(key): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let Some(sink) = context.sinks.get(&key).await else {
+        return Err(ApiError::Error(RuntimeError::SinkNotFound(key)));
+    };
+    let mut sink = sink.lock().await;
+    sink::stop(&mut sink).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn pause_sink(
+    State(context): State<Arc<RuntimeContext>>,
+    Path(key): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let Some(sink) = context.sinks.get(&key).await else {
+        return Err(ApiError::Error(RuntimeError::SinkNotFound(key)));
+    };
+    let sink = sink.lock().await;
+    sink::pause(&sink)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn resume_sink(
+    State(context): State<Arc<RuntimeContext>>,
+    Path(key): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let Some(sink) = context.sinks.get(&key).await else {
+        return Err(ApiError::Error(RuntimeError::SinkNotFound(key)));
+    };
+    let sink = sink.lock().await;
+    sink::resume(&sink)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn restart_sink(
+    State(context): State<Arc<RuntimeContext>>,
+    Path(key): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let Some(sink) = context.sinks.get(&key).await else {
+        return Err(ApiError::Error(RuntimeError::SinkNotFound(key)));
+    };
+    let mut sink = sink.lock().await;
+    sink::restart(&mut sink, &context.iggy_clients).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn update_sink_config(
+    State(context): State<Arc<RuntimeContext>>,
+    Path(key): Path<String>,
+    Json(config): Json<serde_json::Value>,
+) -> Result<StatusCode, ApiError> {
+    let Some(sink) = context.sinks.get(&key).await else {
+        return Err(ApiError::Error(RuntimeError::SinkNotFound(key)));
+    };
+    let mut sink = sink.lock().await;
+    sink.config.config = Some(config);
+    if sink.health.status() == ConnectorStatus::Running {
+        sink::restart(&mut sink, &context.iggy_clients).await?;
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn update_sink_transforms(
+    State(context): State<Arc<RuntimeContext>>,
+    Path(key): Path<String>,
+    Json(transforms): Json<TransformsConfig>,
+) -> Result<StatusCode, ApiError> {
+    let Some(sink) = context.sinks.get(&key).await else {
+        return Err(ApiError::Error(RuntimeError::SinkNotFound(key)));
+    };
+    transform::load(&transforms)?;
+    let mut sink = sink.lock().await;
+    sink.config.transforms = Some(transforms);
+    if sink.health.status() == ConnectorStatus::Running {
+        sink::restart(&mut sink, &context.iggy_clients).await?;
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
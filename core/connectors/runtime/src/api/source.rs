@@ -19,15 +19,23 @@
 use super::{
     config::map_connector_config,
     error::ApiError,
-    models::{SourceDetailsResponse, SourceInfoResponse, TransformResponse},
+    models::{
+        ConnectorHealthResponse, SourceDetailsResponse, SourceInfoResponse, TransformResponse,
+    },
+};
+use crate::{
+    configs::{ConfigFormat, TransformsConfig},
+    context::RuntimeContext,
+    error::RuntimeError,
+    manager::health::ConnectorStatus,
+    source, transform,
 };
-use crate::{configs::ConfigFormat, context::RuntimeContext, error::RuntimeError};
 use axum::{
     Json, Router,
     extract::{Path, Query, State},
     http::{HeaderMap, StatusCode, header},
     response::IntoResponse,
-    routing::get,
+    routing::{get, post},
 };
 use serde::Deserialize;
 use std::sync::Arc;
@@ -36,8 +44,20 @@ pub fn router(state: Arc<RuntimeContext>) -> Router {
     Router::new()
         .route("/sources", get(get_sources))
         .route("/sources/{key}", get(get_source))
-        .route("/sources/{key}/config", get(get_source_config))
-        .route("/sources/{key}/transforms", get(get_source_transforms))
+        .route(
+            "/sources/{key}/config",
+            get(get_source_config).put(update_source_config),
+        )
+        .route(
+            "/sources/{key}/transforms",
+            get(get_source_transforms).put(update_source_transforms),
+        )
+        .route("/sources/{key}/health", get(get_source_health))
+        .route("/sources/{key}/start", post(start_source))
+        .route("/sources/{key}/stop", post(stop_source))
+        .route("/sources/{key}/pause", post(pause_source))
+        .route("/sources/{key}/resume", post(resume_source))
+        .route("/sources/{key}/restart", post(restart_source))
         .with_state(state)
 }
 
@@ -63,8 +83,8 @@ async fn get_source(
     };
     let source = source.lock().await;
     Ok(Json(SourceDetailsResponse {
-        info: source.info.clone().into(),
-        streams: source.streams.to_vec(),
+        info: (source.info.clone(), source.health.status()).into(),
+        streams: source.config.streams.to_vec(),
     }))
 }
 
@@ -77,7 +97,7 @@ async fn get_source_config(
         return Err(ApiError::Error(RuntimeError::SourceNotFound(key)));
     };
     let source = source.lock().await;
-    let Some(config) = source.config.as_ref() else {
+    let Some(config) = source.config.config.as_ref() else {
         return Ok(StatusCode::NOT_FOUND.into_response());
     };
 
@@ -103,7 +123,7 @@ async fn get_source_transforms(
         return Err(ApiError::Error(RuntimeError::SourceNotFound(key)));
     };
     let source = source.lock().await;
-    let Some(transforms) = source.transforms.as_ref() else {
+    let Some(transforms) = source.config.transforms.as_ref() else {
         return Ok(Json(vec![]));
     };
 
@@ -118,3 +138,107 @@ async fn get_source_transforms(
             .collect(),
     ))
 }
+
+async fn get_source_health(
+    State(context): State<Arc<RuntimeContext>>,
+    Path(key): Path<String>,
+) -> Result<Json<ConnectorHealthResponse>, ApiError> {
+    let Some(source) = context.sources.get(&key).await else {
+        return Err(ApiError::Error(RuntimeError::SourceNotFound(key)));
+    };
+    let source = source.lock().await;
+    Ok(Json(source.health.report().into()))
+}
+
+async fn start_source(
+    State(context): State<Arc<RuntimeContext>>,
+    Path(key): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let Some(source) = context.sources.get(&key).await else {
+        return Err(ApiError::Error(RuntimeError::SourceNotFound(key)));
+    };
+    let mut source = source.lock().await;
+    source::start(&mut source, &context.iggy_clients, &context.state).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn stop_source(
+    State(context): State<Arc<RuntimeContext>>,
+    Path(key): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let Some(source) = context.sources.get(&key).await else {
+        return Err(ApiError::Error(RuntimeError::SourceNotFound(key)));
+    };
+    let mut source = source.lock().await;
+    source::stop(&mut source).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn pause_source(
+    State(context): State<Arc<RuntimeContext>>,
+    Path(key): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let Some(source) = context.sources.get(&key).await else {
+        return Err(ApiError::Error(RuntimeError::SourceNotFound(key)));
+    };
+    let mut source = source.lock().await;
+    source::pause(&mut source).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn resume_source(
+    State(context): State<Arc<RuntimeContext>>,
+    Path(key): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let Some(source) = context.sources.get(&key).await else {
+        return Err(ApiError::Error(RuntimeError::SourceNotFound(key)));
+    };
+    let mut source = source.lock().await;
+    source::resume(&mut source, &context.iggy_clients, &context.state).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn restart_source(
+    State(context): State<Arc<RuntimeContext>>,
+    Path(key): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let Some(source) = context.sources.get(&key).await else {
+        return Err(ApiError::Error(RuntimeError::SourceNotFound(key)));
+    };
+    let mut source = source.lock().await;
+    source::restart(&mut source, &context.iggy_clients, &context.state).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn update_source_config(
+    State(context): State<Arc<RuntimeContext>>,
+    Path(key): Path<String>,
+    Json(config): Json<serde_json::Value>,
+) -> Result<StatusCode, ApiError> {
+    let Some(source) = context.sources.get(&key).await else {
+        return Err(ApiError::Error(RuntimeError::SourceNotFound(key)));
+    };
+    let mut source = source.lock().await;
+    source.config.config = Some(config);
+    if source.health.status() == ConnectorStatus::Running {
+        source::restart(&mut source, &context.iggy_clients, &context.state).await?;
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn update_source_transforms(
+    State(context): State<Arc<RuntimeContext>>,
+    Path(key): Path<String>,
+    Json(transforms): Json<TransformsConfig>,
+) -> Result<StatusCode, ApiError> {
+    let Some(source) = context.sources.get(&key).await else {
+        return Err(ApiError::Error(RuntimeError::SourceNotFound(key)));
+    };
+    transform::load(&transforms)?;
+    let mut source = source.lock().await;
+    source.config.transforms = Some(transforms);
+    if source.health.status() == ConnectorStatus::Running {
+        source::restart(&mut source, &context.iggy_clients, &context.state).await?;
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
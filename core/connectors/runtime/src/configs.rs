@@ -16,7 +16,8 @@
  * under the License.
  */
 
-use crate::api::config::HttpApiConfig;
+use crate::{api::config::HttpApiConfig, error::RuntimeError};
+use config::{Config, Environment, File};
 use iggy_connector_sdk::{Schema, transforms::TransformType};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -45,6 +46,8 @@ pub struct RuntimeConfig {
     pub sinks: HashMap<String, SinkConfig>,
     pub sources: HashMap<String, SourceConfig>,
     pub state: StateConfig,
+    #[serde(default)]
+    pub reload: ReloadConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -62,10 +65,39 @@ pub struct SinkConfig {
     pub path: String,
     pub transforms: Option<TransformsConfig>,
     pub streams: Vec<StreamConsumerConfig>,
+    pub retry: Option<SinkRetryConfig>,
+    pub dead_letter: Option<DeadLetterConfig>,
     pub config_format: Option<ConfigFormat>,
     pub config: Option<serde_json::Value>,
 }
 
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize, Display,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum SinkFailureMode {
+    #[default]
+    #[strum(to_string = "skip")]
+    Skip,
+    #[strum(to_string = "pause")]
+    Pause,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SinkRetryConfig {
+    pub max_retries: Option<u32>,
+    pub initial_backoff: Option<String>,
+    pub max_backoff: Option<String>,
+    pub multiplier: Option<f64>,
+    pub on_failure: Option<SinkFailureMode>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadLetterConfig {
+    pub stream: String,
+    pub topic: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StreamConsumerConfig {
     pub stream: String,
@@ -110,4 +142,49 @@ pub struct SharedTransformConfig {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StateConfig {
     pub path: String,
+    #[serde(default)]
+    pub storage: StateStorageKind,
+    pub stream: Option<String>,
+    pub topic: Option<String>,
+    pub message_expiry: Option<String>,
+    pub max_topic_size: Option<String>,
+    pub refresh_interval: Option<String>,
+}
+
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize, Display,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum StateStorageKind {
+    #[default]
+    #[strum(to_string = "file")]
+    File,
+    #[strum(to_string = "iggy")]
+    Iggy,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReloadConfig {
+    pub watch: bool,
+    pub interval: String,
+}
+
+impl Default for ReloadConfig {
+    fn default() -> Self {
+        Self {
+            watch: true,
+            interval: "5s".to_owned(),
+        }
+    }
+}
+
+/// Loads the runtime config from the file (its extension is optional) and `IGGY_CONNECTORS_` environment variables.
+pub fn load(config_path: &str) -> Result<RuntimeConfig, RuntimeError> {
+    Config::builder()
+        .add_source(File::with_name(config_path))
+        .add_source(Environment::with_prefix("IGGY_CONNECTORS").separator("_"))
+        .build()
+        .and_then(|config| config.try_deserialize())
+        .map_err(|error| RuntimeError::InvalidConfiguration(error.to_string()))
 }
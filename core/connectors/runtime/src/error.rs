@@ -34,7 +34,7 @@ pub enum RuntimeError {
     ConnectorSdkError(#[from] iggy_connector_sdk::Error),
     #[error("Iggy client error")]
     IggyClient(#[from] iggy::prelude::ClientError),
-    #[error("Iggy error")]
+    #[error("Iggy error: {0}")]
     IggyError(#[from] iggy::prelude::IggyError),
     #[error("Missing Iggy credentials")]
     MissingIggyCredentials,
@@ -44,6 +44,20 @@ pub enum RuntimeError {
     SinkNotFound(String),
     #[error("Source not found with key: {0}")]
     SourceNotFound(String),
+    #[error("Sink connector with ID: {0} failed to consume messages, error code: {1}")]
+    SinkConsumeFailed(u32, i32),
+    #[error(
+        "Sink connector with ID: {0} could not send {1} rejected messages to the dead letter topic"
+    )]
+    SinkRejectedMessages(u32, usize),
+    #[error("Cannot load plugin: {0}")]
+    CannotLoadPlugin(String),
+    #[error("Cannot open connector with ID: {0}, error code: {1}")]
+    CannotOpenConnector(u32, i32),
+    #[error("Cannot get committed offset for sink connector with ID: {0}, error code: {1}")]
+    CannotGetCommittedOffset(u32, i32),
+    #[error("Invalid connector status: {0}")]
+    InvalidConnectorStatus(String),
     #[error("Cannot convert configuration")]
     CannotConvertConfiguration,
 }
@@ -55,6 +69,7 @@ impl RuntimeError {
             RuntimeError::SourceNotFound(_) => "source_not_found",
             RuntimeError::MissingIggyCredentials => "invalid_configuration",
             RuntimeError::InvalidConfiguration(_) => "invalid_configuration",
+            RuntimeError::InvalidConnectorStatus(_) => "invalid_connector_status",
             _ => "error",
         }
     }
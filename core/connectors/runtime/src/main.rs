@@ -16,23 +16,16 @@
  * under the License.
  */
 
-use config::{Config, Environment, File};
-use configs::{ConfigFormat, RuntimeConfig};
-use dlopen2::wrapper::{Container, WrapperApi};
+use dlopen2::wrapper::WrapperApi;
 use dotenvy::dotenv;
 use error::RuntimeError;
 use figlet_rs::FIGfont;
 use iggy::prelude::{Client, IggyConsumer, IggyProducer};
 use iggy_connector_sdk::{
-    StreamDecoder, StreamEncoder,
-    sink::ConsumeCallback,
-    source::{HandleCallback, SendCallback},
-    transforms::Transform,
+    StreamDecoder, StreamEncoder, source::SendCallback, transforms::Transform,
 };
 use mimalloc::MiMalloc;
-use state::StateStorage;
 use std::{
-    collections::HashMap,
     env,
     sync::{Arc, atomic::AtomicU32},
 };
@@ -44,6 +37,8 @@ pub(crate) mod configs;
 pub(crate) mod context;
 pub(crate) mod error;
 mod manager;
+mod metrics;
+mod reload;
 mod sink;
 mod source;
 mod state;
@@ -67,6 +62,7 @@ struct SourceApi {
         state_len: usize,
     ) -> i32,
     handle: extern "C" fn(id: u32, callback: SendCallback) -> i32,
+    commit: extern "C" fn(id: u32, state_ptr: *const u8, state_len: usize) -> i32,
     close: extern "C" fn(id: u32) -> i32,
 }
 
@@ -83,6 +79,13 @@ struct SinkApi {
         messages_ptr: *const u8,
         messages_len: usize,
     ) -> i32,
+    committed_offset: extern "C" fn(
+        id: u32,
+        topic_meta_ptr: *const u8,
+        topic_meta_len: usize,
+        partition_id: u32,
+        offset_ptr: *mut u64,
+    ) -> i32,
     close: extern "C" fn(id: u32) -> i32,
 }
 
@@ -111,69 +114,18 @@ async fn main() -> Result<(), RuntimeError> {
     let config_path =
         env::var("IGGY_CONNECTORS_CONFIG_PATH").unwrap_or_else(|_| "config".to_string());
     info!("Starting Iggy Connectors Runtime, loading configuration from: {config_path}...");
-    let builder = Config::builder()
-        .add_source(File::with_name(&config_path))
-        .add_source(Environment::with_prefix("IGGY_CONNECTORS").separator("_"));
-
-    let config: RuntimeConfig = builder
-        .build()
-        .expect("Failed to build runtime config")
-        .try_deserialize()
-        .expect("Failed to deserialize runtime config");
-
-    std::fs::create_dir_all(&config.state.path).expect("Failed to create state directory");
-
-    info!("State will be stored in: {}", config.state.path);
+    let config = configs::load(&config_path).expect("Failed to load runtime config");
 
     let iggy_clients = stream::init(config.iggy.clone()).await?;
-    let sources = source::init(
-        config.sources.clone(),
-        &iggy_clients.producer,
-        &config.state.path,
-    )
-    .await?;
-    let sinks = sink::init(config.sinks.clone(), &iggy_clients.consumer).await?;
-
-    let mut sink_wrappers = vec![];
-    let mut sink_with_plugins = HashMap::new();
-    for (key, sink) in sinks {
-        let plugin_ids = sink.plugins.iter().map(|plugin| plugin.id).collect();
-        sink_wrappers.push(SinkConnectorWrapper {
-            callback: sink.container.consume,
-            plugins: sink.plugins,
-        });
-        sink_with_plugins.insert(
-            key,
-            SinkWithPlugins {
-                container: sink.container,
-                plugin_ids,
-            },
-        );
-    }
-
-    let mut source_wrappers = vec![];
-    let mut source_with_plugins = HashMap::new();
-    for (key, source) in sources {
-        let plugin_ids = source.plugins.iter().map(|plugin| plugin.id).collect();
-        source_wrappers.push(SourceConnectorWrapper {
-            callback: source.container.handle,
-            plugins: source.plugins,
-        });
-        source_with_plugins.insert(
-            key,
-            SourceWithPlugins {
-                container: source.container,
-                plugin_ids,
-            },
-        );
-    }
-
-    let context = context::init(&config, &sink_wrappers, &source_wrappers);
+    state::init(&config.state, &iggy_clients).await?;
+    let sources = source::init(config.sources.clone(), &iggy_clients, &config.state).await;
+    let sinks = sink::init(config.sinks.clone(), &iggy_clients).await;
+    let http_api_config = config.http_api.clone();
+    let reload_config = config.reload.clone();
+    let context = context::init(config, config_path, sinks, sources, iggy_clients);
     let context = Arc::new(context);
-    api::init(&config.http_api, context).await;
-
-    source::handle(source_wrappers);
-    sink::consume(sink_wrappers);
+    api::init(&http_api_config, context.clone()).await;
+    reload::watch(context.clone(), &reload_config);
     info!("All sources and sinks spawned.");
 
     #[cfg(unix)]
@@ -195,24 +147,17 @@ async fn main() -> Result<(), RuntimeError> {
         }
     }
 
-    for (key, source) in source_with_plugins {
-        for id in source.plugin_ids {
-            info!("Closing source connector with ID: {id} for plugin: {key}");
-            source.container.close(id);
-            info!("Closed source connector with ID: {id} for plugin: {key}");
-        }
+    for source in context.sources.get_all_details().await {
+        let mut source = source.lock().await;
+        source::stop(&mut source).await;
     }
 
-    for (key, sink) in sink_with_plugins {
-        for id in sink.plugin_ids {
-            info!("Closing sink connector with ID: {id} for plugin: {key}",);
-            sink.container.close(id);
-            info!("Closed sink connector with ID: {id} for plugin: {key}");
-        }
+    for sink in context.sinks.get_all_details().await {
+        let mut sink = sink.lock().await;
+        sink::stop(&mut sink).await;
     }
 
-    iggy_clients.producer.shutdown().await?;
-    iggy_clients.consumer.shutdown().await?;
+    context.iggy_clients.producer.shutdown().await?;
 
     info!("All connectors closed. Runtime shutdown complete.");
     Ok(())
@@ -235,64 +180,16 @@ pub(crate) fn resolve_plugin_path(path: &str) -> String {
     }
 }
 
-struct SinkConnector {
-    container: Container<SinkApi>,
-    plugins: Vec<SinkConnectorPlugin>,
-}
-
-struct SinkConnectorPlugin {
-    id: u32,
-    key: String,
-    name: String,
-    path: String,
-    config_format: Option<ConfigFormat>,
-    consumers: Vec<SinkConnectorConsumer>,
-}
-
 struct SinkConnectorConsumer {
     batch_size: u32,
     consumer: IggyConsumer,
     decoder: Arc<dyn StreamDecoder>,
     transforms: Vec<Arc<dyn Transform>>,
-}
-
-struct SinkConnectorWrapper {
-    callback: ConsumeCallback,
-    plugins: Vec<SinkConnectorPlugin>,
-}
-
-struct SinkWithPlugins {
-    container: Container<SinkApi>,
-    plugin_ids: Vec<u32>,
-}
-
-struct SourceConnector {
-    container: Container<SourceApi>,
-    plugins: Vec<SourceConnectorPlugin>,
-}
-
-struct SourceConnectorPlugin {
-    id: u32,
-    key: String,
-    name: String,
-    path: String,
-    config_format: Option<ConfigFormat>,
-    transforms: Vec<Arc<dyn Transform>>,
-    producer: Option<SourceConnectorProducer>,
-    state_storage: StateStorage,
+    retry: sink::RetryPolicy,
+    dead_letter: Option<Arc<IggyProducer>>,
 }
 
 struct SourceConnectorProducer {
     encoder: Arc<dyn StreamEncoder>,
     producer: IggyProducer,
 }
-
-struct SourceWithPlugins {
-    container: Container<SourceApi>,
-    plugin_ids: Vec<u32>,
-}
-
-struct SourceConnectorWrapper {
-    callback: HandleCallback,
-    plugins: Vec<SourceConnectorPlugin>,
-}
@@ -0,0 +1,153 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use iggy::prelude::IggyTimestamp;
+use serde::{Deserialize, Serialize};
+use std::{
+    sync::{
+        Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Instant,
+};
+use strum::Display;
+use tokio::sync::watch;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectorStatus {
+    #[strum(to_string = "running")]
+    Running,
+    #[strum(to_string = "paused")]
+    Paused,
+    #[strum(to_string = "stopped")]
+    Stopped,
+    #[strum(to_string = "error")]
+    Error,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectorError {
+    pub message: String,
+    pub timestamp: u64,
+}
+
+/// The status and the statistics of the single connector, shared with its running tasks.
+#[derive(Debug)]
+pub struct ConnectorHealth {
+    status: watch::Sender<ConnectorStatus>,
+    messages: AtomicU64,
+    batches: AtomicU64,
+    errors: AtomicU64,
+    last_error: Mutex<Option<ConnectorError>>,
+    started_at: Mutex<Option<Instant>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConnectorHealthReport {
+    pub status: ConnectorStatus,
+    pub messages: u64,
+    pub batches: u64,
+    pub errors: u64,
+    pub throughput: f64,
+    pub uptime_secs: u64,
+    pub last_error: Option<ConnectorError>,
+}
+
+impl Default for ConnectorHealth {
+    fn default() -> Self {
+        Self {
+            status: watch::Sender::new(ConnectorStatus::Stopped),
+            messages: AtomicU64::new(0),
+            batches: AtomicU64::new(0),
+            errors: AtomicU64::new(0),
+            last_error: Mutex::new(None),
+            started_at: Mutex::new(None),
+        }
+    }
+}
+
+impl ConnectorHealth {
+    pub fn status(&self) -> ConnectorStatus {
+        *self.status.borrow()
+    }
+
+    pub fn set_status(&self, status: ConnectorStatus) {
+        self.status.send_replace(status);
+    }
+
+    pub fn subscribe(&self) -> watch::Receiver<ConnectorStatus> {
+        self.status.subscribe()
+    }
+
+    /// Resets the statistics once the connector is (re)started.
+    pub fn reset(&self) {
+        self.messages.store(0, Ordering::Relaxed);
+        self.batches.store(0, Ordering::Relaxed);
+        self.errors.store(0, Ordering::Relaxed);
+        self.started_at
+            .lock()
+            .expect("Failed to lock start time")
+            .replace(Instant::now());
+    }
+
+    pub fn record_batch(&self, messages: u64) {
+        self.messages.fetch_add(messages, Ordering::Relaxed);
+        self.batches.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_error(&self, error: &impl std::fmt::Display) {
+        self.errors.fetch_add(1, Ordering::Relaxed);
+        self.last_error
+            .lock()
+            .expect("Failed to lock last error")
+            .replace(ConnectorError {
+                message: error.to_string(),
+                timestamp: IggyTimestamp::now().as_micros(),
+            });
+    }
+
+    pub fn report(&self) -> ConnectorHealthReport {
+        let messages = self.messages.load(Ordering::Relaxed);
+        let uptime = self
+            .started_at
+            .lock()
+            .expect("Failed to lock start time")
+            .map(|started_at| started_at.elapsed())
+            .unwrap_or_default();
+        let throughput = if uptime.is_zero() {
+            0.0
+        } else {
+            messages as f64 / uptime.as_secs_f64()
+        };
+
+        ConnectorHealthReport {
+            status: self.status(),
+            messages,
+            batches: self.batches.load(Ordering::Relaxed),
+            errors: self.errors.load(Ordering::Relaxed),
+            throughput,
+            uptime_secs: uptime.as_secs(),
+            last_error: self
+                .last_error
+                .lock()
+                .expect("Failed to lock last error")
+                .clone(),
+        }
+    }
+}
@@ -16,5 +16,6 @@
  * under the License.
  */
 
+pub mod health;
 pub mod sink;
 pub mod source;
@@ -16,11 +16,17 @@
  * under the License.
  */
 
-use crate::configs::{ConfigFormat, StreamConsumerConfig, TransformsConfig};
+use crate::{
+    SinkApi,
+    configs::{ConfigFormat, SinkConfig},
+    manager::health::{ConnectorHealth, ConnectorStatus},
+    metrics::ConnectorMetrics,
+};
+use dlopen2::wrapper::Container;
+use iggy::prelude::IggyClient;
 use std::{collections::HashMap, sync::Arc};
-use tokio::sync::Mutex;
+use tokio::{sync::Mutex, task::JoinHandle};
 
-#[derive(Debug)]
 pub struct SinkManager {
     sinks: Mutex<HashMap<String, Arc<Mutex<SinkDetails>>>>,
 }
@@ -42,15 +48,30 @@ impl SinkManager {
         sinks.get(key).cloned()
     }
 
-    pub async fn get_all(&self) -> Vec<SinkInfo> {
+    pub async fn get_all(&self) -> Vec<(SinkInfo, ConnectorStatus)> {
         let sinks = self.sinks.lock().await;
         let mut results = Vec::with_capacity(sinks.len());
         for sink in sinks.values() {
             let sink = sink.lock().await;
-            results.push(sink.info.clone());
+            results.push((sink.info.clone(), sink.health.status()));
         }
         results
     }
+
+    pub async fn insert(&self, sink: SinkDetails) {
+        let mut sinks = self.sinks.lock().await;
+        sinks.insert(sink.info.key.to_owned(), Arc::new(Mutex::new(sink)));
+    }
+
+    pub async fn remove(&self, key: &str) -> Option<Arc<Mutex<SinkDetails>>> {
+        let mut sinks = self.sinks.lock().await;
+        sinks.remove(key)
+    }
+
+    pub async fn get_all_details(&self) -> Vec<Arc<Mutex<SinkDetails>>> {
+        let sinks = self.sinks.lock().await;
+        sinks.values().cloned().collect()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -60,14 +81,15 @@ pub struct SinkInfo {
     pub name: String,
     pub path: String,
     pub enabled: bool,
-    pub running: bool,
     pub config_format: Option<ConfigFormat>,
 }
 
-#[derive(Debug)]
 pub struct SinkDetails {
     pub info: SinkInfo,
-    pub transforms: Option<TransformsConfig>,
-    pub streams: Vec<StreamConsumerConfig>,
-    pub config: Option<serde_json::Value>,
+    pub config: SinkConfig,
+    pub health: Arc<ConnectorHealth>,
+    pub metrics: ConnectorMetrics,
+    pub container: Option<Arc<Container<SinkApi>>>,
+    pub client: Option<IggyClient>,
+    pub tasks: Vec<JoinHandle<()>>,
 }
@@ -16,11 +16,16 @@
  * under the License.
  */
 
-use crate::configs::{ConfigFormat, StreamProducerConfig, TransformsConfig};
+use crate::{
+    SourceApi,
+    configs::{ConfigFormat, SourceConfig},
+    manager::health::{ConnectorHealth, ConnectorStatus},
+    metrics::ConnectorMetrics,
+};
+use dlopen2::wrapper::Container;
 use std::{collections::HashMap, sync::Arc};
-use tokio::sync::Mutex;
+use tokio::{sync::Mutex, task::JoinHandle};
 
-#[derive(Debug)]
 pub struct SourceManager {
     sources: Mutex<HashMap<String, Arc<Mutex<SourceDetails>>>>,
 }
@@ -42,15 +47,30 @@ impl SourceManager {
         sources.get(key).cloned()
     }
 
-    pub async fn get_all(&self) -> Vec<SourceInfo> {
+    pub async fn get_all(&self) -> Vec<(SourceInfo, ConnectorStatus)> {
         let sources = self.sources.lock().await;
         let mut results = Vec::with_capacity(sources.len());
         for source in sources.values() {
             let source = source.lock().await;
-            results.push(source.info.clone());
+            results.push((source.info.clone(), source.health.status()));
         }
         results
     }
+
+    pub async fn insert(&self, source: SourceDetails) {
+        let mut sources = self.sources.lock().await;
+        sources.insert(source.info.key.to_owned(), Arc::new(Mutex::new(source)));
+    }
+
+    pub async fn remove(&self, key: &str) -> Option<Arc<Mutex<SourceDetails>>> {
+        let mut sources = self.sources.lock().await;
+        sources.remove(key)
+    }
+
+    pub async fn get_all_details(&self) -> Vec<Arc<Mutex<SourceDetails>>> {
+        let sources = self.sources.lock().await;
+        sources.values().cloned().collect()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -60,14 +80,14 @@ pub struct SourceInfo {
     pub name: String,
     pub path: String,
     pub enabled: bool,
-    pub running: bool,
     pub config_format: Option<ConfigFormat>,
 }
 
-#[derive(Debug)]
 pub struct SourceDetails {
     pub info: SourceInfo,
-    pub transforms: Option<TransformsConfig>,
-    pub streams: Vec<StreamProducerConfig>,
-    pub config: Option<serde_json::Value>,
+    pub config: SourceConfig,
+    pub health: Arc<ConnectorHealth>,
+    pub metrics: ConnectorMetrics,
+    pub container: Option<Arc<Container<SourceApi>>>,
+    pub tasks: Vec<JoinHandle<()>>,
 }
@@ -0,0 +1,225 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+use once_cell::sync::Lazy;
+use prometheus_client::{
+    encoding::{EncodeLabelSet, text::encode},
+    metrics::{
+        counter::Counter,
+        family::Family,
+        gauge::Gauge,
+        histogram::{Histogram, exponential_buckets},
+    },
+    registry::Registry,
+};
+use std::time::Duration;
+use tracing::error;
+
+pub static METRICS: Lazy<Metrics> = Lazy::new(Metrics::init);
+
+const SINK: &str = "sink";
+const SOURCE: &str = "source";
+
+type HistogramFamily<S> = Family<S, Histogram, fn() -> Histogram>;
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub struct ConnectorLabels {
+    connector_type: &'static str,
+    key: String,
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct PartitionLabels {
+    key: String,
+    stream: String,
+    topic: String,
+    partition_id: u32,
+}
+
+#[derive(Debug)]
+pub struct Metrics {
+    registry: Registry,
+    messages_received: Family<ConnectorLabels, Counter>,
+    messages_transformed: Family<ConnectorLabels, Counter>,
+    messages_filtered: Family<ConnectorLabels, Counter>,
+    messages_failed: Family<ConnectorLabels, Counter>,
+    messages_produced: Family<ConnectorLabels, Counter>,
+    batch_duration: HistogramFamily<ConnectorLabels>,
+    poll_duration: HistogramFamily<ConnectorLabels>,
+    consumer_lag: Family<PartitionLabels, Gauge>,
+    running: Family<ConnectorLabels, Gauge>,
+}
+
+impl Metrics {
+    fn init() -> Self {
+        let mut metrics = Metrics {
+            registry: Registry::with_prefix("iggy_connectors"),
+            messages_received: Family::default(),
+            messages_transformed: Family::default(),
+            messages_filtered: Family::default(),
+            messages_failed: Family::default(),
+            messages_produced: Family::default(),
+            batch_duration: Family::new_with_constructor(duration_histogram),
+            poll_duration: Family::new_with_constructor(duration_histogram),
+            consumer_lag: Family::default(),
+            running: Family::default(),
+        };
+
+        metrics.registry.register(
+            "messages_received",
+            "total count of messages received by the connector (polled by sink, or produced by source plugin)",
+            metrics.messages_received.clone(),
+        );
+        metrics.registry.register(
+            "messages_transformed",
+            "total count of messages which passed through the connector transforms",
+            metrics.messages_transformed.clone(),
+        );
+        metrics.registry.register(
+            "messages_filtered",
+            "total count of messages dropped by the connector transforms",
+            metrics.messages_filtered.clone(),
+        );
+        metrics.registry.register(
+            "messages_failed",
+            "total count of messages which failed to be processed by the connector",
+            metrics.messages_failed.clone(),
+        );
+        metrics.registry.register(
+            "messages_produced",
+            "total count of messages delivered by the connector (consumed by sink plugin, or sent by source)",
+            metrics.messages_produced.clone(),
+        );
+        metrics.registry.register(
+            "batch_duration_seconds",
+            "duration of processing the batch of messages by the connector",
+            metrics.batch_duration.clone(),
+        );
+        metrics.registry.register(
+            "source_poll_duration_seconds",
+            "duration of polling the batch of messages from the source plugin",
+            metrics.poll_duration.clone(),
+        );
+        metrics.registry.register(
+            "sink_consumer_lag",
+            "count of messages in the partition not yet consumed by the sink",
+            metrics.consumer_lag.clone(),
+        );
+        metrics.registry.register(
+            "running",
+            "whether the connector is running (1) or not (0)",
+            metrics.running.clone(),
+        );
+
+        metrics
+    }
+
+    pub fn get_formatted_output(&self) -> String {
+        let mut buffer = String::new();
+        if let Err(err) = encode(&mut buffer, &self.registry) {
+            error!("Failed to encode metrics: {}", err);
+        }
+        buffer
+    }
+}
+
+fn duration_histogram() -> Histogram {
+    Histogram::new(exponential_buckets(0.001, 2.0, 16))
+}
+
+/// The metrics of the single sink or source connector, labeled with its type and key.
+#[derive(Debug, Clone)]
+pub struct ConnectorMetrics {
+    labels: ConnectorLabels,
+}
+
+impl ConnectorMetrics {
+    pub fn sink(key: &str) -> Self {
+        Self {
+            labels: ConnectorLabels {
+                connector_type: SINK,
+                key: key.to_owned(),
+            },
+        }
+    }
+
+    pub fn source(key: &str) -> Self {
+        Self {
+            labels: ConnectorLabels {
+                connector_type: SOURCE,
+                key: key.to_owned(),
+            },
+        }
+    }
+
+    pub fn record_received(&self, count: usize) {
+        inc_by(&METRICS.messages_received, &self.labels, count);
+    }
+
+    pub fn record_transformed(&self, count: usize) {
+        inc_by(&METRICS.messages_transformed, &self.labels, count);
+    }
+
+    pub fn record_filtered(&self, count: usize) {
+        inc_by(&METRICS.messages_filtered, &self.labels, count);
+    }
+
+    pub fn record_failed(&self, count: usize) {
+        inc_by(&METRICS.messages_failed, &self.labels, count);
+    }
+
+    pub fn record_produced(&self, count: usize) {
+        inc_by(&METRICS.messages_produced, &self.labels, count);
+    }
+
+    pub fn record_batch_duration(&self, duration: Duration) {
+        METRICS
+            .batch_duration
+            .get_or_create(&self.labels)
+            .observe(duration.as_secs_f64());
+    }
+
+    pub fn record_poll_duration(&self, duration: Duration) {
+        METRICS
+            .poll_duration
+            .get_or_create(&self.labels)
+            .observe(duration.as_secs_f64());
+    }
+
+    pub fn set_consumer_lag(&self, stream: &str, topic: &str, partition_id: u32, lag: u64) {
+        let labels = PartitionLabels {
+            key: self.labels.key.to_owned(),
+            stream: stream.to_owned(),
+            topic: topic.to_owned(),
+            partition_id,
+        };
+        METRICS.consumer_lag.get_or_create(&labels).set(lag as i64);
+    }
+
+    pub fn set_running(&self, running: bool) {
+        METRICS
+            .running
+            .get_or_create(&self.labels)
+            .set(running as i64);
+    }
+}
+
+fn inc_by(family: &Family<ConnectorLabels, Counter>, labels: &ConnectorLabels, count: usize) {
+    if count > 0 {
+        family.get_or_create(labels).inc_by(count as u64);
+    }
+}
@@ -0,0 +1,283 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+use crate::{
+    configs::{self, ReloadConfig, SinkConfig, SourceConfig},
+    context::RuntimeContext,
+    error::RuntimeError,
+    sink, source,
+};
+use iggy::prelude::IggyDuration;
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+    time::SystemTime,
+};
+use tracing::{error, info, warn};
+
+const CONFIG_EXTENSIONS: [&str; 6] = ["toml", "json", "yaml", "yml", "json5", "ini"];
+
+#[derive(Debug, Default)]
+pub struct ConfigChanges {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub updated: Vec<String>,
+}
+
+impl ConfigChanges {
+    fn diff<T: Serialize>(current: &HashMap<String, T>, new: &HashMap<String, T>) -> Self {
+        let mut changes = ConfigChanges::default();
+        for (key, config) in new {
+            match current.get(key) {
+                None => changes.added.push(key.to_owned()),
+                Some(current_config) if !is_equal(current_config, config) => {
+                    changes.updated.push(key.to_owned())
+                }
+                _ => {}
+            }
+        }
+        changes.removed = current
+            .keys()
+            .filter(|key| !new.contains_key(*key))
+            .cloned()
+            .collect();
+        changes.added.sort();
+        changes.removed.sort();
+        changes.updated.sort();
+        changes
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ReloadResult {
+    pub sinks: ConfigChanges,
+    pub sources: ConfigChanges,
+}
+
+/// Loads the runtime config again and applies the changes of sinks and sources, only the added, removed or updated
+/// connectors are started or stopped, so the other ones keep running with their offsets and state untouched.
+pub async fn reload(context: &RuntimeContext) -> Result<ReloadResult, RuntimeError> {
+    let mut current = context.config.lock().await;
+    let config = configs::load(&context.config_path)?;
+    if !is_equal(&current.http_api, &config.http_api)
+        || !is_equal(&current.iggy, &config.iggy)
+        || !is_equal(&current.state, &config.state)
+        || !is_equal(&current.reload, &config.reload)
+    {
+        warn!(
+            "Changes of http_api, iggy, state or reload config sections require the runtime restart, they will not be applied."
+        );
+    }
+
+    let result = ReloadResult {
+        sinks: reload_sinks(context, &current.sinks, &config.sinks).await,
+        sources: reload_sources(context, &current.sources, &config.sources).await,
+    };
+    *current = config;
+    info!(
+        "Reloaded runtime config, sinks: {:?}, sources: {:?}",
+        result.sinks, result.sources
+    );
+    Ok(result)
+}
+
+/// Reloads the runtime config on SIGHUP, and whenever its file is modified, if watching is enabled.
+pub fn watch(context: Arc<RuntimeContext>, config: &ReloadConfig) {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{SignalKind, signal};
+        let mut sighup = signal(SignalKind::hangup()).expect("Failed to create SIGHUP signal");
+        let context = context.clone();
+        tokio::spawn(async move {
+            while sighup.recv().await.is_some() {
+                info!("Received SIGHUP. Reloading runtime config...");
+                if let Err(error) = reload(&context).await {
+                    error!("Failed to reload runtime config. {error}");
+                }
+            }
+        });
+    }
+
+    if !config.watch {
+        info!("Runtime config file watching is disabled.");
+        return;
+    }
+
+    let Some(path) = resolve_config_file(&context.config_path) else {
+        warn!(
+            "Runtime config file: {} was not found, it will not be watched.",
+            context.config_path
+        );
+        return;
+    };
+
+    let Ok(interval) = IggyDuration::from_str(&config.interval) else {
+        error!(
+            "Invalid runtime config watch interval: {}, the config file will not be watched.",
+            config.interval
+        );
+        return;
+    };
+
+    info!(
+        "Watching runtime config file: {} for changes every {interval}",
+        path.display()
+    );
+    tokio::spawn(async move {
+        let mut last_modified = get_modified_time(&path).await;
+        let mut interval = tokio::time::interval(interval.get_duration());
+        loop {
+            interval.tick().await;
+            let modified = get_modified_time(&path).await;
+            if modified == last_modified {
+                continue;
+            }
+
+            last_modified = modified;
+            info!(
+                "Runtime config file: {} has changed. Reloading runtime config...",
+                path.display()
+            );
+            if let Err(error) = reload(&context).await {
+                error!("Failed to reload runtime config. {error}");
+            }
+        }
+    });
+}
+
+async fn reload_sinks(
+    context: &RuntimeContext,
+    current: &HashMap<String, SinkConfig>,
+    configs: &HashMap<String, SinkConfig>,
+) -> ConfigChanges {
+    let changes = ConfigChanges::diff(current, configs);
+    for key in changes.removed.iter() {
+        if let Some(sink) = context.sinks.remove(key).await {
+            let mut sink = sink.lock().await;
+            sink::stop(&mut sink).await;
+            info!("Removed sink: {} ({key})", sink.info.name);
+        }
+    }
+
+    for key in changes.updated.iter() {
+        let config = configs[key].clone();
+        let Some(sink) = context.sinks.get(key).await else {
+            continue;
+        };
+
+        let mut sink = sink.lock().await;
+        if let Err(error) = sink::update(&mut sink, config, &context.iggy_clients).await {
+            error!("Failed to update sink: {} ({key}). {error}", sink.info.name);
+            continue;
+        }
+        info!("Updated sink: {} ({key})", sink.info.name);
+    }
+
+    for key in changes.added.iter() {
+        let mut sink = sink::create(key, configs[key].clone(), None);
+        if !sink.config.enabled {
+            warn!("Sink: {} is disabled ({key})", sink.info.name);
+        } else if let Err(error) = sink::start(&mut sink, &context.iggy_clients).await {
+            error!("Failed to start sink: {} ({key}). {error}", sink.info.name);
+        }
+        info!("Added sink: {} ({key})", sink.info.name);
+        context.sinks.insert(sink).await;
+    }
+
+    changes
+}
+
+async fn reload_sources(
+    context: &RuntimeContext,
+    current: &HashMap<String, SourceConfig>,
+    configs: &HashMap<String, SourceConfig>,
+) -> ConfigChanges {
+    let changes = ConfigChanges::diff(current, configs);
+    for key in changes.removed.iter() {
+        if let Some(source) = context.sources.remove(key).await {
+            let mut source = source.lock().await;
+            source::stop(&mut source).await;
+            info!("Removed source: {} ({key})", source.info.name);
+        }
+    }
+
+    for key in changes.updated.iter() {
+        let config = configs[key].clone();
+        let Some(source) = context.sources.get(key).await else {
+            continue;
+        };
+
+        let mut source = source.lock().await;
+        if let Err(error) =
+            source::update(&mut source, config, &context.iggy_clients, &context.state).await
+        {
+            error!(
+                "Failed to update source: {} ({key}). {error}",
+                source.info.name
+            );
+            continue;
+        }
+        info!("Updated source: {} ({key})", source.info.name);
+    }
+
+    for key in changes.added.iter() {
+        let mut source = source::create(key, configs[key].clone(), None);
+        if !source.config.enabled {
+            warn!("Source: {} is disabled ({key})", source.info.name);
+        } else if let Err(error) =
+            source::start(&mut source, &context.iggy_clients, &context.state).await
+        {
+            error!(
+                "Failed to start source: {} ({key}). {error}",
+                source.info.name
+            );
+        }
+        info!("Added source: {} ({key})", source.info.name);
+        context.sources.insert(source).await;
+    }
+
+    changes
+}
+
+fn is_equal<T: Serialize>(first: &T, second: &T) -> bool {
+    match (serde_json::to_value(first), serde_json::to_value(second)) {
+        (Ok(first), Ok(second)) => first == second,
+        _ => false,
+    }
+}
+
+fn resolve_config_file(config_path: &str) -> Option<PathBuf> {
+    let path = PathBuf::from(config_path);
+    if path.is_file() {
+        return Some(path);
+    }
+
+    CONFIG_EXTENSIONS
+        .iter()
+        .map(|extension| PathBuf::from(format!("{config_path}.{extension}")))
+        .find(|path| path.is_file())
+}
+
+async fn get_modified_time(path: &Path) -> Option<SystemTime> {
+    tokio::fs::metadata(path)
+        .await
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
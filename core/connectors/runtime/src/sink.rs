@@ -17,14 +17,22 @@
  */
 
 use crate::{
-    PLUGIN_ID, RuntimeError, SinkApi, SinkConnector, SinkConnectorConsumer, SinkConnectorPlugin,
-    SinkConnectorWrapper, configs::SinkConfig, resolve_plugin_path, transform,
+    PLUGIN_ID, RuntimeError, SinkApi, SinkConnectorConsumer,
+    configs::{SinkConfig, SinkFailureMode, SinkRetryConfig},
+    manager::{
+        health::{ConnectorHealth, ConnectorStatus},
+        sink::{SinkDetails, SinkInfo},
+    },
+    metrics::ConnectorMetrics,
+    resolve_plugin_path,
+    stream::IggyClients,
+    transform,
 };
 use dlopen2::wrapper::Container;
 use futures::StreamExt;
 use iggy::prelude::{
-    AutoCommit, AutoCommitWhen, IggyClient, IggyConsumer, IggyDuration, IggyMessage,
-    PollingStrategy,
+    AutoCommit, Client, HeaderKey, HeaderValue, IggyClient, IggyConsumer, IggyDuration,
+    IggyMessage, IggyProducer, PollingStrategy, TopicClient,
 };
 use iggy_connector_sdk::{
     DecodedMessage, MessagesMetadata, RawMessage, RawMessages, ReceivedMessage, StreamDecoder,
@@ -34,256 +42,973 @@ use std::{
     collections::HashMap,
     str::FromStr,
     sync::{Arc, atomic::Ordering},
-    time::Instant,
+    time::{Duration, Instant},
 };
+use tokio::sync::watch;
 use tracing::{error, info, warn};
 
+const DEFAULT_MAX_RETRIES: u32 = 3;
+const DEFAULT_INITIAL_BACKOFF: &str = "100ms";
+const DEFAULT_MAX_BACKOFF: &str = "10s";
+const DEFAULT_BACKOFF_MULTIPLIER: f64 = 2.0;
+const DEAD_LETTER_ERROR_HEADER: &str = "iggy_dlq_error";
+const DEAD_LETTER_STREAM_HEADER: &str = "iggy_dlq_stream";
+const DEAD_LETTER_TOPIC_HEADER: &str = "iggy_dlq_topic";
+const DEAD_LETTER_PARTITION_ID_HEADER: &str = "iggy_dlq_partition_id";
+const DEAD_LETTER_OFFSET_HEADER: &str = "iggy_dlq_offset";
+const DEAD_LETTER_CONNECTOR_ID_HEADER: &str = "iggy_dlq_connector_id";
+const MAX_HEADER_VALUE_LENGTH: usize = 255;
+const UNKNOWN_HEADER_VALUE: &str = "unknown";
+
 pub async fn init(
     sink_configs: HashMap<String, SinkConfig>,
-    iggy_client: &IggyClient,
-) -> Result<HashMap<String, SinkConnector>, RuntimeError> {
-    let mut sink_connectors: HashMap<String, SinkConnector> = HashMap::new();
+    iggy_clients: &IggyClients,
+) -> Vec<SinkDetails> {
+    let mut containers: HashMap<String, Arc<Container<SinkApi>>> = HashMap::new();
+    let mut sinks = Vec::with_capacity(sink_configs.len());
     for (key, config) in sink_configs {
-        let name = config.name;
-        if !config.enabled {
-            warn!("Sink: {name} is disabled ({key})");
+        let path = resolve_plugin_path(&config.path);
+        let mut sink = create(&key, config, containers.get(&path).cloned());
+
+        if !sink.config.enabled {
+            warn!("Sink: {} is disabled ({key})", sink.info.name);
+            sinks.push(sink);
             continue;
         }
 
-        let plugin_id = PLUGIN_ID.load(Ordering::Relaxed);
-        let path = resolve_plugin_path(&config.path);
-        info!("Initializing sink container with name: {name} ({key}), plugin: {path}",);
-        if let Some(container) = sink_connectors.get_mut(&path) {
-            info!("Sink container for plugin: {path} is already loaded.",);
-            init_sink(
-                &container.container,
-                &config.config.unwrap_or_default(),
-                plugin_id,
-            );
-            container.plugins.push(SinkConnectorPlugin {
-                id: plugin_id,
-                key: key.to_owned(),
-                name: name.to_owned(),
-                path: path.to_owned(),
-                config_format: config.config_format,
-                consumers: vec![],
-            });
-        } else {
-            let container: Container<SinkApi> =
-                unsafe { Container::load(&path).expect("Failed to load sink container") };
-            info!("Sink container for plugin: {path} loaded successfully.",);
-            init_sink(&container, &config.config.unwrap_or_default(), plugin_id);
-            sink_connectors.insert(
-                path.to_owned(),
-                SinkConnector {
-                    container,
-                    plugins: vec![SinkConnectorPlugin {
-                        id: plugin_id,
-                        key: key.to_owned(),
-                        name: name.to_owned(),
-                        path: path.to_owned(),
-                        config_format: config.config_format,
-                        consumers: vec![],
-                    }],
-                },
+        if let Err(error) = start(&mut sink, iggy_clients).await {
+            error!(
+                "Failed to start sink with name: {} ({key}). {error}",
+                sink.info.name
             );
         }
 
-        info!(
-            "Sink container with name: {name} ({key}), initialized successfully with ID: {plugin_id}."
-        );
-        PLUGIN_ID.fetch_add(1, Ordering::Relaxed);
-
-        let transforms = if let Some(transforms_config) = config.transforms {
-            let transforms =
-                transform::load(&transforms_config).expect("Failed to load transforms");
-            let types = transforms
-                .iter()
-                .map(|t| t.r#type().into())
-                .collect::<Vec<&'static str>>()
-                .join(", ");
-            info!("Enabled transforms for sink: {name} ({key}): {types}",);
-            transforms
-        } else {
-            vec![]
+        if let Some(container) = sink.container.as_ref() {
+            containers.insert(path, container.clone());
+        }
+        sinks.push(sink);
+    }
+
+    sinks
+}
+
+/// Creates the stopped sink with the newly allocated plugin ID, the container is loaded on start if not provided.
+pub fn create(
+    key: &str,
+    config: SinkConfig,
+    container: Option<Arc<Container<SinkApi>>>,
+) -> SinkDetails {
+    let plugin_id = PLUGIN_ID.fetch_add(1, Ordering::Relaxed);
+    let path = resolve_plugin_path(&config.path);
+    SinkDetails {
+        info: SinkInfo {
+            id: plugin_id,
+            key: key.to_owned(),
+            name: config.name.to_owned(),
+            path,
+            enabled: config.enabled,
+            config_format: config.config_format,
+        },
+        container,
+        config,
+        health: Arc::new(ConnectorHealth::default()),
+        metrics: ConnectorMetrics::sink(key),
+        client: None,
+        tasks: vec![],
+    }
+}
+
+/// Stops the sink, applies the new config and starts it again if it's enabled.
+pub async fn update(
+    sink: &mut SinkDetails,
+    config: SinkConfig,
+    iggy_clients: &IggyClients,
+) -> Result<(), RuntimeError> {
+    stop(sink).await;
+    let path = resolve_plugin_path(&config.path);
+    if sink.info.path != path {
+        sink.container = None;
+    }
+    sink.info.name = config.name.to_owned();
+    sink.info.path = path;
+    sink.info.enabled = config.enabled;
+    sink.info.config_format = config.config_format;
+    sink.config = config;
+    if !sink.config.enabled {
+        warn!("Sink: {} is disabled ({})", sink.info.name, sink.info.key);
+        return Ok(());
+    }
+
+    start(sink, iggy_clients).await
+}
+
+/// Opens the sink plugin and starts consuming the messages from all its configured streams and topics.
+pub async fn start(sink: &mut SinkDetails, iggy_clients: &IggyClients) -> Result<(), RuntimeError> {
+    let status = sink.health.status();
+    if status == ConnectorStatus::Running || status == ConnectorStatus::Paused {
+        return Err(RuntimeError::InvalidConnectorStatus(format!(
+            "sink: {} is already {status}",
+            sink.info.key
+        )));
+    }
+
+    let plugin_id = sink.info.id;
+    let key = &sink.info.key;
+    let name = &sink.info.name;
+    let path = &sink.info.path;
+    info!("Initializing sink container with name: {name} ({key}), plugin: {path}",);
+    let container = if let Some(container) = sink.container.as_ref() {
+        info!("Sink container for plugin: {path} is already loaded.",);
+        container.clone()
+    } else {
+        let container: Container<SinkApi> = unsafe {
+            Container::load(path).map_err(|error| {
+                error!("Failed to load sink container for plugin: {path}. {error}");
+                RuntimeError::CannotLoadPlugin(path.to_owned())
+            })?
         };
+        info!("Sink container for plugin: {path} loaded successfully.",);
+        let container = Arc::new(container);
+        sink.container = Some(container.clone());
+        container
+    };
 
-        let connector = sink_connectors
-            .get_mut(&path)
-            .expect("Failed to get sink connector");
-        let plugin = connector
-            .plugins
-            .iter_mut()
-            .find(|p| p.id == plugin_id)
-            .expect("Failed to get sink plugin");
-
-        for stream in config.streams.iter() {
-            let poll_interval =
-                IggyDuration::from_str(stream.poll_interval.as_deref().unwrap_or("5ms"))
-                    .expect("Invalid poll interval");
-            let default_consumer_group = format!("iggy-connect-sink-{key}");
-            let consumer_group = stream
-                .consumer_group
-                .as_deref()
-                .unwrap_or(&default_consumer_group);
-            let batch_length = stream.batch_length.unwrap_or(1000);
-            for topic in stream.topics.iter() {
-                let mut consumer = iggy_client
-                    .consumer_group(consumer_group, &stream.stream, topic)?
-                    .auto_commit(AutoCommit::When(AutoCommitWhen::PollingMessages))
-                    .create_consumer_group_if_not_exists()
-                    .auto_join_consumer_group()
-                    .polling_strategy(PollingStrategy::next())
-                    .poll_interval(poll_interval)
-                    .batch_length(batch_length)
-                    .build();
-
-                consumer.init().await?;
-                plugin.consumers.push(SinkConnectorConsumer {
-                    consumer,
-                    decoder: stream.schema.decoder(),
-                    batch_size: batch_length,
-                    transforms: transforms.clone(),
-                });
+    let result = match init_sink(
+        &container,
+        sink.config
+            .config
+            .as_ref()
+            .unwrap_or(&serde_json::Value::Null),
+        plugin_id,
+    ) {
+        Ok(()) => create_consumers(&container, &sink.info, &sink.config, iggy_clients).await,
+        Err(error) => Err(error),
+    };
+    let (client, consumers) = match result {
+        Ok(result) => result,
+        Err(error) => {
+            (container.close)(plugin_id);
+            sink.health.record_error(&error);
+            sink.health.set_status(ConnectorStatus::Error);
+            return Err(error);
+        }
+    };
+
+    info!(
+        "Sink container with name: {name} ({key}), initialized successfully with ID: {plugin_id}."
+    );
+    sink.client = Some(client);
+    sink.health.reset();
+    sink.health.set_status(ConnectorStatus::Running);
+    info!("Starting consume for sink with ID: {plugin_id}...");
+    for consumer in consumers {
+        let callback = container.consume;
+        let health = sink.health.clone();
+        let metrics = sink.metrics.clone();
+        sink.tasks.push(tokio::spawn(async move {
+            if let Err(error) =
+                consume_messages(plugin_id, callback, consumer, &health, metrics).await
+            {
+                error!(
+                    "Failed to consume messages for sink connector with ID: {plugin_id}. {error}"
+                );
+                health.record_error(&error);
+                health.set_status(ConnectorStatus::Error);
             }
+        }));
+    }
+    info!("Consume messages for sink connector with ID: {plugin_id} started successfully.");
+    Ok(())
+}
+
+/// Stops consuming the messages (the already polled ones are handled first) and closes the sink plugin.
+pub async fn stop(sink: &mut SinkDetails) {
+    if sink.health.status() == ConnectorStatus::Stopped {
+        return;
+    }
+
+    let plugin_id = sink.info.id;
+    sink.health.set_status(ConnectorStatus::Stopped);
+    for task in sink.tasks.drain(..) {
+        if let Err(error) = task.await {
+            error!(
+                "Failed to stop consuming messages for sink connector with ID: {plugin_id}. {error}"
+            );
         }
     }
 
-    Ok(sink_connectors)
+    let client_shutdown = match sink.client.take() {
+        Some(client) => client.shutdown().await,
+        None => Ok(()),
+    };
+    if let Err(error) = client_shutdown {
+        warn!("Failed to shutdown the client for sink connector with ID: {plugin_id}. {error}");
+    }
+
+    if let Some(container) = sink.container.as_ref() {
+        info!(
+            "Closing sink connector with ID: {plugin_id} for plugin: {}",
+            sink.info.path
+        );
+        (container.close)(plugin_id);
+        info!(
+            "Closed sink connector with ID: {plugin_id} for plugin: {}",
+            sink.info.path
+        );
+    }
 }
 
-pub fn consume(sinks: Vec<SinkConnectorWrapper>) {
-    for sink in sinks {
-        for plugin in sink.plugins {
-            info!("Starting consume for sink with ID: {}...", plugin.id);
-            for consumer in plugin.consumers {
-                tokio::spawn(async move {
-                    if let Err(error) = consume_messages(
-                        plugin.id,
-                        consumer.decoder,
-                        consumer.batch_size,
-                        sink.callback,
-                        consumer.transforms,
-                        consumer.consumer,
-                    )
-                    .await
-                    {
-                        error!(
-                            "Failed to consume messages for sink connector with ID: {}. {error}",
-                            plugin.id
-                        );
-                        return;
-                    }
-                    info!(
-                        "Consume messages for sink connector with ID: {} started successfully.",
-                        plugin.id
-                    );
-                });
-            }
+pub async fn restart(
+    sink: &mut SinkDetails,
+    iggy_clients: &IggyClients,
+) -> Result<(), RuntimeError> {
+    stop(sink).await;
+    start(sink, iggy_clients).await
+}
+
+pub fn pause(sink: &SinkDetails) -> Result<(), RuntimeError> {
+    let status = sink.health.status();
+    if status != ConnectorStatus::Running {
+        return Err(RuntimeError::InvalidConnectorStatus(format!(
+            "sink: {} cannot be paused when {status}",
+            sink.info.key
+        )));
+    }
+
+    sink.health.set_status(ConnectorStatus::Paused);
+    info!("Paused sink connector with ID: {}", sink.info.id);
+    Ok(())
+}
+
+pub fn resume(sink: &SinkDetails) -> Result<(), RuntimeError> {
+    let status = sink.health.status();
+    if status != ConnectorStatus::Paused {
+        return Err(RuntimeError::InvalidConnectorStatus(format!(
+            "sink: {} cannot be resumed when {status}",
+            sink.info.key
+        )));
+    }
+
+    sink.health.set_status(ConnectorStatus::Running);
+    info!("Resumed sink connector with ID: {}", sink.info.id);
+    Ok(())
+}
+
+async fn create_consumers(
+    container: &Container<SinkApi>,
+    info: &SinkInfo,
+    config: &SinkConfig,
+    iggy_clients: &IggyClients,
+) -> Result<(IggyClient, Vec<SinkConnectorConsumer>), RuntimeError> {
+    let key = &info.key;
+    let name = &info.name;
+    let transforms = if let Some(transforms_config) = config.transforms.as_ref() {
+        let transforms = transform::load(transforms_config)?;
+        let types = transforms
+            .iter()
+            .map(|t| t.r#type().into())
+            .collect::<Vec<&'static str>>()
+            .join(", ");
+        info!("Enabled transforms for sink: {name} ({key}): {types}",);
+        transforms
+    } else {
+        vec![]
+    };
+
+    let retry = RetryPolicy::from_config(config.retry.as_ref())?;
+    info!(
+        "Sink: {name} ({key}) will retry failed batches up to {} times, on failure: {}",
+        retry.max_retries, retry.on_failure
+    );
+
+    let dead_letter = if let Some(dead_letter) = config.dead_letter.as_ref() {
+        let producer = iggy_clients
+            .producer
+            .producer(&dead_letter.stream, &dead_letter.topic)?
+            .build();
+        producer.init().await?;
+        info!(
+            "Dead letter topic for sink: {name} ({key}): {}/{}",
+            dead_letter.stream, dead_letter.topic
+        );
+        Some(Arc::new(producer))
+    } else {
+        None
+    };
+
+    let client = iggy_clients.create_consumer_client().await?;
+    let mut consumers = vec![];
+    for stream in config.streams.iter() {
+        let poll_interval =
+            IggyDuration::from_str(stream.poll_interval.as_deref().unwrap_or("5ms"))
+                .map_err(|_| RuntimeError::InvalidConfiguration("poll_interval".to_owned()))?;
+        let default_consumer_group = format!("iggy-connect-sink-{key}");
+        let consumer_group = stream
+            .consumer_group
+            .as_deref()
+            .unwrap_or(&default_consumer_group);
+        let batch_length = stream.batch_length.unwrap_or(1000);
+        for topic in stream.topics.iter() {
+            // Offsets are stored only after the messages were handled by the sink, so that they're never committed before being written.
+            let mut consumer = client
+                .consumer_group(consumer_group, &stream.stream, topic)?
+                .auto_commit(AutoCommit::Disabled)
+                .create_consumer_group_if_not_exists()
+                .auto_join_consumer_group()
+                .polling_strategy(PollingStrategy::next())
+                .poll_interval(poll_interval)
+                .batch_length(batch_length)
+                .build();
+
+            consumer.init().await?;
+            resume_from_committed_offsets(container, info.id, &client, &consumer).await?;
+            consumers.push(SinkConnectorConsumer {
+                consumer,
+                decoder: stream.schema.decoder(),
+                batch_size: batch_length,
+                transforms: transforms.clone(),
+                retry: retry.clone(),
+                dead_letter: dead_letter.clone(),
+            });
         }
     }
+
+    Ok((client, consumers))
 }
 
 async fn consume_messages(
     plugin_id: u32,
-    decoder: Arc<dyn StreamDecoder>,
-    batch_size: u32,
     consume: ConsumeCallback,
-    transforms: Vec<Arc<dyn Transform>>,
-    mut consumer: IggyConsumer,
+    consumer: SinkConnectorConsumer,
+    health: &Arc<ConnectorHealth>,
+    metrics: ConnectorMetrics,
 ) -> Result<(), RuntimeError> {
+    let SinkConnectorConsumer {
+        batch_size,
+        mut consumer,
+        decoder,
+        transforms,
+        retry,
+        dead_letter,
+    } = consumer;
     info!("Started consuming messages for sink connector with ID: {plugin_id}");
-    let batch_size = batch_size as usize;
-    let mut batch = Vec::with_capacity(batch_size);
-    let topic_metadata = TopicMetadata {
-        stream: consumer.stream().to_string(),
-        topic: consumer.topic().to_string(),
+    let processor = BatchProcessor {
+        plugin_id,
+        consume,
+        topic_metadata: TopicMetadata {
+            stream: consumer.stream().to_string(),
+            topic: consumer.topic().to_string(),
+        },
+        decoder,
+        transforms,
+        retry,
+        dead_letter,
+        health: health.clone(),
+        metrics,
     };
+    let mut batcher = Batcher::new(batch_size as usize);
+    let mut status = health.subscribe();
+
+    'consume: loop {
+        if !wait_for_resume(&mut status).await {
+            break;
+        }
+
+        let message = tokio::select! {
+            message = consumer.next() => message,
+            _ = status.changed() => continue,
+        };
+
+        let Some(message) = message else {
+            break;
+        };
 
-    while let Some(message) = consumer.next().await {
         let Ok(message) = message else {
             error!("Failed to receive message.");
             continue;
         };
 
-        let partition_id = message.partition_id;
-        let current_offset = message.current_offset;
-        let message_offset = message.message.header.offset;
-        batch.push(message.message);
-        if current_offset != message_offset && batch.len() < batch_size {
-            continue;
+        for batch in batcher.push(
+            message.partition_id,
+            message.current_offset,
+            message.message,
+        ) {
+            if let Some(last_message) = batch.messages.last() {
+                processor.metrics.set_consumer_lag(
+                    &processor.topic_metadata.stream,
+                    &processor.topic_metadata.topic,
+                    batch.partition_id,
+                    batch
+                        .current_offset
+                        .saturating_sub(last_message.header.offset),
+                );
+            }
+
+            if !processor.process(&consumer, &mut status, batch).await {
+                break 'consume;
+            }
         }
+    }
 
-        let messages = std::mem::take(&mut batch);
-        let messages_count = messages.len();
-        let messages_metadata = MessagesMetadata {
+    if let Some(batch) = batcher.take() {
+        // The messages have already been polled, so they're handled before the sink is closed.
+        processor.process(&consumer, &mut status, batch).await;
+    }
+
+    info!("Stopped consuming messages for sink connector with ID: {plugin_id}");
+    Ok(())
+}
+
+/// The messages polled from a single partition, so that their offset is always stored for the partition they belong to.
+#[derive(Debug)]
+struct PartitionBatch {
+    partition_id: u32,
+    current_offset: u64,
+    messages: Vec<IggyMessage>,
+}
+
+/// Accumulates the polled messages into the batches, each of them holding the messages of a single partition,
+/// as the consumer in the consumer group might poll the messages from several partitions one after another.
+#[derive(Debug)]
+struct Batcher {
+    batch_size: usize,
+    batch: Option<PartitionBatch>,
+}
+
+impl Batcher {
+    fn new(batch_size: usize) -> Self {
+        Batcher {
+            batch_size,
+            batch: None,
+        }
+    }
+
+    /// Adds the polled message, and returns the batches ready to be processed: the pending one, if the message belongs
+    /// to another partition, and the current one, once it's full or the message is the last one available in its partition.
+    fn push(
+        &mut self,
+        partition_id: u32,
+        current_offset: u64,
+        message: IggyMessage,
+    ) -> Vec<PartitionBatch> {
+        let mut ready = Vec::new();
+        if self
+            .batch
+            .as_ref()
+            .is_some_and(|batch| batch.partition_id != partition_id)
+        {
+            ready.extend(self.batch.take());
+        }
+
+        let batch_size = self.batch_size;
+        let message_offset = message.header.offset;
+        let batch = self.batch.get_or_insert_with(|| PartitionBatch {
             partition_id,
             current_offset,
-            schema: decoder.schema(),
-        };
-        info!(
-            "Processing {messages_count} messages for sink connector with ID: {}",
-            plugin_id
-        );
+            messages: Vec::with_capacity(batch_size),
+        });
+        batch.current_offset = current_offset;
+        batch.messages.push(message);
+        if current_offset == message_offset || batch.messages.len() >= batch_size {
+            ready.extend(self.batch.take());
+        }
+        ready
+    }
+
+    fn take(&mut self) -> Option<PartitionBatch> {
+        self.batch.take()
+    }
+}
+
+/// Waits until the sink is running again, returns `false` if it was stopped in the meantime.
+async fn wait_for_resume(status: &mut watch::Receiver<ConnectorStatus>) -> bool {
+    loop {
+        match *status.borrow_and_update() {
+            ConnectorStatus::Running => return true,
+            ConnectorStatus::Paused => {}
+            ConnectorStatus::Stopped | ConnectorStatus::Error => return false,
+        }
+
+        if status.changed().await.is_err() {
+            return false;
+        }
+    }
+}
+
+struct BatchProcessor {
+    plugin_id: u32,
+    consume: ConsumeCallback,
+    topic_metadata: TopicMetadata,
+    decoder: Arc<dyn StreamDecoder>,
+    transforms: Vec<Arc<dyn Transform>>,
+    retry: RetryPolicy,
+    dead_letter: Option<Arc<IggyProducer>>,
+    health: Arc<ConnectorHealth>,
+    metrics: ConnectorMetrics,
+}
+
+impl BatchProcessor {
+    /// Processes the batch of messages, returns `false` if the sink was stopped while paused after a failure.
+    /// The retries, dead letters and the stored offset all refer to the single partition of the batch.
+    async fn process(
+        &self,
+        consumer: &IggyConsumer,
+        status: &mut watch::Receiver<ConnectorStatus>,
+        batch: PartitionBatch,
+    ) -> bool {
+        let plugin_id = self.plugin_id;
+        let partition_id = batch.partition_id;
+        let messages_count = batch.messages.len();
+        info!("Processing {messages_count} messages for sink connector with ID: {plugin_id}");
+        self.metrics.record_received(messages_count);
         let start = Instant::now();
-        if let Err(error) = process_messages(
-            plugin_id,
-            messages_metadata,
-            &topic_metadata,
-            messages,
-            &consume,
-            &transforms,
-            &decoder,
-        )
-        .await
-        {
+        while let Err(error) = self.try_process(consumer, &batch).await {
             error!(
                 "Failed to process {messages_count} messages for sink connector with ID: {plugin_id}. {error}",
             );
-            continue;
+            error!(
+                "Pausing sink connector with ID: {plugin_id}, offsets for stream: {}, topic: {}, partition: {partition_id} will not be committed.",
+                self.topic_metadata.stream, self.topic_metadata.topic
+            );
+            self.metrics.record_failed(messages_count);
+            self.health.record_error(&error);
+            self.health.set_status(ConnectorStatus::Paused);
+            if !wait_for_resume(status).await {
+                return false;
+            }
+
+            info!(
+                "Retrying {messages_count} messages for resumed sink connector with ID: {plugin_id}"
+            );
         }
 
+        self.health.record_batch(messages_count as u64);
         let elapsed = start.elapsed();
+        self.metrics.record_batch_duration(elapsed);
         info!(
             "Consumed {messages_count} messages in {:#?} for sink connector with ID: {plugin_id}",
             elapsed
         );
+        true
     }
-    info!("Stopped consuming messages for sink connector with ID: {plugin_id}");
+
+    /// Fails only if the offsets must not be committed, which is the case for the pause on failure mode.
+    async fn try_process(
+        &self,
+        consumer: &IggyConsumer,
+        batch: &PartitionBatch,
+    ) -> Result<(), RuntimeError> {
+        let plugin_id = self.plugin_id;
+        let PartitionBatch {
+            partition_id,
+            current_offset,
+            ref messages,
+        } = *batch;
+        let messages_metadata = MessagesMetadata {
+            partition_id,
+            current_offset,
+            schema: self.decoder.schema(),
+        };
+        let result = match process_messages(
+            plugin_id,
+            messages_metadata,
+            &self.topic_metadata,
+            messages,
+            &self.transforms,
+            &self.decoder,
+            &self.metrics,
+        ) {
+            Ok(processed) => consume_with_retry(plugin_id, &self.consume, &self.retry, &processed)
+                .await
+                .map(|_| {
+                    self.metrics.record_produced(processed.count);
+                    processed.rejected
+                }),
+            Err(error) => Err(error),
+        };
+
+        let dead_letters = match result {
+            Ok(rejected) => rejected,
+            Err(error) => {
+                if self.retry.on_failure == SinkFailureMode::Skip {
+                    error!(
+                        "Failed to process {} messages for sink connector with ID: {plugin_id}. {error}",
+                        messages.len()
+                    );
+                    self.health.record_error(&error);
+                }
+                self.retry.on_batch_failure(messages, error)?
+            }
+        };
+
+        if !dead_letters.is_empty() {
+            let count = dead_letters.len();
+            self.metrics.record_failed(count);
+            let handled = if let Some(producer) = self.dead_letter.as_ref() {
+                match send_to_dead_letter(
+                    plugin_id,
+                    producer,
+                    &self.topic_metadata,
+                    batch,
+                    dead_letters,
+                )
+                .await
+                {
+                    Ok(()) => true,
+                    Err(error) => {
+                        error!(
+                            "Failed to send {count} messages to dead letter stream: {}, topic: {} for sink connector with ID: {plugin_id}. {error}",
+                            producer.stream(),
+                            producer.topic()
+                        );
+                        self.health.record_error(&error);
+                        false
+                    }
+                }
+            } else {
+                false
+            };
+
+            if !handled {
+                let error = RuntimeError::SinkRejectedMessages(plugin_id, count);
+                if self.retry.on_failure == SinkFailureMode::Pause {
+                    return Err(error);
+                }
+
+                error!("{error}");
+                self.health.record_error(&error);
+            }
+        }
+
+        if let Some(message) = messages.last() {
+            consumer
+                .store_offset(message.header.offset, Some(partition_id))
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Stores the consumer offsets reported by the sink as committed in its destination, so that the consumption is resumed right after them.
+async fn resume_from_committed_offsets(
+    container: &Container<SinkApi>,
+    plugin_id: u32,
+    client: &IggyClient,
+    consumer: &IggyConsumer,
+) -> Result<(), RuntimeError> {
+    let topic_metadata = TopicMetadata {
+        stream: consumer.stream().to_string(),
+        topic: consumer.topic().to_string(),
+    };
+    let Some(topic) = client
+        .get_topic(consumer.stream(), consumer.topic())
+        .await?
+    else {
+        return Ok(());
+    };
+
+    let topic_meta = postcard::to_allocvec(&topic_metadata)
+        .map_err(|_| RuntimeError::FailedToSerializeTopicMetadata)?;
+    for partition_id in 1..=topic.partitions_count {
+        let mut offset = 0;
+        let result = (container.committed_offset)(
+            plugin_id,
+            topic_meta.as_ptr(),
+            topic_meta.len(),
+            partition_id,
+            &mut offset,
+        );
+        match result {
+            0 => {
+                consumer.store_offset(offset, Some(partition_id)).await?;
+                info!(
+                    "Sink connector with ID: {plugin_id} will resume from the committed offset: {offset} for stream: {}, topic: {}, partition: {partition_id}",
+                    topic_metadata.stream, topic_metadata.topic
+                );
+            }
+            1 => {}
+            _ => return Err(RuntimeError::CannotGetCommittedOffset(plugin_id, result)),
+        }
+    }
+
+    Ok(())
+}
+
+fn init_sink(
+    container: &Container<SinkApi>,
+    config: &serde_json::Value,
+    id: u32,
+) -> Result<(), RuntimeError> {
+    let config = serde_json::to_string(config)?;
+    let result = (container.open)(id, config.as_ptr(), config.len());
+    if result != 0 {
+        return Err(RuntimeError::CannotOpenConnector(id, result));
+    }
+
     Ok(())
 }
 
-fn init_sink(container: &Container<SinkApi>, config: &serde_json::Value, id: u32) {
-    let config = serde_json::to_string(config).expect("Invalid sink config.");
-    (container.open)(id, config.as_ptr(), config.len());
+/// The retry and failure handling policy for the batches of messages consumed by the sink.
+#[derive(Debug, Clone)]
+pub(crate) struct RetryPolicy {
+    pub max_retries: u32,
+    pub initial_backoff: IggyDuration,
+    pub max_backoff: IggyDuration,
+    pub multiplier: f64,
+    pub on_failure: SinkFailureMode,
 }
 
-async fn process_messages(
+impl RetryPolicy {
+    /// Creates the policy from the optional config, the missing section is equivalent to the empty one.
+    fn from_config(config: Option<&SinkRetryConfig>) -> Result<Self, RuntimeError> {
+        let default_config = SinkRetryConfig::default();
+        let config = config.unwrap_or(&default_config);
+        let initial_backoff = config
+            .initial_backoff
+            .as_deref()
+            .unwrap_or(DEFAULT_INITIAL_BACKOFF);
+        let max_backoff = config.max_backoff.as_deref().unwrap_or(DEFAULT_MAX_BACKOFF);
+        let multiplier = config.multiplier.unwrap_or(DEFAULT_BACKOFF_MULTIPLIER);
+        let policy = Self {
+            max_retries: config.max_retries.unwrap_or(DEFAULT_MAX_RETRIES),
+            initial_backoff: IggyDuration::from_str(initial_backoff).map_err(|_| {
+                RuntimeError::InvalidConfiguration(format!(
+                    "retry.initial_backoff: {initial_backoff}"
+                ))
+            })?,
+            max_backoff: IggyDuration::from_str(max_backoff).map_err(|_| {
+                RuntimeError::InvalidConfiguration(format!("retry.max_backoff: {max_backoff}"))
+            })?,
+            multiplier,
+            on_failure: config.on_failure.unwrap_or_default(),
+        };
+
+        if policy.initial_backoff.get_duration() > policy.max_backoff.get_duration() {
+            return Err(RuntimeError::InvalidConfiguration(format!(
+                "retry.initial_backoff: {initial_backoff} is greater than retry.max_backoff: {max_backoff}"
+            )));
+        }
+
+        if !multiplier.is_finite() || multiplier < 1.0 {
+            return Err(RuntimeError::InvalidConfiguration(format!(
+                "retry.multiplier: {multiplier} must be at least 1.0"
+            )));
+        }
+
+        Ok(policy)
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        let backoff = self.initial_backoff.get_duration().as_secs_f64()
+            * self.multiplier.powi(attempt as i32);
+        let max_backoff = self.max_backoff.get_duration().as_secs_f64();
+        Duration::from_secs_f64(backoff.min(max_backoff))
+    }
+
+    /// Returns the error if the sink must be paused, otherwise the whole failed batch is skipped as the dead letters.
+    fn on_batch_failure(
+        &self,
+        messages: &[IggyMessage],
+        error: RuntimeError,
+    ) -> Result<Vec<DeadLetter>, RuntimeError> {
+        if self.on_failure == SinkFailureMode::Pause {
+            return Err(error);
+        }
+
+        let error = error.to_string();
+        Ok(messages
+            .iter()
+            .map(|message| DeadLetter {
+                offset: message.header.offset,
+                error: error.clone(),
+            })
+            .collect())
+    }
+}
+
+struct ProcessedMessages {
+    topic_meta: Vec<u8>,
+    messages_meta: Vec<u8>,
+    messages: Vec<u8>,
+    count: usize,
+    rejected: Vec<DeadLetter>,
+}
+
+struct DeadLetter {
+    offset: u64,
+    error: String,
+}
+
+async fn consume_with_retry(
+    plugin_id: u32,
+    consume: &ConsumeCallback,
+    retry: &RetryPolicy,
+    processed: &ProcessedMessages,
+) -> Result<(), RuntimeError> {
+    let mut attempt = 0;
+    loop {
+        let result = (consume)(
+            plugin_id,
+            processed.topic_meta.as_ptr(),
+            processed.topic_meta.len(),
+            processed.messages_meta.as_ptr(),
+            processed.messages_meta.len(),
+            processed.messages.as_ptr(),
+            processed.messages.len(),
+        );
+        if result == 0 {
+            return Ok(());
+        }
+
+        if attempt >= retry.max_retries {
+            return Err(RuntimeError::SinkConsumeFailed(plugin_id, result));
+        }
+
+        let backoff = retry.backoff(attempt);
+        attempt += 1;
+        warn!(
+            "Sink connector with ID: {plugin_id} failed to consume messages, error code: {result}. Retrying in {backoff:?} (attempt {attempt}/{})...",
+            retry.max_retries
+        );
+        tokio::time::sleep(backoff).await;
+    }
+}
+
+async fn send_to_dead_letter(
+    plugin_id: u32,
+    producer: &IggyProducer,
+    topic_metadata: &TopicMetadata,
+    batch: &PartitionBatch,
+    dead_letters: Vec<DeadLetter>,
+) -> Result<(), RuntimeError> {
+    let count = dead_letters.len();
+    let dead_letter_messages =
+        build_dead_letter_messages(plugin_id, topic_metadata, batch, dead_letters)?;
+    producer.send(dead_letter_messages).await?;
+    warn!(
+        "Sent {count} messages to dead letter stream: {}, topic: {} for sink connector with ID: {plugin_id}",
+        producer.stream(),
+        producer.topic()
+    );
+    Ok(())
+}
+
+fn build_dead_letter_messages(
+    plugin_id: u32,
+    topic_metadata: &TopicMetadata,
+    batch: &PartitionBatch,
+    dead_letters: Vec<DeadLetter>,
+) -> Result<Vec<IggyMessage>, RuntimeError> {
+    let mut dead_letter_messages = Vec::with_capacity(dead_letters.len());
+    for dead_letter in dead_letters {
+        let Some(message) = batch
+            .messages
+            .iter()
+            .find(|message| message.header.offset == dead_letter.offset)
+        else {
+            continue;
+        };
+
+        dead_letter_messages.push(build_dead_letter_message(
+            plugin_id,
+            topic_metadata,
+            batch.partition_id,
+            message,
+            dead_letter,
+        )?);
+    }
+    Ok(dead_letter_messages)
+}
+
+fn build_dead_letter_message(
+    plugin_id: u32,
+    topic_metadata: &TopicMetadata,
+    partition_id: u32,
+    message: &IggyMessage,
+    dead_letter: DeadLetter,
+) -> Result<IggyMessage, RuntimeError> {
+    let mut headers = message.user_headers_map()?.unwrap_or_default();
+    headers.insert(
+        HeaderKey::new(DEAD_LETTER_ERROR_HEADER)?,
+        text_header_value(&dead_letter.error)?,
+    );
+    headers.insert(
+        HeaderKey::new(DEAD_LETTER_STREAM_HEADER)?,
+        text_header_value(&topic_metadata.stream)?,
+    );
+    headers.insert(
+        HeaderKey::new(DEAD_LETTER_TOPIC_HEADER)?,
+        text_header_value(&topic_metadata.topic)?,
+    );
+    headers.insert(
+        HeaderKey::new(DEAD_LETTER_PARTITION_ID_HEADER)?,
+        HeaderValue::from_uint32(partition_id)?,
+    );
+    headers.insert(
+        HeaderKey::new(DEAD_LETTER_OFFSET_HEADER)?,
+        HeaderValue::from_uint64(dead_letter.offset)?,
+    );
+    headers.insert(
+        HeaderKey::new(DEAD_LETTER_CONNECTOR_ID_HEADER)?,
+        HeaderValue::from_uint32(plugin_id)?,
+    );
+    Ok(IggyMessage::builder()
+        .payload(message.payload.clone())
+        .user_headers(headers)
+        .build()?)
+}
+
+/// The header values must be non-empty and at most 255 bytes long, so the longer ones are truncated on the char boundary.
+fn text_header_value(value: &str) -> Result<HeaderValue, RuntimeError> {
+    if value.is_empty() {
+        return Ok(HeaderValue::from_str(UNKNOWN_HEADER_VALUE)?);
+    }
+
+    let mut length = value.len().min(MAX_HEADER_VALUE_LENGTH);
+    while !value.is_char_boundary(length) {
+        length -= 1;
+    }
+    Ok(HeaderValue::from_str(&value[..length])?)
+}
+
+fn process_messages(
     plugin_id: u32,
     messages_metadata: MessagesMetadata,
     topic_metadata: &TopicMetadata,
-    messages: Vec<IggyMessage>,
-    consume: &ConsumeCallback,
+    messages: &[IggyMessage],
     transforms: &Vec<Arc<dyn Transform>>,
     decoder: &Arc<dyn StreamDecoder>,
-) -> Result<(), RuntimeError> {
-    let messages = messages.into_iter().map(|message| ReceivedMessage {
-        id: message.header.id,
-        offset: message.header.offset,
-        checksum: message.header.checksum,
-        timestamp: message.header.timestamp,
-        origin_timestamp: message.header.origin_timestamp,
-        headers: message.user_headers_map().unwrap_or_default(),
-        payload: message.payload.into(),
-    });
-
-    let count = messages.len();
-    let decoded_messages = messages.into_iter().flat_map(|message| {
-        let Ok(payload) = decoder.decode(message.payload) else {
-            return None;
+    metrics: &ConnectorMetrics,
+) -> Result<ProcessedMessages, RuntimeError> {
+    let mut rejected = Vec::new();
+    let mut raw_messages = Vec::with_capacity(messages.len());
+    for message in messages {
+        let message = ReceivedMessage {
+            id: message.header.id,
+            offset: message.header.offset,
+            checksum: message.header.checksum,
+            timestamp: message.header.timestamp,
+            origin_timestamp: message.header.origin_timestamp,
+            headers: message.user_headers_map().unwrap_or_default(),
+            payload: message.payload.to_vec(),
+        };
+        let offset = message.offset;
+        let payload = match decoder.decode(message.payload) {
+            Ok(payload) => payload,
+            Err(error) => {
+                error!(
+                    "Failed to decode message with offset: {offset} for sink connector with ID: {plugin_id}. {error}"
+                );
+                rejected.push(DeadLetter {
+                    offset,
+                    error: error.to_string(),
+                });
+                continue;
+            }
         };
 
-        Some(DecodedMessage {
+        let mut current_message = Some(DecodedMessage {
             id: Some(message.id),
             offset: Some(message.offset),
             checksum: Some(message.checksum),
@@ -291,24 +1016,44 @@ async fn process_messages(
             origin_timestamp: Some(message.origin_timestamp),
             headers: message.headers,
             payload,
-        })
-    });
-    let mut messages = Vec::with_capacity(count);
-    for message in decoded_messages {
-        let mut current_message = Some(message);
+        });
+        let mut transform_error = None;
         for transform in transforms.iter() {
             let Some(message) = current_message else {
                 break;
             };
 
-            current_message = transform.transform(topic_metadata, message)?;
+            match transform.transform(topic_metadata, message) {
+                Ok(message) => current_message = message,
+                Err(error) => {
+                    transform_error = Some(error);
+                    current_message = None;
+                    break;
+                }
+            }
+        }
+
+        if let Some(error) = transform_error {
+            error!(
+                "Failed to transform message with offset: {offset} for sink connector with ID: {plugin_id}. {error}"
+            );
+            rejected.push(DeadLetter {
+                offset,
+                error: error.to_string(),
+            });
+            continue;
         }
 
         // The transform may return no message based on some conditions
         let Some(message) = current_message else {
+            metrics.record_filtered(1);
             continue;
         };
 
+        if !transforms.is_empty() {
+            metrics.record_transformed(1);
+        }
+
         let Some(id) = message.id else {
             error!(
                 "ID should be present. Failed to process message for sink connector with ID: {plugin_id}"
@@ -367,7 +1112,7 @@ async fn process_messages(
             continue;
         };
 
-        messages.push(RawMessage {
+        raw_messages.push(RawMessage {
             id,
             offset,
             checksum,
@@ -392,24 +1137,341 @@ async fn process_messages(
         RuntimeError::FailedToSerializeMessagesMetadata
     })?;
 
+    let count = raw_messages.len();
     let messages = postcard::to_allocvec(&RawMessages {
         schema: decoder.schema(),
-        messages,
+        messages: raw_messages,
     })
     .map_err(|error| {
         error!("Failed to serialize messages for sink connector with ID: {plugin_id}. {error}");
         RuntimeError::FailedToSerializeRawMessages
     })?;
 
-    (consume)(
-        plugin_id,
-        topic_meta.as_ptr(),
-        topic_meta.len(),
-        messages_meta.as_ptr(),
-        messages_meta.len(),
-        messages.as_ptr(),
-        messages.len(),
-    );
+    Ok(ProcessedMessages {
+        topic_meta,
+        messages_meta,
+        messages,
+        count,
+        rejected,
+    })
+}
 
-    Ok(())
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicU32;
+
+    static CONSUME_ATTEMPTS: AtomicU32 = AtomicU32::new(0);
+
+    /// Fails the first two attempts of the plugin with ID 1, and all the attempts of any other plugin.
+    extern "C" fn consume(
+        plugin_id: u32,
+        _topic_meta_ptr: *const u8,
+        _topic_meta_len: usize,
+        _messages_meta_ptr: *const u8,
+        _messages_meta_len: usize,
+        _messages_ptr: *const u8,
+        _messages_len: usize,
+    ) -> i32 {
+        let attempt = CONSUME_ATTEMPTS.fetch_add(1, Ordering::SeqCst);
+        if plugin_id == 1 && attempt >= 2 { 0 } else { 1 }
+    }
+
+    #[test]
+    fn retry_policy_should_be_created_with_defaults_given_no_config() {
+        let policy = RetryPolicy::from_config(None).unwrap();
+
+        assert_eq!(policy.max_retries, DEFAULT_MAX_RETRIES);
+        assert_eq!(
+            policy.initial_backoff.get_duration(),
+            Duration::from_millis(100)
+        );
+        assert_eq!(policy.max_backoff.get_duration(), Duration::from_secs(10));
+        assert_eq!(policy.multiplier, DEFAULT_BACKOFF_MULTIPLIER);
+        assert_eq!(policy.on_failure, SinkFailureMode::Skip);
+    }
+
+    #[test]
+    fn retry_policy_should_be_rejected_given_initial_backoff_greater_than_max_backoff() {
+        let config = SinkRetryConfig {
+            initial_backoff: Some("5s".to_owned()),
+            max_backoff: Some("1s".to_owned()),
+            ..Default::default()
+        };
+
+        let result = RetryPolicy::from_config(Some(&config));
+
+        assert!(matches!(result, Err(RuntimeError::InvalidConfiguration(_))));
+    }
+
+    #[test]
+    fn retry_policy_should_be_rejected_given_invalid_multiplier() {
+        for multiplier in [0.5, -1.0, f64::NAN, f64::INFINITY] {
+            let config = SinkRetryConfig {
+                multiplier: Some(multiplier),
+                ..Default::default()
+            };
+
+            let result = RetryPolicy::from_config(Some(&config));
+
+            assert!(matches!(result, Err(RuntimeError::InvalidConfiguration(_))));
+        }
+    }
+
+    #[test]
+    fn retry_policy_should_be_rejected_given_invalid_backoff() {
+        let config = SinkRetryConfig {
+            initial_backoff: Some("invalid".to_owned()),
+            ..Default::default()
+        };
+
+        let result = RetryPolicy::from_config(Some(&config));
+
+        assert!(matches!(result, Err(RuntimeError::InvalidConfiguration(_))));
+    }
+
+    #[test]
+    fn backoff_should_grow_by_multiplier_up_to_max_backoff() {
+        let policy = create_retry_policy(SinkFailureMode::Skip);
+
+        assert_eq!(policy.backoff(0), Duration::from_millis(100));
+        assert_eq!(policy.backoff(1), Duration::from_millis(200));
+        assert_eq!(policy.backoff(2), Duration::from_millis(400));
+        assert_eq!(policy.backoff(10), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn batch_failure_should_return_error_given_pause_mode() {
+        let policy = create_retry_policy(SinkFailureMode::Pause);
+        let messages = create_messages(&[1, 2]);
+
+        let result = policy.on_batch_failure(&messages, RuntimeError::SinkConsumeFailed(1, 1));
+
+        assert!(matches!(result, Err(RuntimeError::SinkConsumeFailed(1, 1))));
+    }
+
+    #[test]
+    fn batch_failure_should_skip_all_messages_as_dead_letters_given_skip_mode() {
+        let policy = create_retry_policy(SinkFailureMode::Skip);
+        let messages = create_messages(&[1, 2]);
+
+        let dead_letters = policy
+            .on_batch_failure(&messages, RuntimeError::SinkConsumeFailed(1, 1))
+            .unwrap();
+
+        let offsets = dead_letters
+            .iter()
+            .map(|dead_letter| dead_letter.offset)
+            .collect::<Vec<_>>();
+        assert_eq!(offsets, vec![1, 2]);
+        assert!(
+            dead_letters.iter().all(|dead_letter| dead_letter.error
+                == RuntimeError::SinkConsumeFailed(1, 1).to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn consume_should_be_retried_until_max_retries() {
+        let mut policy = create_retry_policy(SinkFailureMode::Skip);
+        policy.initial_backoff = IggyDuration::from_str("1ms").unwrap();
+        let processed = ProcessedMessages {
+            topic_meta: vec![],
+            messages_meta: vec![],
+            messages: vec![],
+            count: 0,
+            rejected: vec![],
+        };
+
+        let callback: ConsumeCallback = consume;
+        CONSUME_ATTEMPTS.store(0, Ordering::SeqCst);
+        let result = consume_with_retry(1, &callback, &policy, &processed).await;
+        assert!(result.is_ok());
+        assert_eq!(CONSUME_ATTEMPTS.load(Ordering::SeqCst), 3);
+
+        policy.max_retries = 1;
+        CONSUME_ATTEMPTS.store(0, Ordering::SeqCst);
+        let result = consume_with_retry(1, &callback, &policy, &processed).await;
+        assert!(matches!(result, Err(RuntimeError::SinkConsumeFailed(1, 1))));
+        assert_eq!(CONSUME_ATTEMPTS.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn dead_letter_message_should_contain_original_payload_and_failure_headers() {
+        let topic_metadata = TopicMetadata {
+            stream: "stream".to_owned(),
+            topic: "topic".to_owned(),
+        };
+        let message = create_messages(&[5]).remove(0);
+        let dead_letter = DeadLetter {
+            offset: 5,
+            error: "e".repeat(MAX_HEADER_VALUE_LENGTH + 10),
+        };
+
+        let dead_letter_message =
+            build_dead_letter_message(7, &topic_metadata, 3, &message, dead_letter).unwrap();
+
+        assert_eq!(dead_letter_message.payload, message.payload);
+        let headers = dead_letter_message.user_headers_map().unwrap().unwrap();
+        let header = |key: &str| headers.get(&HeaderKey::new(key).unwrap()).unwrap();
+        assert_eq!(
+            header(DEAD_LETTER_ERROR_HEADER).as_str().unwrap().len(),
+            MAX_HEADER_VALUE_LENGTH
+        );
+        assert_eq!(
+            header(DEAD_LETTER_STREAM_HEADER).as_str().unwrap(),
+            "stream"
+        );
+        assert_eq!(header(DEAD_LETTER_TOPIC_HEADER).as_str().unwrap(), "topic");
+        assert_eq!(
+            header(DEAD_LETTER_PARTITION_ID_HEADER).as_uint32().unwrap(),
+            3
+        );
+        assert_eq!(header(DEAD_LETTER_OFFSET_HEADER).as_uint64().unwrap(), 5);
+        assert_eq!(
+            header(DEAD_LETTER_CONNECTOR_ID_HEADER).as_uint32().unwrap(),
+            7
+        );
+    }
+
+    #[test]
+    fn batch_should_be_flushed_given_message_from_another_partition() {
+        let mut batcher = Batcher::new(10);
+        let mut messages = create_messages(&[1, 2, 7, 3]).into_iter();
+
+        assert!(batcher.push(1, 10, messages.next().unwrap()).is_empty());
+        assert!(batcher.push(1, 10, messages.next().unwrap()).is_empty());
+        let batches = batcher.push(2, 20, messages.next().unwrap());
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].partition_id, 1);
+        assert_eq!(batches[0].current_offset, 10);
+        assert_eq!(offsets(&batches[0].messages), vec![1, 2]);
+
+        let batches = batcher.push(1, 10, messages.next().unwrap());
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].partition_id, 2);
+        assert_eq!(offsets(&batches[0].messages), vec![7]);
+
+        let batch = batcher.take().unwrap();
+        assert_eq!(batch.partition_id, 1);
+        assert_eq!(offsets(&batch.messages), vec![3]);
+        assert!(batcher.take().is_none());
+    }
+
+    #[test]
+    fn batch_should_be_flushed_given_batch_size_or_current_offset_reached() {
+        let mut batcher = Batcher::new(2);
+        let mut messages = create_messages(&[1, 2, 3, 4, 9]).into_iter();
+
+        assert!(batcher.push(1, 4, messages.next().unwrap()).is_empty());
+        let batches = batcher.push(1, 4, messages.next().unwrap());
+        assert_eq!(offsets(&batches[0].messages), vec![1, 2]);
+
+        assert!(batcher.push(1, 4, messages.next().unwrap()).is_empty());
+        let batches = batcher.push(1, 4, messages.next().unwrap());
+        assert_eq!(offsets(&batches[0].messages), vec![3, 4]);
+
+        // The last message of the other partition completes both batches at once.
+        assert!(
+            batcher
+                .push(1, 10, create_messages(&[5]).remove(0))
+                .is_empty()
+        );
+        let batches = batcher.push(2, 9, messages.next().unwrap());
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[0].partition_id, 1);
+        assert_eq!(offsets(&batches[0].messages), vec![5]);
+        assert_eq!(batches[1].partition_id, 2);
+        assert_eq!(offsets(&batches[1].messages), vec![9]);
+        assert!(batcher.take().is_none());
+    }
+
+    #[test]
+    fn dead_letter_messages_should_refer_to_partition_of_batch() {
+        let topic_metadata = TopicMetadata {
+            stream: "stream".to_owned(),
+            topic: "topic".to_owned(),
+        };
+        let mut batcher = Batcher::new(10);
+        let mut messages = create_messages(&[1, 2, 1]).into_iter();
+        batcher.push(1, 5, messages.next().unwrap());
+        batcher.push(1, 5, messages.next().unwrap());
+        let batch = batcher.push(2, 5, messages.next().unwrap()).remove(0);
+        let policy = create_retry_policy(SinkFailureMode::Skip);
+        let dead_letters = policy
+            .on_batch_failure(&batch.messages, RuntimeError::SinkConsumeFailed(1, 1))
+            .unwrap();
+
+        let dead_letter_messages =
+            build_dead_letter_messages(1, &topic_metadata, &batch, dead_letters).unwrap();
+
+        assert_eq!(dead_letter_messages.len(), 2);
+        for (message, offset) in dead_letter_messages.iter().zip([1, 2]) {
+            let headers = message.user_headers_map().unwrap().unwrap();
+            let header = |key: &str| headers.get(&HeaderKey::new(key).unwrap()).unwrap();
+            assert_eq!(
+                header(DEAD_LETTER_PARTITION_ID_HEADER).as_uint32().unwrap(),
+                1
+            );
+            assert_eq!(
+                header(DEAD_LETTER_OFFSET_HEADER).as_uint64().unwrap(),
+                offset
+            );
+        }
+    }
+
+    #[test]
+    fn text_header_value_should_be_truncated_on_char_boundary() {
+        let value = "ą".repeat(MAX_HEADER_VALUE_LENGTH);
+
+        let header_value = text_header_value(&value).unwrap();
+
+        let header_value = header_value.as_str().unwrap();
+        assert_eq!(header_value.len(), MAX_HEADER_VALUE_LENGTH - 1);
+        assert!(value.starts_with(header_value));
+    }
+
+    #[test]
+    fn text_header_value_should_be_kept_given_short_value() {
+        let header_value = text_header_value("error").unwrap();
+
+        assert_eq!(header_value.as_str().unwrap(), "error");
+    }
+
+    #[test]
+    fn text_header_value_should_be_unknown_given_empty_value() {
+        let header_value = text_header_value("").unwrap();
+
+        assert_eq!(header_value.as_str().unwrap(), UNKNOWN_HEADER_VALUE);
+    }
+
+    fn create_retry_policy(on_failure: SinkFailureMode) -> RetryPolicy {
+        RetryPolicy {
+            max_retries: 3,
+            initial_backoff: IggyDuration::from_str("100ms").unwrap(),
+            max_backoff: IggyDuration::from_str("1s").unwrap(),
+            multiplier: 2.0,
+            on_failure,
+        }
+    }
+
+    fn create_messages(offsets: &[u64]) -> Vec<IggyMessage> {
+        offsets
+            .iter()
+            .map(|offset| {
+                let mut message = IggyMessage::builder()
+                    .payload(format!("message-{offset}").into())
+                    .build()
+                    .unwrap();
+                message.header.offset = *offset;
+                message
+            })
+            .collect()
+    }
+
+    fn offsets(messages: &[IggyMessage]) -> Vec<u64> {
+        messages
+            .iter()
+            .map(|message| message.header.offset)
+            .collect()
+    }
 }
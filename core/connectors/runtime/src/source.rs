@@ -19,9 +19,7 @@
 use dashmap::DashMap;
 use dlopen2::wrapper::Container;
 use flume::{Receiver, Sender};
-use iggy::prelude::{
-    DirectConfig, HeaderKey, HeaderValue, IggyClient, IggyDuration, IggyError, IggyMessage,
-};
+use iggy::prelude::{DirectConfig, HeaderKey, HeaderValue, IggyDuration, IggyError, IggyMessage};
 use iggy_connector_sdk::{
     ConnectorState, DecodedMessage, Error, ProducedMessages, StreamEncoder, TopicMetadata,
     transforms::Transform,
@@ -31,139 +29,343 @@ use std::{
     collections::HashMap,
     str::FromStr,
     sync::{Arc, atomic::Ordering},
+    time::{Duration, Instant},
 };
+use tokio::time::{Interval, interval_at};
 use tracing::{debug, error, info, warn};
 
 use crate::{
-    PLUGIN_ID, RuntimeError, SourceApi, SourceConnector, SourceConnectorPlugin,
-    SourceConnectorProducer, SourceConnectorWrapper,
-    configs::SourceConfig,
+    PLUGIN_ID, RuntimeError, SourceApi, SourceConnectorProducer,
+    configs::{SourceConfig, StateConfig},
+    manager::{
+        health::{ConnectorHealth, ConnectorStatus},
+        source::{SourceDetails, SourceInfo},
+    },
+    metrics::ConnectorMetrics,
     resolve_plugin_path,
-    state::{FileStateProvider, StateProvider, StateStorage},
+    state::{StateProvider, StateStorage},
+    stream::IggyClients,
     transform,
 };
 
-pub static SOURCE_SENDERS: Lazy<DashMap<u32, Sender<ProducedMessages>>> = Lazy::new(DashMap::new);
+const SEND_RETRY_INTERVAL: Duration = Duration::from_secs(1);
+
+pub static SOURCE_SENDERS: Lazy<DashMap<u32, Sender<(ProducedMessages, Instant)>>> =
+    Lazy::new(DashMap::new);
 
 pub async fn init(
     source_configs: HashMap<String, SourceConfig>,
-    iggy_client: &IggyClient,
-    state_path: &str,
-) -> Result<HashMap<String, SourceConnector>, RuntimeError> {
-    let mut source_connectors: HashMap<String, SourceConnector> = HashMap::new();
+    iggy_clients: &IggyClients,
+    state_config: &StateConfig,
+) -> Vec<SourceDetails> {
+    let mut containers: HashMap<String, Arc<Container<SourceApi>>> = HashMap::new();
+    let mut sources = Vec::with_capacity(source_configs.len());
     for (key, config) in source_configs {
-        let name = config.name;
-        if !config.enabled {
-            warn!("Source: {name} is disabled ({key})");
+        let path = resolve_plugin_path(&config.path);
+        let mut source = create(&key, config, containers.get(&path).cloned());
+
+        if !source.config.enabled {
+            warn!("Source: {} is disabled ({key})", source.info.name);
+            sources.push(source);
             continue;
         }
 
-        let plugin_id = PLUGIN_ID.load(Ordering::Relaxed);
-        let path = resolve_plugin_path(&config.path);
-        info!("Initializing source container with name: {name} ({key}), plugin: {path}",);
-        let state_storage = get_state_storage(state_path, &key);
-        let state = match &state_storage {
-            StateStorage::File(file) => file.load().await?,
-        };
-        if let Some(container) = source_connectors.get_mut(&path) {
-            info!("Source container for plugin: {path} is already loaded.",);
-            init_source(
-                &container.container,
-                &config.config.unwrap_or_default(),
-                plugin_id,
-                state,
-            );
-            container.plugins.push(SourceConnectorPlugin {
-                id: plugin_id,
-                key: key.to_owned(),
-                name: name.to_owned(),
-                path: path.to_owned(),
-                config_format: config.config_format,
-                producer: None,
-                transforms: vec![],
-                state_storage,
-            });
-        } else {
-            let container: Container<SourceApi> =
-                unsafe { Container::load(&path).expect("Failed to load source container") };
-            info!("Source container for plugin: {path} loaded successfully.",);
-            init_source(
-                &container,
-                &config.config.unwrap_or_default(),
-                plugin_id,
-                state,
-            );
-            source_connectors.insert(
-                path.to_owned(),
-                SourceConnector {
-                    container,
-                    plugins: vec![SourceConnectorPlugin {
-                        id: plugin_id,
-                        key: key.to_owned(),
-                        name: name.to_owned(),
-                        path: path.to_owned(),
-                        config_format: config.config_format,
-                        producer: None,
-                        transforms: vec![],
-                        state_storage,
-                    }],
-                },
+        if let Err(error) = start(&mut source, iggy_clients, state_config).await {
+            error!(
+                "Failed to start source with name: {} ({key}). {error}",
+                source.info.name
             );
         }
 
-        info!(
-            "Source container with name: {name} ({key}), initialized successfully with ID: {plugin_id}."
+        if let Some(container) = source.container.as_ref() {
+            containers.insert(path, container.clone());
+        }
+        sources.push(source);
+    }
+
+    sources
+}
+
+/// Creates the stopped source with the newly allocated plugin ID, the container is loaded on start if not provided.
+pub fn create(
+    key: &str,
+    config: SourceConfig,
+    container: Option<Arc<Container<SourceApi>>>,
+) -> SourceDetails {
+    let plugin_id = PLUGIN_ID.fetch_add(1, Ordering::Relaxed);
+    let path = resolve_plugin_path(&config.path);
+    SourceDetails {
+        info: SourceInfo {
+            id: plugin_id,
+            key: key.to_owned(),
+            name: config.name.to_owned(),
+            path,
+            enabled: config.enabled,
+            config_format: config.config_format,
+        },
+        container,
+        config,
+        health: Arc::new(ConnectorHealth::default()),
+        metrics: ConnectorMetrics::source(key),
+        tasks: vec![],
+    }
+}
+
+/// Stops the source, applies the new config and starts it again if it's enabled.
+pub async fn update(
+    source: &mut SourceDetails,
+    config: SourceConfig,
+    iggy_clients: &IggyClients,
+    state_config: &StateConfig,
+) -> Result<(), RuntimeError> {
+    stop(source).await;
+    let path = resolve_plugin_path(&config.path);
+    if source.info.path != path {
+        source.container = None;
+    }
+    source.info.name = config.name.to_owned();
+    source.info.path = path;
+    source.info.enabled = config.enabled;
+    source.info.config_format = config.config_format;
+    source.config = config;
+    if !source.config.enabled {
+        warn!(
+            "Source: {} is disabled ({})",
+            source.info.name, source.info.key
         );
-        PLUGIN_ID.fetch_add(1, Ordering::Relaxed);
-
-        let transforms = if let Some(transforms_config) = config.transforms {
-            let transforms =
-                transform::load(&transforms_config).expect("Failed to load transforms");
-            let types = transforms
-                .iter()
-                .map(|t| t.r#type().into())
-                .collect::<Vec<&'static str>>()
-                .join(", ");
-            info!("Enabled transforms for source: {name} ({key}): {types}",);
-            transforms
-        } else {
-            vec![]
+        return Ok(());
+    }
+
+    start(source, iggy_clients, state_config).await
+}
+
+/// Opens the source plugin with its last saved state and starts sending the produced messages.
+pub async fn start(
+    source: &mut SourceDetails,
+    iggy_clients: &IggyClients,
+    state_config: &StateConfig,
+) -> Result<(), RuntimeError> {
+    let status = source.health.status();
+    if status == ConnectorStatus::Running || status == ConnectorStatus::Paused {
+        return Err(RuntimeError::InvalidConnectorStatus(format!(
+            "source: {} is already {status}",
+            source.info.key
+        )));
+    }
+
+    open(source, iggy_clients, state_config).await
+}
+
+/// Closes the source plugin, the messages which were already produced are sent before the producer is dropped.
+pub async fn stop(source: &mut SourceDetails) {
+    close(source).await;
+    source.health.set_status(ConnectorStatus::Stopped);
+}
+
+pub async fn restart(
+    source: &mut SourceDetails,
+    iggy_clients: &IggyClients,
+    state_config: &StateConfig,
+) -> Result<(), RuntimeError> {
+    stop(source).await;
+    start(source, iggy_clients, state_config).await
+}
+
+/// Closes the source plugin, so that it stops producing the messages, but keeps its state to be resumed from.
+pub async fn pause(source: &mut SourceDetails) -> Result<(), RuntimeError> {
+    let status = source.health.status();
+    if status != ConnectorStatus::Running {
+        return Err(RuntimeError::InvalidConnectorStatus(format!(
+            "source: {} cannot be paused when {status}",
+            source.info.key
+        )));
+    }
+
+    close(source).await;
+    source.health.set_status(ConnectorStatus::Paused);
+    info!("Paused source connector with ID: {}", source.info.id);
+    Ok(())
+}
+
+pub async fn resume(
+    source: &mut SourceDetails,
+    iggy_clients: &IggyClients,
+    state_config: &StateConfig,
+) -> Result<(), RuntimeError> {
+    let status = source.health.status();
+    if status != ConnectorStatus::Paused {
+        return Err(RuntimeError::InvalidConnectorStatus(format!(
+            "source: {} cannot be resumed when {status}",
+            source.info.key
+        )));
+    }
+
+    open(source, iggy_clients, state_config).await?;
+    info!("Resumed source connector with ID: {}", source.info.id);
+    Ok(())
+}
+
+async fn open(
+    source: &mut SourceDetails,
+    iggy_clients: &IggyClients,
+    state_config: &StateConfig,
+) -> Result<(), RuntimeError> {
+    let plugin_id = source.info.id;
+    let key = &source.info.key;
+    let name = &source.info.name;
+    let path = &source.info.path;
+    info!("Initializing source container with name: {name} ({key}), plugin: {path}",);
+    let container = if let Some(container) = source.container.as_ref() {
+        info!("Source container for plugin: {path} is already loaded.",);
+        container.clone()
+    } else {
+        let container: Container<SourceApi> = unsafe {
+            Container::load(path).map_err(|error| {
+                error!("Failed to load source container for plugin: {path}. {error}");
+                RuntimeError::CannotLoadPlugin(path.to_owned())
+            })?
         };
+        info!("Source container for plugin: {path} loaded successfully.",);
+        let container = Arc::new(container);
+        source.container = Some(container.clone());
+        container
+    };
 
-        let connector = source_connectors
-            .get_mut(&path)
-            .expect("Failed to get source connector");
-        let plugin = connector
-            .plugins
-            .iter_mut()
-            .find(|p| p.id == plugin_id)
-            .expect("Failed to get source plugin");
-
-        for stream in config.streams.iter() {
-            let linger_time =
-                IggyDuration::from_str(stream.linger_time.as_deref().unwrap_or("5ms"))
-                    .expect("Invalid send interval");
-            let batch_length = stream.batch_length.unwrap_or(1000);
-            let producer = iggy_client
-                .producer(&stream.stream, &stream.topic)?
-                .direct(
-                    DirectConfig::builder()
-                        .batch_length(batch_length)
-                        .linger_time(linger_time)
-                        .build(),
-                )
-                .build();
-
-            producer.init().await?;
-            plugin.producer = Some(SourceConnectorProducer {
-                producer,
-                encoder: stream.schema.encoder(),
-            });
-            plugin.transforms = transforms.clone();
+    let state_storage = StateStorage::new(state_config, key, iggy_clients);
+    let result = async {
+        let producer = create_producer(&source.info, &source.config, iggy_clients).await?;
+        let state = state_storage.load().await?;
+        let config = source
+            .config
+            .config
+            .as_ref()
+            .unwrap_or(&serde_json::Value::Null);
+        init_source(&container, config, plugin_id, state)?;
+        Ok::<_, RuntimeError>(producer)
+    }
+    .await;
+
+    let (producer, transforms) = match result {
+        Ok(producer) => producer,
+        Err(error) => {
+            source.health.record_error(&error);
+            source.health.set_status(ConnectorStatus::Error);
+            return Err(error);
+        }
+    };
+
+    info!(
+        "Source container with name: {name} ({key}), initialized successfully with ID: {plugin_id}."
+    );
+    source.health.reset();
+    source.health.set_status(ConnectorStatus::Running);
+
+    let (sender, receiver) = flume::unbounded();
+    SOURCE_SENDERS.insert(plugin_id, sender);
+    let health = source.health.clone();
+    let metrics = source.metrics.clone();
+    let commit_container = container.clone();
+    source.tasks.push(tokio::spawn(async move {
+        send_messages(
+            plugin_id,
+            &commit_container,
+            producer,
+            transforms,
+            state_storage,
+            receiver,
+            health,
+            metrics,
+        )
+        .await;
+    }));
+
+    info!("Starting handler for source connector with ID: {plugin_id}...");
+    let handle = container.handle;
+    tokio::task::spawn_blocking(move || {
+        handle(plugin_id, handle_produced_messages);
+    });
+    info!("Handler for source connector with ID: {plugin_id} started successfully.");
+    Ok(())
+}
+
+async fn close(source: &mut SourceDetails) {
+    if source.health.status() != ConnectorStatus::Running {
+        return;
+    }
+
+    let plugin_id = source.info.id;
+    if let Some(container) = source.container.as_ref() {
+        info!(
+            "Closing source connector with ID: {plugin_id} for plugin: {}",
+            source.info.path
+        );
+        (container.close)(plugin_id);
+        info!(
+            "Closed source connector with ID: {plugin_id} for plugin: {}",
+            source.info.path
+        );
+    }
+
+    SOURCE_SENDERS.remove(&plugin_id);
+    for task in source.tasks.drain(..) {
+        if let Err(error) = task.await {
+            error!(
+                "Failed to stop sending messages for source connector with ID: {plugin_id}. {error}"
+            );
         }
     }
+}
+
+async fn create_producer(
+    info: &SourceInfo,
+    config: &SourceConfig,
+    iggy_clients: &IggyClients,
+) -> Result<(SourceConnectorProducer, Vec<Arc<dyn Transform>>), RuntimeError> {
+    let key = &info.key;
+    let name = &info.name;
+    let transforms = if let Some(transforms_config) = config.transforms.as_ref() {
+        let transforms = transform::load(transforms_config)?;
+        let types = transforms
+            .iter()
+            .map(|t| t.r#type().into())
+            .collect::<Vec<&'static str>>()
+            .join(", ");
+        info!("Enabled transforms for source: {name} ({key}): {types}",);
+        transforms
+    } else {
+        vec![]
+    };
+
+    let mut producer = None;
+    for stream in config.streams.iter() {
+        let linger_time = IggyDuration::from_str(stream.linger_time.as_deref().unwrap_or("5ms"))
+            .map_err(|_| RuntimeError::InvalidConfiguration("linger_time".to_owned()))?;
+        let batch_length = stream.batch_length.unwrap_or(1000);
+        let stream_producer = iggy_clients
+            .producer
+            .producer(&stream.stream, &stream.topic)?
+            .direct(
+                DirectConfig::builder()
+                    .batch_length(batch_length)
+                    .linger_time(linger_time)
+                    .build(),
+            )
+            .build();
+
+        stream_producer.init().await?;
+        producer = Some(SourceConnectorProducer {
+            producer: stream_producer,
+            encoder: stream.schema.encoder(),
+        });
+    }
 
-    Ok(source_connectors)
+    let Some(producer) = producer else {
+        return Err(RuntimeError::InvalidConfiguration(format!(
+            "missing stream for source: {key}"
+        )));
+    };
+
+    Ok((producer, transforms))
 }
 
 fn init_source(
@@ -171,126 +373,175 @@ fn init_source(
     config: &serde_json::Value,
     id: u32,
     state: Option<ConnectorState>,
-) {
-    let config = serde_json::to_string(config).expect("Invalid source config.");
+) -> Result<(), RuntimeError> {
+    let config = serde_json::to_string(config)?;
     let state_ptr = state.as_ref().map_or(std::ptr::null(), |s| s.0.as_ptr());
     let state_len = state.as_ref().map_or(0, |s| s.0.len());
-    (container.open)(id, config.as_ptr(), config.len(), state_ptr, state_len);
-}
+    let result = (container.open)(id, config.as_ptr(), config.len(), state_ptr, state_len);
+    if result != 0 {
+        (container.close)(id);
+        return Err(RuntimeError::CannotOpenConnector(id, result));
+    }
 
-fn get_state_storage(state_path: &str, key: &str) -> StateStorage {
-    let path = format!("{state_path}/source_{key}.state");
-    StateStorage::File(FileStateProvider::new(path))
+    Ok(())
 }
 
-pub fn handle(sources: Vec<SourceConnectorWrapper>) {
-    for source in sources {
-        for plugin in source.plugins {
-            let plugin_id = plugin.id;
-            info!("Starting handler for source connector with ID: {plugin_id}...");
-            let handle = source.callback;
-            tokio::task::spawn_blocking(move || {
-                handle(plugin_id, handle_produced_messages);
-            });
-            info!("Handler for source connector with ID: {plugin_id} started successfully.");
-
-            let (sender, receiver): (Sender<ProducedMessages>, Receiver<ProducedMessages>) =
-                flume::unbounded();
-            SOURCE_SENDERS.insert(plugin_id, sender);
-            tokio::spawn(async move {
-                info!("Source connector with ID: {plugin_id} started.");
-                let Some(producer) = &plugin.producer else {
-                    error!("Producer not initialized for source connector with ID: {plugin_id}");
-                    return;
-                };
-                let encoder = producer.encoder.clone();
-                let producer = &producer.producer;
-                let mut number = 1u64;
-
-                let topic_metadata = TopicMetadata {
-                    stream: producer.stream().to_string(),
-                    topic: producer.topic().to_string(),
-                };
-
-                while let Ok(produced_messages) = receiver.recv_async().await {
-                    let count = produced_messages.messages.len();
-                    info!("Source connector with ID: {plugin_id} received {count} messages",);
-                    let schema = produced_messages.schema;
-                    let mut messages: Vec<DecodedMessage> = Vec::with_capacity(count);
-                    for message in produced_messages.messages {
-                        let Ok(payload) = schema.try_into_payload(message.payload) else {
-                            error!(
-                                "Failed to decode message payload with schema: {} for source connector with ID: {plugin_id}",
-                                produced_messages.schema
-                            );
-                            continue;
-                        };
-
-                        debug!(
-                            "Source connector with ID: {plugin_id}] received message: {number} | schema: {schema} | payload: {payload}"
-                        );
-                        messages.push(DecodedMessage {
-                            id: message.id,
-                            offset: None,
-                            headers: message.headers,
-                            checksum: message.checksum,
-                            timestamp: message.timestamp,
-                            origin_timestamp: message.origin_timestamp,
-                            payload,
-                        });
-                        number += 1;
-                    }
-
-                    let Ok(iggy_messages) = process_messages(
-                        plugin_id,
-                        &encoder,
-                        &topic_metadata,
-                        messages,
-                        &plugin.transforms,
-                    ) else {
-                        error!(
-                            "Failed to process {count} messages by source connector with ID: {plugin_id} before sending them to stream: {}, topic: {}.",
-                            producer.stream(),
-                            producer.topic()
-                        );
-                        continue;
-                    };
-
-                    if let Err(error) = producer.send(iggy_messages).await {
-                        error!(
-                            "Failed to send {count} messages to stream: {}, topic: {} by source connector with ID: {plugin_id}. {error}",
-                            producer.stream(),
-                            producer.topic(),
-                        );
-                        continue;
-                    }
-
-                    info!(
-                        "Sent {count} messages to stream: {}, topic: {} by source connector with ID: {plugin_id}",
-                        producer.stream(),
-                        producer.topic()
-                    );
-
-                    let Some(state) = produced_messages.state else {
-                        debug!("No state provided for source connector with ID: {plugin_id}");
-                        continue;
-                    };
-
-                    match &plugin.state_storage {
-                        StateStorage::File(file) => {
-                            if let Err(error) = file.save(state).await {
-                                error!(
-                                    "Failed to save state for source connector with ID: {plugin_id}. {error}"
-                                );
-                                continue;
-                            }
-                            debug!("State saved for source connector with ID: {plugin_id}");
-                        }
-                    }
+#[allow(clippy::too_many_arguments)]
+async fn send_messages(
+    plugin_id: u32,
+    container: &Container<SourceApi>,
+    producer: SourceConnectorProducer,
+    transforms: Vec<Arc<dyn Transform>>,
+    state_storage: StateStorage,
+    receiver: Receiver<(ProducedMessages, Instant)>,
+    health: Arc<ConnectorHealth>,
+    metrics: ConnectorMetrics,
+) {
+    info!("Source connector with ID: {plugin_id} started.");
+    let encoder = producer.encoder.clone();
+    let producer = &producer.producer;
+    let mut number = 1u64;
+
+    let topic_metadata = TopicMetadata {
+        stream: producer.stream().to_string(),
+        topic: producer.topic().to_string(),
+    };
+
+    let mut last_received_at = Instant::now();
+    let mut refresh = state_storage
+        .refresh_interval()
+        .map(|interval| interval_at(tokio::time::Instant::now() + interval, interval));
+    loop {
+        let (produced_messages, received_at) = tokio::select! {
+            received = receiver.recv_async() => match received {
+                Ok(received) => received,
+                Err(_) => break,
+            },
+            _ = tick(&mut refresh) => {
+                // The state of the idle source is saved again, so that the state topic retention does not remove it.
+                if let Err(error) = state_storage.refresh().await {
+                    warn!("Failed to refresh state for source connector with ID: {plugin_id}. {error}");
                 }
+                continue;
+            }
+        };
+        // The plugin starts polling the next messages right after the previous ones were received.
+        metrics.record_poll_duration(received_at.saturating_duration_since(last_received_at));
+        last_received_at = received_at;
+        let count = produced_messages.messages.len();
+        info!("Source connector with ID: {plugin_id} received {count} messages",);
+        metrics.record_received(count);
+        let start = Instant::now();
+        let schema = produced_messages.schema;
+        let mut messages: Vec<DecodedMessage> = Vec::with_capacity(count);
+        for message in produced_messages.messages {
+            let Ok(payload) = schema.try_into_payload(message.payload) else {
+                error!(
+                    "Failed to decode message payload with schema: {} for source connector with ID: {plugin_id}",
+                    produced_messages.schema
+                );
+                metrics.record_failed(1);
+                continue;
+            };
+
+            debug!(
+                "Source connector with ID: {plugin_id}] received message: {number} | schema: {schema} | payload: {payload}"
+            );
+            messages.push(DecodedMessage {
+                id: message.id,
+                offset: None,
+                headers: message.headers,
+                checksum: message.checksum,
+                timestamp: message.timestamp,
+                origin_timestamp: message.origin_timestamp,
+                payload,
             });
+            number += 1;
+        }
+
+        let iggy_messages = match process_messages(
+            plugin_id,
+            &encoder,
+            &topic_metadata,
+            messages,
+            &transforms,
+            &metrics,
+        ) {
+            Ok(iggy_messages) => iggy_messages,
+            Err(error) => {
+                metrics.record_failed(count);
+                error!(
+                    "Failed to process {count} messages by source connector with ID: {plugin_id} before sending them to stream: {}, topic: {}.",
+                    producer.stream(),
+                    producer.topic()
+                );
+                health.record_error(&error);
+                continue;
+            }
+        };
+
+        // The failed batch is sent again rather than skipped, as the state saved after any of the next batches would skip it too.
+        let sent_count = iggy_messages.len();
+        while let Err(error) = producer.send(iggy_messages.clone()).await {
+            metrics.record_failed(sent_count);
+            error!(
+                "Failed to send {count} messages to stream: {}, topic: {} by source connector with ID: {plugin_id}. {error}",
+                producer.stream(),
+                producer.topic(),
+            );
+            health.record_error(&error);
+            if receiver.is_disconnected() {
+                warn!(
+                    "Source connector with ID: {plugin_id} was stopped, {count} unsent messages will be produced again from its last saved state."
+                );
+                return;
+            }
+
+            tokio::time::sleep(SEND_RETRY_INTERVAL).await;
+        }
+
+        health.record_batch(count as u64);
+        metrics.record_produced(sent_count);
+        metrics.record_batch_duration(start.elapsed());
+        info!(
+            "Sent {count} messages to stream: {}, topic: {} by source connector with ID: {plugin_id}",
+            producer.stream(),
+            producer.topic()
+        );
+
+        let Some(state) = produced_messages.state else {
+            debug!("No state provided for source connector with ID: {plugin_id}");
+            continue;
+        };
+
+        if let Err(error) = state_storage.save(ConnectorState(state.0.clone())).await {
+            error!("Failed to save state for source connector with ID: {plugin_id}. {error}");
+            health.record_error(&error);
+            continue;
+        }
+        debug!("State saved for source connector with ID: {plugin_id}");
+        if let Some(refresh) = refresh.as_mut() {
+            refresh.reset();
+        }
+
+        let result = (container.commit)(plugin_id, state.0.as_ptr(), state.0.len());
+        if result != 0 {
+            warn!(
+                "Failed to commit saved state by source connector with ID: {plugin_id}, error code: {result}"
+            );
         }
     }
+    info!("Source connector with ID: {plugin_id} stopped.");
+}
+
+/// Waits for the next refresh of the state, or forever if it's not refreshed.
+async fn tick(refresh: &mut Option<Interval>) {
+    match refresh {
+        Some(refresh) => {
+            refresh.tick().await;
+        }
+        None => std::future::pending().await,
+    }
 }
 
 fn process_messages(
@@ -299,6 +550,7 @@ fn process_messages(
     topic_metadata: &TopicMetadata,
     messages: Vec<DecodedMessage>,
     transforms: &Vec<Arc<dyn Transform>>,
+    metrics: &ConnectorMetrics,
 ) -> Result<Vec<IggyMessage>, Error> {
     let mut iggy_messages = Vec::with_capacity(messages.len());
     for message in messages {
@@ -313,14 +565,20 @@ fn process_messages(
 
         // The transform may return no message based on some conditions
         let Some(message) = current_message else {
+            metrics.record_filtered(1);
             continue;
         };
 
+        if !transforms.is_empty() {
+            metrics.record_transformed(1);
+        }
+
         let Ok(payload) = encoder.encode(message.payload) else {
             error!(
                 "Failed to encode message payload for source connector with ID: {id}, stream: {}, topic: {}",
                 topic_metadata.stream, topic_metadata.topic
             );
+            metrics.record_failed(1);
             continue;
         };
 
@@ -329,6 +587,7 @@ fn process_messages(
                 "Failed to build Iggy message for source connector with ID: {id}, stream: {}, topic: {}",
                 topic_metadata.stream, topic_metadata.topic
             );
+            metrics.record_failed(1);
             continue;
         };
 
@@ -351,7 +610,7 @@ extern "C" fn handle_produced_messages(
                 );
                 return;
             };
-            let _ = sender.send(messages);
+            let _ = sender.send((messages, Instant::now()));
         }
     }
 }
@@ -15,8 +15,13 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use std::io::SeekFrom;
+use std::{collections::HashMap, io::SeekFrom, str::FromStr, sync::Arc, time::Duration};
 
+use iggy::prelude::{
+    Consumer, HeaderKey, HeaderValue, Identifier, IggyClient, IggyDuration, IggyExpiry,
+    IggyMessage, MaxTopicSize, MessageClient, Partitioning, PollingStrategy, StreamClient,
+    TopicClient,
+};
 use iggy_connector_sdk::{ConnectorState, Error};
 use strum::Display;
 use tokio::{
@@ -24,7 +29,23 @@ use tokio::{
     io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt},
     sync::Mutex,
 };
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
+
+use crate::{
+    configs::{StateConfig, StateStorageKind},
+    error::RuntimeError,
+    stream::IggyClients,
+};
+
+const DEFAULT_STATE_STREAM: &str = "iggy_connectors";
+const DEFAULT_STATE_TOPIC: &str = "connectors_state";
+const STATE_PARTITION_ID: u32 = 1;
+const STATE_POLL_BATCH_LENGTH: u32 = 1000;
+const STATE_KEY_HEADER: &str = "iggy_state_key";
+const STATE_EMPTY_HEADER: &str = "iggy_state_empty";
+const DEFAULT_STATE_MESSAGE_EXPIRY: &str = "none";
+const DEFAULT_STATE_MAX_TOPIC_SIZE: &str = "2 GiB";
+const DEFAULT_STATE_REFRESH_INTERVAL: &str = "1h";
 
 pub trait StateProvider {
     async fn load(&self) -> Result<Option<ConnectorState>, Error>;
@@ -36,6 +57,144 @@ pub trait StateProvider {
 pub enum StateStorage {
     #[strum(to_string = "file")]
     File(FileStateProvider),
+    #[strum(to_string = "iggy")]
+    Iggy(IggyStateProvider),
+}
+
+impl StateStorage {
+    pub fn new(config: &StateConfig, key: &str, iggy_clients: &IggyClients) -> Self {
+        match config.storage {
+            StateStorageKind::File => StateStorage::File(FileStateProvider::new(format!(
+                "{}/source_{key}.state",
+                config.path
+            ))),
+            StateStorageKind::Iggy => StateStorage::Iggy(IggyStateProvider::new(
+                key.to_owned(),
+                config,
+                iggy_clients.producer.clone(),
+            )),
+        }
+    }
+
+    /// The interval of saving again the unchanged state, `None` if it's not needed or disabled.
+    pub fn refresh_interval(&self) -> Option<Duration> {
+        match self {
+            StateStorage::File(_) => None,
+            StateStorage::Iggy(iggy) => iggy.refresh_interval,
+        }
+    }
+
+    /// Saves again the last loaded or saved state, so that it's not removed from the state topic by its retention.
+    pub async fn refresh(&self) -> Result<(), Error> {
+        match self {
+            StateStorage::File(_) => Ok(()),
+            StateStorage::Iggy(iggy) => iggy.refresh().await,
+        }
+    }
+}
+
+impl StateProvider for StateStorage {
+    async fn load(&self) -> Result<Option<ConnectorState>, Error> {
+        match self {
+            StateStorage::File(file) => file.load().await,
+            StateStorage::Iggy(iggy) => iggy.load().await,
+        }
+    }
+
+    async fn save(&self, state: ConnectorState) -> Result<(), Error> {
+        match self {
+            StateStorage::File(file) => file.save(state).await,
+            StateStorage::Iggy(iggy) => iggy.save(state).await,
+        }
+    }
+}
+
+/// Creates the internal stream and topic for the connectors state, if the Iggy state storage is enabled.
+pub async fn init(config: &StateConfig, iggy_clients: &IggyClients) -> Result<(), RuntimeError> {
+    if config.storage != StateStorageKind::Iggy {
+        std::fs::create_dir_all(&config.path).map_err(|error| {
+            RuntimeError::InvalidConfiguration(format!(
+                "cannot create state directory: {}. {error}",
+                config.path
+            ))
+        })?;
+        info!("State will be stored in: {}", config.path);
+        return Ok(());
+    }
+
+    let stream = config.stream.as_deref().unwrap_or(DEFAULT_STATE_STREAM);
+    let topic = config.topic.as_deref().unwrap_or(DEFAULT_STATE_TOPIC);
+    let message_expiry = config
+        .message_expiry
+        .as_deref()
+        .unwrap_or(DEFAULT_STATE_MESSAGE_EXPIRY);
+    let message_expiry = IggyExpiry::from_str(message_expiry).map_err(|_| {
+        RuntimeError::InvalidConfiguration(format!("state.message_expiry: {message_expiry}"))
+    })?;
+    let max_topic_size = config
+        .max_topic_size
+        .as_deref()
+        .unwrap_or(DEFAULT_STATE_MAX_TOPIC_SIZE);
+    let max_topic_size = MaxTopicSize::from_str(max_topic_size).map_err(|_| {
+        RuntimeError::InvalidConfiguration(format!("state.max_topic_size: {max_topic_size}"))
+    })?;
+    let refresh_interval = get_refresh_interval(config)?;
+    if let IggyExpiry::ExpireDuration(expiry) = message_expiry {
+        warn_if_state_expires(refresh_interval, expiry);
+    }
+
+    let client = &iggy_clients.producer;
+    let stream_id = Identifier::from_str(stream)?;
+    if client.get_stream(&stream_id).await?.is_none() {
+        client.create_stream(stream, None).await?;
+        info!("Created state stream: {stream}");
+    }
+
+    if client
+        .get_topic(&stream_id, &Identifier::from_str(topic)?)
+        .await?
+        .is_none()
+    {
+        client
+            .create_topic(
+                &stream_id,
+                topic,
+                1,
+                Default::default(),
+                None,
+                None,
+                message_expiry,
+                max_topic_size,
+            )
+            .await?;
+        info!(
+            "Created state topic: {topic} in stream: {stream}, message expiry: {message_expiry}, max topic size: {max_topic_size}"
+        );
+    }
+
+    info!(
+        "State will be stored in stream: {stream}, topic: {topic}, refresh interval: {refresh_interval}"
+    );
+    Ok(())
+}
+
+/// The state of the idle source is saved again every refresh interval, so it must be shorter than the message expiry.
+fn warn_if_state_expires(refresh_interval: IggyDuration, expiry: IggyDuration) {
+    if refresh_interval.is_zero() || refresh_interval.get_duration() >= expiry.get_duration() {
+        warn!(
+            "State refresh interval: {refresh_interval} is not shorter than the message expiry: {expiry}, the state of the idle source connectors will expire."
+        );
+    }
+}
+
+fn get_refresh_interval(config: &StateConfig) -> Result<IggyDuration, RuntimeError> {
+    let refresh_interval = config
+        .refresh_interval
+        .as_deref()
+        .unwrap_or(DEFAULT_STATE_REFRESH_INTERVAL);
+    IggyDuration::from_str(refresh_interval).map_err(|_| {
+        RuntimeError::InvalidConfiguration(format!("state.refresh_interval: {refresh_interval}"))
+    })
 }
 
 #[derive(Debug)]
@@ -104,3 +263,302 @@ impl StateProvider for FileStateProvider {
         Ok(())
     }
 }
+
+/// Stores the state as the messages appended to the internal topic, each one with the connector key header,
+/// so that the latest entry for the given key is the current state, no matter which runtime instance saved it.
+/// The topic is bounded by its retention, so the state is loaded by scanning it backwards from the tail,
+/// and saved again once loaded and then periodically, so that the state of the idle connector isn't removed.
+#[derive(Debug)]
+pub struct IggyStateProvider {
+    key: String,
+    stream: String,
+    topic: String,
+    client: Arc<IggyClient>,
+    refresh_interval: Option<Duration>,
+    last_state: Mutex<Option<Vec<u8>>>,
+}
+
+impl IggyStateProvider {
+    pub fn new(key: String, config: &StateConfig, client: Arc<IggyClient>) -> Self {
+        IggyStateProvider {
+            key,
+            stream: config
+                .stream
+                .as_deref()
+                .unwrap_or(DEFAULT_STATE_STREAM)
+                .to_owned(),
+            topic: config
+                .topic
+                .as_deref()
+                .unwrap_or(DEFAULT_STATE_TOPIC)
+                .to_owned(),
+            client,
+            refresh_interval: get_refresh_interval(config)
+                .ok()
+                .filter(|interval| !interval.is_zero())
+                .map(|interval| interval.get_duration()),
+            last_state: Mutex::new(None),
+        }
+    }
+
+    async fn refresh(&self) -> Result<(), Error> {
+        let Some(state) = self.last_state.lock().await.clone() else {
+            return Ok(());
+        };
+
+        self.save(ConnectorState(state)).await?;
+        debug!(
+            "Refreshed state for key: {} in stream: {}, topic: {}",
+            self.key, self.stream, self.topic
+        );
+        Ok(())
+    }
+
+    fn identifiers(&self) -> Result<(Identifier, Identifier), Error> {
+        let stream = Identifier::from_str(&self.stream).map_err(|error| {
+            error!("Invalid state stream: {}. {error}.", self.stream);
+            Error::InvalidState
+        })?;
+        let topic = Identifier::from_str(&self.topic).map_err(|error| {
+            error!("Invalid state topic: {}. {error}.", self.topic);
+            Error::InvalidState
+        })?;
+        Ok((stream, topic))
+    }
+
+    async fn poll_state_messages(
+        &self,
+        strategy: PollingStrategy,
+        count: u32,
+    ) -> Result<Vec<IggyMessage>, Error> {
+        let (stream, topic) = self.identifiers()?;
+        let polled_messages = self
+            .client
+            .poll_messages(
+                &stream,
+                &topic,
+                Some(STATE_PARTITION_ID),
+                &Consumer::default(),
+                &strategy,
+                count,
+                false,
+            )
+            .await
+            .map_err(|error| {
+                error!(
+                    "Cannot poll state for key: {} from stream: {}, topic: {}. {error}.",
+                    self.key, self.stream, self.topic
+                );
+                Error::CannotLoadState(error.to_string())
+            })?;
+        Ok(polled_messages.messages)
+    }
+}
+
+impl StateProvider for IggyStateProvider {
+    async fn load(&self) -> Result<Option<ConnectorState>, Error> {
+        let mut strategy = PollingStrategy::last();
+        let mut count = STATE_POLL_BATCH_LENGTH;
+        let mut end_offset = u64::MAX;
+        let message = loop {
+            let mut messages = self.poll_state_messages(strategy, count).await?;
+            messages.retain(|message| message.header.offset < end_offset);
+            let Some(first_offset) = messages.first().map(|message| message.header.offset) else {
+                if end_offset != u64::MAX && end_offset > 0 {
+                    warn!(
+                        "State for key: {} not found in stream: {}, topic: {}, but the messages before offset: {end_offset} were removed by the topic retention, the state might have been among them.",
+                        self.key, self.stream, self.topic
+                    );
+                }
+                break None;
+            };
+
+            if let Some(position) = find_latest_state(&messages, &self.key) {
+                break Some(messages.swap_remove(position));
+            }
+
+            end_offset = first_offset;
+            if end_offset == 0 {
+                break None;
+            }
+
+            let start_offset = end_offset.saturating_sub(STATE_POLL_BATCH_LENGTH as u64);
+            strategy = PollingStrategy::offset(start_offset);
+            count = (end_offset - start_offset) as u32;
+        };
+
+        let Some(message) = message else {
+            info!(
+                "State not found for key: {} in stream: {}, topic: {}",
+                self.key, self.stream, self.topic
+            );
+            return Ok(None);
+        };
+
+        info!(
+            "Loaded state for key: {} from stream: {}, topic: {} at offset: {}",
+            self.key, self.stream, self.topic, message.header.offset
+        );
+        let state = read_state(&message);
+        let refreshed_state = ConnectorState(
+            state
+                .as_ref()
+                .map(|state| state.0.clone())
+                .unwrap_or_default(),
+        );
+        if let Err(error) = self.save(refreshed_state).await {
+            warn!(
+                "Cannot refresh loaded state for key: {} in stream: {}, topic: {}. {error}.",
+                self.key, self.stream, self.topic
+            );
+        }
+        Ok(state)
+    }
+
+    async fn save(&self, state: ConnectorState) -> Result<(), Error> {
+        let (stream, topic) = self.identifiers()?;
+        let key = HeaderValue::from_str(&self.key).map_err(|error| {
+            error!("Invalid state key: {}. {error}.", self.key);
+            Error::InvalidState
+        })?;
+        let message = build_state_message(key, ConnectorState(state.0.clone()))?;
+
+        self.client
+            .send_messages(
+                &stream,
+                &topic,
+                &Partitioning::partition_id(STATE_PARTITION_ID),
+                &mut [message],
+            )
+            .await
+            .map_err(|error| {
+                error!(
+                    "Cannot save state for key: {} to stream: {}, topic: {}. {error}.",
+                    self.key, self.stream, self.topic
+                );
+                Error::CannotSaveState(error.to_string())
+            })?;
+
+        self.last_state.lock().await.replace(state.0);
+        debug!(
+            "Saved state for key: {} to stream: {}, topic: {}",
+            self.key, self.stream, self.topic
+        );
+        Ok(())
+    }
+}
+
+/// Returns the position of the latest state message saved for the given key.
+fn find_latest_state(messages: &[IggyMessage], key: &str) -> Option<usize> {
+    let key_header = HeaderKey::new(STATE_KEY_HEADER).ok()?;
+    messages.iter().rposition(|message| {
+        message.get_user_header(&key_header).is_ok_and(|value| {
+            value.is_some_and(|value| value.as_str().is_ok_and(|value| value == key))
+        })
+    })
+}
+
+/// The message payload can't be empty, so the empty state is saved as the placeholder byte with the empty state header.
+fn build_state_message(key: HeaderValue, state: ConnectorState) -> Result<IggyMessage, Error> {
+    let key_header = HeaderKey::new(STATE_KEY_HEADER).map_err(|_| Error::InvalidState)?;
+    let mut headers = HashMap::from([(key_header, key)]);
+    let payload = if state.0.is_empty() {
+        let empty_header = HeaderKey::new(STATE_EMPTY_HEADER).map_err(|_| Error::InvalidState)?;
+        headers.insert(
+            empty_header,
+            HeaderValue::from_bool(true).map_err(|_| Error::InvalidState)?,
+        );
+        vec![0]
+    } else {
+        state.0
+    };
+
+    IggyMessage::builder()
+        .payload(payload.into())
+        .user_headers(headers)
+        .build()
+        .map_err(|error| Error::CannotSaveState(error.to_string()))
+}
+
+fn read_state(message: &IggyMessage) -> Option<ConnectorState> {
+    let empty_header = HeaderKey::new(STATE_EMPTY_HEADER).ok()?;
+    if message
+        .get_user_header(&empty_header)
+        .is_ok_and(|value| value.is_some())
+    {
+        return None;
+    }
+
+    Some(ConnectorState(message.payload.to_vec()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn file_state_should_be_saved_and_loaded() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("source.state");
+        let provider = FileStateProvider::new(path.to_str().unwrap().to_owned());
+
+        assert!(provider.load().await.unwrap().is_none());
+        provider
+            .save(ConnectorState(b"longer state".to_vec()))
+            .await
+            .unwrap();
+        provider
+            .save(ConnectorState(b"state".to_vec()))
+            .await
+            .unwrap();
+
+        let provider = FileStateProvider::new(path.to_str().unwrap().to_owned());
+        let state = provider.load().await.unwrap().unwrap();
+        assert_eq!(state.0, b"state");
+    }
+
+    #[test]
+    fn latest_state_should_be_found_for_the_given_key() {
+        let messages = vec![
+            create_state_message("first", b"1"),
+            create_state_message("second", b"2"),
+            create_state_message("first", b"3"),
+            IggyMessage::builder()
+                .payload("other".into())
+                .build()
+                .unwrap(),
+        ];
+
+        let position = find_latest_state(&messages, "first").unwrap();
+        assert_eq!(messages[position].payload.as_ref(), b"3");
+        let position = find_latest_state(&messages, "second").unwrap();
+        assert_eq!(messages[position].payload.as_ref(), b"2");
+        assert!(find_latest_state(&messages, "third").is_none());
+    }
+
+    #[test]
+    fn state_message_should_be_read_back_given_non_empty_state() {
+        let message = create_state_message("key", b"state");
+
+        let state = read_state(&message).unwrap();
+
+        assert_eq!(state.0, b"state");
+    }
+
+    #[test]
+    fn state_message_should_be_read_as_no_state_given_empty_state() {
+        let message = create_state_message("key", b"");
+
+        assert!(!message.payload.is_empty());
+        assert!(read_state(&message).is_none());
+        assert!(find_latest_state(&[message], "key").is_some());
+    }
+
+    fn create_state_message(key: &str, state: &[u8]) -> IggyMessage {
+        build_state_message(
+            HeaderValue::from_str(key).unwrap(),
+            ConnectorState(state.to_vec()),
+        )
+        .unwrap()
+    }
+}
@@ -16,19 +16,27 @@
 // under the License.
 
 use iggy::prelude::{Client, IggyClient, IggyClientBuilder};
+use std::sync::Arc;
 use tracing::{error, info};
 
 use crate::{configs::IggyConfig, error::RuntimeError};
 
 pub struct IggyClients {
-    pub producer: IggyClient,
-    pub consumer: IggyClient,
+    pub producer: Arc<IggyClient>,
+    config: IggyConfig,
+}
+
+impl IggyClients {
+    /// Creates a dedicated client for the sink consumers, as dropping a consumer with a pending poll
+    /// (e.g. when the sink is stopped) leaves its connection in an unusable state.
+    pub async fn create_consumer_client(&self) -> Result<IggyClient, RuntimeError> {
+        create_client(&self.config).await
+    }
 }
 
 pub async fn init(config: IggyConfig) -> Result<IggyClients, RuntimeError> {
-    let consumer = create_client(&config).await?;
-    let producer = create_client(&config).await?;
-    let iggy_clients = IggyClients { producer, consumer };
+    let producer = Arc::new(create_client(&config).await?);
+    let iggy_clients = IggyClients { producer, config };
     Ok(iggy_clients)
 }
 
@@ -62,6 +62,13 @@ pub trait Source: Send + Sync {
     /// Invoked every time a batch of messages is produced to the configured stream and topic.
     async fn poll(&self) -> Result<ProducedMessages, Error>;
 
+    /// Invoked once the messages produced along with the given state have been sent and the state has been saved by the runtime,
+    /// so that the source can release whatever it must retain until then (e.g. the changes not yet confirmed to the database).
+    /// The batches are sent in the order they were produced, and the failed one is retried rather than skipped.
+    async fn commit(&self, _state: ConnectorState) -> Result<(), Error> {
+        Ok(())
+    }
+
     /// Invoked when the source is closed, allowing it to perform any necessary cleanup.
     async fn close(&mut self) -> Result<(), Error>;
 }
@@ -74,6 +81,8 @@ pub trait Sink: Send + Sync {
     async fn open(&mut self) -> Result<(), Error>;
 
     /// Invoked every time a batch of messages is received from the configured stream(s) and topic(s).
+    /// The consumer offset is stored by the runtime only once this method returns `Ok`,
+    /// thus the messages must be durably written to the destination before returning.
     async fn consume(
         &self,
         topic_metadata: &TopicMetadata,
@@ -81,6 +90,17 @@ pub trait Sink: Send + Sync {
         messages: Vec<ConsumedMessage>,
     ) -> Result<(), Error>;
 
+    /// Invoked when the sink is started, for each partition of the configured topics, to return the offset of the last message
+    /// committed by the sink itself (e.g. stored transactionally along with the data in the destination).
+    /// If returned, the consumption is resumed from the next offset, otherwise from the consumer offset stored by the runtime.
+    async fn committed_offset(
+        &self,
+        _topic_metadata: &TopicMetadata,
+        _partition_id: u32,
+    ) -> Result<Option<u64>, Error> {
+        Ok(None)
+    }
+
     /// Invoked when the sink is closed, allowing it to perform any necessary cleanup.
     async fn close(&mut self) -> Result<(), Error>;
 }
@@ -316,4 +336,12 @@ pub enum Error {
     CannotWriteStateFile,
     #[error("Invalid state")]
     InvalidState,
+    #[error("Cannot load state: {0}")]
+    CannotLoadState(String),
+    #[error("Cannot save state: {0}")]
+    CannotSaveState(String),
+    #[error("Cannot store data: {0}")]
+    CannotStoreData(String),
+    #[error("Cannot fetch data: {0}")]
+    CannotFetchData(String),
 }
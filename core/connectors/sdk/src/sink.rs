@@ -23,6 +23,14 @@ use tracing_subscriber::{EnvFilter, Registry, layer::SubscriberExt, util::Subscr
 
 use crate::{ConsumedMessage, MessagesMetadata, RawMessages, Sink, TopicMetadata, get_runtime};
 
+pub type CommittedOffsetCallback = extern "C" fn(
+    plugin_id: u32,
+    topic_meta_ptr: *const u8,
+    topic_meta_len: usize,
+    partition_id: u32,
+    offset_ptr: *mut u64,
+) -> i32;
+
 pub type ConsumeCallback = extern "C" fn(
     plugin_id: u32,
     topic_meta_ptr: *const u8,
@@ -204,6 +212,53 @@ impl<T: Sink + std::fmt::Debug> SinkContainer<T> {
             if result.is_ok() { 0 } else { 1 }
         }
     }
+
+    /// Writes the offset committed by the sink for the given partition and returns `0`, or `1` if there's no such offset.
+    ///
+    /// # Safety
+    /// Do not copy the pointer to the topic metadata, the offset pointer must be valid for writes.
+    pub unsafe fn committed_offset(
+        &self,
+        topic_meta_ptr: *const u8,
+        topic_meta_len: usize,
+        partition_id: u32,
+        offset_ptr: *mut u64,
+    ) -> i32 {
+        unsafe {
+            let Some(sink) = self.sink.as_ref() else {
+                error!(
+                    "Sink connector with ID: {} is not initialized - cannot get committed offset.",
+                    self.id
+                );
+                return -1;
+            };
+
+            let topic_meta_slice = std::slice::from_raw_parts(topic_meta_ptr, topic_meta_len);
+            let Ok(topic_metadata) = postcard::from_bytes::<TopicMetadata>(topic_meta_slice) else {
+                error!(
+                    "Failed to decode topic metadata by sink connector with ID: {}",
+                    self.id
+                );
+                return -1;
+            };
+
+            let runtime = get_runtime();
+            match runtime.block_on(sink.committed_offset(&topic_metadata, partition_id)) {
+                Ok(Some(offset)) => {
+                    *offset_ptr = offset;
+                    0
+                }
+                Ok(None) => 1,
+                Err(error) => {
+                    error!(
+                        "Failed to get committed offset by sink connector with ID: {} for stream: {}, topic: {}, partition: {partition_id}. {error}",
+                        self.id, topic_metadata.stream, topic_metadata.topic
+                    );
+                    -1
+                }
+            }
+        }
+    }
 }
 
 #[macro_export]
@@ -256,6 +311,24 @@ macro_rules! sink_connector {
             )
         }
 
+        #[cfg(not(test))]
+        #[unsafe(no_mangle)]
+        unsafe extern "C" fn committed_offset(
+            id: u32,
+            topic_meta_ptr: *const u8,
+            topic_meta_len: usize,
+            partition_id: u32,
+            offset_ptr: *mut u64,
+        ) -> i32 {
+            let Some(instance) = INSTANCES.get(&id) else {
+                tracing::error!(
+                    "Sink connector with ID: {id} was not found and committed offset cannot be returned."
+                );
+                return -1;
+            };
+            instance.committed_offset(topic_meta_ptr, topic_meta_len, partition_id, offset_ptr)
+        }
+
         #[cfg(not(test))]
         #[unsafe(no_mangle)]
         unsafe extern "C" fn close(id: u32) -> i32 {
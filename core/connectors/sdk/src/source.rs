@@ -140,6 +140,34 @@ impl<T: Source + std::fmt::Debug + 'static> SourceContainer<T> {
         0
     }
 
+    /// # Safety
+    /// Do not copy the pointer to the state.
+    pub unsafe fn commit(&self, state_ptr: *const u8, state_len: usize) -> i32 {
+        unsafe {
+            let Some(source) = self.source.as_ref() else {
+                error!(
+                    "Source connector with ID: {} is not initialized - cannot commit state.",
+                    self.id
+                );
+                return -1;
+            };
+
+            let state = std::slice::from_raw_parts(state_ptr, state_len);
+            let state = ConnectorState(state.to_vec());
+            let runtime = get_runtime();
+            match runtime.block_on(source.commit(state)) {
+                Ok(()) => 0,
+                Err(error) => {
+                    error!(
+                        "Failed to commit state by source connector with ID: {}. {error}",
+                        self.id
+                    );
+                    1
+                }
+            }
+        }
+    }
+
     /// # Safety
     /// Do not copy the pointer to the messages.
     pub unsafe fn handle(&mut self, callback: SendCallback) -> i32 {
@@ -245,6 +273,18 @@ macro_rules! source_connector {
             instance.handle(callback)
         }
 
+        #[cfg(not(test))]
+        #[unsafe(no_mangle)]
+        unsafe extern "C" fn commit(id: u32, state_ptr: *const u8, state_len: usize) -> i32 {
+            let Some(instance) = INSTANCES.get(&id) else {
+                tracing::error!(
+                    "Source connector with ID: {id} was not found and state cannot be committed."
+                );
+                return -1;
+            };
+            instance.commit(state_ptr, state_len)
+        }
+
         #[cfg(not(test))]
         #[unsafe(no_mangle)]
         unsafe extern "C" fn close(id: u32) -> i32 {
@@ -0,0 +1,282 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use async_trait::async_trait;
+use base64::{Engine, engine::general_purpose::STANDARD};
+use iggy::prelude::IggyByteSize;
+use iggy_connector_sdk::{
+    ConsumedMessage, Error, MessagesMetadata, Payload, Sink, TopicMetadata, sink_connector,
+};
+use partition::{DEFAULT_PARTITION_PATH, PartitionPath};
+use serde::{Deserialize, Serialize};
+use std::{path::PathBuf, str::FromStr, sync::Arc, time::Duration};
+use storage::{S3Config, Storage};
+use tokio::{sync::Mutex, task::JoinHandle};
+use tracing::{error, info};
+use writer::{FileFormat, FileWriter, ParquetCompression};
+
+mod partition;
+mod storage;
+mod writer;
+
+sink_connector!(FileSink);
+
+const DEFAULT_MAX_FILE_SIZE: &str = "64 MB";
+const DEFAULT_MAX_FILE_AGE: &str = "5m";
+const DEFAULT_S3_STAGING_DIR: &str = "file_sink_staging";
+const ROLL_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug)]
+pub struct FileSink {
+    pub id: u32,
+    config: FileSinkConfig,
+    writer: Option<Arc<FileWriter>>,
+    partition_path: Option<PartitionPath>,
+    roller: Option<JoinHandle<()>>,
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileSinkConfig {
+    pub path: String,
+    pub format: Option<FileFormat>,
+    pub partition_path: Option<String>,
+    pub max_file_size: Option<String>,
+    pub max_file_age: Option<String>,
+    pub staging_dir: Option<String>,
+    pub include_metadata: Option<bool>,
+    pub compression: Option<ParquetCompression>,
+    pub s3: Option<S3Config>,
+}
+
+#[derive(Debug)]
+struct State {
+    messages_processed: u64,
+    write_errors: u64,
+}
+
+impl FileSink {
+    pub fn new(id: u32, config: FileSinkConfig) -> Self {
+        FileSink {
+            id,
+            config,
+            writer: None,
+            partition_path: None,
+            roller: None,
+            state: Mutex::new(State {
+                messages_processed: 0,
+                write_errors: 0,
+            }),
+        }
+    }
+
+    fn create_writer(&self) -> Result<FileWriter, Error> {
+        let max_file_size = self
+            .config
+            .max_file_size
+            .as_deref()
+            .unwrap_or(DEFAULT_MAX_FILE_SIZE);
+        let max_file_size = IggyByteSize::from_str(max_file_size)
+            .map_err(|e| Error::InitError(format!("Invalid max file size: {max_file_size}. {e}")))?
+            .as_bytes_u64();
+
+        let max_file_age = self
+            .config
+            .max_file_age
+            .as_deref()
+            .unwrap_or(DEFAULT_MAX_FILE_AGE);
+        let max_file_age = humantime::Duration::from_str(max_file_age)
+            .map_err(|e| Error::InitError(format!("Invalid max file age: {max_file_age}. {e}")))?
+            .into();
+
+        // The staging files are published by renaming, so by default they're kept on the same file system.
+        let staging_dir = match self.config.staging_dir.as_ref() {
+            Some(staging_dir) => PathBuf::from(staging_dir),
+            None if self.config.s3.is_some() => PathBuf::from(DEFAULT_S3_STAGING_DIR),
+            None => PathBuf::from(&self.config.path).join(".staging"),
+        };
+
+        let storage = Storage::new(&self.config.path, self.config.s3.as_ref())?;
+        Ok(FileWriter::new(
+            self.config.format.unwrap_or_default(),
+            self.config.compression.unwrap_or_default(),
+            staging_dir,
+            storage,
+            max_file_size,
+            max_file_age,
+        ))
+    }
+
+    fn build_record(
+        &self,
+        topic_metadata: &TopicMetadata,
+        messages_metadata: &MessagesMetadata,
+        message: ConsumedMessage,
+    ) -> Result<serde_json::Value, Error> {
+        let value = match message.payload {
+            Payload::Json(value) => {
+                let bytes = simd_json::to_vec(&value).map_err(|_| Error::InvalidJsonPayload)?;
+                serde_json::from_slice(&bytes).map_err(|_| Error::InvalidJsonPayload)?
+            }
+            Payload::Text(text) | Payload::Proto(text) => serde_json::Value::String(text),
+            Payload::Raw(bytes) | Payload::FlatBuffer(bytes) => {
+                serde_json::Value::String(STANDARD.encode(bytes))
+            }
+        };
+
+        let mut record = match value {
+            serde_json::Value::Object(record) => record,
+            value => serde_json::Map::from_iter([("payload".to_owned(), value)]),
+        };
+
+        if self.config.include_metadata.unwrap_or(false) {
+            record.insert(
+                "iggy_id".to_owned(),
+                serde_json::Value::String(format!("{:032x}", message.id)),
+            );
+            record.insert("iggy_offset".to_owned(), message.offset.into());
+            record.insert("iggy_timestamp".to_owned(), message.timestamp.into());
+            record.insert(
+                "iggy_stream".to_owned(),
+                topic_metadata.stream.clone().into(),
+            );
+            record.insert("iggy_topic".to_owned(), topic_metadata.topic.clone().into());
+            record.insert(
+                "iggy_partition_id".to_owned(),
+                messages_metadata.partition_id.into(),
+            );
+        }
+
+        Ok(serde_json::Value::Object(record))
+    }
+
+    async fn write_messages(
+        &self,
+        topic_metadata: &TopicMetadata,
+        messages_metadata: &MessagesMetadata,
+        messages: Vec<ConsumedMessage>,
+    ) -> Result<(), Error> {
+        let writer = self
+            .writer
+            .as_ref()
+            .ok_or_else(|| Error::InitError("File writer not initialized".to_string()))?;
+        let partition_path = self
+            .partition_path
+            .as_ref()
+            .ok_or_else(|| Error::InitError("Partition path not initialized".to_string()))?;
+
+        // The messages are grouped by the partition path, keeping their order within each group.
+        let mut groups: Vec<(String, Vec<u8>)> = Vec::new();
+        for message in messages {
+            let path = partition_path.resolve(topic_metadata, messages_metadata, &message);
+            let record = self.build_record(topic_metadata, messages_metadata, message)?;
+            let index = match groups.iter().position(|(group, _)| *group == path) {
+                Some(index) => index,
+                None => {
+                    groups.push((path, Vec::new()));
+                    groups.len() - 1
+                }
+            };
+            let lines = &mut groups[index].1;
+            serde_json::to_writer(&mut *lines, &record).map_err(|_| Error::InvalidRecord)?;
+            lines.push(b'\n');
+        }
+
+        writer.write(&groups).await
+    }
+}
+
+#[async_trait]
+impl Sink for FileSink {
+    async fn open(&mut self) -> Result<(), Error> {
+        info!(
+            "Opening file sink connector with ID: {}, path: {}, format: {}",
+            self.id,
+            self.config.path,
+            self.config.format.unwrap_or_default().extension()
+        );
+
+        let partition_path = self
+            .config
+            .partition_path
+            .as_deref()
+            .unwrap_or(DEFAULT_PARTITION_PATH);
+        self.partition_path = Some(PartitionPath::from_str(partition_path)?);
+
+        let writer = Arc::new(self.create_writer()?);
+        writer.init().await?;
+
+        let roller = writer.clone();
+        self.roller = Some(tokio::spawn(async move {
+            let mut interval = tokio::time::interval(ROLL_CHECK_INTERVAL);
+            loop {
+                interval.tick().await;
+                roller.roll_expired().await;
+            }
+        }));
+        self.writer = Some(writer);
+
+        info!(
+            "File sink connector with ID: {} opened successfully",
+            self.id
+        );
+        Ok(())
+    }
+
+    async fn consume(
+        &self,
+        topic_metadata: &TopicMetadata,
+        messages_metadata: MessagesMetadata,
+        messages: Vec<ConsumedMessage>,
+    ) -> Result<(), Error> {
+        let messages_count = messages.len() as u64;
+        let result = self
+            .write_messages(topic_metadata, &messages_metadata, messages)
+            .await;
+
+        let mut state = self.state.lock().await;
+        if let Err(error) = result {
+            state.write_errors += messages_count;
+            error!(
+                "Failed to write messages to file sink with ID: {}. {error}",
+                self.id
+            );
+            return Err(error);
+        }
+
+        state.messages_processed += messages_count;
+        Ok(())
+    }
+
+    async fn close(&mut self) -> Result<(), Error> {
+        if let Some(roller) = self.roller.take() {
+            roller.abort();
+        }
+
+        if let Some(writer) = self.writer.take() {
+            writer.roll_all().await;
+        }
+
+        let state = self.state.lock().await;
+        info!(
+            "File sink connector with ID: {} closed. Processed: {} messages, errors: {}",
+            self.id, state.messages_processed, state.write_errors
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,249 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use chrono::{DateTime, Utc};
+use iggy_connector_sdk::{ConsumedMessage, Error, MessagesMetadata, TopicMetadata};
+use std::str::FromStr;
+
+pub const DEFAULT_PARTITION_PATH: &str = "{stream}/{topic}/{date}";
+const UNKNOWN_VALUE: &str = "unknown";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    Stream,
+    Topic,
+    PartitionId,
+    Date,
+    Hour,
+    Header(String),
+}
+
+/// The template of the directory path, under which the files are written, e.g. `{stream}/{topic}/{date}`.
+#[derive(Debug, Clone)]
+pub struct PartitionPath {
+    segments: Vec<Segment>,
+}
+
+impl FromStr for PartitionPath {
+    type Err = Error;
+
+    fn from_str(template: &str) -> Result<Self, Self::Err> {
+        let mut segments = Vec::new();
+        let mut rest = template.trim_matches('/');
+        while let Some(start) = rest.find('{') {
+            if start > 0 {
+                segments.push(Segment::Literal(rest[..start].to_owned()));
+            }
+
+            let Some(end) = rest[start..].find('}') else {
+                return Err(Error::InitError(format!(
+                    "Unclosed placeholder in partition path: {template}"
+                )));
+            };
+            let placeholder = &rest[start + 1..start + end];
+            let segment = match placeholder {
+                "stream" => Segment::Stream,
+                "topic" => Segment::Topic,
+                "partition_id" => Segment::PartitionId,
+                "date" => Segment::Date,
+                "hour" => Segment::Hour,
+                _ => match placeholder.strip_prefix("header.") {
+                    Some(header) if !header.is_empty() => Segment::Header(header.to_owned()),
+                    _ => {
+                        return Err(Error::InitError(format!(
+                            "Unknown placeholder: {{{placeholder}}} in partition path: {template}"
+                        )));
+                    }
+                },
+            };
+            segments.push(segment);
+            rest = &rest[start + end + 1..];
+        }
+
+        if !rest.is_empty() {
+            segments.push(Segment::Literal(rest.to_owned()));
+        }
+
+        let has_invalid_literal = segments.iter().any(|segment| match segment {
+            Segment::Literal(literal) => literal.split('/').any(|part| part == "." || part == ".."),
+            _ => false,
+        });
+        if has_invalid_literal {
+            return Err(Error::InitError(format!(
+                "Relative components are not allowed in partition path: {template}"
+            )));
+        }
+
+        Ok(PartitionPath { segments })
+    }
+}
+
+impl PartitionPath {
+    pub fn resolve(
+        &self,
+        topic_metadata: &TopicMetadata,
+        messages_metadata: &MessagesMetadata,
+        message: &ConsumedMessage,
+    ) -> String {
+        let timestamp = DateTime::<Utc>::from_timestamp_micros(message.timestamp as i64)
+            .unwrap_or_else(Utc::now);
+        let mut path = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(literal) => path.push_str(literal),
+                Segment::Stream => path.push_str(&sanitize(&topic_metadata.stream)),
+                Segment::Topic => path.push_str(&sanitize(&topic_metadata.topic)),
+                Segment::PartitionId => path.push_str(&messages_metadata.partition_id.to_string()),
+                Segment::Date => path.push_str(&timestamp.format("%Y-%m-%d").to_string()),
+                Segment::Hour => path.push_str(&timestamp.format("%H").to_string()),
+                Segment::Header(name) => {
+                    let value = message
+                        .headers
+                        .as_ref()
+                        .and_then(|headers| {
+                            headers
+                                .iter()
+                                .find(|(key, _)| key.as_str() == name)
+                                .map(|(_, value)| value.value_only_to_string())
+                        })
+                        .unwrap_or_else(|| UNKNOWN_VALUE.to_owned());
+                    path.push_str(&sanitize(&value));
+                }
+            }
+        }
+        path
+    }
+}
+
+/// The values are coming from the messages, so they must not escape the output directory.
+fn sanitize(value: &str) -> String {
+    let value = value
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '=') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+
+    match value.as_str() {
+        "" => UNKNOWN_VALUE.to_owned(),
+        "." | ".." => value.replace('.', "_"),
+        _ => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iggy::prelude::{HeaderKey, HeaderValue};
+    use iggy_connector_sdk::{Payload, Schema};
+    use std::collections::HashMap;
+
+    // 2024-03-05T07:08:09Z
+    const TIMESTAMP: u64 = 1_709_622_489_000_000;
+
+    #[test]
+    fn path_should_be_resolved_given_all_placeholders() {
+        let partition_path =
+            PartitionPath::from_str("/data/{stream}/{topic}/p{partition_id}/{date}/{hour}/")
+                .unwrap();
+        let headers = HashMap::from([(
+            HeaderKey::new("tenant").unwrap(),
+            HeaderValue::from_str("acme").unwrap(),
+        )]);
+        let path = partition_path.resolve(&topic_metadata(), &messages_metadata(), &message(None));
+        assert_eq!(path, "data/orders/created/p3/2024-03-05/07");
+
+        let partition_path = PartitionPath::from_str("tenant={header.tenant}").unwrap();
+        let path = partition_path.resolve(
+            &topic_metadata(),
+            &messages_metadata(),
+            &message(Some(headers)),
+        );
+        assert_eq!(path, "tenant=acme");
+    }
+
+    #[test]
+    fn missing_header_should_be_resolved_as_unknown() {
+        let partition_path = PartitionPath::from_str("{header.tenant}").unwrap();
+        let path = partition_path.resolve(&topic_metadata(), &messages_metadata(), &message(None));
+        assert_eq!(path, UNKNOWN_VALUE);
+    }
+
+    #[test]
+    fn header_value_should_be_sanitized() {
+        let partition_path = PartitionPath::from_str("{header.tenant}/{stream}").unwrap();
+        let headers = HashMap::from([(
+            HeaderKey::new("tenant").unwrap(),
+            HeaderValue::from_str("../../etc").unwrap(),
+        )]);
+        let metadata = TopicMetadata {
+            stream: "..".to_owned(),
+            topic: "topic".to_owned(),
+        };
+        let path = partition_path.resolve(&metadata, &messages_metadata(), &message(Some(headers)));
+        assert_eq!(path, ".._.._etc/__");
+    }
+
+    #[test]
+    fn template_should_be_rejected_given_invalid_placeholder_or_relative_component() {
+        for template in [
+            "{stream",
+            "{unknown}",
+            "{header.}",
+            "../{stream}",
+            "{stream}/./{topic}",
+        ] {
+            assert!(
+                PartitionPath::from_str(template).is_err(),
+                "template: {template}"
+            );
+        }
+    }
+
+    fn topic_metadata() -> TopicMetadata {
+        TopicMetadata {
+            stream: "orders".to_owned(),
+            topic: "created".to_owned(),
+        }
+    }
+
+    fn messages_metadata() -> MessagesMetadata {
+        MessagesMetadata {
+            partition_id: 3,
+            current_offset: 0,
+            schema: Schema::Json,
+        }
+    }
+
+    fn message(headers: Option<HashMap<HeaderKey, HeaderValue>>) -> ConsumedMessage {
+        ConsumedMessage {
+            id: 1,
+            offset: 0,
+            checksum: 0,
+            timestamp: TIMESTAMP,
+            origin_timestamp: TIMESTAMP,
+            headers,
+            payload: Payload::Text("test".to_owned()),
+        }
+    }
+}
@@ -0,0 +1,174 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use iggy_connector_sdk::Error;
+use s3::{Bucket, Region, creds::Credentials};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use tracing::{debug, info};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct S3Config {
+    pub bucket: String,
+    pub key_id: String,
+    pub key_secret: String,
+    pub endpoint: Option<String>,
+    pub region: Option<String>,
+    /// Use the path-style URLs, required by some S3-compatible stores (e.g. MinIO).
+    pub path_style: Option<bool>,
+}
+
+/// The destination of the finalized files.
+#[derive(Debug)]
+pub enum Storage {
+    Local { base_path: PathBuf },
+    S3 { bucket: Box<Bucket>, prefix: String },
+}
+
+impl Storage {
+    pub fn new(path: &str, s3: Option<&S3Config>) -> Result<Self, Error> {
+        let Some(config) = s3 else {
+            return Ok(Storage::Local {
+                base_path: PathBuf::from(path),
+            });
+        };
+
+        let credentials = Credentials::new(
+            Some(&config.key_id),
+            Some(&config.key_secret),
+            None,
+            None,
+            None,
+        )
+        .map_err(|e| Error::InitError(format!("Invalid S3 credentials: {e}")))?;
+
+        let bucket = Bucket::new(
+            &config.bucket,
+            Region::Custom {
+                endpoint: config.endpoint.clone().unwrap_or_default(),
+                region: config.region.clone().unwrap_or_default(),
+            },
+            credentials,
+        )
+        .map_err(|e| Error::InitError(format!("Failed to initialize S3 bucket: {e}")))?;
+        let bucket = if config.path_style.unwrap_or(false) {
+            bucket.with_path_style()
+        } else {
+            bucket
+        };
+
+        Ok(Storage::S3 {
+            bucket,
+            prefix: path.trim_matches('/').to_owned(),
+        })
+    }
+
+    pub async fn init(&self) -> Result<(), Error> {
+        match self {
+            Storage::Local { base_path } => fs::create_dir_all(base_path).await.map_err(|e| {
+                Error::InitError(format!(
+                    "Failed to create directory: {}. {e}",
+                    base_path.display()
+                ))
+            }),
+            Storage::S3 { bucket, prefix } => {
+                bucket
+                    .list(prefix.clone(), Some("/".to_owned()))
+                    .await
+                    .map_err(|e| {
+                        Error::InitError(format!("Cannot access S3 bucket: {}. {e}", bucket.name))
+                    })?;
+                info!("Connected to S3 bucket: {}", bucket.name);
+                Ok(())
+            }
+        }
+    }
+
+    /// Publishes the local file under the given key, so that it becomes visible only once it's complete:
+    /// by renaming it within the same file system, or by uploading it as a single S3 object.
+    pub async fn publish(&self, file: &Path, key: &str) -> Result<(), Error> {
+        match self {
+            Storage::Local { base_path } => {
+                let destination = base_path.join(key);
+                if let Some(parent) = destination.parent() {
+                    fs::create_dir_all(parent).await.map_err(|e| {
+                        Error::CannotStoreData(format!(
+                            "Failed to create directory: {}. {e}",
+                            parent.display()
+                        ))
+                    })?;
+                }
+
+                if fs::rename(file, &destination).await.is_err() {
+                    // The staging directory is on the different file system, so the file is copied
+                    // next to the destination first, and then renamed.
+                    let temporary = destination.with_extension("tmp");
+                    fs::copy(file, &temporary).await.map_err(|e| {
+                        Error::CannotStoreData(format!(
+                            "Failed to copy file: {} to: {}. {e}",
+                            file.display(),
+                            temporary.display()
+                        ))
+                    })?;
+                    fs::rename(&temporary, &destination).await.map_err(|e| {
+                        Error::CannotStoreData(format!(
+                            "Failed to rename file: {} to: {}. {e}",
+                            temporary.display(),
+                            destination.display()
+                        ))
+                    })?;
+                    let _ = fs::remove_file(file).await;
+                }
+                debug!("Published file: {}", destination.display());
+                Ok(())
+            }
+            Storage::S3 { bucket, prefix } => {
+                let key = if prefix.is_empty() {
+                    key.to_owned()
+                } else {
+                    format!("{prefix}/{key}")
+                };
+                let mut reader = fs::File::open(file).await.map_err(|e| {
+                    Error::CannotStoreData(format!("Failed to open file: {}. {e}", file.display()))
+                })?;
+                let response = bucket
+                    .put_object_stream(&mut reader, &key)
+                    .await
+                    .map_err(|e| {
+                        Error::CannotStoreData(format!("Failed to upload object: {key}. {e}"))
+                    })?;
+                if response.status_code() >= 300 {
+                    return Err(Error::CannotStoreData(format!(
+                        "Failed to upload object: {key}, status code: {}",
+                        response.status_code()
+                    )));
+                }
+
+                fs::remove_file(file).await.map_err(|e| {
+                    Error::CannotStoreData(format!(
+                        "Failed to remove file: {}. {e}",
+                        file.display()
+                    ))
+                })?;
+                debug!("Published object: {key} to S3 bucket: {}", bucket.name);
+                Ok(())
+            }
+        }
+    }
+}
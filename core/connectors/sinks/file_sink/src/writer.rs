@@ -0,0 +1,752 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::storage::Storage;
+use iggy_connector_sdk::Error;
+use parquet::{
+    arrow::ArrowWriter,
+    basic::{Compression, ZstdLevel},
+    file::properties::WriterProperties,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    fs::File,
+    io::{BufRead, BufReader, Seek, SeekFrom},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use tokio::{fs, io::AsyncWriteExt, sync::Mutex};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+const STAGING_EXTENSION: &str = "inprogress";
+const PARQUET_BATCH_SIZE: usize = 8192;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileFormat {
+    #[default]
+    Jsonl,
+    Csv,
+    Parquet,
+}
+
+impl FileFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            FileFormat::Jsonl => "jsonl",
+            FileFormat::Csv => "csv",
+            FileFormat::Parquet => "parquet",
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParquetCompression {
+    None,
+    #[default]
+    Snappy,
+    Zstd,
+}
+
+impl ParquetCompression {
+    fn to_compression(self) -> Compression {
+        match self {
+            ParquetCompression::None => Compression::UNCOMPRESSED,
+            ParquetCompression::Snappy => Compression::SNAPPY,
+            ParquetCompression::Zstd => Compression::ZSTD(ZstdLevel::default()),
+        }
+    }
+}
+
+/// The file being currently written, always as JSON Lines, until it's rolled and finalized.
+#[derive(Debug)]
+struct StagingFile {
+    partition_path: String,
+    name: String,
+    path: PathBuf,
+    file: fs::File,
+    size: u64,
+    created_at: Instant,
+}
+
+/// The staging file that was closed but couldn't be published yet.
+#[derive(Debug)]
+struct ClosedFile {
+    partition_path: String,
+    name: String,
+    path: PathBuf,
+}
+
+#[derive(Debug)]
+pub struct FileWriter {
+    format: FileFormat,
+    compression: ParquetCompression,
+    staging_dir: PathBuf,
+    storage: Storage,
+    max_file_size: u64,
+    max_file_age: Duration,
+    files: Mutex<HashMap<String, StagingFile>>,
+    pending: Mutex<Vec<ClosedFile>>,
+}
+
+impl FileWriter {
+    pub fn new(
+        format: FileFormat,
+        compression: ParquetCompression,
+        staging_dir: PathBuf,
+        storage: Storage,
+        max_file_size: u64,
+        max_file_age: Duration,
+    ) -> Self {
+        FileWriter {
+            format,
+            compression,
+            staging_dir,
+            storage,
+            max_file_size,
+            max_file_age,
+            files: Mutex::new(HashMap::new()),
+            pending: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Prepares the storage and finalizes the staging files left by the previous run,
+    /// as their content was already acknowledged.
+    pub async fn init(&self) -> Result<(), Error> {
+        self.storage.init().await?;
+        fs::create_dir_all(&self.staging_dir).await.map_err(|e| {
+            Error::InitError(format!(
+                "Failed to create staging directory: {}. {e}",
+                self.staging_dir.display()
+            ))
+        })?;
+
+        let mut staging_files = Vec::new();
+        collect_staging_files(&self.staging_dir, &mut staging_files)
+            .await
+            .map_err(|e| {
+                Error::InitError(format!(
+                    "Failed to read staging directory: {}. {e}",
+                    self.staging_dir.display()
+                ))
+            })?;
+
+        for path in staging_files {
+            if path.extension().and_then(|e| e.to_str()) != Some(STAGING_EXTENSION) {
+                // The leftover of the interrupted conversion, it will be created again.
+                let _ = fs::remove_file(&path).await;
+                continue;
+            }
+
+            let Some(file) = self.recover_file(&path).await? else {
+                continue;
+            };
+
+            info!("Recovering staging file: {}", path.display());
+            if let Err(error) = self.finalize(&file).await {
+                error!(
+                    "Failed to finalize recovered file: {}. {error}",
+                    path.display()
+                );
+                self.pending.lock().await.push(file);
+            }
+        }
+        Ok(())
+    }
+
+    /// Appends the JSON Lines to the staging files of their partition paths, all or none of them:
+    /// if any append fails, the already appended ones are truncated back, so retrying the batch
+    /// doesn't duplicate the records. The data is synced to disk before returning, so it survives the restart.
+    pub async fn write(&self, batch: &[(String, Vec<u8>)]) -> Result<(), Error> {
+        let mut files = self.files.lock().await;
+        let mut appended = Vec::with_capacity(batch.len());
+        for (partition_path, lines) in batch {
+            match self.append(&mut files, partition_path, lines).await {
+                Ok(size) => appended.push((partition_path.as_str(), size)),
+                Err(error) => {
+                    for (partition_path, size) in appended {
+                        truncate(&mut files, partition_path, size).await;
+                    }
+                    return Err(error);
+                }
+            }
+        }
+
+        let full_keys = appended
+            .into_iter()
+            .filter(|(partition_path, _)| {
+                files
+                    .get(*partition_path)
+                    .is_some_and(|file| file.size >= self.max_file_size)
+            })
+            .map(|(partition_path, _)| partition_path)
+            .collect::<Vec<_>>();
+        let full = full_keys
+            .into_iter()
+            .filter_map(|partition_path| files.remove(partition_path))
+            .collect::<Vec<_>>();
+        drop(files);
+
+        for file in full {
+            self.roll(file).await;
+        }
+        Ok(())
+    }
+
+    /// Appends the lines to the staging file, and returns its size from before the append.
+    /// The failed append is truncated back, so no partial line is left in the file.
+    async fn append(
+        &self,
+        files: &mut HashMap<String, StagingFile>,
+        partition_path: &str,
+        lines: &[u8],
+    ) -> Result<u64, Error> {
+        if !files.contains_key(partition_path) {
+            let file = self.create_file(partition_path).await?;
+            files.insert(partition_path.to_owned(), file);
+        }
+
+        let Some(file) = files.get_mut(partition_path) else {
+            return Err(Error::CannotStoreData(format!(
+                "Missing staging file for: {partition_path}"
+            )));
+        };
+
+        let size = file.size;
+        let result = match file.file.write_all(lines).await {
+            Ok(()) => file.file.sync_data().await.map_err(|e| {
+                Error::CannotStoreData(format!("Failed to sync file: {}. {e}", file.path.display()))
+            }),
+            Err(e) => Err(Error::CannotStoreData(format!(
+                "Failed to write to file: {}. {e}",
+                file.path.display()
+            ))),
+        };
+
+        if let Err(error) = result {
+            truncate(files, partition_path, size).await;
+            return Err(error);
+        }
+
+        file.size += lines.len() as u64;
+        Ok(size)
+    }
+
+    /// Rolls the files older than the max file age and retries publishing the pending ones.
+    pub async fn roll_expired(&self) {
+        let expired = {
+            let mut files = self.files.lock().await;
+            let expired_keys = files
+                .iter()
+                .filter(|(_, file)| file.created_at.elapsed() >= self.max_file_age)
+                .map(|(key, _)| key.clone())
+                .collect::<Vec<_>>();
+            expired_keys
+                .into_iter()
+                .filter_map(|key| files.remove(&key))
+                .collect::<Vec<_>>()
+        };
+
+        for file in expired {
+            self.roll(file).await;
+        }
+        self.retry_pending().await;
+    }
+
+    pub async fn roll_all(&self) {
+        let files = self
+            .files
+            .lock()
+            .await
+            .drain()
+            .map(|(_, file)| file)
+            .collect::<Vec<_>>();
+        for file in files {
+            self.roll(file).await;
+        }
+        self.retry_pending().await;
+    }
+
+    async fn roll(&self, file: StagingFile) {
+        let StagingFile {
+            partition_path,
+            name,
+            path,
+            file,
+            size,
+            ..
+        } = file;
+        drop(file);
+        let file = ClosedFile {
+            partition_path,
+            name,
+            path,
+        };
+
+        if size == 0 {
+            let _ = fs::remove_file(&file.path).await;
+            return;
+        }
+
+        if let Err(error) = self.finalize(&file).await {
+            error!(
+                "Failed to finalize file: {}, it will be retried. {error}",
+                file.path.display()
+            );
+            self.pending.lock().await.push(file);
+        }
+    }
+
+    async fn retry_pending(&self) {
+        let mut pending = self.pending.lock().await;
+        let mut failed = Vec::new();
+        for file in pending.drain(..) {
+            if let Err(error) = self.finalize(&file).await {
+                warn!(
+                    "Failed to finalize file: {}, it will be retried. {error}",
+                    file.path.display()
+                );
+                failed.push(file);
+            }
+        }
+        *pending = failed;
+    }
+
+    /// Converts the staging file into the target format and publishes it under the deterministic key,
+    /// so finalizing the same file again (e.g. after the crash) overwrites the same object.
+    async fn finalize(&self, file: &ClosedFile) -> Result<(), Error> {
+        if self.format == FileFormat::Jsonl {
+            return self.publish(file, &file.path, FileFormat::Jsonl).await;
+        }
+
+        let output = file
+            .path
+            .with_extension(format!("{}.tmp", self.format.extension()));
+        let input = file.path.clone();
+        let output_path = output.clone();
+        let format = self.format;
+        let compression = self.compression;
+        let result = tokio::task::spawn_blocking(move || match format {
+            FileFormat::Csv => convert_to_csv(&input, &output_path),
+            FileFormat::Parquet => convert_to_parquet(&input, &output_path, compression),
+            FileFormat::Jsonl => Ok(()),
+        })
+        .await
+        .map_err(|e| Error::CannotStoreData(format!("Conversion task failed. {e}")))?;
+
+        if let Err(error) = result {
+            // Retrying won't help, so the records are published as they are instead of being lost.
+            error!(
+                "Failed to convert file: {} to {}, publishing it as JSON Lines. {error}",
+                file.path.display(),
+                self.format.extension()
+            );
+            let _ = fs::remove_file(&output).await;
+            return self.publish(file, &file.path, FileFormat::Jsonl).await;
+        }
+
+        self.publish(file, &output, self.format).await?;
+        let _ = fs::remove_file(&file.path).await;
+        Ok(())
+    }
+
+    async fn publish(
+        &self,
+        file: &ClosedFile,
+        path: &Path,
+        format: FileFormat,
+    ) -> Result<(), Error> {
+        let key = format!(
+            "{}/{}.{}",
+            file.partition_path,
+            file.name,
+            format.extension()
+        );
+        self.storage.publish(path, &key).await?;
+        info!("Finalized file: {key}");
+        Ok(())
+    }
+
+    async fn create_file(&self, partition_path: &str) -> Result<StagingFile, Error> {
+        let directory = self.staging_dir.join(partition_path);
+        fs::create_dir_all(&directory).await.map_err(|e| {
+            Error::CannotStoreData(format!(
+                "Failed to create directory: {}. {e}",
+                directory.display()
+            ))
+        })?;
+
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        let name = format!("{millis}-{}", Uuid::new_v4().simple());
+        let path = directory.join(format!("{name}.{STAGING_EXTENSION}"));
+        let file = fs::OpenOptions::new()
+            .create_new(true)
+            .append(true)
+            .open(&path)
+            .await
+            .map_err(|e| {
+                Error::CannotStoreData(format!("Failed to create file: {}. {e}", path.display()))
+            })?;
+
+        Ok(StagingFile {
+            partition_path: partition_path.to_owned(),
+            name,
+            path,
+            file,
+            size: 0,
+            created_at: Instant::now(),
+        })
+    }
+
+    /// Drops the partially written last line (if any), and removes the empty files.
+    async fn recover_file(&self, path: &Path) -> Result<Option<ClosedFile>, Error> {
+        let content = fs::read(path).await.map_err(|e| {
+            Error::InitError(format!("Failed to read file: {}. {e}", path.display()))
+        })?;
+        let length = content
+            .iter()
+            .rposition(|byte| *byte == b'\n')
+            .map(|position| position + 1)
+            .unwrap_or(0);
+
+        if length == 0 {
+            let _ = fs::remove_file(path).await;
+            return Ok(None);
+        }
+
+        if length < content.len() {
+            warn!(
+                "Truncating partially written line in file: {}",
+                path.display()
+            );
+            let file = fs::OpenOptions::new()
+                .write(true)
+                .open(path)
+                .await
+                .map_err(|e| {
+                    Error::InitError(format!("Failed to open file: {}. {e}", path.display()))
+                })?;
+            file.set_len(length as u64).await.map_err(|e| {
+                Error::InitError(format!("Failed to truncate file: {}. {e}", path.display()))
+            })?;
+            file.sync_all().await.map_err(|e| {
+                Error::InitError(format!("Failed to sync file: {}. {e}", path.display()))
+            })?;
+        }
+
+        let partition_path = path
+            .parent()
+            .and_then(|parent| parent.strip_prefix(&self.staging_dir).ok())
+            .map(|parent| parent.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        Ok(Some(ClosedFile {
+            partition_path,
+            name,
+            path: path.to_owned(),
+        }))
+    }
+}
+
+/// Truncates the staging file back to the given size. If that fails too, the file is no longer written,
+/// and its partially written line is dropped when it's recovered on the next start.
+async fn truncate(files: &mut HashMap<String, StagingFile>, partition_path: &str, size: u64) {
+    let Some(file) = files.get_mut(partition_path) else {
+        return;
+    };
+
+    let result = match file.file.set_len(size).await {
+        Ok(()) => file.file.sync_data().await,
+        Err(e) => Err(e),
+    };
+    match result {
+        Ok(()) => file.size = size,
+        Err(e) => {
+            error!(
+                "Failed to truncate file: {}, it will be recovered on the next start. {e}",
+                file.path.display()
+            );
+            files.remove(partition_path);
+        }
+    }
+}
+
+async fn collect_staging_files(directory: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    let mut directories = vec![directory.to_owned()];
+    while let Some(directory) = directories.pop() {
+        let mut entries = fs::read_dir(&directory).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            if entry.file_type().await?.is_dir() {
+                directories.push(entry.path());
+            } else {
+                files.push(entry.path());
+            }
+        }
+    }
+    Ok(())
+}
+
+fn read_records(
+    reader: &mut BufReader<File>,
+) -> impl Iterator<Item = Result<serde_json::Map<String, serde_json::Value>, String>> {
+    reader
+        .lines()
+        .filter(|line| line.as_ref().map(|line| !line.is_empty()).unwrap_or(true))
+        .map(|line| {
+            let line = line.map_err(|e| format!("Failed to read line. {e}"))?;
+            match serde_json::from_str(&line) {
+                Ok(serde_json::Value::Object(record)) => Ok(record),
+                Ok(_) => Err("Record is not a JSON object".to_owned()),
+                Err(e) => Err(format!("Invalid JSON record. {e}")),
+            }
+        })
+}
+
+/// The columns are the union of all the record fields, nested values are written as JSON.
+fn convert_to_csv(input: &Path, output: &Path) -> Result<(), String> {
+    let file = File::open(input).map_err(|e| format!("Failed to open file. {e}"))?;
+    let mut reader = BufReader::new(file);
+    let mut headers = Vec::new();
+    let mut known_headers = HashSet::new();
+    for record in read_records(&mut reader) {
+        for key in record?.keys() {
+            if known_headers.insert(key.clone()) {
+                headers.push(key.clone());
+            }
+        }
+    }
+
+    reader
+        .seek(SeekFrom::Start(0))
+        .map_err(|e| format!("Failed to seek file. {e}"))?;
+    let file = File::create(output).map_err(|e| format!("Failed to create file. {e}"))?;
+    let mut writer = csv::Writer::from_writer(file);
+    writer
+        .write_record(&headers)
+        .map_err(|e| format!("Failed to write CSV header. {e}"))?;
+    for record in read_records(&mut reader) {
+        let record = record?;
+        let row = headers.iter().map(|header| match record.get(header) {
+            None | Some(serde_json::Value::Null) => String::new(),
+            Some(serde_json::Value::String(value)) => value.clone(),
+            Some(value) => value.to_string(),
+        });
+        writer
+            .write_record(row)
+            .map_err(|e| format!("Failed to write CSV record. {e}"))?;
+    }
+
+    let file = writer
+        .into_inner()
+        .map_err(|e| format!("Failed to flush CSV file. {e}"))?;
+    file.sync_all()
+        .map_err(|e| format!("Failed to sync file. {e}"))
+}
+
+/// The schema is inferred from all the records in the file.
+fn convert_to_parquet(
+    input: &Path,
+    output: &Path,
+    compression: ParquetCompression,
+) -> Result<(), String> {
+    let file = File::open(input).map_err(|e| format!("Failed to open file. {e}"))?;
+    let mut reader = BufReader::new(file);
+    let (schema, _) = arrow_json::reader::infer_json_schema_from_seekable(&mut reader, None)
+        .map_err(|e| format!("Failed to infer schema. {e}"))?;
+    let schema = Arc::new(schema);
+    let batches = arrow_json::ReaderBuilder::new(schema.clone())
+        .with_batch_size(PARQUET_BATCH_SIZE)
+        .build(reader)
+        .map_err(|e| format!("Failed to read JSON records. {e}"))?;
+
+    let file = File::create(output).map_err(|e| format!("Failed to create file. {e}"))?;
+    let properties = WriterProperties::builder()
+        .set_compression(compression.to_compression())
+        .build();
+    let mut writer = ArrowWriter::try_new(file, schema, Some(properties))
+        .map_err(|e| format!("Failed to create Parquet writer. {e}"))?;
+    for batch in batches {
+        let batch = batch.map_err(|e| format!("Failed to read JSON records. {e}"))?;
+        writer
+            .write(&batch)
+            .map_err(|e| format!("Failed to write Parquet records. {e}"))?;
+    }
+
+    let file = writer
+        .into_inner()
+        .map_err(|e| format!("Failed to close Parquet file. {e}"))?;
+    file.sync_all()
+        .map_err(|e| format!("Failed to sync file. {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use tempfile::TempDir;
+
+    const LINES: &[u8] = b"{\"id\":1,\"name\":\"a\"}\n{\"id\":2,\"tags\":[\"x\"]}\n";
+
+    #[tokio::test]
+    async fn staging_files_should_be_recovered_on_init() {
+        let directory = TempDir::new().unwrap();
+        let writer = writer(&directory, FileFormat::Jsonl);
+        let partition = writer.staging_dir.join("stream/topic");
+        std::fs::create_dir_all(&partition).unwrap();
+        std::fs::write(
+            partition.join(format!("1-partial.{STAGING_EXTENSION}")),
+            b"{\"id\":1}\n{\"id\":",
+        )
+        .unwrap();
+        std::fs::write(
+            partition.join(format!("2-empty.{STAGING_EXTENSION}")),
+            b"{\"id\":",
+        )
+        .unwrap();
+        std::fs::write(partition.join("3-converted.parquet.tmp"), b"leftover").unwrap();
+
+        writer.init().await.unwrap();
+
+        let output = directory.path().join("output/stream/topic");
+        assert_eq!(
+            std::fs::read(output.join("1-partial.jsonl")).unwrap(),
+            b"{\"id\":1}\n"
+        );
+        assert_eq!(std::fs::read_dir(&output).unwrap().count(), 1);
+        assert_eq!(std::fs::read_dir(&partition).unwrap().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn batch_should_be_truncated_back_given_failed_write() {
+        let directory = TempDir::new().unwrap();
+        let writer = writer(&directory, FileFormat::Jsonl);
+        writer.init().await.unwrap();
+        writer
+            .write(&[("first".to_owned(), b"{\"id\":1}\n".to_vec())])
+            .await
+            .unwrap();
+        // The staging file can't be created, as its directory is blocked by the regular file.
+        std::fs::write(writer.staging_dir.join("blocked"), b"").unwrap();
+
+        let result = writer
+            .write(&[
+                ("first".to_owned(), b"{\"id\":2}\n".to_vec()),
+                ("blocked/second".to_owned(), b"{\"id\":3}\n".to_vec()),
+            ])
+            .await;
+        assert!(result.is_err());
+
+        let files = writer.files.lock().await;
+        let file = files.get("first").unwrap();
+        assert_eq!(file.size, 9);
+        assert_eq!(std::fs::read(&file.path).unwrap(), b"{\"id\":1}\n");
+        assert!(!files.contains_key("blocked/second"));
+    }
+
+    #[tokio::test]
+    async fn file_should_be_rolled_given_max_file_size_exceeded() {
+        let directory = TempDir::new().unwrap();
+        let mut writer = writer(&directory, FileFormat::Jsonl);
+        writer.max_file_size = LINES.len() as u64;
+        writer.init().await.unwrap();
+
+        writer
+            .write(&[("partition".to_owned(), LINES.to_vec())])
+            .await
+            .unwrap();
+
+        assert!(writer.files.lock().await.is_empty());
+        let output = directory.path().join("output/partition");
+        let entry = std::fs::read_dir(&output).unwrap().next().unwrap().unwrap();
+        assert_eq!(std::fs::read(entry.path()).unwrap(), LINES);
+    }
+
+    #[test]
+    fn records_should_be_converted_to_csv() {
+        let directory = TempDir::new().unwrap();
+        let input = directory.path().join("input.jsonl");
+        let output = directory.path().join("output.csv");
+        std::fs::write(&input, LINES).unwrap();
+
+        convert_to_csv(&input, &output).unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(&output).unwrap(),
+            "id,name,tags\n1,a,\n2,,\"[\"\"x\"\"]\"\n"
+        );
+    }
+
+    #[test]
+    fn records_should_be_converted_to_parquet() {
+        let directory = TempDir::new().unwrap();
+        let input = directory.path().join("input.jsonl");
+        let output = directory.path().join("output.parquet");
+        std::fs::write(&input, LINES).unwrap();
+
+        convert_to_parquet(&input, &output, ParquetCompression::Zstd).unwrap();
+
+        let reader = ParquetRecordBatchReaderBuilder::try_new(File::open(&output).unwrap())
+            .unwrap()
+            .build()
+            .unwrap();
+        let batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        let schema = batches[0].schema();
+        let columns = schema
+            .fields()
+            .iter()
+            .map(|field| field.name().as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(columns, ["id", "name", "tags"]);
+        assert_eq!(
+            batches.iter().map(|batch| batch.num_rows()).sum::<usize>(),
+            2
+        );
+    }
+
+    #[test]
+    fn conversion_should_fail_given_invalid_record() {
+        let directory = TempDir::new().unwrap();
+        let input = directory.path().join("input.jsonl");
+        std::fs::write(&input, b"{\"id\":1}\n[1]\n").unwrap();
+
+        assert!(convert_to_csv(&input, &directory.path().join("output.csv")).is_err());
+    }
+
+    fn writer(directory: &TempDir, format: FileFormat) -> FileWriter {
+        let output = directory.path().join("output");
+        FileWriter::new(
+            format,
+            ParquetCompression::default(),
+            directory.path().join("staging"),
+            Storage::new(output.to_str().unwrap(), None).unwrap(),
+            u64::MAX,
+            Duration::from_secs(3600),
+        )
+    }
+}
@@ -21,11 +21,15 @@ use chrono::{DateTime, Utc};
 use iggy_connector_sdk::{
     ConsumedMessage, Error, MessagesMetadata, Sink, TopicMetadata, sink_connector,
 };
+use mapping::{MappingConfig, RowChange, quote_identifier};
 use serde::{Deserialize, Serialize};
-use sqlx::{Pool, Postgres, postgres::PgPoolOptions};
+use sqlx::{PgConnection, Pool, Postgres, Row, postgres::PgPoolOptions};
+use std::{collections::HashMap, sync::Arc};
 use tokio::sync::Mutex;
 use tracing::{error, info};
 
+mod mapping;
+
 sink_connector!(PostgresSink);
 
 #[derive(Debug)]
@@ -34,6 +38,7 @@ pub struct PostgresSink {
     pool: Option<Pool<Postgres>>,
     config: PostgresSinkConfig,
     state: Mutex<State>,
+    table_columns: Mutex<HashMap<String, Arc<Vec<String>>>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -46,6 +51,8 @@ pub struct PostgresSinkConfig {
     pub include_metadata: Option<bool>,
     pub include_checksum: Option<bool>,
     pub include_origin_timestamp: Option<bool>,
+    pub offsets_table: Option<String>,
+    pub mapping: Option<MappingConfig>,
 }
 
 #[derive(Debug)]
@@ -64,6 +71,7 @@ impl PostgresSink {
                 messages_processed: 0,
                 insertion_errors: 0,
             }),
+            table_columns: Mutex::new(HashMap::new()),
         }
     }
 
@@ -90,7 +98,8 @@ impl PostgresSink {
     }
 
     async fn ensure_table_exists(&self) -> Result<(), Error> {
-        if !self.config.auto_create_table.unwrap_or(false) {
+        // In the mapping mode, the tables are created when the first message is written into them.
+        if !self.config.auto_create_table.unwrap_or(false) || self.config.mapping.is_some() {
             return Ok(());
         }
 
@@ -134,6 +143,31 @@ impl PostgresSink {
         Ok(())
     }
 
+    async fn ensure_offsets_table_exists(&self) -> Result<(), Error> {
+        let Some(offsets_table) = self.config.offsets_table.as_ref() else {
+            return Ok(());
+        };
+
+        let pool = self
+            .pool
+            .as_ref()
+            .ok_or_else(|| Error::InitError("Database not connected".to_string()))?;
+        let create_table_sql = format!(
+            "CREATE TABLE IF NOT EXISTS {offsets_table} (iggy_stream TEXT NOT NULL, iggy_topic TEXT NOT NULL, iggy_partition_id INTEGER NOT NULL, iggy_offset BIGINT NOT NULL, updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(), PRIMARY KEY (iggy_stream, iggy_topic, iggy_partition_id))"
+        );
+        sqlx::query(&create_table_sql)
+            .execute(pool)
+            .await
+            .map_err(|e| {
+                Error::InitError(format!(
+                    "Failed to create offsets table '{offsets_table}': {e}"
+                ))
+            })?;
+
+        info!("Ensured offsets table '{}' exists", offsets_table);
+        Ok(())
+    }
+
     async fn process_messages(
         &self,
         topic_metadata: &TopicMetadata,
@@ -146,15 +180,31 @@ impl PostgresSink {
             .ok_or_else(|| Error::InitError("Database not connected".to_string()))?;
         let batch_size = self.config.batch_size.unwrap_or(100) as usize;
 
-        for batch in messages.chunks(batch_size) {
+        if let Some(mapping) = self.config.mapping.as_ref() {
             if let Err(e) = self
-                .insert_batch(batch, topic_metadata, messages_metadata, pool)
+                .write_mapped(mapping, messages, topic_metadata, messages_metadata, pool)
                 .await
             {
                 let mut state = self.state.lock().await;
-                state.insertion_errors += batch.len() as u64;
-                error!("Failed to insert batch: {}", e);
+                state.insertion_errors += messages.len() as u64;
+                error!("Failed to write mapped messages: {}", e);
+                return Err(e);
             }
+        } else if let Err(e) = self
+            .insert_messages(
+                self.config.offsets_table.as_deref(),
+                messages,
+                topic_metadata,
+                messages_metadata,
+                batch_size,
+                pool,
+            )
+            .await
+        {
+            let mut state = self.state.lock().await;
+            state.insertion_errors += messages.len() as u64;
+            error!("Failed to insert messages: {}", e);
+            return Err(e);
         }
 
         let mut state = self.state.lock().await;
@@ -170,12 +220,314 @@ impl PostgresSink {
         Ok(())
     }
 
-    async fn insert_batch(
+    /// Inserts all the messages (and stores the offset of the last one, if configured) within the single transaction,
+    /// so that the batch is either fully inserted or not at all, and the committed offset always matches the inserted messages.
+    async fn insert_messages(
+        &self,
+        offsets_table: Option<&str>,
+        messages: &[ConsumedMessage],
+        topic_metadata: &TopicMetadata,
+        messages_metadata: &MessagesMetadata,
+        batch_size: usize,
+        pool: &Pool<Postgres>,
+    ) -> Result<(), Error> {
+        let Some(last_message) = messages.last() else {
+            return Ok(());
+        };
+
+        let mut transaction = pool
+            .begin()
+            .await
+            .map_err(|e| Error::CannotStoreData(format!("Failed to begin transaction: {e}")))?;
+        for batch in messages.chunks(batch_size) {
+            self.insert_batch(batch, topic_metadata, messages_metadata, &mut transaction)
+                .await?;
+        }
+
+        if let Some(offsets_table) = offsets_table {
+            self.store_offset(
+                offsets_table,
+                last_message.offset,
+                topic_metadata,
+                messages_metadata,
+                &mut transaction,
+            )
+            .await?;
+        }
+
+        transaction
+            .commit()
+            .await
+            .map_err(|e| Error::CannotStoreData(format!("Failed to commit transaction: {e}")))
+    }
+
+    async fn store_offset(
+        &self,
+        offsets_table: &str,
+        offset: u64,
+        topic_metadata: &TopicMetadata,
+        messages_metadata: &MessagesMetadata,
+        connection: &mut PgConnection,
+    ) -> Result<(), Error> {
+        sqlx::query(&format!(
+            "INSERT INTO {offsets_table} (iggy_stream, iggy_topic, iggy_partition_id, iggy_offset) VALUES ($1, $2, $3, $4) \
+            ON CONFLICT (iggy_stream, iggy_topic, iggy_partition_id) DO UPDATE SET iggy_offset = EXCLUDED.iggy_offset, updated_at = NOW()"
+        ))
+        .bind(&topic_metadata.stream)
+        .bind(&topic_metadata.topic)
+        .bind(messages_metadata.partition_id as i32)
+        .bind(offset as i64)
+        .execute(&mut *connection)
+        .await
+        .map_err(|e| Error::CannotStoreData(format!("Failed to store offset: {e}")))?;
+        Ok(())
+    }
+
+    /// Writes the mapped rows of all the messages (and the offset of the last one, if configured)
+    /// within the single transaction, so that the batch is either fully applied or not at all.
+    async fn write_mapped(
         &self,
+        mapping: &MappingConfig,
         messages: &[ConsumedMessage],
         topic_metadata: &TopicMetadata,
         messages_metadata: &MessagesMetadata,
         pool: &Pool<Postgres>,
+    ) -> Result<(), Error> {
+        let Some(last_message) = messages.last() else {
+            return Ok(());
+        };
+
+        let mut transaction = pool
+            .begin()
+            .await
+            .map_err(|e| Error::CannotStoreData(format!("Failed to begin transaction: {e}")))?;
+        for message in messages {
+            let payload = message.payload.clone().try_into_vec().map_err(|e| {
+                error!("Failed to convert payload to bytes: {}", e);
+                Error::InvalidRecord
+            })?;
+            let payload = mapping::parse_payload(&payload)?;
+            let table = mapping.resolve_table(message, &payload, &self.config.target_table)?;
+            let columns = self.get_table_columns(mapping, &table, pool).await?;
+            match mapping.map_change(&table, message, &payload)? {
+                RowChange::Upsert(row) => {
+                    self.upsert_row(mapping, &table, &columns, row, &mut transaction)
+                        .await?
+                }
+                RowChange::Delete(keys) => {
+                    self.delete_row(mapping, &table, keys, &mut transaction)
+                        .await?
+                }
+            }
+        }
+
+        if let Some(offsets_table) = self.config.offsets_table.as_ref() {
+            self.store_offset(
+                offsets_table,
+                last_message.offset,
+                topic_metadata,
+                messages_metadata,
+                &mut transaction,
+            )
+            .await?;
+        }
+
+        transaction
+            .commit()
+            .await
+            .map_err(|e| Error::CannotStoreData(format!("Failed to commit transaction: {e}")))
+    }
+
+    /// Returns the columns of the table, creating it first if it doesn't exist and `auto_create_table` is enabled.
+    async fn get_table_columns(
+        &self,
+        mapping: &MappingConfig,
+        table: &str,
+        pool: &Pool<Postgres>,
+    ) -> Result<Arc<Vec<String>>, Error> {
+        let mut table_columns = self.table_columns.lock().await;
+        if let Some(columns) = table_columns.get(table) {
+            return Ok(columns.clone());
+        }
+
+        let mut columns = self.fetch_table_columns(table, pool).await?;
+        if columns.is_empty() && self.config.auto_create_table.unwrap_or(false) {
+            self.create_mapped_table(mapping, table, pool).await?;
+            columns = self.fetch_table_columns(table, pool).await?;
+        }
+
+        if columns.is_empty() {
+            return Err(Error::CannotStoreData(format!(
+                "Table '{table}' doesn't exist"
+            )));
+        }
+
+        let columns = Arc::new(columns);
+        table_columns.insert(table.to_owned(), columns.clone());
+        Ok(columns)
+    }
+
+    async fn fetch_table_columns(
+        &self,
+        table: &str,
+        pool: &Pool<Postgres>,
+    ) -> Result<Vec<String>, Error> {
+        // The unquoted identifiers are folded to lower case by PostgreSQL.
+        let (schema, name) = match table.split_once('.') {
+            Some((schema, name)) => (Some(schema.to_lowercase()), name.to_lowercase()),
+            None => (None, table.to_lowercase()),
+        };
+
+        sqlx::query_scalar(
+            "SELECT column_name::text FROM information_schema.columns WHERE table_schema = COALESCE($1, current_schema()) AND table_name = $2 ORDER BY ordinal_position",
+        )
+        .bind(schema)
+        .bind(name)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| {
+            Error::CannotStoreData(format!("Failed to get columns of table '{table}': {e}"))
+        })
+    }
+
+    async fn create_mapped_table(
+        &self,
+        mapping: &MappingConfig,
+        table: &str,
+        pool: &Pool<Postgres>,
+    ) -> Result<(), Error> {
+        let Some(columns) = mapping.columns(table) else {
+            return Err(Error::CannotStoreData(format!(
+                "Cannot create table '{table}' without the mapped columns"
+            )));
+        };
+
+        let mut definitions = Vec::with_capacity(columns.len() + 1);
+        for column in columns {
+            let Some(column_type) = column.column_type.as_ref() else {
+                return Err(Error::CannotStoreData(format!(
+                    "Cannot create table '{table}' without the type of column '{}'",
+                    column.name
+                )));
+            };
+            definitions.push(format!("{} {column_type}", quote_identifier(&column.name)));
+        }
+
+        let key_columns = mapping.key_columns(table);
+        if !key_columns.is_empty() {
+            definitions.push(format!("PRIMARY KEY ({})", quote_identifiers(key_columns)));
+        }
+
+        let create_table_sql = format!(
+            "CREATE TABLE IF NOT EXISTS {table} ({})",
+            definitions.join(", ")
+        );
+        sqlx::query(&create_table_sql)
+            .execute(pool)
+            .await
+            .map_err(|e| {
+                Error::CannotStoreData(format!("Failed to create table '{table}': {e}"))
+            })?;
+
+        info!("Ensured table '{}' exists", table);
+        Ok(())
+    }
+
+    /// Inserts the row, or updates the existing one with the same key columns, converting
+    /// the JSON values to the column types by `jsonb_populate_record`.
+    async fn upsert_row(
+        &self,
+        mapping: &MappingConfig,
+        table: &str,
+        columns: &[String],
+        row: serde_json::Map<String, serde_json::Value>,
+        connection: &mut PgConnection,
+    ) -> Result<(), Error> {
+        let row_columns = columns
+            .iter()
+            .filter(|column| row.contains_key(*column))
+            .cloned()
+            .collect::<Vec<_>>();
+        if row_columns.is_empty() {
+            error!("No columns of table '{table}' found in the message payload");
+            return Err(Error::InvalidRecord);
+        }
+
+        let quoted_columns = quote_identifiers(&row_columns);
+        let mut query = format!(
+            "INSERT INTO {table} ({quoted_columns}) SELECT {quoted_columns} FROM jsonb_populate_record(NULL::{table}, $1::jsonb)"
+        );
+
+        let key_columns = mapping.key_columns(table);
+        if !key_columns.is_empty() {
+            let updates = row_columns
+                .iter()
+                .filter(|column| !key_columns.contains(column))
+                .map(|column| {
+                    let column = quote_identifier(column);
+                    format!("{column} = EXCLUDED.{column}")
+                })
+                .collect::<Vec<_>>();
+            let conflict_columns = quote_identifiers(key_columns);
+            if updates.is_empty() {
+                query.push_str(&format!(" ON CONFLICT ({conflict_columns}) DO NOTHING"));
+            } else {
+                query.push_str(&format!(
+                    " ON CONFLICT ({conflict_columns}) DO UPDATE SET {}",
+                    updates.join(", ")
+                ));
+            }
+        }
+
+        sqlx::query(&query)
+            .bind(serde_json::Value::Object(row))
+            .execute(&mut *connection)
+            .await
+            .map_err(|e| {
+                Error::CannotStoreData(format!("Failed to upsert row into table '{table}': {e}"))
+            })?;
+        Ok(())
+    }
+
+    async fn delete_row(
+        &self,
+        mapping: &MappingConfig,
+        table: &str,
+        keys: serde_json::Map<String, serde_json::Value>,
+        connection: &mut PgConnection,
+    ) -> Result<(), Error> {
+        let key_columns = mapping.key_columns(table);
+        if key_columns.is_empty() || key_columns.iter().any(|column| !keys.contains_key(column)) {
+            error!("Missing key columns of table '{table}' to delete the row");
+            return Err(Error::InvalidRecord);
+        }
+
+        let conditions = key_columns
+            .iter()
+            .map(|column| {
+                let column = quote_identifier(column);
+                format!("t.{column} = r.{column}")
+            })
+            .collect::<Vec<_>>()
+            .join(" AND ");
+        sqlx::query(&format!(
+            "DELETE FROM {table} AS t USING jsonb_populate_record(NULL::{table}, $1::jsonb) AS r WHERE {conditions}"
+        ))
+        .bind(serde_json::Value::Object(keys))
+        .execute(&mut *connection)
+        .await
+        .map_err(|e| {
+            Error::CannotStoreData(format!("Failed to delete row from table '{table}': {e}"))
+        })?;
+        Ok(())
+    }
+
+    async fn insert_batch(
+        &self,
+        messages: &[ConsumedMessage],
+        topic_metadata: &TopicMetadata,
+        messages_metadata: &MessagesMetadata,
+        connection: &mut PgConnection,
     ) -> Result<(), Error> {
         let table_name = &self.config.target_table;
         let include_metadata = self.config.include_metadata.unwrap_or(true);
@@ -254,7 +606,7 @@ impl PostgresSink {
 
             query_obj = query_obj.bind(payload_bytes);
 
-            query_obj.execute(pool).await.map_err(|e| {
+            query_obj.execute(&mut *connection).await.map_err(|e| {
                 error!("Failed to insert message: {}", e);
                 Error::InvalidRecord
             })?;
@@ -273,6 +625,7 @@ impl Sink for PostgresSink {
         );
         self.connect().await?;
         self.ensure_table_exists().await?;
+        self.ensure_offsets_table_exists().await?;
         Ok(())
     }
 
@@ -286,6 +639,32 @@ impl Sink for PostgresSink {
             .await
     }
 
+    async fn committed_offset(
+        &self,
+        topic_metadata: &TopicMetadata,
+        partition_id: u32,
+    ) -> Result<Option<u64>, Error> {
+        let Some(offsets_table) = self.config.offsets_table.as_ref() else {
+            return Ok(None);
+        };
+
+        let pool = self
+            .pool
+            .as_ref()
+            .ok_or_else(|| Error::InitError("Database not connected".to_string()))?;
+        let row = sqlx::query(&format!(
+            "SELECT iggy_offset FROM {offsets_table} WHERE iggy_stream = $1 AND iggy_topic = $2 AND iggy_partition_id = $3"
+        ))
+        .bind(&topic_metadata.stream)
+        .bind(&topic_metadata.topic)
+        .bind(partition_id as i32)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| Error::CannotStoreData(format!("Failed to get committed offset: {e}")))?;
+
+        Ok(row.map(|row| row.get::<i64, _>("iggy_offset") as u64))
+    }
+
     async fn close(&mut self) -> Result<(), Error> {
         info!("Closing PostgreSQL sink connector with ID: {}", self.id);
         let state = self.state.lock().await;
@@ -296,3 +675,11 @@ impl Sink for PostgresSink {
         Ok(())
     }
 }
+
+fn quote_identifiers(identifiers: &[String]) -> String {
+    identifiers
+        .iter()
+        .map(|identifier| quote_identifier(identifier))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -0,0 +1,234 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use iggy_connector_sdk::{ConsumedMessage, Error};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use tracing::error;
+
+/// Configuration of the mapping mode, in which the JSON payload fields are written into the typed table columns.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MappingConfig {
+    /// Header containing the name of the table to write the message into.
+    pub table_header: Option<String>,
+    /// Payload field containing the name of the table to write the message into.
+    pub table_field: Option<String>,
+    /// Payload field containing the row to be written, e.g. `after` for the CDC records, the whole payload by default.
+    pub row_field: Option<String>,
+    /// Payload field containing the key values of the row to be deleted, e.g. `before` for the CDC records.
+    pub key_field: Option<String>,
+    /// Columns used to upsert and delete the rows, unless overridden for the specific table.
+    pub key_columns: Option<Vec<String>>,
+    /// Columns mapped from the payload fields, unless overridden for the specific table.
+    pub columns: Option<Vec<ColumnMapping>>,
+    /// Configuration of the specific tables.
+    pub tables: Option<HashMap<String, TableMapping>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TableMapping {
+    pub key_columns: Option<Vec<String>>,
+    pub columns: Option<Vec<ColumnMapping>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColumnMapping {
+    pub name: String,
+    /// Path of the payload field, separated by dots, the same as column name by default.
+    pub field: Option<String>,
+    /// SQL type of the column used when the table is created automatically.
+    #[serde(rename = "type")]
+    pub column_type: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum RowChange {
+    Upsert(Map<String, Value>),
+    /// The tombstone, containing only the key values of the row to be deleted.
+    Delete(Map<String, Value>),
+}
+
+impl MappingConfig {
+    /// Returns the table for the message based on the header or payload field, or the default one.
+    pub fn resolve_table(
+        &self,
+        message: &ConsumedMessage,
+        payload: &Value,
+        default_table: &str,
+    ) -> Result<String, Error> {
+        let header_table = self
+            .table_header
+            .as_deref()
+            .and_then(|header| get_header(message, header));
+        let field_table = || {
+            self.table_field
+                .as_deref()
+                .and_then(|field| get_field(payload, field))
+                .and_then(|value| value.as_str().map(str::to_owned))
+        };
+
+        let Some(table) = header_table.or_else(field_table) else {
+            return Ok(default_table.to_owned());
+        };
+
+        if !is_valid_table_name(&table) {
+            error!(
+                "Invalid table name: '{table}' for message with ID: {}",
+                message.id
+            );
+            return Err(Error::InvalidRecord);
+        }
+
+        Ok(table)
+    }
+
+    pub fn key_columns(&self, table: &str) -> &[String] {
+        self.table(table)
+            .and_then(|table| table.key_columns.as_deref())
+            .or(self.key_columns.as_deref())
+            .unwrap_or_default()
+    }
+
+    pub fn columns(&self, table: &str) -> Option<&[ColumnMapping]> {
+        self.table(table)
+            .and_then(|table| table.columns.as_deref())
+            .or(self.columns.as_deref())
+    }
+
+    /// Maps the message to the upsert of its row, or to the delete when the row is missing (tombstone).
+    /// The key values of the tombstone are taken from the `key_field`, or from the headers named after the key columns.
+    pub fn map_change(
+        &self,
+        table: &str,
+        message: &ConsumedMessage,
+        payload: &Value,
+    ) -> Result<RowChange, Error> {
+        let row = match self.row_field.as_deref() {
+            Some(field) => get_field(payload, field).unwrap_or(&Value::Null),
+            None => payload,
+        };
+
+        match row {
+            Value::Object(row) => Ok(RowChange::Upsert(self.map_columns(table, row))),
+            Value::Null => {
+                let keys = match self
+                    .key_field
+                    .as_deref()
+                    .map(|field| get_field(payload, field))
+                {
+                    Some(Some(Value::Object(keys))) => self.map_columns(table, keys),
+                    Some(_) => {
+                        error!(
+                            "Missing key field for tombstone message with ID: {}",
+                            message.id
+                        );
+                        return Err(Error::InvalidRecord);
+                    }
+                    None => self
+                        .key_columns(table)
+                        .iter()
+                        .filter_map(|column| {
+                            get_header(message, column)
+                                .map(|value| (column.clone(), Value::String(value)))
+                        })
+                        .collect(),
+                };
+                Ok(RowChange::Delete(keys))
+            }
+            _ => {
+                error!(
+                    "Payload of message with ID: {} is not a JSON object",
+                    message.id
+                );
+                Err(Error::InvalidRecord)
+            }
+        }
+    }
+
+    fn table(&self, table: &str) -> Option<&TableMapping> {
+        self.tables.as_ref().and_then(|tables| tables.get(table))
+    }
+
+    fn map_columns(&self, table: &str, row: &Map<String, Value>) -> Map<String, Value> {
+        let Some(columns) = self.columns(table) else {
+            return row.clone();
+        };
+
+        columns
+            .iter()
+            .filter_map(|column| {
+                let field = column.field.as_deref().unwrap_or(&column.name);
+                get_object_field(row, field).map(|value| (column.name.clone(), value.clone()))
+            })
+            .collect()
+    }
+}
+
+/// Parses the payload as JSON, the empty payload is treated as `null`.
+pub fn parse_payload(payload: &[u8]) -> Result<Value, Error> {
+    if payload.is_empty() {
+        return Ok(Value::Null);
+    }
+
+    serde_json::from_slice(payload).map_err(|_| Error::InvalidJsonPayload)
+}
+
+/// Quotes the identifier, so that it can be safely used in the SQL query.
+pub fn quote_identifier(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+/// Checks whether the table name (optionally prefixed with the schema) consists of the unquoted identifiers only.
+pub fn is_valid_table_name(table: &str) -> bool {
+    let parts = table.split('.').collect::<Vec<_>>();
+    parts.len() <= 2
+        && parts.iter().all(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .is_some_and(|char| char.is_ascii_alphabetic() || char == '_')
+                && chars.all(|char| char.is_ascii_alphanumeric() || char == '_')
+        })
+}
+
+fn get_header(message: &ConsumedMessage, name: &str) -> Option<String> {
+    message
+        .headers
+        .as_ref()?
+        .iter()
+        .find(|(key, _)| key.as_str() == name)
+        .map(|(_, value)| value.value_only_to_string())
+}
+
+fn get_field<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .try_fold(value, |value, field| value.get(field))
+}
+
+fn get_object_field<'a>(object: &'a Map<String, Value>, path: &str) -> Option<&'a Value> {
+    let (field, rest) = match path.split_once('.') {
+        Some((field, rest)) => (field, Some(rest)),
+        None => (path, None),
+    };
+    let value = object.get(field)?;
+    match rest {
+        Some(rest) => get_field(value, rest),
+        None => Some(value),
+    }
+}
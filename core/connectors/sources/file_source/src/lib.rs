@@ -0,0 +1,556 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use std::{collections::HashMap, io::ErrorKind, path::Path, str::FromStr, time::Duration};
+
+use async_trait::async_trait;
+use iggy::prelude::{HeaderKey, HeaderValue};
+use iggy_connector_sdk::{
+    ConnectorState, Error, ProducedMessage, ProducedMessages, Schema, Source, source_connector,
+};
+use serde::{Deserialize, Serialize};
+use tail::{Line, TrackedFile};
+use tokio::{fs, sync::Mutex};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+mod tail;
+
+source_connector!(FileSource);
+
+const DEFAULT_POLL_INTERVAL: &str = "1s";
+const DEFAULT_BATCH_SIZE: u32 = 1000;
+const DEFAULT_PATTERN: &str = "*";
+const FILE_NAME_HEADER: &str = "file_name";
+const FILE_PATH_HEADER: &str = "file_path";
+const FILE_LINE_HEADER: &str = "file_line";
+const FILE_OFFSET_HEADER: &str = "file_offset";
+const MAX_HEADER_VALUE_LENGTH: usize = 255;
+
+#[derive(Debug)]
+pub struct FileSource {
+    pub id: u32,
+    config: FileSourceConfig,
+    poll_interval: Duration,
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileSourceConfig {
+    pub path: String,
+    pub pattern: Option<String>,
+    pub recursive: Option<bool>,
+    pub format: Option<FileFormat>,
+    pub start_position: Option<StartPosition>,
+    pub poll_interval: Option<String>,
+    pub batch_size: Option<u32>,
+    pub csv_delimiter: Option<char>,
+    pub csv_header: Option<bool>,
+    pub csv_columns: Option<Vec<String>>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileFormat {
+    #[default]
+    Text,
+    Jsonl,
+    Csv,
+}
+
+/// Where to start reading the files found at the first start, for which there's no position stored yet.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StartPosition {
+    #[default]
+    Beginning,
+    End,
+}
+
+#[derive(Debug)]
+struct State {
+    files: HashMap<String, TrackedFile>,
+    stored_positions: HashMap<String, FilePosition>,
+    initialized: bool,
+    has_more: bool,
+    produced_messages: u64,
+}
+
+/// The state persisted by the runtime, used to resume reading the files after the last produced line.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct FileSourceState {
+    files: Vec<FilePosition>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct FilePosition {
+    id: String,
+    path: String,
+    offset: u64,
+    line: u64,
+    columns: Option<Vec<String>>,
+}
+
+impl FileSource {
+    pub fn new(id: u32, config: FileSourceConfig, state: Option<ConnectorState>) -> Self {
+        let poll_interval = config
+            .poll_interval
+            .as_deref()
+            .unwrap_or(DEFAULT_POLL_INTERVAL);
+        let poll_interval = humantime::Duration::from_str(poll_interval)
+            .unwrap_or_else(|_| Duration::from_secs(1).into())
+            .into();
+
+        let stored_positions = state
+            .and_then(|state| {
+                serde_json::from_slice::<FileSourceState>(&state.0)
+                    .inspect_err(|error| error!("Failed to deserialize file source state. {error}"))
+                    .ok()
+            })
+            .unwrap_or_default()
+            .files
+            .into_iter()
+            .map(|position| (position.id.clone(), position))
+            .collect::<HashMap<_, _>>();
+        if !stored_positions.is_empty() {
+            info!(
+                "File source connector with ID: {id} will resume reading {} file(s)",
+                stored_positions.len()
+            );
+        }
+
+        FileSource {
+            id,
+            config,
+            poll_interval,
+            state: Mutex::new(State {
+                files: HashMap::new(),
+                stored_positions,
+                initialized: false,
+                has_more: false,
+                produced_messages: 0,
+            }),
+        }
+    }
+
+    fn format(&self) -> FileFormat {
+        self.config.format.unwrap_or_default()
+    }
+
+    /// Starts tracking the new files and updates the paths of the renamed ones.
+    async fn scan(&self, state: &mut State) {
+        let path = Path::new(&self.config.path);
+        let pattern = self.config.pattern.as_deref().unwrap_or(DEFAULT_PATTERN);
+        let files =
+            match tail::scan_files(path, pattern, self.config.recursive.unwrap_or(false)).await {
+                Ok(files) => files,
+                // The path will be scanned again, once it's created (the open already warned about it).
+                Err(error) if error.kind() == ErrorKind::NotFound => return,
+                Err(error) => {
+                    warn!("Failed to scan path: {}. {error}", path.display());
+                    return;
+                }
+            };
+
+        for file in state.files.values_mut() {
+            file.present = false;
+        }
+
+        for (path, metadata) in files {
+            let id = tail::file_id(&path, &metadata);
+            let modified = metadata.modified().unwrap_or(std::time::UNIX_EPOCH);
+            if let Some(file) = state.files.get_mut(&id) {
+                if file.path != path {
+                    info!(
+                        "File: {} was renamed to: {}",
+                        file.path.display(),
+                        path.display()
+                    );
+                    file.path = path;
+                }
+                file.present = true;
+                file.modified = modified;
+                continue;
+            }
+
+            let handle = match fs::File::open(&path).await {
+                Ok(handle) => handle,
+                Err(error) => {
+                    warn!("Failed to open file: {}. {error}", path.display());
+                    continue;
+                }
+            };
+
+            let (offset, line, columns) = match state.stored_positions.remove(&id) {
+                Some(position) if position.offset <= metadata.len() => {
+                    (position.offset, position.line, position.columns)
+                }
+                Some(_) => {
+                    warn!(
+                        "File: {} is smaller than its stored position, reading it from the beginning",
+                        path.display()
+                    );
+                    (0, 0, None)
+                }
+                None if !state.initialized
+                    && self.config.start_position.unwrap_or_default() == StartPosition::End =>
+                {
+                    (metadata.len(), 0, None)
+                }
+                None => (0, 0, None),
+            };
+
+            info!("Reading file: {} from offset: {offset}", path.display());
+            let mut file = TrackedFile::new(path, handle, offset, line, columns, modified);
+            if offset > 0 && file.columns.is_none() && self.expects_csv_header() {
+                match file.read_first_line().await {
+                    Ok(Some(header)) => file.columns = Some(self.parse_csv_record(&header)),
+                    Ok(None) => {}
+                    Err(error) => warn!(
+                        "Failed to read CSV header of file: {}. {error}",
+                        file.path.display()
+                    ),
+                }
+            }
+            state.files.insert(id, file);
+        }
+
+        // The positions of the files that no longer exist are not needed anymore.
+        state.stored_positions.clear();
+        state.initialized = true;
+    }
+
+    async fn read_files(&self, state: &mut State) -> Result<Vec<ProducedMessage>, Error> {
+        let batch_size = self.config.batch_size.unwrap_or(DEFAULT_BATCH_SIZE) as usize;
+        let mut ids = state
+            .files
+            .iter()
+            .map(|(id, file)| (file.modified, file.path.clone(), id.clone()))
+            .collect::<Vec<_>>();
+        // The older files (e.g. the rotated ones) are read first.
+        ids.sort();
+
+        let mut messages = Vec::new();
+        for (_, _, id) in ids {
+            if messages.len() >= batch_size {
+                break;
+            }
+
+            let Some(file) = state.files.get_mut(&id) else {
+                continue;
+            };
+
+            let (lines, end_of_file) = match file.read_lines(batch_size - messages.len()).await {
+                Ok(result) => result,
+                Err(error) => {
+                    error!("Failed to read file: {}. {error}", file.path.display());
+                    continue;
+                }
+            };
+
+            for line in lines {
+                if let Some(message) = self.build_message(file, line)? {
+                    messages.push(message);
+                }
+            }
+
+            if !file.present && end_of_file {
+                info!("Finished reading file: {}", file.path.display());
+                state.files.remove(&id);
+            }
+        }
+
+        state.has_more = messages.len() >= batch_size;
+        Ok(messages)
+    }
+
+    fn build_message(
+        &self,
+        file: &mut TrackedFile,
+        line: Line,
+    ) -> Result<Option<ProducedMessage>, Error> {
+        if line.content.iter().all(|byte| byte.is_ascii_whitespace()) {
+            return Ok(None);
+        }
+
+        let payload = match self.format() {
+            FileFormat::Text => line.content,
+            FileFormat::Jsonl => {
+                if let Err(error) = simd_json::to_owned_value(&mut line.content.clone()) {
+                    error!(
+                        "Invalid JSON in file: {}, line: {}. {error}",
+                        file.path.display(),
+                        line.number
+                    );
+                    return Ok(None);
+                }
+                line.content
+            }
+            FileFormat::Csv => {
+                let values = self.parse_csv_record(&line.content);
+                if file.columns.is_none() && self.expects_csv_header() {
+                    file.columns = Some(values);
+                    return Ok(None);
+                }
+
+                let record = values
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, value)| {
+                        let column = self
+                            .config
+                            .csv_columns
+                            .as_ref()
+                            .or(file.columns.as_ref())
+                            .and_then(|columns| columns.get(index).cloned())
+                            .unwrap_or_else(|| format!("column_{}", index + 1));
+                        (column, serde_json::Value::String(value))
+                    })
+                    .collect::<serde_json::Map<_, _>>();
+                serde_json::to_vec(&record).map_err(|_| Error::InvalidRecord)?
+            }
+        };
+
+        let file_name = file
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let mut headers = HashMap::from([
+            (
+                header_key(FILE_LINE_HEADER)?,
+                HeaderValue::from_uint64(line.number).map_err(|_| Error::InvalidRecord)?,
+            ),
+            (
+                header_key(FILE_OFFSET_HEADER)?,
+                HeaderValue::from_uint64(line.offset).map_err(|_| Error::InvalidRecord)?,
+            ),
+        ]);
+        if let Some(value) = text_header_value(&file_name) {
+            headers.insert(header_key(FILE_NAME_HEADER)?, value);
+        }
+        if let Some(value) = text_header_value(&file.path.to_string_lossy()) {
+            headers.insert(header_key(FILE_PATH_HEADER)?, value);
+        }
+
+        Ok(Some(ProducedMessage {
+            id: Some(Uuid::new_v4().as_u128()),
+            headers: Some(headers),
+            checksum: None,
+            timestamp: None,
+            origin_timestamp: None,
+            payload,
+        }))
+    }
+
+    fn expects_csv_header(&self) -> bool {
+        self.format() == FileFormat::Csv && self.config.csv_header.unwrap_or(true)
+    }
+
+    fn parse_csv_record(&self, line: &[u8]) -> Vec<String> {
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .delimiter(self.config.csv_delimiter.unwrap_or(',') as u8)
+            .from_reader(line);
+        reader
+            .records()
+            .next()
+            .and_then(|record| record.ok())
+            .map(|record| record.iter().map(str::to_owned).collect())
+            .unwrap_or_default()
+    }
+
+    fn file_source_state(&self, state: &State) -> Result<ConnectorState, Error> {
+        let files = state
+            .files
+            .iter()
+            .map(|(id, file)| FilePosition {
+                id: id.clone(),
+                path: file.path.to_string_lossy().into_owned(),
+                offset: file.offset,
+                line: file.line,
+                columns: file.columns.clone(),
+            })
+            .collect();
+        let state = serde_json::to_vec(&FileSourceState { files }).map_err(|error| {
+            error!("Failed to serialize file source state. {error}");
+            Error::InvalidState
+        })?;
+        Ok(ConnectorState(state))
+    }
+}
+
+#[async_trait]
+impl Source for FileSource {
+    async fn open(&mut self) -> Result<(), Error> {
+        info!(
+            "Opening file source connector with ID: {}. Path: {}, pattern: {}, format: {:?}",
+            self.id,
+            self.config.path,
+            self.config.pattern.as_deref().unwrap_or(DEFAULT_PATTERN),
+            self.format()
+        );
+
+        if self
+            .config
+            .csv_delimiter
+            .is_some_and(|delimiter| !delimiter.is_ascii())
+        {
+            return Err(Error::InitError(
+                "CSV delimiter must be an ASCII character".to_string(),
+            ));
+        }
+
+        if fs::metadata(&self.config.path).await.is_err() {
+            warn!(
+                "Path: {} doesn't exist yet, it will be read once it's created",
+                self.config.path
+            );
+        }
+
+        info!(
+            "File source connector with ID: {} opened successfully",
+            self.id
+        );
+        Ok(())
+    }
+
+    async fn poll(&self) -> Result<ProducedMessages, Error> {
+        // The next batch is read right away, if the previous one didn't read all the available lines.
+        let has_more = self.state.lock().await.has_more;
+        if !has_more {
+            tokio::time::sleep(self.poll_interval).await;
+        }
+
+        let mut state = self.state.lock().await;
+        self.scan(&mut state).await;
+        let messages = self.read_files(&mut state).await?;
+        state.produced_messages += messages.len() as u64;
+        if !messages.is_empty() {
+            info!(
+                "File source connector with ID: {} produced {} messages. Total produced: {}",
+                self.id,
+                messages.len(),
+                state.produced_messages
+            );
+        }
+
+        let schema = match self.format() {
+            FileFormat::Text => Schema::Text,
+            FileFormat::Jsonl | FileFormat::Csv => Schema::Json,
+        };
+        Ok(ProducedMessages {
+            schema,
+            messages,
+            state: Some(self.file_source_state(&state)?),
+        })
+    }
+
+    async fn close(&mut self) -> Result<(), Error> {
+        let state = self.state.lock().await;
+        info!(
+            "File source connector with ID: {} closed. Total produced: {} messages",
+            self.id, state.produced_messages
+        );
+        Ok(())
+    }
+}
+
+fn header_key(key: &str) -> Result<HeaderKey, Error> {
+    HeaderKey::new(key).map_err(|_| Error::InvalidRecord)
+}
+
+/// The header values must be non-empty and at most 255 bytes long, so the longer ones keep only their end
+/// (e.g. the file name of the long path), truncated on the char boundary, and the empty ones are omitted.
+fn text_header_value(value: &str) -> Option<HeaderValue> {
+    let mut start = value.len().saturating_sub(MAX_HEADER_VALUE_LENGTH);
+    while !value.is_char_boundary(start) {
+        start += 1;
+    }
+    HeaderValue::from_str(&value[start..]).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::SystemTime;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn message_should_be_built_given_path_longer_than_header_value() {
+        let directory = TempDir::new().unwrap();
+        let mut path = directory.path().to_owned();
+        for name in ["a", "b", "c"] {
+            path.push(name.repeat(100));
+        }
+        std::fs::create_dir_all(&path).unwrap();
+        let file_name = format!("{}.log", "f".repeat(251));
+        path.push(&file_name);
+        std::fs::write(&path, b"line\n").unwrap();
+        let handle = fs::File::open(&path).await.unwrap();
+        let mut file = TrackedFile::new(path.clone(), handle, 0, 0, None, SystemTime::now());
+        let source = FileSource::new(1, config(), None);
+
+        let line = Line {
+            number: 1,
+            offset: 0,
+            content: b"line".to_vec(),
+        };
+        let message = source.build_message(&mut file, line).unwrap().unwrap();
+
+        let headers = message.headers.unwrap();
+        let header = |key: &str| headers.get(&HeaderKey::new(key).unwrap()).unwrap();
+        assert_eq!(header(FILE_NAME_HEADER).as_str().unwrap(), file_name);
+        let file_path = header(FILE_PATH_HEADER).as_str().unwrap();
+        assert_eq!(file_path.len(), MAX_HEADER_VALUE_LENGTH);
+        assert!(path.to_string_lossy().ends_with(file_path));
+        assert_eq!(header(FILE_LINE_HEADER).as_uint64().unwrap(), 1);
+        assert_eq!(message.payload, b"line");
+    }
+
+    #[test]
+    fn text_header_value_should_keep_end_truncated_on_char_boundary() {
+        let value = format!("{}ab", "ą".repeat(MAX_HEADER_VALUE_LENGTH));
+
+        let header_value = text_header_value(&value).unwrap();
+
+        let header_value = header_value.as_str().unwrap();
+        assert_eq!(header_value.len(), MAX_HEADER_VALUE_LENGTH - 1);
+        assert!(header_value.ends_with("ąab"));
+        assert_eq!(
+            text_header_value("short").unwrap().as_str().unwrap(),
+            "short"
+        );
+        assert!(text_header_value("").is_none());
+    }
+
+    fn config() -> FileSourceConfig {
+        FileSourceConfig {
+            path: ".".to_owned(),
+            pattern: None,
+            recursive: None,
+            format: None,
+            start_position: None,
+            poll_interval: None,
+            batch_size: None,
+            csv_delimiter: None,
+            csv_header: None,
+            csv_columns: None,
+        }
+    }
+}
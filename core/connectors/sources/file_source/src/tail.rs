@@ -0,0 +1,413 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use std::{
+    fs::Metadata,
+    io::SeekFrom,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+use tokio::{
+    fs,
+    io::{AsyncReadExt, AsyncSeekExt},
+};
+use tracing::warn;
+
+const READ_BUFFER_SIZE: usize = 64 * 1024;
+
+#[derive(Debug)]
+pub struct Line {
+    pub number: u64,
+    pub offset: u64,
+    pub content: Vec<u8>,
+}
+
+/// The file being read, identified by its device and inode (on Unix), so that it's still read
+/// until the end after being renamed (rotated), while the new file with the same path is read separately.
+#[derive(Debug)]
+pub struct TrackedFile {
+    pub path: PathBuf,
+    pub offset: u64,
+    pub line: u64,
+    pub columns: Option<Vec<String>>,
+    pub modified: SystemTime,
+    /// Whether the file was found by the last scan, i.e. it wasn't deleted or rotated away.
+    pub present: bool,
+    file: fs::File,
+}
+
+impl TrackedFile {
+    pub fn new(
+        path: PathBuf,
+        file: fs::File,
+        offset: u64,
+        line: u64,
+        columns: Option<Vec<String>>,
+        modified: SystemTime,
+    ) -> Self {
+        TrackedFile {
+            path,
+            offset,
+            line,
+            columns,
+            modified,
+            present: true,
+            file,
+        }
+    }
+
+    /// Reads up to the given number of complete lines from the current offset, and returns them
+    /// along with the flag, whether the end of the file was reached. The partially written last line
+    /// is returned only once the file is no longer present, as nothing will be appended to it anymore.
+    pub async fn read_lines(&mut self, max_lines: usize) -> std::io::Result<(Vec<Line>, bool)> {
+        let length = self.file.metadata().await?.len();
+        if length < self.offset {
+            warn!(
+                "File: {} was truncated, reading it from the beginning",
+                self.path.display()
+            );
+            self.offset = 0;
+            self.line = 0;
+            self.columns = None;
+        }
+
+        let mut lines = Vec::new();
+        let mut buffer_size = READ_BUFFER_SIZE;
+        while lines.len() < max_lines && self.offset < length {
+            let remaining = length - self.offset;
+            let buffer = self
+                .read_at(self.offset, buffer_size.min(remaining as usize))
+                .await?;
+            if buffer.is_empty() {
+                break;
+            }
+
+            let mut start = 0;
+            while lines.len() < max_lines {
+                let Some(end) = buffer[start..].iter().position(|byte| *byte == b'\n') else {
+                    break;
+                };
+                lines.push(self.line(&buffer[start..start + end], start));
+                start += end + 1;
+            }
+
+            if start == 0 {
+                if (buffer.len() as u64) < remaining {
+                    // The line is longer than the buffer.
+                    buffer_size *= 2;
+                    continue;
+                }
+
+                if self.present {
+                    break;
+                }
+
+                lines.push(self.line(&buffer, 0));
+                start = buffer.len();
+            }
+            self.offset += start as u64;
+        }
+
+        let end_of_file = self.offset >= length;
+        Ok((lines, end_of_file))
+    }
+
+    /// Reads the first line of the file, e.g. the CSV header, when the reading starts in the middle of the file.
+    pub async fn read_first_line(&mut self) -> std::io::Result<Option<Vec<u8>>> {
+        let buffer = self.read_at(0, READ_BUFFER_SIZE).await?;
+        Ok(buffer
+            .iter()
+            .position(|byte| *byte == b'\n')
+            .map(|end| trim_line_end(&buffer[..end]).to_vec()))
+    }
+
+    fn line(&mut self, content: &[u8], start: usize) -> Line {
+        self.line += 1;
+        Line {
+            number: self.line,
+            offset: self.offset + start as u64,
+            content: trim_line_end(content).to_vec(),
+        }
+    }
+
+    async fn read_at(&mut self, offset: u64, size: usize) -> std::io::Result<Vec<u8>> {
+        self.file.seek(SeekFrom::Start(offset)).await?;
+        let mut buffer = vec![0; size];
+        let mut read = 0;
+        while read < size {
+            let bytes = self.file.read(&mut buffer[read..]).await?;
+            if bytes == 0 {
+                break;
+            }
+            read += bytes;
+        }
+        buffer.truncate(read);
+        Ok(buffer)
+    }
+}
+
+fn trim_line_end(line: &[u8]) -> &[u8] {
+    line.strip_suffix(b"\r").unwrap_or(line)
+}
+
+#[cfg(unix)]
+pub fn file_id(_path: &Path, metadata: &Metadata) -> String {
+    use std::os::unix::fs::MetadataExt;
+    format!("{}:{}", metadata.dev(), metadata.ino())
+}
+
+#[cfg(not(unix))]
+pub fn file_id(path: &Path, _metadata: &Metadata) -> String {
+    path.to_string_lossy().into_owned()
+}
+
+/// Returns the file itself, or the files in the directory (and its subdirectories, if recursive) matching the pattern.
+pub async fn scan_files(
+    path: &Path,
+    pattern: &str,
+    recursive: bool,
+) -> std::io::Result<Vec<(PathBuf, Metadata)>> {
+    let metadata = fs::metadata(path).await?;
+    if metadata.is_file() {
+        return Ok(vec![(path.to_owned(), metadata)]);
+    }
+
+    let mut files = Vec::new();
+    let mut directories = vec![path.to_owned()];
+    while let Some(directory) = directories.pop() {
+        let mut entries = fs::read_dir(&directory).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let Ok(metadata) = entry.metadata().await else {
+                continue;
+            };
+
+            if metadata.is_dir() {
+                if recursive {
+                    directories.push(entry.path());
+                }
+                continue;
+            }
+
+            if metadata.is_file() && matches_pattern(pattern, &entry.file_name().to_string_lossy())
+            {
+                files.push((entry.path(), metadata));
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// Matches the file name against the pattern with `*` (any characters) and `?` (single character) wildcards.
+fn matches_pattern(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let name = name.chars().collect::<Vec<_>>();
+    let (mut p, mut n) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, n));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            n = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn partial_line_should_be_read_once_completed_or_file_no_longer_present() {
+        let directory = TempDir::new().unwrap();
+        let path = directory.path().join("test.log");
+        std::fs::write(&path, b"first\r\nsecond\nthi").unwrap();
+        let mut file = open(&path).await;
+
+        let (lines, end_of_file) = file.read_lines(10).await.unwrap();
+        assert_eq!(contents(&lines), ["first", "second"]);
+        assert_eq!(lines[1].number, 2);
+        assert_eq!(lines[1].offset, 7);
+        assert_eq!(file.offset, 14);
+        assert!(!end_of_file);
+
+        append(&path, b"rd\nfou");
+        let (lines, _) = file.read_lines(10).await.unwrap();
+        assert_eq!(contents(&lines), ["third"]);
+        assert_eq!(lines[0].number, 3);
+
+        file.present = false;
+        let (lines, end_of_file) = file.read_lines(10).await.unwrap();
+        assert_eq!(contents(&lines), ["fou"]);
+        assert!(end_of_file);
+    }
+
+    #[tokio::test]
+    async fn lines_should_be_read_up_to_max_lines() {
+        let directory = TempDir::new().unwrap();
+        let path = directory.path().join("test.log");
+        std::fs::write(&path, b"1\n2\n3\n").unwrap();
+        let mut file = open(&path).await;
+
+        let (lines, end_of_file) = file.read_lines(2).await.unwrap();
+        assert_eq!(contents(&lines), ["1", "2"]);
+        assert!(!end_of_file);
+
+        let (lines, end_of_file) = file.read_lines(2).await.unwrap();
+        assert_eq!(contents(&lines), ["3"]);
+        assert!(end_of_file);
+    }
+
+    #[tokio::test]
+    async fn line_longer_than_buffer_should_be_read() {
+        let directory = TempDir::new().unwrap();
+        let path = directory.path().join("test.log");
+        let long_line = "x".repeat(READ_BUFFER_SIZE * 3);
+        std::fs::write(&path, format!("{long_line}\nshort\n")).unwrap();
+        let mut file = open(&path).await;
+
+        let (lines, end_of_file) = file.read_lines(10).await.unwrap();
+        assert_eq!(contents(&lines), [long_line, "short".to_owned()]);
+        assert!(end_of_file);
+    }
+
+    #[tokio::test]
+    async fn truncated_file_should_be_read_from_beginning() {
+        let directory = TempDir::new().unwrap();
+        let path = directory.path().join("test.log");
+        std::fs::write(&path, b"first\nsecond\n").unwrap();
+        let mut file = open(&path).await;
+        file.columns = Some(vec!["column".to_owned()]);
+        file.read_lines(10).await.unwrap();
+
+        std::fs::write(&path, b"new\n").unwrap();
+        let (lines, end_of_file) = file.read_lines(10).await.unwrap();
+        assert_eq!(contents(&lines), ["new"]);
+        assert_eq!(lines[0].number, 1);
+        assert_eq!(lines[0].offset, 0);
+        assert!(file.columns.is_none());
+        assert!(end_of_file);
+    }
+
+    #[tokio::test]
+    async fn rotated_file_should_be_read_until_end() {
+        let directory = TempDir::new().unwrap();
+        let path = directory.path().join("test.log");
+        let rotated_path = directory.path().join("test.log.1");
+        std::fs::write(&path, b"first\n").unwrap();
+        let old_id = file_id(&path, &std::fs::metadata(&path).unwrap());
+        let mut file = open(&path).await;
+        file.read_lines(10).await.unwrap();
+
+        append(&path, b"second\nlast");
+        std::fs::rename(&path, &rotated_path).unwrap();
+        std::fs::write(&path, b"new\n").unwrap();
+        let new_id = file_id(&path, &std::fs::metadata(&path).unwrap());
+        assert_eq!(
+            file_id(&rotated_path, &std::fs::metadata(&rotated_path).unwrap()),
+            old_id
+        );
+        if cfg!(unix) {
+            assert_ne!(old_id, new_id);
+        }
+
+        file.present = false;
+        let (lines, end_of_file) = file.read_lines(10).await.unwrap();
+        assert_eq!(contents(&lines), ["second", "last"]);
+        assert!(end_of_file);
+
+        let mut new_file = open(&path).await;
+        let (lines, _) = new_file.read_lines(10).await.unwrap();
+        assert_eq!(contents(&lines), ["new"]);
+    }
+
+    #[tokio::test]
+    async fn first_line_should_be_read_given_complete_line() {
+        let directory = TempDir::new().unwrap();
+        let path = directory.path().join("test.csv");
+        std::fs::write(&path, b"id,name\r\n1,a\n").unwrap();
+        let mut file = open(&path).await;
+        assert_eq!(
+            file.read_first_line().await.unwrap(),
+            Some(b"id,name".to_vec())
+        );
+
+        std::fs::write(&path, b"id,na").unwrap();
+        assert_eq!(file.read_first_line().await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn matching_files_should_be_scanned() {
+        let directory = TempDir::new().unwrap();
+        let nested = directory.path().join("nested");
+        std::fs::create_dir(&nested).unwrap();
+        std::fs::write(directory.path().join("a.log"), b"").unwrap();
+        std::fs::write(directory.path().join("b.txt"), b"").unwrap();
+        std::fs::write(nested.join("c.log"), b"").unwrap();
+
+        let mut files = scan_files(directory.path(), "*.log", false).await.unwrap();
+        assert_eq!(files.len(), 1);
+        assert!(files[0].0.ends_with("a.log"));
+
+        files = scan_files(directory.path(), "*.log", true).await.unwrap();
+        assert_eq!(files.len(), 2);
+
+        let path = directory.path().join("b.txt");
+        files = scan_files(&path, "*.log", false).await.unwrap();
+        assert_eq!(files.len(), 1);
+    }
+
+    #[test]
+    fn pattern_should_match_wildcards() {
+        assert!(matches_pattern("*", "app.log"));
+        assert!(matches_pattern("*.log", "app.log"));
+        assert!(matches_pattern("app-?.log", "app-1.log"));
+        assert!(matches_pattern("*-*.log", "app-2024-01.log"));
+        assert!(!matches_pattern("*.log", "app.log.1"));
+        assert!(!matches_pattern("app-?.log", "app-10.log"));
+        assert!(!matches_pattern("app.log", "app.txt"));
+    }
+
+    async fn open(path: &Path) -> TrackedFile {
+        let file = fs::File::open(path).await.unwrap();
+        TrackedFile::new(path.to_owned(), file, 0, 0, None, SystemTime::now())
+    }
+
+    fn append(path: &Path, content: &[u8]) {
+        let mut file = std::fs::OpenOptions::new().append(true).open(path).unwrap();
+        file.write_all(content).unwrap();
+    }
+
+    fn contents(lines: &[Line]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|line| String::from_utf8(line.content.clone()).unwrap())
+            .collect()
+    }
+}
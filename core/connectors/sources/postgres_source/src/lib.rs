@@ -20,17 +20,39 @@ use std::{collections::HashMap, str::FromStr, time::Duration};
 
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
+use iggy::prelude::{HeaderKey, HeaderValue};
 use iggy_connector_sdk::{
     ConnectorState, Error, ProducedMessage, ProducedMessages, Schema, Source, source_connector,
 };
+use pgoutput::{LogicalMessage, OldTuple, Relation, TupleValue};
 use serde::{Deserialize, Serialize};
 use sqlx::{Column, Pool, Postgres, Row, TypeInfo, postgres::PgPoolOptions};
 use tokio::sync::Mutex;
 use tracing::{error, info};
 use uuid::Uuid;
 
+mod pgoutput;
+
 source_connector!(PostgresSource);
 
+const DEFAULT_PUBLICATION_NAME: &str = "iggy_publication";
+const DEFAULT_REPLICATION_SLOT: &str = "iggy_slot";
+const TRANSACTION_ID_HEADER: &str = "pg_transaction_id";
+const COMMIT_LSN_HEADER: &str = "pg_commit_lsn";
+const COMMIT_TIMESTAMP_HEADER: &str = "pg_commit_timestamp";
+const TRANSACTION_BEGIN_HEADER: &str = "pg_transaction_begin";
+const TRANSACTION_COMMIT_HEADER: &str = "pg_transaction_commit";
+const BOOL_OID: u32 = 16;
+const INT8_OID: u32 = 20;
+const INT2_OID: u32 = 21;
+const INT4_OID: u32 = 23;
+const OID_OID: u32 = 26;
+const JSON_OID: u32 = 114;
+const FLOAT4_OID: u32 = 700;
+const FLOAT8_OID: u32 = 701;
+const NUMERIC_OID: u32 = 1700;
+const JSONB_OID: u32 = 3802;
+
 #[derive(Debug)]
 pub struct PostgresSource {
     pub id: u32,
@@ -49,13 +71,12 @@ pub struct PostgresSourceConfig {
     pub tracking_column: Option<String>,
     pub initial_offset: Option<String>,
     pub max_connections: Option<u32>,
-    pub enable_wal_cdc: Option<bool>,
     pub custom_query: Option<String>,
     pub snake_case_columns: Option<bool>,
     pub include_metadata: Option<bool>,
     pub replication_slot: Option<String>,
     pub publication_name: Option<String>,
-    pub capture_operations: Option<Vec<String>>, // INSERT, UPDATE, DELETE
+    pub capture_operations: Option<Vec<String>>, // INSERT, UPDATE, DELETE, TRUNCATE
 }
 
 #[derive(Debug)]
@@ -63,6 +84,17 @@ struct State {
     last_poll_time: DateTime<Utc>,
     tracking_offsets: HashMap<String, String>,
     processed_rows: u64,
+    /// The end LSN of the last produced transaction, the already produced ones are skipped when peeked again.
+    last_lsn: Option<u64>,
+    /// The end LSN of the last transaction whose state has been saved by the runtime, up to which the slot can be advanced.
+    committed_lsn: Option<u64>,
+    relations: HashMap<u32, Relation>,
+}
+
+/// The state persisted by the runtime, used to resume the CDC after the last produced transaction.
+#[derive(Debug, Serialize, Deserialize)]
+struct CdcState {
+    last_lsn: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -74,8 +106,36 @@ pub struct DatabaseRecord {
     pub old_data: Option<serde_json::Value>,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ChangeRecord {
+    pub schema_name: String,
+    pub table_name: String,
+    pub operation_type: String, // INSERT, UPDATE, DELETE, TRUNCATE
+    pub timestamp: DateTime<Utc>,
+    pub transaction_id: u32,
+    pub lsn: String,
+    pub before: Option<serde_json::Value>,
+    pub after: Option<serde_json::Value>,
+}
+
+#[derive(Debug)]
+struct Transaction {
+    xid: u32,
+    commit_lsn: u64,
+    commit_timestamp: DateTime<Utc>,
+    changes: Vec<ChangeRecord>,
+}
+
 impl PostgresSource {
-    pub fn new(id: u32, config: PostgresSourceConfig, _state: Option<ConnectorState>) -> Self {
+    pub fn new(id: u32, config: PostgresSourceConfig, state: Option<ConnectorState>) -> Self {
+        let last_lsn = state.as_ref().and_then(read_cdc_lsn);
+        if let Some(lsn) = last_lsn {
+            info!(
+                "PostgreSQL source connector with ID: {id} will resume CDC after LSN: {}",
+                pgoutput::format_lsn(lsn)
+            );
+        }
+
         PostgresSource {
             id,
             pool: None,
@@ -84,6 +144,9 @@ impl PostgresSource {
                 last_poll_time: Utc::now(),
                 tracking_offsets: HashMap::new(),
                 processed_rows: 0,
+                last_lsn,
+                committed_lsn: last_lsn,
+                relations: HashMap::new(),
             }),
         }
     }
@@ -110,11 +173,21 @@ impl PostgresSource {
         Ok(())
     }
 
-    async fn setup_cdc(&self) -> Result<(), Error> {
-        if !self.config.enable_wal_cdc.unwrap_or(false) {
-            return Ok(());
-        }
+    fn publication_name(&self) -> &str {
+        self.config
+            .publication_name
+            .as_deref()
+            .unwrap_or(DEFAULT_PUBLICATION_NAME)
+    }
+
+    fn replication_slot(&self) -> &str {
+        self.config
+            .replication_slot
+            .as_deref()
+            .unwrap_or(DEFAULT_REPLICATION_SLOT)
+    }
 
+    async fn setup_cdc(&self) -> Result<(), Error> {
         let pool = self
             .pool
             .as_ref()
@@ -131,38 +204,38 @@ impl PostgresSource {
             ));
         }
 
-        let publication_name = self
-            .config
-            .publication_name
-            .as_deref()
-            .unwrap_or("iggy_publication");
-        let tables_clause = if self.config.tables.is_empty() {
-            "FOR ALL TABLES".to_string()
-        } else {
-            format!("FOR TABLE {}", self.config.tables.join(", "))
-        };
+        let publication_name = self.publication_name();
+        let publication_exists: bool =
+            sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM pg_publication WHERE pubname = $1)")
+                .bind(publication_name)
+                .fetch_one(pool)
+                .await
+                .map_err(|e| Error::InitError(format!("Failed to check publication: {e}")))?;
 
-        let create_publication_sql =
-            format!("CREATE PUBLICATION IF NOT EXISTS {publication_name} {tables_clause}");
+        if !publication_exists {
+            let tables_clause = if self.config.tables.is_empty() {
+                "FOR ALL TABLES".to_string()
+            } else {
+                format!("FOR TABLE {}", self.config.tables.join(", "))
+            };
 
-        sqlx::query(&create_publication_sql)
-            .execute(pool)
-            .await
-            .map_err(|e| Error::InitError(format!("Failed to create publication: {e}")))?;
+            let create_publication_sql =
+                format!("CREATE PUBLICATION {publication_name} {tables_clause}");
 
-        let slot_name = self
-            .config
-            .replication_slot
-            .as_deref()
-            .unwrap_or("iggy_slot");
-        let create_slot_sql = format!(
-            "SELECT pg_create_logical_replication_slot('{slot_name}', 'pgoutput') WHERE NOT EXISTS (SELECT 1 FROM pg_replication_slots WHERE slot_name = '{slot_name}')"
-        );
+            sqlx::query(&create_publication_sql)
+                .execute(pool)
+                .await
+                .map_err(|e| Error::InitError(format!("Failed to create publication: {e}")))?;
+        }
 
-        sqlx::query(&create_slot_sql)
-            .fetch_optional(pool)
-            .await
-            .map_err(|e| Error::InitError(format!("Failed to create replication slot: {e}")))?;
+        let slot_name = self.replication_slot();
+        sqlx::query(
+            "SELECT pg_create_logical_replication_slot($1, 'pgoutput') WHERE NOT EXISTS (SELECT 1 FROM pg_replication_slots WHERE slot_name = $1)",
+        )
+        .bind(slot_name)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| Error::InitError(format!("Failed to create replication slot: {e}")))?;
 
         info!(
             "PostgreSQL CDC setup completed. Publication: {}, Slot: {}",
@@ -177,16 +250,9 @@ impl PostgresSource {
             .as_ref()
             .ok_or_else(|| Error::InitError("Database not connected".to_string()))?;
 
-        let slot_name = self
-            .config
-            .replication_slot
-            .as_deref()
-            .unwrap_or("iggy_slot");
-        let publication_name = self
-            .config
-            .publication_name
-            .as_deref()
-            .unwrap_or("iggy_publication");
+        let slot_name = self.replication_slot();
+        let publication_name = self.publication_name();
+        let batch_size = self.config.batch_size.unwrap_or(1000);
         let capture_ops = self
             .config
             .capture_operations
@@ -194,187 +260,249 @@ impl PostgresSource {
             .map(|ops| ops.iter().map(|s| s.as_str()).collect::<Vec<_>>())
             .unwrap_or_else(|| vec!["INSERT", "UPDATE", "DELETE"]);
 
-        let logical_repl_sql = format!(
-            "SELECT lsn, xid, data FROM pg_logical_slot_get_changes('{slot_name}', NULL, NULL, 'proto_version', '1', 'publication_names', '{publication_name}')"
-        );
+        let mut state = self.state.lock().await;
 
-        let rows = sqlx::query(&logical_repl_sql)
-            .fetch_all(pool)
+        // The transactions whose state has been saved by the runtime are confirmed, so the slot no longer needs to retain their WAL.
+        // The produced but not yet saved ones are still retained, to be produced again if the runtime stops before saving them.
+        // The slot is never moved backwards, e.g. when the persisted state is older than the slot.
+        if let Some(committed_lsn) = state.committed_lsn {
+            sqlx::query(
+                "SELECT pg_replication_slot_advance(slot_name, $2::pg_lsn) FROM pg_replication_slots WHERE slot_name = $1 AND confirmed_flush_lsn < $2::pg_lsn",
+            )
+            .bind(slot_name)
+            .bind(pgoutput::format_lsn(committed_lsn))
+            .fetch_optional(pool)
             .await
             .map_err(|e| {
-                error!("Failed to fetch CDC changes: {}", e);
-                Error::InvalidRecord
+                Error::CannotFetchData(format!("Failed to advance replication slot: {e}"))
             })?;
+        }
+
+        // The changes are only peeked, the decoding stops at the end of the transaction exceeding the batch size.
+        let rows = sqlx::query(
+            "SELECT lsn::text AS lsn, data FROM pg_logical_slot_peek_binary_changes($1, NULL, $2, 'proto_version', '1', 'publication_names', $3)",
+        )
+        .bind(slot_name)
+        .bind(i32::try_from(batch_size).unwrap_or(i32::MAX))
+        .bind(publication_name)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| Error::CannotFetchData(format!("Failed to fetch CDC changes: {e}")))?;
 
         let mut messages = Vec::new();
-        let mut state = self.state.lock().await;
+        let mut transaction: Option<Transaction> = None;
 
         for row in rows {
-            let data: String = row.try_get("data").map_err(|_| Error::InvalidRecord)?;
+            let lsn: String = row.try_get("lsn").map_err(|_| Error::InvalidRecord)?;
+            let data: Vec<u8> = row.try_get("data").map_err(|_| Error::InvalidRecord)?;
+            let message = LogicalMessage::decode(&data).map_err(|error| {
+                error!("Failed to decode logical replication message at LSN: {lsn}. {error}");
+                Error::InvalidRecord
+            })?;
 
-            if let Some(change_record) = self.parse_logical_replication_message(&data, &capture_ops)
-            {
-                let payload =
-                    simd_json::to_vec(&change_record).map_err(|_| Error::InvalidRecord)?;
+            let (relation_id, operation_type, before, after) = match message {
+                LogicalMessage::Begin {
+                    final_lsn,
+                    commit_timestamp,
+                    xid,
+                } => {
+                    transaction = Some(Transaction {
+                        xid,
+                        commit_lsn: final_lsn,
+                        commit_timestamp: to_datetime(commit_timestamp),
+                        changes: Vec::new(),
+                    });
+                    continue;
+                }
+                LogicalMessage::Commit { end_lsn, .. } => {
+                    let Some(transaction) = transaction.take() else {
+                        continue;
+                    };
 
-                let message = ProducedMessage {
-                    id: Some(Uuid::new_v4().as_u128()),
-                    headers: None,
-                    checksum: None,
-                    timestamp: Some(chrono::Utc::now().timestamp_millis() as u64),
-                    origin_timestamp: Some(chrono::Utc::now().timestamp_millis() as u64),
-                    payload,
-                };
+                    // Already produced before the connector was restarted.
+                    if state.last_lsn.is_some_and(|last_lsn| end_lsn <= last_lsn) {
+                        continue;
+                    }
 
-                messages.push(message);
-                state.processed_rows += 1;
-            }
-        }
+                    state.processed_rows += transaction.changes.len() as u64;
+                    state.last_lsn = Some(end_lsn);
+                    messages.extend(self.build_cdc_messages(transaction)?);
+                    continue;
+                }
+                LogicalMessage::Relation(relation) => {
+                    state.relations.insert(relation.id, relation);
+                    continue;
+                }
+                LogicalMessage::Insert { relation_id, new } => {
+                    (relation_id, "INSERT", None, Some(new))
+                }
+                LogicalMessage::Update {
+                    relation_id,
+                    old,
+                    new,
+                } => (relation_id, "UPDATE", old, Some(new)),
+                LogicalMessage::Delete { relation_id, old } => {
+                    (relation_id, "DELETE", Some(old), None)
+                }
+                LogicalMessage::Truncate { relation_ids } => {
+                    let Some(transaction) = transaction.as_mut() else {
+                        continue;
+                    };
 
-        info!("CDC: Fetched {} change records", messages.len());
-        Ok(messages)
-    }
+                    if !capture_ops.contains(&"TRUNCATE") {
+                        continue;
+                    }
 
-    fn parse_logical_replication_message(
-        &self,
-        data: &str,
-        capture_ops: &[&str],
-    ) -> Option<DatabaseRecord> {
-        if data.starts_with("BEGIN") || data.starts_with("COMMIT") {
-            return None;
-        }
+                    for relation_id in relation_ids {
+                        let relation = get_relation(&state.relations, relation_id)?;
+                        let record = self.build_change_record(
+                            relation,
+                            transaction,
+                            &lsn,
+                            "TRUNCATE",
+                            None,
+                            None,
+                        );
+                        transaction.changes.push(record);
+                    }
+                    continue;
+                }
+                LogicalMessage::Other => continue,
+            };
 
-        if data.starts_with("INSERT:") && capture_ops.contains(&"INSERT") {
-            return self.parse_insert_message(data);
-        }
+            let Some(transaction) = transaction.as_mut() else {
+                continue;
+            };
 
-        if data.starts_with("UPDATE:") && capture_ops.contains(&"UPDATE") {
-            return self.parse_update_message(data);
-        }
+            if !capture_ops.contains(&operation_type) {
+                continue;
+            }
 
-        if data.starts_with("DELETE:") && capture_ops.contains(&"DELETE") {
-            return self.parse_delete_message(data);
+            let relation = get_relation(&state.relations, relation_id)?;
+            let record = self.build_change_record(
+                relation,
+                transaction,
+                &lsn,
+                operation_type,
+                before,
+                after,
+            );
+            transaction.changes.push(record);
         }
 
-        None
+        info!("CDC: Fetched {} change records", messages.len());
+        Ok(messages)
     }
 
-    fn parse_insert_message(&self, data: &str) -> Option<DatabaseRecord> {
-        if let Some(table_start) = data.find("table ") {
-            if let Some(colon_pos) = data[table_start..].find(':') {
-                let table_part = &data[table_start + 6..table_start + colon_pos];
-                let table_name = table_part
-                    .split('.')
-                    .next_back()
-                    .unwrap_or(table_part)
-                    .to_string();
-
-                let data_part = &data[table_start + colon_pos + 1..];
-                let parsed_data = self.parse_record_data(data_part);
-
-                return Some(DatabaseRecord {
-                    table_name,
-                    operation_type: "INSERT".to_string(),
-                    timestamp: Utc::now(),
-                    data: serde_json::Value::Object(parsed_data),
-                    old_data: None,
-                });
-            }
+    fn build_change_record(
+        &self,
+        relation: &Relation,
+        transaction: &Transaction,
+        lsn: &str,
+        operation_type: &str,
+        before: Option<OldTuple>,
+        after: Option<Vec<TupleValue>>,
+    ) -> ChangeRecord {
+        ChangeRecord {
+            schema_name: relation.namespace.clone(),
+            table_name: relation.name.clone(),
+            operation_type: operation_type.to_string(),
+            timestamp: transaction.commit_timestamp,
+            transaction_id: transaction.xid,
+            lsn: lsn.to_string(),
+            before: before.map(|old| self.tuple_to_json(relation, old.values, old.key_only)),
+            after: after.map(|values| self.tuple_to_json(relation, values, false)),
         }
-        None
     }
 
-    fn parse_update_message(&self, data: &str) -> Option<DatabaseRecord> {
-        if let Some(table_start) = data.find("table ") {
-            if let Some(colon_pos) = data[table_start..].find(':') {
-                let table_part = &data[table_start + 6..table_start + colon_pos];
-                let table_name = table_part
-                    .split('.')
-                    .next_back()
-                    .unwrap_or(table_part)
-                    .to_string();
-
-                let data_part = &data[table_start + colon_pos + 1..];
-                let parsed_data = self.parse_record_data(data_part);
-
-                return Some(DatabaseRecord {
-                    table_name,
-                    operation_type: "UPDATE".to_string(),
-                    timestamp: Utc::now(),
-                    data: serde_json::Value::Object(parsed_data),
-                    old_data: None,
-                });
+    fn tuple_to_json(
+        &self,
+        relation: &Relation,
+        values: Vec<TupleValue>,
+        key_only: bool,
+    ) -> serde_json::Value {
+        let mut data = serde_json::Map::new();
+        for (column, value) in relation.columns.iter().zip(values) {
+            if key_only && !column.is_key {
+                continue;
             }
+
+            let value = match value {
+                TupleValue::Null => serde_json::Value::Null,
+                // The value wasn't changed, so it's not included in the image at all.
+                TupleValue::UnchangedToast => continue,
+                TupleValue::Text(text) => text_to_json(column.type_id, text),
+                TupleValue::Binary(bytes) => {
+                    serde_json::Value::String(String::from_utf8_lossy(&bytes).into_owned())
+                }
+            };
+            data.insert(self.column_name(&column.name), value);
         }
-        None
+        serde_json::Value::Object(data)
     }
 
-    fn parse_delete_message(&self, data: &str) -> Option<DatabaseRecord> {
-        if let Some(table_start) = data.find("table ") {
-            if let Some(colon_pos) = data[table_start..].find(':') {
-                let table_part = &data[table_start + 6..table_start + colon_pos];
-                let table_name = table_part
-                    .split('.')
-                    .next_back()
-                    .unwrap_or(table_part)
-                    .to_string();
-
-                let data_part = &data[table_start + colon_pos + 1..];
-                let parsed_data = self.parse_record_data(data_part);
-
-                return Some(DatabaseRecord {
-                    table_name,
-                    operation_type: "DELETE".to_string(),
-                    timestamp: Utc::now(),
-                    data: serde_json::Value::Object(parsed_data),
-                    old_data: None,
-                });
-            }
+    fn column_name(&self, name: &str) -> String {
+        if self.config.snake_case_columns.unwrap_or(false) {
+            self.to_snake_case(name)
+        } else {
+            name.to_string()
         }
-        None
     }
 
-    fn parse_record_data(&self, data: &str) -> serde_json::Map<String, serde_json::Value> {
-        let mut result = serde_json::Map::new();
-
-        let parts = data.split_whitespace();
-
-        for part in parts {
-            if let Some(bracket_pos) = part.find('[') {
-                if let Some(_close_bracket) = part.find(']') {
-                    if let Some(colon_pos) = part.find(':') {
-                        let column_name = &part[..bracket_pos];
-                        let value_str = &part[colon_pos + 1..];
-
-                        let cleaned_value =
-                            if value_str.starts_with('\'') && value_str.ends_with('\'') {
-                                &value_str[1..value_str.len() - 1]
-                            } else {
-                                value_str
-                            };
-
-                        let value = if let Ok(num) = cleaned_value.parse::<i64>() {
-                            serde_json::Value::Number(serde_json::Number::from(num))
-                        } else if let Ok(float) = cleaned_value.parse::<f64>() {
-                            serde_json::Value::Number(
-                                serde_json::Number::from_f64(float)
-                                    .unwrap_or(serde_json::Number::from(0)),
-                            )
-                        } else if cleaned_value.eq_ignore_ascii_case("true") {
-                            serde_json::Value::Bool(true)
-                        } else if cleaned_value.eq_ignore_ascii_case("false") {
-                            serde_json::Value::Bool(false)
-                        } else {
-                            serde_json::Value::String(cleaned_value.to_string())
-                        };
-
-                        result.insert(column_name.to_string(), value);
-                    }
-                }
-            }
+    fn build_cdc_messages(&self, transaction: Transaction) -> Result<Vec<ProducedMessage>, Error> {
+        let commit_lsn = pgoutput::format_lsn(transaction.commit_lsn);
+        let commit_timestamp = transaction.commit_timestamp.timestamp_micros() as u64;
+        let last_index = transaction.changes.len().saturating_sub(1);
+        let mut messages = Vec::with_capacity(transaction.changes.len());
+
+        for (index, record) in transaction.changes.into_iter().enumerate() {
+            let headers = HashMap::from([
+                (
+                    header_key(TRANSACTION_ID_HEADER)?,
+                    HeaderValue::from_uint32(transaction.xid).map_err(|_| Error::InvalidRecord)?,
+                ),
+                (
+                    header_key(COMMIT_LSN_HEADER)?,
+                    HeaderValue::from_str(&commit_lsn).map_err(|_| Error::InvalidRecord)?,
+                ),
+                (
+                    header_key(COMMIT_TIMESTAMP_HEADER)?,
+                    HeaderValue::from_uint64(commit_timestamp).map_err(|_| Error::InvalidRecord)?,
+                ),
+                (
+                    header_key(TRANSACTION_BEGIN_HEADER)?,
+                    HeaderValue::from_bool(index == 0).map_err(|_| Error::InvalidRecord)?,
+                ),
+                (
+                    header_key(TRANSACTION_COMMIT_HEADER)?,
+                    HeaderValue::from_bool(index == last_index)
+                        .map_err(|_| Error::InvalidRecord)?,
+                ),
+            ]);
+
+            let payload = simd_json::to_vec(&record).map_err(|_| Error::InvalidRecord)?;
+            messages.push(ProducedMessage {
+                id: Some(Uuid::new_v4().as_u128()),
+                headers: Some(headers),
+                checksum: None,
+                timestamp: Some(Utc::now().timestamp_millis() as u64),
+                origin_timestamp: Some(transaction.commit_timestamp.timestamp_millis() as u64),
+                payload,
+            });
         }
 
-        result
+        Ok(messages)
+    }
+
+    fn cdc_state(&self, state: &State) -> Result<ConnectorState, Error> {
+        let cdc_state = CdcState {
+            last_lsn: state.last_lsn.map(pgoutput::format_lsn),
+        };
+        let state = serde_json::to_vec(&cdc_state).map_err(|error| {
+            error!("Failed to serialize CDC state. {error}");
+            Error::InvalidState
+        })?;
+        Ok(ConnectorState(state))
     }
 
     async fn poll_tables(&self) -> Result<Vec<ProducedMessage>, Error> {
@@ -664,13 +792,33 @@ impl Source for PostgresSource {
             state.processed_rows
         );
 
+        let state = match self.config.mode.as_str() {
+            "cdc" => Some(self.cdc_state(&state)?),
+            _ => None,
+        };
+
         Ok(ProducedMessages {
             schema: Schema::Json,
             messages,
-            state: None,
+            state,
         })
     }
 
+    async fn commit(&self, state: ConnectorState) -> Result<(), Error> {
+        let Some(lsn) = read_cdc_lsn(&state) else {
+            return Ok(());
+        };
+
+        let mut state = self.state.lock().await;
+        if state
+            .committed_lsn
+            .is_none_or(|committed_lsn| lsn > committed_lsn)
+        {
+            state.committed_lsn = Some(lsn);
+        }
+        Ok(())
+    }
+
     async fn close(&mut self) -> Result<(), Error> {
         if let Some(pool) = self.pool.take() {
             pool.close().await;
@@ -688,3 +836,107 @@ impl Source for PostgresSource {
         Ok(())
     }
 }
+
+fn read_cdc_lsn(state: &ConnectorState) -> Option<u64> {
+    serde_json::from_slice::<CdcState>(&state.0)
+        .inspect_err(|error| error!("Failed to deserialize CDC state. {error}"))
+        .ok()
+        .and_then(|state| state.last_lsn)
+        .and_then(|lsn| pgoutput::parse_lsn(&lsn))
+}
+
+fn get_relation(relations: &HashMap<u32, Relation>, relation_id: u32) -> Result<&Relation, Error> {
+    relations.get(&relation_id).ok_or_else(|| {
+        error!("Received the change for unknown relation with ID: {relation_id}");
+        Error::InvalidRecord
+    })
+}
+
+fn to_datetime(timestamp: i64) -> DateTime<Utc> {
+    DateTime::from_timestamp_micros(pgoutput::to_unix_timestamp_micros(timestamp))
+        .unwrap_or_default()
+}
+
+fn header_key(key: &str) -> Result<HeaderKey, Error> {
+    HeaderKey::new(key).map_err(|_| Error::InvalidRecord)
+}
+
+/// Converts the value sent in the text format to JSON, based on the OID of the column type.
+fn text_to_json(type_id: u32, text: String) -> serde_json::Value {
+    match type_id {
+        BOOL_OID => serde_json::Value::Bool(text == "t"),
+        INT2_OID | INT4_OID | INT8_OID | OID_OID => text
+            .parse::<i64>()
+            .map(serde_json::Value::from)
+            .unwrap_or(serde_json::Value::String(text)),
+        FLOAT4_OID | FLOAT8_OID | NUMERIC_OID => text
+            .parse::<f64>()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+            .map(serde_json::Value::Number)
+            .unwrap_or(serde_json::Value::String(text)),
+        JSON_OID | JSONB_OID => {
+            serde_json::from_str(&text).unwrap_or(serde_json::Value::String(text))
+        }
+        _ => serde_json::Value::String(text),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn committed_lsn_should_only_move_forward() {
+        let state = cdc_state("0/10");
+        let source = PostgresSource::new(1, config(), Some(state));
+        assert_eq!(source.state.lock().await.committed_lsn, Some(0x10));
+
+        source.commit(cdc_state("0/20")).await.unwrap();
+        assert_eq!(source.state.lock().await.committed_lsn, Some(0x20));
+
+        source.commit(cdc_state("0/15")).await.unwrap();
+        source
+            .commit(ConnectorState(b"invalid".to_vec()))
+            .await
+            .unwrap();
+        let state = source.state.lock().await;
+        assert_eq!(state.committed_lsn, Some(0x20));
+        assert_eq!(state.last_lsn, Some(0x10));
+    }
+
+    #[test]
+    fn cdc_lsn_should_not_be_read_given_invalid_state() {
+        assert_eq!(read_cdc_lsn(&cdc_state("1/A")), Some((1 << 32) | 0xA));
+        assert_eq!(read_cdc_lsn(&ConnectorState(b"{}".to_vec())), None);
+        assert_eq!(read_cdc_lsn(&ConnectorState(vec![])), None);
+    }
+
+    fn cdc_state(lsn: &str) -> ConnectorState {
+        ConnectorState(
+            serde_json::to_vec(&CdcState {
+                last_lsn: Some(lsn.to_owned()),
+            })
+            .unwrap(),
+        )
+    }
+
+    fn config() -> PostgresSourceConfig {
+        PostgresSourceConfig {
+            connection_string: "postgres://localhost".to_owned(),
+            mode: "cdc".to_owned(),
+            tables: vec![],
+            poll_interval: None,
+            batch_size: None,
+            tracking_column: None,
+            initial_offset: None,
+            max_connections: None,
+            custom_query: None,
+            snake_case_columns: None,
+            include_metadata: None,
+            replication_slot: None,
+            publication_name: None,
+            capture_operations: None,
+        }
+    }
+}
@@ -0,0 +1,537 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+//! Decoder of the `pgoutput` logical replication protocol messages (version 1),
+//! as returned by `pg_logical_slot_peek_binary_changes`.
+
+use std::fmt;
+
+const BEGIN: u8 = b'B';
+const COMMIT: u8 = b'C';
+const RELATION: u8 = b'R';
+const INSERT: u8 = b'I';
+const UPDATE: u8 = b'U';
+const DELETE: u8 = b'D';
+const TRUNCATE: u8 = b'T';
+const KEY_TUPLE: u8 = b'K';
+const OLD_TUPLE: u8 = b'O';
+const NEW_TUPLE: u8 = b'N';
+const NULL_VALUE: u8 = b'n';
+const UNCHANGED_TOAST_VALUE: u8 = b'u';
+const TEXT_VALUE: u8 = b't';
+const BINARY_VALUE: u8 = b'b';
+const KEY_COLUMN_FLAG: u8 = 1;
+/// Microseconds between the Unix epoch and the PostgreSQL epoch (2000-01-01).
+const POSTGRES_EPOCH_OFFSET_MICROS: i64 = 946_684_800_000_000;
+
+#[derive(Debug)]
+pub enum LogicalMessage {
+    Begin {
+        final_lsn: u64,
+        commit_timestamp: i64,
+        xid: u32,
+    },
+    Commit {
+        end_lsn: u64,
+    },
+    Relation(Relation),
+    Insert {
+        relation_id: u32,
+        new: Vec<TupleValue>,
+    },
+    Update {
+        relation_id: u32,
+        old: Option<OldTuple>,
+        new: Vec<TupleValue>,
+    },
+    Delete {
+        relation_id: u32,
+        old: OldTuple,
+    },
+    Truncate {
+        relation_ids: Vec<u32>,
+    },
+    /// Origin, type and generic logical decoding messages which are not needed to build the change records.
+    Other,
+}
+
+#[derive(Debug, Clone)]
+pub struct Relation {
+    pub id: u32,
+    pub namespace: String,
+    pub name: String,
+    pub columns: Vec<RelationColumn>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RelationColumn {
+    pub name: String,
+    pub type_id: u32,
+    pub is_key: bool,
+}
+
+/// The old values of the updated or deleted row, depending on the replica identity of the table.
+#[derive(Debug)]
+pub struct OldTuple {
+    /// Only the replica identity (e.g. primary key) columns are set, the other ones are always null.
+    pub key_only: bool,
+    pub values: Vec<TupleValue>,
+}
+
+#[derive(Debug)]
+pub enum TupleValue {
+    Null,
+    /// The TOASTed value that wasn't changed and thus wasn't sent.
+    UnchangedToast,
+    Text(String),
+    Binary(Vec<u8>),
+}
+
+#[derive(Debug)]
+pub struct DecodeError(String);
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl LogicalMessage {
+    pub fn decode(data: &[u8]) -> Result<Self, DecodeError> {
+        let mut reader = Reader { data, position: 0 };
+        let tag = reader.read_u8()?;
+        let message = match tag {
+            BEGIN => LogicalMessage::Begin {
+                final_lsn: reader.read_u64()?,
+                commit_timestamp: reader.read_i64()?,
+                xid: reader.read_u32()?,
+            },
+            COMMIT => {
+                let _flags = reader.read_u8()?;
+                let _commit_lsn = reader.read_u64()?;
+                LogicalMessage::Commit {
+                    end_lsn: reader.read_u64()?,
+                }
+            }
+            RELATION => {
+                let id = reader.read_u32()?;
+                let namespace = reader.read_string()?;
+                let name = reader.read_string()?;
+                let _replica_identity = reader.read_u8()?;
+                let columns_count = reader.read_u16()?;
+                let mut columns = Vec::with_capacity(columns_count as usize);
+                for _ in 0..columns_count {
+                    let flags = reader.read_u8()?;
+                    let name = reader.read_string()?;
+                    let type_id = reader.read_u32()?;
+                    let _type_modifier = reader.read_u32()?;
+                    columns.push(RelationColumn {
+                        name,
+                        type_id,
+                        is_key: flags & KEY_COLUMN_FLAG == KEY_COLUMN_FLAG,
+                    });
+                }
+                LogicalMessage::Relation(Relation {
+                    id,
+                    namespace,
+                    name,
+                    columns,
+                })
+            }
+            INSERT => {
+                let relation_id = reader.read_u32()?;
+                reader.expect(NEW_TUPLE)?;
+                LogicalMessage::Insert {
+                    relation_id,
+                    new: reader.read_tuple()?,
+                }
+            }
+            UPDATE => {
+                let relation_id = reader.read_u32()?;
+                let old = match reader.read_u8()? {
+                    tag @ (KEY_TUPLE | OLD_TUPLE) => {
+                        let old = OldTuple {
+                            key_only: tag == KEY_TUPLE,
+                            values: reader.read_tuple()?,
+                        };
+                        reader.expect(NEW_TUPLE)?;
+                        Some(old)
+                    }
+                    NEW_TUPLE => None,
+                    tag => {
+                        return Err(DecodeError(format!(
+                            "Invalid update tuple type: {}",
+                            tag as char
+                        )));
+                    }
+                };
+                LogicalMessage::Update {
+                    relation_id,
+                    old,
+                    new: reader.read_tuple()?,
+                }
+            }
+            DELETE => {
+                let relation_id = reader.read_u32()?;
+                let key_only = match reader.read_u8()? {
+                    KEY_TUPLE => true,
+                    OLD_TUPLE => false,
+                    tag => {
+                        return Err(DecodeError(format!(
+                            "Invalid delete tuple type: {}",
+                            tag as char
+                        )));
+                    }
+                };
+                LogicalMessage::Delete {
+                    relation_id,
+                    old: OldTuple {
+                        key_only,
+                        values: reader.read_tuple()?,
+                    },
+                }
+            }
+            TRUNCATE => {
+                let relations_count = reader.read_u32()?;
+                let _options = reader.read_u8()?;
+                let mut relation_ids = Vec::with_capacity(relations_count as usize);
+                for _ in 0..relations_count {
+                    relation_ids.push(reader.read_u32()?);
+                }
+                LogicalMessage::Truncate { relation_ids }
+            }
+            _ => LogicalMessage::Other,
+        };
+        Ok(message)
+    }
+}
+
+/// Converts the PostgreSQL timestamp (microseconds since 2000-01-01) to the Unix timestamp in microseconds.
+pub fn to_unix_timestamp_micros(timestamp: i64) -> i64 {
+    timestamp + POSTGRES_EPOCH_OFFSET_MICROS
+}
+
+/// Formats the LSN the same way as PostgreSQL does, e.g. `0/16B3748`.
+pub fn format_lsn(lsn: u64) -> String {
+    format!("{:X}/{:X}", lsn >> 32, lsn & 0xFFFF_FFFF)
+}
+
+pub fn parse_lsn(lsn: &str) -> Option<u64> {
+    let (high, low) = lsn.split_once('/')?;
+    let high = u64::from_str_radix(high, 16).ok()?;
+    let low = u64::from_str_radix(low, 16).ok()?;
+    Some((high << 32) | low)
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl Reader<'_> {
+    fn read_bytes(&mut self, length: usize) -> Result<&[u8], DecodeError> {
+        let end = self.position + length;
+        if end > self.data.len() {
+            return Err(DecodeError(format!(
+                "Unexpected end of message at position: {}, expected {length} more bytes",
+                self.position
+            )));
+        }
+        let bytes = &self.data[self.position..end];
+        self.position = end;
+        Ok(bytes)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], DecodeError> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.read_bytes(N)?);
+        Ok(array)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, DecodeError> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    fn read_u16(&mut self) -> Result<u16, DecodeError> {
+        Ok(u16::from_be_bytes(self.read_array()?))
+    }
+
+    fn read_u32(&mut self) -> Result<u32, DecodeError> {
+        Ok(u32::from_be_bytes(self.read_array()?))
+    }
+
+    fn read_u64(&mut self) -> Result<u64, DecodeError> {
+        Ok(u64::from_be_bytes(self.read_array()?))
+    }
+
+    fn read_i64(&mut self) -> Result<i64, DecodeError> {
+        Ok(i64::from_be_bytes(self.read_array()?))
+    }
+
+    fn read_string(&mut self) -> Result<String, DecodeError> {
+        let remaining = &self.data[self.position..];
+        let Some(length) = remaining.iter().position(|byte| *byte == 0) else {
+            return Err(DecodeError(format!(
+                "Unterminated string at position: {}",
+                self.position
+            )));
+        };
+        let value = String::from_utf8_lossy(&remaining[..length]).into_owned();
+        self.position += length + 1;
+        Ok(value)
+    }
+
+    fn expect(&mut self, expected: u8) -> Result<(), DecodeError> {
+        let tag = self.read_u8()?;
+        if tag != expected {
+            return Err(DecodeError(format!(
+                "Expected tuple type: {}, got: {}",
+                expected as char, tag as char
+            )));
+        }
+        Ok(())
+    }
+
+    fn read_tuple(&mut self) -> Result<Vec<TupleValue>, DecodeError> {
+        let columns_count = self.read_u16()?;
+        let mut values = Vec::with_capacity(columns_count as usize);
+        for _ in 0..columns_count {
+            let value = match self.read_u8()? {
+                NULL_VALUE => TupleValue::Null,
+                UNCHANGED_TOAST_VALUE => TupleValue::UnchangedToast,
+                TEXT_VALUE => {
+                    let length = self.read_u32()? as usize;
+                    TupleValue::Text(String::from_utf8_lossy(self.read_bytes(length)?).into_owned())
+                }
+                BINARY_VALUE => {
+                    let length = self.read_u32()? as usize;
+                    TupleValue::Binary(self.read_bytes(length)?.to_vec())
+                }
+                tag => {
+                    return Err(DecodeError(format!(
+                        "Invalid tuple value type: {}",
+                        tag as char
+                    )));
+                }
+            };
+            values.push(value);
+        }
+        Ok(values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lsn_should_be_formatted_and_parsed() {
+        let lsn = (0x16 << 32) | 0xB374_8;
+
+        assert_eq!(format_lsn(lsn), "16/B3748");
+        assert_eq!(parse_lsn("16/B3748"), Some(lsn));
+        assert_eq!(parse_lsn(&format_lsn(u64::MAX)), Some(u64::MAX));
+        assert_eq!(parse_lsn("0/0"), Some(0));
+    }
+
+    #[test]
+    fn lsn_should_not_be_parsed_given_invalid_value() {
+        assert_eq!(parse_lsn("16B3748"), None);
+        assert_eq!(parse_lsn("X/1"), None);
+        assert_eq!(parse_lsn("1/"), None);
+    }
+
+    #[test]
+    fn begin_and_commit_should_be_decoded() {
+        let mut data = vec![BEGIN];
+        data.extend(100u64.to_be_bytes());
+        data.extend(200i64.to_be_bytes());
+        data.extend(7u32.to_be_bytes());
+        let LogicalMessage::Begin {
+            final_lsn,
+            commit_timestamp,
+            xid,
+        } = LogicalMessage::decode(&data).unwrap()
+        else {
+            panic!("Expected begin message");
+        };
+        assert_eq!((final_lsn, commit_timestamp, xid), (100, 200, 7));
+
+        let mut data = vec![COMMIT, 0];
+        data.extend(100u64.to_be_bytes());
+        data.extend(150u64.to_be_bytes());
+        data.extend(200i64.to_be_bytes());
+        let LogicalMessage::Commit { end_lsn } = LogicalMessage::decode(&data).unwrap() else {
+            panic!("Expected commit message");
+        };
+        assert_eq!(end_lsn, 150);
+    }
+
+    #[test]
+    fn relation_should_be_decoded() {
+        let mut data = vec![RELATION];
+        data.extend(16384u32.to_be_bytes());
+        data.extend(b"public\0users\0");
+        data.push(b'd');
+        data.extend(2u16.to_be_bytes());
+        data.push(KEY_COLUMN_FLAG);
+        data.extend(b"id\0");
+        data.extend(23u32.to_be_bytes());
+        data.extend((-1i32).to_be_bytes());
+        data.push(0);
+        data.extend(b"name\0");
+        data.extend(25u32.to_be_bytes());
+        data.extend((-1i32).to_be_bytes());
+
+        let LogicalMessage::Relation(relation) = LogicalMessage::decode(&data).unwrap() else {
+            panic!("Expected relation message");
+        };
+
+        assert_eq!(relation.id, 16384);
+        assert_eq!(relation.namespace, "public");
+        assert_eq!(relation.name, "users");
+        assert_eq!(relation.columns.len(), 2);
+        assert_eq!(relation.columns[0].name, "id");
+        assert_eq!(relation.columns[0].type_id, 23);
+        assert!(relation.columns[0].is_key);
+        assert_eq!(relation.columns[1].name, "name");
+        assert!(!relation.columns[1].is_key);
+    }
+
+    #[test]
+    fn insert_should_be_decoded_with_all_tuple_value_types() {
+        let mut data = vec![INSERT];
+        data.extend(1u32.to_be_bytes());
+        data.push(NEW_TUPLE);
+        data.extend(4u16.to_be_bytes());
+        data.push(NULL_VALUE);
+        data.push(UNCHANGED_TOAST_VALUE);
+        data.push(TEXT_VALUE);
+        data.extend(2u32.to_be_bytes());
+        data.extend(b"42");
+        data.push(BINARY_VALUE);
+        data.extend(3u32.to_be_bytes());
+        data.extend([1, 2, 3]);
+
+        let LogicalMessage::Insert { relation_id, new } = LogicalMessage::decode(&data).unwrap()
+        else {
+            panic!("Expected insert message");
+        };
+
+        assert_eq!(relation_id, 1);
+        assert!(matches!(new[0], TupleValue::Null));
+        assert!(matches!(new[1], TupleValue::UnchangedToast));
+        assert!(matches!(&new[2], TupleValue::Text(text) if text == "42"));
+        assert!(matches!(&new[3], TupleValue::Binary(bytes) if bytes == &[1, 2, 3]));
+    }
+
+    #[test]
+    fn update_should_be_decoded_with_and_without_old_tuple() {
+        let mut data = vec![UPDATE];
+        data.extend(1u32.to_be_bytes());
+        data.push(KEY_TUPLE);
+        data.extend(text_tuple("1"));
+        data.push(NEW_TUPLE);
+        data.extend(text_tuple("2"));
+        let LogicalMessage::Update { old, new, .. } = LogicalMessage::decode(&data).unwrap() else {
+            panic!("Expected update message");
+        };
+        let old = old.unwrap();
+        assert!(old.key_only);
+        assert!(matches!(&old.values[0], TupleValue::Text(text) if text == "1"));
+        assert!(matches!(&new[0], TupleValue::Text(text) if text == "2"));
+
+        let mut data = vec![UPDATE];
+        data.extend(1u32.to_be_bytes());
+        data.push(NEW_TUPLE);
+        data.extend(text_tuple("2"));
+        let LogicalMessage::Update { old, .. } = LogicalMessage::decode(&data).unwrap() else {
+            panic!("Expected update message");
+        };
+        assert!(old.is_none());
+    }
+
+    #[test]
+    fn delete_and_truncate_should_be_decoded() {
+        let mut data = vec![DELETE];
+        data.extend(1u32.to_be_bytes());
+        data.push(OLD_TUPLE);
+        data.extend(text_tuple("1"));
+        let LogicalMessage::Delete { relation_id, old } = LogicalMessage::decode(&data).unwrap()
+        else {
+            panic!("Expected delete message");
+        };
+        assert_eq!(relation_id, 1);
+        assert!(!old.key_only);
+
+        let mut data = vec![TRUNCATE];
+        data.extend(2u32.to_be_bytes());
+        data.push(0);
+        data.extend(1u32.to_be_bytes());
+        data.extend(2u32.to_be_bytes());
+        let LogicalMessage::Truncate { relation_ids } = LogicalMessage::decode(&data).unwrap()
+        else {
+            panic!("Expected truncate message");
+        };
+        assert_eq!(relation_ids, vec![1, 2]);
+    }
+
+    #[test]
+    fn unsupported_message_should_be_decoded_as_other() {
+        assert!(matches!(
+            LogicalMessage::decode(b"Ytype").unwrap(),
+            LogicalMessage::Other
+        ));
+    }
+
+    #[test]
+    fn decoding_should_fail_given_invalid_message() {
+        assert!(LogicalMessage::decode(&[]).is_err());
+        assert!(LogicalMessage::decode(&[BEGIN, 0, 0]).is_err());
+
+        let mut data = vec![RELATION];
+        data.extend(1u32.to_be_bytes());
+        data.extend(b"unterminated");
+        assert!(LogicalMessage::decode(&data).is_err());
+
+        let mut data = vec![INSERT];
+        data.extend(1u32.to_be_bytes());
+        data.push(OLD_TUPLE);
+        assert!(LogicalMessage::decode(&data).is_err());
+
+        let mut data = vec![DELETE];
+        data.extend(1u32.to_be_bytes());
+        data.push(NEW_TUPLE);
+        assert!(LogicalMessage::decode(&data).is_err());
+
+        let mut data = vec![INSERT];
+        data.extend(1u32.to_be_bytes());
+        data.push(NEW_TUPLE);
+        data.extend(1u16.to_be_bytes());
+        data.push(b'x');
+        assert!(LogicalMessage::decode(&data).is_err());
+    }
+
+    fn text_tuple(value: &str) -> Vec<u8> {
+        let mut data = 1u16.to_be_bytes().to_vec();
+        data.push(TEXT_VALUE);
+        data.extend((value.len() as u32).to_be_bytes());
+        data.extend(value.as_bytes());
+        data
+    }
+}
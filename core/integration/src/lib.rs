@@ -27,3 +27,5 @@ pub mod tcp_client;
 #[allow(deprecated)]
 pub mod test_server;
 pub mod test_tls_utils;
+#[allow(deprecated)]
+pub mod websocket_client;
@@ -68,6 +68,9 @@ pub enum Transport {
 
     #[display("tcp")]
     Tcp,
+
+    #[display("websocket")]
+    WebSocket,
 }
 
 #[derive(Display, Debug)]
@@ -80,6 +83,12 @@ enum ServerProtocolAddr {
 
     #[display("QUIC_UDP:{_0}")]
     QuicUdp(SocketAddr),
+
+    #[display("KAFKA_TCP:{_0}")]
+    KafkaTcp(SocketAddr),
+
+    #[display("MQTT_TCP:{_0}")]
+    MqttTcp(SocketAddr),
 }
 
 #[derive(Debug)]
@@ -297,6 +306,12 @@ impl TestServer {
                 ServerProtocolAddr::QuicUdp(addr) => {
                     ("IGGY_QUIC_ADDRESS".to_string(), addr.to_string())
                 }
+                ServerProtocolAddr::KafkaTcp(addr) => {
+                    ("IGGY_KAFKA_ADDRESS".to_string(), addr.to_string())
+                }
+                ServerProtocolAddr::MqttTcp(addr) => {
+                    ("IGGY_MQTT_ADDRESS".to_string(), addr.to_string())
+                }
             };
 
             self.envs.entry(key.0).or_insert(key.1);
@@ -346,6 +361,18 @@ impl TestServer {
             self.server_addrs.push(ServerProtocolAddr::HttpTcp(
                 config.http.address.parse().unwrap(),
             ));
+
+            if config.kafka.enabled {
+                self.server_addrs.push(ServerProtocolAddr::KafkaTcp(
+                    config.kafka.address.parse().unwrap(),
+                ));
+            }
+
+            if config.mqtt.enabled {
+                self.server_addrs.push(ServerProtocolAddr::MqttTcp(
+                    config.mqtt.address.parse().unwrap(),
+                ));
+            }
         } else {
             panic!(
                 "Failed to load config from file {config_path} in {MAX_PORT_WAIT_DURATION_S} s!"
@@ -408,6 +435,24 @@ impl TestServer {
         None
     }
 
+    pub fn get_kafka_addr(&self) -> Option<String> {
+        for server_protocol_addr in &self.server_addrs {
+            if let ServerProtocolAddr::KafkaTcp(a) = server_protocol_addr {
+                return Some(a.to_string());
+            }
+        }
+        None
+    }
+
+    pub fn get_mqtt_addr(&self) -> Option<String> {
+        for server_protocol_addr in &self.server_addrs {
+            if let ServerProtocolAddr::MqttTcp(a) = server_protocol_addr {
+                return Some(a.to_string());
+            }
+        }
+        None
+    }
+
     pub fn get_server_ip_addr(&self) -> Option<String> {
         if let Some(server_address) = self
             .get_raw_tcp_addr()
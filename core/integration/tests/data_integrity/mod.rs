@@ -17,3 +17,5 @@
  */
 
 mod verify_after_server_restart;
+mod verify_moved_partition_after_server_restart;
+mod verify_producer_states_after_server_restart;
@@ -0,0 +1,341 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use bytes::Bytes;
+use iggy::prelude::*;
+use integration::{
+    tcp_client::TcpClientFactory,
+    test_server::{ClientFactory, IpAddrKind, SYSTEM_PATH_ENV_VAR, TestServer, login_root},
+};
+use serial_test::parallel;
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+
+const STREAM_ID: u32 = 1;
+const TOPIC_ID: u32 = 1;
+const STREAM_NAME: &str = "test-stream";
+const TOPIC_NAME: &str = "test-topic";
+const PARTITION_ID: u32 = 1;
+const MESSAGES_COUNT: u64 = 200;
+const STORED_OFFSET: u64 = 99;
+
+#[tokio::test]
+#[parallel]
+async fn should_keep_messages_and_offsets_of_moved_partition_after_restart() {
+    // 1. Start server with an additional data directory
+    let system_path = TestServer::get_random_path();
+    let extra_directory = format!("{system_path}_extra");
+    let env_vars = HashMap::from([
+        (SYSTEM_PATH_ENV_VAR.to_owned(), system_path.clone()),
+        (
+            "IGGY_SYSTEM_PARTITION_DIRECTORIES".to_owned(),
+            format!("[{extra_directory}]"),
+        ),
+    ]);
+    let mut test_server = TestServer::new(Some(env_vars.clone()), false, None, IpAddrKind::V4);
+    test_server.start();
+    let client = create_client(&test_server).await;
+
+    // 2. The partitions are placed in the data directories in the round-robin fashion
+    client
+        .create_stream(STREAM_NAME, Some(STREAM_ID))
+        .await
+        .unwrap();
+    client
+        .create_topic_with_overrides(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            TOPIC_NAME,
+            2,
+            CompressionAlgorithm::default(),
+            None,
+            Some(TOPIC_ID),
+            IggyExpiry::NeverExpire,
+            MaxTopicSize::ServerDefault,
+            TopicOverrides {
+                segment_size: Some(IggyByteSize::from_str("16 KiB").unwrap()),
+                messages_required_to_save: Some(32),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert!(partition_exists(&system_path, 1));
+    assert!(partition_exists(&extra_directory, 2));
+    send_messages(&client, 0, MESSAGES_COUNT).await;
+    store_offset(&client).await;
+
+    // 3. Move the partition with multiple segments to the additional data directory
+    client
+        .move_partition(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            PARTITION_ID,
+            &extra_directory,
+        )
+        .await
+        .unwrap();
+    assert!(!partition_exists(&system_path, PARTITION_ID));
+    assert!(partition_exists(&extra_directory, PARTITION_ID));
+    assert_messages(&client, MESSAGES_COUNT).await;
+    assert_eq!(get_offset(&client).await, Some(STORED_OFFSET));
+
+    // 4. The moved partition accepts the new messages, and the unknown directory is rejected
+    send_messages(&client, MESSAGES_COUNT, 50).await;
+    assert_messages(&client, MESSAGES_COUNT + 50).await;
+    assert!(
+        client
+            .move_partition(
+                &Identifier::numeric(STREAM_ID).unwrap(),
+                &Identifier::numeric(TOPIC_ID).unwrap(),
+                PARTITION_ID,
+                "unknown_directory",
+            )
+            .await
+            .is_err()
+    );
+
+    // 5. Restart server with same settings, the partition should be loaded from the new directory
+    drop(client);
+    test_server.stop();
+    drop(test_server);
+    let mut test_server = TestServer::new(Some(env_vars), false, None, IpAddrKind::V4);
+    test_server.start();
+    let client = create_client(&test_server).await;
+    assert!(!partition_exists(&system_path, PARTITION_ID));
+    assert!(partition_exists(&extra_directory, PARTITION_ID));
+    assert_messages(&client, MESSAGES_COUNT + 50).await;
+    assert_eq!(get_offset(&client).await, Some(STORED_OFFSET));
+
+    // 6. Move the partition back to the system path
+    client
+        .move_partition(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            PARTITION_ID,
+            &system_path,
+        )
+        .await
+        .unwrap();
+    assert!(partition_exists(&system_path, PARTITION_ID));
+    assert!(!partition_exists(&extra_directory, PARTITION_ID));
+    assert_messages(&client, MESSAGES_COUNT + 50).await;
+
+    // 7. Deleting the stream removes its partitions from all the data directories
+    client
+        .delete_stream(&Identifier::numeric(STREAM_ID).unwrap())
+        .await
+        .unwrap();
+    assert!(!Path::new(&format!("{extra_directory}/streams/{STREAM_ID}")).exists());
+
+    // 8. Manual cleanup
+    drop(client);
+    test_server.stop();
+    std::fs::remove_dir_all(system_path).unwrap();
+    std::fs::remove_dir_all(extra_directory).unwrap();
+}
+
+#[tokio::test]
+#[parallel]
+async fn should_keep_messages_of_partition_when_server_stops_in_the_middle_of_move() {
+    // 1. Start server with an additional data directory, and create the partition in the system path
+    let system_path = TestServer::get_random_path();
+    let extra_directory = format!("{system_path}_extra");
+    let env_vars = HashMap::from([
+        (SYSTEM_PATH_ENV_VAR.to_owned(), system_path.clone()),
+        (
+            "IGGY_SYSTEM_PARTITION_DIRECTORIES".to_owned(),
+            format!("[{extra_directory}]"),
+        ),
+    ]);
+    let mut test_server = TestServer::new(Some(env_vars.clone()), false, None, IpAddrKind::V4);
+    test_server.start();
+    let client = create_client(&test_server).await;
+    client
+        .create_stream(STREAM_NAME, Some(STREAM_ID))
+        .await
+        .unwrap();
+    client
+        .create_topic(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            TOPIC_NAME,
+            1,
+            CompressionAlgorithm::default(),
+            None,
+            Some(TOPIC_ID),
+            IggyExpiry::NeverExpire,
+            MaxTopicSize::ServerDefault,
+        )
+        .await
+        .unwrap();
+    send_messages(&client, 0, MESSAGES_COUNT).await;
+    drop(client);
+    test_server.stop();
+    drop(test_server);
+
+    // 2. The server stopped after copying the partition, but before the move was persisted,
+    // so the partition is loaded from the previous directory, and the copy is removed
+    copy_partition(&system_path, &extra_directory);
+    let mut test_server = TestServer::new(Some(env_vars.clone()), false, None, IpAddrKind::V4);
+    test_server.start();
+    let client = create_client(&test_server).await;
+    assert!(partition_exists(&system_path, PARTITION_ID));
+    assert!(!partition_exists(&extra_directory, PARTITION_ID));
+    assert_messages(&client, MESSAGES_COUNT).await;
+
+    // 3. Move the partition and append more messages to it in the new directory
+    client
+        .move_partition(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            PARTITION_ID,
+            &extra_directory,
+        )
+        .await
+        .unwrap();
+    send_messages(&client, MESSAGES_COUNT, 50).await;
+    drop(client);
+    test_server.stop();
+    drop(test_server);
+
+    // 4. The server stopped after the move was persisted, but before the previous directory was removed,
+    // so the partition is loaded from the new directory, and the stale copy is removed
+    copy_partition(&extra_directory, &system_path);
+    let mut test_server = TestServer::new(Some(env_vars), false, None, IpAddrKind::V4);
+    test_server.start();
+    let client = create_client(&test_server).await;
+    assert!(!partition_exists(&system_path, PARTITION_ID));
+    assert!(partition_exists(&extra_directory, PARTITION_ID));
+    assert_messages(&client, MESSAGES_COUNT + 50).await;
+
+    // 5. Manual cleanup
+    drop(client);
+    test_server.stop();
+    std::fs::remove_dir_all(system_path).unwrap();
+    std::fs::remove_dir_all(extra_directory).unwrap();
+}
+
+fn partition_exists(directory: &str, partition_id: u32) -> bool {
+    Path::new(&format!(
+        "{directory}/streams/{STREAM_ID}/topics/{TOPIC_ID}/partitions/{partition_id}"
+    ))
+    .exists()
+}
+
+fn copy_partition(source_directory: &str, target_directory: &str) {
+    let partition_path = format!("streams/{STREAM_ID}/topics/{TOPIC_ID}/partitions/{PARTITION_ID}");
+    copy_directory(
+        &Path::new(source_directory).join(&partition_path),
+        &Path::new(target_directory).join(&partition_path),
+    );
+}
+
+fn copy_directory(source: &Path, target: &Path) {
+    std::fs::create_dir_all(target).unwrap();
+    for entry in std::fs::read_dir(source).unwrap() {
+        let entry = entry.unwrap();
+        let target = target.join(entry.file_name());
+        if entry.file_type().unwrap().is_dir() {
+            copy_directory(&entry.path(), &target);
+        } else {
+            std::fs::copy(entry.path(), target).unwrap();
+        }
+    }
+}
+
+async fn create_client(test_server: &TestServer) -> IggyClient {
+    let client = TcpClientFactory {
+        server_addr: test_server.get_raw_tcp_addr().unwrap(),
+        ..Default::default()
+    }
+    .create_client()
+    .await;
+    let client = IggyClient::create(client, None, None);
+    login_root(&client).await;
+    client
+}
+
+async fn send_messages(client: &IggyClient, start: u64, count: u64) {
+    let mut messages = (start..start + count)
+        .map(|index| {
+            IggyMessage::builder()
+                .payload(Bytes::from(format!("message {index} {}", "x".repeat(200))))
+                .build()
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    client
+        .send_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            &Partitioning::partition_id(PARTITION_ID),
+            &mut messages,
+        )
+        .await
+        .unwrap();
+}
+
+async fn assert_messages(client: &IggyClient, count: u64) {
+    let polled_messages = client
+        .poll_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            Some(PARTITION_ID),
+            &Consumer::default(),
+            &PollingStrategy::offset(0),
+            count as u32 + 1,
+            false,
+        )
+        .await
+        .unwrap();
+    assert_eq!(polled_messages.messages.len() as u64, count);
+    for (offset, message) in polled_messages.messages.iter().enumerate() {
+        assert_eq!(message.header.offset, offset as u64);
+        assert!(
+            message
+                .payload
+                .starts_with(format!("message {offset} ").as_bytes())
+        );
+    }
+}
+
+async fn store_offset(client: &IggyClient) {
+    client
+        .store_consumer_offset(
+            &Consumer::default(),
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            Some(PARTITION_ID),
+            STORED_OFFSET,
+        )
+        .await
+        .unwrap();
+}
+
+async fn get_offset(client: &IggyClient) -> Option<u64> {
+    client
+        .get_consumer_offset(
+            &Consumer::default(),
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            Some(PARTITION_ID),
+        )
+        .await
+        .unwrap()
+        .map(|offset| offset.stored_offset)
+}
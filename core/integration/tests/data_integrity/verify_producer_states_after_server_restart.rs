@@ -0,0 +1,135 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use bytes::Bytes;
+use iggy::prelude::*;
+use integration::{
+    tcp_client::TcpClientFactory,
+    test_server::{ClientFactory, IpAddrKind, SYSTEM_PATH_ENV_VAR, TestServer, login_root},
+};
+use serial_test::parallel;
+use std::collections::HashMap;
+
+const STREAM_NAME: &str = "test-stream";
+const TOPIC_NAME: &str = "test-topic";
+const PARTITION_ID: u32 = 1;
+const PRODUCER_ID: u64 = 1;
+
+#[tokio::test]
+#[parallel]
+async fn should_discard_retried_batch_of_idempotent_producer_after_restart() {
+    // 1. Start server and append the batch of the idempotent producer
+    let env_vars = HashMap::from([(
+        SYSTEM_PATH_ENV_VAR.to_owned(),
+        TestServer::get_random_path(),
+    )]);
+    let mut test_server = TestServer::new(Some(env_vars.clone()), false, None, IpAddrKind::V4);
+    test_server.start();
+    let local_data_path = test_server.get_local_data_path().to_owned();
+    let client = create_client(&test_server).await;
+    client.create_stream(STREAM_NAME, None).await.unwrap();
+    client
+        .create_topic(
+            &Identifier::named(STREAM_NAME).unwrap(),
+            TOPIC_NAME,
+            1,
+            CompressionAlgorithm::default(),
+            None,
+            None,
+            IggyExpiry::NeverExpire,
+            MaxTopicSize::ServerDefault,
+        )
+        .await
+        .unwrap();
+    send_batch(&client, 0, 5).await.unwrap();
+
+    // 2. Restart server with same settings
+    drop(client);
+    test_server.stop();
+    drop(test_server);
+    let mut test_server = TestServer::new(Some(env_vars), false, None, IpAddrKind::V4);
+    test_server.start();
+    let client = create_client(&test_server).await;
+
+    // 3. The retried batch should be discarded, and the batch with a gap in the sequence rejected
+    send_batch(&client, 3, 2).await.unwrap();
+    assert!(send_batch(&client, 10, 1).await.is_err());
+    assert_eq!(get_messages_count(&client).await, 5);
+
+    // 4. The batch continuing the sequence should be appended
+    send_batch(&client, 5, 1).await.unwrap();
+    assert_eq!(get_messages_count(&client).await, 6);
+
+    // 5. Manual cleanup
+    drop(client);
+    test_server.stop();
+    std::fs::remove_dir_all(local_data_path).unwrap();
+}
+
+async fn create_client(test_server: &TestServer) -> IggyClient {
+    let client = TcpClientFactory {
+        server_addr: test_server.get_raw_tcp_addr().unwrap(),
+        ..Default::default()
+    }
+    .create_client()
+    .await;
+    let client = IggyClient::create(client, None, None);
+    login_root(&client).await;
+    client
+}
+
+async fn send_batch(
+    client: &IggyClient,
+    base_sequence: u64,
+    messages_count: u64,
+) -> Result<(), IggyError> {
+    let mut messages = (base_sequence..base_sequence + messages_count)
+        .map(|sequence| {
+            IggyMessage::builder()
+                .payload(Bytes::from(format!("message {sequence}")))
+                .build()
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    client
+        .send_messages_with_sequence(
+            &Identifier::named(STREAM_NAME).unwrap(),
+            &Identifier::named(TOPIC_NAME).unwrap(),
+            PARTITION_ID,
+            &ProducerSequence::new(PRODUCER_ID, base_sequence),
+            &mut messages,
+        )
+        .await
+}
+
+async fn get_messages_count(client: &IggyClient) -> usize {
+    client
+        .poll_messages(
+            &Identifier::named(STREAM_NAME).unwrap(),
+            &Identifier::named(TOPIC_NAME).unwrap(),
+            Some(PARTITION_ID),
+            &Consumer::default(),
+            &PollingStrategy::offset(0),
+            100,
+            false,
+        )
+        .await
+        .unwrap()
+        .messages
+        .len()
+}
@@ -16,8 +16,10 @@
 // under the License.
 
 use crate::server::{
-    ScenarioFn, bench_scenario, create_message_payload_scenario, message_headers_scenario,
-    run_scenario, stream_size_validation_scenario, system_scenario, user_scenario,
+    ScenarioFn, bench_scenario, create_message_payload_scenario, data_snapshot_scenario,
+    idempotent_producer_scenario, long_polling_scenario, message_headers_scenario, run_scenario,
+    stream_size_validation_scenario, system_scenario, topic_overrides_scenario,
+    topic_subscription_scenario, user_scenario,
 };
 use integration::test_server::Transport;
 use serial_test::parallel;
@@ -31,6 +33,9 @@ use test_case::test_matrix;
         message_headers_scenario(),
         create_message_payload_scenario(),
         stream_size_validation_scenario(),
+        idempotent_producer_scenario(),
+        topic_overrides_scenario(),
+        data_snapshot_scenario(),
         bench_scenario(),
     ]
 )]
@@ -39,3 +44,34 @@ use test_case::test_matrix;
 async fn matrix(transport: Transport, scenario: ScenarioFn) {
     run_scenario(transport, scenario).await;
 }
+
+// The benchmark scenario is skipped, as the WebSocket transport is not supported by the benchmarking tool.
+#[test_matrix(
+    [Transport::WebSocket],
+    [
+        system_scenario(),
+        user_scenario(),
+        message_headers_scenario(),
+        create_message_payload_scenario(),
+        stream_size_validation_scenario(),
+        idempotent_producer_scenario(),
+        topic_overrides_scenario(),
+        data_snapshot_scenario(),
+    ]
+)]
+#[tokio::test]
+#[parallel]
+async fn websocket_matrix(transport: Transport, scenario: ScenarioFn) {
+    run_scenario(transport, scenario).await;
+}
+
+// Long polling (of the messages and the topics changes) is supported only by the binary protocol transports.
+#[test_matrix(
+    [Transport::Tcp, Transport::Quic, Transport::WebSocket],
+    [long_polling_scenario(), topic_subscription_scenario()]
+)]
+#[tokio::test]
+#[parallel]
+async fn long_polling_matrix(transport: Transport, scenario: ScenarioFn) {
+    run_scenario(transport, scenario).await;
+}
@@ -26,12 +26,15 @@ use integration::{
     quic_client::QuicClientFactory,
     tcp_client::TcpClientFactory,
     test_server::{ClientFactory, TestServer, Transport},
+    websocket_client::WebSocketClientFactory,
 };
 use scenarios::{
     bench_scenario, consumer_group_join_scenario,
     consumer_group_with_multiple_clients_polling_messages_scenario,
     consumer_group_with_single_client_polling_messages_scenario, create_message_payload,
-    message_headers_scenario, stream_size_validation_scenario, system_scenario, user_scenario,
+    data_snapshot_scenario, idempotent_producer_scenario, long_polling_scenario,
+    message_headers_scenario, stream_size_validation_scenario, system_scenario,
+    topic_overrides_scenario, topic_subscription_scenario, user_scenario,
 };
 use std::future::Future;
 use std::pin::Pin;
@@ -54,10 +57,30 @@ fn create_message_payload_scenario() -> ScenarioFn {
     |factory| Box::pin(create_message_payload::run(factory))
 }
 
+fn data_snapshot_scenario() -> ScenarioFn {
+    |factory| Box::pin(data_snapshot_scenario::run(factory))
+}
+
 fn join_scenario() -> ScenarioFn {
     |factory| Box::pin(consumer_group_join_scenario::run(factory))
 }
 
+fn idempotent_producer_scenario() -> ScenarioFn {
+    |factory| Box::pin(idempotent_producer_scenario::run(factory))
+}
+
+fn long_polling_scenario() -> ScenarioFn {
+    |factory| Box::pin(long_polling_scenario::run(factory))
+}
+
+fn topic_overrides_scenario() -> ScenarioFn {
+    |factory| Box::pin(topic_overrides_scenario::run(factory))
+}
+
+fn topic_subscription_scenario() -> ScenarioFn {
+    |factory| Box::pin(topic_subscription_scenario::run(factory))
+}
+
 fn stream_size_validation_scenario() -> ScenarioFn {
     |factory| Box::pin(stream_size_validation_scenario::run(factory))
 }
@@ -94,6 +117,10 @@ async fn run_scenario(transport: Transport, scenario: ScenarioFn) {
             let server_addr = test_server.get_http_api_addr().unwrap();
             Box::new(HttpClientFactory { server_addr })
         }
+        Transport::WebSocket => {
+            let server_addr = test_server.get_http_api_addr().unwrap();
+            Box::new(WebSocketClientFactory { server_addr })
+        }
     };
 
     scenario(&*client_factory).await;
@@ -0,0 +1,176 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::server::scenarios::{
+    CONSUMER_GROUP_ID, CONSUMER_GROUP_NAME, CONSUMER_ID, PARTITIONS_COUNT, STREAM_ID, STREAM_NAME,
+    TOPIC_ID, TOPIC_NAME, cleanup, create_client, get_consumer_group,
+};
+use bytes::Bytes;
+use iggy::prelude::*;
+use integration::test_server::{ClientFactory, assert_clean_system, login_root};
+
+const MESSAGES_PER_PARTITION: u32 = 10;
+const STORED_OFFSET: u64 = 4;
+
+pub async fn run(client_factory: &dyn ClientFactory) {
+    let client = create_client(client_factory).await;
+    login_root(&client).await;
+    let stream_id = Identifier::numeric(STREAM_ID).unwrap();
+    let topic_id = Identifier::numeric(TOPIC_ID).unwrap();
+    let consumer = Consumer::new(Identifier::numeric(CONSUMER_ID).unwrap());
+
+    client
+        .create_stream(STREAM_NAME, Some(STREAM_ID))
+        .await
+        .unwrap();
+    client
+        .create_topic(
+            &stream_id,
+            TOPIC_NAME,
+            PARTITIONS_COUNT,
+            CompressionAlgorithm::default(),
+            None,
+            Some(TOPIC_ID),
+            IggyExpiry::NeverExpire,
+            MaxTopicSize::ServerDefault,
+        )
+        .await
+        .unwrap();
+    client
+        .create_consumer_group(
+            &stream_id,
+            &topic_id,
+            CONSUMER_GROUP_NAME,
+            Some(CONSUMER_GROUP_ID),
+        )
+        .await
+        .unwrap();
+    for partition_id in 1..=PARTITIONS_COUNT {
+        send_messages(&client, partition_id).await;
+    }
+    client
+        .store_consumer_offset(&consumer, &stream_id, &topic_id, Some(1), STORED_OFFSET)
+        .await
+        .unwrap();
+
+    // 1. The snapshot of the stream should contain all its topics
+    let snapshot = client
+        .data_snapshot(vec![DataSnapshotTarget::stream(stream_id.clone())])
+        .await
+        .unwrap();
+    assert!(!snapshot.0.is_empty());
+
+    // 2. The topic which already exists cannot be restored
+    assert!(client.restore_data_snapshot(&snapshot).await.is_err());
+
+    // 3. The deleted stream should be restored along with its topics, consumer groups, messages and offsets
+    client.delete_stream(&stream_id).await.unwrap();
+    client.restore_data_snapshot(&snapshot).await.unwrap();
+
+    let stream = client
+        .get_stream(&stream_id)
+        .await
+        .unwrap()
+        .expect("Stream should be restored");
+    assert_eq!(stream.name, STREAM_NAME);
+    let topic = client
+        .get_topic(&stream_id, &topic_id)
+        .await
+        .unwrap()
+        .expect("Topic should be restored");
+    assert_eq!(topic.name, TOPIC_NAME);
+    assert_eq!(topic.partitions_count, PARTITIONS_COUNT);
+    assert_eq!(
+        topic.messages_count,
+        (PARTITIONS_COUNT * MESSAGES_PER_PARTITION) as u64
+    );
+    let consumer_group = get_consumer_group(&client).await;
+    assert_eq!(consumer_group.name, CONSUMER_GROUP_NAME);
+
+    for partition_id in 1..=PARTITIONS_COUNT {
+        let polled_messages = poll_messages(&client, partition_id).await;
+        assert_eq!(
+            polled_messages.messages.len() as u32,
+            MESSAGES_PER_PARTITION
+        );
+        for (index, message) in polled_messages.messages.iter().enumerate() {
+            assert_eq!(message.header.offset, index as u64);
+            assert_eq!(
+                message.payload,
+                Bytes::from(format!("message-{partition_id}-{index}"))
+            );
+        }
+    }
+
+    let consumer_offset = client
+        .get_consumer_offset(&consumer, &stream_id, &topic_id, Some(1))
+        .await
+        .unwrap()
+        .expect("Consumer offset should be restored");
+    assert_eq!(consumer_offset.stored_offset, STORED_OFFSET);
+
+    // 4. The restored partitions should continue from the restored offsets
+    send_messages(&client, 1).await;
+    let polled_messages = poll_messages(&client, 1).await;
+    assert_eq!(
+        polled_messages.messages.len() as u32,
+        2 * MESSAGES_PER_PARTITION
+    );
+    assert_eq!(
+        polled_messages.messages.last().unwrap().header.offset,
+        (2 * MESSAGES_PER_PARTITION - 1) as u64
+    );
+
+    cleanup(&client, false).await;
+    assert_clean_system(&client).await;
+}
+
+async fn send_messages(client: &IggyClient, partition_id: u32) {
+    let mut messages = (0..MESSAGES_PER_PARTITION)
+        .map(|index| {
+            IggyMessage::builder()
+                .payload(Bytes::from(format!("message-{partition_id}-{index}")))
+                .build()
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    client
+        .send_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            &Partitioning::partition_id(partition_id),
+            &mut messages,
+        )
+        .await
+        .unwrap();
+}
+
+async fn poll_messages(client: &IggyClient, partition_id: u32) -> PolledMessages {
+    client
+        .poll_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            Some(partition_id),
+            &Consumer::default(),
+            &PollingStrategy::offset(0),
+            4 * MESSAGES_PER_PARTITION,
+            false,
+        )
+        .await
+        .unwrap()
+}
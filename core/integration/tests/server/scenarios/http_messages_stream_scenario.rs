@@ -0,0 +1,188 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::server::scenarios::{
+    PARTITION_ID, STREAM_ID, STREAM_NAME, TOPIC_ID, TOPIC_NAME, cleanup, create_client,
+};
+use bytes::Bytes;
+use iggy::prelude::*;
+use iggy_common::login_user::LoginUser;
+use integration::http_client::HttpClientFactory;
+use integration::test_server::{assert_clean_system, login_root};
+use reqwest::StatusCode;
+use std::time::Duration;
+use tokio::time::timeout;
+
+const MESSAGES_COUNT: u64 = 5;
+
+pub async fn run(server_addr: &str) {
+    let client_factory = HttpClientFactory {
+        server_addr: server_addr.to_owned(),
+    };
+    let client = create_client(&client_factory).await;
+    login_root(&client).await;
+    init_system(&client).await;
+
+    let http = reqwest::Client::new();
+    let identity = http
+        .post(format!("http://{server_addr}/users/login"))
+        .json(&LoginUser {
+            username: DEFAULT_ROOT_USERNAME.to_owned(),
+            password: DEFAULT_ROOT_PASSWORD.to_owned(),
+            version: None,
+            context: None,
+        })
+        .send()
+        .await
+        .unwrap()
+        .json::<IdentityInfo>()
+        .await
+        .unwrap();
+    let token = identity.access_token.unwrap().token;
+    let url = format!("http://{server_addr}/streams/{STREAM_ID}/topics/{TOPIC_ID}/messages/stream");
+
+    // 1. Streaming without the access token should be rejected
+    let response = http.get(&url).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    // 2. Streaming with the `Next` strategy, without committing the offsets, should be rejected
+    let response = http
+        .get(&url)
+        .query(&[("access_token", token.as_str()), ("kind", "next")])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // 3. Stream the empty partition from the first offset, using the access token passed via query
+    let mut response = http
+        .get(&url)
+        .query(&[
+            ("access_token", token.as_str()),
+            ("consumer_id", "1"),
+            ("partition_id", &PARTITION_ID.to_string()),
+            ("kind", "offset"),
+            ("value", "0"),
+            ("count", "2"),
+        ])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()[reqwest::header::CONTENT_TYPE],
+        "text/event-stream"
+    );
+
+    // 4. Send the messages, which should be emitted as the separate events in order
+    for offset in 0..MESSAGES_COUNT {
+        send_message(&client, offset).await;
+    }
+
+    let mut buffer = String::new();
+    let mut events = Vec::new();
+    while events.len() < MESSAGES_COUNT as usize {
+        let chunk = timeout(Duration::from_secs(10), response.chunk())
+            .await
+            .expect("Failed to receive the event in time")
+            .unwrap()
+            .expect("The events stream has ended");
+        buffer.push_str(std::str::from_utf8(&chunk).unwrap());
+        while let Some(position) = buffer.find("\n\n") {
+            let event = buffer[..position].to_owned();
+            buffer.drain(..position + 2);
+            if event.lines().any(|line| line == "event: message") {
+                events.push(event);
+            }
+        }
+    }
+
+    for (offset, event) in events.iter().enumerate() {
+        let offset = offset as u64;
+        assert!(event.lines().any(|line| line == format!("id: {offset}")));
+        let data = event
+            .lines()
+            .find_map(|line| line.strip_prefix("data:"))
+            .unwrap()
+            .trim_start();
+        let fields = serde_json::from_str::<serde_json::Value>(data).unwrap();
+        assert_eq!(fields["partition_id"], PARTITION_ID);
+        let message = serde_json::from_str::<IggyMessage>(data).unwrap();
+        assert_eq!(message.header.offset, offset);
+        assert_eq!(message.payload, create_message_payload(offset));
+        let headers = message.user_headers_map().unwrap().unwrap();
+        assert_eq!(
+            headers[&HeaderKey::new("offset").unwrap()],
+            HeaderValue::from_uint64(offset).unwrap()
+        );
+    }
+    drop(response);
+
+    cleanup(&client, false).await;
+    assert_clean_system(&client).await;
+}
+
+async fn send_message(client: &IggyClient, offset: u64) {
+    let headers = [(
+        HeaderKey::new("offset").unwrap(),
+        HeaderValue::from_uint64(offset).unwrap(),
+    )]
+    .into_iter()
+    .collect();
+    let mut messages = vec![
+        IggyMessage::builder()
+            .payload(create_message_payload(offset))
+            .user_headers(headers)
+            .build()
+            .unwrap(),
+    ];
+    client
+        .send_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            &Partitioning::partition_id(PARTITION_ID),
+            &mut messages,
+        )
+        .await
+        .unwrap();
+}
+
+async fn init_system(client: &IggyClient) {
+    client
+        .create_stream(STREAM_NAME, Some(STREAM_ID))
+        .await
+        .unwrap();
+
+    client
+        .create_topic(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            TOPIC_NAME,
+            1,
+            CompressionAlgorithm::default(),
+            None,
+            Some(TOPIC_ID),
+            IggyExpiry::NeverExpire,
+            MaxTopicSize::ServerDefault,
+        )
+        .await
+        .unwrap();
+}
+
+fn create_message_payload(offset: u64) -> Bytes {
+    Bytes::from(format!("message {offset}"))
+}
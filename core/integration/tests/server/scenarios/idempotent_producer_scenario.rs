@@ -0,0 +1,139 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::server::scenarios::{
+    PARTITION_ID, PARTITIONS_COUNT, STREAM_ID, STREAM_NAME, TOPIC_ID, TOPIC_NAME, cleanup,
+    create_client,
+};
+use bytes::Bytes;
+use iggy::prelude::*;
+use integration::test_server::{ClientFactory, assert_clean_system, login_root};
+
+const PRODUCER_ID: u64 = 7;
+
+pub async fn run(client_factory: &dyn ClientFactory) {
+    let client = create_client(client_factory).await;
+    login_root(&client).await;
+    init_system(&client).await;
+
+    // 1. The first batch of the producer should be appended
+    send_batch(&client, 0, 3).await.unwrap();
+    assert_eq!(get_messages_count(&client, PARTITION_ID).await, 3);
+
+    // 2. The retried batch should be discarded without an error
+    send_batch(&client, 0, 3).await.unwrap();
+    assert_eq!(get_messages_count(&client, PARTITION_ID).await, 3);
+
+    // 3. The batch with a gap in the sequence should be rejected
+    assert!(send_batch(&client, 5, 1).await.is_err());
+    assert_eq!(get_messages_count(&client, PARTITION_ID).await, 3);
+
+    // 4. The batch continuing the sequence should be appended
+    send_batch(&client, 3, 2).await.unwrap();
+    assert_eq!(get_messages_count(&client, PARTITION_ID).await, 5);
+
+    // 5. The idempotent producer should resolve the partitions on its own and append all the messages
+    let producer = client
+        .producer(STREAM_NAME, TOPIC_NAME)
+        .unwrap()
+        .idempotent()
+        .partitioning(Partitioning::balanced())
+        .direct(DirectConfig::builder().batch_length(1).build())
+        .build();
+    producer.init().await.unwrap();
+    let messages_count = PARTITIONS_COUNT * 2;
+    let messages = (0..messages_count)
+        .map(|offset| create_message(offset as u64))
+        .collect::<Vec<_>>();
+    producer.send(messages).await.unwrap();
+    for partition_id in 1..=PARTITIONS_COUNT {
+        let expected_messages_count = if partition_id == PARTITION_ID { 7 } else { 2 };
+        assert_eq!(
+            get_messages_count(&client, partition_id).await,
+            expected_messages_count
+        );
+    }
+    drop(producer);
+
+    cleanup(&client, false).await;
+    assert_clean_system(&client).await;
+}
+
+async fn send_batch(
+    client: &IggyClient,
+    base_sequence: u64,
+    messages_count: u64,
+) -> Result<(), IggyError> {
+    let mut messages = (base_sequence..base_sequence + messages_count)
+        .map(create_message)
+        .collect::<Vec<_>>();
+    client
+        .send_messages_with_sequence(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            PARTITION_ID,
+            &ProducerSequence::new(PRODUCER_ID, base_sequence),
+            &mut messages,
+        )
+        .await
+}
+
+async fn get_messages_count(client: &IggyClient, partition_id: u32) -> u32 {
+    client
+        .poll_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            Some(partition_id),
+            &Consumer::default(),
+            &PollingStrategy::offset(0),
+            100,
+            false,
+        )
+        .await
+        .unwrap()
+        .messages
+        .len() as u32
+}
+
+async fn init_system(client: &IggyClient) {
+    client
+        .create_stream(STREAM_NAME, Some(STREAM_ID))
+        .await
+        .unwrap();
+
+    client
+        .create_topic(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            TOPIC_NAME,
+            PARTITIONS_COUNT,
+            CompressionAlgorithm::default(),
+            None,
+            Some(TOPIC_ID),
+            IggyExpiry::NeverExpire,
+            MaxTopicSize::ServerDefault,
+        )
+        .await
+        .unwrap();
+}
+
+fn create_message(sequence: u64) -> IggyMessage {
+    IggyMessage::builder()
+        .payload(Bytes::from(format!("message {sequence}")))
+        .build()
+        .unwrap()
+}
@@ -0,0 +1,349 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::server::scenarios::{
+    PARTITIONS_COUNT, STREAM_ID, STREAM_NAME, TOPIC_ID, TOPIC_NAME, cleanup, create_client,
+};
+use bytes::{BufMut, Bytes, BytesMut};
+use iggy::prelude::*;
+use integration::tcp_client::TcpClientFactory;
+use integration::test_server::{assert_clean_system, login_root};
+use server::kafka::codec::{KafkaBufMut, KafkaReader};
+use server::kafka::protocol::{
+    API_VERSIONS, FETCH, LIST_OFFSETS, METADATA, NONE, OFFSET_COMMIT, OFFSET_FETCH, PRODUCE,
+    SASL_AUTHENTICATE, SASL_HANDSHAKE,
+};
+use server::kafka::records::{KAFKA_KEY_HEADER, RecordBatch, encode_record_batch};
+use std::collections::HashMap;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+const KAFKA_TOPIC: &str = "test-stream.test-topic";
+const KAFKA_GROUP: &str = "kafka-group";
+const MESSAGES_COUNT: u64 = 3;
+
+pub async fn run(tcp_server_addr: &str, kafka_server_addr: &str) {
+    let client_factory = TcpClientFactory {
+        server_addr: tcp_server_addr.to_owned(),
+        ..Default::default()
+    };
+    let client = create_client(&client_factory).await;
+    login_root(&client).await;
+    init_system(&client).await;
+
+    // 1. The requests other than ApiVersions and SASL should be rejected before authentication
+    let mut kafka = KafkaClient::connect(kafka_server_addr).await;
+    let mut body = BytesMut::new();
+    body.put_array_length(0);
+    assert!(kafka.try_send(METADATA, 1, body).await.is_none());
+
+    // 2. The supported APIs should be returned before authentication
+    let mut kafka = KafkaClient::connect(kafka_server_addr).await;
+    let mut response = kafka.send(API_VERSIONS, 2, BytesMut::new()).await;
+    assert_eq!(response.read_i16().unwrap(), NONE);
+    let api_keys = response
+        .read_array(|reader| {
+            let api_key = reader.read_i16()?;
+            reader.read_i16()?;
+            reader.read_i16()?;
+            Ok(api_key)
+        })
+        .unwrap();
+    assert!(api_keys.contains(&PRODUCE));
+    assert!(api_keys.contains(&FETCH));
+
+    // 3. Authenticate using SASL/PLAIN with the Iggy user credentials
+    let mut body = BytesMut::new();
+    body.put_string("PLAIN");
+    let mut response = kafka.send(SASL_HANDSHAKE, 1, body).await;
+    assert_eq!(response.read_i16().unwrap(), NONE);
+
+    let mut body = BytesMut::new();
+    body.put_kafka_bytes(format!("\0{DEFAULT_ROOT_USERNAME}\0{DEFAULT_ROOT_PASSWORD}").as_bytes());
+    let mut response = kafka.send(SASL_AUTHENTICATE, 1, body).await;
+    assert_eq!(response.read_i16().unwrap(), NONE);
+
+    // 4. The Iggy topic should be available as the Kafka topic named after the stream and the topic
+    let mut body = BytesMut::new();
+    body.put_array_length(1);
+    body.put_string(KAFKA_TOPIC);
+    body.put_bool(false);
+    body.put_bool(false);
+    body.put_bool(false);
+    let mut response = kafka.send(METADATA, 8, body).await;
+    response.read_i32().unwrap();
+    let brokers = response
+        .read_array(|reader| {
+            reader.read_i32()?;
+            let host = reader.read_string()?;
+            let port = reader.read_i32()?;
+            reader.read_nullable_string()?;
+            Ok(format!("{host}:{port}"))
+        })
+        .unwrap();
+    assert_eq!(brokers, vec![kafka_server_addr.to_owned()]);
+    response.read_nullable_string().unwrap();
+    response.read_i32().unwrap();
+    assert_eq!(response.read_i32().unwrap(), 1);
+    assert_eq!(response.read_i16().unwrap(), NONE);
+    assert_eq!(response.read_string().unwrap(), KAFKA_TOPIC);
+    response.read_bool().unwrap();
+    assert_eq!(response.read_i32().unwrap(), PARTITIONS_COUNT as i32);
+
+    // 5. Produce the records to the first partition
+    let messages = (0..MESSAGES_COUNT)
+        .map(|offset| {
+            let mut message = IggyMessage::builder()
+                .payload(Bytes::from(format!("message {offset}")))
+                .user_headers(HashMap::from([(
+                    HeaderKey::new(KAFKA_KEY_HEADER).unwrap(),
+                    HeaderValue::from_raw(format!("key {offset}").as_bytes()).unwrap(),
+                )]))
+                .build()
+                .unwrap();
+            message.header.offset = offset;
+            message
+        })
+        .collect::<Vec<_>>();
+    let mut records = BytesMut::new();
+    encode_record_batch(&messages, &mut records);
+
+    let mut body = BytesMut::new();
+    body.put_nullable_string(None);
+    body.put_i16(1);
+    body.put_i32(1000);
+    body.put_array_length(1);
+    body.put_string(KAFKA_TOPIC);
+    body.put_array_length(1);
+    body.put_i32(0);
+    body.put_nullable_bytes(Some(&records));
+    let mut response = kafka.send(PRODUCE, 8, body).await;
+    assert_eq!(response.read_i32().unwrap(), 1);
+    assert_eq!(response.read_string().unwrap(), KAFKA_TOPIC);
+    assert_eq!(response.read_i32().unwrap(), 1);
+    assert_eq!(response.read_i32().unwrap(), 0);
+    assert_eq!(response.read_i16().unwrap(), NONE);
+
+    // 6. The records should be stored as the Iggy messages with the key stored in the user header
+    let polled_messages = client
+        .poll_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            Some(1),
+            &Consumer::default(),
+            &PollingStrategy::offset(0),
+            10,
+            false,
+        )
+        .await
+        .unwrap();
+    assert_eq!(polled_messages.messages.len() as u64, MESSAGES_COUNT);
+    for (offset, message) in polled_messages.messages.iter().enumerate() {
+        assert_eq!(message.payload, Bytes::from(format!("message {offset}")));
+        let headers = message.user_headers_map().unwrap().unwrap();
+        let key = headers
+            .get(&HeaderKey::new(KAFKA_KEY_HEADER).unwrap())
+            .unwrap();
+        assert_eq!(key.as_raw().unwrap(), format!("key {offset}").as_bytes());
+    }
+
+    // 7. Fetch the records starting at the second offset
+    let mut body = BytesMut::new();
+    body.put_i32(-1);
+    body.put_i32(100);
+    body.put_i32(1);
+    body.put_i32(1024 * 1024);
+    body.put_i8(0);
+    body.put_i32(0);
+    body.put_i32(-1);
+    body.put_array_length(1);
+    body.put_string(KAFKA_TOPIC);
+    body.put_array_length(1);
+    body.put_i32(0);
+    body.put_i32(-1);
+    body.put_i64(1);
+    body.put_i64(-1);
+    body.put_i32(1024 * 1024);
+    body.put_array_length(0);
+    body.put_string("");
+    let mut response = kafka.send(FETCH, 11, body).await;
+    response.read_i32().unwrap();
+    assert_eq!(response.read_i16().unwrap(), NONE);
+    response.read_i32().unwrap();
+    assert_eq!(response.read_i32().unwrap(), 1);
+    assert_eq!(response.read_string().unwrap(), KAFKA_TOPIC);
+    assert_eq!(response.read_i32().unwrap(), 1);
+    assert_eq!(response.read_i32().unwrap(), 0);
+    assert_eq!(response.read_i16().unwrap(), NONE);
+    assert_eq!(response.read_i64().unwrap(), MESSAGES_COUNT as i64);
+    response.read_i64().unwrap();
+    assert_eq!(response.read_i64().unwrap(), 0);
+    response.read_i32().unwrap();
+    response.read_i32().unwrap();
+    let records = response.read_nullable_bytes().unwrap().unwrap();
+    let batches = RecordBatch::decode_all(records).unwrap();
+    let values = batches
+        .into_iter()
+        .flat_map(|batch| batch.records)
+        .map(|record| record.value.unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(values, vec!["message 1", "message 2"]);
+
+    // 8. The latest offset should be the offset of the next record
+    let mut body = BytesMut::new();
+    body.put_i32(-1);
+    body.put_i8(0);
+    body.put_array_length(1);
+    body.put_string(KAFKA_TOPIC);
+    body.put_array_length(1);
+    body.put_i32(0);
+    body.put_i32(-1);
+    body.put_i64(-1);
+    let mut response = kafka.send(LIST_OFFSETS, 5, body).await;
+    response.read_i32().unwrap();
+    response.read_i32().unwrap();
+    response.read_string().unwrap();
+    response.read_i32().unwrap();
+    response.read_i32().unwrap();
+    assert_eq!(response.read_i16().unwrap(), NONE);
+    response.read_i64().unwrap();
+    assert_eq!(response.read_i64().unwrap(), MESSAGES_COUNT as i64);
+
+    // 9. Commit the offset outside of the group membership, which creates the Iggy consumer group
+    let mut body = BytesMut::new();
+    body.put_string(KAFKA_GROUP);
+    body.put_i32(-1);
+    body.put_string("");
+    body.put_nullable_string(None);
+    body.put_array_length(1);
+    body.put_string(KAFKA_TOPIC);
+    body.put_array_length(1);
+    body.put_i32(0);
+    body.put_i64(2);
+    body.put_i32(-1);
+    body.put_nullable_string(None);
+    let mut response = kafka.send(OFFSET_COMMIT, 7, body).await;
+    response.read_i32().unwrap();
+    response.read_i32().unwrap();
+    response.read_string().unwrap();
+    response.read_i32().unwrap();
+    response.read_i32().unwrap();
+    assert_eq!(response.read_i16().unwrap(), NONE);
+
+    let offset = client
+        .get_consumer_offset(
+            &Consumer::group(Identifier::named(KAFKA_GROUP).unwrap()),
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            Some(1),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(offset.stored_offset, 1);
+
+    // 10. The committed offset should be fetched
+    let mut body = BytesMut::new();
+    body.put_string(KAFKA_GROUP);
+    body.put_array_length(1);
+    body.put_string(KAFKA_TOPIC);
+    body.put_array_length(1);
+    body.put_i32(0);
+    let mut response = kafka.send(OFFSET_FETCH, 5, body).await;
+    response.read_i32().unwrap();
+    response.read_i32().unwrap();
+    response.read_string().unwrap();
+    response.read_i32().unwrap();
+    response.read_i32().unwrap();
+    assert_eq!(response.read_i64().unwrap(), 2);
+    response.read_i32().unwrap();
+    response.read_nullable_string().unwrap();
+    assert_eq!(response.read_i16().unwrap(), NONE);
+
+    cleanup(&client, false).await;
+    assert_clean_system(&client).await;
+}
+
+struct KafkaClient {
+    stream: TcpStream,
+    correlation_id: i32,
+}
+
+impl KafkaClient {
+    async fn connect(address: &str) -> Self {
+        Self {
+            stream: TcpStream::connect(address).await.unwrap(),
+            correlation_id: 0,
+        }
+    }
+
+    async fn send(&mut self, api_key: i16, api_version: i16, body: BytesMut) -> KafkaReader {
+        self.try_send(api_key, api_version, body)
+            .await
+            .expect("Kafka connection has been closed")
+    }
+
+    /// Returns the response body, or none if the connection has been closed by the server.
+    async fn try_send(
+        &mut self,
+        api_key: i16,
+        api_version: i16,
+        body: BytesMut,
+    ) -> Option<KafkaReader> {
+        self.correlation_id += 1;
+        let mut request = BytesMut::new();
+        request.put_i16(api_key);
+        request.put_i16(api_version);
+        request.put_i32(self.correlation_id);
+        request.put_nullable_string(Some("integration"));
+        request.put_slice(&body);
+
+        let mut frame = BytesMut::new();
+        frame.put_i32(request.len() as i32);
+        frame.put_slice(&request);
+        self.stream.write_all(&frame).await.ok()?;
+
+        let length = self.stream.read_i32().await.ok()?;
+        let mut response = vec![0; length as usize];
+        self.stream.read_exact(&mut response).await.ok()?;
+        let mut reader = KafkaReader::new(Bytes::from(response));
+        assert_eq!(reader.read_i32().unwrap(), self.correlation_id);
+        Some(reader)
+    }
+}
+
+async fn init_system(client: &IggyClient) {
+    client
+        .create_stream(STREAM_NAME, Some(STREAM_ID))
+        .await
+        .unwrap();
+
+    client
+        .create_topic(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            TOPIC_NAME,
+            PARTITIONS_COUNT,
+            CompressionAlgorithm::default(),
+            None,
+            Some(TOPIC_ID),
+            IggyExpiry::NeverExpire,
+            MaxTopicSize::ServerDefault,
+        )
+        .await
+        .unwrap();
+}
@@ -0,0 +1,174 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::server::scenarios::{
+    PARTITION_ID, STREAM_ID, STREAM_NAME, TOPIC_ID, TOPIC_NAME, cleanup, create_client,
+};
+use bytes::Bytes;
+use futures::StreamExt;
+use iggy::prelude::*;
+use integration::test_server::{ClientFactory, assert_clean_system, login_root};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+const CONSUMER_NAME: &str = "long-polling-consumer";
+
+pub async fn run(client_factory: &dyn ClientFactory) {
+    let client = create_client(client_factory).await;
+    login_root(&client).await;
+    init_system(&client).await;
+
+    let consumer = Consumer::default();
+
+    // 1. Long poll the empty partition, the empty result should be returned after the timeout
+    let timeout = IggyDuration::from_str("200ms").unwrap();
+    let started_at = Instant::now();
+    let polled_messages = long_poll(&client, &consumer, 0, timeout).await;
+    assert!(polled_messages.messages.is_empty());
+    assert!(started_at.elapsed() >= timeout.get_duration());
+
+    // 2. Long poll the empty partition, while another client sends the message in the meantime
+    let producer = create_client(client_factory).await;
+    login_root(&producer).await;
+    let send_task = tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        send_message(&producer, 0).await;
+    });
+
+    let timeout = IggyDuration::from_str("30s").unwrap();
+    let started_at = Instant::now();
+    let polled_messages = long_poll(&client, &consumer, 0, timeout).await;
+    send_task.await.unwrap();
+    assert_eq!(polled_messages.messages.len(), 1);
+    assert_eq!(polled_messages.messages[0].header.offset, 0);
+    assert_eq!(
+        polled_messages.messages[0].payload,
+        create_message_payload(0)
+    );
+    assert!(started_at.elapsed() < timeout.get_duration());
+
+    // 3. Long poll the partition with the available messages, they should be returned immediately
+    let started_at = Instant::now();
+    let polled_messages = long_poll(&client, &consumer, 0, timeout).await;
+    assert_eq!(polled_messages.messages.len(), 1);
+    assert!(started_at.elapsed() < timeout.get_duration());
+
+    // 4. Long poll with the timeout exceeding the maximum allowed value should fail
+    let timeout =
+        IggyDuration::new(MAX_LONG_POLLING_TIMEOUT.get_duration() + Duration::from_secs(1));
+    let result = client
+        .long_poll_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            Some(PARTITION_ID),
+            &consumer,
+            &PollingStrategy::offset(0),
+            1,
+            false,
+            &timeout,
+        )
+        .await;
+    assert!(result.is_err());
+
+    // 5. The consumer should use the long polling to receive the new messages
+    let mut consumer = client
+        .consumer(CONSUMER_NAME, STREAM_NAME, TOPIC_NAME, PARTITION_ID)
+        .unwrap()
+        .polling_strategy(PollingStrategy::offset(1))
+        .long_polling_timeout(IggyDuration::from_str("5s").unwrap())
+        .auto_commit(AutoCommit::Disabled)
+        .build();
+    consumer.init().await.unwrap();
+
+    send_message(&client, 1).await;
+    let message = tokio::time::timeout(Duration::from_secs(10), consumer.next())
+        .await
+        .expect("Failed to receive the message in time")
+        .unwrap()
+        .unwrap();
+    assert_eq!(message.message.header.offset, 1);
+    assert_eq!(message.message.payload, create_message_payload(1));
+    drop(consumer);
+
+    cleanup(&client, false).await;
+    assert_clean_system(&client).await;
+}
+
+async fn long_poll(
+    client: &IggyClient,
+    consumer: &Consumer,
+    offset: u64,
+    timeout: IggyDuration,
+) -> PolledMessages {
+    client
+        .long_poll_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            Some(PARTITION_ID),
+            consumer,
+            &PollingStrategy::offset(offset),
+            1,
+            false,
+            &timeout,
+        )
+        .await
+        .unwrap()
+}
+
+async fn send_message(client: &IggyClient, offset: u64) {
+    let mut messages = vec![
+        IggyMessage::builder()
+            .payload(create_message_payload(offset))
+            .build()
+            .unwrap(),
+    ];
+    client
+        .send_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            &Partitioning::partition_id(PARTITION_ID),
+            &mut messages,
+        )
+        .await
+        .unwrap();
+}
+
+async fn init_system(client: &IggyClient) {
+    client
+        .create_stream(STREAM_NAME, Some(STREAM_ID))
+        .await
+        .unwrap();
+
+    client
+        .create_topic(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            TOPIC_NAME,
+            1,
+            CompressionAlgorithm::default(),
+            None,
+            Some(TOPIC_ID),
+            IggyExpiry::NeverExpire,
+            MaxTopicSize::ServerDefault,
+        )
+        .await
+        .unwrap();
+}
+
+fn create_message_payload(offset: u64) -> Bytes {
+    Bytes::from(format!("message {offset}"))
+}
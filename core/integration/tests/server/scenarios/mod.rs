@@ -21,13 +21,23 @@ pub mod consumer_group_join_scenario;
 pub mod consumer_group_with_multiple_clients_polling_messages_scenario;
 pub mod consumer_group_with_single_client_polling_messages_scenario;
 pub mod create_message_payload;
+pub mod data_snapshot_scenario;
 pub mod delete_segments_scenario;
+pub mod http_messages_stream_scenario;
+pub mod idempotent_producer_scenario;
+pub mod kafka_scenario;
+pub mod long_polling_scenario;
 pub mod message_headers_scenario;
 pub mod message_size_scenario;
+pub mod mqtt_scenario;
+pub mod shrink_partitions_scenario;
 pub mod stream_size_validation_scenario;
 pub mod system_scenario;
 pub mod tcp_tls_scenario;
+pub mod topic_overrides_scenario;
+pub mod topic_subscription_scenario;
 pub mod user_scenario;
+pub mod websocket_subscription_scenario;
 
 use iggy::prelude::*;
 use integration::test_server::{ClientFactory, delete_user};
@@ -0,0 +1,351 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::server::scenarios::{
+    PARTITIONS_COUNT, STREAM_ID, STREAM_NAME, TOPIC_ID, TOPIC_NAME, cleanup, create_client,
+};
+use bytes::{BufMut, Bytes, BytesMut};
+use iggy::prelude::*;
+use integration::tcp_client::TcpClientFactory;
+use integration::test_server::{assert_clean_system, login_root};
+use server::mqtt::codec::{MqttBufMut, MqttReader};
+use server::mqtt::messages::MQTT_TOPIC_HEADER;
+use server::mqtt::packets::{
+    FixedHeader, Properties, PropertyValue, Publish, encode_packet, encode_puback,
+};
+use server::mqtt::protocol::{
+    BAD_USER_NAME_OR_PASSWORD, CONNACK, CONNECT, CONNECTION_ACCEPTED, CONTENT_TYPE, PINGREQ,
+    PINGRESP, PROTOCOL_LEVEL_3_1_1, PROTOCOL_LEVEL_5, PROTOCOL_NAME, PUBACK, PUBLISH,
+    QOS_AT_LEAST_ONCE, SUBACK, SUBSCRIBE, SUBSCRIPTION_FAILURE, SUCCESS, TOPIC_NAME_INVALID,
+    USER_PROPERTY,
+};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::time::timeout;
+
+/// The MQTT topic filter mapped onto the test topic, configured with `IGGY_MQTT_MAPPINGS`.
+pub const MAPPED_FILTER: &str = "devices/+/telemetry";
+const DEVICE_TOPIC: &str = "devices/1/telemetry";
+const WILL_TOPIC: &str = "devices/2/telemetry";
+
+pub async fn run(tcp_server_addr: &str, mqtt_server_addr: &str) {
+    let client_factory = TcpClientFactory {
+        server_addr: tcp_server_addr.to_owned(),
+        ..Default::default()
+    };
+    let client = create_client(&client_factory).await;
+    login_root(&client).await;
+    init_system(&client).await;
+
+    // 1. The connection with invalid credentials should be refused
+    let mut mqtt = MqttClient::connect(mqtt_server_addr).await;
+    let (header, mut packet) = mqtt
+        .connect_as(PROTOCOL_LEVEL_5, "device-0", "root", "invalid", None)
+        .await;
+    assert_eq!(header.packet_type, CONNACK);
+    packet.read_u8().unwrap();
+    assert_eq!(packet.read_u8().unwrap(), BAD_USER_NAME_OR_PASSWORD);
+
+    // 2. The MQTT 3.1.1 subscriber should subscribe to the mapped topic filter and the topic named after the stream and the topic,
+    // while the shared subscriptions should be rejected
+    let mut subscriber = MqttClient::connect(mqtt_server_addr).await;
+    subscriber
+        .connect_root(PROTOCOL_LEVEL_3_1_1, "subscriber", None)
+        .await;
+    let iggy_topic = format!("{STREAM_NAME}/{TOPIC_NAME}");
+    let mut body = BytesMut::new();
+    body.put_u16(1);
+    for filter in [MAPPED_FILTER, &iggy_topic, "$share/group/devices/#"] {
+        body.put_mqtt_string(filter);
+        body.put_u8(QOS_AT_LEAST_ONCE);
+    }
+    subscriber
+        .send(encode_packet(SUBSCRIBE, 0b0010, &body))
+        .await;
+    let (header, mut packet) = subscriber.read_packet().await;
+    assert_eq!(header.packet_type, SUBACK);
+    assert_eq!(packet.read_u16().unwrap(), 1);
+    assert_eq!(
+        packet.read_remaining().as_ref(),
+        &[QOS_AT_LEAST_ONCE, QOS_AT_LEAST_ONCE, SUBSCRIPTION_FAILURE]
+    );
+
+    // 3. The MQTT 5 publisher should publish the message with QoS 1, along with the properties
+    let mut publisher = MqttClient::connect(mqtt_server_addr).await;
+    publisher
+        .connect_root(PROTOCOL_LEVEL_5, "publisher", None)
+        .await;
+    let mut properties = Properties::default();
+    properties.push(CONTENT_TYPE, PropertyValue::String("text/plain".to_owned()));
+    properties.push(
+        USER_PROPERTY,
+        PropertyValue::StringPair("unit".to_owned(), "celsius".to_owned()),
+    );
+    publisher
+        .publish(PROTOCOL_LEVEL_5, DEVICE_TOPIC, 1, properties, "21.5")
+        .await;
+    let (header, mut packet) = publisher.read_packet().await;
+    assert_eq!(header.packet_type, PUBACK);
+    assert_eq!(packet.read_u16().unwrap(), 1);
+    assert_eq!(packet.remaining(), 0);
+
+    // 4. The published message should be received by the subscriber
+    let publish = subscriber.read_publish(PROTOCOL_LEVEL_3_1_1).await;
+    assert_eq!(publish.topic, DEVICE_TOPIC);
+    assert_eq!(publish.payload.as_ref(), b"21.5");
+    assert_eq!(publish.qos, QOS_AT_LEAST_ONCE);
+    subscriber
+        .send(encode_puback(
+            PROTOCOL_LEVEL_3_1_1,
+            publish.packet_id.unwrap(),
+            SUCCESS,
+        ))
+        .await;
+
+    // 5. The message should be appended to the mapped Iggy topic, with the MQTT topic and properties in the headers
+    let messages = poll_all_messages(&client).await;
+    assert_eq!(messages.len(), 1);
+    let headers = messages[0].user_headers_map().unwrap().unwrap();
+    assert_eq!(
+        headers[&HeaderKey::new(MQTT_TOPIC_HEADER).unwrap()]
+            .as_str()
+            .unwrap(),
+        DEVICE_TOPIC
+    );
+    assert_eq!(
+        headers[&HeaderKey::new("unit").unwrap()].as_str().unwrap(),
+        "celsius"
+    );
+
+    // 6. The message published to the topic which isn't mapped onto any Iggy topic should be rejected
+    publisher
+        .publish(
+            PROTOCOL_LEVEL_5,
+            "unknown",
+            2,
+            Properties::default(),
+            "data",
+        )
+        .await;
+    let (header, mut packet) = publisher.read_packet().await;
+    assert_eq!(header.packet_type, PUBACK);
+    assert_eq!(packet.read_u16().unwrap(), 2);
+    assert_eq!(packet.read_u8().unwrap(), TOPIC_NAME_INVALID);
+
+    // 7. The message appended by the Iggy client should be received under the name of the stream and the topic
+    let mut messages = vec![
+        IggyMessage::builder()
+            .payload(Bytes::from("iggy message"))
+            .build()
+            .unwrap(),
+    ];
+    client
+        .send_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            &Partitioning::partition_id(1),
+            &mut messages,
+        )
+        .await
+        .unwrap();
+    let publish = subscriber.read_publish(PROTOCOL_LEVEL_3_1_1).await;
+    assert_eq!(publish.topic, iggy_topic);
+    assert_eq!(publish.payload.as_ref(), b"iggy message");
+    subscriber
+        .send(encode_puback(
+            PROTOCOL_LEVEL_3_1_1,
+            publish.packet_id.unwrap(),
+            SUCCESS,
+        ))
+        .await;
+
+    // 8. The will message should be published once the connection is closed without DISCONNECT
+    let mut device = MqttClient::connect(mqtt_server_addr).await;
+    device
+        .connect_root(PROTOCOL_LEVEL_5, "device-2", Some((WILL_TOPIC, "offline")))
+        .await;
+    drop(device);
+    let publish = subscriber.read_publish(PROTOCOL_LEVEL_3_1_1).await;
+    assert_eq!(publish.topic, WILL_TOPIC);
+    assert_eq!(publish.payload.as_ref(), b"offline");
+
+    // 9. The server should respond to the ping
+    subscriber.send(encode_packet(PINGREQ, 0, &[])).await;
+    let (header, _) = subscriber.read_packet().await;
+    assert_eq!(header.packet_type, PINGRESP);
+
+    cleanup(&client, false).await;
+    assert_clean_system(&client).await;
+}
+
+struct MqttClient {
+    stream: TcpStream,
+}
+
+impl MqttClient {
+    async fn connect(address: &str) -> Self {
+        Self {
+            stream: TcpStream::connect(address).await.unwrap(),
+        }
+    }
+
+    async fn connect_root(
+        &mut self,
+        protocol_level: u8,
+        client_id: &str,
+        will: Option<(&str, &str)>,
+    ) {
+        let (header, mut packet) = self
+            .connect_as(
+                protocol_level,
+                client_id,
+                DEFAULT_ROOT_USERNAME,
+                DEFAULT_ROOT_PASSWORD,
+                will,
+            )
+            .await;
+        assert_eq!(header.packet_type, CONNACK);
+        assert_eq!(packet.read_u8().unwrap(), 0);
+        assert_eq!(packet.read_u8().unwrap(), CONNECTION_ACCEPTED);
+    }
+
+    async fn connect_as(
+        &mut self,
+        protocol_level: u8,
+        client_id: &str,
+        username: &str,
+        password: &str,
+        will: Option<(&str, &str)>,
+    ) -> (FixedHeader, MqttReader) {
+        let is_v5 = protocol_level == PROTOCOL_LEVEL_5;
+        let mut body = BytesMut::new();
+        body.put_mqtt_string(PROTOCOL_NAME);
+        body.put_u8(protocol_level);
+        body.put_u8(0b1100_0010 | if will.is_some() { 0b0000_0100 } else { 0 });
+        body.put_u16(60);
+        if is_v5 {
+            Properties::default().write(&mut body);
+        }
+        body.put_mqtt_string(client_id);
+        if let Some((topic, payload)) = will {
+            if is_v5 {
+                Properties::default().write(&mut body);
+            }
+            body.put_mqtt_string(topic);
+            body.put_mqtt_binary(payload.as_bytes());
+        }
+        body.put_mqtt_string(username);
+        body.put_mqtt_binary(password.as_bytes());
+        self.send(encode_packet(CONNECT, 0, &body)).await;
+        self.read_packet().await
+    }
+
+    async fn publish(
+        &mut self,
+        protocol_level: u8,
+        topic: &str,
+        packet_id: u16,
+        properties: Properties,
+        payload: &'static str,
+    ) {
+        let publish = Publish {
+            dup: false,
+            qos: QOS_AT_LEAST_ONCE,
+            retain: false,
+            topic: topic.to_owned(),
+            packet_id: Some(packet_id),
+            properties,
+            payload: Bytes::from_static(payload.as_bytes()),
+        };
+        self.send(publish.encode(protocol_level)).await;
+    }
+
+    async fn send(&mut self, packet: Bytes) {
+        self.stream.write_all(&packet).await.unwrap();
+    }
+
+    async fn read_publish(&mut self, protocol_level: u8) -> Publish {
+        let (header, mut packet) = self.read_packet().await;
+        assert_eq!(header.packet_type, PUBLISH);
+        Publish::read(header.flags, &mut packet, protocol_level).unwrap()
+    }
+
+    async fn read_packet(&mut self) -> (FixedHeader, MqttReader) {
+        timeout(Duration::from_secs(5), async {
+            let byte = self.stream.read_u8().await.unwrap();
+            let mut remaining_length = 0u32;
+            for index in 0..4 {
+                let length_byte = self.stream.read_u8().await.unwrap();
+                remaining_length |= ((length_byte & 0x7f) as u32) << (index * 7);
+                if length_byte & 0x80 == 0 {
+                    break;
+                }
+            }
+            let mut body = vec![0; remaining_length as usize];
+            self.stream.read_exact(&mut body).await.unwrap();
+            (
+                FixedHeader::new(byte, remaining_length),
+                MqttReader::new(Bytes::from(body)),
+            )
+        })
+        .await
+        .expect("Timed out waiting for the MQTT packet")
+    }
+}
+
+async fn poll_all_messages(client: &IggyClient) -> Vec<IggyMessage> {
+    let mut messages = Vec::new();
+    for partition_id in 1..=PARTITIONS_COUNT {
+        let polled_messages = client
+            .poll_messages(
+                &Identifier::numeric(STREAM_ID).unwrap(),
+                &Identifier::numeric(TOPIC_ID).unwrap(),
+                Some(partition_id),
+                &Consumer::default(),
+                &PollingStrategy::offset(0),
+                10,
+                false,
+            )
+            .await
+            .unwrap();
+        messages.extend(polled_messages.messages);
+    }
+    messages
+}
+
+async fn init_system(client: &IggyClient) {
+    client
+        .create_stream(STREAM_NAME, Some(STREAM_ID))
+        .await
+        .unwrap();
+
+    client
+        .create_topic(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            TOPIC_NAME,
+            PARTITIONS_COUNT,
+            CompressionAlgorithm::default(),
+            None,
+            Some(TOPIC_ID),
+            IggyExpiry::NeverExpire,
+            MaxTopicSize::ServerDefault,
+        )
+        .await
+        .unwrap();
+}
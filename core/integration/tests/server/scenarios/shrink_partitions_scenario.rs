@@ -0,0 +1,318 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::server::scenarios::{
+    CONSUMER_GROUP_ID, CONSUMER_GROUP_NAME, STREAM_ID, STREAM_NAME, TOPIC_ID, TOPIC_NAME, cleanup,
+    create_client,
+};
+use bytes::Bytes;
+use iggy::prelude::*;
+use integration::test_server::{ClientFactory, assert_clean_system, login_root};
+use std::collections::HashMap;
+use std::str::FromStr;
+use tokio::time::{Duration, sleep};
+
+const PARTITIONS_COUNT: u32 = 4;
+const MESSAGES_PER_PARTITION: u32 = 10;
+const KEY_HEADER: &str = "key";
+const MAX_DRAIN_WAIT_ATTEMPTS: u32 = 100;
+const CONSUMER_ID: u32 = 1;
+const DRAINER_INTERVAL: Duration = Duration::from_secs(1);
+
+pub async fn run(client_factory: &dyn ClientFactory) {
+    let client = create_client(client_factory).await;
+    login_root(&client).await;
+    let stream_id = Identifier::numeric(STREAM_ID).unwrap();
+    let topic_id = Identifier::numeric(TOPIC_ID).unwrap();
+    let consumer = Consumer::group(Identifier::numeric(CONSUMER_GROUP_ID).unwrap());
+    client
+        .create_stream(STREAM_NAME, Some(STREAM_ID))
+        .await
+        .unwrap();
+    client
+        .create_topic(
+            &stream_id,
+            TOPIC_NAME,
+            PARTITIONS_COUNT,
+            CompressionAlgorithm::default(),
+            None,
+            Some(TOPIC_ID),
+            IggyExpiry::NeverExpire,
+            MaxTopicSize::ServerDefault,
+        )
+        .await
+        .unwrap();
+    client
+        .create_consumer_group(
+            &stream_id,
+            &topic_id,
+            CONSUMER_GROUP_NAME,
+            Some(CONSUMER_GROUP_ID),
+        )
+        .await
+        .unwrap();
+    for partition_id in 1..=PARTITIONS_COUNT {
+        send_messages(&client, partition_id).await.unwrap();
+    }
+
+    // 1. At least one partition must remain
+    assert!(
+        client
+            .shrink_partitions(&stream_id, &topic_id, PARTITIONS_COUNT, false, None, false)
+            .await
+            .is_err()
+    );
+
+    // 2. The draining partition no longer accepts new messages, but still can be consumed
+    client
+        .shrink_partitions(&stream_id, &topic_id, 1, false, None, false)
+        .await
+        .unwrap();
+    assert!(send_messages(&client, PARTITIONS_COUNT).await.is_err());
+    assert!(
+        client
+            .create_partitions(&stream_id, &topic_id, 1)
+            .await
+            .is_err()
+    );
+    assert!(
+        client
+            .delete_partitions(&stream_id, &topic_id, 1)
+            .await
+            .is_err()
+    );
+    let mut messages = create_messages("balanced", None);
+    client
+        .send_messages(
+            &stream_id,
+            &topic_id,
+            &Partitioning::balanced(),
+            &mut messages,
+        )
+        .await
+        .unwrap();
+    let topic = get_topic(&client).await;
+    assert_eq!(topic.partitions_count, PARTITIONS_COUNT);
+    assert_eq!(
+        topic.partitions[PARTITIONS_COUNT as usize - 1].messages_count,
+        MESSAGES_PER_PARTITION as u64
+    );
+    let polled_messages = client
+        .poll_messages(
+            &stream_id,
+            &topic_id,
+            Some(PARTITIONS_COUNT),
+            &consumer,
+            &PollingStrategy::offset(0),
+            MESSAGES_PER_PARTITION,
+            false,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        polled_messages.messages.len() as u32,
+        MESSAGES_PER_PARTITION
+    );
+
+    // 3. Once consumed by the consumer group and the consumer with the stored offset, the draining partition is deleted
+    let individual_consumer = Consumer::new(Identifier::numeric(CONSUMER_ID).unwrap());
+    client
+        .store_consumer_offset(
+            &individual_consumer,
+            &stream_id,
+            &topic_id,
+            Some(PARTITIONS_COUNT),
+            0,
+        )
+        .await
+        .unwrap();
+    client
+        .store_consumer_offset(
+            &consumer,
+            &stream_id,
+            &topic_id,
+            Some(PARTITIONS_COUNT),
+            MESSAGES_PER_PARTITION as u64 - 1,
+        )
+        .await
+        .unwrap();
+    sleep(DRAINER_INTERVAL * 2).await;
+    assert_eq!(get_topic(&client).await.partitions_count, PARTITIONS_COUNT);
+    client
+        .store_consumer_offset(
+            &individual_consumer,
+            &stream_id,
+            &topic_id,
+            Some(PARTITIONS_COUNT),
+            MESSAGES_PER_PARTITION as u64 - 1,
+        )
+        .await
+        .unwrap();
+    let mut attempts = 0;
+    while get_topic(&client).await.partitions_count == PARTITIONS_COUNT {
+        attempts += 1;
+        assert!(attempts < MAX_DRAIN_WAIT_ATTEMPTS);
+        sleep(Duration::from_millis(100)).await;
+    }
+    let topic = get_topic(&client).await;
+    assert_eq!(topic.partitions_count, PARTITIONS_COUNT - 1);
+    client
+        .create_partitions(&stream_id, &topic_id, 1)
+        .await
+        .unwrap();
+    client
+        .delete_partitions(&stream_id, &topic_id, 1)
+        .await
+        .unwrap();
+
+    // 4. The unconsumed messages are copied by the consistent hash of the key into the remaining partitions, preserving their order
+    let partition_id = PARTITIONS_COUNT - 1;
+    let mut messages = create_messages("keyed", Some("tenant-1"));
+    client
+        .send_messages(
+            &stream_id,
+            &topic_id,
+            &Partitioning::partition_id(partition_id),
+            &mut messages,
+        )
+        .await
+        .unwrap();
+    let consumed_messages_count = 5;
+    client
+        .store_consumer_offset(
+            &consumer,
+            &stream_id,
+            &topic_id,
+            Some(partition_id),
+            consumed_messages_count - 1,
+        )
+        .await
+        .unwrap();
+    let messages_count = get_topic(&client).await.messages_count;
+    client
+        .shrink_partitions(&stream_id, &topic_id, 1, true, Some(KEY_HEADER), true)
+        .await
+        .unwrap();
+    let topic = get_topic(&client).await;
+    assert_eq!(topic.partitions_count, partition_id - 1);
+    assert_eq!(
+        topic.messages_count,
+        messages_count - consumed_messages_count
+    );
+
+    let mut keyed_payloads = Vec::new();
+    for partition_id in 1..partition_id {
+        let polled_messages = client
+            .poll_messages(
+                &stream_id,
+                &topic_id,
+                Some(partition_id),
+                &consumer,
+                &PollingStrategy::offset(0),
+                1000,
+                false,
+            )
+            .await
+            .unwrap();
+        let messages_count = topic.partitions[partition_id as usize - 1].messages_count;
+        assert_eq!(polled_messages.messages.len() as u64, messages_count);
+        for message in polled_messages.messages {
+            let payload = message.payload_as_string().unwrap();
+            if payload.starts_with("keyed") {
+                keyed_payloads.push((partition_id, payload));
+            }
+        }
+    }
+
+    // All the keyed messages landed in the same partition, in the original order.
+    assert_eq!(keyed_payloads.len() as u32, MESSAGES_PER_PARTITION);
+    let keyed_partition_id = keyed_payloads[0].0;
+    assert!(
+        keyed_payloads
+            .iter()
+            .all(|(partition_id, _)| *partition_id == keyed_partition_id)
+    );
+    let expected_payloads = (0..MESSAGES_PER_PARTITION)
+        .map(|index| format!("keyed-{index}"))
+        .collect::<Vec<_>>();
+    let keyed_payloads = keyed_payloads
+        .into_iter()
+        .map(|(_, payload)| payload)
+        .collect::<Vec<_>>();
+    assert_eq!(keyed_payloads, expected_payloads);
+
+    // The next messages with the same key land in the same partition as the copied ones.
+    let mut messages = create_messages("keyed", Some("tenant-1"));
+    client
+        .send_messages(
+            &stream_id,
+            &topic_id,
+            &Partitioning::consistent_messages_key_str("tenant-1").unwrap(),
+            &mut messages,
+        )
+        .await
+        .unwrap();
+    let keyed_partition_index = keyed_partition_id as usize - 1;
+    assert_eq!(
+        get_topic(&client).await.partitions[keyed_partition_index].messages_count,
+        topic.partitions[keyed_partition_index].messages_count + MESSAGES_PER_PARTITION as u64
+    );
+
+    cleanup(&client, false).await;
+    assert_clean_system(&client).await;
+}
+
+async fn send_messages(client: &IggyClient, partition_id: u32) -> Result<(), IggyError> {
+    let mut messages = create_messages(&format!("partition-{partition_id}"), None);
+    client
+        .send_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            &Partitioning::partition_id(partition_id),
+            &mut messages,
+        )
+        .await
+}
+
+fn create_messages(prefix: &str, key: Option<&str>) -> Vec<IggyMessage> {
+    (0..MESSAGES_PER_PARTITION)
+        .map(|index| {
+            IggyMessage::builder()
+                .payload(Bytes::from(format!("{prefix}-{index}")))
+                .maybe_user_headers(key.map(|key| {
+                    HashMap::from([(
+                        HeaderKey::new(KEY_HEADER).unwrap(),
+                        HeaderValue::from_str(key).unwrap(),
+                    )])
+                }))
+                .build()
+                .unwrap()
+        })
+        .collect()
+}
+
+async fn get_topic(client: &IggyClient) -> TopicDetails {
+    client
+        .get_topic(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+        )
+        .await
+        .unwrap()
+        .expect("Failed to get topic")
+}
@@ -0,0 +1,167 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::server::scenarios::{
+    PARTITION_ID, STREAM_ID, STREAM_NAME, TOPIC_ID, TOPIC_NAME, cleanup, create_client,
+};
+use bytes::Bytes;
+use iggy::prelude::*;
+use integration::test_server::{ClientFactory, assert_clean_system, login_root};
+use std::str::FromStr;
+
+const BATCHES_COUNT: u32 = 10;
+const MESSAGES_PER_BATCH: u32 = 10;
+const PAYLOAD_SIZE: usize = 1000;
+
+pub async fn run(client_factory: &dyn ClientFactory) {
+    let client = create_client(client_factory).await;
+    login_root(&client).await;
+    client
+        .create_stream(STREAM_NAME, Some(STREAM_ID))
+        .await
+        .unwrap();
+
+    // 1. The overrides breaking the same rules as the server config should be rejected
+    let invalid_overrides = TopicOverrides {
+        segment_size: Some(IggyByteSize::from(1000)),
+        ..Default::default()
+    };
+    assert!(create_topic(&client, invalid_overrides).await.is_err());
+    let invalid_overrides = TopicOverrides {
+        messages_required_to_save: Some(10),
+        ..Default::default()
+    };
+    assert!(create_topic(&client, invalid_overrides).await.is_err());
+
+    // 2. The small segments should be created, as the buffered messages are saved on disk frequently
+    let overrides = TopicOverrides {
+        segment_size: Some(IggyByteSize::from_str("16 KiB").unwrap()),
+        enforce_fsync: Some(true),
+        messages_required_to_save: Some(32),
+        server_confirmation: Some(Confirmation::Wait),
+        ..Default::default()
+    };
+    create_topic(&client, overrides).await.unwrap();
+    send_batches(&client).await;
+    let segments_count = get_segments_count(&client).await;
+    assert!(segments_count > 1);
+    assert_eq!(
+        get_messages_count(&client).await,
+        BATCHES_COUNT * MESSAGES_PER_BATCH
+    );
+
+    // 3. The updated overrides should apply to the already existing topic
+    let overrides = TopicOverrides {
+        segment_size: Some(IggyByteSize::from_str("1 MiB").unwrap()),
+        enforce_fsync: Some(false),
+        messages_required_to_save: Some(32),
+        server_confirmation: None,
+        ..Default::default()
+    };
+    client
+        .update_topic_with_overrides(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            TOPIC_NAME,
+            CompressionAlgorithm::default(),
+            None,
+            IggyExpiry::NeverExpire,
+            MaxTopicSize::ServerDefault,
+            overrides,
+        )
+        .await
+        .unwrap();
+    send_batches(&client).await;
+    assert!(get_segments_count(&client).await <= segments_count + 1);
+    assert_eq!(
+        get_messages_count(&client).await,
+        2 * BATCHES_COUNT * MESSAGES_PER_BATCH
+    );
+
+    cleanup(&client, false).await;
+    assert_clean_system(&client).await;
+}
+
+async fn create_topic(
+    client: &IggyClient,
+    overrides: TopicOverrides,
+) -> Result<TopicDetails, IggyError> {
+    client
+        .create_topic_with_overrides(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            TOPIC_NAME,
+            1,
+            CompressionAlgorithm::default(),
+            None,
+            Some(TOPIC_ID),
+            IggyExpiry::NeverExpire,
+            MaxTopicSize::ServerDefault,
+            overrides,
+        )
+        .await
+}
+
+async fn send_batches(client: &IggyClient) {
+    for _ in 0..BATCHES_COUNT {
+        let mut messages = (0..MESSAGES_PER_BATCH)
+            .map(|_| {
+                IggyMessage::builder()
+                    .payload(Bytes::from(vec![0; PAYLOAD_SIZE]))
+                    .build()
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        client
+            .send_messages(
+                &Identifier::numeric(STREAM_ID).unwrap(),
+                &Identifier::numeric(TOPIC_ID).unwrap(),
+                &Partitioning::partition_id(PARTITION_ID),
+                &mut messages,
+            )
+            .await
+            .unwrap();
+    }
+}
+
+async fn get_segments_count(client: &IggyClient) -> u32 {
+    let topic = client
+        .get_topic(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+        )
+        .await
+        .unwrap()
+        .expect("Topic should exist");
+    topic.partitions[0].segments_count
+}
+
+async fn get_messages_count(client: &IggyClient) -> u32 {
+    let polled_messages = client
+        .poll_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            Some(PARTITION_ID),
+            &Consumer::default(),
+            &PollingStrategy::offset(0),
+            4 * BATCHES_COUNT * MESSAGES_PER_BATCH,
+            false,
+        )
+        .await
+        .unwrap();
+    polled_messages.messages.len() as u32
+}
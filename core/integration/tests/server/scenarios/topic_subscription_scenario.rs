@@ -0,0 +1,177 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::server::scenarios::{STREAM_ID, STREAM_NAME, cleanup, create_client};
+use bytes::Bytes;
+use futures::StreamExt;
+use iggy::prelude::*;
+use integration::test_server::{ClientFactory, assert_clean_system, login_root};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+const CONSUMER_GROUP_NAME: &str = "tenants-consumer-group";
+const TENANT_1_TOPIC: &str = "tenant-1";
+const TENANT_2_TOPIC: &str = "tenant-2";
+const OTHER_TOPIC: &str = "other";
+
+pub async fn run(client_factory: &dyn ClientFactory) {
+    let client = create_client(client_factory).await;
+    login_root(&client).await;
+    client
+        .create_stream(STREAM_NAME, Some(STREAM_ID))
+        .await
+        .unwrap();
+
+    // 1. The current topics version should be returned immediately for the unknown version
+    let no_timeout = IggyDuration::from_str("0ms").unwrap();
+    let version = client.watch_topics(0, &no_timeout).await.unwrap();
+    assert!(version > 0);
+
+    // 2. Watch the unchanged topics, the same version should be returned after the timeout
+    let timeout = IggyDuration::from_str("200ms").unwrap();
+    let started_at = Instant::now();
+    assert_eq!(
+        client.watch_topics(version, &timeout).await.unwrap(),
+        version
+    );
+    assert!(started_at.elapsed() >= timeout.get_duration());
+
+    // 3. Watch the topics, while another client creates the topic in the meantime
+    let other_client = create_client(client_factory).await;
+    login_root(&other_client).await;
+    let create_task = tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        create_topic(&other_client, TENANT_1_TOPIC).await;
+        other_client
+    });
+
+    let timeout = IggyDuration::from_str("30s").unwrap();
+    let started_at = Instant::now();
+    let changed_version = client.watch_topics(version, &timeout).await.unwrap();
+    let other_client = create_task.await.unwrap();
+    assert_ne!(changed_version, version);
+    assert!(started_at.elapsed() < timeout.get_duration());
+
+    // 4. The pattern consumer should subscribe only to the matching topics
+    create_topic(&client, OTHER_TOPIC).await;
+    // The consumer uses its own connection, as its background tasks are aborted when it's dropped.
+    let consumer_client = create_client(client_factory).await;
+    login_root(&consumer_client).await;
+    let mut consumer = consumer_client
+        .pattern_consumer_group(
+            CONSUMER_GROUP_NAME,
+            NamePattern::exact(STREAM_NAME),
+            NamePattern::glob("tenant-*").unwrap(),
+        )
+        .unwrap()
+        .discovery_interval(IggyDuration::from_str("100ms").unwrap())
+        .build();
+    consumer.init().await.unwrap();
+    assert_eq!(
+        consumer.subscribed_topics(),
+        vec![(STREAM_NAME, TENANT_1_TOPIC)]
+    );
+
+    // 5. The topic created after the initialization should be subscribed automatically
+    create_topic(&other_client, TENANT_2_TOPIC).await;
+    wait_for_subscribed_topics(&mut consumer, 2).await;
+    for topic in [OTHER_TOPIC, TENANT_1_TOPIC, TENANT_2_TOPIC] {
+        send_message(&other_client, topic).await;
+    }
+
+    let mut received_topics = Vec::new();
+    for _ in 0..2 {
+        let message = tokio::time::timeout(Duration::from_secs(10), consumer.next())
+            .await
+            .expect("Failed to receive the message in time")
+            .unwrap()
+            .unwrap();
+        assert_eq!(&*message.stream, STREAM_NAME);
+        assert_eq!(
+            message.message.message.payload,
+            Bytes::from(message.topic.to_string())
+        );
+        received_topics.push(message.topic.to_string());
+    }
+    received_topics.sort();
+    assert_eq!(received_topics, vec![TENANT_1_TOPIC, TENANT_2_TOPIC]);
+
+    // 6. The deleted topic should be unsubscribed
+    other_client
+        .delete_topic(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::named(TENANT_2_TOPIC).unwrap(),
+        )
+        .await
+        .unwrap();
+    wait_for_subscribed_topics(&mut consumer, 1).await;
+    assert_eq!(
+        consumer.subscribed_topics(),
+        vec![(STREAM_NAME, TENANT_1_TOPIC)]
+    );
+    drop(consumer);
+
+    cleanup(&client, false).await;
+    assert_clean_system(&client).await;
+}
+
+/// The subscriptions are updated when polling the consumer, thus it's polled until the expected topics count is reached.
+async fn wait_for_subscribed_topics(consumer: &mut IggyPatternConsumer, count: usize) {
+    let started_at = Instant::now();
+    while consumer.subscribed_topics().len() != count {
+        assert!(
+            started_at.elapsed() < Duration::from_secs(10),
+            "Failed to subscribe to {count} topic(s) in time"
+        );
+        let _ = tokio::time::timeout(Duration::from_millis(100), consumer.next()).await;
+    }
+}
+
+async fn create_topic(client: &IggyClient, name: &str) {
+    client
+        .create_topic(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            name,
+            1,
+            CompressionAlgorithm::default(),
+            None,
+            None,
+            IggyExpiry::NeverExpire,
+            MaxTopicSize::ServerDefault,
+        )
+        .await
+        .unwrap();
+}
+
+async fn send_message(client: &IggyClient, topic: &str) {
+    let mut messages = vec![
+        IggyMessage::builder()
+            .payload(Bytes::from(topic.to_owned()))
+            .build()
+            .unwrap(),
+    ];
+    client
+        .send_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::named(topic).unwrap(),
+            &Partitioning::partition_id(1),
+            &mut messages,
+        )
+        .await
+        .unwrap();
+}
@@ -0,0 +1,115 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use bytes::Bytes;
+use iggy::prelude::*;
+use std::time::Duration;
+use tokio::time::timeout;
+
+const STREAM_ID: u32 = 1;
+const TOPIC_ID: u32 = 1;
+const PARTITION_ID: u32 = 1;
+const SUBSCRIPTION_ID: u32 = 1;
+const MESSAGES_COUNT: u32 = 10;
+
+pub async fn run(client: &WebSocketClient) {
+    client
+        .login_user(DEFAULT_ROOT_USERNAME, DEFAULT_ROOT_PASSWORD)
+        .await
+        .unwrap();
+    client
+        .create_stream("test-websocket-stream", Some(STREAM_ID))
+        .await
+        .unwrap();
+    client
+        .create_topic(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            "test-websocket-topic",
+            1,
+            CompressionAlgorithm::default(),
+            None,
+            Some(TOPIC_ID),
+            IggyExpiry::NeverExpire,
+            MaxTopicSize::ServerDefault,
+        )
+        .await
+        .unwrap();
+
+    // 1. Subscribe to the empty partition, starting from the first offset
+    let subscription = MessagesSubscription {
+        subscription_id: SUBSCRIPTION_ID,
+        stream_id: Identifier::numeric(STREAM_ID).unwrap(),
+        topic_id: Identifier::numeric(TOPIC_ID).unwrap(),
+        partition_id: Some(PARTITION_ID),
+        consumer_kind: ConsumerKind::Consumer,
+        consumer_id: Identifier::numeric(1).unwrap(),
+        strategy: PollingStrategy::offset(0),
+        count: MESSAGES_COUNT,
+        auto_commit: false,
+    };
+    let receiver = client.subscribe(subscription.clone()).await.unwrap();
+
+    // 2. Subscribing again with the same ID must fail
+    assert!(client.subscribe(subscription).await.is_err());
+
+    // 3. Send the messages, which should be pushed to the subscriber
+    let mut messages = (0..MESSAGES_COUNT)
+        .map(|offset| {
+            IggyMessage::builder()
+                .payload(Bytes::from(format!("message {offset}")))
+                .build()
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    client
+        .send_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            &Partitioning::partition_id(PARTITION_ID),
+            &mut messages,
+        )
+        .await
+        .unwrap();
+
+    let mut received_messages = Vec::new();
+    while received_messages.len() < MESSAGES_COUNT as usize {
+        let polled_messages = timeout(Duration::from_secs(5), receiver.recv_async())
+            .await
+            .expect("Timed out waiting for the pushed messages")
+            .unwrap()
+            .unwrap();
+        assert_eq!(polled_messages.partition_id, PARTITION_ID);
+        received_messages.extend(polled_messages.messages);
+    }
+
+    assert_eq!(received_messages.len() as u32, MESSAGES_COUNT);
+    for (offset, message) in received_messages.iter().enumerate() {
+        assert_eq!(message.header.offset, offset as u64);
+        assert_eq!(message.payload, Bytes::from(format!("message {offset}")));
+    }
+
+    // 4. Unsubscribe, the channel should be closed
+    client.unsubscribe(SUBSCRIPTION_ID).await.unwrap();
+    assert!(receiver.recv_async().await.is_err());
+    assert!(client.unsubscribe(SUBSCRIPTION_ID).await.is_err());
+
+    client
+        .delete_stream(&Identifier::numeric(STREAM_ID).unwrap())
+        .await
+        .unwrap();
+}
@@ -16,7 +16,10 @@
  * under the License.
  */
 
-use crate::server::scenarios::{delete_segments_scenario, message_size_scenario, tcp_tls_scenario};
+use crate::server::scenarios::{
+    delete_segments_scenario, http_messages_stream_scenario, kafka_scenario, message_size_scenario,
+    mqtt_scenario, shrink_partitions_scenario, tcp_tls_scenario, websocket_subscription_scenario,
+};
 use iggy::prelude::*;
 use integration::{
     tcp_client::TcpClientFactory,
@@ -25,6 +28,7 @@ use integration::{
 };
 use serial_test::parallel;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 // This test can run on any transport, but it requires both ClientFactory and
 // TestServer parameters, which doesn't fit the unified matrix approach.
@@ -143,3 +147,98 @@ async fn message_size_scenario() {
 
     message_size_scenario::run(&client_factory).await;
 }
+
+// Message subscriptions are available only for the WebSocket transport, which pushes the messages to the client.
+#[tokio::test]
+#[parallel]
+async fn websocket_subscription_scenario_should_be_valid() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_http_api_addr().unwrap();
+    let client = WebSocketClient::create(Arc::new(WebSocketClientConfig {
+        server_url: format!("ws://{server_addr}/ws"),
+        ..WebSocketClientConfig::default()
+    }))
+    .unwrap();
+    client.connect().await.unwrap();
+
+    websocket_subscription_scenario::run(&client).await;
+}
+
+// Streaming the messages as Server-Sent Events is available only for the HTTP transport.
+#[tokio::test]
+#[parallel]
+async fn http_messages_stream_scenario_should_be_valid() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_http_api_addr().unwrap();
+
+    http_messages_stream_scenario::run(&server_addr).await;
+}
+
+// The Kafka wire protocol is served by the dedicated listener, disabled by default.
+#[tokio::test]
+#[parallel]
+async fn kafka_scenario_should_be_valid() {
+    let mut test_server = TestServer::new(
+        Some(HashMap::from([
+            ("IGGY_KAFKA_ENABLED".to_string(), "true".to_string()),
+            ("IGGY_KAFKA_ADDRESS".to_string(), "127.0.0.1:0".to_string()),
+        ])),
+        true,
+        None,
+        IpAddrKind::V4,
+    );
+    test_server.start();
+    let tcp_server_addr = test_server.get_raw_tcp_addr().unwrap();
+    let kafka_server_addr = test_server.get_kafka_addr().unwrap();
+
+    kafka_scenario::run(&tcp_server_addr, &kafka_server_addr).await;
+}
+
+// The MQTT gateway is served by the dedicated listener, disabled by default.
+#[tokio::test]
+#[parallel]
+async fn mqtt_scenario_should_be_valid() {
+    let mut test_server = TestServer::new(
+        Some(HashMap::from([
+            ("IGGY_MQTT_ENABLED".to_string(), "true".to_string()),
+            ("IGGY_MQTT_ADDRESS".to_string(), "127.0.0.1:0".to_string()),
+            (
+                "IGGY_MQTT_MAPPINGS".to_string(),
+                format!("[{}=test-stream/test-topic]", mqtt_scenario::MAPPED_FILTER),
+            ),
+        ])),
+        true,
+        None,
+        IpAddrKind::V4,
+    );
+    test_server.start();
+    let tcp_server_addr = test_server.get_raw_tcp_addr().unwrap();
+    let mqtt_server_addr = test_server.get_mqtt_addr().unwrap();
+
+    mqtt_scenario::run(&tcp_server_addr, &mqtt_server_addr).await;
+}
+
+// The draining partitions are deleted by the background job, so its interval is shortened.
+#[tokio::test]
+#[parallel]
+async fn shrink_partitions_scenario_should_be_valid() {
+    let mut test_server = TestServer::new(
+        Some(HashMap::from([(
+            "IGGY_DATA_MAINTENANCE_PARTITIONS_INTERVAL".to_string(),
+            "1 s".to_string(),
+        )])),
+        true,
+        None,
+        IpAddrKind::V4,
+    );
+    test_server.start();
+    let server_addr = test_server.get_raw_tcp_addr().unwrap();
+    let client_factory = TcpClientFactory {
+        server_addr,
+        ..Default::default()
+    };
+
+    shrink_partitions_scenario::run(&client_factory).await;
+}
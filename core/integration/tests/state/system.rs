@@ -17,19 +17,21 @@
  */
 
 use crate::state::StateSetup;
-use iggy::prelude::IggyExpiry;
+use iggy::prelude::{IggyExpiry, TopicOverrides};
 use iggy_common::create_consumer_group::CreateConsumerGroup;
 use iggy_common::create_partitions::CreatePartitions;
 use iggy_common::create_personal_access_token::CreatePersonalAccessToken;
 use iggy_common::create_stream::CreateStream;
 use iggy_common::create_topic::CreateTopic;
 use iggy_common::create_user::CreateUser;
+use iggy_common::delete_partitions::DeletePartitions;
 use iggy_common::delete_stream::DeleteStream;
+use iggy_common::shrink_partitions::ShrinkPartitions;
 use server::state::State;
 use server::state::command::EntryCommand;
 use server::state::models::{
     CreateConsumerGroupWithId, CreatePersonalAccessTokenWithHash, CreateStreamWithId,
-    CreateTopicWithId, CreateUserWithId,
+    CreateTopicWithId, CreateUserWithId, DeleteDrainedPartitions,
 };
 use server::state::system::SystemState;
 
@@ -74,6 +76,11 @@ async fn should_be_initialized_based_on_state_entries() {
         max_topic_size: Default::default(),
         name: "topic1".to_string(),
         replication_factor: None,
+        overrides: TopicOverrides {
+            enforce_fsync: Some(true),
+            messages_required_to_save: Some(32),
+            ..Default::default()
+        },
     };
 
     let create_topic1_clone = CreateTopic {
@@ -85,6 +92,11 @@ async fn should_be_initialized_based_on_state_entries() {
         max_topic_size: Default::default(),
         name: "topic1".to_string(),
         replication_factor: None,
+        overrides: TopicOverrides {
+            enforce_fsync: Some(true),
+            messages_required_to_save: Some(32),
+            ..Default::default()
+        },
     };
 
     let stream2_id = 2;
@@ -103,6 +115,7 @@ async fn should_be_initialized_based_on_state_entries() {
         max_topic_size: Default::default(),
         name: "topic2".to_string(),
         replication_factor: None,
+        overrides: Default::default(),
     };
 
     let create_partitions = CreatePartitions {
@@ -262,6 +275,7 @@ async fn should_be_initialized_based_on_state_entries() {
         .unwrap();
     assert_eq!(topic.id, create_topic1_clone.topic_id.unwrap());
     assert_eq!(topic.name, create_topic1_clone.name);
+    assert_eq!(topic.overrides, create_topic1_clone.overrides);
     assert_eq!(topic.partitions.len(), 3);
 
     assert_eq!(topic.consumer_groups.len(), 1);
@@ -273,3 +287,90 @@ async fn should_be_initialized_based_on_state_entries() {
     );
     assert_eq!(consumer_group.name, create_consumer_group_clone.name);
 }
+
+#[tokio::test]
+async fn drained_partitions_should_be_deleted_once_given_repeated_state_entries() {
+    let setup = StateSetup::init().await;
+    let state = setup.state();
+    state.init().await.unwrap();
+
+    let user_id = 1;
+    let stream_id = 1;
+    let topic_id = 1;
+    state
+        .apply(
+            user_id,
+            &EntryCommand::CreateStream(CreateStreamWithId {
+                stream_id,
+                command: CreateStream {
+                    stream_id: Some(stream_id),
+                    name: "stream".to_string(),
+                },
+            }),
+        )
+        .await
+        .unwrap();
+    state
+        .apply(
+            user_id,
+            &EntryCommand::CreateTopic(CreateTopicWithId {
+                topic_id,
+                command: CreateTopic {
+                    stream_id: stream_id.try_into().unwrap(),
+                    topic_id: Some(topic_id),
+                    partitions_count: 5,
+                    compression_algorithm: Default::default(),
+                    message_expiry: Default::default(),
+                    max_topic_size: Default::default(),
+                    name: "topic".to_string(),
+                    replication_factor: None,
+                    overrides: Default::default(),
+                },
+            }),
+        )
+        .await
+        .unwrap();
+    state
+        .apply(
+            user_id,
+            &EntryCommand::ShrinkPartitions(ShrinkPartitions {
+                stream_id: stream_id.try_into().unwrap(),
+                topic_id: topic_id.try_into().unwrap(),
+                partitions_count: 2,
+                copy_unconsumed: false,
+                key_header: None,
+                consistent_hashing: false,
+            }),
+        )
+        .await
+        .unwrap();
+
+    // The same entry is applied again when the deletion of the partitions failed and was retried.
+    for _ in 0..2 {
+        state
+            .apply(
+                user_id,
+                &EntryCommand::DeleteDrainedPartitions(DeleteDrainedPartitions {
+                    command: DeletePartitions {
+                        stream_id: stream_id.try_into().unwrap(),
+                        topic_id: topic_id.try_into().unwrap(),
+                        partitions_count: 2,
+                    },
+                    partition_ids: vec![5, 4],
+                }),
+            )
+            .await
+            .unwrap();
+    }
+
+    let entries = state.load_entries().await.unwrap();
+    assert_eq!(entries.len(), 5);
+
+    let mut system = SystemState::init(entries).await.unwrap();
+    let mut stream = system.streams.remove(&stream_id).unwrap();
+    let topic = stream.topics.remove(&topic_id).unwrap();
+    assert_eq!(topic.partitions.len(), 3);
+    assert!(topic.partitions.contains_key(&1));
+    assert!(topic.partitions.contains_key(&3));
+    assert_eq!(topic.draining_partitions_count, 0);
+}
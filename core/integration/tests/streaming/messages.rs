@@ -130,6 +130,7 @@ async fn should_persist_messages_and_then_load_them_from_disk() {
     .await;
     let partition_state = PartitionState {
         id: partition.partition_id,
+        directory: None,
         created_at: now,
     };
     loaded_partition.load(partition_state).await.unwrap();
@@ -105,6 +105,7 @@ async fn should_load_existing_partition_from_disk() {
         .await;
         let partition_state = PartitionState {
             id: partition.partition_id,
+            directory: None,
             created_at: now,
         };
         loaded_partition.load(partition_state).await.unwrap();
@@ -460,6 +460,130 @@ async fn given_at_least_one_not_expired_message_segment_should_not_be_expired()
     );
 }
 
+#[tokio::test]
+async fn given_all_messages_with_expired_ttl_segment_should_be_expired() {
+    let config = SystemConfig {
+        partition: PartitionConfig {
+            enforce_fsync: true,
+            ..Default::default()
+        },
+        segment: SegmentConfig {
+            size: IggyByteSize::from_str("10B").unwrap(), // small size to force expiration
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let setup = TestSetup::init_with_config(config).await;
+    let stream_id = 1;
+    let topic_id = 2;
+    let partition_id = 3;
+    let start_offset = 0;
+    let message_ttl_us = 100000;
+    let mut segment = Segment::create(
+        stream_id,
+        topic_id,
+        partition_id,
+        start_offset,
+        setup.config.clone(),
+        IggyExpiry::NeverExpire,
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        true,
+    );
+
+    setup
+        .create_partition_directory(stream_id, topic_id, partition_id)
+        .await;
+    segment.persist().await.unwrap();
+    let messages_count = 10;
+    let mut messages = Vec::new();
+    let mut messages_size = 0;
+    for i in 0..messages_count {
+        let message = IggyMessage::builder()
+            .id(i as u128)
+            .payload(Bytes::from("test"))
+            .ttl(IggyDuration::from(message_ttl_us))
+            .build()
+            .expect("Failed to create message");
+        messages_size += message.get_size_bytes().as_bytes_u32();
+        messages.push(message);
+    }
+    let batch = IggyMessagesBatchMut::from_messages(&messages, messages_size);
+    segment.append_batch(0, batch, None).await.unwrap();
+    segment.persist_messages(None).await.unwrap();
+    let not_expired_ts = IggyTimestamp::now();
+    let expired_ts = not_expired_ts + IggyDuration::from(message_ttl_us + 1);
+
+    assert!(!segment.is_expired(not_expired_ts).await);
+    assert!(segment.is_expired(expired_ts).await);
+}
+
+#[tokio::test]
+async fn given_at_least_one_message_without_ttl_segment_should_not_be_expired() {
+    let config = SystemConfig {
+        partition: PartitionConfig {
+            enforce_fsync: true,
+            ..Default::default()
+        },
+        segment: SegmentConfig {
+            size: IggyByteSize::from_str("10B").unwrap(), // small size to force expiration
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let setup = TestSetup::init_with_config(config).await;
+    let stream_id = 1;
+    let topic_id = 2;
+    let partition_id = 3;
+    let start_offset = 0;
+    let message_ttl_us = 100000;
+    let mut segment = Segment::create(
+        stream_id,
+        topic_id,
+        partition_id,
+        start_offset,
+        setup.config.clone(),
+        IggyExpiry::NeverExpire,
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        true,
+    );
+
+    setup
+        .create_partition_directory(stream_id, topic_id, partition_id)
+        .await;
+    segment.persist().await.unwrap();
+    let messages = vec![
+        IggyMessage::builder()
+            .payload(Bytes::from("expiring"))
+            .ttl(IggyDuration::from(message_ttl_us))
+            .build()
+            .expect("Failed to create message"),
+        IggyMessage::builder()
+            .payload(Bytes::from("durable"))
+            .build()
+            .expect("Failed to create message"),
+    ];
+    let messages_size = messages
+        .iter()
+        .map(|message| message.get_size_bytes().as_bytes_u32())
+        .sum();
+    let batch = IggyMessagesBatchMut::from_messages(&messages, messages_size);
+    segment.append_batch(0, batch, None).await.unwrap();
+    segment.persist_messages(None).await.unwrap();
+    let expired_ts = IggyTimestamp::now() + IggyDuration::from(message_ttl_us + 1);
+
+    assert!(!segment.is_expired(expired_ts).await);
+}
+
 #[tokio::test]
 async fn should_delete_persisted_segments() -> Result<(), Box<dyn std::error::Error>> {
     let config = SystemConfig {
@@ -510,6 +634,7 @@ async fn should_delete_persisted_segments() -> Result<(), Box<dyn std::error::Er
             CompressionAlgorithm::default(),
             MaxTopicSize::default(),
             None,
+            TopicOverrides::default(),
         )
         .await?;
 
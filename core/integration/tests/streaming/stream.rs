@@ -131,6 +131,7 @@ async fn should_purge_existing_stream_on_disk() {
                 Default::default(),
                 MaxTopicSize::ServerDefault,
                 1,
+                TopicOverrides::default(),
             )
             .await
             .unwrap();
@@ -146,7 +147,7 @@ async fn should_purge_existing_stream_on_disk() {
             .get_topic(&Identifier::numeric(topic_id).unwrap())
             .unwrap();
         topic
-            .append_messages(&Partitioning::partition_id(1), batch, None)
+            .append_messages(&Partitioning::partition_id(1), batch, None, None)
             .await
             .unwrap();
         let (_, loaded_messages) = topic
@@ -52,6 +52,7 @@ async fn should_persist_topics_with_partitions_directories_and_info_file() {
             CompressionAlgorithm::default(),
             MaxTopicSize::ServerDefault,
             1,
+            TopicOverrides::default(),
         )
         .await
         .unwrap();
@@ -90,6 +91,7 @@ async fn should_load_existing_topic_from_disk() {
             CompressionAlgorithm::default(),
             MaxTopicSize::ServerDefault,
             1,
+            TopicOverrides::default(),
         )
         .await
         .unwrap();
@@ -120,14 +122,25 @@ async fn should_load_existing_topic_from_disk() {
                 AHashMap::new()
             } else {
                 (1..=partitions_count)
-                    .map(|id| (id, PartitionState { id, created_at }))
+                    .map(|id| {
+                        (
+                            id,
+                            PartitionState {
+                                id,
+                                directory: None,
+                                created_at,
+                            },
+                        )
+                    })
                     .collect()
             },
+            draining_partitions_count: 0,
             consumer_groups: Default::default(),
             compression_algorithm: Default::default(),
             message_expiry: IggyExpiry::NeverExpire,
             max_topic_size: MaxTopicSize::ServerDefault,
             replication_factor: Some(1),
+            overrides: Default::default(),
             created_at: Default::default(),
         };
         loaded_topic.load(topic_state).await.unwrap();
@@ -167,6 +180,7 @@ async fn should_delete_existing_topic_from_disk() {
             CompressionAlgorithm::default(),
             MaxTopicSize::ServerDefault,
             1,
+            TopicOverrides::default(),
         )
         .await
         .unwrap();
@@ -207,6 +221,7 @@ async fn should_purge_existing_topic_on_disk() {
             CompressionAlgorithm::default(),
             MaxTopicSize::ServerDefault,
             1,
+            TopicOverrides::default(),
         )
         .await
         .unwrap();
@@ -226,7 +241,7 @@ async fn should_purge_existing_topic_on_disk() {
             .sum::<u32>();
         let batch = IggyMessagesBatchMut::from_messages(&messages, batch_size);
         topic
-            .append_messages(&Partitioning::partition_id(1), batch, None)
+            .append_messages(&Partitioning::partition_id(1), batch, None, None)
             .await
             .unwrap();
         let (_, loaded_messages) = topic
@@ -63,7 +63,7 @@ async fn assert_polling_messages() {
         .sum::<IggyByteSize>();
     let batch = IggyMessagesBatchMut::from_messages(&messages, batch_size.as_bytes_u32());
     topic
-        .append_messages(&partitioning, batch, None)
+        .append_messages(&partitioning, batch, None, None)
         .await
         .unwrap();
 
@@ -110,7 +110,7 @@ async fn given_key_none_messages_should_be_appended_to_the_next_partition_using_
             batch_size.as_bytes_u32(),
         );
         topic
-            .append_messages(&partitioning, messages, None)
+            .append_messages(&partitioning, messages, None, None)
             .await
             .unwrap();
     }
@@ -139,7 +139,7 @@ async fn given_key_partition_id_messages_should_be_appended_to_the_chosen_partit
             batch_size.as_bytes_u32(),
         );
         topic
-            .append_messages(&partitioning, messages, None)
+            .append_messages(&partitioning, messages, None, None)
             .await
             .unwrap();
     }
@@ -172,7 +172,7 @@ async fn given_key_messages_key_messages_should_be_appended_to_the_calculated_pa
             batch_size.as_bytes_u32(),
         );
         topic
-            .append_messages(&partitioning, messages, None)
+            .append_messages(&partitioning, messages, None, None)
             .await
             .unwrap();
     }
@@ -229,6 +229,7 @@ async fn init_topic(setup: &TestSetup, partitions_count: u32) -> Topic {
         Default::default(),
         MaxTopicSize::ServerDefault,
         1,
+        TopicOverrides::default(),
     )
     .await
     .unwrap();
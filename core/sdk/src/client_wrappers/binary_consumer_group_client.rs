@@ -51,6 +51,11 @@ impl ConsumerGroupClient for ClientWrapper {
                     .get_consumer_group(stream_id, topic_id, group_id)
                     .await
             }
+            ClientWrapper::WebSocket(client) => {
+                client
+                    .get_consumer_group(stream_id, topic_id, group_id)
+                    .await
+            }
         }
     }
 
@@ -64,6 +69,9 @@ impl ConsumerGroupClient for ClientWrapper {
             ClientWrapper::Http(client) => client.get_consumer_groups(stream_id, topic_id).await,
             ClientWrapper::Tcp(client) => client.get_consumer_groups(stream_id, topic_id).await,
             ClientWrapper::Quic(client) => client.get_consumer_groups(stream_id, topic_id).await,
+            ClientWrapper::WebSocket(client) => {
+                client.get_consumer_groups(stream_id, topic_id).await
+            }
         }
     }
 
@@ -95,6 +103,11 @@ impl ConsumerGroupClient for ClientWrapper {
                     .create_consumer_group(stream_id, topic_id, name, group_id)
                     .await
             }
+            ClientWrapper::WebSocket(client) => {
+                client
+                    .create_consumer_group(stream_id, topic_id, name, group_id)
+                    .await
+            }
         }
     }
 
@@ -125,6 +138,11 @@ impl ConsumerGroupClient for ClientWrapper {
                     .delete_consumer_group(stream_id, topic_id, group_id)
                     .await
             }
+            ClientWrapper::WebSocket(client) => {
+                client
+                    .delete_consumer_group(stream_id, topic_id, group_id)
+                    .await
+            }
         }
     }
 
@@ -155,6 +173,11 @@ impl ConsumerGroupClient for ClientWrapper {
                     .join_consumer_group(stream_id, topic_id, group_id)
                     .await
             }
+            ClientWrapper::WebSocket(client) => {
+                client
+                    .join_consumer_group(stream_id, topic_id, group_id)
+                    .await
+            }
         }
     }
 
@@ -185,6 +208,11 @@ impl ConsumerGroupClient for ClientWrapper {
                     .leave_consumer_group(stream_id, topic_id, group_id)
                     .await
             }
+            ClientWrapper::WebSocket(client) => {
+                client
+                    .leave_consumer_group(stream_id, topic_id, group_id)
+                    .await
+            }
         }
     }
 }
@@ -205,6 +233,9 @@ impl AsyncDrop for ClientWrapper {
             ClientWrapper::Quic(client) => {
                 let _ = client.logout_user().await;
             }
+            ClientWrapper::WebSocket(client) => {
+                let _ = client.logout_user().await;
+            }
         }
     }
 }
@@ -52,6 +52,11 @@ impl ConsumerOffsetClient for ClientWrapper {
                     .store_consumer_offset(consumer, stream_id, topic_id, partition_id, offset)
                     .await
             }
+            ClientWrapper::WebSocket(client) => {
+                client
+                    .store_consumer_offset(consumer, stream_id, topic_id, partition_id, offset)
+                    .await
+            }
         }
     }
 
@@ -83,6 +88,11 @@ impl ConsumerOffsetClient for ClientWrapper {
                     .get_consumer_offset(consumer, stream_id, topic_id, partition_id)
                     .await
             }
+            ClientWrapper::WebSocket(client) => {
+                client
+                    .get_consumer_offset(consumer, stream_id, topic_id, partition_id)
+                    .await
+            }
         }
     }
 
@@ -114,6 +124,11 @@ impl ConsumerOffsetClient for ClientWrapper {
                     .delete_consumer_offset(consumer, stream_id, topic_id, partition_id)
                     .await
             }
+            ClientWrapper::WebSocket(client) => {
+                client
+                    .delete_consumer_offset(consumer, stream_id, topic_id, partition_id)
+                    .await
+            }
         }
     }
 }
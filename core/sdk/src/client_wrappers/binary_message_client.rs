@@ -20,7 +20,8 @@ use crate::client_wrappers::client_wrapper::ClientWrapper;
 use async_trait::async_trait;
 use iggy_binary_protocol::MessageClient;
 use iggy_common::{
-    Consumer, Identifier, IggyError, IggyMessage, Partitioning, PolledMessages, PollingStrategy,
+    Consumer, Identifier, IggyDuration, IggyError, IggyMessage, Partitioning, PolledMessages,
+    PollingStrategy, ProducerSequence,
 };
 
 #[async_trait]
@@ -88,6 +89,104 @@ impl MessageClient for ClientWrapper {
                     )
                     .await
             }
+            ClientWrapper::WebSocket(client) => {
+                client
+                    .poll_messages(
+                        stream_id,
+                        topic_id,
+                        partition_id,
+                        consumer,
+                        strategy,
+                        count,
+                        auto_commit,
+                    )
+                    .await
+            }
+        }
+    }
+
+    async fn long_poll_messages(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: Option<u32>,
+        consumer: &Consumer,
+        strategy: &PollingStrategy,
+        count: u32,
+        auto_commit: bool,
+        timeout: &IggyDuration,
+    ) -> Result<PolledMessages, IggyError> {
+        match self {
+            ClientWrapper::Iggy(client) => {
+                client
+                    .long_poll_messages(
+                        stream_id,
+                        topic_id,
+                        partition_id,
+                        consumer,
+                        strategy,
+                        count,
+                        auto_commit,
+                        timeout,
+                    )
+                    .await
+            }
+            ClientWrapper::Http(client) => {
+                client
+                    .long_poll_messages(
+                        stream_id,
+                        topic_id,
+                        partition_id,
+                        consumer,
+                        strategy,
+                        count,
+                        auto_commit,
+                        timeout,
+                    )
+                    .await
+            }
+            ClientWrapper::Tcp(client) => {
+                client
+                    .long_poll_messages(
+                        stream_id,
+                        topic_id,
+                        partition_id,
+                        consumer,
+                        strategy,
+                        count,
+                        auto_commit,
+                        timeout,
+                    )
+                    .await
+            }
+            ClientWrapper::Quic(client) => {
+                client
+                    .long_poll_messages(
+                        stream_id,
+                        topic_id,
+                        partition_id,
+                        consumer,
+                        strategy,
+                        count,
+                        auto_commit,
+                        timeout,
+                    )
+                    .await
+            }
+            ClientWrapper::WebSocket(client) => {
+                client
+                    .long_poll_messages(
+                        stream_id,
+                        topic_id,
+                        partition_id,
+                        consumer,
+                        strategy,
+                        count,
+                        auto_commit,
+                        timeout,
+                    )
+                    .await
+            }
         }
     }
 
@@ -119,6 +218,78 @@ impl MessageClient for ClientWrapper {
                     .send_messages(stream_id, topic_id, partitioning, messages)
                     .await
             }
+            ClientWrapper::WebSocket(client) => {
+                client
+                    .send_messages(stream_id, topic_id, partitioning, messages)
+                    .await
+            }
+        }
+    }
+
+    async fn send_messages_with_sequence(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: u32,
+        producer_sequence: &ProducerSequence,
+        messages: &mut [IggyMessage],
+    ) -> Result<(), IggyError> {
+        match self {
+            ClientWrapper::Iggy(client) => {
+                client
+                    .send_messages_with_sequence(
+                        stream_id,
+                        topic_id,
+                        partition_id,
+                        producer_sequence,
+                        messages,
+                    )
+                    .await
+            }
+            ClientWrapper::Http(client) => {
+                client
+                    .send_messages_with_sequence(
+                        stream_id,
+                        topic_id,
+                        partition_id,
+                        producer_sequence,
+                        messages,
+                    )
+                    .await
+            }
+            ClientWrapper::Tcp(client) => {
+                client
+                    .send_messages_with_sequence(
+                        stream_id,
+                        topic_id,
+                        partition_id,
+                        producer_sequence,
+                        messages,
+                    )
+                    .await
+            }
+            ClientWrapper::Quic(client) => {
+                client
+                    .send_messages_with_sequence(
+                        stream_id,
+                        topic_id,
+                        partition_id,
+                        producer_sequence,
+                        messages,
+                    )
+                    .await
+            }
+            ClientWrapper::WebSocket(client) => {
+                client
+                    .send_messages_with_sequence(
+                        stream_id,
+                        topic_id,
+                        partition_id,
+                        producer_sequence,
+                        messages,
+                    )
+                    .await
+            }
         }
     }
 
@@ -150,6 +321,11 @@ impl MessageClient for ClientWrapper {
                     .flush_unsaved_buffer(stream_id, topic_id, partitioning_id, fsync)
                     .await
             }
+            ClientWrapper::WebSocket(client) => {
+                client
+                    .flush_unsaved_buffer(stream_id, topic_id, partitioning_id, fsync)
+                    .await
+            }
         }
     }
 }
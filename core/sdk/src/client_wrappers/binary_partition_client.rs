@@ -50,6 +50,11 @@ impl PartitionClient for ClientWrapper {
                     .create_partitions(stream_id, topic_id, partitions_count)
                     .await
             }
+            ClientWrapper::WebSocket(client) => {
+                client
+                    .create_partitions(stream_id, topic_id, partitions_count)
+                    .await
+            }
         }
     }
 
@@ -80,6 +85,120 @@ impl PartitionClient for ClientWrapper {
                     .delete_partitions(stream_id, topic_id, partitions_count)
                     .await
             }
+            ClientWrapper::WebSocket(client) => {
+                client
+                    .delete_partitions(stream_id, topic_id, partitions_count)
+                    .await
+            }
+        }
+    }
+
+    async fn move_partition(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: u32,
+        directory: &str,
+    ) -> Result<(), IggyError> {
+        match self {
+            ClientWrapper::Iggy(client) => {
+                client
+                    .move_partition(stream_id, topic_id, partition_id, directory)
+                    .await
+            }
+            ClientWrapper::Http(client) => {
+                client
+                    .move_partition(stream_id, topic_id, partition_id, directory)
+                    .await
+            }
+            ClientWrapper::Tcp(client) => {
+                client
+                    .move_partition(stream_id, topic_id, partition_id, directory)
+                    .await
+            }
+            ClientWrapper::Quic(client) => {
+                client
+                    .move_partition(stream_id, topic_id, partition_id, directory)
+                    .await
+            }
+            ClientWrapper::WebSocket(client) => {
+                client
+                    .move_partition(stream_id, topic_id, partition_id, directory)
+                    .await
+            }
+        }
+    }
+
+    async fn shrink_partitions(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partitions_count: u32,
+        copy_unconsumed: bool,
+        key_header: Option<&str>,
+        consistent_hashing: bool,
+    ) -> Result<(), IggyError> {
+        match self {
+            ClientWrapper::Iggy(client) => {
+                client
+                    .shrink_partitions(
+                        stream_id,
+                        topic_id,
+                        partitions_count,
+                        copy_unconsumed,
+                        key_header,
+                        consistent_hashing,
+                    )
+                    .await
+            }
+            ClientWrapper::Http(client) => {
+                client
+                    .shrink_partitions(
+                        stream_id,
+                        topic_id,
+                        partitions_count,
+                        copy_unconsumed,
+                        key_header,
+                        consistent_hashing,
+                    )
+                    .await
+            }
+            ClientWrapper::Tcp(client) => {
+                client
+                    .shrink_partitions(
+                        stream_id,
+                        topic_id,
+                        partitions_count,
+                        copy_unconsumed,
+                        key_header,
+                        consistent_hashing,
+                    )
+                    .await
+            }
+            ClientWrapper::Quic(client) => {
+                client
+                    .shrink_partitions(
+                        stream_id,
+                        topic_id,
+                        partitions_count,
+                        copy_unconsumed,
+                        key_header,
+                        consistent_hashing,
+                    )
+                    .await
+            }
+            ClientWrapper::WebSocket(client) => {
+                client
+                    .shrink_partitions(
+                        stream_id,
+                        topic_id,
+                        partitions_count,
+                        copy_unconsumed,
+                        key_header,
+                        consistent_hashing,
+                    )
+                    .await
+            }
         }
     }
 }
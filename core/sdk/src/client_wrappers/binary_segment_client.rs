@@ -51,6 +51,11 @@ impl SegmentClient for ClientWrapper {
                     .delete_segments(stream_id, topic_id, partition_id, segments_count)
                     .await
             }
+            ClientWrapper::WebSocket(client) => {
+                client
+                    .delete_segments(stream_id, topic_id, partition_id, segments_count)
+                    .await
+            }
         }
     }
 }
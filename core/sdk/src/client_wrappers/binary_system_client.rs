@@ -20,8 +20,8 @@ use crate::client_wrappers::client_wrapper::ClientWrapper;
 use async_trait::async_trait;
 use iggy_binary_protocol::SystemClient;
 use iggy_common::{
-    ClientInfo, ClientInfoDetails, IggyDuration, IggyError, Snapshot, SnapshotCompression, Stats,
-    SystemSnapshotType,
+    ClientInfo, ClientInfoDetails, DataSnapshotTarget, IggyDuration, IggyError, Snapshot,
+    SnapshotCompression, Stats, SystemSnapshotType,
 };
 
 #[async_trait]
@@ -32,6 +32,7 @@ impl SystemClient for ClientWrapper {
             ClientWrapper::Http(client) => client.get_stats().await,
             ClientWrapper::Tcp(client) => client.get_stats().await,
             ClientWrapper::Quic(client) => client.get_stats().await,
+            ClientWrapper::WebSocket(client) => client.get_stats().await,
         }
     }
 
@@ -41,6 +42,7 @@ impl SystemClient for ClientWrapper {
             ClientWrapper::Http(client) => client.get_me().await,
             ClientWrapper::Tcp(client) => client.get_me().await,
             ClientWrapper::Quic(client) => client.get_me().await,
+            ClientWrapper::WebSocket(client) => client.get_me().await,
         }
     }
 
@@ -50,6 +52,7 @@ impl SystemClient for ClientWrapper {
             ClientWrapper::Http(client) => client.get_client(client_id).await,
             ClientWrapper::Tcp(client) => client.get_client(client_id).await,
             ClientWrapper::Quic(client) => client.get_client(client_id).await,
+            ClientWrapper::WebSocket(client) => client.get_client(client_id).await,
         }
     }
 
@@ -59,6 +62,7 @@ impl SystemClient for ClientWrapper {
             ClientWrapper::Http(client) => client.get_clients().await,
             ClientWrapper::Tcp(client) => client.get_clients().await,
             ClientWrapper::Quic(client) => client.get_clients().await,
+            ClientWrapper::WebSocket(client) => client.get_clients().await,
         }
     }
 
@@ -68,6 +72,7 @@ impl SystemClient for ClientWrapper {
             ClientWrapper::Http(client) => client.ping().await,
             ClientWrapper::Tcp(client) => client.ping().await,
             ClientWrapper::Quic(client) => client.ping().await,
+            ClientWrapper::WebSocket(client) => client.ping().await,
         }
     }
 
@@ -77,6 +82,7 @@ impl SystemClient for ClientWrapper {
             ClientWrapper::Http(client) => client.heartbeat_interval().await,
             ClientWrapper::Tcp(client) => client.heartbeat_interval().await,
             ClientWrapper::Quic(client) => client.heartbeat_interval().await,
+            ClientWrapper::WebSocket(client) => client.heartbeat_interval().await,
         }
     }
 
@@ -90,6 +96,27 @@ impl SystemClient for ClientWrapper {
             ClientWrapper::Http(client) => client.snapshot(compression, snapshot_types).await,
             ClientWrapper::Tcp(client) => client.snapshot(compression, snapshot_types).await,
             ClientWrapper::Quic(client) => client.snapshot(compression, snapshot_types).await,
+            ClientWrapper::WebSocket(client) => client.snapshot(compression, snapshot_types).await,
+        }
+    }
+
+    async fn data_snapshot(&self, targets: Vec<DataSnapshotTarget>) -> Result<Snapshot, IggyError> {
+        match self {
+            ClientWrapper::Iggy(client) => client.data_snapshot(targets).await,
+            ClientWrapper::Http(client) => client.data_snapshot(targets).await,
+            ClientWrapper::Tcp(client) => client.data_snapshot(targets).await,
+            ClientWrapper::Quic(client) => client.data_snapshot(targets).await,
+            ClientWrapper::WebSocket(client) => client.data_snapshot(targets).await,
+        }
+    }
+
+    async fn restore_data_snapshot(&self, snapshot: &Snapshot) -> Result<(), IggyError> {
+        match self {
+            ClientWrapper::Iggy(client) => client.restore_data_snapshot(snapshot).await,
+            ClientWrapper::Http(client) => client.restore_data_snapshot(snapshot).await,
+            ClientWrapper::Tcp(client) => client.restore_data_snapshot(snapshot).await,
+            ClientWrapper::Quic(client) => client.restore_data_snapshot(snapshot).await,
+            ClientWrapper::WebSocket(client) => client.restore_data_snapshot(snapshot).await,
         }
     }
 }
@@ -20,7 +20,8 @@ use crate::client_wrappers::client_wrapper::ClientWrapper;
 use async_trait::async_trait;
 use iggy_binary_protocol::TopicClient;
 use iggy_common::{
-    CompressionAlgorithm, Identifier, IggyError, IggyExpiry, MaxTopicSize, Topic, TopicDetails,
+    CompressionAlgorithm, Identifier, IggyDuration, IggyError, IggyExpiry, MaxTopicSize, Topic,
+    TopicDetails, TopicOverrides,
 };
 
 #[async_trait]
@@ -35,6 +36,7 @@ impl TopicClient for ClientWrapper {
             ClientWrapper::Http(client) => client.get_topic(stream_id, topic_id).await,
             ClientWrapper::Tcp(client) => client.get_topic(stream_id, topic_id).await,
             ClientWrapper::Quic(client) => client.get_topic(stream_id, topic_id).await,
+            ClientWrapper::WebSocket(client) => client.get_topic(stream_id, topic_id).await,
         }
     }
 
@@ -44,6 +46,7 @@ impl TopicClient for ClientWrapper {
             ClientWrapper::Http(client) => client.get_topics(stream_id).await,
             ClientWrapper::Tcp(client) => client.get_topics(stream_id).await,
             ClientWrapper::Quic(client) => client.get_topics(stream_id).await,
+            ClientWrapper::WebSocket(client) => client.get_topics(stream_id).await,
         }
     }
 
@@ -115,6 +118,111 @@ impl TopicClient for ClientWrapper {
                     )
                     .await
             }
+            ClientWrapper::WebSocket(client) => {
+                client
+                    .create_topic(
+                        stream_id,
+                        name,
+                        partitions_count,
+                        compression_algorithm,
+                        replication_factor,
+                        topic_id,
+                        message_expiry,
+                        max_topic_size,
+                    )
+                    .await
+            }
+        }
+    }
+
+    async fn create_topic_with_overrides(
+        &self,
+        stream_id: &Identifier,
+        name: &str,
+        partitions_count: u32,
+        compression_algorithm: CompressionAlgorithm,
+        replication_factor: Option<u8>,
+        topic_id: Option<u32>,
+        message_expiry: IggyExpiry,
+        max_topic_size: MaxTopicSize,
+        overrides: TopicOverrides,
+    ) -> Result<TopicDetails, IggyError> {
+        match self {
+            ClientWrapper::Iggy(client) => {
+                client
+                    .create_topic_with_overrides(
+                        stream_id,
+                        name,
+                        partitions_count,
+                        compression_algorithm,
+                        replication_factor,
+                        topic_id,
+                        message_expiry,
+                        max_topic_size,
+                        overrides,
+                    )
+                    .await
+            }
+            ClientWrapper::Http(client) => {
+                client
+                    .create_topic_with_overrides(
+                        stream_id,
+                        name,
+                        partitions_count,
+                        compression_algorithm,
+                        replication_factor,
+                        topic_id,
+                        message_expiry,
+                        max_topic_size,
+                        overrides,
+                    )
+                    .await
+            }
+            ClientWrapper::Tcp(client) => {
+                client
+                    .create_topic_with_overrides(
+                        stream_id,
+                        name,
+                        partitions_count,
+                        compression_algorithm,
+                        replication_factor,
+                        topic_id,
+                        message_expiry,
+                        max_topic_size,
+                        overrides,
+                    )
+                    .await
+            }
+            ClientWrapper::Quic(client) => {
+                client
+                    .create_topic_with_overrides(
+                        stream_id,
+                        name,
+                        partitions_count,
+                        compression_algorithm,
+                        replication_factor,
+                        topic_id,
+                        message_expiry,
+                        max_topic_size,
+                        overrides,
+                    )
+                    .await
+            }
+            ClientWrapper::WebSocket(client) => {
+                client
+                    .create_topic_with_overrides(
+                        stream_id,
+                        name,
+                        partitions_count,
+                        compression_algorithm,
+                        replication_factor,
+                        topic_id,
+                        message_expiry,
+                        max_topic_size,
+                        overrides,
+                    )
+                    .await
+            }
         }
     }
 
@@ -181,6 +289,104 @@ impl TopicClient for ClientWrapper {
                     )
                     .await
             }
+            ClientWrapper::WebSocket(client) => {
+                client
+                    .update_topic(
+                        stream_id,
+                        topic_id,
+                        name,
+                        compression_algorithm,
+                        replication_factor,
+                        message_expiry,
+                        max_topic_size,
+                    )
+                    .await
+            }
+        }
+    }
+
+    async fn update_topic_with_overrides(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        name: &str,
+        compression_algorithm: CompressionAlgorithm,
+        replication_factor: Option<u8>,
+        message_expiry: IggyExpiry,
+        max_topic_size: MaxTopicSize,
+        overrides: TopicOverrides,
+    ) -> Result<(), IggyError> {
+        match self {
+            ClientWrapper::Iggy(client) => {
+                client
+                    .update_topic_with_overrides(
+                        stream_id,
+                        topic_id,
+                        name,
+                        compression_algorithm,
+                        replication_factor,
+                        message_expiry,
+                        max_topic_size,
+                        overrides,
+                    )
+                    .await
+            }
+            ClientWrapper::Http(client) => {
+                client
+                    .update_topic_with_overrides(
+                        stream_id,
+                        topic_id,
+                        name,
+                        compression_algorithm,
+                        replication_factor,
+                        message_expiry,
+                        max_topic_size,
+                        overrides,
+                    )
+                    .await
+            }
+            ClientWrapper::Tcp(client) => {
+                client
+                    .update_topic_with_overrides(
+                        stream_id,
+                        topic_id,
+                        name,
+                        compression_algorithm,
+                        replication_factor,
+                        message_expiry,
+                        max_topic_size,
+                        overrides,
+                    )
+                    .await
+            }
+            ClientWrapper::Quic(client) => {
+                client
+                    .update_topic_with_overrides(
+                        stream_id,
+                        topic_id,
+                        name,
+                        compression_algorithm,
+                        replication_factor,
+                        message_expiry,
+                        max_topic_size,
+                        overrides,
+                    )
+                    .await
+            }
+            ClientWrapper::WebSocket(client) => {
+                client
+                    .update_topic_with_overrides(
+                        stream_id,
+                        topic_id,
+                        name,
+                        compression_algorithm,
+                        replication_factor,
+                        message_expiry,
+                        max_topic_size,
+                        overrides,
+                    )
+                    .await
+            }
         }
     }
 
@@ -194,6 +400,7 @@ impl TopicClient for ClientWrapper {
             ClientWrapper::Http(client) => client.delete_topic(stream_id, topic_id).await,
             ClientWrapper::Tcp(client) => client.delete_topic(stream_id, topic_id).await,
             ClientWrapper::Quic(client) => client.delete_topic(stream_id, topic_id).await,
+            ClientWrapper::WebSocket(client) => client.delete_topic(stream_id, topic_id).await,
         }
     }
 
@@ -207,6 +414,17 @@ impl TopicClient for ClientWrapper {
             ClientWrapper::Http(client) => client.purge_topic(stream_id, topic_id).await,
             ClientWrapper::Tcp(client) => client.purge_topic(stream_id, topic_id).await,
             ClientWrapper::Quic(client) => client.purge_topic(stream_id, topic_id).await,
+            ClientWrapper::WebSocket(client) => client.purge_topic(stream_id, topic_id).await,
+        }
+    }
+
+    async fn watch_topics(&self, version: u64, timeout: &IggyDuration) -> Result<u64, IggyError> {
+        match self {
+            ClientWrapper::Iggy(client) => client.watch_topics(version, timeout).await,
+            ClientWrapper::Http(client) => client.watch_topics(version, timeout).await,
+            ClientWrapper::Tcp(client) => client.watch_topics(version, timeout).await,
+            ClientWrapper::Quic(client) => client.watch_topics(version, timeout).await,
+            ClientWrapper::WebSocket(client) => client.watch_topics(version, timeout).await,
         }
     }
 }
@@ -22,7 +22,8 @@ use bytes::Bytes;
 use iggy_binary_protocol::MessageClient;
 use iggy_common::locking::IggySharedMutFn;
 use iggy_common::{
-    Consumer, Identifier, IggyError, IggyMessage, Partitioning, PolledMessages, PollingStrategy,
+    Consumer, Identifier, IggyDuration, IggyError, IggyMessage, Partitioning, PolledMessages,
+    PollingStrategy, ProducerSequence,
 };
 
 #[async_trait]
@@ -67,6 +68,48 @@ impl MessageClient for IggyClient {
         Ok(polled_messages)
     }
 
+    async fn long_poll_messages(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: Option<u32>,
+        consumer: &Consumer,
+        strategy: &PollingStrategy,
+        count: u32,
+        auto_commit: bool,
+        timeout: &IggyDuration,
+    ) -> Result<PolledMessages, IggyError> {
+        if count == 0 {
+            return Err(IggyError::InvalidMessagesCount);
+        }
+
+        let mut polled_messages = self
+            .client
+            .read()
+            .await
+            .long_poll_messages(
+                stream_id,
+                topic_id,
+                partition_id,
+                consumer,
+                strategy,
+                count,
+                auto_commit,
+                timeout,
+            )
+            .await?;
+
+        if let Some(ref encryptor) = self.encryptor {
+            for message in &mut polled_messages.messages {
+                let payload = encryptor.decrypt(&message.payload)?;
+                message.payload = Bytes::from(payload);
+                message.header.payload_length = message.payload.len() as u32;
+            }
+        }
+
+        Ok(polled_messages)
+    }
+
     async fn send_messages(
         &self,
         stream_id: &Identifier,
@@ -92,6 +135,38 @@ impl MessageClient for IggyClient {
             .await
     }
 
+    async fn send_messages_with_sequence(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: u32,
+        producer_sequence: &ProducerSequence,
+        messages: &mut [IggyMessage],
+    ) -> Result<(), IggyError> {
+        if messages.is_empty() {
+            return Err(IggyError::InvalidMessagesCount);
+        }
+
+        if let Some(encryptor) = &self.encryptor {
+            for message in &mut *messages {
+                message.payload = Bytes::from(encryptor.encrypt(&message.payload)?);
+                message.header.payload_length = message.payload.len() as u32;
+            }
+        }
+
+        self.client
+            .read()
+            .await
+            .send_messages_with_sequence(
+                stream_id,
+                topic_id,
+                partition_id,
+                producer_sequence,
+                messages,
+            )
+            .await
+    }
+
     async fn flush_unsaved_buffer(
         &self,
         stream_id: &Identifier,
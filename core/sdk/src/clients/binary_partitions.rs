@@ -49,4 +49,41 @@ impl PartitionClient for IggyClient {
             .delete_partitions(stream_id, topic_id, partitions_count)
             .await
     }
+
+    async fn move_partition(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: u32,
+        directory: &str,
+    ) -> Result<(), IggyError> {
+        self.client
+            .read()
+            .await
+            .move_partition(stream_id, topic_id, partition_id, directory)
+            .await
+    }
+
+    async fn shrink_partitions(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partitions_count: u32,
+        copy_unconsumed: bool,
+        key_header: Option<&str>,
+        consistent_hashing: bool,
+    ) -> Result<(), IggyError> {
+        self.client
+            .read()
+            .await
+            .shrink_partitions(
+                stream_id,
+                topic_id,
+                partitions_count,
+                copy_unconsumed,
+                key_header,
+                consistent_hashing,
+            )
+            .await
+    }
 }
@@ -21,8 +21,8 @@ use async_trait::async_trait;
 use iggy_binary_protocol::SystemClient;
 use iggy_common::locking::IggySharedMutFn;
 use iggy_common::{
-    ClientInfo, ClientInfoDetails, IggyDuration, IggyError, Snapshot, SnapshotCompression, Stats,
-    SystemSnapshotType,
+    ClientInfo, ClientInfoDetails, DataSnapshotTarget, IggyDuration, IggyError, Snapshot,
+    SnapshotCompression, Stats, SystemSnapshotType,
 };
 
 #[async_trait]
@@ -62,4 +62,16 @@ impl SystemClient for IggyClient {
             .snapshot(compression, snapshot_types)
             .await
     }
+
+    async fn data_snapshot(&self, targets: Vec<DataSnapshotTarget>) -> Result<Snapshot, IggyError> {
+        self.client.read().await.data_snapshot(targets).await
+    }
+
+    async fn restore_data_snapshot(&self, snapshot: &Snapshot) -> Result<(), IggyError> {
+        self.client
+            .read()
+            .await
+            .restore_data_snapshot(snapshot)
+            .await
+    }
 }
@@ -21,7 +21,8 @@ use async_trait::async_trait;
 use iggy_binary_protocol::TopicClient;
 use iggy_common::locking::IggySharedMutFn;
 use iggy_common::{
-    CompressionAlgorithm, Identifier, IggyError, IggyExpiry, MaxTopicSize, Topic, TopicDetails,
+    CompressionAlgorithm, Identifier, IggyDuration, IggyError, IggyExpiry, MaxTopicSize, Topic,
+    TopicDetails, TopicOverrides,
 };
 
 #[async_trait]
@@ -69,6 +70,35 @@ impl TopicClient for IggyClient {
             .await
     }
 
+    async fn create_topic_with_overrides(
+        &self,
+        stream_id: &Identifier,
+        name: &str,
+        partitions_count: u32,
+        compression_algorithm: CompressionAlgorithm,
+        replication_factor: Option<u8>,
+        topic_id: Option<u32>,
+        message_expiry: IggyExpiry,
+        max_topic_size: MaxTopicSize,
+        overrides: TopicOverrides,
+    ) -> Result<TopicDetails, IggyError> {
+        self.client
+            .read()
+            .await
+            .create_topic_with_overrides(
+                stream_id,
+                name,
+                partitions_count,
+                compression_algorithm,
+                replication_factor,
+                topic_id,
+                message_expiry,
+                max_topic_size,
+                overrides,
+            )
+            .await
+    }
+
     async fn update_topic(
         &self,
         stream_id: &Identifier,
@@ -94,6 +124,33 @@ impl TopicClient for IggyClient {
             .await
     }
 
+    async fn update_topic_with_overrides(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        name: &str,
+        compression_algorithm: CompressionAlgorithm,
+        replication_factor: Option<u8>,
+        message_expiry: IggyExpiry,
+        max_topic_size: MaxTopicSize,
+        overrides: TopicOverrides,
+    ) -> Result<(), IggyError> {
+        self.client
+            .read()
+            .await
+            .update_topic_with_overrides(
+                stream_id,
+                topic_id,
+                name,
+                compression_algorithm,
+                replication_factor,
+                message_expiry,
+                max_topic_size,
+                overrides,
+            )
+            .await
+    }
+
     async fn delete_topic(
         &self,
         stream_id: &Identifier,
@@ -117,4 +174,12 @@ impl TopicClient for IggyClient {
             .purge_topic(stream_id, topic_id)
             .await
     }
+
+    async fn watch_topics(&self, version: u64, timeout: &IggyDuration) -> Result<u64, IggyError> {
+        self.client
+            .read()
+            .await
+            .watch_topics(version, timeout)
+            .await
+    }
 }
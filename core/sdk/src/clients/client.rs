@@ -24,14 +24,16 @@ use crate::http::http_client::HttpClient;
 use crate::prelude::EncryptorKind;
 use crate::prelude::IggyConsumerBuilder;
 use crate::prelude::IggyError;
+use crate::prelude::IggyPatternConsumerBuilder;
 use crate::prelude::IggyProducerBuilder;
+use crate::prelude::NamePattern;
 use crate::quic::quick_client::QuicClient;
 use crate::tcp::tcp_client::TcpClient;
 use async_broadcast::Receiver;
 use async_trait::async_trait;
 use iggy_binary_protocol::{Client, SystemClient};
 use iggy_common::{
-    ConnectionStringUtils, Consumer, DiagnosticEvent, Partitioner, TransportProtocol,
+    ConnectionStringUtils, Consumer, DiagnosticEvent, Identifier, Partitioner, TransportProtocol,
 };
 use std::fmt::Debug;
 use std::sync::Arc;
@@ -159,6 +161,31 @@ impl IggyClient {
         ))
     }
 
+    /// Returns the builder for the consumer group subscribed to all the topics matching the stream and topic name patterns,
+    /// including the ones created after the consumer is initialized.
+    pub fn pattern_consumer_group(
+        &self,
+        name: &str,
+        streams: NamePattern,
+        topics: NamePattern,
+    ) -> Result<IggyPatternConsumerBuilder, IggyError> {
+        Ok(IggyPatternConsumerBuilder::new(
+            self.client.clone(),
+            streams,
+            topics,
+            IggyConsumerBuilder::new(
+                self.client.clone(),
+                name.to_owned(),
+                Consumer::group(name.try_into()?),
+                Identifier::default(),
+                Identifier::default(),
+                None,
+                self.encryptor.clone(),
+                None,
+            ),
+        ))
+    }
+
     /// Returns the builder for the producer.
     pub fn producer(&self, stream: &str, topic: &str) -> Result<IggyProducerBuilder, IggyError> {
         Ok(IggyProducerBuilder::new(
@@ -21,10 +21,11 @@ use crate::clients::client::IggyClient;
 use crate::http::http_client::HttpClient;
 use crate::prelude::{
     AutoLogin, EncryptorKind, HttpClientConfigBuilder, IggyDuration, IggyError, Partitioner,
-    QuicClientConfigBuilder, TcpClientConfigBuilder,
+    QuicClientConfigBuilder, TcpClientConfigBuilder, WebSocketClientConfigBuilder,
 };
 use crate::quic::quick_client::QuicClient;
 use crate::tcp::tcp_client::TcpClient;
+use crate::websocket::websocket_client::WebSocketClient;
 use iggy_common::{ConnectionStringUtils, TransportProtocol};
 use std::sync::Arc;
 use tracing::error;
@@ -116,10 +117,20 @@ impl IggyClientBuilder {
         }
     }
 
+    /// This method provides fluent API for the WebSocket client configuration.
+    /// It returns the `WebSocketClientBuilder` instance, which allows to configure the WebSocket client with custom settings or using defaults.
+    /// This should be called after the non-protocol specific methods, such as `with_partitioner`, `with_encryptor` or `with_message_handler`.
+    pub fn with_websocket(self) -> WebSocketClientBuilder {
+        WebSocketClientBuilder {
+            config: WebSocketClientConfigBuilder::default(),
+            parent_builder: self,
+        }
+    }
+
     /// Build the `IggyClient` instance.
     /// This method returns an error if the client is not provided.
     /// If the client is provided, it creates the `IggyClient` instance with the provided configuration.
-    /// To provide the client configuration, use the `with_tcp`, `with_quic` or `with_http` methods.
+    /// To provide the client configuration, use the `with_tcp`, `with_quic`, `with_http` or `with_websocket` methods.
     pub fn build(self) -> Result<IggyClient, IggyError> {
         let Some(client) = self.client else {
             error!("Client is not provided");
@@ -290,6 +301,48 @@ impl HttpClientBuilder {
     }
 }
 
+#[derive(Debug, Default)]
+pub struct WebSocketClientBuilder {
+    config: WebSocketClientConfigBuilder,
+    parent_builder: IggyClientBuilder,
+}
+
+impl WebSocketClientBuilder {
+    /// Sets the server URL for the WebSocket client, e.g. `ws://localhost:3000/ws`.
+    pub fn with_server_url(mut self, server_url: String) -> Self {
+        self.config = self.config.with_server_url(server_url);
+        self
+    }
+
+    /// Sets the JWT access token used to authenticate the WebSocket connection.
+    pub fn with_access_token(mut self, access_token: String) -> Self {
+        self.config = self.config.with_access_token(access_token);
+        self
+    }
+
+    /// Sets the auto sign in during connection.
+    pub fn with_auto_sign_in(mut self, auto_sign_in: AutoLogin) -> Self {
+        self.config = self.config.with_auto_sign_in(auto_sign_in);
+        self
+    }
+
+    /// Sets the heartbeat interval for the WebSocket client.
+    pub fn with_heartbeat_interval(mut self, interval: IggyDuration) -> Self {
+        self.config = self.config.with_heartbeat_interval(interval);
+        self
+    }
+
+    /// Builds the parent `IggyClient` with WebSocket configuration.
+    pub fn build(self) -> Result<IggyClient, IggyError> {
+        let client = WebSocketClient::create(Arc::new(self.config.build()))?;
+        let client = self
+            .parent_builder
+            .with_client(ClientWrapper::WebSocket(client))
+            .build()?;
+        Ok(client)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
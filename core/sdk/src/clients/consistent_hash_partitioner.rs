@@ -0,0 +1,158 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+use super::ORDERING;
+use iggy_common::{
+    HeaderKey, Identifier, IggyError, IggyMessage, Partitioner,
+    calculate_partition_id_by_consistent_hash,
+};
+use std::sync::atomic::AtomicU32;
+use twox_hash::XxHash32;
+
+/// The `Partitioner` calculating the partition ID on the client side in the same way as the server does
+/// for `Partitioning::consistent_messages_key`, using the value of the user header as the messages key.
+/// Growing the topic moves only a minimal fraction of the keys to the new partitions, preserving the per-key ordering for the rest.
+///
+/// The whole batch is sent to a single partition, so the messages key is read from the first message of the batch.
+/// The partitioner is not aware of the topic changes, so `set_partitions_count` must be called once the partitions count changes.
+#[derive(Debug)]
+pub struct ConsistentHashPartitioner {
+    key_header: HeaderKey,
+    partitions_count: AtomicU32,
+}
+
+impl ConsistentHashPartitioner {
+    /// Creates a new partitioner reading the messages key from the provided user header.
+    pub fn new(key_header: HeaderKey, partitions_count: u32) -> Self {
+        Self {
+            key_header,
+            partitions_count: AtomicU32::new(partitions_count),
+        }
+    }
+
+    /// Updates the partitions count of the topic.
+    pub fn set_partitions_count(&self, partitions_count: u32) {
+        self.partitions_count.store(partitions_count, ORDERING);
+    }
+
+    /// Returns the ID of the partition for the provided messages key.
+    pub fn calculate_partition_id_by_key(&self, messages_key: &[u8]) -> Result<u32, IggyError> {
+        let partitions_count = self.partitions_count.load(ORDERING);
+        if partitions_count == 0 {
+            return Err(IggyError::NoPartitions(0, 0));
+        }
+
+        let hash = XxHash32::oneshot(0, messages_key);
+        Ok(calculate_partition_id_by_consistent_hash(
+            hash as u64,
+            partitions_count,
+        ))
+    }
+}
+
+impl Partitioner for ConsistentHashPartitioner {
+    fn calculate_partition_id(
+        &self,
+        _stream_id: &Identifier,
+        _topic_id: &Identifier,
+        messages: &[IggyMessage],
+    ) -> Result<u32, IggyError> {
+        let message = messages.first().ok_or(IggyError::InvalidMessagesCount)?;
+        let messages_key = message
+            .get_user_header(&self.key_header)?
+            .ok_or(IggyError::InvalidHeaderKey)?;
+        self.calculate_partition_id_by_key(&messages_key.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::Bytes;
+    use iggy_common::HeaderValue;
+    use std::collections::HashMap;
+    use std::str::FromStr;
+
+    #[test]
+    fn partition_id_should_be_calculated_by_the_key_header() {
+        let partitioner = ConsistentHashPartitioner::new(HeaderKey::new("key").unwrap(), 3);
+        let stream_id = Identifier::numeric(1).unwrap();
+        let topic_id = Identifier::numeric(1).unwrap();
+        let message = IggyMessage::builder()
+            .payload(Bytes::from("test"))
+            .user_headers(HashMap::from([(
+                HeaderKey::new("key").unwrap(),
+                HeaderValue::from_str("tenant-1").unwrap(),
+            )]))
+            .build()
+            .unwrap();
+
+        let partition_id = partitioner
+            .calculate_partition_id(&stream_id, &topic_id, &[message])
+            .unwrap();
+        assert!((1..=3).contains(&partition_id));
+        assert_eq!(
+            partition_id,
+            partitioner
+                .calculate_partition_id_by_key(b"tenant-1")
+                .unwrap()
+        );
+
+        let message = IggyMessage::builder()
+            .payload(Bytes::from("test"))
+            .build()
+            .unwrap();
+        assert!(
+            partitioner
+                .calculate_partition_id(&stream_id, &topic_id, &[message])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn growing_partitions_should_keep_most_of_the_keys_in_the_same_partition() {
+        let partitioner = ConsistentHashPartitioner::new(HeaderKey::new("key").unwrap(), 4);
+        let keys = (0..1000)
+            .map(|key| format!("key-{key}"))
+            .collect::<Vec<_>>();
+        let partition_ids = keys
+            .iter()
+            .map(|key| {
+                partitioner
+                    .calculate_partition_id_by_key(key.as_bytes())
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        partitioner.set_partitions_count(5);
+        let moved_keys = keys
+            .iter()
+            .zip(partition_ids)
+            .map(|(key, partition_id)| {
+                (
+                    partitioner
+                        .calculate_partition_id_by_key(key.as_bytes())
+                        .unwrap(),
+                    partition_id,
+                )
+            })
+            .filter(|(new_partition_id, partition_id)| new_partition_id != partition_id)
+            .inspect(|(new_partition_id, _)| assert_eq!(*new_partition_id, 5))
+            .count();
+        assert!(moved_keys < keys.len() / 3);
+    }
+}
@@ -106,6 +106,8 @@ pub struct IggyConsumer {
     partition_id: Option<u32>,
     polling_strategy: PollingStrategy,
     poll_interval_micros: u64,
+    long_polling_timeout: Option<IggyDuration>,
+    long_polling_supported: Arc<AtomicBool>,
     batch_length: u32,
     auto_commit: AutoCommit,
     auto_commit_after_polling: bool,
@@ -139,6 +141,7 @@ impl IggyConsumer {
         topic_id: Identifier,
         partition_id: Option<u32>,
         polling_interval: Option<IggyDuration>,
+        long_polling_timeout: Option<IggyDuration>,
         polling_strategy: PollingStrategy,
         batch_length: u32,
         auto_commit: AutoCommit,
@@ -164,6 +167,8 @@ impl IggyConsumer {
             partition_id,
             polling_strategy,
             poll_interval_micros: polling_interval.map_or(0, |interval| interval.as_micros()),
+            long_polling_timeout,
+            long_polling_supported: Arc::new(AtomicBool::new(true)),
             last_stored_offsets: Arc::new(DashMap::new()),
             last_consumed_offsets: Arc::new(DashMap::new()),
             current_offsets: Arc::new(DashMap::new()),
@@ -630,6 +635,8 @@ impl IggyConsumer {
         let auto_commit_after_polling = self.auto_commit_after_polling;
         let auto_commit_enabled = self.auto_commit != AutoCommit::Disabled;
         let interval = self.poll_interval_micros;
+        let long_polling_timeout = self.long_polling_timeout;
+        let long_polling_supported = self.long_polling_supported.clone();
         let last_polled_at = self.last_polled_at.clone();
         let can_poll = self.can_poll.clone();
         let retry_interval = self.reconnection_retry_interval;
@@ -649,19 +656,21 @@ impl IggyConsumer {
 
             trace!("Sending poll messages request");
             last_polled_at.store(IggyTimestamp::now().into(), ORDERING);
-            let polled_messages = client
-                .read()
-                .await
-                .poll_messages(
-                    &stream_id,
-                    &topic_id,
-                    partition_id,
-                    &consumer,
-                    &polling_strategy,
-                    count,
-                    auto_commit_after_polling,
-                )
-                .await;
+            let long_polling_timeout =
+                long_polling_timeout.filter(|_| long_polling_supported.load(ORDERING));
+            let polled_messages = Self::poll_messages(
+                &client,
+                &stream_id,
+                &topic_id,
+                partition_id,
+                &consumer,
+                &polling_strategy,
+                count,
+                auto_commit_after_polling,
+                long_polling_timeout,
+                &long_polling_supported,
+            )
+            .await;
 
             if let Ok(mut polled_messages) = polled_messages {
                 if polled_messages.messages.is_empty() {
@@ -764,6 +773,56 @@ impl IggyConsumer {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
+    async fn poll_messages(
+        client: &IggySharedMut<ClientWrapper>,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: Option<u32>,
+        consumer: &Consumer,
+        polling_strategy: &PollingStrategy,
+        count: u32,
+        auto_commit: bool,
+        long_polling_timeout: Option<IggyDuration>,
+        long_polling_supported: &AtomicBool,
+    ) -> Result<PolledMessages, IggyError> {
+        let client = client.read().await;
+        if let Some(timeout) = long_polling_timeout {
+            let polled_messages = client
+                .long_poll_messages(
+                    stream_id,
+                    topic_id,
+                    partition_id,
+                    consumer,
+                    polling_strategy,
+                    count,
+                    auto_commit,
+                    &timeout,
+                )
+                .await;
+            if !matches!(polled_messages, Err(IggyError::FeatureUnavailable)) {
+                return polled_messages;
+            }
+
+            info!(
+                "Long polling is not available for the consumer of stream: {stream_id}, topic: {topic_id}, falling back to the regular polling."
+            );
+            long_polling_supported.store(false, ORDERING);
+        }
+
+        client
+            .poll_messages(
+                stream_id,
+                topic_id,
+                partition_id,
+                consumer,
+                polling_strategy,
+                count,
+                auto_commit,
+            )
+            .await
+    }
+
     async fn wait_before_polling(interval: u64, last_sent_at: u64) {
         if interval == 0 {
             return;
@@ -22,7 +22,7 @@ use iggy_common::locking::IggySharedMut;
 use iggy_common::{Consumer, EncryptorKind, Identifier, IggyDuration, PollingStrategy};
 use std::sync::Arc;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct IggyConsumerBuilder {
     client: IggySharedMut<ClientWrapper>,
     consumer_name: String,
@@ -32,6 +32,7 @@ pub struct IggyConsumerBuilder {
     partition: Option<u32>,
     polling_strategy: PollingStrategy,
     polling_interval: Option<IggyDuration>,
+    long_polling_timeout: Option<IggyDuration>,
     batch_length: u32,
     auto_commit: AutoCommit,
     auto_join_consumer_group: bool,
@@ -72,6 +73,7 @@ impl IggyConsumerBuilder {
             create_consumer_group_if_not_exists: true,
             encryptor,
             polling_interval,
+            long_polling_timeout: Some(IggyDuration::ONE_SECOND),
             polling_retry_interval: IggyDuration::ONE_SECOND,
             init_retries: None,
             init_retry_interval: IggyDuration::ONE_SECOND,
@@ -173,6 +175,25 @@ impl IggyConsumerBuilder {
         }
     }
 
+    /// Sets the maximum time the server may hold the poll request until the new messages are available, 1 second by default.
+    /// Long polling is used only by the binary transports (TCP, QUIC, WebSocket), otherwise the regular polling is used.
+    /// The polling interval is still respected, but as the server responds as soon as any messages are available,
+    /// there's no need to poll the idle topics frequently.
+    pub fn long_polling_timeout(self, timeout: IggyDuration) -> Self {
+        Self {
+            long_polling_timeout: Some(timeout),
+            ..self
+        }
+    }
+
+    /// Disables long polling, so that the messages are always polled using the polling interval.
+    pub fn without_long_polling(self) -> Self {
+        Self {
+            long_polling_timeout: None,
+            ..self
+        }
+    }
+
     /// Sets the encryptor for decrypting the messages' payloads.
     pub fn encryptor(self, encryptor: Arc<EncryptorKind>) -> Self {
         Self {
@@ -228,6 +249,7 @@ impl IggyConsumerBuilder {
             self.topic,
             self.partition,
             self.polling_interval,
+            self.long_polling_timeout,
             self.polling_strategy,
             self.batch_length,
             self.auto_commit,
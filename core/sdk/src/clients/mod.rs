@@ -28,13 +28,17 @@ mod binary_topics;
 mod binary_users;
 pub mod client;
 pub mod client_builder;
+pub mod consistent_hash_partitioner;
 pub mod consumer;
 pub mod consumer_builder;
+pub mod pattern_consumer;
+pub mod pattern_consumer_builder;
 pub mod producer;
 pub mod producer_builder;
 pub mod producer_config;
 pub mod producer_dispatcher;
 pub mod producer_error_callback;
+mod producer_idempotence;
 pub mod producer_sharding;
 
 const ORDERING: std::sync::atomic::Ordering = std::sync::atomic::Ordering::SeqCst;
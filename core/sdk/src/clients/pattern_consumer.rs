@@ -0,0 +1,382 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::client_wrappers::client_wrapper::ClientWrapper;
+use crate::prelude::{IggyConsumer, IggyConsumerBuilder, ReceivedMessage};
+use futures::Stream;
+use futures_util::StreamExt;
+use iggy_binary_protocol::{StreamClient, TopicClient};
+use iggy_common::locking::{IggySharedMut, IggySharedMutFn};
+use iggy_common::{Identifier, IggyDuration, IggyError};
+use regex::Regex;
+use std::collections::HashSet;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel};
+use tokio::task::JoinHandle;
+use tokio::time::sleep;
+use tracing::{info, warn};
+
+/// The topics version is only checked (without waiting on the server for the changes),
+/// as the connection is shared with the consumers of the topics, which would be blocked otherwise.
+const WATCH_TOPICS_TIMEOUT: IggyDuration = IggyDuration::new(Duration::ZERO);
+
+/// The pattern of the stream or topic names.
+#[derive(Debug, Clone)]
+pub enum NamePattern {
+    /// Matches only the exact name.
+    Exact(String),
+    /// Matches the names which entirely match the regular expression.
+    Regex(Regex),
+}
+
+impl NamePattern {
+    /// Matches only the exact name.
+    pub fn exact(name: &str) -> Self {
+        NamePattern::Exact(name.to_owned())
+    }
+
+    /// Matches any name.
+    pub fn any() -> Self {
+        NamePattern::Regex(Regex::new(".*").expect("Invalid regex"))
+    }
+
+    /// Matches the names using the glob pattern, in which `*` matches any sequence of characters and `?` any single character.
+    pub fn glob(pattern: &str) -> Result<Self, IggyError> {
+        let regex = regex::escape(pattern)
+            .replace(r"\*", ".*")
+            .replace(r"\?", ".");
+        Self::regex(&regex)
+    }
+
+    /// Matches the names which entirely match the regular expression.
+    pub fn regex(pattern: &str) -> Result<Self, IggyError> {
+        Regex::new(&format!("^(?:{pattern})$"))
+            .map(NamePattern::Regex)
+            .map_err(|_| IggyError::InvalidFormat)
+    }
+
+    pub fn matches(&self, name: &str) -> bool {
+        match self {
+            NamePattern::Exact(exact) => exact == name,
+            NamePattern::Regex(regex) => regex.is_match(name),
+        }
+    }
+}
+
+/// The message received by `IggyPatternConsumer` along with the names of its stream and topic.
+pub struct ReceivedTopicMessage {
+    pub stream: Arc<str>,
+    pub topic: Arc<str>,
+    pub message: ReceivedMessage,
+}
+
+struct Subscription {
+    stream: Arc<str>,
+    topic: Arc<str>,
+    consumer: IggyConsumer,
+}
+
+enum SubscriptionChange {
+    Subscribed(Box<Subscription>),
+    Unsubscribed(Arc<str>, Arc<str>),
+}
+
+/// The consumer group subscribed to all the topics matching the stream and topic name patterns,
+/// e.g. one topic per tenant. Each matching topic is consumed by its own `IggyConsumer`,
+/// and the messages of all of them are returned by the single stream in a round-robin fashion.
+///
+/// The topics created (or renamed) after the initialization are subscribed automatically,
+/// once the server reports the change of the topics version, while the deleted ones are unsubscribed.
+pub struct IggyPatternConsumer {
+    initialized: bool,
+    client: IggySharedMut<ClientWrapper>,
+    streams: NamePattern,
+    topics: NamePattern,
+    consumer: IggyConsumerBuilder,
+    discovery_interval: IggyDuration,
+    subscriptions: Vec<Subscription>,
+    next_subscription: usize,
+    changes: Option<UnboundedReceiver<SubscriptionChange>>,
+    discovery: Option<JoinHandle<()>>,
+}
+
+impl IggyPatternConsumer {
+    pub(crate) fn new(
+        client: IggySharedMut<ClientWrapper>,
+        streams: NamePattern,
+        topics: NamePattern,
+        consumer: IggyConsumerBuilder,
+        discovery_interval: IggyDuration,
+    ) -> Self {
+        Self {
+            initialized: false,
+            client,
+            streams,
+            topics,
+            consumer,
+            discovery_interval,
+            subscriptions: Vec::new(),
+            next_subscription: 0,
+            changes: None,
+            discovery: None,
+        }
+    }
+
+    /// Returns the `(stream, topic)` names of the currently subscribed topics.
+    pub fn subscribed_topics(&self) -> Vec<(&str, &str)> {
+        self.subscriptions
+            .iter()
+            .map(|subscription| (&*subscription.stream, &*subscription.topic))
+            .collect()
+    }
+
+    /// Returns the consumer of the subscribed topic, e.g. to store the offset manually.
+    pub fn consumer(&self, stream: &str, topic: &str) -> Option<&IggyConsumer> {
+        self.subscriptions
+            .iter()
+            .find(|subscription| &*subscription.stream == stream && &*subscription.topic == topic)
+            .map(|subscription| &subscription.consumer)
+    }
+
+    /// Initializes the consumers of all the currently matching topics, and starts watching the topics changes in the background.
+    ///
+    /// Note: This method must be called before polling messages.
+    pub async fn init(&mut self) -> Result<(), IggyError> {
+        if self.initialized {
+            return Ok(());
+        }
+
+        let mut discovery = TopicsDiscovery {
+            client: self.client.clone(),
+            streams: self.streams.clone(),
+            topics: self.topics.clone(),
+            consumer: self.consumer.clone(),
+            subscribed: HashSet::new(),
+            version: 0,
+        };
+        let (sender, mut receiver) = unbounded_channel();
+        discovery.refresh(&sender).await?;
+        while let Ok(change) = receiver.try_recv() {
+            self.apply(change);
+        }
+
+        info!(
+            "Pattern consumer has been initialized with {} subscribed topic(s).",
+            self.subscriptions.len()
+        );
+        self.discovery = Some(tokio::spawn(discovery.run(sender, self.discovery_interval)));
+        self.changes = Some(receiver);
+        self.initialized = true;
+        Ok(())
+    }
+
+    fn apply(&mut self, change: SubscriptionChange) {
+        match change {
+            SubscriptionChange::Subscribed(subscription) => {
+                info!(
+                    "Subscribed to topic: {} in stream: {}.",
+                    subscription.topic, subscription.stream
+                );
+                self.subscriptions.push(*subscription);
+            }
+            SubscriptionChange::Unsubscribed(stream, topic) => {
+                info!("Unsubscribed from topic: {topic} in stream: {stream}.");
+                self.subscriptions.retain(|subscription| {
+                    subscription.stream != stream || subscription.topic != topic
+                });
+            }
+        }
+    }
+}
+
+impl Drop for IggyPatternConsumer {
+    fn drop(&mut self) {
+        if let Some(discovery) = self.discovery.take() {
+            discovery.abort();
+        }
+    }
+}
+
+impl Stream for IggyPatternConsumer {
+    type Item = Result<ReceivedTopicMessage, IggyError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        if let Some(changes) = this.changes.as_mut() {
+            let mut pending_changes = Vec::new();
+            while let Poll::Ready(Some(change)) = changes.poll_recv(cx) {
+                pending_changes.push(change);
+            }
+            for change in pending_changes {
+                this.apply(change);
+            }
+        }
+
+        // Start from the subscription following the one which returned the last message, so that none of them is starved.
+        let subscriptions_count = this.subscriptions.len();
+        for _ in 0..subscriptions_count {
+            let index = this.next_subscription % subscriptions_count;
+            this.next_subscription = index + 1;
+            let subscription = &mut this.subscriptions[index];
+            if let Poll::Ready(Some(result)) = subscription.consumer.poll_next_unpin(cx) {
+                return Poll::Ready(Some(result.map(|message| ReceivedTopicMessage {
+                    stream: subscription.stream.clone(),
+                    topic: subscription.topic.clone(),
+                    message,
+                })));
+            }
+        }
+
+        Poll::Pending
+    }
+}
+
+struct TopicsDiscovery {
+    client: IggySharedMut<ClientWrapper>,
+    streams: NamePattern,
+    topics: NamePattern,
+    consumer: IggyConsumerBuilder,
+    subscribed: HashSet<(Arc<str>, Arc<str>)>,
+    version: u64,
+}
+
+impl TopicsDiscovery {
+    async fn run(mut self, sender: UnboundedSender<SubscriptionChange>, interval: IggyDuration) {
+        loop {
+            sleep(interval.get_duration()).await;
+            if sender.is_closed() {
+                return;
+            }
+
+            if let Err(error) = self.refresh(&sender).await {
+                warn!("Failed to refresh the subscribed topics. {error}");
+            }
+        }
+    }
+
+    /// Subscribes to the new matching topics and unsubscribes from the ones which no longer exist (or match),
+    /// as long as the topics version has changed or it's not supported by the transport.
+    async fn refresh(
+        &mut self,
+        sender: &UnboundedSender<SubscriptionChange>,
+    ) -> Result<(), IggyError> {
+        let client = self.client.read().await;
+        let version = match client
+            .watch_topics(self.version, &WATCH_TOPICS_TIMEOUT)
+            .await
+        {
+            Ok(version) if version == self.version => return Ok(()),
+            Ok(version) => version,
+            Err(IggyError::FeatureUnavailable) => self.version,
+            Err(error) => return Err(error),
+        };
+
+        let mut matching_topics = HashSet::new();
+        for stream in client.get_streams().await? {
+            if !self.streams.matches(&stream.name) {
+                continue;
+            }
+
+            let stream_name: Arc<str> = stream.name.into();
+            for topic in client.get_topics(&Identifier::numeric(stream.id)?).await? {
+                if self.topics.matches(&topic.name) {
+                    matching_topics.insert((stream_name.clone(), topic.name.into()));
+                }
+            }
+        }
+        // The consumers use the same client during the initialization.
+        drop(client);
+
+        for (stream, topic) in self.subscribed.difference(&matching_topics) {
+            _ = sender.send(SubscriptionChange::Unsubscribed(
+                stream.clone(),
+                topic.clone(),
+            ));
+        }
+        self.subscribed.retain(|key| matching_topics.contains(key));
+
+        let mut all_subscribed = true;
+        for (stream, topic) in matching_topics {
+            if self.subscribed.contains(&(stream.clone(), topic.clone())) {
+                continue;
+            }
+
+            let mut consumer = self
+                .consumer
+                .clone()
+                .stream(Identifier::named(&stream)?)
+                .topic(Identifier::named(&topic)?)
+                .build();
+            if let Err(error) = consumer.init().await {
+                // The version isn't updated, so that the subscription is retried on the next refresh.
+                warn!("Failed to subscribe to topic: {topic} in stream: {stream}. {error}");
+                all_subscribed = false;
+                continue;
+            }
+
+            self.subscribed.insert((stream.clone(), topic.clone()));
+            _ = sender.send(SubscriptionChange::Subscribed(Box::new(Subscription {
+                stream,
+                topic,
+                consumer,
+            })));
+        }
+
+        if all_subscribed {
+            self.version = version;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glob_pattern_should_match_entire_name() {
+        let pattern = NamePattern::glob("tenant-*").unwrap();
+        assert!(pattern.matches("tenant-1"));
+        assert!(pattern.matches("tenant-"));
+        assert!(!pattern.matches("my-tenant-1"));
+
+        let pattern = NamePattern::glob("tenant-?.events").unwrap();
+        assert!(pattern.matches("tenant-1.events"));
+        assert!(!pattern.matches("tenant-10.events"));
+        assert!(!pattern.matches("tenant-1xevents"));
+    }
+
+    #[test]
+    fn regex_pattern_should_match_entire_name() {
+        let pattern = NamePattern::regex(r"tenant-\d+|admin").unwrap();
+        assert!(pattern.matches("tenant-12"));
+        assert!(pattern.matches("admin"));
+        assert!(!pattern.matches("tenant-12-events"));
+        assert!(NamePattern::regex("tenant-(").is_err());
+    }
+
+    #[test]
+    fn exact_and_any_patterns_should_match() {
+        assert!(NamePattern::exact("tenant-*").matches("tenant-*"));
+        assert!(!NamePattern::exact("tenant-*").matches("tenant-1"));
+        assert!(NamePattern::any().matches("anything"));
+    }
+}
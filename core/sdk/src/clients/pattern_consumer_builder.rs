@@ -0,0 +1,116 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::client_wrappers::client_wrapper::ClientWrapper;
+use crate::prelude::{AutoCommit, IggyConsumerBuilder, IggyPatternConsumer, NamePattern};
+use iggy_common::locking::IggySharedMut;
+use iggy_common::{IggyDuration, PollingStrategy};
+
+/// The builder of the consumer group subscribed to all the topics matching the stream and topic name patterns.
+/// Each matching topic is consumed by its own `IggyConsumer`, built with the settings configured here.
+#[derive(Debug)]
+pub struct IggyPatternConsumerBuilder {
+    client: IggySharedMut<ClientWrapper>,
+    streams: NamePattern,
+    topics: NamePattern,
+    consumer: IggyConsumerBuilder,
+    discovery_interval: IggyDuration,
+}
+
+impl IggyPatternConsumerBuilder {
+    pub(crate) fn new(
+        client: IggySharedMut<ClientWrapper>,
+        streams: NamePattern,
+        topics: NamePattern,
+        consumer: IggyConsumerBuilder,
+    ) -> Self {
+        Self {
+            client,
+            streams,
+            topics,
+            consumer,
+            discovery_interval: IggyDuration::ONE_SECOND,
+        }
+    }
+
+    /// Sets the polling strategy of the consumer of each topic.
+    pub fn polling_strategy(self, polling_strategy: PollingStrategy) -> Self {
+        Self {
+            consumer: self.consumer.polling_strategy(polling_strategy),
+            ..self
+        }
+    }
+
+    /// Sets the batch size for polling messages from each topic.
+    pub fn batch_length(self, batch_length: u32) -> Self {
+        Self {
+            consumer: self.consumer.batch_length(batch_length),
+            ..self
+        }
+    }
+
+    /// Sets the auto-commit configuration for storing the offsets on the server.
+    pub fn auto_commit(self, auto_commit: AutoCommit) -> Self {
+        Self {
+            consumer: self.consumer.auto_commit(auto_commit),
+            ..self
+        }
+    }
+
+    /// Sets the polling interval for messages.
+    pub fn poll_interval(self, interval: IggyDuration) -> Self {
+        Self {
+            consumer: self.consumer.poll_interval(interval),
+            ..self
+        }
+    }
+
+    /// Configures the consumer of each topic with any other settings of `IggyConsumerBuilder`.
+    /// The stream and topic are always overridden with the ones of the matching topic.
+    pub fn consumer(
+        self,
+        configure: impl FnOnce(IggyConsumerBuilder) -> IggyConsumerBuilder,
+    ) -> Self {
+        Self {
+            consumer: configure(self.consumer),
+            ..self
+        }
+    }
+
+    /// Sets how often the topics are checked for changes (created, renamed or deleted topics and streams).
+    /// The topics are listed again only if the server reports the changes. By default, it's 1 second.
+    pub fn discovery_interval(self, discovery_interval: IggyDuration) -> Self {
+        Self {
+            discovery_interval,
+            ..self
+        }
+    }
+
+    /// Builds the consumer.
+    ///
+    /// Note: After building the consumer, `init()` must be invoked before consuming messages.
+    pub fn build(self) -> IggyPatternConsumer {
+        IggyPatternConsumer::new(
+            self.client,
+            self.streams,
+            self.topics,
+            self.consumer,
+            self.discovery_interval,
+        )
+    }
+}
@@ -21,6 +21,7 @@ use crate::clients::MAX_BATCH_LENGTH;
 use crate::clients::producer_builder::SendMode;
 use crate::clients::producer_config::DirectConfig;
 use crate::clients::producer_dispatcher::ProducerDispatcher;
+use crate::clients::producer_idempotence::{ProducerIdempotence, advance_sequence, reset_sequence};
 use bytes::Bytes;
 use futures_util::StreamExt;
 use iggy_binary_protocol::{Client, MessageClient, StreamClient, TopicClient};
@@ -28,6 +29,7 @@ use iggy_common::locking::{IggySharedMut, IggySharedMutFn};
 use iggy_common::{
     CompressionAlgorithm, DiagnosticEvent, EncryptorKind, IdKind, Identifier, IggyDuration,
     IggyError, IggyExpiry, IggyMessage, IggyTimestamp, MaxTopicSize, Partitioner, Partitioning,
+    ProducerSequence,
 };
 use std::sync::Arc;
 use std::sync::atomic::Ordering;
@@ -72,6 +74,7 @@ pub struct ProducerCore {
     send_retries_count: Option<u32>,
     send_retries_interval: Option<IggyDuration>,
     direct_config: Option<DirectConfig>,
+    idempotence: Option<ProducerIdempotence>,
 }
 
 impl ProducerCore {
@@ -103,7 +106,8 @@ impl ProducerCore {
             client.create_stream(&name, id).await?;
         }
 
-        if client.get_topic(&stream_id, &topic_id).await?.is_none() {
+        let topic = client.get_topic(&stream_id, &topic_id).await?;
+        if topic.is_none() {
             if !self.create_topic_if_not_exists {
                 error!("Topic does not exist and auto-creation is disabled.");
                 return Err(IggyError::TopicNameNotFound(
@@ -134,6 +138,14 @@ impl ProducerCore {
                 .await?;
         }
 
+        if let Some(idempotence) = &self.idempotence {
+            let partitions_count = match topic {
+                Some(topic) => topic.partitions_count,
+                None => self.topic_partitions_count,
+            };
+            idempotence.set_partitions_count(partitions_count);
+        }
+
         let _ = self
             .initialized
             .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst);
@@ -185,16 +197,51 @@ impl ProducerCore {
         partitioning: &Arc<Partitioning>,
         messages: &mut [IggyMessage],
     ) -> Result<(), IggyError> {
-        let client = self.client.read().await;
+        let Some(idempotence) = &self.idempotence else {
+            return self
+                .try_send_messages_with_sequence(stream, topic, partitioning, None, messages)
+                .await;
+        };
+
+        // The sequence stays locked until the batch is sent, so that the batches sent to the same partition
+        // are appended in the order of their sequence numbers, also when the sending is being retried.
+        let partition_id = idempotence.get_partition_id(partitioning)?;
+        let sequence = idempotence.get_sequence(partition_id);
+        let mut sequence = sequence.lock().await;
+        let partitioning = Arc::new(Partitioning::partition_id(partition_id));
+        let result = self
+            .try_send_messages_with_sequence(
+                stream,
+                topic,
+                &partitioning,
+                Some(&*sequence),
+                messages,
+            )
+            .await;
+        match &result {
+            Ok(()) => advance_sequence(&mut sequence, messages.len()),
+            Err(_) => reset_sequence(&mut sequence),
+        }
+        result
+    }
+
+    async fn try_send_messages_with_sequence(
+        &self,
+        stream: &Identifier,
+        topic: &Identifier,
+        partitioning: &Arc<Partitioning>,
+        sequence: Option<&ProducerSequence>,
+        messages: &mut [IggyMessage],
+    ) -> Result<(), IggyError> {
         let Some(max_retries) = self.send_retries_count else {
-            return client
-                .send_messages(stream, topic, partitioning, messages)
+            return self
+                .send_messages(stream, topic, partitioning, sequence, messages)
                 .await;
         };
 
         if max_retries == 0 {
-            return client
-                .send_messages(stream, topic, partitioning, messages)
+            return self
+                .send_messages(stream, topic, partitioning, sequence, messages)
                 .await;
         }
 
@@ -213,12 +260,41 @@ impl ProducerCore {
             stream,
             topic,
             partitioning,
+            sequence,
             messages,
             &mut timer,
         )
         .await
     }
 
+    async fn send_messages(
+        &self,
+        stream: &Identifier,
+        topic: &Identifier,
+        partitioning: &Partitioning,
+        sequence: Option<&ProducerSequence>,
+        messages: &mut [IggyMessage],
+    ) -> Result<(), IggyError> {
+        let client = self.client.read().await;
+        match sequence {
+            Some(sequence) => {
+                let partition_id = u32::from_le_bytes(
+                    partitioning.value[..partitioning.length as usize]
+                        .try_into()
+                        .map_err(|_| IggyError::InvalidNumberEncoding)?,
+                );
+                client
+                    .send_messages_with_sequence(stream, topic, partition_id, sequence, messages)
+                    .await
+            }
+            None => {
+                client
+                    .send_messages(stream, topic, partitioning, messages)
+                    .await
+            }
+        }
+    }
+
     async fn wait_until_connected(
         &self,
         max_retries: u32,
@@ -253,20 +329,21 @@ impl ProducerCore {
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn send_with_retries(
         &self,
         max_retries: u32,
         stream: &Identifier,
         topic: &Identifier,
         partitioning: &Arc<Partitioning>,
+        sequence: Option<&ProducerSequence>,
         messages: &mut [IggyMessage],
         timer: &mut Option<Interval>,
     ) -> Result<(), IggyError> {
-        let client = self.client.read().await;
         let mut retries = 0;
         loop {
-            match client
-                .send_messages(stream, topic, partitioning, messages)
+            match self
+                .send_messages(stream, topic, partitioning, sequence, messages)
                 .await
             {
                 Ok(_) => return Ok(()),
@@ -448,6 +525,7 @@ impl IggyProducer {
         topic_max_size: MaxTopicSize,
         send_retries_count: Option<u32>,
         send_retries_interval: Option<IggyDuration>,
+        idempotent: bool,
         mode: SendMode,
     ) -> Self {
         let core = Arc::new(ProducerCore {
@@ -475,6 +553,7 @@ impl IggyProducer {
                 SendMode::Direct(ref cfg) => Some(cfg.clone()),
                 _ => None,
             },
+            idempotence: idempotent.then(ProducerIdempotence::default),
         });
         let dispatcher = match mode {
             SendMode::Background(cfg) => Some(ProducerDispatcher::new(core.clone(), cfg)),
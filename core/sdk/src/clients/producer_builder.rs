@@ -52,6 +52,7 @@ pub struct IggyProducerBuilder {
     topic_message_expiry: IggyExpiry,
     topic_max_size: MaxTopicSize,
     partitioning: Option<Partitioning>,
+    idempotent: bool,
     mode: SendMode,
 }
 
@@ -83,6 +84,7 @@ impl IggyProducerBuilder {
             topic_max_size: MaxTopicSize::ServerDefault,
             send_retries_count: Some(3),
             send_retries_interval: Some(IggyDuration::ONE_SECOND),
+            idempotent: false,
             mode: SendMode::default(),
         }
     }
@@ -198,6 +200,24 @@ impl IggyProducerBuilder {
         }
     }
 
+    /// Enables the idempotent sending of messages, so that the retries never result in duplicated or reordered messages.
+    /// Each batch is sent with the producer ID and the sequence number, which are validated by the server for each partition.
+    /// The partition is resolved on the client side, based on the partitions count fetched during the initialization.
+    pub fn idempotent(self) -> Self {
+        Self {
+            idempotent: true,
+            ..self
+        }
+    }
+
+    /// Disables the idempotent sending of messages.
+    pub fn without_idempotence(self) -> Self {
+        Self {
+            idempotent: false,
+            ..self
+        }
+    }
+
     /// Sets the producer to use direct message sending.
     /// This mode ensures that messages are sent immediately to the server
     /// without being buffered or delayed.
@@ -231,6 +251,7 @@ impl IggyProducerBuilder {
             self.topic_max_size,
             self.send_retries_count,
             self.send_retries_interval,
+            self.idempotent,
             self.mode,
         )
     }
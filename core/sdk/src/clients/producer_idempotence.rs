@@ -0,0 +1,146 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+use super::ORDERING;
+use dashmap::DashMap;
+use iggy_common::{
+    IggyError, Partitioning, PartitioningKind, ProducerSequence,
+    calculate_partition_id_by_consistent_hash,
+};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, AtomicU64};
+use tokio::sync::Mutex;
+use twox_hash::XxHash32;
+
+/// Keeps track of the producer ID and the next sequence number for each partition the idempotent producer sends to.
+///
+/// The partition must be known before sending the messages, as the sequence numbers are validated
+/// by the server separately for each partition, thus the partitioning is resolved on the client side
+/// in the same way as it's done by the server.
+#[derive(Debug, Default)]
+pub(crate) struct ProducerIdempotence {
+    partitions_count: AtomicU32,
+    current_partition_id: AtomicU64,
+    sequences: DashMap<u32, Arc<Mutex<ProducerSequence>>>,
+}
+
+impl ProducerIdempotence {
+    pub fn set_partitions_count(&self, partitions_count: u32) {
+        self.partitions_count.store(partitions_count, ORDERING);
+    }
+
+    /// Returns the ID of the partition to which the messages will be appended.
+    pub fn get_partition_id(&self, partitioning: &Partitioning) -> Result<u32, IggyError> {
+        let partitions_count = self.partitions_count.load(ORDERING);
+        match partitioning.kind {
+            PartitioningKind::PartitionId => Ok(u32::from_le_bytes(
+                partitioning.value[..partitioning.length as usize]
+                    .try_into()
+                    .map_err(|_| IggyError::InvalidNumberEncoding)?,
+            )),
+            _ if partitions_count == 0 => Err(IggyError::NoPartitions(0, 0)),
+            PartitioningKind::Balanced => {
+                let counter = self.current_partition_id.fetch_add(1, ORDERING);
+                Ok((counter % partitions_count as u64) as u32 + 1)
+            }
+            PartitioningKind::MessagesKey => {
+                let hash = XxHash32::oneshot(0, &partitioning.value);
+                let partition_id = hash % partitions_count;
+                if partition_id == 0 {
+                    return Ok(partitions_count);
+                }
+                Ok(partition_id)
+            }
+            PartitioningKind::ConsistentMessagesKey => {
+                let hash = XxHash32::oneshot(0, &partitioning.value);
+                Ok(calculate_partition_id_by_consistent_hash(
+                    hash as u64,
+                    partitions_count,
+                ))
+            }
+        }
+    }
+
+    /// Returns the sequence of the given partition, which must be locked for the whole duration of sending the batch.
+    pub fn get_sequence(&self, partition_id: u32) -> Arc<Mutex<ProducerSequence>> {
+        self.sequences
+            .entry(partition_id)
+            .or_insert_with(|| Arc::new(Mutex::new(ProducerSequence::new(next_producer_id(), 0))))
+            .clone()
+    }
+}
+
+/// Advances the sequence past the successfully sent batch.
+pub(crate) fn advance_sequence(sequence: &mut ProducerSequence, messages_count: usize) {
+    sequence.base_sequence += messages_count as u64;
+}
+
+/// Starts a new sequence with a fresh producer ID, used when it's unknown whether the batch has been appended.
+/// Reusing the same sequence for the next batch could cause it to be discarded as a duplicate,
+/// while skipping it would result in the gap rejected by the server.
+pub(crate) fn reset_sequence(sequence: &mut ProducerSequence) {
+    *sequence = ProducerSequence::new(next_producer_id(), 0);
+}
+
+fn next_producer_id() -> u64 {
+    rand::random()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn partition_id_should_be_resolved_for_each_partitioning_kind() {
+        let idempotence = ProducerIdempotence::default();
+        idempotence.set_partitions_count(3);
+
+        assert_eq!(
+            idempotence
+                .get_partition_id(&Partitioning::partition_id(2))
+                .unwrap(),
+            2
+        );
+        let balanced = Partitioning::balanced();
+        let partition_ids = (0..4)
+            .map(|_| idempotence.get_partition_id(&balanced).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(partition_ids, vec![1, 2, 3, 1]);
+
+        let key = Partitioning::messages_key_str("key").unwrap();
+        let partition_id = idempotence.get_partition_id(&key).unwrap();
+        assert!((1..=3).contains(&partition_id));
+        assert_eq!(idempotence.get_partition_id(&key).unwrap(), partition_id);
+
+        let consistent_key = Partitioning::consistent_messages_key_str("key").unwrap();
+        let partition_id = idempotence.get_partition_id(&consistent_key).unwrap();
+        assert!((1..=3).contains(&partition_id));
+        assert_eq!(
+            idempotence.get_partition_id(&consistent_key).unwrap(),
+            partition_id
+        );
+    }
+
+    #[test]
+    fn sequence_should_be_advanced_or_reset() {
+        let mut sequence = ProducerSequence::new(1, 0);
+        advance_sequence(&mut sequence, 10);
+        assert_eq!(sequence.base_sequence, 10);
+        reset_sequence(&mut sequence);
+        assert_eq!(sequence.base_sequence, 0);
+    }
+}
@@ -19,8 +19,8 @@
 use crate::http::http_client::HttpClient;
 use crate::http::http_transport::HttpTransport;
 use crate::prelude::{
-    Consumer, FlushUnsavedBuffer, Identifier, IggyError, IggyMessage, Partitioning, PollMessages,
-    PolledMessages, PollingStrategy, SendMessages,
+    Consumer, FlushUnsavedBuffer, Identifier, IggyDuration, IggyError, IggyMessage, Partitioning,
+    PollMessages, PolledMessages, PollingStrategy, ProducerSequence, SendMessages,
 };
 use async_trait::async_trait;
 use iggy_binary_protocol::MessageClient;
@@ -59,6 +59,20 @@ impl MessageClient for HttpClient {
         Ok(messages)
     }
 
+    async fn long_poll_messages(
+        &self,
+        _stream_id: &Identifier,
+        _topic_id: &Identifier,
+        _partition_id: Option<u32>,
+        _consumer: &Consumer,
+        _strategy: &PollingStrategy,
+        _count: u32,
+        _auto_commit: bool,
+        _timeout: &IggyDuration,
+    ) -> Result<PolledMessages, IggyError> {
+        Err(IggyError::FeatureUnavailable)
+    }
+
     async fn send_messages(
         &self,
         stream_id: &Identifier,
@@ -74,6 +88,31 @@ impl MessageClient for HttpClient {
                 stream_id: stream_id.clone(),
                 topic_id: topic_id.clone(),
                 partitioning: partitioning.clone(),
+                producer_sequence: None,
+                batch,
+            },
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn send_messages_with_sequence(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: u32,
+        producer_sequence: &ProducerSequence,
+        messages: &mut [IggyMessage],
+    ) -> Result<(), IggyError> {
+        let batch = IggyMessagesBatch::from(&*messages);
+        self.post(
+            &get_path(&stream_id.as_cow_str(), &topic_id.as_cow_str()),
+            &SendMessages {
+                metadata_length: 0, // this field is used only for TCP/QUIC
+                stream_id: stream_id.clone(),
+                topic_id: topic_id.clone(),
+                partitioning: Partitioning::partition_id(partition_id),
+                producer_sequence: Some(*producer_sequence),
                 batch,
             },
         )
@@ -23,6 +23,8 @@ use async_trait::async_trait;
 use iggy_binary_protocol::PartitionClient;
 use iggy_common::create_partitions::CreatePartitions;
 use iggy_common::delete_partitions::DeletePartitions;
+use iggy_common::move_partition::MovePartition;
+use iggy_common::shrink_partitions::ShrinkPartitions;
 
 #[async_trait]
 impl PartitionClient for HttpClient {
@@ -61,6 +63,56 @@ impl PartitionClient for HttpClient {
         .await?;
         Ok(())
     }
+
+    async fn move_partition(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: u32,
+        directory: &str,
+    ) -> Result<(), IggyError> {
+        self.post(
+            &format!(
+                "{}/{partition_id}/move",
+                get_path(&stream_id.as_cow_str(), &topic_id.as_cow_str())
+            ),
+            &MovePartition {
+                stream_id: stream_id.clone(),
+                topic_id: topic_id.clone(),
+                partition_id,
+                directory: directory.to_string(),
+            },
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn shrink_partitions(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partitions_count: u32,
+        copy_unconsumed: bool,
+        key_header: Option<&str>,
+        consistent_hashing: bool,
+    ) -> Result<(), IggyError> {
+        self.post(
+            &format!(
+                "{}/shrink",
+                get_path(&stream_id.as_cow_str(), &topic_id.as_cow_str())
+            ),
+            &ShrinkPartitions {
+                stream_id: stream_id.clone(),
+                topic_id: topic_id.clone(),
+                partitions_count,
+                copy_unconsumed,
+                key_header: key_header.map(|key_header| key_header.to_string()),
+                consistent_hashing,
+            },
+        )
+        .await?;
+        Ok(())
+    }
 }
 
 fn get_path(stream_id: &str, topic_id: &str) -> String {
@@ -23,14 +23,17 @@ use async_trait::async_trait;
 use iggy_binary_protocol::SystemClient;
 use iggy_common::Snapshot;
 use iggy_common::Stats;
+use iggy_common::get_data_snapshot::GetDataSnapshot;
 use iggy_common::get_snapshot::GetSnapshot;
 use iggy_common::{ClientInfo, ClientInfoDetails};
-use iggy_common::{SnapshotCompression, SystemSnapshotType};
+use iggy_common::{DataSnapshotTarget, SnapshotCompression, SystemSnapshotType};
 
 const PING: &str = "/ping";
 const CLIENTS: &str = "/clients";
 const STATS: &str = "/stats";
 const SNAPSHOT: &str = "/snapshot";
+const DATA_SNAPSHOT: &str = "/snapshot/data";
+const RESTORE_DATA_SNAPSHOT: &str = "/snapshot/data/restore";
 
 #[async_trait]
 impl SystemClient for HttpClient {
@@ -103,4 +106,22 @@ impl SystemClient for HttpClient {
         let snapshot = Snapshot::new(file.to_vec());
         Ok(snapshot)
     }
+
+    async fn data_snapshot(&self, targets: Vec<DataSnapshotTarget>) -> Result<Snapshot, IggyError> {
+        let response = self
+            .post(DATA_SNAPSHOT, &GetDataSnapshot { targets })
+            .await?;
+        let file = response
+            .bytes()
+            .await
+            .map_err(|_| IggyError::InvalidBytesResponse)?;
+        let snapshot = Snapshot::new(file.to_vec());
+        Ok(snapshot)
+    }
+
+    async fn restore_data_snapshot(&self, snapshot: &Snapshot) -> Result<(), IggyError> {
+        self.post_bytes(RESTORE_DATA_SNAPSHOT, "application/zstd", snapshot.0.clone())
+            .await?;
+        Ok(())
+    }
 }
@@ -18,12 +18,14 @@
 
 use crate::http::http_client::HttpClient;
 use crate::http::http_transport::HttpTransport;
-use crate::prelude::{CompressionAlgorithm, Identifier, IggyError, IggyExpiry, MaxTopicSize};
+use crate::prelude::{
+    CompressionAlgorithm, Identifier, IggyDuration, IggyError, IggyExpiry, MaxTopicSize,
+};
 use async_trait::async_trait;
 use iggy_binary_protocol::TopicClient;
 use iggy_common::create_topic::CreateTopic;
 use iggy_common::update_topic::UpdateTopic;
-use iggy_common::{Topic, TopicDetails};
+use iggy_common::{Topic, TopicDetails, TopicOverrides};
 
 #[async_trait]
 impl TopicClient for HttpClient {
@@ -72,6 +74,32 @@ impl TopicClient for HttpClient {
         topic_id: Option<u32>,
         message_expiry: IggyExpiry,
         max_topic_size: MaxTopicSize,
+    ) -> Result<TopicDetails, IggyError> {
+        self.create_topic_with_overrides(
+            stream_id,
+            name,
+            partitions_count,
+            compression_algorithm,
+            replication_factor,
+            topic_id,
+            message_expiry,
+            max_topic_size,
+            TopicOverrides::default(),
+        )
+        .await
+    }
+
+    async fn create_topic_with_overrides(
+        &self,
+        stream_id: &Identifier,
+        name: &str,
+        partitions_count: u32,
+        compression_algorithm: CompressionAlgorithm,
+        replication_factor: Option<u8>,
+        topic_id: Option<u32>,
+        message_expiry: IggyExpiry,
+        max_topic_size: MaxTopicSize,
+        overrides: TopicOverrides,
     ) -> Result<TopicDetails, IggyError> {
         let response = self
             .post(
@@ -85,6 +113,7 @@ impl TopicClient for HttpClient {
                     topic_id,
                     message_expiry,
                     max_topic_size,
+                    overrides,
                 },
             )
             .await?;
@@ -115,6 +144,35 @@ impl TopicClient for HttpClient {
                 replication_factor,
                 message_expiry,
                 max_topic_size,
+                overrides: None,
+            },
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn update_topic_with_overrides(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        name: &str,
+        compression_algorithm: CompressionAlgorithm,
+        replication_factor: Option<u8>,
+        message_expiry: IggyExpiry,
+        max_topic_size: MaxTopicSize,
+        overrides: TopicOverrides,
+    ) -> Result<(), IggyError> {
+        self.put(
+            &get_details_path(&stream_id.as_cow_str(), &topic_id.as_cow_str()),
+            &UpdateTopic {
+                stream_id: stream_id.clone(),
+                topic_id: topic_id.clone(),
+                name: name.to_string(),
+                compression_algorithm,
+                replication_factor,
+                message_expiry,
+                max_topic_size,
+                overrides: Some(overrides),
             },
         )
         .await?;
@@ -146,6 +204,10 @@ impl TopicClient for HttpClient {
         .await?;
         Ok(())
     }
+
+    async fn watch_topics(&self, _version: u64, _timeout: &IggyDuration) -> Result<u64, IggyError> {
+        Err(IggyError::FeatureUnavailable)
+    }
 }
 
 fn get_path(stream_id: &str) -> String {
@@ -147,6 +147,28 @@ impl HttpTransport for HttpClient {
         Self::handle_response(response).await
     }
 
+    /// Invoke HTTP POST request to the Iggy API with the raw bytes of the given content type as the body.
+    async fn post_bytes(
+        &self,
+        path: &str,
+        content_type: &'static str,
+        payload: Vec<u8>,
+    ) -> Result<Response, IggyError> {
+        let url = self.get_url(path)?;
+        self.fail_if_not_authenticated(path).await?;
+        let token = self.access_token.read().await;
+        let response = self
+            .client
+            .post(url)
+            .bearer_auth(token.deref())
+            .header(reqwest::header::CONTENT_TYPE, content_type)
+            .body(payload)
+            .send()
+            .await
+            .map_err(|_| IggyError::InvalidHttpRequest)?;
+        Self::handle_response(response).await
+    }
+
     /// Invoke HTTP PUT request to the Iggy API.
     async fn put<T: Serialize + Sync + ?Sized>(
         &self,
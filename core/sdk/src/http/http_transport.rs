@@ -42,6 +42,14 @@ pub trait HttpTransport {
         payload: &T,
     ) -> Result<Response, IggyError>;
 
+    /// Invoke HTTP POST request to the Iggy API with the raw bytes of the given content type as the body.
+    async fn post_bytes(
+        &self,
+        path: &str,
+        content_type: &'static str,
+        payload: Vec<u8>,
+    ) -> Result<Response, IggyError>;
+
     /// Invoke HTTP PUT request to the Iggy API.
     async fn put<T: Serialize + Sync + ?Sized>(
         &self,
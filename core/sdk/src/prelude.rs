@@ -32,10 +32,15 @@ pub use crate::client_provider::ClientProviderConfig;
 pub use crate::client_wrappers::client_wrapper::ClientWrapper;
 pub use crate::clients::client::IggyClient;
 pub use crate::clients::client_builder::IggyClientBuilder;
+pub use crate::clients::consistent_hash_partitioner::ConsistentHashPartitioner;
 pub use crate::clients::consumer::{
     AutoCommit, AutoCommitAfter, AutoCommitWhen, IggyConsumer, ReceivedMessage,
 };
 pub use crate::clients::consumer_builder::IggyConsumerBuilder;
+pub use crate::clients::pattern_consumer::{
+    IggyPatternConsumer, NamePattern, ReceivedTopicMessage,
+};
+pub use crate::clients::pattern_consumer_builder::IggyPatternConsumerBuilder;
 pub use crate::clients::producer::IggyProducer;
 pub use crate::clients::producer_builder::IggyProducerBuilder;
 pub use crate::clients::producer_config::{BackgroundConfig, DirectConfig};
@@ -45,6 +50,7 @@ pub use crate::stream_builder::IggyStreamConsumer;
 pub use crate::stream_builder::{IggyProducerConfig, IggyStreamProducer};
 pub use crate::stream_builder::{IggyStream, IggyStreamConfig};
 pub use crate::tcp::tcp_client::TcpClient;
+pub use crate::websocket::websocket_client::WebSocketClient;
 pub use iggy_binary_protocol::{
     Client, ConsumerGroupClient, ConsumerOffsetClient, MessageClient, PartitionClient,
     PersonalAccessTokenClient, SegmentClient, StreamClient, SystemClient, TopicClient, UserClient,
@@ -52,22 +58,25 @@ pub use iggy_binary_protocol::{
 pub use iggy_common::{
     Aes256GcmEncryptor, Args, ArgsOptional, AutoLogin, BytesSerializable, CacheMetrics,
     CacheMetricsKey, ClientError, ClientInfoDetails, CompressionAlgorithm, Confirmation, Consumer,
-    ConsumerGroupDetails, ConsumerKind, EncryptorKind, FlushUnsavedBuffer, GlobalPermissions,
-    HeaderKey, HeaderValue, HttpClientConfig, HttpClientConfigBuilder, IdKind, Identifier,
-    IdentityInfo, IggyByteSize, IggyDuration, IggyError, IggyExpiry, IggyIndexView, IggyMessage,
-    IggyMessageHeader, IggyMessageHeaderView, IggyMessageView, IggyMessageViewIterator,
-    IggyTimestamp, MaxTopicSize, Partition, Partitioner, Partitioning, Permissions,
-    PersonalAccessTokenExpiry, PollMessages, PolledMessages, PollingKind, PollingStrategy,
-    QuicClientConfig, QuicClientConfigBuilder, QuicClientReconnectionConfig, SendMessages,
-    Sizeable, SnapshotCompression, Stats, Stream, StreamDetails, StreamPermissions,
-    SystemSnapshotType, TcpClientConfig, TcpClientConfigBuilder, TcpClientReconnectionConfig,
-    Topic, TopicDetails, TopicPermissions, UserId, UserStatus, Validatable, defaults, locking,
+    ConsumerGroupDetails, ConsumerKind, DataSnapshotTarget, EncryptorKind, FlushUnsavedBuffer,
+    GlobalPermissions, HeaderKey, HeaderValue, HttpClientConfig, HttpClientConfigBuilder, IdKind,
+    Identifier, IdentityInfo, IggyByteSize, IggyDuration, IggyError, IggyExpiry, IggyIndexView,
+    IggyMessage, IggyMessageHeader, IggyMessageHeaderView, IggyMessageView,
+    IggyMessageViewIterator, IggyTimestamp, LongPollMessages, MaxTopicSize, MessagesSubscription,
+    Partition, Partitioner, Partitioning, Permissions, PersonalAccessTokenExpiry, PollMessages,
+    PolledMessages, PollingKind, PollingStrategy, ProducerSequence, QuicClientConfig,
+    QuicClientConfigBuilder, QuicClientReconnectionConfig, SendMessages, Sizeable,
+    SnapshotCompression, Stats, Stream, StreamDetails, StreamPermissions, SystemSnapshotType,
+    TcpClientConfig, TcpClientConfigBuilder, TcpClientReconnectionConfig, Topic, TopicDetails,
+    TopicOverrides, TopicPermissions, UserId, UserStatus, Validatable, WebSocketClientConfig,
+    WebSocketClientConfigBuilder, defaults, locking,
 };
 pub use iggy_common::{
     IGGY_MESSAGE_CHECKSUM_OFFSET_RANGE, IGGY_MESSAGE_HEADER_SIZE,
     IGGY_MESSAGE_HEADERS_LENGTH_OFFSET_RANGE, IGGY_MESSAGE_ID_OFFSET_RANGE,
     IGGY_MESSAGE_OFFSET_OFFSET_RANGE, IGGY_MESSAGE_ORIGIN_TIMESTAMP_OFFSET_RANGE,
     IGGY_MESSAGE_PAYLOAD_LENGTH_OFFSET_RANGE, IGGY_MESSAGE_TIMESTAMP_OFFSET_RANGE, INDEX_SIZE,
-    MAX_PAYLOAD_SIZE, MAX_USER_HEADERS_SIZE, SEC_IN_MICRO,
+    MAX_LONG_POLLING_TIMEOUT, MAX_PAYLOAD_SIZE, MAX_USER_HEADERS_SIZE, MESSAGE_TTL_HEADER,
+    SEC_IN_MICRO,
     defaults::{DEFAULT_ROOT_PASSWORD, DEFAULT_ROOT_USER_ID, DEFAULT_ROOT_USERNAME},
 };
@@ -0,0 +1,579 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::prelude::Client;
+use async_broadcast::{Receiver, Sender, broadcast};
+use async_trait::async_trait;
+use bytes::{BufMut, Bytes, BytesMut};
+use futures::stream::{SplitSink, SplitStream};
+use futures::{SinkExt, StreamExt};
+use iggy_binary_protocol::{BinaryClient, BinaryTransport, PersonalAccessTokenClient, UserClient};
+use iggy_common::{
+    AutoLogin, ClientState, Command, Credentials, DiagnosticEvent, IggyDuration, IggyError,
+    IggyTimestamp, MessagesSubscription, PolledMessages, WebSocketClientConfig,
+    WebSocketClientFrame, WebSocketServerFrame,
+};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex as StdMutex};
+use tokio::net::TcpStream;
+use tokio::sync::{Mutex, oneshot};
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::HeaderValue;
+use tokio_tungstenite::tungstenite::http::header::AUTHORIZATION;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+use tracing::{debug, error, info, trace, warn};
+
+const REQUEST_INITIAL_BYTES_LENGTH: usize = 4;
+const RESPONSE_INITIAL_BYTES_LENGTH: usize = 8;
+const NAME: &str = "Iggy";
+
+type WebSocketWriter = SplitSink<WebSocketStream<MaybeTlsStream<TcpStream>>, Message>;
+type WebSocketReader = SplitStream<WebSocketStream<MaybeTlsStream<TcpStream>>>;
+
+/// WebSocket client for interacting with the Iggy API.
+/// It requires a valid server URL pointing to the WebSocket endpoint of the HTTP API (e.g. `ws://localhost:3000/ws`).
+///
+/// The binary protocol commands are sent as binary WebSocket messages, the same way as for TCP,
+/// while the messages for the subscribed topics are pushed by the server (see `subscribe()`).
+#[derive(Debug)]
+pub struct WebSocketClient {
+    pub(crate) config: Arc<WebSocketClientConfig>,
+    pub(crate) state: Mutex<ClientState>,
+    connection: Mutex<Option<WebSocketConnection>>,
+    subscriptions: Arc<WebSocketSubscriptions>,
+    events: (Sender<DiagnosticEvent>, Receiver<DiagnosticEvent>),
+}
+
+#[derive(Debug)]
+struct WebSocketConnection {
+    writer: WebSocketWriter,
+    responses: flume::Receiver<Bytes>,
+    reader: JoinHandle<()>,
+}
+
+/// Subscriptions registered by the client, shared with the background task reading the pushed frames.
+#[derive(Debug, Default)]
+struct WebSocketSubscriptions {
+    acknowledgements: StdMutex<HashMap<u32, oneshot::Sender<Result<(), IggyError>>>>,
+    messages: StdMutex<HashMap<u32, flume::Sender<Result<PolledMessages, IggyError>>>>,
+}
+
+impl Default for WebSocketClient {
+    fn default() -> Self {
+        WebSocketClient::create(Arc::new(WebSocketClientConfig::default())).unwrap()
+    }
+}
+
+#[async_trait]
+impl Client for WebSocketClient {
+    async fn connect(&self) -> Result<(), IggyError> {
+        WebSocketClient::connect(self).await
+    }
+
+    async fn disconnect(&self) -> Result<(), IggyError> {
+        WebSocketClient::disconnect(self).await
+    }
+
+    async fn shutdown(&self) -> Result<(), IggyError> {
+        WebSocketClient::shutdown(self).await
+    }
+
+    async fn subscribe_events(&self) -> Receiver<DiagnosticEvent> {
+        self.events.1.clone()
+    }
+}
+
+#[async_trait]
+impl BinaryTransport for WebSocketClient {
+    async fn get_state(&self) -> ClientState {
+        *self.state.lock().await
+    }
+
+    async fn set_state(&self, state: ClientState) {
+        *self.state.lock().await = state;
+    }
+
+    async fn publish_event(&self, event: DiagnosticEvent) {
+        if let Err(error) = self.events.0.broadcast(event).await {
+            error!("Failed to send a WebSocket diagnostic event: {error}");
+        }
+    }
+
+    async fn send_with_response<T: Command>(&self, command: &T) -> Result<Bytes, IggyError> {
+        command.validate()?;
+        self.send_raw_with_response(command.code(), command.to_bytes())
+            .await
+    }
+
+    async fn send_raw_with_response(&self, code: u32, payload: Bytes) -> Result<Bytes, IggyError> {
+        self.send_raw(code, payload).await
+    }
+
+    fn get_heartbeat_interval(&self) -> IggyDuration {
+        self.config.heartbeat_interval
+    }
+}
+
+impl BinaryClient for WebSocketClient {}
+
+impl WebSocketClient {
+    /// Create a new WebSocket client for the provided server URL.
+    pub fn new(
+        server_url: &str,
+        auto_sign_in: AutoLogin,
+        heartbeat_interval: IggyDuration,
+    ) -> Result<Self, IggyError> {
+        Self::create(Arc::new(WebSocketClientConfig {
+            server_url: server_url.to_string(),
+            auto_login: auto_sign_in,
+            heartbeat_interval,
+            ..Default::default()
+        }))
+    }
+
+    /// Create a new WebSocket client based on the provided configuration.
+    pub fn create(config: Arc<WebSocketClientConfig>) -> Result<Self, IggyError> {
+        if config.server_url.is_empty() {
+            return Err(IggyError::InvalidConfiguration);
+        }
+
+        Ok(Self {
+            config,
+            state: Mutex::new(ClientState::Disconnected),
+            connection: Mutex::new(None),
+            subscriptions: Arc::new(WebSocketSubscriptions::default()),
+            events: broadcast(1000),
+        })
+    }
+
+    /// Subscribe to the messages of the topic, which will be pushed by the server as soon as they are available.
+    /// The subscription remains active until `unsubscribe()` is invoked, the returned receiver is dropped
+    /// or the connection is closed. An error received through the channel ends the subscription.
+    pub async fn subscribe(
+        &self,
+        subscription: MessagesSubscription,
+    ) -> Result<flume::Receiver<Result<PolledMessages, IggyError>>, IggyError> {
+        let subscription_id = subscription.subscription_id;
+        let (sender, receiver) = flume::unbounded();
+        {
+            let mut messages = self.subscriptions.messages.lock().unwrap();
+            if messages.contains_key(&subscription_id) {
+                error!("Subscription with ID: {subscription_id} already exists.");
+                return Err(IggyError::InvalidCommand);
+            }
+            messages.insert(subscription_id, sender);
+        }
+
+        let (acknowledgement_sender, acknowledgement) = oneshot::channel();
+        self.subscriptions
+            .acknowledgements
+            .lock()
+            .unwrap()
+            .insert(subscription_id, acknowledgement_sender);
+
+        let result = self
+            .send_frame(&WebSocketClientFrame::Subscribe(subscription))
+            .await;
+        let result = match result {
+            Ok(()) => acknowledgement
+                .await
+                .unwrap_or(Err(IggyError::Disconnected)),
+            Err(error) => Err(error),
+        };
+
+        if let Err(error) = result {
+            self.subscriptions.remove(subscription_id);
+            return Err(error);
+        }
+
+        Ok(receiver)
+    }
+
+    /// Remove the subscription with the provided ID.
+    pub async fn unsubscribe(&self, subscription_id: u32) -> Result<(), IggyError> {
+        let (acknowledgement_sender, acknowledgement) = oneshot::channel();
+        self.subscriptions
+            .acknowledgements
+            .lock()
+            .unwrap()
+            .insert(subscription_id, acknowledgement_sender);
+        let result = self
+            .send_frame(&WebSocketClientFrame::Unsubscribe { subscription_id })
+            .await;
+        let result = match result {
+            Ok(()) => acknowledgement
+                .await
+                .unwrap_or(Err(IggyError::Disconnected)),
+            Err(error) => Err(error),
+        };
+        self.subscriptions.remove(subscription_id);
+        result
+    }
+
+    async fn send_frame(&self, frame: &WebSocketClientFrame) -> Result<(), IggyError> {
+        let json = serde_json::to_string(frame).map_err(|_| IggyError::CannotSerializeResource)?;
+        let mut connection = self.connection.lock().await;
+        let Some(connection) = connection.as_mut() else {
+            error!("Cannot send data. Client is not connected.");
+            return Err(IggyError::NotConnected);
+        };
+
+        connection
+            .writer
+            .send(Message::Text(json.into()))
+            .await
+            .map_err(|error| {
+                error!("Failed to send a WebSocket frame: {error}");
+                IggyError::WebSocketError
+            })
+    }
+
+    async fn connect(&self) -> Result<(), IggyError> {
+        match self.get_state().await {
+            ClientState::Shutdown => {
+                trace!("Cannot connect. Client is shutdown.");
+                return Err(IggyError::ClientShutdown);
+            }
+            ClientState::Connected | ClientState::Authenticating | ClientState::Authenticated => {
+                trace!("Client is already connected.");
+                return Ok(());
+            }
+            ClientState::Connecting => {
+                trace!("Client is already connecting.");
+                return Ok(());
+            }
+            _ => {}
+        }
+
+        self.set_state(ClientState::Connecting).await;
+        info!(
+            "{NAME} client is connecting to server: {}...",
+            self.config.server_url
+        );
+        let mut request = self
+            .config
+            .server_url
+            .as_str()
+            .into_client_request()
+            .map_err(|error| {
+                error!(
+                    "Invalid WebSocket server URL: {}. {error}",
+                    self.config.server_url
+                );
+                IggyError::InvalidConfiguration
+            })?;
+        if let Some(access_token) = &self.config.access_token {
+            let header = HeaderValue::from_str(&format!("Bearer {access_token}"))
+                .map_err(|_| IggyError::InvalidAccessToken)?;
+            request.headers_mut().insert(AUTHORIZATION, header);
+        }
+
+        let stream = match tokio_tungstenite::connect_async(request).await {
+            Ok((stream, _)) => stream,
+            Err(error) => {
+                error!(
+                    "Failed to connect to server: {}. Error: {error}",
+                    self.config.server_url
+                );
+                self.set_state(ClientState::Disconnected).await;
+                self.publish_event(DiagnosticEvent::Disconnected).await;
+                return Err(IggyError::CannotEstablishConnection);
+            }
+        };
+
+        let (writer, reader) = stream.split();
+        let (responses_sender, responses) = flume::unbounded();
+        let reader = tokio::spawn(read_messages(
+            reader,
+            responses_sender,
+            self.subscriptions.clone(),
+        ));
+        self.connection.lock().await.replace(WebSocketConnection {
+            writer,
+            responses,
+            reader,
+        });
+
+        let now = IggyTimestamp::now();
+        info!(
+            "{NAME} client has connected to server: {} at: {now}",
+            self.config.server_url
+        );
+        if self.config.access_token.is_some() {
+            self.set_state(ClientState::Authenticated).await;
+            self.publish_event(DiagnosticEvent::Connected).await;
+            return Ok(());
+        }
+
+        self.set_state(ClientState::Connected).await;
+        self.publish_event(DiagnosticEvent::Connected).await;
+        match &self.config.auto_login {
+            AutoLogin::Disabled => {
+                info!("Automatic sign-in is disabled.");
+                Ok(())
+            }
+            AutoLogin::Enabled(credentials) => {
+                info!("{NAME} client is signing in...");
+                self.set_state(ClientState::Authenticating).await;
+                match credentials {
+                    Credentials::UsernamePassword(username, password) => {
+                        self.login_user(username, password).await?;
+                        info!(
+                            "{NAME} client has signed in with the user credentials, username: {username}",
+                        );
+                        Ok(())
+                    }
+                    Credentials::PersonalAccessToken(token) => {
+                        self.login_with_personal_access_token(token).await?;
+                        info!("{NAME} client has signed in with a personal access token.",);
+                        Ok(())
+                    }
+                }
+            }
+        }
+    }
+
+    async fn disconnect(&self) -> Result<(), IggyError> {
+        if self.get_state().await == ClientState::Disconnected {
+            return Ok(());
+        }
+
+        info!("{NAME} client is disconnecting from server...");
+        self.set_state(ClientState::Disconnected).await;
+        if let Some(connection) = self.connection.lock().await.take() {
+            connection.close().await;
+        }
+        self.subscriptions.clear();
+        self.publish_event(DiagnosticEvent::Disconnected).await;
+        let now = IggyTimestamp::now();
+        info!("{NAME} client has disconnected from server at: {now}.");
+        Ok(())
+    }
+
+    async fn shutdown(&self) -> Result<(), IggyError> {
+        if self.get_state().await == ClientState::Shutdown {
+            return Ok(());
+        }
+
+        info!("Shutting down the {NAME} WebSocket client.");
+        if let Some(connection) = self.connection.lock().await.take() {
+            connection.close().await;
+        }
+        self.subscriptions.clear();
+        self.set_state(ClientState::Shutdown).await;
+        self.publish_event(DiagnosticEvent::Shutdown).await;
+        info!("{NAME} WebSocket client has been shutdown.");
+        Ok(())
+    }
+
+    async fn send_raw(&self, code: u32, payload: Bytes) -> Result<Bytes, IggyError> {
+        match self.get_state().await {
+            ClientState::Shutdown => {
+                trace!("Cannot send data. Client is shutdown.");
+                return Err(IggyError::ClientShutdown);
+            }
+            ClientState::Disconnected => {
+                trace!("Cannot send data. Client is not connected.");
+                return Err(IggyError::NotConnected);
+            }
+            ClientState::Connecting => {
+                trace!("Cannot send data. Client is still connecting.");
+                return Err(IggyError::NotConnected);
+            }
+            _ => {}
+        }
+
+        let mut connection = self.connection.lock().await;
+        let Some(connection) = connection.as_mut() else {
+            error!("Cannot send data. Client is not connected.");
+            return Err(IggyError::NotConnected);
+        };
+
+        let payload_length = payload.len() + REQUEST_INITIAL_BYTES_LENGTH;
+        trace!("Sending a WebSocket request of size {payload_length} with code: {code}");
+        let mut request = BytesMut::with_capacity(REQUEST_INITIAL_BYTES_LENGTH + payload_length);
+        request.put_u32_le(payload_length as u32);
+        request.put_u32_le(code);
+        request.put_slice(&payload);
+        connection
+            .writer
+            .send(Message::Binary(request.freeze()))
+            .await
+            .map_err(|error| {
+                error!("Failed to send a WebSocket request with code: {code}: {error}");
+                IggyError::Disconnected
+            })?;
+
+        trace!("Sent a WebSocket request with code: {code}, waiting for a response...");
+        let response = connection.responses.recv_async().await.map_err(|_| {
+            error!("Failed to read response for WebSocket request with code: {code}");
+            IggyError::Disconnected
+        })?;
+        handle_response(response)
+    }
+}
+
+impl WebSocketConnection {
+    async fn close(mut self) {
+        if let Err(error) = self.writer.close().await {
+            debug!("Failed to close the WebSocket connection: {error}");
+        }
+        self.reader.abort();
+    }
+}
+
+impl WebSocketSubscriptions {
+    fn acknowledge(&self, subscription_id: u32, result: Result<(), IggyError>) -> bool {
+        match self
+            .acknowledgements
+            .lock()
+            .unwrap()
+            .remove(&subscription_id)
+        {
+            Some(acknowledgement) => {
+                let _ = acknowledgement.send(result);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn publish(&self, subscription_id: u32, result: Result<PolledMessages, IggyError>) {
+        let mut messages = self.messages.lock().unwrap();
+        let Some(sender) = messages.get(&subscription_id) else {
+            trace!("Received messages for unknown subscription with ID: {subscription_id}");
+            return;
+        };
+
+        let is_error = result.is_err();
+        if sender.send(result).is_err() || is_error {
+            messages.remove(&subscription_id);
+        }
+    }
+
+    fn remove(&self, subscription_id: u32) {
+        self.acknowledgements
+            .lock()
+            .unwrap()
+            .remove(&subscription_id);
+        self.messages.lock().unwrap().remove(&subscription_id);
+    }
+
+    fn clear(&self) {
+        self.acknowledgements.lock().unwrap().clear();
+        self.messages.lock().unwrap().clear();
+    }
+}
+
+async fn read_messages(
+    mut reader: WebSocketReader,
+    responses: flume::Sender<Bytes>,
+    subscriptions: Arc<WebSocketSubscriptions>,
+) {
+    while let Some(message) = reader.next().await {
+        match message {
+            Ok(Message::Binary(response)) => {
+                if responses.send(response).is_err() {
+                    break;
+                }
+            }
+            Ok(Message::Text(frame)) => {
+                match serde_json::from_str::<WebSocketServerFrame>(frame.as_str()) {
+                    Ok(frame) => handle_frame(frame, &subscriptions),
+                    Err(error) => warn!("Received an invalid WebSocket frame: {error}"),
+                }
+            }
+            Ok(Message::Close(_)) => {
+                info!("WebSocket connection has been closed by the server.");
+                break;
+            }
+            Ok(_) => {}
+            Err(error) => {
+                error!("Failed to read from the WebSocket connection: {error}");
+                break;
+            }
+        }
+    }
+    subscriptions.clear();
+}
+
+fn handle_frame(frame: WebSocketServerFrame, subscriptions: &WebSocketSubscriptions) {
+    match frame {
+        WebSocketServerFrame::Subscribed { subscription_id }
+        | WebSocketServerFrame::Unsubscribed { subscription_id } => {
+            subscriptions.acknowledge(subscription_id, Ok(()));
+        }
+        WebSocketServerFrame::Messages {
+            subscription_id,
+            messages,
+        } => {
+            subscriptions.publish(subscription_id, Ok(messages));
+        }
+        WebSocketServerFrame::Error {
+            subscription_id,
+            code,
+            reason,
+        } => {
+            let Some(subscription_id) = subscription_id else {
+                error!("Received a WebSocket error: {reason} ({code}).");
+                return;
+            };
+
+            let error = IggyError::from_code(code);
+            if !subscriptions.acknowledge(subscription_id, Err(IggyError::from_code(code))) {
+                error!("Subscription with ID: {subscription_id} has failed: {reason} ({code}).");
+                subscriptions.publish(subscription_id, Err(error));
+            }
+        }
+    }
+}
+
+fn handle_response(response: Bytes) -> Result<Bytes, IggyError> {
+    if response.len() < RESPONSE_INITIAL_BYTES_LENGTH {
+        error!("Received an invalid or empty response.");
+        return Err(IggyError::EmptyResponse);
+    }
+
+    let status = u32::from_le_bytes(
+        response[..4]
+            .try_into()
+            .map_err(|_| IggyError::InvalidNumberEncoding)?,
+    );
+    if status != 0 {
+        error!(
+            "Received an invalid response with status: {} ({}).",
+            status,
+            IggyError::from_code_as_string(status),
+        );
+        return Err(IggyError::from_code(status));
+    }
+
+    let length = u32::from_le_bytes(
+        response[4..RESPONSE_INITIAL_BYTES_LENGTH]
+            .try_into()
+            .map_err(|_| IggyError::InvalidNumberEncoding)?,
+    );
+    trace!("Status: OK. Response length: {}", length);
+    if length <= 1 {
+        return Ok(Bytes::new());
+    }
+
+    Ok(response.slice(RESPONSE_INITIAL_BYTES_LENGTH..))
+}
@@ -42,6 +42,7 @@ use iggy_common::get_clients::GetClients;
 use iggy_common::get_consumer_group::GetConsumerGroup;
 use iggy_common::get_consumer_groups::GetConsumerGroups;
 use iggy_common::get_consumer_offset::GetConsumerOffset;
+use iggy_common::get_data_snapshot::GetDataSnapshot;
 use iggy_common::get_me::GetMe;
 use iggy_common::get_personal_access_tokens::GetPersonalAccessTokens;
 use iggy_common::get_snapshot::GetSnapshot;
@@ -57,14 +58,18 @@ use iggy_common::leave_consumer_group::LeaveConsumerGroup;
 use iggy_common::login_user::LoginUser;
 use iggy_common::login_with_personal_access_token::LoginWithPersonalAccessToken;
 use iggy_common::logout_user::LogoutUser;
+use iggy_common::move_partition::MovePartition;
 use iggy_common::ping::Ping;
 use iggy_common::purge_stream::PurgeStream;
 use iggy_common::purge_topic::PurgeTopic;
+use iggy_common::restore_data_snapshot::RestoreDataSnapshot;
+use iggy_common::shrink_partitions::ShrinkPartitions;
 use iggy_common::store_consumer_offset::StoreConsumerOffset;
 use iggy_common::update_permissions::UpdatePermissions;
 use iggy_common::update_stream::UpdateStream;
 use iggy_common::update_topic::UpdateTopic;
 use iggy_common::update_user::UpdateUser;
+use iggy_common::watch_topics::WatchTopics;
 use iggy_common::*;
 use strum::EnumString;
 use tracing::error;
@@ -76,7 +81,10 @@ define_server_command_enum! {
     GetClient(GetClient), GET_CLIENT_CODE, GET_CLIENT, true;
     GetClients(GetClients), GET_CLIENTS_CODE, GET_CLIENTS, false;
     GetSnapshot(GetSnapshot), GET_SNAPSHOT_FILE_CODE, GET_SNAPSHOT_FILE, false;
+    GetDataSnapshot(GetDataSnapshot), GET_DATA_SNAPSHOT_CODE, GET_DATA_SNAPSHOT, true;
+    RestoreDataSnapshot(RestoreDataSnapshot), RESTORE_DATA_SNAPSHOT_CODE, RESTORE_DATA_SNAPSHOT, false;
     PollMessages(PollMessages), POLL_MESSAGES_CODE, POLL_MESSAGES, true;
+    LongPollMessages(LongPollMessages), LONG_POLL_MESSAGES_CODE, LONG_POLL_MESSAGES, true;
     FlushUnsavedBuffer(FlushUnsavedBuffer), FLUSH_UNSAVED_BUFFER_CODE, FLUSH_UNSAVED_BUFFER, true;
     GetUser(GetUser), GET_USER_CODE, GET_USER, true;
     GetUsers(GetUsers), GET_USERS_CODE, GET_USERS, false;
@@ -107,8 +115,11 @@ define_server_command_enum! {
     DeleteTopic(DeleteTopic), DELETE_TOPIC_CODE, DELETE_TOPIC, true;
     UpdateTopic(UpdateTopic), UPDATE_TOPIC_CODE, UPDATE_TOPIC, true;
     PurgeTopic(PurgeTopic), PURGE_TOPIC_CODE, PURGE_TOPIC, true;
+    WatchTopics(WatchTopics), WATCH_TOPICS_CODE, WATCH_TOPICS, true;
     CreatePartitions(CreatePartitions), CREATE_PARTITIONS_CODE, CREATE_PARTITIONS, true;
     DeletePartitions(DeletePartitions), DELETE_PARTITIONS_CODE, DELETE_PARTITIONS, true;
+    MovePartition(MovePartition), MOVE_PARTITION_CODE, MOVE_PARTITION, true;
+    ShrinkPartitions(ShrinkPartitions), SHRINK_PARTITIONS_CODE, SHRINK_PARTITIONS, true;
     DeleteSegments(DeleteSegments), DELETE_SEGMENTS_CODE, DELETE_SEGMENTS, true;
     GetConsumerGroup(GetConsumerGroup), GET_CONSUMER_GROUP_CODE, GET_CONSUMER_GROUP, true;
     GetConsumerGroups(GetConsumerGroups), GET_CONSUMER_GROUPS_CODE, GET_CONSUMER_GROUPS, false;
@@ -250,11 +261,30 @@ mod tests {
             LOGIN_WITH_PERSONAL_ACCESS_TOKEN_CODE,
             &LoginWithPersonalAccessToken::default(),
         );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::GetDataSnapshot(GetDataSnapshot::default()),
+            GET_DATA_SNAPSHOT_CODE,
+            &GetDataSnapshot::default(),
+        );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::RestoreDataSnapshot(RestoreDataSnapshot {
+                snapshot: vec![1, 2, 3],
+            }),
+            RESTORE_DATA_SNAPSHOT_CODE,
+            &RestoreDataSnapshot {
+                snapshot: vec![1, 2, 3],
+            },
+        );
         assert_serialized_as_bytes_and_deserialized_from_bytes(
             &ServerCommand::PollMessages(PollMessages::default()),
             POLL_MESSAGES_CODE,
             &PollMessages::default(),
         );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::LongPollMessages(LongPollMessages::default()),
+            LONG_POLL_MESSAGES_CODE,
+            &LongPollMessages::default(),
+        );
         assert_serialized_as_bytes_and_deserialized_from_bytes(
             &ServerCommand::StoreConsumerOffset(StoreConsumerOffset::default()),
             STORE_CONSUMER_OFFSET_CODE,
@@ -325,6 +355,11 @@ mod tests {
             PURGE_TOPIC_CODE,
             &PurgeTopic::default(),
         );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::WatchTopics(WatchTopics::default()),
+            WATCH_TOPICS_CODE,
+            &WatchTopics::default(),
+        );
         assert_serialized_as_bytes_and_deserialized_from_bytes(
             &ServerCommand::CreatePartitions(CreatePartitions::default()),
             CREATE_PARTITIONS_CODE,
@@ -335,6 +370,16 @@ mod tests {
             DELETE_PARTITIONS_CODE,
             &DeletePartitions::default(),
         );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::MovePartition(MovePartition::default()),
+            MOVE_PARTITION_CODE,
+            &MovePartition::default(),
+        );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::ShrinkPartitions(ShrinkPartitions::default()),
+            SHRINK_PARTITIONS_CODE,
+            &ShrinkPartitions::default(),
+        );
         assert_serialized_as_bytes_and_deserialized_from_bytes(
             &ServerCommand::DeleteSegments(DeleteSegments::default()),
             DELETE_SEGMENTS_CODE,
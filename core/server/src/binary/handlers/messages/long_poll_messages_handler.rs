@@ -0,0 +1,106 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::binary::command::{BinaryServerCommand, ServerCommand, ServerCommandHandler};
+use crate::binary::handlers::messages::COMPONENT;
+use crate::binary::handlers::messages::poll_messages_handler::send_polled_messages;
+use crate::binary::handlers::utils::receive_and_validate;
+use crate::binary::sender::SenderKind;
+use crate::streaming::session::Session;
+use crate::streaming::systems::messages::PollingArgs;
+use crate::streaming::systems::system::SharedSystem;
+use anyhow::Result;
+use error_set::ErrContext;
+use iggy_common::{IggyError, LongPollMessages};
+use tokio::time::{Instant, timeout_at};
+use tracing::{debug, trace};
+
+impl ServerCommandHandler for LongPollMessages {
+    fn code(&self) -> u32 {
+        iggy_common::LONG_POLL_MESSAGES_CODE
+    }
+
+    async fn handle(
+        self,
+        sender: &mut SenderKind,
+        _length: u32,
+        session: &Session,
+        system: &SharedSystem,
+    ) -> Result<(), IggyError> {
+        debug!("session: {session}, command: {self}");
+        self.validate_timeout()?;
+        let poll = &self.poll;
+        let deadline = Instant::now() + self.timeout.get_duration();
+        let (metadata, messages) = loop {
+            let system = system.read().await;
+            let notifier = system
+                .get_new_messages_notifier(session, &poll.stream_id, &poll.topic_id)
+                .with_error_context(|error| {
+                    format!(
+                        "{COMPONENT} (error: {error}) - failed to get new messages notifier for stream_id: {}, topic_id: {}, session: {session}.",
+                        poll.stream_id, poll.topic_id
+                    )
+                })?;
+            // Register the interest before polling, so that the messages appended in between are not missed.
+            let notified = notifier.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+
+            let (metadata, messages) = system
+                .poll_messages(
+                    session,
+                    &poll.consumer,
+                    &poll.stream_id,
+                    &poll.topic_id,
+                    poll.partition_id,
+                    PollingArgs::new(poll.strategy, poll.count, poll.auto_commit),
+                )
+                .await
+                .with_error_context(|error| format!(
+                    "{COMPONENT} (error: {error}) - failed to long poll messages for consumer: {}, stream_id: {}, topic_id: {}, partition_id: {:?}, session: {session}.",
+                    poll.consumer, poll.stream_id, poll.topic_id, poll.partition_id
+                ))?;
+            // The system lock must not be held while waiting, otherwise the producers would be blocked.
+            drop(system);
+
+            if !messages.is_empty() || Instant::now() >= deadline {
+                break (metadata, messages);
+            }
+
+            trace!("No messages available, waiting for new messages, session: {session}.");
+            if timeout_at(deadline, notified).await.is_err() {
+                break (metadata, messages);
+            }
+        };
+
+        send_polled_messages(sender, metadata, messages).await
+    }
+}
+
+impl BinaryServerCommand for LongPollMessages {
+    async fn from_sender(
+        sender: &mut SenderKind,
+        code: u32,
+        length: u32,
+    ) -> Result<Self, IggyError> {
+        match receive_and_validate(sender, code, length).await? {
+            ServerCommand::LongPollMessages(long_poll_messages) => Ok(long_poll_messages),
+            _ => Err(IggyError::InvalidCommand),
+        }
+    }
+}
@@ -17,6 +17,7 @@
  */
 
 pub mod flush_unsaved_buffer_handler;
+pub mod long_poll_messages_handler;
 pub mod poll_messages_handler;
 pub mod send_messages_handler;
 
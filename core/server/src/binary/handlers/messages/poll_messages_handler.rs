@@ -20,6 +20,7 @@ use crate::binary::command::{BinaryServerCommand, ServerCommand, ServerCommandHa
 use crate::binary::handlers::messages::COMPONENT;
 use crate::binary::handlers::utils::receive_and_validate;
 use crate::binary::sender::SenderKind;
+use crate::streaming::segments::IggyMessagesBatchSet;
 use crate::streaming::session::Session;
 use crate::streaming::systems::messages::PollingArgs;
 use crate::streaming::systems::system::SharedSystem;
@@ -74,38 +75,46 @@ impl ServerCommandHandler for PollMessages {
                 self.consumer, self.stream_id, self.topic_id, self.partition_id
             ))?;
         drop(system);
+        send_polled_messages(sender, metadata, messages).await
+    }
+}
 
-        // Collect all chunks first into a Vec to extend their lifetimes.
-        // This ensures the Bytes (in reality Arc<[u8]>) references from each IggyMessagesBatch stay alive
-        // throughout the async vectored I/O operation, preventing "borrowed value does not live
-        // long enough" errors while optimizing transmission by using larger chunks.
+/// Sends the polled messages to the client, using the vectored I/O to avoid copying the batches.
+pub(crate) async fn send_polled_messages(
+    sender: &mut SenderKind,
+    metadata: IggyPollMetadata,
+    messages: IggyMessagesBatchSet,
+) -> Result<(), IggyError> {
+    // Collect all chunks first into a Vec to extend their lifetimes.
+    // This ensures the Bytes (in reality Arc<[u8]>) references from each IggyMessagesBatch stay alive
+    // throughout the async vectored I/O operation, preventing "borrowed value does not live
+    // long enough" errors while optimizing transmission by using larger chunks.
 
-        // 4 bytes for partition_id + 8 bytes for current_offset + 4 bytes for messages_count + size of all batches.
-        let response_length = 4 + 8 + 4 + messages.size();
-        let response_length_bytes = response_length.to_le_bytes();
+    // 4 bytes for partition_id + 8 bytes for current_offset + 4 bytes for messages_count + size of all batches.
+    let response_length = 4 + 8 + 4 + messages.size();
+    let response_length_bytes = response_length.to_le_bytes();
 
-        let partition_id = metadata.partition_id.to_le_bytes();
-        let current_offset = metadata.current_offset.to_le_bytes();
-        let count = messages.count().to_le_bytes();
+    let partition_id = metadata.partition_id.to_le_bytes();
+    let current_offset = metadata.current_offset.to_le_bytes();
+    let count = messages.count().to_le_bytes();
 
-        let mut io_slices = Vec::with_capacity(messages.containers_count() + 3);
-        io_slices.push(IoSlice::new(&partition_id));
-        io_slices.push(IoSlice::new(&current_offset));
-        io_slices.push(IoSlice::new(&count));
+    let mut io_slices = Vec::with_capacity(messages.containers_count() + 3);
+    io_slices.push(IoSlice::new(&partition_id));
+    io_slices.push(IoSlice::new(&current_offset));
+    io_slices.push(IoSlice::new(&count));
 
-        io_slices.extend(messages.iter().map(|m| IoSlice::new(m)));
+    io_slices.extend(messages.iter().map(|m| IoSlice::new(m)));
 
-        trace!(
-            "Sending {} messages to client ({} bytes) to client",
-            messages.count(),
-            response_length
-        );
+    trace!(
+        "Sending {} messages to client ({} bytes) to client",
+        messages.count(),
+        response_length
+    );
 
-        sender
-            .send_ok_response_vectored(&response_length_bytes, io_slices)
-            .await?;
-        Ok(())
-    }
+    sender
+        .send_ok_response_vectored(&response_length_bytes, io_slices)
+        .await?;
+    Ok(())
 }
 
 impl BinaryServerCommand for PollMessages {
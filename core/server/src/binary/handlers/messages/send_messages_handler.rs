@@ -26,7 +26,9 @@ use anyhow::Result;
 use iggy_common::INDEX_SIZE;
 use iggy_common::Identifier;
 use iggy_common::Sizeable;
-use iggy_common::{IggyError, Partitioning, SendMessages, Validatable};
+use iggy_common::{
+    IggyError, PRODUCER_SEQUENCE_SIZE, Partitioning, ProducerSequence, SendMessages, Validatable,
+};
 use tracing::instrument;
 
 impl ServerCommandHandler for SendMessages {
@@ -78,6 +80,15 @@ impl ServerCommandHandler for SendMessages {
                 .try_into()
                 .unwrap(),
         );
+        element_size += 4;
+
+        // The producer sequence is optional and sent only by the idempotent producer.
+        if metadata_size as usize >= element_size + PRODUCER_SEQUENCE_SIZE {
+            self.producer_sequence = Some(ProducerSequence::from_raw_bytes(
+                &metadata_buffer[element_size..],
+            )?);
+        }
+
         let indexes_size = messages_count as usize * INDEX_SIZE;
 
         let mut indexes_buffer = PooledBuffer::with_capacity(indexes_size);
@@ -107,6 +118,7 @@ impl ServerCommandHandler for SendMessages {
                 &self.topic_id,
                 &self.partitioning,
                 batch,
+                self.producer_sequence,
                 None,
             )
             .await?;
@@ -18,5 +18,7 @@
 
 pub mod create_partitions_handler;
 pub mod delete_partitions_handler;
+pub mod move_partition_handler;
+pub mod shrink_partitions_handler;
 
 pub const COMPONENT: &str = "PARTITIONS_HANDLER";
@@ -0,0 +1,78 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::binary::command::{BinaryServerCommand, ServerCommand, ServerCommandHandler};
+use crate::binary::handlers::system::COMPONENT;
+use crate::binary::handlers::utils::receive_and_validate;
+use crate::binary::sender::SenderKind;
+use crate::streaming::session::Session;
+use crate::streaming::systems::system::SharedSystem;
+use error_set::ErrContext;
+use iggy_common::IggyError;
+use iggy_common::get_data_snapshot::GetDataSnapshot;
+use tracing::{debug, error};
+
+impl ServerCommandHandler for GetDataSnapshot {
+    fn code(&self) -> u32 {
+        iggy_common::GET_DATA_SNAPSHOT_CODE
+    }
+
+    async fn handle(
+        self,
+        sender: &mut SenderKind,
+        _length: u32,
+        session: &Session,
+        system: &SharedSystem,
+    ) -> Result<(), IggyError> {
+        debug!("session: {session}, command: {self}");
+
+        let system = system.read().await;
+        let mut snapshot = system
+            .get_data_snapshot(session, &self.targets)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to get data snapshot, session: {session}"
+                )
+            })?;
+        drop(system);
+        let Ok(length) = u32::try_from(snapshot.size) else {
+            error!(
+                "Data snapshot of size: {} exceeds the maximum response size, session: {session}",
+                snapshot.size
+            );
+            return Err(IggyError::SnapshotFileCompletionFailed);
+        };
+        sender
+            .send_ok_response_from_file(&mut snapshot.file, length)
+            .await?;
+        Ok(())
+    }
+}
+
+impl BinaryServerCommand for GetDataSnapshot {
+    async fn from_sender(sender: &mut SenderKind, code: u32, length: u32) -> Result<Self, IggyError>
+    where
+        Self: Sized,
+    {
+        match receive_and_validate(sender, code, length).await? {
+            ServerCommand::GetDataSnapshot(get_data_snapshot) => Ok(get_data_snapshot),
+            _ => Err(IggyError::InvalidCommand),
+        }
+    }
+}
@@ -18,9 +18,11 @@
 
 pub mod get_client_handler;
 pub mod get_clients_handler;
+pub mod get_data_snapshot_handler;
 pub mod get_me_handler;
 pub mod get_snapshot;
 pub mod get_stats_handler;
 pub mod ping_handler;
+pub mod restore_data_snapshot_handler;
 
 pub const COMPONENT: &str = "SYSTEM_HANDLER";
@@ -0,0 +1,92 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::binary::command::{BinaryServerCommand, ServerCommandHandler};
+use crate::binary::handlers::system::COMPONENT;
+use crate::binary::sender::SenderKind;
+use crate::streaming::session::Session;
+use crate::streaming::systems::snapshot::{DATA_SNAPSHOT_CHUNK_SIZE, DataSnapshotUpload};
+use crate::streaming::systems::system::SharedSystem;
+use error_set::ErrContext;
+use iggy_common::IggyError;
+use iggy_common::restore_data_snapshot::RestoreDataSnapshot;
+use tracing::debug;
+
+impl ServerCommandHandler for RestoreDataSnapshot {
+    fn code(&self) -> u32 {
+        iggy_common::RESTORE_DATA_SNAPSHOT_CODE
+    }
+
+    async fn handle(
+        self,
+        sender: &mut SenderKind,
+        length: u32,
+        session: &Session,
+        system: &SharedSystem,
+    ) -> Result<(), IggyError> {
+        debug!("session: {session}, command: {self}");
+
+        // The archive is streamed from the sender to the temporary file, instead of being read into the command,
+        // and unpacked before the system gets locked for the restore.
+        let config = system.read().await.config.clone();
+        let mut remaining_size = length as usize - std::mem::size_of::<u32>();
+        let mut upload = DataSnapshotUpload::create(&config).await;
+        let mut chunk = vec![0u8; DATA_SNAPSHOT_CHUNK_SIZE.min(remaining_size)];
+        while remaining_size > 0 {
+            let chunk_size = chunk.len().min(remaining_size);
+            sender.read(&mut chunk[..chunk_size]).await?;
+            remaining_size -= chunk_size;
+            // The remaining payload must be read even if the upload failed, so the next command can be parsed.
+            let written = match &mut upload {
+                Ok(upload) => upload.write(&chunk[..chunk_size]).await,
+                Err(_) => continue,
+            };
+            if let Err(error) = written {
+                upload = Err(error);
+            }
+        }
+
+        let snapshot = upload?.unpack(&config).await.with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to unpack data snapshot, session: {session}"
+            )
+        })?;
+        let mut system = system.write().await;
+        system
+            .restore_data_snapshot(session, snapshot)
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to restore data snapshot, session: {session}")
+            })?;
+        sender.send_empty_ok_response().await?;
+        Ok(())
+    }
+}
+
+impl BinaryServerCommand for RestoreDataSnapshot {
+    async fn from_sender(
+        _sender: &mut SenderKind,
+        _code: u32,
+        _length: u32,
+    ) -> Result<Self, IggyError>
+    where
+        Self: Sized,
+    {
+        Ok(Self::default())
+    }
+}
@@ -58,6 +58,7 @@ impl ServerCommandHandler for CreateTopic {
                     self.compression_algorithm,
                     self.max_topic_size,
                     self.replication_factor,
+                    self.overrides,
                 )
                 .await
                 .with_error_context(|error| format!("{COMPONENT} (error: {error}) - failed to create topic for stream_id: {stream_id}, topic_id: {topic_id:?}"
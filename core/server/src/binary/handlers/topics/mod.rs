@@ -22,5 +22,6 @@ pub mod get_topic_handler;
 pub mod get_topics_handler;
 pub mod purge_topic_handler;
 pub mod update_topic_handler;
+pub mod watch_topics_handler;
 
 pub const COMPONENT: &str = "TOPIC_HANDLER";
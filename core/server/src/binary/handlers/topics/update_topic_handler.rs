@@ -55,6 +55,7 @@ impl ServerCommandHandler for UpdateTopic {
                     self.compression_algorithm,
                     self.max_topic_size,
                     self.replication_factor,
+                    self.overrides,
                 )
                 .await
                 .with_error_context(|error| format!(
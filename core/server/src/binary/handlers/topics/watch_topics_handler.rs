@@ -0,0 +1,88 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::binary::command::{BinaryServerCommand, ServerCommand, ServerCommandHandler};
+use crate::binary::handlers::topics::COMPONENT;
+use crate::binary::handlers::utils::receive_and_validate;
+use crate::binary::sender::SenderKind;
+use crate::streaming::session::Session;
+use crate::streaming::systems::system::SharedSystem;
+use anyhow::Result;
+use error_set::ErrContext;
+use iggy_common::IggyError;
+use iggy_common::watch_topics::WatchTopics;
+use tokio::time::{Instant, timeout_at};
+use tracing::{debug, trace};
+
+impl ServerCommandHandler for WatchTopics {
+    fn code(&self) -> u32 {
+        iggy_common::WATCH_TOPICS_CODE
+    }
+
+    async fn handle(
+        self,
+        sender: &mut SenderKind,
+        _length: u32,
+        session: &Session,
+        system: &SharedSystem,
+    ) -> Result<(), IggyError> {
+        debug!("session: {session}, command: {self}");
+        self.validate_timeout()?;
+        let deadline = Instant::now() + self.timeout.get_duration();
+        let version = loop {
+            let system = system.read().await;
+            let (version, notifier) = system
+                .get_topics_version(session)
+                .with_error_context(|error| {
+                    format!("{COMPONENT} (error: {error}) - failed to get topics version, session: {session}")
+                })?;
+            // Register the interest while holding the system lock, so that the changes made in between are not missed.
+            let notified = notifier.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+            // The system lock must not be held while waiting, otherwise the changes would be blocked.
+            drop(system);
+
+            if version != self.version || Instant::now() >= deadline {
+                break version;
+            }
+
+            trace!(
+                "Topics version: {version} is unchanged, waiting for changes, session: {session}."
+            );
+            if timeout_at(deadline, notified).await.is_err() {
+                break version;
+            }
+        };
+
+        sender.send_ok_response(&version.to_le_bytes()).await?;
+        Ok(())
+    }
+}
+
+impl BinaryServerCommand for WatchTopics {
+    async fn from_sender(sender: &mut SenderKind, code: u32, length: u32) -> Result<Self, IggyError>
+    where
+        Self: Sized,
+    {
+        match receive_and_validate(sender, code, length).await? {
+            ServerCommand::WatchTopics(watch_topics) => Ok(watch_topics),
+            _ => Err(IggyError::InvalidCommand),
+        }
+    }
+}
@@ -21,10 +21,15 @@ use std::io::IoSlice;
 
 use crate::tcp::tcp_sender::TcpSender;
 use crate::tcp::tcp_tls_sender::TcpTlsSender;
+use crate::websocket::websocket_sender::WebSocketSender;
 use crate::{quic::quic_sender::QuicSender, server_error::ServerError};
+use axum::extract::ws::Message;
+use bytes::Bytes;
 use iggy_common::IggyError;
 use quinn::{RecvStream, SendStream};
+use tokio::fs::File;
 use tokio::net::TcpStream;
+use tokio::sync::mpsc;
 use tokio_rustls::server::TlsStream;
 
 macro_rules! forward_async_methods {
@@ -41,6 +46,7 @@ macro_rules! forward_async_methods {
                     Self::Tcp(d) => d.$method_name($( $arg ),*).await,
                     Self::TcpTls(s) => s.$method_name($( $arg ),*).await,
                     Self::Quic(s) => s.$method_name($( $arg ),*).await,
+                    Self::WebSocket(s) => s.$method_name($( $arg ),*).await,
                 }
             }
         )*
@@ -59,6 +65,11 @@ pub trait Sender {
         length: &[u8],
         slices: Vec<IoSlice<'_>>,
     ) -> impl Future<Output = Result<(), IggyError>> + Send;
+    fn send_ok_response_from_file(
+        &mut self,
+        file: &mut File,
+        length: u32,
+    ) -> impl Future<Output = Result<(), IggyError>> + Send;
     fn send_error_response(
         &mut self,
         error: IggyError,
@@ -71,6 +82,7 @@ pub enum SenderKind {
     Tcp(TcpSender),
     TcpTls(TcpTlsSender),
     Quic(QuicSender),
+    WebSocket(WebSocketSender),
 }
 
 impl SenderKind {
@@ -89,11 +101,16 @@ impl SenderKind {
         })
     }
 
+    pub fn get_websocket_sender(request: Bytes, outgoing: mpsc::Sender<Message>) -> Self {
+        Self::WebSocket(WebSocketSender { request, outgoing })
+    }
+
     forward_async_methods! {
         async fn read(&mut self, buffer: &mut [u8]) -> Result<usize, IggyError>;
         async fn send_empty_ok_response(&mut self) -> Result<(), IggyError>;
         async fn send_ok_response(&mut self, payload: &[u8]) -> Result<(), IggyError>;
         async fn send_ok_response_vectored(&mut self, length: &[u8], slices: Vec<IoSlice<'_>>) -> Result<(), IggyError>;
+        async fn send_ok_response_from_file(&mut self, file: &mut File, length: u32) -> Result<(), IggyError>;
         async fn send_error_response(&mut self, error: IggyError) -> Result<(), IggyError>;
         async fn shutdown(&mut self) -> Result<(), ServerError>;
     }
@@ -0,0 +1,182 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::channels::server_command::BackgroundServerCommand;
+use crate::configs::server::PartitionsMaintenanceConfig;
+use crate::state::command::EntryCommand;
+use crate::state::models::DeleteDrainedPartitions;
+use crate::streaming::systems::system::SharedSystem;
+use flume::Sender;
+use iggy_common::defaults::DEFAULT_ROOT_USER_ID;
+use iggy_common::delete_partitions::DeletePartitions;
+use iggy_common::{Identifier, IggyDuration};
+use tokio::time;
+use tracing::{debug, error, info, instrument};
+
+pub struct PartitionsDrainer {
+    interval: IggyDuration,
+    retention: IggyDuration,
+    sender: Sender<DrainPartitionsCommand>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct DrainPartitionsCommand {
+    retention: IggyDuration,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct DrainPartitionsExecutor;
+
+impl PartitionsDrainer {
+    pub fn new(
+        config: &PartitionsMaintenanceConfig,
+        sender: Sender<DrainPartitionsCommand>,
+    ) -> Self {
+        Self {
+            interval: config.interval,
+            retention: config.draining_retention,
+            sender,
+        }
+    }
+
+    pub fn start(&self) {
+        let interval = self.interval;
+        let retention = self.retention;
+        let sender = self.sender.clone();
+        info!(
+            "Draining partitions will be deleted once consumed, or after: {retention} if they have no consumers, checked every: {interval}."
+        );
+        tokio::spawn(async move {
+            let mut interval_timer = time::interval(interval.get_duration());
+            loop {
+                interval_timer.tick().await;
+                sender
+                    .send(DrainPartitionsCommand { retention })
+                    .unwrap_or_else(|err| {
+                        error!("Failed to send DrainPartitionsCommand. Error: {}", err);
+                    });
+            }
+        });
+    }
+}
+
+impl BackgroundServerCommand<DrainPartitionsCommand> for DrainPartitionsExecutor {
+    #[instrument(skip_all, name = "trace_drain_partitions")]
+    async fn execute(&mut self, system: &SharedSystem, command: DrainPartitionsCommand) {
+        // Most of the time there are no partitions being drained, so the system is write-locked only when needed.
+        let mut drained_topics = Vec::new();
+        {
+            let system = system.read().await;
+            for stream in system.get_streams() {
+                for topic in stream.get_topics() {
+                    if topic.get_draining_partitions_count() > 0
+                        && !topic
+                            .get_drained_partition_ids(command.retention)
+                            .await
+                            .is_empty()
+                    {
+                        drained_topics.push((topic.stream_id, topic.topic_id));
+                    }
+                }
+            }
+        }
+
+        if drained_topics.is_empty() {
+            debug!("No drained partitions found.");
+            return;
+        }
+
+        let mut system = system.write().await;
+        for (stream_id, topic_id) in drained_topics {
+            let stream_id = Identifier::numeric(stream_id).unwrap();
+            let topic_id = Identifier::numeric(topic_id).unwrap();
+            let partition_ids = match system
+                .get_drained_partition_ids(&stream_id, &topic_id, command.retention)
+                .await
+            {
+                Ok(partition_ids) if !partition_ids.is_empty() => partition_ids,
+                _ => continue,
+            };
+            let partitions_count = partition_ids.len() as u32;
+
+            // The deletion is applied to the state first, so if it fails, the partitions remain draining
+            // and their deletion is retried on the next run. The entry stores the IDs of the partitions,
+            // so when it's applied again for the retried deletion, only the remaining partitions are deleted.
+            if let Err(error) = system
+                .state
+                .apply(
+                    DEFAULT_ROOT_USER_ID,
+                    &EntryCommand::DeleteDrainedPartitions(DeleteDrainedPartitions {
+                        command: DeletePartitions {
+                            stream_id: stream_id.clone(),
+                            topic_id: topic_id.clone(),
+                            partitions_count,
+                        },
+                        partition_ids: partition_ids.clone(),
+                    }),
+                )
+                .await
+            {
+                error!(
+                    "Failed to apply delete drained partitions for stream ID: {stream_id}, topic ID: {topic_id}, it will be retried. Error: {error}"
+                );
+                continue;
+            }
+
+            if let Err(error) = system
+                .delete_drained_partitions(&stream_id, &topic_id, &partition_ids)
+                .await
+            {
+                error!(
+                    "Failed to delete drained partitions for stream ID: {stream_id}, topic ID: {topic_id}. Error: {error}"
+                );
+                continue;
+            }
+
+            info!(
+                "Deleted {partitions_count} drained partition(s) for stream ID: {stream_id}, topic ID: {topic_id}."
+            );
+        }
+    }
+
+    fn start_command_sender(
+        &mut self,
+        _system: SharedSystem,
+        config: &crate::configs::server::ServerConfig,
+        sender: Sender<DrainPartitionsCommand>,
+    ) {
+        let partitions_drainer =
+            PartitionsDrainer::new(&config.data_maintenance.partitions, sender);
+        partitions_drainer.start();
+    }
+
+    fn start_command_consumer(
+        mut self,
+        system: SharedSystem,
+        _config: &crate::configs::server::ServerConfig,
+        receiver: flume::Receiver<DrainPartitionsCommand>,
+    ) {
+        tokio::spawn(async move {
+            let system = system.clone();
+            while let Ok(command) = receiver.recv_async().await {
+                self.execute(&system, command).await;
+            }
+            info!("Partitions drainer receiver stopped.");
+        });
+    }
+}
@@ -18,6 +18,7 @@
 
 pub mod archive_state;
 pub mod clean_personal_access_tokens;
+pub mod drain_partitions;
 pub mod maintain_messages;
 pub mod print_sysinfo;
 pub mod save_messages;
@@ -22,7 +22,7 @@ use std::str::FromStr;
 use strum::Display;
 
 #[serde_as]
-#[derive(Debug, Serialize, Display, Default, PartialEq)]
+#[derive(Debug, Clone, Serialize, Display, Default, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum CacheIndexesConfig {
     All,
@@ -20,13 +20,16 @@ use super::system::MemoryPoolConfig;
 use super::tcp::TcpSocketConfig;
 use crate::configs::http::{
     HttpConfig, HttpCorsConfig, HttpJwtConfig, HttpMetricsConfig, HttpTlsConfig,
+    HttpWebSocketConfig,
 };
+use crate::configs::kafka::KafkaConfig;
+use crate::configs::mqtt::MqttConfig;
 use crate::configs::quic::{QuicCertificateConfig, QuicConfig};
 use crate::configs::server::{
     ArchiverConfig, DataMaintenanceConfig, HeartbeatConfig, MessageSaverConfig,
-    MessagesMaintenanceConfig, PersonalAccessTokenCleanerConfig, PersonalAccessTokenConfig,
-    ServerConfig, StateMaintenanceConfig, TelemetryConfig, TelemetryLogsConfig,
-    TelemetryTracesConfig,
+    MessagesMaintenanceConfig, PartitionsMaintenanceConfig, PersonalAccessTokenCleanerConfig,
+    PersonalAccessTokenConfig, ServerConfig, StateMaintenanceConfig, TelemetryConfig,
+    TelemetryLogsConfig, TelemetryTracesConfig,
 };
 use crate::configs::system::{
     BackupConfig, CompatibilityConfig, CompressionConfig, EncryptionConfig, LoggingConfig,
@@ -55,6 +58,8 @@ impl Default for ServerConfig {
             quic: QuicConfig::default(),
             tcp: TcpConfig::default(),
             http: HttpConfig::default(),
+            kafka: KafkaConfig::default(),
+            mqtt: MqttConfig::default(),
             telemetry: TelemetryConfig::default(),
         }
     }
@@ -91,6 +96,25 @@ impl Default for MessagesMaintenanceConfig {
     }
 }
 
+impl Default for PartitionsMaintenanceConfig {
+    fn default() -> PartitionsMaintenanceConfig {
+        PartitionsMaintenanceConfig {
+            interval: SERVER_CONFIG
+                .data_maintenance
+                .partitions
+                .interval
+                .parse()
+                .unwrap(),
+            draining_retention: SERVER_CONFIG
+                .data_maintenance
+                .partitions
+                .draining_retention
+                .parse()
+                .unwrap(),
+        }
+    }
+}
+
 impl Default for StateMaintenanceConfig {
     fn default() -> StateMaintenanceConfig {
         StateMaintenanceConfig {
@@ -127,6 +151,29 @@ impl Default for QuicConfig {
     }
 }
 
+impl Default for KafkaConfig {
+    fn default() -> KafkaConfig {
+        KafkaConfig {
+            enabled: SERVER_CONFIG.kafka.enabled,
+            address: SERVER_CONFIG.kafka.address.parse().unwrap(),
+            advertised_address: SERVER_CONFIG.kafka.advertised_address.parse().unwrap(),
+            max_request_size: SERVER_CONFIG.kafka.max_request_size.parse().unwrap(),
+        }
+    }
+}
+
+impl Default for MqttConfig {
+    fn default() -> MqttConfig {
+        MqttConfig {
+            enabled: SERVER_CONFIG.mqtt.enabled,
+            address: SERVER_CONFIG.mqtt.address.parse().unwrap(),
+            max_packet_size: SERVER_CONFIG.mqtt.max_packet_size.parse().unwrap(),
+            // There are no mappings configured by default, so their type can't be inferred from server.toml.
+            mappings: Vec::new(),
+        }
+    }
+}
+
 impl Default for QuicCertificateConfig {
     fn default() -> QuicCertificateConfig {
         QuicCertificateConfig {
@@ -183,6 +230,7 @@ impl Default for HttpConfig {
             jwt: HttpJwtConfig::default(),
             metrics: HttpMetricsConfig::default(),
             tls: HttpTlsConfig::default(),
+            websocket: HttpWebSocketConfig::default(),
         }
     }
 }
@@ -274,6 +322,22 @@ impl Default for HttpTlsConfig {
     }
 }
 
+impl Default for HttpWebSocketConfig {
+    fn default() -> HttpWebSocketConfig {
+        HttpWebSocketConfig {
+            enabled: SERVER_CONFIG.http.websocket.enabled,
+            max_message_size: SERVER_CONFIG
+                .http
+                .websocket
+                .max_message_size
+                .parse()
+                .unwrap(),
+            poll_interval: SERVER_CONFIG.http.websocket.poll_interval.parse().unwrap(),
+            max_subscriptions: SERVER_CONFIG.http.websocket.max_subscriptions as u32,
+        }
+    }
+}
+
 impl Default for MessageSaverConfig {
     fn default() -> MessageSaverConfig {
         MessageSaverConfig {
@@ -440,6 +504,15 @@ impl Default for PartitionConfig {
                 as u32,
             enforce_fsync: SERVER_CONFIG.system.partition.enforce_fsync,
             validate_checksum: SERVER_CONFIG.system.partition.validate_checksum,
+            producer_state_expiry: SERVER_CONFIG
+                .system
+                .partition
+                .producer_state_expiry
+                .parse()
+                .unwrap(),
+            // There are no additional directories configured by default, so their type can't be inferred from server.toml.
+            directories: Vec::new(),
+            placement: SERVER_CONFIG.system.partition.placement.parse().unwrap(),
         }
     }
 }
@@ -16,15 +16,20 @@
  * under the License.
  */
 
+use crate::configs::kafka::KafkaConfig;
+use crate::configs::mqtt::MqttConfig;
 use crate::configs::quic::{QuicCertificateConfig, QuicConfig};
 use crate::configs::server::{
     ArchiverConfig, DataMaintenanceConfig, DiskArchiverConfig, HeartbeatConfig,
-    MessagesMaintenanceConfig, S3ArchiverConfig, StateMaintenanceConfig, TelemetryConfig,
-    TelemetryLogsConfig, TelemetryTracesConfig,
+    MessagesMaintenanceConfig, PartitionsMaintenanceConfig, S3ArchiverConfig,
+    StateMaintenanceConfig, TelemetryConfig, TelemetryLogsConfig, TelemetryTracesConfig,
 };
 use crate::configs::system::MessageDeduplicationConfig;
 use crate::configs::{
-    http::{HttpConfig, HttpCorsConfig, HttpJwtConfig, HttpMetricsConfig, HttpTlsConfig},
+    http::{
+        HttpConfig, HttpCorsConfig, HttpJwtConfig, HttpMetricsConfig, HttpTlsConfig,
+        HttpWebSocketConfig,
+    },
     server::{MessageSaverConfig, ServerConfig},
     system::{
         CompressionConfig, EncryptionConfig, LoggingConfig, PartitionConfig, SegmentConfig,
@@ -38,14 +43,15 @@ impl Display for HttpConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{{ enabled: {}, address: {}, max_request_size: {}, cors: {}, jwt: {}, metrics: {}, tls: {} }}",
+            "{{ enabled: {}, address: {}, max_request_size: {}, cors: {}, jwt: {}, metrics: {}, tls: {}, websocket: {} }}",
             self.enabled,
             self.address,
             self.max_request_size,
             self.cors,
             self.jwt,
             self.metrics,
-            self.tls
+            self.tls,
+            self.websocket
         )
     }
 }
@@ -96,6 +102,16 @@ impl Display for HttpTlsConfig {
     }
 }
 
+impl Display for HttpWebSocketConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{{ enabled: {}, max_message_size: {}, poll_interval: {}, max_subscriptions: {} }}",
+            self.enabled, self.max_message_size, self.poll_interval, self.max_subscriptions
+        )
+    }
+}
+
 impl Display for QuicConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -115,6 +131,32 @@ impl Display for QuicConfig {
     }
 }
 
+impl Display for KafkaConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{{ enabled: {}, address: {}, advertised_address: {}, max_request_size: {} }}",
+            self.enabled, self.address, self.advertised_address, self.max_request_size
+        )
+    }
+}
+
+impl Display for MqttConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{{ enabled: {}, address: {}, max_packet_size: {}, mappings: {:?} }}",
+            self.enabled,
+            self.address,
+            self.max_packet_size,
+            self.mappings
+                .iter()
+                .map(|mapping| mapping.to_string())
+                .collect::<Vec<_>>()
+        )
+    }
+}
+
 impl Display for QuicCertificateConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -139,8 +181,8 @@ impl Display for DataMaintenanceConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{{ archiver: {}, messages: {}, state: {} }}",
-            self.archiver, self.messages, self.state
+            "{{ archiver: {}, messages: {}, state: {}, partitions: {} }}",
+            self.archiver, self.messages, self.state, self.partitions
         )
     }
 }
@@ -192,6 +234,16 @@ impl Display for MessagesMaintenanceConfig {
     }
 }
 
+impl Display for PartitionsMaintenanceConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{{ interval: {}, draining_retention: {} }}",
+            self.interval, self.draining_retention
+        )
+    }
+}
+
 impl Display for StateMaintenanceConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -206,7 +258,7 @@ impl Display for ServerConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{{ data_maintenance: {}, message_saver: {}, heartbeat: {}, system: {}, quic: {}, tcp: {}, http: {}, telemetry: {} }}",
+            "{{ data_maintenance: {}, message_saver: {}, heartbeat: {}, system: {}, quic: {}, tcp: {}, http: {}, kafka: {}, mqtt: {}, telemetry: {} }}",
             self.data_maintenance,
             self.message_saver,
             self.heartbeat,
@@ -214,6 +266,8 @@ impl Display for ServerConfig {
             self.quic,
             self.tcp,
             self.http,
+            self.kafka,
+            self.mqtt,
             self.telemetry
         )
     }
@@ -265,12 +319,15 @@ impl Display for PartitionConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{{ path: {}, messages_required_to_save: {}, size_of_messages_required_to_save: {}, enforce_fsync: {}, validate_checksum: {} }}",
+            "{{ path: {}, messages_required_to_save: {}, size_of_messages_required_to_save: {}, enforce_fsync: {}, validate_checksum: {}, producer_state_expiry: {}, directories: {:?}, placement: {} }}",
             self.path,
             self.messages_required_to_save,
             self.size_of_messages_required_to_save,
             self.enforce_fsync,
-            self.validate_checksum
+            self.validate_checksum,
+            self.producer_state_expiry,
+            self.directories,
+            self.placement
         )
     }
 }
@@ -34,6 +34,7 @@ pub struct HttpConfig {
     pub jwt: HttpJwtConfig,
     pub metrics: HttpMetricsConfig,
     pub tls: HttpTlsConfig,
+    pub websocket: HttpWebSocketConfig,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -72,6 +73,16 @@ pub struct HttpMetricsConfig {
     pub endpoint: String,
 }
 
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct HttpWebSocketConfig {
+    pub enabled: bool,
+    pub max_message_size: IggyByteSize,
+    #[serde_as(as = "DisplayFromStr")]
+    pub poll_interval: IggyDuration,
+    pub max_subscriptions: u32,
+}
+
 #[derive(Debug)]
 pub enum JwtSecret {
     Default(String),
@@ -21,6 +21,9 @@ pub mod config_provider;
 pub mod defaults;
 pub mod displays;
 pub mod http;
+pub mod kafka;
+pub mod mqtt;
+pub mod partition_placement;
 pub mod quic;
 pub mod server;
 pub mod system;
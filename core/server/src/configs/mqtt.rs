@@ -0,0 +1,74 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::mqtt::topics::is_valid_topic_filter;
+use iggy_common::IggyByteSize;
+use serde::{Deserialize, Serialize};
+use serde_with::DisplayFromStr;
+use serde_with::serde_as;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct MqttConfig {
+    pub enabled: bool,
+    pub address: String,
+    pub max_packet_size: IggyByteSize,
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub mappings: Vec<MqttTopicMapping>,
+}
+
+/// Maps the MQTT topics matching the filter onto the Iggy topic of the stream,
+/// written as `<topic filter>=<stream name>/<topic name>`, e.g. `devices/+/telemetry=iot/telemetry`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MqttTopicMapping {
+    pub filter: String,
+    pub stream: String,
+    pub topic: String,
+}
+
+impl FromStr for MqttTopicMapping {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid_mapping = || {
+            format!(
+                "Invalid MQTT topic mapping: '{value}', expected format: <topic filter>=<stream>/<topic>"
+            )
+        };
+        let (filter, destination) = value.rsplit_once('=').ok_or_else(invalid_mapping)?;
+        let (stream, topic) = destination.split_once('/').ok_or_else(invalid_mapping)?;
+        let (filter, stream, topic) = (filter.trim(), stream.trim(), topic.trim());
+        if !is_valid_topic_filter(filter) || stream.is_empty() || topic.is_empty() {
+            return Err(invalid_mapping());
+        }
+
+        Ok(Self {
+            filter: filter.to_owned(),
+            stream: stream.to_owned(),
+            topic: topic.to_owned(),
+        })
+    }
+}
+
+impl Display for MqttTopicMapping {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}={}/{}", self.filter, self.stream, self.topic)
+    }
+}
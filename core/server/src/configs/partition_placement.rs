@@ -0,0 +1,45 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use strum::Display;
+
+/// Policy used to pick the data directory for the newly created partitions.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Display, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum PartitionPlacement {
+    /// Partitions are spread evenly across the data directories by their IDs.
+    #[default]
+    RoundRobin,
+    /// Partitions are placed in the data directory with the most available space.
+    MostFreeSpace,
+}
+
+impl FromStr for PartitionPlacement {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "round_robin" => Ok(PartitionPlacement::RoundRobin),
+            "most_free_space" => Ok(PartitionPlacement::MostFreeSpace),
+            _ => Err(format!("Invalid PartitionPlacement: {s}")),
+        }
+    }
+}
@@ -20,6 +20,8 @@ use crate::archiver::ArchiverKindType;
 use crate::configs::COMPONENT;
 use crate::configs::config_provider::ConfigProviderKind;
 use crate::configs::http::HttpConfig;
+use crate::configs::kafka::KafkaConfig;
+use crate::configs::mqtt::MqttConfig;
 use crate::configs::quic::QuicConfig;
 use crate::configs::system::SystemConfig;
 use crate::configs::tcp::TcpConfig;
@@ -44,6 +46,8 @@ pub struct ServerConfig {
     pub quic: QuicConfig,
     pub tcp: TcpConfig,
     pub http: HttpConfig,
+    pub kafka: KafkaConfig,
+    pub mqtt: MqttConfig,
     pub telemetry: TelemetryConfig,
 }
 
@@ -53,6 +57,7 @@ pub struct DataMaintenanceConfig {
     pub archiver: ArchiverConfig,
     pub messages: MessagesMaintenanceConfig,
     pub state: StateMaintenanceConfig,
+    pub partitions: PartitionsMaintenanceConfig,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -81,6 +86,15 @@ pub struct StateMaintenanceConfig {
     pub interval: IggyDuration,
 }
 
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct PartitionsMaintenanceConfig {
+    #[serde_as(as = "DisplayFromStr")]
+    pub interval: IggyDuration,
+    #[serde_as(as = "DisplayFromStr")]
+    pub draining_retention: IggyDuration,
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct DiskArchiverConfig {
     pub path: String,
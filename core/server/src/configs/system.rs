@@ -17,6 +17,7 @@
  */
 
 use super::cache_indexes::CacheIndexesConfig;
+use super::partition_placement::PartitionPlacement;
 use iggy_common::Confirmation;
 use iggy_common::IggyByteSize;
 use iggy_common::IggyExpiry;
@@ -26,7 +27,7 @@ use serde::{Deserialize, Serialize};
 use serde_with::DisplayFromStr;
 use serde_with::serde_as;
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct SystemConfig {
     pub path: String,
     pub backup: BackupConfig,
@@ -44,35 +45,35 @@ pub struct SystemConfig {
     pub memory_pool: MemoryPoolConfig,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct BackupConfig {
     pub path: String,
     pub compatibility: CompatibilityConfig,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct CompatibilityConfig {
     pub path: String,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct DatabaseConfig {
     pub path: String,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct RuntimeConfig {
     pub path: String,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct CompressionConfig {
     pub allow_override: bool,
     pub default_algorithm: CompressionAlgorithm,
 }
 
 #[serde_as]
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct LoggingConfig {
     pub path: String,
     pub level: String,
@@ -83,19 +84,19 @@ pub struct LoggingConfig {
     pub sysinfo_print_interval: IggyDuration,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct EncryptionConfig {
     pub enabled: bool,
     pub key: String,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct StreamConfig {
     pub path: String,
 }
 
 #[serde_as]
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct TopicConfig {
     pub path: String,
     #[serde_as(as = "DisplayFromStr")]
@@ -103,17 +104,24 @@ pub struct TopicConfig {
     pub delete_oldest_segments: bool,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[serde_as]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct PartitionConfig {
     pub path: String,
     pub messages_required_to_save: u32,
     pub size_of_messages_required_to_save: IggyByteSize,
     pub enforce_fsync: bool,
     pub validate_checksum: bool,
+    #[serde_as(as = "DisplayFromStr")]
+    pub producer_state_expiry: IggyDuration,
+    #[serde(default)]
+    pub directories: Vec<String>,
+    #[serde(default)]
+    pub placement: PartitionPlacement,
 }
 
 #[serde_as]
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct MessageDeduplicationConfig {
     pub enabled: bool,
     pub max_entries: u64,
@@ -121,12 +129,12 @@ pub struct MessageDeduplicationConfig {
     pub expiry: IggyDuration,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct RecoveryConfig {
     pub recreate_missing_state: bool,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct MemoryPoolConfig {
     pub enabled: bool,
     pub size: IggyByteSize,
@@ -134,7 +142,7 @@ pub struct MemoryPoolConfig {
 }
 
 #[serde_as]
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct SegmentConfig {
     pub size: IggyByteSize,
     pub cache_indexes: CacheIndexesConfig,
@@ -146,7 +154,7 @@ pub struct SegmentConfig {
 }
 
 #[serde_as]
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct StateConfig {
     pub enforce_fsync: bool,
     pub max_file_operation_retries: u32,
@@ -159,6 +167,21 @@ impl SystemConfig {
         self.path.to_string()
     }
 
+    /// Returns all the data directories in which the partitions can be placed, starting with the system path.
+    pub fn get_data_directories(&self) -> Vec<String> {
+        let mut directories = Vec::with_capacity(1 + self.partition.directories.len());
+        directories.push(self.get_system_path());
+        directories.extend(self.partition.directories.iter().cloned());
+        directories
+    }
+
+    /// Returns the copy of the config with the partitions data placed in the given data directory.
+    pub fn with_data_directory(&self, directory: &str) -> SystemConfig {
+        let mut config = self.clone();
+        config.path = directory.to_owned();
+        config
+    }
+
     pub fn get_state_path(&self) -> String {
         format!("{}/state", self.get_system_path())
     }
@@ -229,6 +252,25 @@ impl SystemConfig {
         )
     }
 
+    pub fn get_producers_path(&self, stream_id: u32, topic_id: u32, partition_id: u32) -> String {
+        format!(
+            "{}/producers",
+            self.get_partition_path(stream_id, topic_id, partition_id)
+        )
+    }
+
+    pub fn get_copied_offsets_path(
+        &self,
+        stream_id: u32,
+        topic_id: u32,
+        partition_id: u32,
+    ) -> String {
+        format!(
+            "{}/copied",
+            self.get_partition_path(stream_id, topic_id, partition_id)
+        )
+    }
+
     pub fn get_consumer_offsets_path(
         &self,
         stream_id: u32,
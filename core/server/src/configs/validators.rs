@@ -20,15 +20,19 @@ extern crate sysinfo;
 
 use super::server::{
     ArchiverConfig, DataMaintenanceConfig, MessageSaverConfig, MessagesMaintenanceConfig,
-    StateMaintenanceConfig, TelemetryConfig,
+    PartitionsMaintenanceConfig, StateMaintenanceConfig, TelemetryConfig,
 };
 use super::system::{CompressionConfig, MemoryPoolConfig, PartitionConfig};
 use crate::archiver::ArchiverKindType;
 use crate::configs::COMPONENT;
+use crate::configs::kafka::KafkaConfig;
+use crate::configs::mqtt::MqttConfig;
 use crate::configs::server::{PersonalAccessTokenConfig, ServerConfig};
 use crate::configs::system::SegmentConfig;
+use crate::mqtt::protocol::MQTT_MAX_PACKET_SIZE;
 use crate::server_error::ConfigError;
 use crate::streaming::segments::*;
+use ahash::AHashSet;
 use error_set::ErrContext;
 use iggy_common::CompressionAlgorithm;
 use iggy_common::IggyExpiry;
@@ -68,6 +72,12 @@ impl Validatable<ConfigError> for ServerConfig {
         self.telemetry.validate().with_error_context(|error| {
             format!("{COMPONENT} (error: {error}) - failed to validate telemetry config")
         })?;
+        self.kafka.validate().with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to validate Kafka config")
+        })?;
+        self.mqtt.validate().with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to validate MQTT config")
+        })?;
 
         let topic_size = match self.system.topic.max_size {
             MaxTopicSize::Custom(size) => Ok(size.as_bytes_u64()),
@@ -89,6 +99,20 @@ impl Validatable<ConfigError> for ServerConfig {
             return Err(ConfigError::InvalidConfiguration);
         }
 
+        let data_directories = self.system.get_data_directories();
+        let unique_data_directories = data_directories.iter().collect::<AHashSet<_>>();
+        if data_directories
+            .iter()
+            .any(|directory| directory.is_empty())
+            || unique_data_directories.len() != data_directories.len()
+        {
+            eprintln!(
+                "Configured system.partition.directories {:?} must be non-empty and distinct from each other and from system.path {}",
+                self.system.partition.directories, self.system.path
+            );
+            return Err(ConfigError::InvalidConfiguration);
+        }
+
         Ok(())
     }
 }
@@ -167,6 +191,57 @@ impl Validatable<ConfigError> for PartitionConfig {
     }
 }
 
+impl Validatable<ConfigError> for KafkaConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        if !self.enabled {
+            return Ok(());
+        }
+
+        if self.max_request_size.as_bytes_u64() == 0 {
+            eprintln!("Configured kafka.max_request_size cannot be 0");
+            return Err(ConfigError::InvalidConfiguration);
+        }
+
+        if self.advertised_address.is_empty() {
+            return Ok(());
+        }
+
+        let is_valid = self
+            .advertised_address
+            .rsplit_once(':')
+            .map(|(host, port)| !host.is_empty() && port.parse::<u16>().is_ok())
+            .unwrap_or(false);
+        if !is_valid {
+            eprintln!(
+                "Configured kafka.advertised_address '{}' is invalid, expected format: <host>:<port>",
+                self.advertised_address
+            );
+            return Err(ConfigError::InvalidConfiguration);
+        }
+
+        Ok(())
+    }
+}
+
+impl Validatable<ConfigError> for MqttConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        if !self.enabled {
+            return Ok(());
+        }
+
+        let max_packet_size = self.max_packet_size.as_bytes_u64();
+        if max_packet_size == 0 || max_packet_size > MQTT_MAX_PACKET_SIZE as u64 {
+            eprintln!(
+                "Configured mqtt.max_packet_size {} must be greater than 0 and not greater than {MQTT_MAX_PACKET_SIZE} bytes",
+                self.max_packet_size
+            );
+            return Err(ConfigError::InvalidConfiguration);
+        }
+
+        Ok(())
+    }
+}
+
 impl Validatable<ConfigError> for SegmentConfig {
     fn validate(&self) -> Result<(), ConfigError> {
         if self.size > SEGMENT_MAX_SIZE_BYTES {
@@ -211,6 +286,11 @@ impl Validatable<ConfigError> for DataMaintenanceConfig {
         self.state.validate().with_error_context(|error| {
             format!("{COMPONENT} (error: {error}) - failed to validate state maintenance config")
         })?;
+        self.partitions.validate().with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to validate partitions maintenance config"
+            )
+        })?;
         Ok(())
     }
 }
@@ -276,6 +356,16 @@ impl Validatable<ConfigError> for MessagesMaintenanceConfig {
     }
 }
 
+impl Validatable<ConfigError> for PartitionsMaintenanceConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        if self.interval.is_zero() {
+            return Err(ConfigError::InvalidConfiguration);
+        }
+
+        Ok(())
+    }
+}
+
 impl Validatable<ConfigError> for StateMaintenanceConfig {
     fn validate(&self) -> Result<(), ConfigError> {
         if self.archiver_enabled && self.interval.is_zero() {
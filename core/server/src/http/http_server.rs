@@ -54,7 +54,13 @@ pub async fn start(config: HttpConfig, system: SharedSystem) -> SocketAddr {
         .merge(consumer_groups::router(app_state.clone()))
         .merge(consumer_offsets::router(app_state.clone()))
         .merge(partitions::router(app_state.clone()))
-        .merge(messages::router(app_state.clone()))
+        .merge(messages::router(app_state.clone()));
+
+    if config.websocket.enabled {
+        app = app.merge(websocket::router(app_state.clone(), &config.websocket));
+    }
+
+    app = app
         .layer(DefaultBodyLimit::max(
             config.max_request_size.as_bytes_u64() as usize,
         ))
@@ -17,7 +17,9 @@
  */
 
 use crate::http::jwt::json_web_token::Identity;
+use crate::http::messages::STREAM_MESSAGES_PATH_SUFFIX;
 use crate::http::shared::{AppState, RequestDetails};
+use crate::http::websocket::WEBSOCKET_PATH;
 use axum::body::Body;
 use axum::{
     extract::State,
@@ -26,11 +28,13 @@ use axum::{
     response::Response,
 };
 use error_set::ErrContext;
+use std::net::SocketAddr;
 use std::sync::Arc;
 
 const COMPONENT: &str = "JWT_MIDDLEWARE";
 const AUTHORIZATION: &str = "authorization";
 const BEARER: &str = "Bearer ";
+const ACCESS_TOKEN_QUERY: &str = "access_token";
 const UNAUTHORIZED: StatusCode = StatusCode::UNAUTHORIZED;
 
 const PUBLIC_PATHS: &[&str] = &[
@@ -52,6 +56,41 @@ pub async fn jwt_auth(
         return Ok(next.run(request).await);
     }
 
+    let ip_address = request
+        .extensions()
+        .get::<RequestDetails>()
+        .unwrap()
+        .ip_address;
+
+    // The WebSocket clients (e.g. browsers) might not be able to set the Authorization header,
+    // thus the token can be also provided via query, or omitted entirely to log in later
+    // using the binary protocol over the established connection.
+    if request.uri().path() == WEBSOCKET_PATH {
+        let jwt_token = match request.headers().get(AUTHORIZATION) {
+            Some(_) => Some(get_bearer_token(&request)?.to_owned()),
+            None => get_query_token(&request),
+        };
+        if let Some(jwt_token) = jwt_token {
+            let identity = authenticate(&state, &jwt_token, ip_address).await?;
+            request.extensions_mut().insert(identity);
+        }
+        return Ok(next.run(request).await);
+    }
+
+    // The same applies to the Server-Sent Events (e.g. browser EventSource), however, the token is required.
+    let jwt_token = if request.uri().path().ends_with(STREAM_MESSAGES_PATH_SUFFIX)
+        && request.headers().get(AUTHORIZATION).is_none()
+    {
+        get_query_token(&request).ok_or(UNAUTHORIZED)?
+    } else {
+        get_bearer_token(&request)?.to_owned()
+    };
+    let identity = authenticate(&state, &jwt_token, ip_address).await?;
+    request.extensions_mut().insert(identity);
+    Ok(next.run(request).await)
+}
+
+fn get_bearer_token(request: &Request<Body>) -> Result<&str, StatusCode> {
     let bearer = request
         .headers()
         .get(AUTHORIZATION)
@@ -69,7 +108,22 @@ pub async fn jwt_auth(
         return Err(StatusCode::UNAUTHORIZED);
     }
 
-    let jwt_token = &bearer[BEARER.len()..];
+    Ok(&bearer[BEARER.len()..])
+}
+
+fn get_query_token(request: &Request<Body>) -> Option<String> {
+    request.uri().query()?.split('&').find_map(|pair| {
+        pair.strip_prefix(ACCESS_TOKEN_QUERY)
+            .and_then(|value| value.strip_prefix('='))
+            .map(|value| value.to_owned())
+    })
+}
+
+async fn authenticate(
+    state: &AppState,
+    jwt_token: &str,
+    ip_address: SocketAddr,
+) -> Result<Identity, StatusCode> {
     let token_header = jsonwebtoken::decode_header(jwt_token)
         .with_error_context(|error| {
             format!("{COMPONENT} (error: {error}) - failed to decode JWT header")
@@ -90,13 +144,10 @@ pub async fn jwt_auth(
         return Err(StatusCode::UNAUTHORIZED);
     }
 
-    let request_details = request.extensions().get::<RequestDetails>().unwrap();
-    let identity = Identity {
+    Ok(Identity {
         token_id: jwt_claims.claims.jti,
         token_expiry: jwt_claims.claims.exp,
         user_id: jwt_claims.claims.sub,
-        ip_address: request_details.ip_address,
-    };
-    request.extensions_mut().insert(identity);
-    Ok(next.run(request).await)
+        ip_address,
+    })
 }
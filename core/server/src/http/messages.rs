@@ -17,24 +17,53 @@
  */
 
 use crate::http::COMPONENT;
-use crate::http::error::CustomError;
+use crate::http::error::{CustomError, ErrorResponse};
 use crate::http::jwt::json_web_token::Identity;
 use crate::http::shared::AppState;
+use crate::streaming::clients::client_manager::Transport;
 use crate::streaming::segments::{IggyIndexesMut, IggyMessagesBatchMut};
 use crate::streaming::session::Session;
 use crate::streaming::systems::messages::PollingArgs;
+use crate::streaming::systems::system::SharedSystem;
 use crate::streaming::utils::PooledBuffer;
+use crate::websocket::subscriptions::next_strategy;
 use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
+use axum::response::sse::{Event, KeepAlive, Sse};
 use axum::routing::get;
 use axum::{Extension, Json, Router};
 use error_set::ErrContext;
-use iggy_common::Identifier;
-use iggy_common::IggyMessagesBatch;
-use iggy_common::Validatable;
+use futures::Stream;
+use iggy_common::locking::IggySharedMutFn;
 use iggy_common::{Consumer, PollMessages, PolledMessages, SendMessages};
+use iggy_common::{ConsumerKind, Identifier, IggyError, IggyMessage, PollingKind};
+use iggy_common::{IggyMessagesBatch, Validatable};
+use serde::{Deserialize, Serialize};
+use std::convert::Infallible;
 use std::sync::Arc;
-use tracing::instrument;
+use std::time::Duration;
+use tokio::sync::{Notify, mpsc};
+use tracing::{debug, error, instrument};
+
+pub const STREAM_MESSAGES_PATH_SUFFIX: &str = "/messages/stream";
+const STREAM_EVENTS_CAPACITY: usize = 1024;
+/// Interval at which the idle stream polls the partition again, even if no new messages were appended,
+/// e.g. to pick up the partitions reassigned to the consumer group member.
+const STREAM_IDLE_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Default, Deserialize)]
+struct StreamMessagesQuery {
+    #[serde(default)]
+    consumer_kind: ConsumerKind,
+}
+
+#[derive(Debug, Serialize)]
+struct StreamedMessage<'a> {
+    partition_id: u32,
+    current_offset: u64,
+    #[serde(flatten)]
+    message: &'a IggyMessage,
+}
 
 pub fn router(state: Arc<AppState>) -> Router {
     Router::new()
@@ -46,6 +75,10 @@ pub fn router(state: Arc<AppState>) -> Router {
             "/streams/{stream_id}/topics/{topic_id}/messages/flush/{partition_id}/{fsync}",
             get(flush_unsaved_buffer),
         )
+        .route(
+            "/streams/{stream_id}/topics/{topic_id}/messages/stream",
+            get(stream_messages),
+        )
         .with_state(state)
 }
 
@@ -105,6 +138,7 @@ async fn send_messages(
             &command_topic_id,
             &partitioning,
             batch,
+            command.producer_sequence,
             None,
         )
         .await
@@ -137,6 +171,181 @@ async fn flush_unsaved_buffer(
     Ok(StatusCode::OK)
 }
 
+/// Streams the messages as Server-Sent Events, starting from the provided polling strategy.
+///
+/// Every message is emitted as a separate `message` event (with the offset used as the event ID),
+/// and the stream is kept open waiting for the new messages until the client disconnects.
+/// The connection is registered as an HTTP client, so that it can join the consumer group
+/// (`consumer_kind=consumer_group`) and have its partitions assigned for the lifetime of the stream.
+async fn stream_messages(
+    State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<Identity>,
+    Path((stream_id, topic_id)): Path<(String, String)>,
+    mut query: Query<PollMessages>,
+    Query(kind): Query<StreamMessagesQuery>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, CustomError> {
+    query.stream_id = Identifier::from_str_value(&stream_id)?;
+    query.topic_id = Identifier::from_str_value(&topic_id)?;
+    query.consumer.kind = kind.consumer_kind;
+    query.validate()?;
+    let poll = query.0;
+    if poll.count == 0 {
+        return Err(IggyError::InvalidMessagesCount.into());
+    }
+
+    // Without committing the offsets, the `Next` strategy would keep returning the same messages.
+    if poll.strategy.kind == PollingKind::Next && !poll.auto_commit {
+        return Err(IggyError::InvalidCommand.into());
+    }
+
+    // The consumer group members might be assigned multiple partitions,
+    // thus only the offsets stored by the server can be used to keep track of the progress.
+    if poll.consumer.kind == ConsumerKind::ConsumerGroup
+        && poll.partition_id.is_none()
+        && poll.strategy.kind != PollingKind::Next
+    {
+        return Err(IggyError::InvalidCommand.into());
+    }
+
+    let system = state.system.clone();
+    let (session, notifier) = {
+        let system = system.read().await;
+        let session = system
+            .add_client(&identity.ip_address, Transport::Http)
+            .await;
+        let result = async {
+            system
+                .client_manager
+                .write()
+                .await
+                .set_user_id(session.client_id, identity.user_id)
+                .await?;
+            session.set_user_id(identity.user_id);
+            if poll.consumer.kind == ConsumerKind::ConsumerGroup {
+                system
+                    .join_consumer_group(
+                        &session,
+                        &poll.stream_id,
+                        &poll.topic_id,
+                        &poll.consumer.id,
+                    )
+                    .await?;
+            }
+            system.get_new_messages_notifier(&session, &poll.stream_id, &poll.topic_id)
+        }
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to start streaming messages, stream ID: {stream_id}, topic ID: {topic_id}"
+            )
+        });
+        match result {
+            Ok(notifier) => (session, notifier),
+            Err(error) => {
+                system.delete_client(session.client_id).await;
+                return Err(error.into());
+            }
+        }
+    };
+
+    let (events, receiver) = mpsc::channel(STREAM_EVENTS_CAPACITY);
+    tokio::spawn(async move {
+        let client_id = session.client_id;
+        serve_stream(poll, &session, &system, notifier, events).await;
+        debug!("Stopped streaming messages for HTTP client with ID: {client_id}.");
+        system.read().await.delete_client(client_id).await;
+    });
+
+    let stream = futures::stream::unfold(receiver, |mut receiver| async move {
+        receiver.recv().await.map(|event| (Ok(event), receiver))
+    });
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
+async fn serve_stream(
+    poll: PollMessages,
+    session: &Session,
+    system: &SharedSystem,
+    notifier: Arc<Notify>,
+    events: mpsc::Sender<Event>,
+) {
+    let mut strategy = poll.strategy;
+    loop {
+        // Register the interest before polling, so that the messages appended in between are not missed.
+        let notified = notifier.notified();
+        tokio::pin!(notified);
+        notified.as_mut().enable();
+
+        let polled_messages = system
+            .read()
+            .await
+            .poll_messages(
+                session,
+                &poll.consumer,
+                &poll.stream_id,
+                &poll.topic_id,
+                poll.partition_id,
+                PollingArgs::new(strategy, poll.count, poll.auto_commit),
+            )
+            .await
+            .map(|(metadata, messages)| messages.into_polled_messages(metadata))
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to poll messages for stream, stream ID: {}, topic ID: {}, session: {session}",
+                    poll.stream_id, poll.topic_id
+                )
+            });
+
+        let polled_messages = match polled_messages {
+            Ok(polled_messages) => polled_messages,
+            Err(error) => {
+                error!("Streaming messages for session: {session} has failed, error: {error}");
+                let event = Event::default()
+                    .event("error")
+                    .json_data(ErrorResponse::from_error(error));
+                if let Ok(event) = event {
+                    let _ = events.send(event).await;
+                }
+                return;
+            }
+        };
+
+        if polled_messages.messages.is_empty() {
+            tokio::select! {
+                _ = notified => {}
+                _ = tokio::time::sleep(STREAM_IDLE_POLL_INTERVAL) => {}
+                _ = events.closed() => return,
+            }
+            continue;
+        }
+
+        strategy = next_strategy(strategy, &polled_messages);
+        for message in &polled_messages.messages {
+            let event = Event::default()
+                .event("message")
+                .id(message.header.offset.to_string())
+                .json_data(StreamedMessage {
+                    partition_id: polled_messages.partition_id,
+                    current_offset: polled_messages.current_offset,
+                    message,
+                });
+            let event = match event {
+                Ok(event) => event,
+                Err(error) => {
+                    error!(
+                        "Failed to serialize message with offset: {} for session: {session}, error: {error}",
+                        message.header.offset
+                    );
+                    return;
+                }
+            };
+            if events.send(event).await.is_err() {
+                return;
+            }
+        }
+    }
+}
+
 fn make_mutable(batch: IggyMessagesBatch) -> IggyMessagesBatchMut {
     let (_, indexes, messages) = batch.decompose();
     let (_, indexes_buffer) = indexes.decompose();
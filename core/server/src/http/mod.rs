@@ -32,5 +32,6 @@ pub mod streams;
 pub mod system;
 pub mod topics;
 pub mod users;
+pub mod websocket;
 
 pub const COMPONENT: &str = "HTTP";
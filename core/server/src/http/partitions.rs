@@ -21,6 +21,7 @@ use crate::http::error::CustomError;
 use crate::http::jwt::json_web_token::Identity;
 use crate::http::shared::AppState;
 use crate::state::command::EntryCommand;
+use crate::streaming::partitions::relocation::move_partition;
 use crate::streaming::session::Session;
 use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
@@ -31,6 +32,8 @@ use iggy_common::Identifier;
 use iggy_common::Validatable;
 use iggy_common::create_partitions::CreatePartitions;
 use iggy_common::delete_partitions::DeletePartitions;
+use iggy_common::move_partition::MovePartition;
+use iggy_common::shrink_partitions::ShrinkPartitions;
 use std::sync::Arc;
 use tracing::instrument;
 
@@ -40,6 +43,14 @@ pub fn router(state: Arc<AppState>) -> Router {
             "/streams/{stream_id}/topics/{topic_id}/partitions",
             post(create_partitions).delete(delete_partitions),
         )
+        .route(
+            "/streams/{stream_id}/topics/{topic_id}/partitions/shrink",
+            post(shrink_partitions),
+        )
+        .route(
+            "/streams/{stream_id}/topics/{topic_id}/partitions/{partition_id}/move",
+            post(move_partition_to_directory),
+        )
         .with_state(state)
 }
 
@@ -127,3 +138,105 @@ async fn delete_partitions(
         })?;
     Ok(StatusCode::NO_CONTENT)
 }
+
+#[instrument(skip_all, name = "trace_shrink_partitions", fields(iggy_user_id = identity.user_id, iggy_stream_id = stream_id, iggy_topic_id = topic_id))]
+async fn shrink_partitions(
+    State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<Identity>,
+    Path((stream_id, topic_id)): Path<(String, String)>,
+    Json(mut command): Json<ShrinkPartitions>,
+) -> Result<StatusCode, CustomError> {
+    command.stream_id = Identifier::from_str_value(&stream_id)?;
+    command.topic_id = Identifier::from_str_value(&topic_id)?;
+    command.validate()?;
+
+    let mut system = state.system.write().await;
+    let copy = system
+            .shrink_partitions(
+                &Session::stateless(identity.user_id, identity.ip_address),
+                &command.stream_id,
+                &command.topic_id,
+                command.partitions_count,
+                command.copy_unconsumed,
+                command.key_header.as_deref(),
+                command.consistent_hashing,
+            )
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to shrink partitions, stream ID: {stream_id}, topic ID: {topic_id}"
+                )
+            })?;
+
+    // The unconsumed messages are copied without the system lock, as it might take a while.
+    if let Some(copy) = copy {
+        drop(system);
+        let copied_messages_count = copy.run().await;
+        system = state.system.write().await;
+        system
+            .finish_shrinking_partitions(&copy, copied_messages_count)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to copy unconsumed messages, stream ID: {stream_id}, topic ID: {topic_id}"
+                )
+            })?;
+    }
+
+    let system = system.downgrade();
+    system
+        .state
+        .apply(identity.user_id, &EntryCommand::ShrinkPartitions(command))
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to apply shrink partitions, stream ID: {stream_id}, topic ID: {topic_id}"
+            )
+        })?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[instrument(skip_all, name = "trace_move_partition", fields(iggy_user_id = identity.user_id, iggy_stream_id = stream_id, iggy_topic_id = topic_id, iggy_partition_id = partition_id))]
+async fn move_partition_to_directory(
+    State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<Identity>,
+    Path((stream_id, topic_id, partition_id)): Path<(String, String, u32)>,
+    Json(mut command): Json<MovePartition>,
+) -> Result<StatusCode, CustomError> {
+    command.stream_id = Identifier::from_str_value(&stream_id)?;
+    command.topic_id = Identifier::from_str_value(&topic_id)?;
+    command.partition_id = partition_id;
+    command.validate()?;
+
+    let partition = {
+        let system = state.system.read().await;
+        system.find_partition_to_move(
+            &Session::stateless(identity.user_id, identity.ip_address),
+            &command.stream_id,
+            &command.topic_id,
+            command.partition_id,
+            &command.directory,
+        )?
+    };
+    // The move is applied to the state before the partition is switched to the new directory.
+    let system_state = state.system.read().await.state.clone();
+    let directory = command.directory.clone();
+    let persist_move = async {
+        system_state
+            .apply(identity.user_id, &EntryCommand::MovePartition(command))
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to apply move partition with ID: {partition_id}, stream ID: {stream_id}, topic ID: {topic_id}"
+                )
+            })
+    };
+    move_partition(&partition, &directory, persist_move)
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to move partition with ID: {partition_id}, stream ID: {stream_id}, topic ID: {topic_id}"
+            )
+        })?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -23,21 +23,27 @@ use crate::http::jwt::json_web_token::Identity;
 use crate::http::mapper;
 use crate::http::shared::AppState;
 use crate::streaming::session::Session;
+use crate::streaming::systems::snapshot::{DATA_SNAPSHOT_CHUNK_SIZE, DataSnapshotUpload};
 use axum::body::Body;
 use axum::extract::{Path, State};
-use axum::http::{HeaderMap, header};
+use axum::http::{HeaderMap, StatusCode, header};
 use axum::response::IntoResponse;
 use axum::routing::{get, post};
 use axum::{Extension, Json, Router};
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 use chrono::Local;
 use error_set::ErrContext;
+use futures::StreamExt;
+use iggy_common::IggyError;
 use iggy_common::Stats;
 use iggy_common::Validatable;
+use iggy_common::get_data_snapshot::GetDataSnapshot;
 use iggy_common::get_snapshot::GetSnapshot;
 use iggy_common::locking::IggySharedMutFn;
 use iggy_common::{ClientInfo, ClientInfoDetails};
 use std::sync::Arc;
+use tokio::io::AsyncReadExt;
+use tracing::error;
 
 const NAME: &str = "Iggy API";
 const PONG: &str = "pong";
@@ -49,7 +55,9 @@ pub fn router(state: Arc<AppState>, metrics_config: &HttpMetricsConfig) -> Route
         .route("/stats", get(get_stats))
         .route("/clients", get(get_clients))
         .route("/clients/{client_id}", get(get_client))
-        .route("/snapshot", post(get_snapshot));
+        .route("/snapshot", post(get_snapshot))
+        .route("/snapshot/data", post(get_data_snapshot))
+        .route("/snapshot/data/restore", post(restore_data_snapshot));
     if metrics_config.enabled {
         router = router.route(&metrics_config.endpoint, get(get_metrics));
     }
@@ -146,3 +154,80 @@ async fn get_snapshot(
     );
     Ok((headers, Body::from(zip_data)))
 }
+
+async fn get_data_snapshot(
+    State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<Identity>,
+    Json(command): Json<GetDataSnapshot>,
+) -> Result<impl IntoResponse, CustomError> {
+    command.validate()?;
+
+    let session = Session::stateless(identity.user_id, identity.ip_address);
+    let system = state.system.read().await;
+    let snapshot = system
+        .get_data_snapshot(&session, &command.targets)
+        .await
+        .with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to get data snapshot")
+        })?;
+    drop(system);
+
+    let archive = futures::stream::try_unfold(snapshot.file, |mut file| async move {
+        let mut chunk = BytesMut::with_capacity(DATA_SNAPSHOT_CHUNK_SIZE);
+        if file.read_buf(&mut chunk).await? == 0 {
+            return Ok::<_, std::io::Error>(None);
+        }
+        Ok(Some((chunk.freeze(), file)))
+    });
+    let filename = format!(
+        "iggy_data_snapshot_{}.tar.zst",
+        Local::now().format("%Y%m%d_%H%M%S")
+    );
+
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::CONTENT_TYPE,
+        header::HeaderValue::from_static("application/zstd"),
+    );
+    headers.insert(
+        header::CONTENT_LENGTH,
+        header::HeaderValue::from(snapshot.size),
+    );
+    headers.insert(
+        header::CONTENT_DISPOSITION,
+        header::HeaderValue::from_str(&format!("attachment; filename=\"{filename}\"")).unwrap(),
+    );
+    Ok((headers, Body::from_stream(archive)))
+}
+
+/// The archive is sent as the raw request body, which is streamed to the temporary file
+/// and unpacked before the system gets locked for the restore.
+async fn restore_data_snapshot(
+    State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<Identity>,
+    body: Body,
+) -> Result<StatusCode, CustomError> {
+    let session = Session::stateless(identity.user_id, identity.ip_address);
+    let config = state.system.read().await.config.clone();
+    let mut upload = DataSnapshotUpload::create(&config).await?;
+    let mut chunks = body.into_data_stream();
+    while let Some(chunk) = chunks.next().await {
+        let chunk = chunk.map_err(|error| {
+            error!("Failed to receive the data snapshot archive, error: {error}");
+            IggyError::InvalidHttpRequest
+        })?;
+        upload.write(&chunk).await?;
+    }
+    let snapshot = upload.unpack(&config).await.with_error_context(|error| {
+        format!("{COMPONENT} (error: {error}) - failed to unpack data snapshot")
+    })?;
+
+    let mut system = state.system.write().await;
+    system
+        .restore_data_snapshot(&session, snapshot)
+        .await
+        .with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to restore data snapshot")
+        })?;
+    Ok(StatusCode::NO_CONTENT)
+}
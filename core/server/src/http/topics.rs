@@ -122,6 +122,7 @@ async fn create_topic(
             command.compression_algorithm,
             command.max_topic_size,
             command.replication_factor,
+            command.overrides,
         )
         .await
         .with_error_context(|error| {
@@ -170,6 +171,7 @@ async fn update_topic(
                 command.compression_algorithm,
                 command.max_topic_size,
                 command.replication_factor,
+                command.overrides,
             )
             .await
             .with_error_context(|error| {
@@ -0,0 +1,61 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::configs::http::HttpWebSocketConfig;
+use crate::http::jwt::json_web_token::Identity;
+use crate::http::shared::AppState;
+use crate::websocket::connection_handler::handle_connection;
+use axum::extract::ws::WebSocketUpgrade;
+use axum::extract::{ConnectInfo, State};
+use axum::response::Response;
+use axum::routing::get;
+use axum::{Extension, Router};
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+pub const WEBSOCKET_PATH: &str = "/ws";
+
+struct WebSocketState {
+    app_state: Arc<AppState>,
+    config: HttpWebSocketConfig,
+}
+
+pub fn router(state: Arc<AppState>, config: &HttpWebSocketConfig) -> Router {
+    Router::new()
+        .route(WEBSOCKET_PATH, get(upgrade))
+        .with_state(Arc::new(WebSocketState {
+            app_state: state,
+            config: config.clone(),
+        }))
+}
+
+async fn upgrade(
+    State(state): State<Arc<WebSocketState>>,
+    ConnectInfo(address): ConnectInfo<SocketAddr>,
+    identity: Option<Extension<Identity>>,
+    upgrade: WebSocketUpgrade,
+) -> Response {
+    let max_message_size = state.config.max_message_size.as_bytes_u64() as usize;
+    let user_id = identity.map(|Extension(identity)| identity.user_id);
+    let system = state.app_state.system.clone();
+    let config = state.config.clone();
+    upgrade
+        .max_message_size(max_message_size)
+        .max_frame_size(max_message_size)
+        .on_upgrade(move |socket| handle_connection(socket, address, user_id, system, config))
+}
@@ -0,0 +1,316 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use iggy_common::IggyError;
+
+/// Reads the primitive types of the Kafka protocol (big-endian integers, length-prefixed strings,
+/// bytes and arrays, zigzag-encoded varints) from the request body.
+///
+/// Only the non-flexible versions of the requests are supported, thus the compact types and
+/// the tagged fields are never read.
+#[derive(Debug)]
+pub struct KafkaReader {
+    bytes: Bytes,
+}
+
+impl KafkaReader {
+    pub fn new(bytes: Bytes) -> Self {
+        Self { bytes }
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.bytes.remaining()
+    }
+
+    fn ensure_remaining(&self, size: usize) -> Result<(), IggyError> {
+        if self.bytes.remaining() < size {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        Ok(())
+    }
+
+    pub fn read_bool(&mut self) -> Result<bool, IggyError> {
+        Ok(self.read_i8()? != 0)
+    }
+
+    pub fn read_i8(&mut self) -> Result<i8, IggyError> {
+        self.ensure_remaining(1)?;
+        Ok(self.bytes.get_i8())
+    }
+
+    pub fn read_i16(&mut self) -> Result<i16, IggyError> {
+        self.ensure_remaining(2)?;
+        Ok(self.bytes.get_i16())
+    }
+
+    pub fn read_i32(&mut self) -> Result<i32, IggyError> {
+        self.ensure_remaining(4)?;
+        Ok(self.bytes.get_i32())
+    }
+
+    pub fn read_u32(&mut self) -> Result<u32, IggyError> {
+        self.ensure_remaining(4)?;
+        Ok(self.bytes.get_u32())
+    }
+
+    pub fn read_i64(&mut self) -> Result<i64, IggyError> {
+        self.ensure_remaining(8)?;
+        Ok(self.bytes.get_i64())
+    }
+
+    pub fn read_varint(&mut self) -> Result<i32, IggyError> {
+        let value = self.read_unsigned_varint(5)?;
+        Ok(((value >> 1) as i32) ^ -((value & 1) as i32))
+    }
+
+    pub fn read_varlong(&mut self) -> Result<i64, IggyError> {
+        let value = self.read_unsigned_varint(10)?;
+        Ok(((value >> 1) as i64) ^ -((value & 1) as i64))
+    }
+
+    fn read_unsigned_varint(&mut self, max_bytes: usize) -> Result<u64, IggyError> {
+        let mut value = 0u64;
+        for index in 0..max_bytes {
+            self.ensure_remaining(1)?;
+            let byte = self.bytes.get_u8();
+            value |= ((byte & 0x7f) as u64) << (index * 7);
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+
+        Err(IggyError::InvalidNumberEncoding)
+    }
+
+    pub fn read_string(&mut self) -> Result<String, IggyError> {
+        self.read_nullable_string()?
+            .ok_or(IggyError::InvalidCommand)
+    }
+
+    pub fn read_nullable_string(&mut self) -> Result<Option<String>, IggyError> {
+        let length = self.read_i16()?;
+        if length < 0 {
+            return Ok(None);
+        }
+
+        let bytes = self.read_raw(length as usize)?;
+        String::from_utf8(bytes.to_vec())
+            .map(Some)
+            .map_err(|_| IggyError::InvalidUtf8)
+    }
+
+    pub fn read_bytes(&mut self) -> Result<Bytes, IggyError> {
+        self.read_nullable_bytes()?.ok_or(IggyError::InvalidCommand)
+    }
+
+    pub fn read_nullable_bytes(&mut self) -> Result<Option<Bytes>, IggyError> {
+        let length = self.read_i32()?;
+        if length < 0 {
+            return Ok(None);
+        }
+
+        self.read_raw(length as usize).map(Some)
+    }
+
+    /// Reads the bytes prefixed with the varint length, as used by the records and their headers.
+    pub fn read_varint_bytes(&mut self) -> Result<Option<Bytes>, IggyError> {
+        let length = self.read_varint()?;
+        if length < 0 {
+            return Ok(None);
+        }
+
+        self.read_raw(length as usize).map(Some)
+    }
+
+    pub fn read_raw(&mut self, size: usize) -> Result<Bytes, IggyError> {
+        self.ensure_remaining(size)?;
+        Ok(self.bytes.split_to(size))
+    }
+
+    pub fn read_array<T>(
+        &mut self,
+        read_item: impl FnMut(&mut Self) -> Result<T, IggyError>,
+    ) -> Result<Vec<T>, IggyError> {
+        Ok(self.read_nullable_array(read_item)?.unwrap_or_default())
+    }
+
+    pub fn read_nullable_array<T>(
+        &mut self,
+        mut read_item: impl FnMut(&mut Self) -> Result<T, IggyError>,
+    ) -> Result<Option<Vec<T>>, IggyError> {
+        let length = self.read_i32()?;
+        if length < 0 {
+            return Ok(None);
+        }
+
+        // Each item takes at least a single byte, which protects from the allocation of huge arrays.
+        let length = length as usize;
+        self.ensure_remaining(length)?;
+        let mut items = Vec::with_capacity(length);
+        for _ in 0..length {
+            items.push(read_item(self)?);
+        }
+        Ok(Some(items))
+    }
+}
+
+/// Writes the primitive types of the Kafka protocol to the response body.
+pub trait KafkaBufMut: BufMut {
+    fn put_bool(&mut self, value: bool) {
+        self.put_i8(value as i8);
+    }
+
+    fn put_string(&mut self, value: &str) {
+        self.put_i16(value.len() as i16);
+        self.put_slice(value.as_bytes());
+    }
+
+    fn put_nullable_string(&mut self, value: Option<&str>) {
+        match value {
+            Some(value) => self.put_string(value),
+            None => self.put_i16(-1),
+        }
+    }
+
+    fn put_kafka_bytes(&mut self, value: &[u8]) {
+        self.put_i32(value.len() as i32);
+        self.put_slice(value);
+    }
+
+    fn put_nullable_bytes(&mut self, value: Option<&[u8]>) {
+        match value {
+            Some(value) => self.put_kafka_bytes(value),
+            None => self.put_i32(-1),
+        }
+    }
+
+    fn put_array_length(&mut self, length: usize) {
+        self.put_i32(length as i32);
+    }
+
+    fn put_null_array(&mut self) {
+        self.put_i32(-1);
+    }
+
+    fn put_varint(&mut self, value: i32) {
+        self.put_unsigned_varint(((value << 1) ^ (value >> 31)) as u32 as u64);
+    }
+
+    fn put_varlong(&mut self, value: i64) {
+        self.put_unsigned_varint(((value << 1) ^ (value >> 63)) as u64);
+    }
+
+    fn put_unsigned_varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.put_u8((value as u8 & 0x7f) | 0x80);
+            value >>= 7;
+        }
+        self.put_u8(value as u8);
+    }
+
+    /// Writes the bytes prefixed with the varint length, as used by the records and their headers.
+    fn put_varint_bytes(&mut self, value: Option<&[u8]>) {
+        match value {
+            Some(value) => {
+                self.put_varint(value.len() as i32);
+                self.put_slice(value);
+            }
+            None => self.put_varint(-1),
+        }
+    }
+}
+
+impl KafkaBufMut for BytesMut {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn primitives_should_be_written_and_read() {
+        let mut bytes = BytesMut::new();
+        bytes.put_bool(true);
+        bytes.put_i16(-2);
+        bytes.put_i32(300);
+        bytes.put_i64(-4);
+        bytes.put_string("topic");
+        bytes.put_nullable_string(None);
+        bytes.put_kafka_bytes(b"value");
+        bytes.put_nullable_bytes(None);
+        bytes.put_array_length(2);
+        bytes.put_i32(1);
+        bytes.put_i32(2);
+        bytes.put_null_array();
+
+        let mut reader = KafkaReader::new(bytes.freeze());
+        assert!(reader.read_bool().unwrap());
+        assert_eq!(reader.read_i16().unwrap(), -2);
+        assert_eq!(reader.read_i32().unwrap(), 300);
+        assert_eq!(reader.read_i64().unwrap(), -4);
+        assert_eq!(reader.read_string().unwrap(), "topic");
+        assert_eq!(reader.read_nullable_string().unwrap(), None);
+        assert_eq!(reader.read_bytes().unwrap().as_ref(), b"value");
+        assert_eq!(reader.read_nullable_bytes().unwrap(), None);
+        assert_eq!(reader.read_array(|r| r.read_i32()).unwrap(), vec![1, 2]);
+        assert_eq!(reader.read_nullable_array(|r| r.read_i32()).unwrap(), None);
+        assert_eq!(reader.remaining(), 0);
+    }
+
+    #[test]
+    fn varints_should_be_zigzag_encoded() {
+        let values = [
+            0i64,
+            -1,
+            1,
+            63,
+            -64,
+            64,
+            300,
+            i32::MAX as i64,
+            i32::MIN as i64,
+        ];
+        let mut bytes = BytesMut::new();
+        for value in values {
+            bytes.put_varint(value as i32);
+            bytes.put_varlong(value * 1_000_000);
+        }
+        bytes.put_varint_bytes(Some(b"key"));
+        bytes.put_varint_bytes(None);
+
+        let mut reader = KafkaReader::new(bytes.freeze());
+        for value in values {
+            assert_eq!(reader.read_varint().unwrap(), value as i32);
+            assert_eq!(reader.read_varlong().unwrap(), value * 1_000_000);
+        }
+        assert_eq!(
+            reader.read_varint_bytes().unwrap().unwrap().as_ref(),
+            b"key"
+        );
+        assert_eq!(reader.read_varint_bytes().unwrap(), None);
+    }
+
+    #[test]
+    fn truncated_input_should_fail() {
+        let mut reader = KafkaReader::new(Bytes::from_static(&[0, 5, b'a']));
+        assert!(reader.read_string().is_err());
+        let mut reader = KafkaReader::new(Bytes::from_static(&[0, 0, 0, 100, 1]));
+        assert!(reader.read_array(|r| r.read_i8()).is_err());
+    }
+}
@@ -0,0 +1,168 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::kafka::codec::KafkaReader;
+use crate::kafka::group_coordinator::GroupCoordinator;
+use crate::kafka::handlers::{
+    BrokerAddress, RequestContext, api_versions, fetch, groups, list_offsets, metadata, offsets,
+    produce, sasl,
+};
+use crate::kafka::protocol::{
+    API_VERSIONS, FETCH, FIND_COORDINATOR, HEARTBEAT, INIT_PRODUCER_ID, JOIN_GROUP, LEAVE_GROUP,
+    LIST_OFFSETS, METADATA, OFFSET_COMMIT, OFFSET_FETCH, PRODUCE, RequestHeader, SASL_AUTHENTICATE,
+    SASL_HANDSHAKE, SYNC_GROUP, is_version_supported,
+};
+use crate::server_error::ConnectionError;
+use crate::streaming::session::Session;
+use crate::streaming::systems::system::SharedSystem;
+use bytes::{BufMut, BytesMut};
+use iggy_common::IggyError;
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tracing::{debug, warn};
+
+const DEFAULT_CLIENT_ID: &str = "kafka";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SaslState {
+    Handshake,
+    Authenticate,
+    Authenticated,
+}
+
+/// Handles the requests one by one, in the order they were sent. The connection is closed (just like Kafka does)
+/// whenever the request is malformed, unsupported, or sent before the client has authenticated using SASL/PLAIN.
+pub(crate) async fn handle_connection(
+    stream: &mut TcpStream,
+    session: Arc<Session>,
+    system: SharedSystem,
+    coordinator: Arc<GroupCoordinator>,
+    broker: BrokerAddress,
+    max_request_size: u64,
+) -> Result<(), ConnectionError> {
+    let mut sasl_state = SaslState::Handshake;
+    loop {
+        let length = stream.read_i32().await?;
+        if length < 0 || length as u64 > max_request_size {
+            warn!(
+                "Received Kafka request of invalid size: {length} bytes (max: {max_request_size} bytes), session: {session}."
+            );
+            return Err(ConnectionError::from(IggyError::InvalidCommand));
+        }
+
+        let mut buffer = BytesMut::zeroed(length as usize);
+        stream.read_exact(&mut buffer).await?;
+        let mut reader = KafkaReader::new(buffer.freeze());
+        let header = RequestHeader::read(&mut reader)?;
+        debug!(
+            "Received Kafka request, API key: {}, version: {}, correlation ID: {}, session: {session}",
+            header.api_key, header.api_version, header.correlation_id
+        );
+
+        let context = RequestContext {
+            api_version: header.api_version,
+            client_id: header.client_id.as_deref().unwrap_or(DEFAULT_CLIENT_ID),
+            session: &session,
+            system: &system,
+            coordinator: &coordinator,
+            broker: &broker,
+            max_request_size,
+        };
+        let (response, close) =
+            handle_request(&context, header.api_key, &mut reader, &mut sasl_state).await?;
+        if let Some(response) = response {
+            let mut frame = BytesMut::with_capacity(8 + response.len());
+            frame.put_i32(4 + response.len() as i32);
+            frame.put_i32(header.correlation_id);
+            frame.put_slice(&response);
+            stream.write_all(&frame).await?;
+        }
+        if close {
+            return Err(ConnectionError::from(IggyError::Unauthenticated));
+        }
+    }
+}
+
+/// Returns the response body (if any) and whether the connection should be closed after sending it.
+async fn handle_request(
+    context: &RequestContext<'_>,
+    api_key: i16,
+    reader: &mut KafkaReader,
+    sasl_state: &mut SaslState,
+) -> Result<(Option<BytesMut>, bool), IggyError> {
+    let api_version = context.api_version;
+    if api_key == API_VERSIONS {
+        let version_supported = is_version_supported(api_key, api_version);
+        return Ok((
+            Some(api_versions::handle(api_version, version_supported)),
+            false,
+        ));
+    }
+
+    if !is_version_supported(api_key, api_version) {
+        warn!(
+            "Unsupported Kafka API key: {api_key} with version: {api_version}, session: {}.",
+            context.session
+        );
+        return Err(IggyError::InvalidCommand);
+    }
+
+    match (*sasl_state, api_key) {
+        (SaslState::Handshake, SASL_HANDSHAKE) => {
+            let (response, accepted) = sasl::handle_handshake(reader)?;
+            if accepted {
+                *sasl_state = SaslState::Authenticate;
+            }
+            return Ok((Some(response), false));
+        }
+        (SaslState::Authenticate, SASL_AUTHENTICATE) => {
+            let (response, authenticated) = sasl::handle_authenticate(context, reader).await?;
+            if authenticated {
+                *sasl_state = SaslState::Authenticated;
+            }
+            return Ok((Some(response), !authenticated));
+        }
+        (SaslState::Authenticated, api_key)
+            if api_key != SASL_HANDSHAKE && api_key != SASL_AUTHENTICATE => {}
+        (sasl_state, api_key) => {
+            warn!(
+                "Received Kafka request with API key: {api_key} in invalid SASL state: {sasl_state:?}, session: {}.",
+                context.session
+            );
+            return Err(IggyError::Unauthenticated);
+        }
+    }
+
+    let response = match api_key {
+        PRODUCE => return Ok((produce::handle(context, reader).await?, false)),
+        FETCH => fetch::handle(context, reader).await?,
+        LIST_OFFSETS => list_offsets::handle(context, reader).await?,
+        METADATA => metadata::handle(context, reader).await?,
+        OFFSET_COMMIT => offsets::handle_offset_commit(context, reader).await?,
+        OFFSET_FETCH => offsets::handle_offset_fetch(context, reader).await?,
+        FIND_COORDINATOR => groups::handle_find_coordinator(context, reader)?,
+        JOIN_GROUP => groups::handle_join_group(context, reader).await?,
+        HEARTBEAT => groups::handle_heartbeat(context, reader).await?,
+        LEAVE_GROUP => groups::handle_leave_group(context, reader).await?,
+        SYNC_GROUP => groups::handle_sync_group(context, reader).await?,
+        INIT_PRODUCER_ID => produce::handle_init_producer_id(reader)?,
+        _ => return Err(IggyError::InvalidCommand),
+    };
+    Ok((Some(response), false))
+}
@@ -0,0 +1,664 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::kafka::protocol::{
+    ILLEGAL_GENERATION, INCONSISTENT_GROUP_PROTOCOL, INVALID_SESSION_TIMEOUT, NONE,
+    REBALANCE_IN_PROGRESS, UNKNOWN_MEMBER_ID,
+};
+use ahash::AHashMap;
+use bytes::Bytes;
+use std::time::Duration;
+use tokio::sync::{Mutex, watch};
+use tokio::time::Instant;
+use tracing::info;
+use uuid::Uuid;
+
+const MIN_SESSION_TIMEOUT: Duration = Duration::from_secs(1);
+const MAX_SESSION_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// Coordinates the membership of the Kafka consumer groups, following the classic rebalance protocol:
+/// the members join the group (JoinGroup), the leader computes the partitions assignment on its own
+/// and distributes it through the coordinator (SyncGroup), and the members keep sending heartbeats
+/// to learn about the next rebalance.
+///
+/// The membership lives only in memory, while the committed offsets are stored by the Iggy consumer groups.
+#[derive(Debug, Default)]
+pub struct GroupCoordinator {
+    groups: Mutex<AHashMap<String, Group>>,
+}
+
+#[derive(Debug)]
+pub struct JoinGroupMember {
+    pub member_id: String,
+    pub session_timeout: Duration,
+    pub rebalance_timeout: Duration,
+    pub protocol_type: String,
+    pub protocols: Vec<(String, Bytes)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct JoinGroupResult {
+    pub error_code: i16,
+    pub generation_id: i32,
+    pub protocol_name: String,
+    pub leader_id: String,
+    pub member_id: String,
+    /// The members with their metadata, returned only to the leader.
+    pub members: Vec<(String, Bytes)>,
+}
+
+impl JoinGroupResult {
+    fn error(error_code: i16, member_id: String) -> Self {
+        Self {
+            error_code,
+            generation_id: -1,
+            protocol_name: String::new(),
+            leader_id: String::new(),
+            member_id,
+            members: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum GroupState {
+    Empty,
+    PreparingRebalance,
+    CompletingRebalance,
+    Stable,
+}
+
+#[derive(Debug)]
+struct Member {
+    protocols: Vec<(String, Bytes)>,
+    session_timeout: Duration,
+    rebalance_timeout: Duration,
+    last_heartbeat: Instant,
+    join_order: u64,
+    joined: bool,
+    join_result: Option<JoinGroupResult>,
+    assignment: Option<Bytes>,
+}
+
+#[derive(Debug)]
+struct Group {
+    state: GroupState,
+    generation_id: i32,
+    protocol_type: Option<String>,
+    protocol_name: Option<String>,
+    leader_id: Option<String>,
+    members: AHashMap<String, Member>,
+    rebalance_deadline: Instant,
+    next_join_order: u64,
+    changes: watch::Sender<u64>,
+}
+
+impl Default for Group {
+    fn default() -> Self {
+        Self {
+            state: GroupState::Empty,
+            generation_id: 0,
+            protocol_type: None,
+            protocol_name: None,
+            leader_id: None,
+            members: AHashMap::new(),
+            rebalance_deadline: Instant::now(),
+            next_join_order: 0,
+            changes: watch::channel(0).0,
+        }
+    }
+}
+
+impl GroupCoordinator {
+    /// Adds the member to the group (or updates the existing one) and waits until the rebalance completes,
+    /// which happens once all the known members have rejoined, or the rebalance timeout has elapsed.
+    pub async fn join_group(
+        &self,
+        group_id: &str,
+        client_id: &str,
+        member: JoinGroupMember,
+    ) -> JoinGroupResult {
+        if !(MIN_SESSION_TIMEOUT..=MAX_SESSION_TIMEOUT).contains(&member.session_timeout) {
+            return JoinGroupResult::error(INVALID_SESSION_TIMEOUT, member.member_id);
+        }
+
+        let member_id;
+        let mut changes;
+        let mut deadline;
+        {
+            let mut groups = self.groups.lock().await;
+            let group = groups.entry(group_id.to_owned()).or_default();
+            let now = Instant::now();
+            group.remove_expired_members(now, group_id);
+            if !group.members.is_empty()
+                && (group.protocol_type.as_deref() != Some(member.protocol_type.as_str())
+                    || !member
+                        .protocols
+                        .iter()
+                        .any(|(name, _)| group.supports_protocol(name)))
+            {
+                return JoinGroupResult::error(INCONSISTENT_GROUP_PROTOCOL, member.member_id);
+            }
+
+            member_id = if member.member_id.is_empty() {
+                format!("{client_id}-{}", Uuid::new_v4())
+            } else if group.members.contains_key(&member.member_id) {
+                member.member_id
+            } else {
+                return JoinGroupResult::error(UNKNOWN_MEMBER_ID, member.member_id);
+            };
+
+            if group.state != GroupState::PreparingRebalance {
+                group.prepare_rebalance(now);
+            }
+            let join_order = group.next_join_order;
+            group.next_join_order += 1;
+            group.protocol_type = Some(member.protocol_type);
+            let entry = group
+                .members
+                .entry(member_id.clone())
+                .or_insert_with(|| Member {
+                    protocols: Vec::new(),
+                    session_timeout: member.session_timeout,
+                    rebalance_timeout: member.rebalance_timeout,
+                    last_heartbeat: now,
+                    join_order,
+                    joined: false,
+                    join_result: None,
+                    assignment: None,
+                });
+            entry.protocols = member.protocols;
+            entry.session_timeout = member.session_timeout;
+            entry.rebalance_timeout = member.rebalance_timeout;
+            entry.last_heartbeat = now;
+            entry.joined = true;
+            entry.join_result = None;
+            group.rebalance_deadline = group.rebalance_deadline.max(now + member.rebalance_timeout);
+            group.try_complete_join(group_id);
+            if let Some(result) = group.take_join_result(&member_id) {
+                return result;
+            }
+
+            changes = group.changes.subscribe();
+            deadline = group.rebalance_deadline;
+        }
+
+        loop {
+            let _ = tokio::time::timeout_at(deadline, changes.changed()).await;
+            let mut groups = self.groups.lock().await;
+            let Some(group) = groups.get_mut(group_id) else {
+                return JoinGroupResult::error(UNKNOWN_MEMBER_ID, member_id);
+            };
+
+            if let Some(result) = group.take_join_result(&member_id) {
+                return result;
+            }
+
+            if !group.members.contains_key(&member_id) {
+                return JoinGroupResult::error(UNKNOWN_MEMBER_ID, member_id);
+            }
+
+            if group.state != GroupState::PreparingRebalance {
+                return JoinGroupResult::error(REBALANCE_IN_PROGRESS, member_id);
+            }
+
+            if Instant::now() >= group.rebalance_deadline {
+                group.members.retain(|_, member| member.joined);
+                group.complete_join(group_id);
+                if let Some(result) = group.take_join_result(&member_id) {
+                    return result;
+                }
+            }
+
+            changes = group.changes.subscribe();
+            deadline = group.rebalance_deadline;
+        }
+    }
+
+    /// Stores the assignments sent by the leader and returns the assignment of the member,
+    /// the followers wait until the leader has sent the assignments.
+    pub async fn sync_group(
+        &self,
+        group_id: &str,
+        generation_id: i32,
+        member_id: &str,
+        assignments: Vec<(String, Bytes)>,
+    ) -> (i16, Bytes) {
+        let mut assignments = Some(assignments);
+        let mut wait_deadline = None;
+        loop {
+            let (mut changes, deadline) = {
+                let mut groups = self.groups.lock().await;
+                let Some(group) = groups.get_mut(group_id) else {
+                    return (UNKNOWN_MEMBER_ID, Bytes::new());
+                };
+
+                let now = Instant::now();
+                group.remove_expired_members(now, group_id);
+                let error_code = group.validate_member(member_id, generation_id);
+                if error_code != NONE {
+                    return (error_code, Bytes::new());
+                }
+
+                if group.state == GroupState::PreparingRebalance {
+                    return (REBALANCE_IN_PROGRESS, Bytes::new());
+                }
+
+                let member = group.members.get_mut(member_id).unwrap();
+                member.last_heartbeat = now;
+                let rebalance_timeout = member.rebalance_timeout;
+                if group.state == GroupState::Stable {
+                    return (NONE, member.assignment.clone().unwrap_or_default());
+                }
+
+                let is_leader = group.leader_id.as_deref() == Some(member_id);
+                if let Some(assignments) = assignments.take().filter(|_| is_leader) {
+                    group.complete_sync(assignments);
+                    let member = &group.members[member_id];
+                    return (NONE, member.assignment.clone().unwrap_or_default());
+                }
+
+                let deadline = *wait_deadline.get_or_insert(now + rebalance_timeout);
+                if now >= deadline {
+                    return (REBALANCE_IN_PROGRESS, Bytes::new());
+                }
+                (group.changes.subscribe(), deadline)
+            };
+
+            let _ = tokio::time::timeout_at(deadline, changes.changed()).await;
+        }
+    }
+
+    pub async fn heartbeat(&self, group_id: &str, generation_id: i32, member_id: &str) -> i16 {
+        let mut groups = self.groups.lock().await;
+        let Some(group) = groups.get_mut(group_id) else {
+            return UNKNOWN_MEMBER_ID;
+        };
+
+        let now = Instant::now();
+        group.remove_expired_members(now, group_id);
+        let error_code = group.validate_member(member_id, generation_id);
+        if error_code != NONE {
+            return error_code;
+        }
+
+        group.members.get_mut(member_id).unwrap().last_heartbeat = now;
+        if group.state == GroupState::PreparingRebalance {
+            return REBALANCE_IN_PROGRESS;
+        }
+
+        NONE
+    }
+
+    pub async fn leave_group(&self, group_id: &str, member_id: &str) -> i16 {
+        let mut groups = self.groups.lock().await;
+        let Some(group) = groups.get_mut(group_id) else {
+            return UNKNOWN_MEMBER_ID;
+        };
+
+        if group.members.remove(member_id).is_none() {
+            return UNKNOWN_MEMBER_ID;
+        }
+
+        info!("Member: {member_id} has left Kafka consumer group: {group_id}.");
+        group.on_members_removed(Instant::now(), group_id);
+        NONE
+    }
+
+    /// Validates the member committing the offsets, the generation ID equal to -1 is used
+    /// by the consumers which don't belong to any group and manage the partitions on their own.
+    pub async fn validate_offset_commit(
+        &self,
+        group_id: &str,
+        generation_id: i32,
+        member_id: &str,
+    ) -> i16 {
+        if generation_id < 0 {
+            return NONE;
+        }
+
+        let mut groups = self.groups.lock().await;
+        let Some(group) = groups.get_mut(group_id) else {
+            return UNKNOWN_MEMBER_ID;
+        };
+
+        let now = Instant::now();
+        group.remove_expired_members(now, group_id);
+        let error_code = group.validate_member(member_id, generation_id);
+        if error_code == NONE {
+            group.members.get_mut(member_id).unwrap().last_heartbeat = now;
+        }
+        error_code
+    }
+}
+
+impl Group {
+    fn supports_protocol(&self, protocol_name: &str) -> bool {
+        self.members.values().all(|member| {
+            member
+                .protocols
+                .iter()
+                .any(|(name, _)| name == protocol_name)
+        })
+    }
+
+    fn validate_member(&self, member_id: &str, generation_id: i32) -> i16 {
+        if !self.members.contains_key(member_id) {
+            return UNKNOWN_MEMBER_ID;
+        }
+
+        if generation_id != self.generation_id {
+            return ILLEGAL_GENERATION;
+        }
+
+        NONE
+    }
+
+    fn take_join_result(&mut self, member_id: &str) -> Option<JoinGroupResult> {
+        self.members
+            .get_mut(member_id)
+            .and_then(|member| member.join_result.take())
+    }
+
+    fn prepare_rebalance(&mut self, now: Instant) {
+        self.state = GroupState::PreparingRebalance;
+        self.rebalance_deadline = self
+            .members
+            .values()
+            .map(|member| now + member.rebalance_timeout)
+            .max()
+            .unwrap_or(now);
+        for member in self.members.values_mut() {
+            member.joined = false;
+            member.join_result = None;
+        }
+    }
+
+    fn remove_expired_members(&mut self, now: Instant, group_id: &str) {
+        let members_count = self.members.len();
+        self.members.retain(|_, member| {
+            member.joined || now.duration_since(member.last_heartbeat) <= member.session_timeout
+        });
+        if self.members.len() != members_count {
+            info!(
+                "Removed {} expired members from Kafka consumer group: {group_id}.",
+                members_count - self.members.len()
+            );
+            self.on_members_removed(now, group_id);
+        }
+    }
+
+    fn on_members_removed(&mut self, now: Instant, group_id: &str) {
+        match self.state {
+            GroupState::PreparingRebalance => self.try_complete_join(group_id),
+            _ if self.members.is_empty() => self.complete_join(group_id),
+            _ => self.prepare_rebalance(now),
+        }
+    }
+
+    fn try_complete_join(&mut self, group_id: &str) {
+        if self.members.values().all(|member| member.joined) {
+            self.complete_join(group_id);
+        }
+    }
+
+    fn complete_join(&mut self, group_id: &str) {
+        self.generation_id += 1;
+        if self.members.is_empty() {
+            self.state = GroupState::Empty;
+            self.protocol_type = None;
+            self.protocol_name = None;
+            self.leader_id = None;
+            self.changes.send_modify(|changes| *changes += 1);
+            return;
+        }
+
+        let leader_id = match &self.leader_id {
+            Some(leader_id) if self.members.contains_key(leader_id) => leader_id.clone(),
+            _ => self
+                .members
+                .iter()
+                .min_by_key(|(_, member)| member.join_order)
+                .map(|(member_id, _)| member_id.clone())
+                .unwrap(),
+        };
+        let protocol_name = self.members[&leader_id]
+            .protocols
+            .iter()
+            .map(|(name, _)| name)
+            .find(|name| self.supports_protocol(name))
+            .cloned()
+            .unwrap_or_default();
+
+        let mut members = self
+            .members
+            .iter()
+            .map(|(member_id, member)| {
+                let metadata = member
+                    .protocols
+                    .iter()
+                    .find(|(name, _)| *name == protocol_name)
+                    .map(|(_, metadata)| metadata.clone())
+                    .unwrap_or_default();
+                (member.join_order, member_id.clone(), metadata)
+            })
+            .collect::<Vec<_>>();
+        members.sort_by_key(|(join_order, _, _)| *join_order);
+        let members = members
+            .into_iter()
+            .map(|(_, member_id, metadata)| (member_id, metadata))
+            .collect::<Vec<_>>();
+
+        for (member_id, member) in self.members.iter_mut() {
+            member.joined = false;
+            member.assignment = None;
+            member.join_result = Some(JoinGroupResult {
+                error_code: NONE,
+                generation_id: self.generation_id,
+                protocol_name: protocol_name.clone(),
+                leader_id: leader_id.clone(),
+                member_id: member_id.clone(),
+                members: if *member_id == leader_id {
+                    members.clone()
+                } else {
+                    Vec::new()
+                },
+            });
+        }
+
+        info!(
+            "Kafka consumer group: {group_id} has completed the rebalance, generation: {}, members: {}, leader: {leader_id}, protocol: {protocol_name}.",
+            self.generation_id,
+            self.members.len()
+        );
+        self.state = GroupState::CompletingRebalance;
+        self.protocol_name = Some(protocol_name);
+        self.leader_id = Some(leader_id);
+        self.changes.send_modify(|changes| *changes += 1);
+    }
+
+    fn complete_sync(&mut self, assignments: Vec<(String, Bytes)>) {
+        for member in self.members.values_mut() {
+            member.assignment = Some(Bytes::new());
+        }
+        for (member_id, assignment) in assignments {
+            if let Some(member) = self.members.get_mut(&member_id) {
+                member.assignment = Some(assignment);
+            }
+        }
+        self.state = GroupState::Stable;
+        self.changes.send_modify(|changes| *changes += 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    const GROUP_ID: &str = "group";
+
+    fn create_member(member_id: &str) -> JoinGroupMember {
+        JoinGroupMember {
+            member_id: member_id.to_owned(),
+            session_timeout: Duration::from_secs(10),
+            rebalance_timeout: Duration::from_secs(5),
+            protocol_type: "consumer".to_owned(),
+            protocols: vec![("range".to_owned(), Bytes::from_static(b"metadata"))],
+        }
+    }
+
+    #[tokio::test]
+    async fn single_member_should_join_and_sync_group() {
+        let coordinator = GroupCoordinator::default();
+        let result = coordinator
+            .join_group(GROUP_ID, "client", create_member(""))
+            .await;
+        assert_eq!(result.error_code, NONE);
+        assert_eq!(result.generation_id, 1);
+        assert_eq!(result.leader_id, result.member_id);
+        assert_eq!(result.protocol_name, "range");
+        assert_eq!(result.members.len(), 1);
+
+        let assignment = Bytes::from_static(b"assignment");
+        let (error_code, member_assignment) = coordinator
+            .sync_group(
+                GROUP_ID,
+                1,
+                &result.member_id,
+                vec![(result.member_id.clone(), assignment.clone())],
+            )
+            .await;
+        assert_eq!(error_code, NONE);
+        assert_eq!(member_assignment, assignment);
+        assert_eq!(
+            coordinator.heartbeat(GROUP_ID, 1, &result.member_id).await,
+            NONE
+        );
+        assert_eq!(
+            coordinator.heartbeat(GROUP_ID, 2, &result.member_id).await,
+            ILLEGAL_GENERATION
+        );
+        assert_eq!(
+            coordinator.heartbeat(GROUP_ID, 1, "unknown").await,
+            UNKNOWN_MEMBER_ID
+        );
+    }
+
+    #[tokio::test]
+    async fn joining_member_should_trigger_rebalance_of_existing_members() {
+        let coordinator = Arc::new(GroupCoordinator::default());
+        let leader = coordinator
+            .join_group(GROUP_ID, "client-1", create_member(""))
+            .await;
+        coordinator
+            .sync_group(GROUP_ID, 1, &leader.member_id, Vec::new())
+            .await;
+
+        let follower_coordinator = coordinator.clone();
+        let follower = tokio::spawn(async move {
+            follower_coordinator
+                .join_group(GROUP_ID, "client-2", create_member(""))
+                .await
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(
+            coordinator.heartbeat(GROUP_ID, 1, &leader.member_id).await,
+            REBALANCE_IN_PROGRESS
+        );
+
+        let leader = coordinator
+            .join_group(GROUP_ID, "client-1", create_member(&leader.member_id))
+            .await;
+        let follower = follower.await.unwrap();
+        assert_eq!(leader.generation_id, 2);
+        assert_eq!(follower.generation_id, 2);
+        assert_eq!(leader.members.len(), 2);
+        assert!(follower.members.is_empty());
+        assert_eq!(follower.leader_id, leader.member_id);
+
+        let follower_coordinator = coordinator.clone();
+        let follower_id = follower.member_id.clone();
+        let follower_sync = tokio::spawn(async move {
+            follower_coordinator
+                .sync_group(GROUP_ID, 2, &follower_id, Vec::new())
+                .await
+        });
+        let assignment = Bytes::from_static(b"partition-1");
+        coordinator
+            .sync_group(
+                GROUP_ID,
+                2,
+                &leader.member_id,
+                vec![(follower.member_id.clone(), assignment.clone())],
+            )
+            .await;
+        assert_eq!(follower_sync.await.unwrap(), (NONE, assignment));
+    }
+
+    #[tokio::test]
+    async fn leaving_member_should_trigger_rebalance() {
+        let coordinator = Arc::new(GroupCoordinator::default());
+        let first = coordinator
+            .join_group(GROUP_ID, "client-1", create_member(""))
+            .await;
+        let second_coordinator = coordinator.clone();
+        let second = tokio::spawn(async move {
+            second_coordinator
+                .join_group(GROUP_ID, "client-2", create_member(""))
+                .await
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let first = coordinator
+            .join_group(GROUP_ID, "client-1", create_member(&first.member_id))
+            .await;
+        let second = second.await.unwrap();
+
+        assert_eq!(
+            coordinator.leave_group(GROUP_ID, &second.member_id).await,
+            NONE
+        );
+        assert_eq!(
+            coordinator
+                .heartbeat(GROUP_ID, first.generation_id, &first.member_id)
+                .await,
+            REBALANCE_IN_PROGRESS
+        );
+        assert_eq!(
+            coordinator.leave_group(GROUP_ID, &second.member_id).await,
+            UNKNOWN_MEMBER_ID
+        );
+    }
+
+    #[tokio::test]
+    async fn member_with_unknown_id_or_protocol_should_be_rejected() {
+        let coordinator = GroupCoordinator::default();
+        let result = coordinator
+            .join_group(GROUP_ID, "client", create_member("unknown"))
+            .await;
+        assert_eq!(result.error_code, UNKNOWN_MEMBER_ID);
+
+        coordinator
+            .join_group(GROUP_ID, "client", create_member(""))
+            .await;
+        let mut member = create_member("");
+        member.protocols = vec![("roundrobin".to_owned(), Bytes::new())];
+        let result = coordinator.join_group(GROUP_ID, "client", member).await;
+        assert_eq!(result.error_code, INCONSISTENT_GROUP_PROTOCOL);
+    }
+}
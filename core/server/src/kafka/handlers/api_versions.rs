@@ -0,0 +1,42 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::kafka::codec::KafkaBufMut;
+use crate::kafka::protocol::{NONE, SUPPORTED_APIS, THROTTLE_TIME_MS, UNSUPPORTED_VERSION};
+use bytes::{BufMut, BytesMut};
+
+/// Returns the supported APIs with their versions. The request body is empty in all the supported versions,
+/// while the unsupported version is answered with the error using the v0 format, as expected by the clients.
+pub fn handle(api_version: i16, version_supported: bool) -> BytesMut {
+    let mut response = BytesMut::new();
+    response.put_i16(if version_supported {
+        NONE
+    } else {
+        UNSUPPORTED_VERSION
+    });
+    response.put_array_length(SUPPORTED_APIS.len());
+    for (api_key, min_version, max_version) in SUPPORTED_APIS {
+        response.put_i16(api_key);
+        response.put_i16(min_version);
+        response.put_i16(max_version);
+    }
+    if version_supported && api_version >= 1 {
+        response.put_i32(THROTTLE_TIME_MS);
+    }
+    response
+}
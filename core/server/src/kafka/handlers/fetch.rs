@@ -0,0 +1,285 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::kafka::codec::{KafkaBufMut, KafkaReader};
+use crate::kafka::handlers::{
+    PartitionOffsets, RequestContext, get_partition_offsets, to_partition_id,
+};
+use crate::kafka::protocol::{
+    NONE, OFFSET_OUT_OF_RANGE, THROTTLE_TIME_MS, UNKNOWN_TOPIC_OR_PARTITION, map_error,
+};
+use crate::kafka::records::encode_record_batch;
+use crate::kafka::topics::resolve_topic;
+use crate::streaming::systems::messages::PollingArgs;
+use crate::streaming::systems::system::System;
+use crate::streaming::topics::topic::Topic;
+use bytes::{BufMut, BytesMut};
+use futures::future::select_all;
+use iggy_common::{Consumer, Identifier, IggyError, PollingStrategy, Sizeable};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio::time::{Instant, timeout_at};
+
+/// The max number of messages polled from the single partition, further limited by the max bytes.
+const MAX_FETCH_MESSAGES: u32 = 1000;
+const NO_SESSION_ID: i32 = 0;
+const NO_PREFERRED_READ_REPLICA: i32 = -1;
+
+struct FetchTopic {
+    name: String,
+    partitions: Vec<FetchPartition>,
+}
+
+struct FetchPartition {
+    index: i32,
+    fetch_offset: i64,
+    max_bytes: i32,
+}
+
+struct PartitionResult {
+    index: i32,
+    error_code: i16,
+    offsets: Option<PartitionOffsets>,
+    records: BytesMut,
+}
+
+/// Returns the messages starting at the requested offsets. When less than the min bytes are available,
+/// the response is delayed until the new messages are appended to any of the topics or the max wait elapses.
+/// The incremental fetch sessions are not supported, thus the full fetch is always performed.
+pub async fn handle(
+    context: &RequestContext<'_>,
+    reader: &mut KafkaReader,
+) -> Result<BytesMut, IggyError> {
+    let version = context.api_version;
+    let _replica_id = reader.read_i32()?;
+    let max_wait_ms = reader.read_i32()?;
+    let min_bytes = reader.read_i32()?;
+    let max_bytes = reader.read_i32()?;
+    let _isolation_level = reader.read_i8()?;
+    if version >= 7 {
+        let _session_id = reader.read_i32()?;
+        let _session_epoch = reader.read_i32()?;
+    }
+    let topics = reader.read_array(|reader| {
+        let name = reader.read_string()?;
+        let partitions = reader.read_array(|reader| {
+            let index = reader.read_i32()?;
+            if version >= 9 {
+                let _current_leader_epoch = reader.read_i32()?;
+            }
+            let fetch_offset = reader.read_i64()?;
+            if version >= 5 {
+                let _log_start_offset = reader.read_i64()?;
+            }
+            let max_bytes = reader.read_i32()?;
+            Ok(FetchPartition {
+                index,
+                fetch_offset,
+                max_bytes,
+            })
+        })?;
+        Ok(FetchTopic { name, partitions })
+    })?;
+
+    let deadline = Instant::now() + Duration::from_millis(max_wait_ms.max(0) as u64);
+    let results = loop {
+        // The notifications are enabled before fetching, so that no messages appended in the meantime are missed.
+        let notifiers = get_notifiers(context, &topics).await;
+        let mut notifications = notifiers
+            .iter()
+            .map(|notifier| Box::pin(notifier.notified()))
+            .collect::<Vec<_>>();
+        for notification in notifications.iter_mut() {
+            notification.as_mut().enable();
+        }
+
+        let (results, fetched_bytes) = fetch(context, &topics, max_bytes).await;
+        if fetched_bytes >= min_bytes as usize
+            || notifications.is_empty()
+            || Instant::now() >= deadline
+        {
+            break results;
+        }
+
+        if timeout_at(deadline, select_all(notifications))
+            .await
+            .is_err()
+        {
+            break fetch(context, &topics, max_bytes).await.0;
+        }
+    };
+
+    let mut response = BytesMut::new();
+    response.put_i32(THROTTLE_TIME_MS);
+    if version >= 7 {
+        response.put_i16(NONE);
+        response.put_i32(NO_SESSION_ID);
+    }
+    response.put_array_length(results.len());
+    for (name, partitions) in results {
+        response.put_string(&name);
+        response.put_array_length(partitions.len());
+        for partition in partitions {
+            let (log_start_offset, high_watermark) = partition
+                .offsets
+                .map(|offsets| (offsets.log_start_offset, offsets.high_watermark))
+                .unwrap_or((-1, -1));
+            response.put_i32(partition.index);
+            response.put_i16(partition.error_code);
+            response.put_i64(high_watermark);
+            response.put_i64(high_watermark);
+            if version >= 5 {
+                response.put_i64(log_start_offset);
+            }
+            response.put_null_array();
+            if version >= 11 {
+                response.put_i32(NO_PREFERRED_READ_REPLICA);
+            }
+            response.put_nullable_bytes(Some(&partition.records));
+        }
+    }
+    Ok(response)
+}
+
+async fn get_notifiers(context: &RequestContext<'_>, topics: &[FetchTopic]) -> Vec<Arc<Notify>> {
+    let system = context.system.read().await;
+    topics
+        .iter()
+        .filter_map(
+            |topic| match resolve_topic(&system, context.session, &topic.name) {
+                Ok(Some(topic)) => Some(topic.new_messages.clone()),
+                _ => None,
+            },
+        )
+        .collect()
+}
+
+async fn fetch(
+    context: &RequestContext<'_>,
+    topics: &[FetchTopic],
+    max_bytes: i32,
+) -> (Vec<(String, Vec<PartitionResult>)>, usize) {
+    let system = context.system.read().await;
+    let mut remaining_bytes = max_bytes.max(0) as usize;
+    let mut fetched_bytes = 0;
+    let mut results = Vec::with_capacity(topics.len());
+    for fetch_topic in topics {
+        let topic = resolve_topic(&system, context.session, &fetch_topic.name);
+        let mut partition_results = Vec::with_capacity(fetch_topic.partitions.len());
+        for partition in &fetch_topic.partitions {
+            let result = match &topic {
+                Ok(Some(topic)) => {
+                    fetch_partition(context, &system, topic, partition, remaining_bytes).await
+                }
+                Ok(None) => PartitionResult::error(partition.index, UNKNOWN_TOPIC_OR_PARTITION),
+                Err(error) => PartitionResult::error(partition.index, map_error(error)),
+            };
+            remaining_bytes = remaining_bytes.saturating_sub(result.records.len());
+            fetched_bytes += result.records.len();
+            partition_results.push(result);
+        }
+        results.push((fetch_topic.name.clone(), partition_results));
+    }
+    (results, fetched_bytes)
+}
+
+async fn fetch_partition(
+    context: &RequestContext<'_>,
+    system: &System,
+    topic: &Topic,
+    partition: &FetchPartition,
+    remaining_bytes: usize,
+) -> PartitionResult {
+    let Some(partition_id) = to_partition_id(topic, partition.index) else {
+        return PartitionResult::error(partition.index, UNKNOWN_TOPIC_OR_PARTITION);
+    };
+
+    let offsets = match get_partition_offsets(topic, partition_id).await {
+        Ok(offsets) => offsets,
+        Err(error) => return PartitionResult::error(partition.index, map_error(&error)),
+    };
+
+    let mut result = PartitionResult {
+        index: partition.index,
+        error_code: NONE,
+        offsets: Some(offsets),
+        records: BytesMut::new(),
+    };
+    if partition.fetch_offset < offsets.log_start_offset
+        || partition.fetch_offset > offsets.high_watermark
+    {
+        result.error_code = OFFSET_OUT_OF_RANGE;
+        return result;
+    }
+
+    if partition.fetch_offset == offsets.high_watermark || remaining_bytes == 0 {
+        return result;
+    }
+
+    let polled_messages = match system
+        .poll_messages(
+            context.session,
+            &Consumer::default(),
+            &Identifier::numeric(topic.stream_id).unwrap(),
+            &Identifier::numeric(topic.topic_id).unwrap(),
+            Some(partition_id),
+            PollingArgs::new(
+                PollingStrategy::offset(partition.fetch_offset as u64),
+                MAX_FETCH_MESSAGES,
+                false,
+            ),
+        )
+        .await
+    {
+        Ok((metadata, batch_set)) => batch_set.into_polled_messages(metadata),
+        Err(error) => {
+            result.error_code = map_error(&error);
+            return result;
+        }
+    };
+
+    // Similarly to Kafka, the first message is always returned, even if it exceeds the max bytes.
+    let max_bytes = (partition.max_bytes.max(0) as usize).min(remaining_bytes);
+    let mut messages_size = 0;
+    let messages_count = polled_messages
+        .messages
+        .iter()
+        .take_while(|message| {
+            let first = messages_size == 0;
+            messages_size += message.get_size_bytes().as_bytes_u64() as usize;
+            first || messages_size <= max_bytes
+        })
+        .count();
+    encode_record_batch(
+        &polled_messages.messages[..messages_count],
+        &mut result.records,
+    );
+    result
+}
+
+impl PartitionResult {
+    fn error(index: i32, error_code: i16) -> Self {
+        Self {
+            index,
+            error_code,
+            offsets: None,
+            records: BytesMut::new(),
+        }
+    }
+}
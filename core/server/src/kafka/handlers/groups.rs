@@ -0,0 +1,190 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::kafka::codec::{KafkaBufMut, KafkaReader};
+use crate::kafka::group_coordinator::JoinGroupMember;
+use crate::kafka::handlers::RequestContext;
+use crate::kafka::protocol::{COORDINATOR_NOT_AVAILABLE, NODE_ID, NONE, THROTTLE_TIME_MS};
+use bytes::{BufMut, BytesMut};
+use iggy_common::IggyError;
+use std::time::Duration;
+
+const GROUP_KEY_TYPE: i8 = 0;
+
+/// Returns this server as the coordinator of every consumer group, the transaction coordinator is not available.
+pub fn handle_find_coordinator(
+    context: &RequestContext<'_>,
+    reader: &mut KafkaReader,
+) -> Result<BytesMut, IggyError> {
+    let version = context.api_version;
+    let _key = reader.read_string()?;
+    let key_type = if version >= 1 {
+        reader.read_i8()?
+    } else {
+        GROUP_KEY_TYPE
+    };
+
+    let mut response = BytesMut::new();
+    if version >= 1 {
+        response.put_i32(THROTTLE_TIME_MS);
+    }
+    if key_type != GROUP_KEY_TYPE {
+        response.put_i16(COORDINATOR_NOT_AVAILABLE);
+        if version >= 1 {
+            response.put_nullable_string(Some("Transactions are not supported"));
+        }
+        response.put_i32(-1);
+        response.put_string("");
+        response.put_i32(-1);
+        return Ok(response);
+    }
+
+    response.put_i16(NONE);
+    if version >= 1 {
+        response.put_nullable_string(None);
+    }
+    response.put_i32(NODE_ID);
+    response.put_string(&context.broker.host);
+    response.put_i32(context.broker.port);
+    Ok(response)
+}
+
+pub async fn handle_join_group(
+    context: &RequestContext<'_>,
+    reader: &mut KafkaReader,
+) -> Result<BytesMut, IggyError> {
+    let version = context.api_version;
+    let group_id = reader.read_string()?;
+    let session_timeout = reader.read_i32()?;
+    let rebalance_timeout = if version >= 1 {
+        reader.read_i32()?
+    } else {
+        session_timeout
+    };
+    let member_id = reader.read_string()?;
+    if version >= 5 {
+        let _group_instance_id = reader.read_nullable_string()?;
+    }
+    let protocol_type = reader.read_string()?;
+    let protocols =
+        reader.read_array(|reader| Ok((reader.read_string()?, reader.read_bytes()?)))?;
+
+    let result = context
+        .coordinator
+        .join_group(
+            &group_id,
+            context.client_id,
+            JoinGroupMember {
+                member_id,
+                session_timeout: Duration::from_millis(session_timeout.max(0) as u64),
+                rebalance_timeout: Duration::from_millis(rebalance_timeout.max(0) as u64),
+                protocol_type,
+                protocols,
+            },
+        )
+        .await;
+
+    let mut response = BytesMut::new();
+    if version >= 2 {
+        response.put_i32(THROTTLE_TIME_MS);
+    }
+    response.put_i16(result.error_code);
+    response.put_i32(result.generation_id);
+    response.put_string(&result.protocol_name);
+    response.put_string(&result.leader_id);
+    response.put_string(&result.member_id);
+    response.put_array_length(result.members.len());
+    for (member_id, metadata) in &result.members {
+        response.put_string(member_id);
+        if version >= 5 {
+            response.put_nullable_string(None);
+        }
+        response.put_kafka_bytes(metadata);
+    }
+    Ok(response)
+}
+
+pub async fn handle_sync_group(
+    context: &RequestContext<'_>,
+    reader: &mut KafkaReader,
+) -> Result<BytesMut, IggyError> {
+    let version = context.api_version;
+    let group_id = reader.read_string()?;
+    let generation_id = reader.read_i32()?;
+    let member_id = reader.read_string()?;
+    if version >= 3 {
+        let _group_instance_id = reader.read_nullable_string()?;
+    }
+    let assignments =
+        reader.read_array(|reader| Ok((reader.read_string()?, reader.read_bytes()?)))?;
+
+    let (error_code, assignment) = context
+        .coordinator
+        .sync_group(&group_id, generation_id, &member_id, assignments)
+        .await;
+
+    let mut response = BytesMut::new();
+    if version >= 1 {
+        response.put_i32(THROTTLE_TIME_MS);
+    }
+    response.put_i16(error_code);
+    response.put_kafka_bytes(&assignment);
+    Ok(response)
+}
+
+pub async fn handle_heartbeat(
+    context: &RequestContext<'_>,
+    reader: &mut KafkaReader,
+) -> Result<BytesMut, IggyError> {
+    let version = context.api_version;
+    let group_id = reader.read_string()?;
+    let generation_id = reader.read_i32()?;
+    let member_id = reader.read_string()?;
+    if version >= 3 {
+        let _group_instance_id = reader.read_nullable_string()?;
+    }
+
+    let error_code = context
+        .coordinator
+        .heartbeat(&group_id, generation_id, &member_id)
+        .await;
+
+    let mut response = BytesMut::new();
+    if version >= 1 {
+        response.put_i32(THROTTLE_TIME_MS);
+    }
+    response.put_i16(error_code);
+    Ok(response)
+}
+
+pub async fn handle_leave_group(
+    context: &RequestContext<'_>,
+    reader: &mut KafkaReader,
+) -> Result<BytesMut, IggyError> {
+    let group_id = reader.read_string()?;
+    let member_id = reader.read_string()?;
+
+    let error_code = context.coordinator.leave_group(&group_id, &member_id).await;
+
+    let mut response = BytesMut::new();
+    if context.api_version >= 1 {
+        response.put_i32(THROTTLE_TIME_MS);
+    }
+    response.put_i16(error_code);
+    Ok(response)
+}
@@ -0,0 +1,130 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::kafka::codec::{KafkaBufMut, KafkaReader};
+use crate::kafka::handlers::{RequestContext, get_partition_offsets, to_partition_id};
+use crate::kafka::protocol::{
+    INVALID_REQUEST, NONE, THROTTLE_TIME_MS, UNKNOWN_TOPIC_OR_PARTITION, map_error,
+};
+use crate::kafka::topics::resolve_topic;
+use crate::streaming::systems::messages::PollingArgs;
+use crate::streaming::systems::system::System;
+use crate::streaming::topics::topic::Topic;
+use bytes::{BufMut, BytesMut};
+use iggy_common::{Consumer, Identifier, IggyError, IggyTimestamp, PollingStrategy};
+
+const LATEST_TIMESTAMP: i64 = -1;
+const EARLIEST_TIMESTAMP: i64 = -2;
+const UNKNOWN_OFFSET: i64 = -1;
+const NO_TIMESTAMP: i64 = -1;
+const LEADER_EPOCH: i32 = 0;
+
+/// Returns the earliest or the latest offset of the partition, or the offset of the first message
+/// with the timestamp greater than or equal to the requested one (in milliseconds).
+pub async fn handle(
+    context: &RequestContext<'_>,
+    reader: &mut KafkaReader,
+) -> Result<BytesMut, IggyError> {
+    let version = context.api_version;
+    let _replica_id = reader.read_i32()?;
+    if version >= 2 {
+        let _isolation_level = reader.read_i8()?;
+    }
+    let topics = reader.read_array(|reader| {
+        let name = reader.read_string()?;
+        let partitions = reader.read_array(|reader| {
+            let index = reader.read_i32()?;
+            if version >= 4 {
+                let _current_leader_epoch = reader.read_i32()?;
+            }
+            Ok((index, reader.read_i64()?))
+        })?;
+        Ok((name, partitions))
+    })?;
+
+    let mut response = BytesMut::new();
+    if version >= 2 {
+        response.put_i32(THROTTLE_TIME_MS);
+    }
+    let system = context.system.read().await;
+    response.put_array_length(topics.len());
+    for (name, partitions) in topics {
+        let topic = resolve_topic(&system, context.session, &name);
+        response.put_string(&name);
+        response.put_array_length(partitions.len());
+        for (index, timestamp) in partitions {
+            let result = match &topic {
+                Ok(Some(topic)) => list_offset(context, &system, topic, index, timestamp).await,
+                Ok(None) => Err(UNKNOWN_TOPIC_OR_PARTITION),
+                Err(error) => Err(map_error(error)),
+            };
+            let (error_code, timestamp, offset) = match result {
+                Ok((timestamp, offset)) => (NONE, timestamp, offset),
+                Err(error_code) => (error_code, NO_TIMESTAMP, UNKNOWN_OFFSET),
+            };
+            response.put_i32(index);
+            response.put_i16(error_code);
+            response.put_i64(timestamp);
+            response.put_i64(offset);
+            if version >= 4 {
+                response.put_i32(LEADER_EPOCH);
+            }
+        }
+    }
+    Ok(response)
+}
+
+async fn list_offset(
+    context: &RequestContext<'_>,
+    system: &System,
+    topic: &Topic,
+    partition_index: i32,
+    timestamp: i64,
+) -> Result<(i64, i64), i16> {
+    let partition_id = to_partition_id(topic, partition_index).ok_or(UNKNOWN_TOPIC_OR_PARTITION)?;
+    let offsets = get_partition_offsets(topic, partition_id)
+        .await
+        .map_err(|error| map_error(&error))?;
+    match timestamp {
+        LATEST_TIMESTAMP => Ok((NO_TIMESTAMP, offsets.high_watermark)),
+        EARLIEST_TIMESTAMP => Ok((NO_TIMESTAMP, offsets.log_start_offset)),
+        timestamp if timestamp < 0 => Err(INVALID_REQUEST),
+        timestamp => {
+            let (_, batch_set) = system
+                .poll_messages(
+                    context.session,
+                    &Consumer::default(),
+                    &Identifier::numeric(topic.stream_id).unwrap(),
+                    &Identifier::numeric(topic.topic_id).unwrap(),
+                    Some(partition_id),
+                    PollingArgs::new(
+                        PollingStrategy::timestamp(IggyTimestamp::from(timestamp as u64 * 1000)),
+                        1,
+                        false,
+                    ),
+                )
+                .await
+                .map_err(|error| map_error(&error))?;
+            let Some(message) = batch_set.iter().flat_map(|batch| batch.iter()).next() else {
+                return Ok((NO_TIMESTAMP, UNKNOWN_OFFSET));
+            };
+            let header = message.header();
+            Ok(((header.timestamp() / 1000) as i64, header.offset() as i64))
+        }
+    }
+}
@@ -0,0 +1,146 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::kafka::codec::{KafkaBufMut, KafkaReader};
+use crate::kafka::handlers::RequestContext;
+use crate::kafka::protocol::{
+    CLUSTER_ID, NODE_ID, NONE, THROTTLE_TIME_MS, UNKNOWN_TOPIC_OR_PARTITION, map_error,
+};
+use crate::kafka::topics::{resolve_topic, to_kafka_topic_name};
+use bytes::{BufMut, BytesMut};
+use iggy_common::{Identifier, IggyError};
+
+/// The authorized operations are never returned, as the Iggy permissions can't be mapped onto the Kafka ACLs.
+const AUTHORIZED_OPERATIONS_OMITTED: i32 = i32::MIN;
+
+struct TopicMetadata {
+    error_code: i16,
+    name: String,
+    partitions_count: u32,
+}
+
+/// Returns the single broker (this server) being the leader of all the partitions, and the topics
+/// mapped onto the Iggy topics. The topics are never created automatically.
+pub async fn handle(
+    context: &RequestContext<'_>,
+    reader: &mut KafkaReader,
+) -> Result<BytesMut, IggyError> {
+    let version = context.api_version;
+    let requested_topics = if version == 0 {
+        Some(reader.read_array(KafkaReader::read_string)?).filter(|topics| !topics.is_empty())
+    } else {
+        reader.read_nullable_array(KafkaReader::read_string)?
+    };
+
+    let topics = {
+        let system = context.system.read().await;
+        match requested_topics {
+            Some(names) => names
+                .into_iter()
+                .map(
+                    |name| match resolve_topic(&system, context.session, &name) {
+                        Ok(Some(topic)) => TopicMetadata {
+                            error_code: NONE,
+                            name,
+                            partitions_count: topic.partitions.len() as u32,
+                        },
+                        Ok(None) => TopicMetadata {
+                            error_code: UNKNOWN_TOPIC_OR_PARTITION,
+                            name,
+                            partitions_count: 0,
+                        },
+                        Err(error) => TopicMetadata {
+                            error_code: map_error(&error),
+                            name,
+                            partitions_count: 0,
+                        },
+                    },
+                )
+                .collect::<Vec<_>>(),
+            None => {
+                let mut topics = Vec::new();
+                for stream in system.get_streams() {
+                    // The streams (or topics) which the user isn't allowed to see are skipped.
+                    let Ok(stream_topics) = system
+                        .find_topics(context.session, &Identifier::numeric(stream.stream_id)?)
+                    else {
+                        continue;
+                    };
+                    for topic in stream_topics {
+                        topics.push(TopicMetadata {
+                            error_code: NONE,
+                            name: to_kafka_topic_name(&stream.name, &topic.name),
+                            partitions_count: topic.partitions.len() as u32,
+                        });
+                    }
+                }
+                topics
+            }
+        }
+    };
+
+    let mut response = BytesMut::new();
+    if version >= 3 {
+        response.put_i32(THROTTLE_TIME_MS);
+    }
+    response.put_array_length(1);
+    response.put_i32(NODE_ID);
+    response.put_string(&context.broker.host);
+    response.put_i32(context.broker.port);
+    if version >= 1 {
+        response.put_nullable_string(None);
+    }
+    if version >= 2 {
+        response.put_nullable_string(Some(CLUSTER_ID));
+    }
+    if version >= 1 {
+        response.put_i32(NODE_ID);
+    }
+
+    response.put_array_length(topics.len());
+    for topic in topics {
+        response.put_i16(topic.error_code);
+        response.put_string(&topic.name);
+        if version >= 1 {
+            response.put_bool(false);
+        }
+        response.put_array_length(topic.partitions_count as usize);
+        for partition_index in 0..topic.partitions_count {
+            response.put_i16(NONE);
+            response.put_i32(partition_index as i32);
+            response.put_i32(NODE_ID);
+            if version >= 7 {
+                response.put_i32(0);
+            }
+            response.put_array_length(1);
+            response.put_i32(NODE_ID);
+            response.put_array_length(1);
+            response.put_i32(NODE_ID);
+            if version >= 5 {
+                response.put_array_length(0);
+            }
+        }
+        if version >= 8 {
+            response.put_i32(AUTHORIZED_OPERATIONS_OMITTED);
+        }
+    }
+    if version >= 8 {
+        response.put_i32(AUTHORIZED_OPERATIONS_OMITTED);
+    }
+    Ok(response)
+}
@@ -0,0 +1,92 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+pub mod api_versions;
+pub mod fetch;
+pub mod groups;
+pub mod list_offsets;
+pub mod metadata;
+pub mod offsets;
+pub mod produce;
+pub mod sasl;
+
+use crate::kafka::group_coordinator::GroupCoordinator;
+use crate::streaming::session::Session;
+use crate::streaming::systems::system::SharedSystem;
+use crate::streaming::topics::topic::Topic;
+use iggy_common::IggyError;
+use iggy_common::locking::IggySharedMutFn;
+
+/// The address of the broker returned by the Metadata and FindCoordinator responses.
+#[derive(Debug, Clone)]
+pub struct BrokerAddress {
+    pub host: String,
+    pub port: i32,
+}
+
+/// The state shared by the handlers of the requests received within the single connection.
+pub struct RequestContext<'a> {
+    pub api_version: i16,
+    pub client_id: &'a str,
+    pub session: &'a Session,
+    pub system: &'a SharedSystem,
+    pub coordinator: &'a GroupCoordinator,
+    pub broker: &'a BrokerAddress,
+    /// Limits also the size of the decompressed records of the single batch.
+    pub max_request_size: u64,
+}
+
+/// The offsets of the partition, as seen by the Kafka clients.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PartitionOffsets {
+    pub log_start_offset: i64,
+    /// The offset of the next message to be appended.
+    pub high_watermark: i64,
+}
+
+/// Maps the Kafka partition index (starting at 0) onto the Iggy partition ID (starting at 1).
+pub(crate) fn to_partition_id(topic: &Topic, partition_index: i32) -> Option<u32> {
+    if partition_index < 0 || partition_index as usize >= topic.partitions.len() {
+        return None;
+    }
+
+    Some(partition_index as u32 + 1)
+}
+
+pub(crate) async fn get_partition_offsets(
+    topic: &Topic,
+    partition_id: u32,
+) -> Result<PartitionOffsets, IggyError> {
+    let partition = topic.get_partition(partition_id)?;
+    let partition = partition.read().await;
+    let high_watermark = if partition.should_increment_offset {
+        partition.current_offset as i64 + 1
+    } else {
+        0
+    };
+    let log_start_offset = partition
+        .get_segments()
+        .first()
+        .map(|segment| segment.start_offset() as i64)
+        .unwrap_or(high_watermark)
+        .min(high_watermark);
+    Ok(PartitionOffsets {
+        log_start_offset,
+        high_watermark,
+    })
+}
@@ -0,0 +1,313 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::kafka::COMPONENT;
+use crate::kafka::codec::{KafkaBufMut, KafkaReader};
+use crate::kafka::handlers::{RequestContext, to_partition_id};
+use crate::kafka::protocol::{NONE, THROTTLE_TIME_MS, UNKNOWN_TOPIC_OR_PARTITION, map_error};
+use crate::kafka::topics::{resolve_topic, to_kafka_topic_name};
+use crate::state::command::EntryCommand;
+use crate::state::models::CreateConsumerGroupWithId;
+use crate::streaming::systems::system::System;
+use crate::streaming::topics::topic::Topic;
+use bytes::{BufMut, BytesMut};
+use error_set::ErrContext;
+use iggy_common::create_consumer_group::CreateConsumerGroup;
+use iggy_common::{Consumer, Identifier, IggyError};
+use tracing::info;
+
+const NO_OFFSET: i64 = -1;
+const NO_LEADER_EPOCH: i32 = -1;
+const NO_METADATA: &str = "";
+
+/// Stores the offsets committed by the Kafka consumer group in the Iggy consumer group with the same name,
+/// which is created on the first commit. The Kafka offset is the offset of the next message to consume,
+/// thus the offset of the last consumed message is stored, just like the Iggy consumers do.
+pub async fn handle_offset_commit(
+    context: &RequestContext<'_>,
+    reader: &mut KafkaReader,
+) -> Result<BytesMut, IggyError> {
+    let version = context.api_version;
+    let group_id = reader.read_string()?;
+    let generation_id = reader.read_i32()?;
+    let member_id = reader.read_string()?;
+    if version >= 7 {
+        let _group_instance_id = reader.read_nullable_string()?;
+    }
+    if version <= 4 {
+        let _retention_time_ms = reader.read_i64()?;
+    }
+    let topics = reader.read_array(|reader| {
+        let name = reader.read_string()?;
+        let partitions = reader.read_array(|reader| {
+            let index = reader.read_i32()?;
+            let offset = reader.read_i64()?;
+            if version >= 6 {
+                let _committed_leader_epoch = reader.read_i32()?;
+            }
+            let _committed_metadata = reader.read_nullable_string()?;
+            Ok((index, offset))
+        })?;
+        Ok((name, partitions))
+    })?;
+
+    let group_error_code = context
+        .coordinator
+        .validate_offset_commit(&group_id, generation_id, &member_id)
+        .await;
+
+    let mut response = BytesMut::new();
+    if version >= 3 {
+        response.put_i32(THROTTLE_TIME_MS);
+    }
+    response.put_array_length(topics.len());
+    for (name, partitions) in topics {
+        let topic_error_code = if group_error_code != NONE {
+            group_error_code
+        } else {
+            match ensure_consumer_group(context, &name, &group_id).await {
+                Ok(()) => NONE,
+                Err(error) => map_error(&error),
+            }
+        };
+
+        let system = context.system.read().await;
+        let topic = resolve_topic(&system, context.session, &name);
+        response.put_string(&name);
+        response.put_array_length(partitions.len());
+        for (index, offset) in partitions {
+            let error_code = match &topic {
+                _ if topic_error_code != NONE => topic_error_code,
+                Ok(Some(topic)) => {
+                    match commit_offset(context, &system, topic, &group_id, index, offset).await {
+                        Ok(()) => NONE,
+                        Err(error_code) => error_code,
+                    }
+                }
+                Ok(None) => UNKNOWN_TOPIC_OR_PARTITION,
+                Err(error) => map_error(error),
+            };
+            response.put_i32(index);
+            response.put_i16(error_code);
+        }
+    }
+    Ok(response)
+}
+
+async fn commit_offset(
+    context: &RequestContext<'_>,
+    system: &System,
+    topic: &Topic,
+    group_id: &str,
+    partition_index: i32,
+    offset: i64,
+) -> Result<(), i16> {
+    let partition_id = to_partition_id(topic, partition_index).ok_or(UNKNOWN_TOPIC_OR_PARTITION)?;
+    // No message has been consumed yet, and the Iggy offsets can't represent it.
+    if offset <= 0 {
+        return Ok(());
+    }
+
+    system
+        .store_consumer_offset(
+            context.session,
+            Consumer::group(Identifier::named(group_id).map_err(|error| map_error(&error))?),
+            &Identifier::numeric(topic.stream_id).unwrap(),
+            &Identifier::numeric(topic.topic_id).unwrap(),
+            Some(partition_id),
+            offset as u64 - 1,
+        )
+        .await
+        .map_err(|error| map_error(&error))
+}
+
+/// Creates the Iggy consumer group storing the offsets of the Kafka consumer group, unless it already exists.
+async fn ensure_consumer_group(
+    context: &RequestContext<'_>,
+    topic_name: &str,
+    group_name: &str,
+) -> Result<(), IggyError> {
+    let group_identifier = Identifier::named(group_name)?;
+    let (stream_id, topic_id) = {
+        let system = context.system.read().await;
+        let Some(topic) = resolve_topic(&system, context.session, topic_name)? else {
+            return Err(IggyError::ResourceNotFound(topic_name.to_owned()));
+        };
+        let stream_id = Identifier::numeric(topic.stream_id)?;
+        let topic_id = Identifier::numeric(topic.topic_id)?;
+        if system
+            .get_consumer_group(context.session, &stream_id, &topic_id, &group_identifier)?
+            .is_some()
+        {
+            return Ok(());
+        }
+        (stream_id, topic_id)
+    };
+
+    let mut system = context.system.write().await;
+    let consumer_group = match system
+        .create_consumer_group(context.session, &stream_id, &topic_id, None, group_name)
+        .await
+    {
+        Ok(consumer_group) => consumer_group,
+        // The group might have been created by another connection in the meantime.
+        Err(IggyError::ConsumerGroupNameAlreadyExists(_, _)) => return Ok(()),
+        Err(error) => return Err(error),
+    };
+    let consumer_group_id = consumer_group.read().await.group_id;
+    let system = system.downgrade();
+    system
+        .state
+        .apply(
+            context.session.get_user_id(),
+            &EntryCommand::CreateConsumerGroup(CreateConsumerGroupWithId {
+                group_id: consumer_group_id,
+                command: CreateConsumerGroup {
+                    stream_id: stream_id.clone(),
+                    topic_id: topic_id.clone(),
+                    group_id: None,
+                    name: group_name.to_owned(),
+                },
+            }),
+        )
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to apply create consumer group for stream_id: {stream_id}, topic_id: {topic_id}, group: {group_name}, session: {}",
+                context.session
+            )
+        })?;
+    info!(
+        "Created consumer group: {group_name} with ID: {consumer_group_id} for Kafka topic: {topic_name}."
+    );
+    Ok(())
+}
+
+/// Returns the offsets committed by the Kafka consumer group, either for the requested partitions
+/// or for all the partitions of the topics having the Iggy consumer group with the same name.
+pub async fn handle_offset_fetch(
+    context: &RequestContext<'_>,
+    reader: &mut KafkaReader,
+) -> Result<BytesMut, IggyError> {
+    let version = context.api_version;
+    let group_id = reader.read_string()?;
+    let requested_topics = reader.read_nullable_array(|reader| {
+        let name = reader.read_string()?;
+        let partitions = reader.read_array(KafkaReader::read_i32)?;
+        Ok((name, partitions))
+    })?;
+
+    let consumer = Consumer::group(Identifier::named(&group_id)?);
+    let system = context.system.read().await;
+    let mut topics = Vec::new();
+    match requested_topics {
+        Some(requested_topics) => {
+            for (name, partitions) in requested_topics {
+                let topic = resolve_topic(&system, context.session, &name);
+                let mut results = Vec::with_capacity(partitions.len());
+                for index in partitions {
+                    let result = match &topic {
+                        Ok(Some(topic)) => {
+                            fetch_offset(context, &system, topic, &consumer, index).await
+                        }
+                        Ok(None) => Err(UNKNOWN_TOPIC_OR_PARTITION),
+                        Err(error) => Err(map_error(error)),
+                    };
+                    results.push((index, result));
+                }
+                topics.push((name, results));
+            }
+        }
+        None => {
+            for stream in system.get_streams() {
+                let Ok(stream_topics) =
+                    system.find_topics(context.session, &Identifier::numeric(stream.stream_id)?)
+                else {
+                    continue;
+                };
+                for topic in stream_topics {
+                    if topic.get_consumer_group(&consumer.id).is_err() {
+                        continue;
+                    }
+
+                    let mut results = Vec::new();
+                    for index in 0..topic.partitions.len() as i32 {
+                        let result = fetch_offset(context, &system, topic, &consumer, index).await;
+                        if matches!(result, Ok(offset) if offset != NO_OFFSET) {
+                            results.push((index, result));
+                        }
+                    }
+                    if !results.is_empty() {
+                        topics.push((to_kafka_topic_name(&stream.name, &topic.name), results));
+                    }
+                }
+            }
+        }
+    }
+
+    let mut response = BytesMut::new();
+    if version >= 3 {
+        response.put_i32(THROTTLE_TIME_MS);
+    }
+    response.put_array_length(topics.len());
+    for (name, partitions) in topics {
+        response.put_string(&name);
+        response.put_array_length(partitions.len());
+        for (index, result) in partitions {
+            let (offset, error_code) = match result {
+                Ok(offset) => (offset, NONE),
+                Err(error_code) => (NO_OFFSET, error_code),
+            };
+            response.put_i32(index);
+            response.put_i64(offset);
+            if version >= 5 {
+                response.put_i32(NO_LEADER_EPOCH);
+            }
+            response.put_nullable_string(Some(NO_METADATA));
+            response.put_i16(error_code);
+        }
+    }
+    if version >= 2 {
+        response.put_i16(NONE);
+    }
+    Ok(response)
+}
+
+async fn fetch_offset(
+    context: &RequestContext<'_>,
+    system: &System,
+    topic: &Topic,
+    consumer: &Consumer,
+    partition_index: i32,
+) -> Result<i64, i16> {
+    let partition_id = to_partition_id(topic, partition_index).ok_or(UNKNOWN_TOPIC_OR_PARTITION)?;
+    match system
+        .get_consumer_offset(
+            context.session,
+            consumer,
+            &Identifier::numeric(topic.stream_id).unwrap(),
+            &Identifier::numeric(topic.topic_id).unwrap(),
+            Some(partition_id),
+        )
+        .await
+    {
+        Ok(Some(offset)) => Ok(offset.stored_offset as i64 + 1),
+        Ok(None) | Err(IggyError::ConsumerGroupNameNotFound(_, _)) => Ok(NO_OFFSET),
+        Err(error) => Err(map_error(&error)),
+    }
+}
@@ -0,0 +1,215 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::kafka::COMPONENT;
+use crate::kafka::codec::{KafkaBufMut, KafkaReader};
+use crate::kafka::handlers::{RequestContext, to_partition_id};
+use crate::kafka::protocol::{
+    INVALID_REQUEST, NONE, THROTTLE_TIME_MS, UNKNOWN_TOPIC_OR_PARTITION, map_error,
+};
+use crate::kafka::records::RecordBatch;
+use crate::kafka::topics::resolve_topic;
+use crate::streaming::segments::IggyMessagesBatchMut;
+use crate::streaming::systems::system::System;
+use bytes::{BufMut, Bytes, BytesMut};
+use iggy_common::{Identifier, IggyError, IggyMessage, Partitioning, ProducerSequence, Sizeable};
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+const NO_ACKS: i16 = 0;
+const UNKNOWN_OFFSET: i64 = -1;
+const NO_TIMESTAMP: i64 = -1;
+
+struct PartitionData {
+    index: i32,
+    records: Option<Bytes>,
+}
+
+/// Appends the record batches to the Iggy partitions. The batches of the idempotent producers are appended
+/// with the producer sequence, so the retried batches are discarded by the partition. The response isn't sent
+/// at all when no acknowledgements were requested.
+pub async fn handle(
+    context: &RequestContext<'_>,
+    reader: &mut KafkaReader,
+) -> Result<Option<BytesMut>, IggyError> {
+    let version = context.api_version;
+    let transactional_id = reader.read_nullable_string()?;
+    let acks = reader.read_i16()?;
+    let _timeout_ms = reader.read_i32()?;
+    let topics = reader.read_array(|reader| {
+        let name = reader.read_string()?;
+        let partitions = reader.read_array(|reader| {
+            Ok(PartitionData {
+                index: reader.read_i32()?,
+                records: reader.read_nullable_bytes()?,
+            })
+        })?;
+        Ok((name, partitions))
+    })?;
+
+    let mut results = Vec::with_capacity(topics.len());
+    {
+        let system = context.system.read().await;
+        for (name, partitions) in topics {
+            let topic = resolve_topic(&system, context.session, &name);
+            let mut partition_results = Vec::with_capacity(partitions.len());
+            for partition in partitions {
+                let error_code = if transactional_id.is_some() {
+                    INVALID_REQUEST
+                } else {
+                    match &topic {
+                        Ok(Some(topic)) => match to_partition_id(topic, partition.index) {
+                            Some(partition_id) => {
+                                let stream_id = Identifier::numeric(topic.stream_id)?;
+                                let topic_id = Identifier::numeric(topic.topic_id)?;
+                                append_records(
+                                    context,
+                                    &system,
+                                    &stream_id,
+                                    &topic_id,
+                                    partition_id,
+                                    partition.records,
+                                )
+                                .await
+                            }
+                            None => UNKNOWN_TOPIC_OR_PARTITION,
+                        },
+                        Ok(None) => UNKNOWN_TOPIC_OR_PARTITION,
+                        Err(error) => map_error(error),
+                    }
+                };
+                partition_results.push((partition.index, error_code));
+            }
+            results.push((name, partition_results));
+        }
+    }
+
+    if acks == NO_ACKS {
+        return Ok(None);
+    }
+
+    let mut response = BytesMut::new();
+    response.put_array_length(results.len());
+    for (name, partitions) in results {
+        response.put_string(&name);
+        response.put_array_length(partitions.len());
+        for (index, error_code) in partitions {
+            response.put_i32(index);
+            response.put_i16(error_code);
+            response.put_i64(UNKNOWN_OFFSET);
+            response.put_i64(NO_TIMESTAMP);
+            if version >= 5 {
+                response.put_i64(UNKNOWN_OFFSET);
+            }
+            if version >= 8 {
+                response.put_array_length(0);
+                response.put_nullable_string(None);
+            }
+        }
+    }
+    response.put_i32(THROTTLE_TIME_MS);
+    Ok(Some(response))
+}
+
+async fn append_records(
+    context: &RequestContext<'_>,
+    system: &System,
+    stream_id: &Identifier,
+    topic_id: &Identifier,
+    partition_id: u32,
+    records: Option<Bytes>,
+) -> i16 {
+    let Some(records) = records else {
+        return NONE;
+    };
+
+    let batches = match RecordBatch::decode_all(records, context.max_request_size as usize) {
+        Ok(batches) => batches,
+        Err(error) => {
+            warn!(
+                "{COMPONENT} - invalid record batch, session: {}, error: {error}",
+                context.session
+            );
+            return error.error_code();
+        }
+    };
+
+    for batch in batches {
+        let producer_sequence = (batch.producer_id >= 0 && batch.base_sequence >= 0)
+            .then(|| ProducerSequence::new(batch.producer_id as u64, batch.base_sequence as u64));
+        let messages = match batch
+            .records
+            .into_iter()
+            .map(|record| record.into_message())
+            .collect::<Result<Vec<IggyMessage>, IggyError>>()
+        {
+            Ok(messages) => messages,
+            Err(error) => return map_error(&error),
+        };
+        if messages.is_empty() {
+            continue;
+        }
+
+        let messages_size = messages
+            .iter()
+            .map(|message| message.get_size_bytes().as_bytes_u32())
+            .sum();
+        let batch = IggyMessagesBatchMut::from_messages(&messages, messages_size);
+        if let Err(error) = system
+            .append_messages(
+                context.session,
+                stream_id,
+                topic_id,
+                &Partitioning::partition_id(partition_id),
+                batch,
+                producer_sequence,
+                None,
+            )
+            .await
+        {
+            debug!(
+                "{COMPONENT} - failed to append messages to partition: {partition_id}, topic: {topic_id}, stream: {stream_id}, session: {}, error: {error}",
+                context.session
+            );
+            return map_error(&error);
+        }
+    }
+
+    NONE
+}
+
+/// Assigns the new producer ID, the transactional producers are not supported.
+pub fn handle_init_producer_id(reader: &mut KafkaReader) -> Result<BytesMut, IggyError> {
+    let transactional_id = reader.read_nullable_string()?;
+    let _transaction_timeout_ms = reader.read_i32()?;
+
+    let mut response = BytesMut::new();
+    response.put_i32(THROTTLE_TIME_MS);
+    if transactional_id.is_some() {
+        response.put_i16(INVALID_REQUEST);
+        response.put_i64(-1);
+        response.put_i16(-1);
+        return Ok(response);
+    }
+
+    let producer_id = (Uuid::new_v4().as_u64_pair().0 >> 1) as i64;
+    response.put_i16(NONE);
+    response.put_i64(producer_id);
+    response.put_i16(0);
+    Ok(response)
+}
@@ -0,0 +1,95 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::kafka::codec::{KafkaBufMut, KafkaReader};
+use crate::kafka::handlers::RequestContext;
+use crate::kafka::protocol::{NONE, SASL_AUTHENTICATION_FAILED, UNSUPPORTED_SASL_MECHANISM};
+use bytes::{BufMut, BytesMut};
+use iggy_common::IggyError;
+use tracing::{info, warn};
+
+const PLAIN_MECHANISM: &str = "PLAIN";
+const NO_SESSION_LIFETIME: i64 = 0;
+
+/// Accepts the PLAIN mechanism only, returns whether the client may proceed with the authentication.
+pub fn handle_handshake(reader: &mut KafkaReader) -> Result<(BytesMut, bool), IggyError> {
+    let mechanism = reader.read_string()?;
+    let accepted = mechanism == PLAIN_MECHANISM;
+
+    let mut response = BytesMut::new();
+    response.put_i16(if accepted {
+        NONE
+    } else {
+        UNSUPPORTED_SASL_MECHANISM
+    });
+    response.put_array_length(1);
+    response.put_string(PLAIN_MECHANISM);
+    Ok((response, accepted))
+}
+
+/// Logs in the Iggy user with the credentials sent using the PLAIN mechanism (`authzid\0username\0password`),
+/// returns whether the user has been authenticated.
+pub async fn handle_authenticate(
+    context: &RequestContext<'_>,
+    reader: &mut KafkaReader,
+) -> Result<(BytesMut, bool), IggyError> {
+    let auth_bytes = reader.read_bytes()?;
+    let credentials = std::str::from_utf8(&auth_bytes)
+        .ok()
+        .map(|credentials| credentials.split('\0').collect::<Vec<_>>());
+
+    let authenticated = match credentials.as_deref() {
+        Some([_, username, password]) => {
+            let system = context.system.read().await;
+            match system
+                .login_user(username, password, Some(context.session))
+                .await
+            {
+                Ok(user) => {
+                    info!(
+                        "Authenticated user: {} with ID: {} using Kafka SASL/PLAIN, session: {}.",
+                        user.username, user.id, context.session
+                    );
+                    true
+                }
+                Err(error) => {
+                    warn!(
+                        "Failed to authenticate user: {username} using Kafka SASL/PLAIN, session: {}, error: {error}",
+                        context.session
+                    );
+                    false
+                }
+            }
+        }
+        _ => false,
+    };
+
+    let mut response = BytesMut::new();
+    if authenticated {
+        response.put_i16(NONE);
+        response.put_nullable_string(None);
+    } else {
+        response.put_i16(SASL_AUTHENTICATION_FAILED);
+        response.put_nullable_string(Some("Invalid username or password"));
+    }
+    response.put_kafka_bytes(&[]);
+    if context.api_version >= 1 {
+        response.put_i64(NO_SESSION_LIFETIME);
+    }
+    Ok((response, authenticated))
+}
@@ -0,0 +1,138 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::configs::kafka::KafkaConfig;
+use crate::kafka::connection_handler::handle_connection;
+use crate::kafka::group_coordinator::GroupCoordinator;
+use crate::kafka::handlers::BrokerAddress;
+use crate::streaming::clients::client_manager::Transport;
+use crate::streaming::systems::system::SharedSystem;
+use crate::tcp::connection_handler::handle_error;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::io::AsyncWriteExt;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::oneshot;
+use tracing::{error, info};
+
+/// Starts the listener accepting the connections of the Kafka clients (producers and consumers)
+/// speaking the Kafka wire protocol.
+pub async fn start(config: KafkaConfig, system: SharedSystem) -> SocketAddr {
+    info!("Initializing Kafka server...");
+    let (tx, rx) = oneshot::channel();
+    tokio::spawn(async move {
+        let listener = TcpListener::bind(&config.address)
+            .await
+            .unwrap_or_else(|error| {
+                panic!(
+                    "Unable to start Kafka server on address: {}. {error}",
+                    config.address
+                )
+            });
+        let local_addr = listener
+            .local_addr()
+            .expect("Failed to get local address for Kafka listener");
+        tx.send(local_addr).unwrap_or_else(|_| {
+            panic!("Failed to send the local address {local_addr:?} for Kafka listener")
+        });
+
+        let coordinator = Arc::new(GroupCoordinator::default());
+        let max_request_size = config.max_request_size.as_bytes_u64();
+        loop {
+            let (mut stream, address) = match listener.accept().await {
+                Ok(connection) => connection,
+                Err(error) => {
+                    error!("Unable to accept Kafka socket. {error}");
+                    continue;
+                }
+            };
+
+            info!("Accepted new Kafka connection: {address}");
+            let broker = match get_broker_address(&config.advertised_address, &stream) {
+                Ok(broker) => broker,
+                Err(error) => {
+                    error!("Unable to resolve Kafka broker address for: {address}. {error}");
+                    continue;
+                }
+            };
+            let session = system
+                .read()
+                .await
+                .add_client(&address, Transport::Kafka)
+                .await;
+            let client_id = session.client_id;
+            info!("Created new Kafka session: {session}");
+            let system = system.clone();
+            let coordinator = coordinator.clone();
+            tokio::spawn(async move {
+                if let Err(error) = handle_connection(
+                    &mut stream,
+                    session,
+                    system.clone(),
+                    coordinator,
+                    broker,
+                    max_request_size,
+                )
+                .await
+                {
+                    handle_error(error);
+                }
+                system.read().await.delete_client(client_id).await;
+                if let Err(error) = stream.shutdown().await {
+                    error!(
+                        "Failed to shutdown Kafka stream for client: {client_id}, address: {address}. {error}"
+                    );
+                } else {
+                    info!(
+                        "Successfully closed Kafka stream for client: {client_id}, address: {address}."
+                    );
+                }
+            });
+        }
+    });
+    match rx.await {
+        Ok(addr) => {
+            info!("Kafka server has started on: {addr}");
+            addr
+        }
+        Err(_) => panic!("Failed to get the local address for Kafka listener."),
+    }
+}
+
+/// Returns the address returned to the clients in the metadata, which is either the configured one
+/// (e.g. when running behind NAT), or the local address of the accepted connection.
+fn get_broker_address(
+    advertised_address: &str,
+    stream: &TcpStream,
+) -> Result<BrokerAddress, std::io::Error> {
+    let advertised_address = advertised_address
+        .rsplit_once(':')
+        .and_then(|(host, port)| Some((host, port.parse::<u16>().ok()?)));
+    if let Some((host, port)) = advertised_address {
+        return Ok(BrokerAddress {
+            host: host.to_owned(),
+            port: port as i32,
+        });
+    }
+
+    let local_addr = stream.local_addr()?;
+    Ok(BrokerAddress {
+        host: local_addr.ip().to_string(),
+        port: local_addr.port() as i32,
+    })
+}
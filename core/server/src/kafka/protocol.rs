@@ -0,0 +1,143 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::kafka::codec::KafkaReader;
+use iggy_common::IggyError;
+
+pub const PRODUCE: i16 = 0;
+pub const FETCH: i16 = 1;
+pub const LIST_OFFSETS: i16 = 2;
+pub const METADATA: i16 = 3;
+pub const OFFSET_COMMIT: i16 = 8;
+pub const OFFSET_FETCH: i16 = 9;
+pub const FIND_COORDINATOR: i16 = 10;
+pub const JOIN_GROUP: i16 = 11;
+pub const HEARTBEAT: i16 = 12;
+pub const LEAVE_GROUP: i16 = 13;
+pub const SYNC_GROUP: i16 = 14;
+pub const SASL_HANDSHAKE: i16 = 17;
+pub const API_VERSIONS: i16 = 18;
+pub const INIT_PRODUCER_ID: i16 = 22;
+pub const SASL_AUTHENTICATE: i16 = 36;
+
+/// The supported APIs with their min and max versions, which are advertised in the ApiVersions response.
+/// Only the versions preceding the flexible ones (with the compact types and the tagged fields) are supported.
+pub const SUPPORTED_APIS: [(i16, i16, i16); 15] = [
+    (PRODUCE, 3, 8),
+    (FETCH, 4, 11),
+    (LIST_OFFSETS, 1, 5),
+    (METADATA, 0, 8),
+    (OFFSET_COMMIT, 2, 7),
+    (OFFSET_FETCH, 1, 5),
+    (FIND_COORDINATOR, 0, 2),
+    (JOIN_GROUP, 0, 5),
+    (HEARTBEAT, 0, 3),
+    (LEAVE_GROUP, 0, 2),
+    (SYNC_GROUP, 0, 3),
+    (SASL_HANDSHAKE, 1, 1),
+    (API_VERSIONS, 0, 2),
+    (INIT_PRODUCER_ID, 0, 1),
+    (SASL_AUTHENTICATE, 0, 1),
+];
+
+pub const NONE: i16 = 0;
+pub const UNKNOWN_SERVER_ERROR: i16 = -1;
+pub const OFFSET_OUT_OF_RANGE: i16 = 1;
+pub const CORRUPT_MESSAGE: i16 = 2;
+pub const UNKNOWN_TOPIC_OR_PARTITION: i16 = 3;
+pub const MESSAGE_TOO_LARGE: i16 = 10;
+pub const COORDINATOR_NOT_AVAILABLE: i16 = 15;
+pub const ILLEGAL_GENERATION: i16 = 22;
+pub const INCONSISTENT_GROUP_PROTOCOL: i16 = 23;
+pub const UNKNOWN_MEMBER_ID: i16 = 25;
+pub const INVALID_SESSION_TIMEOUT: i16 = 26;
+pub const REBALANCE_IN_PROGRESS: i16 = 27;
+pub const TOPIC_AUTHORIZATION_FAILED: i16 = 29;
+pub const GROUP_AUTHORIZATION_FAILED: i16 = 30;
+pub const UNSUPPORTED_SASL_MECHANISM: i16 = 33;
+pub const ILLEGAL_SASL_STATE: i16 = 34;
+pub const UNSUPPORTED_VERSION: i16 = 35;
+pub const INVALID_REQUEST: i16 = 42;
+pub const OUT_OF_ORDER_SEQUENCE_NUMBER: i16 = 45;
+pub const SASL_AUTHENTICATION_FAILED: i16 = 58;
+pub const UNSUPPORTED_COMPRESSION_TYPE: i16 = 76;
+pub const INVALID_RECORD: i16 = 87;
+
+/// The ID of the single broker (this server) returned in the metadata responses.
+pub const NODE_ID: i32 = 0;
+pub const CLUSTER_ID: &str = "iggy";
+pub const THROTTLE_TIME_MS: i32 = 0;
+
+/// Returns the max supported version of the API, or `None` if it's not supported at all.
+pub fn max_version(api_key: i16) -> Option<i16> {
+    SUPPORTED_APIS
+        .iter()
+        .find(|(key, _, _)| *key == api_key)
+        .map(|(_, _, max_version)| *max_version)
+}
+
+pub fn is_version_supported(api_key: i16, api_version: i16) -> bool {
+    SUPPORTED_APIS
+        .iter()
+        .any(|(key, min_version, max_version)| {
+            *key == api_key && (*min_version..=*max_version).contains(&api_version)
+        })
+}
+
+/// The header of every request, the client ID is read in all the header versions,
+/// while the tagged fields of the flexible header are never read, as the flexible versions aren't supported.
+#[derive(Debug)]
+pub struct RequestHeader {
+    pub api_key: i16,
+    pub api_version: i16,
+    pub correlation_id: i32,
+    pub client_id: Option<String>,
+}
+
+impl RequestHeader {
+    pub fn read(reader: &mut KafkaReader) -> Result<Self, IggyError> {
+        Ok(Self {
+            api_key: reader.read_i16()?,
+            api_version: reader.read_i16()?,
+            correlation_id: reader.read_i32()?,
+            client_id: reader.read_nullable_string()?,
+        })
+    }
+}
+
+/// Maps the Iggy error onto the closest Kafka error code.
+pub fn map_error(error: &IggyError) -> i16 {
+    match error {
+        IggyError::StreamIdNotFound(_)
+        | IggyError::StreamNameNotFound(_)
+        | IggyError::TopicIdNotFound(_, _)
+        | IggyError::TopicNameNotFound(_, _)
+        | IggyError::PartitionNotFound(_, _, _)
+        | IggyError::NoPartitions(_, _)
+        | IggyError::ResourceNotFound(_) => UNKNOWN_TOPIC_OR_PARTITION,
+        IggyError::Unauthorized => TOPIC_AUTHORIZATION_FAILED,
+        IggyError::Unauthenticated | IggyError::InvalidCredentials => SASL_AUTHENTICATION_FAILED,
+        IggyError::ProducerSequenceOutOfOrder(_, _, _) => OUT_OF_ORDER_SEQUENCE_NUMBER,
+        IggyError::InvalidMessagePayloadLength
+        | IggyError::TooBigMessagePayload
+        | IggyError::TooBigUserHeaders
+        | IggyError::InvalidHeaderKey
+        | IggyError::InvalidHeaderValue => INVALID_RECORD,
+        _ => UNKNOWN_SERVER_ERROR,
+    }
+}
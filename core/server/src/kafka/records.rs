@@ -0,0 +1,567 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::kafka::codec::{KafkaBufMut, KafkaReader};
+use crate::kafka::protocol::{
+    CORRUPT_MESSAGE, INVALID_REQUEST, MESSAGE_TOO_LARGE, UNSUPPORTED_COMPRESSION_TYPE,
+};
+use bytes::{BufMut, Bytes, BytesMut};
+use crc::{CRC_32_ISCSI, Crc};
+use flate2::read::MultiGzDecoder;
+use iggy_common::{HeaderKey, HeaderValue, IggyError, IggyMessage};
+use std::collections::HashMap;
+use std::io::Read;
+use thiserror::Error;
+
+/// The user header storing the key of the Kafka record, as the Iggy message has no dedicated key field.
+pub const KAFKA_KEY_HEADER: &str = "kafka_key";
+
+const MAGIC: i8 = 2;
+const COMPRESSION_MASK: i16 = 0x07;
+const NO_COMPRESSION: i16 = 0;
+const GZIP_COMPRESSION: i16 = 1;
+const SNAPPY_COMPRESSION: i16 = 2;
+const LZ4_COMPRESSION: i16 = 3;
+const ZSTD_COMPRESSION: i16 = 4;
+/// The Java client frames the snappy blocks the way the xerial library does, other clients send a single raw block.
+const XERIAL_SNAPPY_MAGIC: &[u8] = b"\x82SNAPPY\x00";
+/// The magic followed by the version and the compatible version.
+const XERIAL_SNAPPY_HEADER_SIZE: usize = 16;
+const TRANSACTIONAL_FLAG: i16 = 0x10;
+const CONTROL_FLAG: i16 = 0x20;
+/// The size of the batch fields following the batch length (excluding the records), which are covered by it.
+const BATCH_HEADER_SIZE: usize = 49;
+/// The position of the attributes (the first field covered by the CRC) relative to the end of the batch length.
+const CRC_START: usize = 9;
+const NO_PRODUCER_ID: i64 = -1;
+const NO_PRODUCER_EPOCH: i16 = -1;
+const NO_SEQUENCE: i32 = -1;
+const CASTAGNOLI: Crc<u32> = Crc::<u32>::new(&CRC_32_ISCSI);
+
+#[derive(Debug, Error, PartialEq)]
+pub enum RecordsError {
+    #[error("Corrupted record batch")]
+    Corrupted,
+    #[error("Unsupported compression type: {0}")]
+    UnsupportedCompression(i16),
+    #[error("Decompressed records exceed the max size: {0} bytes")]
+    TooLarge(usize),
+    #[error("Transactional and control record batches are not supported")]
+    Transactional,
+}
+
+impl RecordsError {
+    pub fn error_code(&self) -> i16 {
+        match self {
+            RecordsError::Corrupted => CORRUPT_MESSAGE,
+            RecordsError::UnsupportedCompression(_) => UNSUPPORTED_COMPRESSION_TYPE,
+            RecordsError::TooLarge(_) => MESSAGE_TOO_LARGE,
+            RecordsError::Transactional => INVALID_REQUEST,
+        }
+    }
+}
+
+impl From<IggyError> for RecordsError {
+    fn from(_: IggyError) -> Self {
+        RecordsError::Corrupted
+    }
+}
+
+/// The record batch (message format v2) sent by the producer.
+#[derive(Debug, PartialEq)]
+pub struct RecordBatch {
+    pub producer_id: i64,
+    pub producer_epoch: i16,
+    pub base_sequence: i32,
+    pub records: Vec<Record>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Record {
+    pub timestamp: i64,
+    pub key: Option<Bytes>,
+    pub value: Option<Bytes>,
+    pub headers: Vec<(String, Option<Bytes>)>,
+}
+
+impl RecordBatch {
+    /// Decodes all the record batches stored one after another in the records field of the produce request.
+    /// The records of the compressed batch are decompressed up to the given size, as only the compressed
+    /// size is limited by the max request size.
+    pub fn decode_all(
+        bytes: Bytes,
+        max_records_size: usize,
+    ) -> Result<Vec<RecordBatch>, RecordsError> {
+        let mut reader = KafkaReader::new(bytes);
+        let mut batches = Vec::new();
+        while reader.remaining() > 0 {
+            batches.push(Self::decode(&mut reader, max_records_size)?);
+        }
+        Ok(batches)
+    }
+
+    fn decode(
+        reader: &mut KafkaReader,
+        max_records_size: usize,
+    ) -> Result<RecordBatch, RecordsError> {
+        let _base_offset = reader.read_i64()?;
+        let batch_length = reader.read_i32()?;
+        if batch_length < BATCH_HEADER_SIZE as i32 {
+            return Err(RecordsError::Corrupted);
+        }
+
+        let mut batch = KafkaReader::new(reader.read_raw(batch_length as usize)?);
+        let _partition_leader_epoch = batch.read_i32()?;
+        if batch.read_i8()? != MAGIC {
+            return Err(RecordsError::Corrupted);
+        }
+
+        let crc = batch.read_u32()?;
+        let checksummed = batch.read_raw(batch.remaining())?;
+        if CASTAGNOLI.checksum(&checksummed) != crc {
+            return Err(RecordsError::Corrupted);
+        }
+
+        let mut batch = KafkaReader::new(checksummed);
+        let attributes = batch.read_i16()?;
+        let compression = attributes & COMPRESSION_MASK;
+        if compression > ZSTD_COMPRESSION {
+            return Err(RecordsError::UnsupportedCompression(compression));
+        }
+        if attributes & (TRANSACTIONAL_FLAG | CONTROL_FLAG) != 0 {
+            return Err(RecordsError::Transactional);
+        }
+
+        let _last_offset_delta = batch.read_i32()?;
+        let base_timestamp = batch.read_i64()?;
+        let _max_timestamp = batch.read_i64()?;
+        let producer_id = batch.read_i64()?;
+        let producer_epoch = batch.read_i16()?;
+        let base_sequence = batch.read_i32()?;
+        if compression != NO_COMPRESSION {
+            // Only the records following their count are compressed.
+            let records_count = batch.read_i32()?;
+            let compressed = batch.read_raw(batch.remaining())?;
+            let records = decompress(compression, &compressed, max_records_size)?;
+            let mut decompressed = BytesMut::with_capacity(4 + records.len());
+            decompressed.put_i32(records_count);
+            decompressed.put_slice(&records);
+            batch = KafkaReader::new(decompressed.freeze());
+        }
+        let records = batch.read_array(|reader| {
+            Record::decode(reader, base_timestamp).map_err(|_| IggyError::InvalidCommand)
+        })?;
+        Ok(RecordBatch {
+            producer_id,
+            producer_epoch,
+            base_sequence,
+            records,
+        })
+    }
+}
+
+fn decompress(compression: i16, bytes: &[u8], max_size: usize) -> Result<Vec<u8>, RecordsError> {
+    match compression {
+        GZIP_COMPRESSION => read_to_end(MultiGzDecoder::new(bytes), max_size),
+        SNAPPY_COMPRESSION => decompress_snappy(bytes, max_size),
+        LZ4_COMPRESSION => read_to_end(lz4_flex::frame::FrameDecoder::new(bytes), max_size),
+        ZSTD_COMPRESSION => read_to_end(
+            zstd::stream::read::Decoder::new(bytes).map_err(|_| RecordsError::Corrupted)?,
+            max_size,
+        ),
+        _ => Err(RecordsError::UnsupportedCompression(compression)),
+    }
+}
+
+fn read_to_end(reader: impl Read, max_size: usize) -> Result<Vec<u8>, RecordsError> {
+    let mut decompressed = Vec::new();
+    reader
+        .take(max_size as u64 + 1)
+        .read_to_end(&mut decompressed)
+        .map_err(|_| RecordsError::Corrupted)?;
+    if decompressed.len() > max_size {
+        return Err(RecordsError::TooLarge(max_size));
+    }
+    Ok(decompressed)
+}
+
+fn decompress_snappy(bytes: &[u8], max_size: usize) -> Result<Vec<u8>, RecordsError> {
+    let mut decompressed = Vec::new();
+    if !bytes.starts_with(XERIAL_SNAPPY_MAGIC) {
+        decompress_snappy_block(bytes, max_size, &mut decompressed)?;
+        return Ok(decompressed);
+    }
+
+    let mut blocks = bytes
+        .get(XERIAL_SNAPPY_HEADER_SIZE..)
+        .ok_or(RecordsError::Corrupted)?;
+    while !blocks.is_empty() {
+        let (length, rest) = blocks.split_at_checked(4).ok_or(RecordsError::Corrupted)?;
+        let length = u32::from_be_bytes(length.try_into().unwrap()) as usize;
+        let (block, rest) = rest
+            .split_at_checked(length)
+            .ok_or(RecordsError::Corrupted)?;
+        decompress_snappy_block(block, max_size, &mut decompressed)?;
+        blocks = rest;
+    }
+    Ok(decompressed)
+}
+
+fn decompress_snappy_block(
+    block: &[u8],
+    max_size: usize,
+    decompressed: &mut Vec<u8>,
+) -> Result<(), RecordsError> {
+    let length = snap::raw::decompress_len(block).map_err(|_| RecordsError::Corrupted)?;
+    let start = decompressed.len();
+    if start + length > max_size {
+        return Err(RecordsError::TooLarge(max_size));
+    }
+
+    decompressed.resize(start + length, 0);
+    snap::raw::Decoder::new()
+        .decompress(block, &mut decompressed[start..])
+        .map_err(|_| RecordsError::Corrupted)?;
+    Ok(())
+}
+
+impl Record {
+    fn decode(reader: &mut KafkaReader, base_timestamp: i64) -> Result<Record, IggyError> {
+        let length = reader.read_varint()?;
+        if length < 0 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let mut record = KafkaReader::new(reader.read_raw(length as usize)?);
+        let _attributes = record.read_i8()?;
+        let timestamp_delta = record.read_varlong()?;
+        let _offset_delta = record.read_varint()?;
+        let key = record.read_varint_bytes()?;
+        let value = record.read_varint_bytes()?;
+        let headers_count = record.read_varint()?;
+        if headers_count < 0 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let mut headers = Vec::with_capacity((headers_count as usize).min(record.remaining()));
+        for _ in 0..headers_count {
+            let name = record
+                .read_varint_bytes()?
+                .ok_or(IggyError::InvalidCommand)?;
+            let name = String::from_utf8(name.to_vec()).map_err(|_| IggyError::InvalidUtf8)?;
+            let value = record.read_varint_bytes()?;
+            headers.push((name, value));
+        }
+
+        Ok(Record {
+            timestamp: base_timestamp + timestamp_delta,
+            key,
+            value,
+            headers,
+        })
+    }
+
+    /// Converts the record into the Iggy message, the key and headers are stored as the raw user headers,
+    /// while the timestamp (in milliseconds) is stored as the origin timestamp.
+    pub fn into_message(self) -> Result<IggyMessage, IggyError> {
+        let payload = self.value.ok_or(IggyError::InvalidMessagePayloadLength)?;
+        let mut user_headers = HashMap::with_capacity(self.headers.len() + 1);
+        if let Some(key) = self.key {
+            user_headers.insert(
+                HeaderKey::new(KAFKA_KEY_HEADER)?,
+                HeaderValue::from_raw(&key)?,
+            );
+        }
+        for (name, value) in self.headers {
+            let value = value.ok_or(IggyError::InvalidHeaderValue)?;
+            user_headers.insert(HeaderKey::new(&name)?, HeaderValue::from_raw(&value)?);
+        }
+
+        let mut message = IggyMessage::builder()
+            .payload(payload)
+            .maybe_user_headers((!user_headers.is_empty()).then_some(user_headers))
+            .build()?;
+        if self.timestamp >= 0 {
+            message.header.origin_timestamp = self.timestamp as u64 * 1000;
+        }
+        Ok(message)
+    }
+}
+
+/// Encodes the polled messages as a single record batch, the offsets of the messages are preserved
+/// as the base offset and the offset deltas, so the gaps between them (if any) are allowed.
+pub fn encode_record_batch(messages: &[IggyMessage], bytes: &mut BytesMut) {
+    let Some(first_message) = messages.first() else {
+        return;
+    };
+
+    let base_offset = first_message.header.offset;
+    let base_timestamp = get_timestamp(first_message);
+    let mut max_timestamp = base_timestamp;
+    let mut records = BytesMut::new();
+    let mut record = BytesMut::new();
+    for message in messages {
+        let timestamp = get_timestamp(message);
+        max_timestamp = max_timestamp.max(timestamp);
+        let (key, headers) = split_user_headers(message);
+
+        record.clear();
+        record.put_i8(0);
+        record.put_varlong(timestamp - base_timestamp);
+        record.put_varint((message.header.offset - base_offset) as i32);
+        record.put_varint_bytes(key.as_deref());
+        record.put_varint_bytes(Some(&message.payload));
+        record.put_varint(headers.len() as i32);
+        for (name, value) in &headers {
+            record.put_varint_bytes(Some(name.as_bytes()));
+            record.put_varint_bytes(Some(value));
+        }
+        records.put_varint(record.len() as i32);
+        records.put_slice(&record);
+    }
+
+    let last_offset_delta = messages
+        .last()
+        .map(|message| message.header.offset - base_offset)
+        .unwrap_or_default();
+    let batch_start = bytes.len();
+    bytes.put_i64(base_offset as i64);
+    bytes.put_i32((BATCH_HEADER_SIZE + records.len()) as i32);
+    bytes.put_i32(0);
+    bytes.put_i8(MAGIC);
+    let crc_position = bytes.len();
+    bytes.put_u32(0);
+    bytes.put_i16(0);
+    bytes.put_i32(last_offset_delta as i32);
+    bytes.put_i64(base_timestamp);
+    bytes.put_i64(max_timestamp);
+    bytes.put_i64(NO_PRODUCER_ID);
+    bytes.put_i16(NO_PRODUCER_EPOCH);
+    bytes.put_i32(NO_SEQUENCE);
+    bytes.put_array_length(messages.len());
+    bytes.put_slice(&records);
+
+    let crc = CASTAGNOLI.checksum(&bytes[batch_start + 12 + CRC_START..]);
+    bytes[crc_position..crc_position + 4].copy_from_slice(&crc.to_be_bytes());
+}
+
+/// Returns the timestamp of the message in milliseconds, preferring the one set by the producer.
+fn get_timestamp(message: &IggyMessage) -> i64 {
+    let timestamp = match message.header.origin_timestamp {
+        0 => message.header.timestamp,
+        origin_timestamp => origin_timestamp,
+    };
+    (timestamp / 1000) as i64
+}
+
+fn split_user_headers(message: &IggyMessage) -> (Option<Bytes>, Vec<(String, Bytes)>) {
+    let Ok(Some(user_headers)) = message.user_headers_map() else {
+        return (None, Vec::new());
+    };
+
+    let mut key = None;
+    let mut headers = Vec::with_capacity(user_headers.len());
+    for (name, value) in user_headers {
+        if name.as_str() == KAFKA_KEY_HEADER {
+            key = Some(value.value);
+        } else {
+            headers.push((name.as_str().to_owned(), value.value));
+        }
+    }
+    (key, headers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    const MAX_RECORDS_SIZE: usize = 1024;
+
+    fn create_message(offset: u64, key: Option<&str>, payload: &str) -> IggyMessage {
+        let record = Record {
+            timestamp: 1_000 + offset as i64,
+            key: key.map(|key| Bytes::copy_from_slice(key.as_bytes())),
+            value: Some(Bytes::copy_from_slice(payload.as_bytes())),
+            headers: vec![("source".to_owned(), Some(Bytes::from_static(b"test")))],
+        };
+        let mut message = record.into_message().unwrap();
+        message.header.offset = offset;
+        message
+    }
+
+    #[test]
+    fn encoded_record_batch_should_be_decoded() {
+        let messages = vec![
+            create_message(10, Some("key"), "message 1"),
+            create_message(12, None, "message 2"),
+        ];
+        let mut bytes = BytesMut::new();
+        encode_record_batch(&messages, &mut bytes);
+
+        let batches = RecordBatch::decode_all(bytes.freeze(), MAX_RECORDS_SIZE).unwrap();
+        assert_eq!(batches.len(), 1);
+        let batch = &batches[0];
+        assert_eq!(batch.producer_id, NO_PRODUCER_ID);
+        assert_eq!(batch.records.len(), 2);
+        assert_eq!(batch.records[0].timestamp, 1_010);
+        assert_eq!(batch.records[0].key.as_deref(), Some(b"key".as_slice()));
+        assert_eq!(
+            batch.records[0].value.as_deref(),
+            Some(b"message 1".as_slice())
+        );
+        assert_eq!(
+            batch.records[0].headers,
+            vec![("source".to_owned(), Some(Bytes::from_static(b"test")))]
+        );
+        assert_eq!(batch.records[1].timestamp, 1_012);
+        assert_eq!(batch.records[1].key, None);
+    }
+
+    #[test]
+    fn record_batch_with_invalid_crc_should_be_rejected() {
+        let messages = vec![create_message(0, None, "message")];
+        let mut bytes = BytesMut::new();
+        encode_record_batch(&messages, &mut bytes);
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0xff;
+
+        assert_eq!(
+            RecordBatch::decode_all(bytes.freeze(), MAX_RECORDS_SIZE),
+            Err(RecordsError::Corrupted)
+        );
+    }
+
+    /// Encodes the messages as the record batch whose records (following their count) are compressed.
+    fn encode_compressed_record_batch(
+        messages: &[IggyMessage],
+        compression: i16,
+        compress: impl Fn(&[u8]) -> Vec<u8>,
+    ) -> Bytes {
+        let mut bytes = BytesMut::new();
+        encode_record_batch(messages, &mut bytes);
+        let records_position = 12 + BATCH_HEADER_SIZE;
+        let records = compress(&bytes[records_position..]);
+        bytes.truncate(records_position);
+        bytes.put_slice(&records);
+
+        let batch_length = (bytes.len() - 12) as i32;
+        bytes[8..12].copy_from_slice(&batch_length.to_be_bytes());
+        bytes[12 + CRC_START..12 + CRC_START + 2].copy_from_slice(&compression.to_be_bytes());
+        let crc = CASTAGNOLI.checksum(&bytes[12 + CRC_START..]);
+        bytes[12 + CRC_START - 4..12 + CRC_START].copy_from_slice(&crc.to_be_bytes());
+        bytes.freeze()
+    }
+
+    fn compress_gzip(bytes: &[u8]) -> Vec<u8> {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(bytes).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn compress_snappy(bytes: &[u8]) -> Vec<u8> {
+        snap::raw::Encoder::new().compress_vec(bytes).unwrap()
+    }
+
+    fn compress_xerial_snappy(bytes: &[u8]) -> Vec<u8> {
+        let mut compressed = XERIAL_SNAPPY_MAGIC.to_vec();
+        compressed.extend_from_slice(&1i32.to_be_bytes());
+        compressed.extend_from_slice(&1i32.to_be_bytes());
+        for chunk in bytes.chunks(16) {
+            let block = compress_snappy(chunk);
+            compressed.extend_from_slice(&(block.len() as i32).to_be_bytes());
+            compressed.extend_from_slice(&block);
+        }
+        compressed
+    }
+
+    fn compress_lz4(bytes: &[u8]) -> Vec<u8> {
+        let mut encoder = lz4_flex::frame::FrameEncoder::new(Vec::new());
+        encoder.write_all(bytes).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn compress_zstd(bytes: &[u8]) -> Vec<u8> {
+        zstd::encode_all(bytes, 0).unwrap()
+    }
+
+    #[test]
+    fn compressed_record_batch_should_be_decoded() {
+        let messages = vec![
+            create_message(10, Some("key"), "message 1"),
+            create_message(12, None, "message 2"),
+        ];
+        let mut bytes = BytesMut::new();
+        encode_record_batch(&messages, &mut bytes);
+        let expected = RecordBatch::decode_all(bytes.freeze(), MAX_RECORDS_SIZE).unwrap();
+
+        let codecs: [(i16, fn(&[u8]) -> Vec<u8>); 5] = [
+            (GZIP_COMPRESSION, compress_gzip),
+            (SNAPPY_COMPRESSION, compress_snappy),
+            (SNAPPY_COMPRESSION, compress_xerial_snappy),
+            (LZ4_COMPRESSION, compress_lz4),
+            (ZSTD_COMPRESSION, compress_zstd),
+        ];
+        for (compression, compress) in codecs {
+            let bytes = encode_compressed_record_batch(&messages, compression, compress);
+            assert_eq!(
+                RecordBatch::decode_all(bytes, MAX_RECORDS_SIZE).unwrap(),
+                expected,
+                "compression: {compression}"
+            );
+        }
+    }
+
+    #[test]
+    fn compressed_record_batch_should_be_rejected_given_records_exceeding_max_size() {
+        let messages = vec![create_message(0, None, &"a".repeat(MAX_RECORDS_SIZE))];
+        let codecs: [(i16, fn(&[u8]) -> Vec<u8>); 4] = [
+            (GZIP_COMPRESSION, compress_gzip),
+            (SNAPPY_COMPRESSION, compress_xerial_snappy),
+            (LZ4_COMPRESSION, compress_lz4),
+            (ZSTD_COMPRESSION, compress_zstd),
+        ];
+        for (compression, compress) in codecs {
+            let bytes = encode_compressed_record_batch(&messages, compression, compress);
+            assert_eq!(
+                RecordBatch::decode_all(bytes, MAX_RECORDS_SIZE),
+                Err(RecordsError::TooLarge(MAX_RECORDS_SIZE)),
+                "compression: {compression}"
+            );
+        }
+    }
+
+    #[test]
+    fn record_batch_with_unknown_compression_should_be_rejected() {
+        let messages = vec![create_message(0, None, "message")];
+        let bytes = encode_compressed_record_batch(&messages, 5, <[u8]>::to_vec);
+        assert_eq!(
+            RecordBatch::decode_all(bytes, MAX_RECORDS_SIZE),
+            Err(RecordsError::UnsupportedCompression(5))
+        );
+    }
+
+    #[test]
+    fn record_without_value_should_not_be_converted_into_message() {
+        let record = Record {
+            key: Some(Bytes::from_static(b"key")),
+            ..Default::default()
+        };
+        assert!(record.into_message().is_err());
+    }
+}
@@ -0,0 +1,57 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::streaming::session::Session;
+use crate::streaming::systems::system::System;
+use crate::streaming::topics::topic::Topic;
+use iggy_common::{Identifier, IggyError};
+
+/// Returns the name of the Kafka topic mapped onto the Iggy topic, which is the name of the stream
+/// joined with the name of the topic by a dot, e.g. `orders.created`.
+pub fn to_kafka_topic_name(stream_name: &str, topic_name: &str) -> String {
+    format!("{stream_name}.{topic_name}")
+}
+
+/// Finds the Iggy topic mapped onto the Kafka topic. As the names of both the streams and the topics
+/// might contain the dots as well, the name is split at each of the dots until the topic is found.
+pub fn resolve_topic<'a>(
+    system: &'a System,
+    session: &Session,
+    kafka_topic_name: &str,
+) -> Result<Option<&'a Topic>, IggyError> {
+    for (position, _) in kafka_topic_name.match_indices('.') {
+        let stream_name = &kafka_topic_name[..position];
+        let topic_name = &kafka_topic_name[position + 1..];
+        if stream_name.is_empty() || topic_name.is_empty() {
+            continue;
+        }
+
+        let (Ok(stream_id), Ok(topic_id)) = (
+            Identifier::named(stream_name),
+            Identifier::named(topic_name),
+        ) else {
+            continue;
+        };
+
+        if let Some(topic) = system.try_find_topic(session, &stream_id, &topic_id)? {
+            return Ok(Some(topic));
+        }
+    }
+
+    Ok(None)
+}
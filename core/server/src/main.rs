@@ -23,6 +23,7 @@ use figlet_rs::FIGfont;
 use server::args::Args;
 use server::channels::commands::archive_state::ArchiveStateExecutor;
 use server::channels::commands::clean_personal_access_tokens::CleanPersonalAccessTokensExecutor;
+use server::channels::commands::drain_partitions::DrainPartitionsExecutor;
 use server::channels::commands::maintain_messages::MaintainMessagesExecutor;
 use server::channels::commands::print_sysinfo::SysInfoPrintExecutor;
 use server::channels::commands::save_messages::SaveMessagesExecutor;
@@ -31,10 +32,12 @@ use server::channels::handler::BackgroundServerCommandHandler;
 use server::configs::config_provider;
 use server::configs::server::ServerConfig;
 use server::http::http_server;
+use server::kafka::kafka_server;
 #[cfg(not(feature = "tokio-console"))]
 use server::log::logger::Logging;
 #[cfg(feature = "tokio-console")]
 use server::log::tokio_console::Logging;
+use server::mqtt::mqtt_server;
 use server::quic::quic_server;
 use server::server_error::ServerError;
 use server::streaming::systems::system::{SharedSystem, System};
@@ -110,6 +113,7 @@ async fn main() -> Result<(), ServerError> {
         .install_handler(SaveMessagesExecutor)
         .install_handler(MaintainMessagesExecutor)
         .install_handler(ArchiveStateExecutor)
+        .install_handler(DrainPartitionsExecutor)
         .install_handler(CleanPersonalAccessTokensExecutor)
         .install_handler(SysInfoPrintExecutor)
         .install_handler(VerifyHeartbeatsExecutor);
@@ -140,6 +144,16 @@ async fn main() -> Result<(), ServerError> {
         current_config.tcp.address = tcp_addr.to_string();
     }
 
+    if config.kafka.enabled {
+        let kafka_addr = kafka_server::start(config.kafka, system.clone()).await;
+        current_config.kafka.address = kafka_addr.to_string();
+    }
+
+    if config.mqtt.enabled {
+        let mqtt_addr = mqtt_server::start(config.mqtt, system.clone()).await;
+        current_config.mqtt.address = mqtt_addr.to_string();
+    }
+
     let runtime_path = current_config.system.get_runtime_path();
     let current_config_path = format!("{runtime_path}/current_config.toml");
     let current_config_content =
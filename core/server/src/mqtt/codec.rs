@@ -0,0 +1,190 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use iggy_common::IggyError;
+
+/// The max value of the variable byte integer, encoded using at most 4 bytes.
+pub const MAX_VARIABLE_BYTE_INTEGER: u32 = 268_435_455;
+
+/// Reads the data types of the MQTT protocol (big-endian integers, variable byte integers,
+/// UTF-8 strings and binary data prefixed with the two-byte length) from the packet.
+#[derive(Debug)]
+pub struct MqttReader {
+    bytes: Bytes,
+}
+
+impl MqttReader {
+    pub fn new(bytes: Bytes) -> Self {
+        Self { bytes }
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.bytes.remaining()
+    }
+
+    fn ensure_remaining(&self, size: usize) -> Result<(), IggyError> {
+        if self.bytes.remaining() < size {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        Ok(())
+    }
+
+    pub fn read_u8(&mut self) -> Result<u8, IggyError> {
+        self.ensure_remaining(1)?;
+        Ok(self.bytes.get_u8())
+    }
+
+    pub fn read_u16(&mut self) -> Result<u16, IggyError> {
+        self.ensure_remaining(2)?;
+        Ok(self.bytes.get_u16())
+    }
+
+    pub fn read_u32(&mut self) -> Result<u32, IggyError> {
+        self.ensure_remaining(4)?;
+        Ok(self.bytes.get_u32())
+    }
+
+    pub fn read_variable_byte_integer(&mut self) -> Result<u32, IggyError> {
+        let mut value = 0u32;
+        for index in 0..4 {
+            let byte = self.read_u8()?;
+            value |= ((byte & 0x7f) as u32) << (index * 7);
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+
+        Err(IggyError::InvalidNumberEncoding)
+    }
+
+    pub fn read_string(&mut self) -> Result<String, IggyError> {
+        let bytes = self.read_binary()?;
+        let value = String::from_utf8(bytes.to_vec()).map_err(|_| IggyError::InvalidUtf8)?;
+        // The null character is never allowed in the UTF-8 encoded strings.
+        if value.contains('\0') {
+            return Err(IggyError::InvalidUtf8);
+        }
+
+        Ok(value)
+    }
+
+    pub fn read_binary(&mut self) -> Result<Bytes, IggyError> {
+        let length = self.read_u16()?;
+        self.read_raw(length as usize)
+    }
+
+    pub fn read_raw(&mut self, size: usize) -> Result<Bytes, IggyError> {
+        self.ensure_remaining(size)?;
+        Ok(self.bytes.split_to(size))
+    }
+
+    /// Reads all the remaining bytes, e.g. the payload of the PUBLISH packet.
+    pub fn read_remaining(&mut self) -> Bytes {
+        self.bytes.split_off(0)
+    }
+}
+
+/// Writes the data types of the MQTT protocol to the packet.
+pub trait MqttBufMut: BufMut {
+    fn put_variable_byte_integer(&mut self, mut value: u32) {
+        while value >= 0x80 {
+            self.put_u8((value as u8 & 0x7f) | 0x80);
+            value >>= 7;
+        }
+        self.put_u8(value as u8);
+    }
+
+    fn put_mqtt_string(&mut self, value: &str) {
+        self.put_mqtt_binary(value.as_bytes());
+    }
+
+    fn put_mqtt_binary(&mut self, value: &[u8]) {
+        self.put_u16(value.len() as u16);
+        self.put_slice(value);
+    }
+}
+
+impl MqttBufMut for BytesMut {}
+
+/// Returns the number of bytes used to encode the value as the variable byte integer.
+pub fn variable_byte_integer_size(value: u32) -> usize {
+    match value {
+        0..=127 => 1,
+        128..=16_383 => 2,
+        16_384..=2_097_151 => 3,
+        _ => 4,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn data_types_should_be_written_and_read() {
+        let mut bytes = BytesMut::new();
+        bytes.put_u8(1);
+        bytes.put_u16(2);
+        bytes.put_u32(3);
+        bytes.put_mqtt_string("devices/1");
+        bytes.put_mqtt_binary(b"data");
+        bytes.put_slice(b"payload");
+
+        let mut reader = MqttReader::new(bytes.freeze());
+        assert_eq!(reader.read_u8().unwrap(), 1);
+        assert_eq!(reader.read_u16().unwrap(), 2);
+        assert_eq!(reader.read_u32().unwrap(), 3);
+        assert_eq!(reader.read_string().unwrap(), "devices/1");
+        assert_eq!(reader.read_binary().unwrap().as_ref(), b"data");
+        assert_eq!(reader.read_remaining().as_ref(), b"payload");
+        assert_eq!(reader.remaining(), 0);
+    }
+
+    #[test]
+    fn variable_byte_integers_should_be_written_and_read() {
+        let values = [
+            0,
+            127,
+            128,
+            16_383,
+            16_384,
+            2_097_151,
+            2_097_152,
+            MAX_VARIABLE_BYTE_INTEGER,
+        ];
+        for value in values {
+            let mut bytes = BytesMut::new();
+            bytes.put_variable_byte_integer(value);
+            assert_eq!(bytes.len(), variable_byte_integer_size(value));
+            let mut reader = MqttReader::new(bytes.freeze());
+            assert_eq!(reader.read_variable_byte_integer().unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn invalid_input_should_fail() {
+        let mut reader = MqttReader::new(Bytes::from_static(&[0, 5, b'a']));
+        assert!(reader.read_string().is_err());
+        let mut reader = MqttReader::new(Bytes::from_static(&[0, 1, 0]));
+        assert!(reader.read_string().is_err());
+        let mut reader = MqttReader::new(Bytes::from_static(&[0xff, 0xff, 0xff, 0xff, 0x01]));
+        assert!(reader.read_variable_byte_integer().is_err());
+    }
+}
@@ -0,0 +1,584 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::configs::mqtt::MqttTopicMapping;
+use crate::mqtt::COMPONENT;
+use crate::mqtt::codec::{MqttReader, variable_byte_integer_size};
+use crate::mqtt::messages::append_message;
+use crate::mqtt::packets::{
+    Connect, FixedHeader, Properties, PropertyValue, Publish, Subscribe, Unsubscribe, Will,
+    encode_connack, encode_disconnect, encode_pingresp, encode_puback, encode_suback,
+    encode_unsuback, read_disconnect_reason_code, read_packet_id,
+};
+use crate::mqtt::protocol::{
+    ASSIGNED_CLIENT_IDENTIFIER, AUTHENTICATION_METHOD, BAD_AUTHENTICATION_METHOD,
+    BAD_USER_NAME_OR_PASSWORD, BAD_USERNAME_OR_PASSWORD, CONNECT, CONNECTION_ACCEPTED, DISCONNECT,
+    DISCONNECT_WITH_WILL_MESSAGE, IDENTIFIER_REJECTED, KEEP_ALIVE_TIMEOUT, MALFORMED_PACKET,
+    MAX_SUPPORTED_QOS, MAXIMUM_PACKET_SIZE, MAXIMUM_QOS, NO_SUBSCRIPTION_EXISTED, PACKET_TOO_LARGE,
+    PINGREQ, PROTOCOL_ERROR, PROTOCOL_LEVEL_5, PUBACK, PUBLISH, QOS_EXACTLY_ONCE,
+    QOS_NOT_SUPPORTED, RECEIVE_MAXIMUM, RETAIN_AVAILABLE, RETAIN_NOT_SUPPORTED,
+    SESSION_EXPIRY_INTERVAL, SHARED_SUBSCRIPTION_AVAILABLE, SHARED_SUBSCRIPTIONS_NOT_SUPPORTED,
+    SUBSCRIBE, SUBSCRIPTION_FAILURE, SUBSCRIPTION_IDENTIFIER, SUBSCRIPTION_IDENTIFIER_AVAILABLE,
+    SUBSCRIPTION_IDENTIFIERS_NOT_SUPPORTED, SUCCESS, TOPIC_ALIAS, TOPIC_ALIAS_INVALID,
+    TOPIC_FILTER_INVALID, TOPIC_NAME_INVALID, UNACCEPTABLE_PROTOCOL_VERSION, UNSPECIFIED_ERROR,
+    UNSUBSCRIBE, UNSUPPORTED_PROTOCOL_VERSION, is_protocol_level_supported, map_error,
+};
+use crate::mqtt::subscriptions::{InflightMessages, MqttSubscriptions, Subscriber};
+use crate::mqtt::topics::{SHARED_SUBSCRIPTION_PREFIX, is_valid_topic_filter, is_valid_topic_name};
+use crate::server_error::ConnectionError;
+use crate::streaming::session::Session;
+use crate::streaming::systems::system::SharedSystem;
+use bytes::{Bytes, BytesMut};
+use iggy_common::IggyError;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn};
+
+const OUTGOING_PACKETS_CAPACITY: usize = 1024;
+/// The time given to the client to send the CONNECT packet once the connection is established.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Handles the MQTT connection until it's closed by either side.
+///
+/// The client must send the CONNECT packet with the credentials of the Iggy user first. Afterwards,
+/// the published messages are appended to the mapped Iggy topics, and the messages matched by the subscriptions
+/// are pushed to the client through the connection's outgoing channel. The sessions are never persisted,
+/// thus the subscriptions are removed once the connection is closed. Whenever the connection is closed
+/// without the DISCONNECT packet, the will message (if any) is published on behalf of the client.
+pub(crate) async fn handle_connection(
+    stream: TcpStream,
+    session: Arc<Session>,
+    system: SharedSystem,
+    mappings: Arc<Vec<MqttTopicMapping>>,
+    max_packet_size: u32,
+) -> Result<(), ConnectionError> {
+    let client_id = session.client_id;
+    let (mut reader, writer) = stream.into_split();
+    let (outgoing, receiver) = mpsc::channel(OUTGOING_PACKETS_CAPACITY);
+    let writer = tokio::spawn(write_packets(writer, receiver, client_id));
+    let result = async {
+        let Some(mut connection) = connect(
+            &mut reader,
+            session,
+            system,
+            mappings,
+            max_packet_size,
+            outgoing,
+        )
+        .await?
+        else {
+            return Err(ConnectionError::from(IggyError::Unauthenticated));
+        };
+
+        let result = connection.handle_packets(&mut reader).await;
+        connection.publish_will().await;
+        result
+    }
+    .await;
+    let _ = writer.await;
+    result
+}
+
+async fn write_packets(
+    mut writer: OwnedWriteHalf,
+    mut receiver: mpsc::Receiver<Bytes>,
+    client_id: u32,
+) {
+    while let Some(packet) = receiver.recv().await {
+        if let Err(error) = writer.write_all(&packet).await {
+            debug!("Failed to write MQTT packet for client: {client_id}, error: {error}");
+            return;
+        }
+    }
+
+    if let Err(error) = writer.shutdown().await {
+        debug!("Failed to shutdown MQTT stream for client: {client_id}, error: {error}");
+    }
+}
+
+/// Reads the CONNECT packet and authenticates the client, returns `None` if the connection has been refused.
+async fn connect(
+    reader: &mut OwnedReadHalf,
+    session: Arc<Session>,
+    system: SharedSystem,
+    mappings: Arc<Vec<MqttTopicMapping>>,
+    max_packet_size: u32,
+    outgoing: mpsc::Sender<Bytes>,
+) -> Result<Option<MqttConnection>, ConnectionError> {
+    let Ok(header) = tokio::time::timeout(CONNECT_TIMEOUT, read_fixed_header(reader)).await else {
+        warn!("MQTT client hasn't sent the CONNECT packet in time, session: {session}.");
+        return Err(ConnectionError::from(IggyError::InvalidCommand));
+    };
+    let header = header?;
+    if header.packet_type != CONNECT
+        || !header.has_valid_flags()
+        || get_packet_size(&header) > max_packet_size as usize
+    {
+        warn!("Received invalid MQTT packet instead of CONNECT, session: {session}.");
+        return Err(ConnectionError::from(IggyError::InvalidCommand));
+    }
+
+    let mut packet = read_body(reader, &header).await?;
+    let protocol_level = Connect::read_protocol_level(&mut packet)?;
+    if !is_protocol_level_supported(protocol_level) {
+        warn!("Unsupported MQTT protocol level: {protocol_level}, session: {session}.");
+        return if protocol_level > PROTOCOL_LEVEL_5 {
+            refuse(&outgoing, PROTOCOL_LEVEL_5, UNSUPPORTED_PROTOCOL_VERSION).await
+        } else {
+            refuse(&outgoing, protocol_level, UNACCEPTABLE_PROTOCOL_VERSION).await
+        };
+    }
+
+    let is_v5 = protocol_level == PROTOCOL_LEVEL_5;
+    let connect = Connect::read(&mut packet, protocol_level)?;
+    if connect.properties.contains(AUTHENTICATION_METHOD) {
+        warn!("MQTT enhanced authentication isn't supported, session: {session}.");
+        return refuse(&outgoing, protocol_level, BAD_AUTHENTICATION_METHOD).await;
+    }
+
+    if connect.client_id.is_empty() && !connect.clean_start && !is_v5 {
+        warn!(
+            "MQTT client without identifier requested the persistent session, session: {session}."
+        );
+        return refuse(&outgoing, protocol_level, IDENTIFIER_REJECTED).await;
+    }
+
+    let receive_maximum = connect
+        .properties
+        .get_u16(RECEIVE_MAXIMUM)
+        .unwrap_or(u16::MAX);
+    if receive_maximum == 0 {
+        return refuse(&outgoing, protocol_level, PROTOCOL_ERROR).await;
+    }
+
+    if connect
+        .will
+        .as_ref()
+        .is_some_and(|will| !is_valid_topic_name(&will.topic))
+    {
+        warn!("MQTT client has sent will message with invalid topic, session: {session}.");
+        if is_v5 {
+            return refuse(&outgoing, protocol_level, TOPIC_NAME_INVALID).await;
+        }
+        return Err(ConnectionError::from(IggyError::InvalidCommand));
+    }
+
+    if !authenticate(&connect, &session, &system).await {
+        let code = if is_v5 {
+            BAD_USER_NAME_OR_PASSWORD
+        } else {
+            BAD_USERNAME_OR_PASSWORD
+        };
+        return refuse(&outgoing, protocol_level, code).await;
+    }
+
+    let mut properties = Properties::default();
+    let client_identifier = if connect.client_id.is_empty() {
+        let client_identifier = format!("iggy-{}", session.client_id);
+        properties.push(
+            ASSIGNED_CLIENT_IDENTIFIER,
+            PropertyValue::String(client_identifier.clone()),
+        );
+        client_identifier
+    } else {
+        connect.client_id
+    };
+    properties.push(MAXIMUM_QOS, PropertyValue::Byte(MAX_SUPPORTED_QOS));
+    properties.push(RETAIN_AVAILABLE, PropertyValue::Byte(0));
+    properties.push(SHARED_SUBSCRIPTION_AVAILABLE, PropertyValue::Byte(0));
+    properties.push(SUBSCRIPTION_IDENTIFIER_AVAILABLE, PropertyValue::Byte(0));
+    properties.push(
+        MAXIMUM_PACKET_SIZE,
+        PropertyValue::FourByteInteger(max_packet_size),
+    );
+    // The sessions are never persisted, thus they expire as soon as the connection is closed.
+    if connect
+        .properties
+        .get_u32(SESSION_EXPIRY_INTERVAL)
+        .is_some_and(|interval| interval > 0)
+    {
+        properties.push(SESSION_EXPIRY_INTERVAL, PropertyValue::FourByteInteger(0));
+    }
+    outgoing
+        .send(encode_connack(
+            protocol_level,
+            false,
+            CONNECTION_ACCEPTED,
+            &properties,
+        ))
+        .await
+        .map_err(|_| IggyError::Disconnected)?;
+    info!("MQTT client: {client_identifier} has connected, session: {session}.");
+
+    let subscriber = Arc::new(Subscriber {
+        protocol_level,
+        max_packet_size: connect
+            .properties
+            .get_u32(MAXIMUM_PACKET_SIZE)
+            .map(|size| size as usize)
+            .unwrap_or(usize::MAX),
+        inflight: InflightMessages::new(receive_maximum),
+        outgoing: outgoing.clone(),
+    });
+    // The server disconnects the client after one and a half times the keep alive period without any packets.
+    let keep_alive =
+        (connect.keep_alive > 0).then(|| Duration::from_millis(connect.keep_alive as u64 * 1500));
+    Ok(Some(MqttConnection {
+        subscriptions: MqttSubscriptions::new(
+            session.clone(),
+            system.clone(),
+            mappings.clone(),
+            subscriber,
+        ),
+        session,
+        system,
+        mappings,
+        protocol_level,
+        max_packet_size,
+        keep_alive,
+        outgoing,
+        will: connect.will,
+    }))
+}
+
+async fn refuse(
+    outgoing: &mpsc::Sender<Bytes>,
+    protocol_level: u8,
+    code: u8,
+) -> Result<Option<MqttConnection>, ConnectionError> {
+    let connack = encode_connack(protocol_level, false, code, &Properties::default());
+    let _ = outgoing.send(connack).await;
+    Ok(None)
+}
+
+async fn authenticate(connect: &Connect, session: &Session, system: &SharedSystem) -> bool {
+    let (Some(username), Some(password)) = (&connect.username, &connect.password) else {
+        warn!("MQTT client has connected without credentials, session: {session}.");
+        return false;
+    };
+
+    let Ok(password) = std::str::from_utf8(password) else {
+        warn!("MQTT client has sent invalid password for user: {username}, session: {session}.");
+        return false;
+    };
+
+    let system = system.read().await;
+    match system.login_user(username, password, Some(session)).await {
+        Ok(user) => {
+            info!(
+                "Authenticated user: {} with ID: {} using MQTT, session: {session}.",
+                user.username, user.id
+            );
+            true
+        }
+        Err(error) => {
+            warn!(
+                "Failed to authenticate user: {username} using MQTT, session: {session}, error: {error}"
+            );
+            false
+        }
+    }
+}
+
+/// The state of the connection established by the client which has been authenticated.
+struct MqttConnection {
+    session: Arc<Session>,
+    system: SharedSystem,
+    mappings: Arc<Vec<MqttTopicMapping>>,
+    protocol_level: u8,
+    max_packet_size: u32,
+    keep_alive: Option<Duration>,
+    outgoing: mpsc::Sender<Bytes>,
+    subscriptions: MqttSubscriptions,
+    will: Option<Will>,
+}
+
+impl MqttConnection {
+    fn is_v5(&self) -> bool {
+        self.protocol_level == PROTOCOL_LEVEL_5
+    }
+
+    /// Handles the packets one by one until the client disconnects. Whenever the client violates the protocol,
+    /// the connection is closed, preceded by the DISCONNECT packet with the reason code (MQTT 5 only).
+    async fn handle_packets(&mut self, reader: &mut OwnedReadHalf) -> Result<(), ConnectionError> {
+        loop {
+            let header = match self.keep_alive {
+                Some(keep_alive) => {
+                    let Ok(header) =
+                        tokio::time::timeout(keep_alive, read_fixed_header(reader)).await
+                    else {
+                        info!(
+                            "MQTT client has exceeded the keep alive period, session: {}.",
+                            self.session
+                        );
+                        self.disconnect(KEEP_ALIVE_TIMEOUT).await;
+                        return Ok(());
+                    };
+                    header?
+                }
+                None => read_fixed_header(reader).await?,
+            };
+
+            if get_packet_size(&header) > self.max_packet_size as usize {
+                warn!(
+                    "Received MQTT packet of size: {} bytes exceeding the max size: {} bytes, session: {}.",
+                    get_packet_size(&header),
+                    self.max_packet_size,
+                    self.session
+                );
+                self.disconnect(PACKET_TOO_LARGE).await;
+                return Err(ConnectionError::from(IggyError::InvalidCommand));
+            }
+
+            if !header.has_valid_flags() {
+                self.disconnect(MALFORMED_PACKET).await;
+                return Err(ConnectionError::from(IggyError::InvalidCommand));
+            }
+
+            let mut packet = read_body(reader, &header).await?;
+            debug!(
+                "Received MQTT packet of type: {}, session: {}",
+                header.packet_type, self.session
+            );
+            let result = match header.packet_type {
+                PUBLISH => self.handle_publish(header.flags, &mut packet).await,
+                PUBACK => read_packet_id(&mut packet)
+                    .map(|packet_id| {
+                        self.subscriptions.subscriber().inflight.complete(packet_id);
+                    })
+                    .map_err(|_| MALFORMED_PACKET),
+                SUBSCRIBE => self.handle_subscribe(&mut packet).await,
+                UNSUBSCRIBE => self.handle_unsubscribe(&mut packet).await,
+                PINGREQ => self.send(encode_pingresp()).await,
+                DISCONNECT => {
+                    let reason_code = read_disconnect_reason_code(&mut packet)?;
+                    if reason_code != DISCONNECT_WITH_WILL_MESSAGE {
+                        self.will = None;
+                    }
+                    info!("MQTT client has disconnected, session: {}.", self.session);
+                    return Ok(());
+                }
+                // The second CONNECT, the QoS 2 flow and the enhanced authentication are not allowed.
+                _ => Err(PROTOCOL_ERROR),
+            };
+
+            if let Err(reason_code) = result {
+                warn!(
+                    "MQTT client has violated the protocol with packet of type: {}, reason code: {reason_code}, session: {}.",
+                    header.packet_type, self.session
+                );
+                self.disconnect(reason_code).await;
+                return Err(ConnectionError::from(IggyError::InvalidCommand));
+            }
+        }
+    }
+
+    /// Appends the published message and acknowledges it (QoS 1). The MQTT 3.1.1 clients can't be notified
+    /// that the message has been rejected, thus the connection is closed instead.
+    async fn handle_publish(&self, flags: u8, packet: &mut MqttReader) -> Result<(), u8> {
+        let publish =
+            Publish::read(flags, packet, self.protocol_level).map_err(|_| MALFORMED_PACKET)?;
+        if publish.qos == QOS_EXACTLY_ONCE {
+            return Err(QOS_NOT_SUPPORTED);
+        }
+
+        if self.is_v5() && publish.retain {
+            return Err(RETAIN_NOT_SUPPORTED);
+        }
+
+        if publish.properties.contains(TOPIC_ALIAS) {
+            return Err(TOPIC_ALIAS_INVALID);
+        }
+
+        if !is_valid_topic_name(&publish.topic) {
+            return Err(TOPIC_NAME_INVALID);
+        }
+
+        let result = append_message(
+            &self.system,
+            &self.session,
+            &self.mappings,
+            &publish.topic,
+            publish.payload,
+            &publish.properties,
+        )
+        .await;
+        if let Err(error) = &result {
+            warn!(
+                "{COMPONENT} - failed to append message published to topic: {}, session: {}, error: {error}",
+                publish.topic, self.session
+            );
+        }
+
+        let Some(packet_id) = publish.packet_id else {
+            return Ok(());
+        };
+
+        let reason_code = match result {
+            Ok(()) => SUCCESS,
+            Err(error) if self.is_v5() => map_error(&error),
+            Err(_) => return Err(UNSPECIFIED_ERROR),
+        };
+        self.send(encode_puback(self.protocol_level, packet_id, reason_code))
+            .await
+    }
+
+    async fn handle_subscribe(&mut self, packet: &mut MqttReader) -> Result<(), u8> {
+        let subscribe =
+            Subscribe::read(packet, self.protocol_level).map_err(|_| MALFORMED_PACKET)?;
+        if subscribe.properties.contains(SUBSCRIPTION_IDENTIFIER) {
+            return Err(SUBSCRIPTION_IDENTIFIERS_NOT_SUPPORTED);
+        }
+
+        let mut codes = Vec::with_capacity(subscribe.filters.len());
+        for (filter, options) in subscribe.filters {
+            let qos = options & 0b11;
+            if qos > QOS_EXACTLY_ONCE {
+                return Err(MALFORMED_PACKET);
+            }
+
+            let code = if filter.starts_with(SHARED_SUBSCRIPTION_PREFIX) {
+                self.subscription_failure(SHARED_SUBSCRIPTIONS_NOT_SUPPORTED)
+            } else if !is_valid_topic_filter(&filter) {
+                self.subscription_failure(TOPIC_FILTER_INVALID)
+            } else {
+                match self.subscriptions.subscribe(&filter, qos).await {
+                    Ok(granted_qos) => granted_qos,
+                    Err(error) => {
+                        warn!(
+                            "{COMPONENT} - failed to subscribe to topic filter: {filter}, session: {}, error: {error}",
+                            self.session
+                        );
+                        self.subscription_failure(match map_error(&error) {
+                            TOPIC_NAME_INVALID => TOPIC_FILTER_INVALID,
+                            reason_code => reason_code,
+                        })
+                    }
+                }
+            };
+            codes.push(code);
+        }
+        self.send(encode_suback(
+            self.protocol_level,
+            subscribe.packet_id,
+            &codes,
+        ))
+        .await
+    }
+
+    async fn handle_unsubscribe(&mut self, packet: &mut MqttReader) -> Result<(), u8> {
+        let unsubscribe =
+            Unsubscribe::read(packet, self.protocol_level).map_err(|_| MALFORMED_PACKET)?;
+        let codes = unsubscribe
+            .filters
+            .iter()
+            .map(|filter| {
+                if self.subscriptions.unsubscribe(filter) {
+                    SUCCESS
+                } else {
+                    NO_SUBSCRIPTION_EXISTED
+                }
+            })
+            .collect::<Vec<_>>();
+        self.send(encode_unsuback(
+            self.protocol_level,
+            unsubscribe.packet_id,
+            &codes,
+        ))
+        .await
+    }
+
+    /// MQTT 3.1.1 has a single return code for all the rejected subscriptions.
+    fn subscription_failure(&self, reason_code: u8) -> u8 {
+        if self.is_v5() {
+            reason_code
+        } else {
+            SUBSCRIPTION_FAILURE
+        }
+    }
+
+    async fn send(&self, packet: Bytes) -> Result<(), u8> {
+        self.outgoing
+            .send(packet)
+            .await
+            .map_err(|_| UNSPECIFIED_ERROR)
+    }
+
+    async fn disconnect(&self, reason_code: u8) {
+        if self.is_v5() {
+            let _ = self.outgoing.send(encode_disconnect(reason_code)).await;
+        }
+    }
+
+    async fn publish_will(&mut self) {
+        let Some(will) = self.will.take() else {
+            return;
+        };
+
+        match append_message(
+            &self.system,
+            &self.session,
+            &self.mappings,
+            &will.topic,
+            will.payload,
+            &will.properties,
+        )
+        .await
+        {
+            Ok(()) => debug!(
+                "Published MQTT will message to topic: {}, session: {}.",
+                will.topic, self.session
+            ),
+            Err(error) => warn!(
+                "{COMPONENT} - failed to publish will message to topic: {}, session: {}, error: {error}",
+                will.topic, self.session
+            ),
+        }
+    }
+}
+
+async fn read_fixed_header(reader: &mut OwnedReadHalf) -> Result<FixedHeader, ConnectionError> {
+    let byte = reader.read_u8().await?;
+    let mut remaining_length = 0u32;
+    for index in 0..4 {
+        let length_byte = reader.read_u8().await?;
+        remaining_length |= ((length_byte & 0x7f) as u32) << (index * 7);
+        if length_byte & 0x80 == 0 {
+            return Ok(FixedHeader::new(byte, remaining_length));
+        }
+    }
+
+    Err(ConnectionError::from(IggyError::InvalidNumberEncoding))
+}
+
+async fn read_body(
+    reader: &mut OwnedReadHalf,
+    header: &FixedHeader,
+) -> Result<MqttReader, ConnectionError> {
+    let mut buffer = BytesMut::zeroed(header.remaining_length as usize);
+    reader.read_exact(&mut buffer).await?;
+    Ok(MqttReader::new(buffer.freeze()))
+}
+
+fn get_packet_size(header: &FixedHeader) -> usize {
+    1 + variable_byte_integer_size(header.remaining_length) + header.remaining_length as usize
+}
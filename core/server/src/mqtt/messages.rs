@@ -0,0 +1,254 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::configs::mqtt::MqttTopicMapping;
+use crate::mqtt::packets::{Properties, PropertyValue, Publish};
+use crate::mqtt::protocol::{
+    CONTENT_TYPE, CORRELATION_DATA, PROTOCOL_LEVEL_5, RESPONSE_TOPIC, USER_PROPERTY,
+};
+use crate::mqtt::topics::resolve_publish_topic;
+use crate::streaming::segments::IggyMessagesBatchMut;
+use crate::streaming::session::Session;
+use crate::streaming::systems::system::SharedSystem;
+use bytes::Bytes;
+use iggy_common::{
+    HeaderKey, HeaderValue, Identifier, IggyError, IggyMessage, Partitioning, Sizeable,
+};
+use std::collections::HashMap;
+
+pub const MQTT_TOPIC_HEADER: &str = "mqtt_topic";
+pub const MQTT_CONTENT_TYPE_HEADER: &str = "mqtt_content_type";
+pub const MQTT_RESPONSE_TOPIC_HEADER: &str = "mqtt_response_topic";
+pub const MQTT_CORRELATION_DATA_HEADER: &str = "mqtt_correlation_data";
+
+/// Converts the published payload into the Iggy message. The MQTT topic name is always stored
+/// in the user headers, along with the content type, the response topic, the correlation data
+/// and the user properties (named after the property) sent by the MQTT 5 clients.
+pub fn to_message(
+    topic: &str,
+    payload: Bytes,
+    properties: &Properties,
+) -> Result<IggyMessage, IggyError> {
+    let mut user_headers = HashMap::new();
+    for (name, value) in properties.user_properties() {
+        user_headers.insert(HeaderKey::new(name)?, value.parse::<HeaderValue>()?);
+    }
+    if let Some(content_type) = properties.get_string(CONTENT_TYPE) {
+        user_headers.insert(
+            HeaderKey::new(MQTT_CONTENT_TYPE_HEADER)?,
+            content_type.parse()?,
+        );
+    }
+    if let Some(response_topic) = properties.get_string(RESPONSE_TOPIC) {
+        user_headers.insert(
+            HeaderKey::new(MQTT_RESPONSE_TOPIC_HEADER)?,
+            response_topic.parse()?,
+        );
+    }
+    if let Some(correlation_data) = properties.get_binary(CORRELATION_DATA) {
+        user_headers.insert(
+            HeaderKey::new(MQTT_CORRELATION_DATA_HEADER)?,
+            HeaderValue::from_raw(correlation_data)?,
+        );
+    }
+    user_headers.insert(HeaderKey::new(MQTT_TOPIC_HEADER)?, topic.parse()?);
+
+    IggyMessage::builder()
+        .payload(payload)
+        .user_headers(user_headers)
+        .build()
+}
+
+/// Appends the message published to the MQTT topic to the Iggy topic it's mapped onto.
+/// The messages are partitioned by the MQTT topic name, so the messages of the same topic (e.g. device) are kept in order.
+pub async fn append_message(
+    system: &SharedSystem,
+    session: &Session,
+    mappings: &[MqttTopicMapping],
+    topic: &str,
+    payload: Bytes,
+    properties: &Properties,
+) -> Result<(), IggyError> {
+    let Some(iggy_topic) = resolve_publish_topic(mappings, topic) else {
+        return Err(IggyError::ResourceNotFound(format!(
+            "Iggy topic for MQTT topic: {topic}"
+        )));
+    };
+
+    let stream_id = Identifier::named(&iggy_topic.stream)?;
+    let topic_id = Identifier::named(&iggy_topic.topic)?;
+    let message = to_message(topic, payload, properties)?;
+    let messages_size = message.get_size_bytes().as_bytes_u32();
+    let batch = IggyMessagesBatchMut::from_messages(&[message], messages_size);
+    let partitioning =
+        Partitioning::messages_key_str(topic).unwrap_or_else(|_| Partitioning::balanced());
+    system
+        .read()
+        .await
+        .append_messages(
+            session,
+            &stream_id,
+            &topic_id,
+            &partitioning,
+            batch,
+            None,
+            None,
+        )
+        .await
+}
+
+/// Returns the MQTT topic name of the message, which is either the one it was published to,
+/// or the name of the stream joined with the name of the topic, if the message was appended by the other clients.
+pub fn get_topic_name(message: &IggyMessage, stream_name: &str, topic_name: &str) -> String {
+    let header = HeaderKey::new(MQTT_TOPIC_HEADER)
+        .and_then(|key| message.get_user_header(&key))
+        .ok()
+        .flatten();
+    match header.as_ref().map(|header| header.as_str()) {
+        Some(Ok(name)) => name.to_owned(),
+        _ => format!("{stream_name}/{topic_name}"),
+    }
+}
+
+/// Converts the polled message into the PUBLISH packet. For the MQTT 5 clients, the user headers are sent back
+/// as the properties they were created from, or as the user properties, e.g. for the messages appended by the other clients.
+pub fn to_publish(
+    message: &IggyMessage,
+    topic: String,
+    qos: u8,
+    packet_id: Option<u16>,
+    protocol_level: u8,
+) -> Publish {
+    let mut properties = Properties::default();
+    if protocol_level == PROTOCOL_LEVEL_5 {
+        let user_headers = message
+            .user_headers_map()
+            .ok()
+            .flatten()
+            .unwrap_or_default();
+        for (key, value) in user_headers {
+            let property = match (key.as_str(), value.as_str()) {
+                (MQTT_TOPIC_HEADER, _) => continue,
+                (MQTT_CONTENT_TYPE_HEADER, Ok(value)) => {
+                    (CONTENT_TYPE, PropertyValue::String(value.to_owned()))
+                }
+                (MQTT_RESPONSE_TOPIC_HEADER, Ok(value)) => {
+                    (RESPONSE_TOPIC, PropertyValue::String(value.to_owned()))
+                }
+                (MQTT_CORRELATION_DATA_HEADER, _) => match value.as_raw() {
+                    Ok(value) => (
+                        CORRELATION_DATA,
+                        PropertyValue::Binary(Bytes::copy_from_slice(value)),
+                    ),
+                    Err(_) => continue,
+                },
+                (name, _) => (
+                    USER_PROPERTY,
+                    PropertyValue::StringPair(name.to_owned(), value.value_only_to_string()),
+                ),
+            };
+            properties.push(property.0, property.1);
+        }
+    }
+
+    Publish {
+        dup: false,
+        qos,
+        retain: false,
+        topic,
+        packet_id,
+        properties,
+        payload: message.payload.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mqtt::protocol::{PROTOCOL_LEVEL_3_1_1, QOS_AT_LEAST_ONCE};
+
+    #[test]
+    fn published_message_should_keep_topic_and_properties() {
+        let mut properties = Properties::default();
+        properties.push(CONTENT_TYPE, PropertyValue::String("text/plain".to_owned()));
+        properties.push(
+            CORRELATION_DATA,
+            PropertyValue::Binary(Bytes::from_static(b"1")),
+        );
+        properties.push(
+            USER_PROPERTY,
+            PropertyValue::StringPair("unit".to_owned(), "celsius".to_owned()),
+        );
+        let message = to_message(
+            "devices/1/telemetry",
+            Bytes::from_static(b"21.5"),
+            &properties,
+        )
+        .unwrap();
+        assert_eq!(
+            get_topic_name(&message, "iot", "telemetry"),
+            "devices/1/telemetry"
+        );
+
+        let publish = to_publish(
+            &message,
+            "devices/1/telemetry".to_owned(),
+            QOS_AT_LEAST_ONCE,
+            Some(1),
+            PROTOCOL_LEVEL_5,
+        );
+        assert_eq!(publish.payload.as_ref(), b"21.5");
+        assert_eq!(
+            publish.properties.get_string(CONTENT_TYPE),
+            Some("text/plain")
+        );
+        assert_eq!(
+            publish
+                .properties
+                .get_binary(CORRELATION_DATA)
+                .unwrap()
+                .as_ref(),
+            b"1"
+        );
+        assert_eq!(
+            publish.properties.user_properties().collect::<Vec<_>>(),
+            vec![("unit", "celsius")]
+        );
+
+        let publish = to_publish(
+            &message,
+            "devices/1/telemetry".to_owned(),
+            QOS_AT_LEAST_ONCE,
+            Some(1),
+            PROTOCOL_LEVEL_3_1_1,
+        );
+        assert!(publish.properties.is_empty());
+    }
+
+    #[test]
+    fn message_without_topic_header_should_be_named_after_stream_and_topic() {
+        let message = IggyMessage::builder()
+            .payload(Bytes::from_static(b"data"))
+            .build()
+            .unwrap();
+        assert_eq!(
+            get_topic_name(&message, "iot", "telemetry"),
+            "iot/telemetry"
+        );
+    }
+}
@@ -0,0 +1,90 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::configs::mqtt::MqttConfig;
+use crate::mqtt::connection_handler::handle_connection;
+use crate::streaming::clients::client_manager::Transport;
+use crate::streaming::systems::system::SharedSystem;
+use crate::tcp::connection_handler::handle_error;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use tokio::sync::oneshot;
+use tracing::{error, info};
+
+/// Starts the listener accepting the connections of the MQTT clients (publishers and subscribers).
+pub async fn start(config: MqttConfig, system: SharedSystem) -> SocketAddr {
+    info!("Initializing MQTT server...");
+    let (tx, rx) = oneshot::channel();
+    tokio::spawn(async move {
+        let listener = TcpListener::bind(&config.address)
+            .await
+            .unwrap_or_else(|error| {
+                panic!(
+                    "Unable to start MQTT server on address: {}. {error}",
+                    config.address
+                )
+            });
+        let local_addr = listener
+            .local_addr()
+            .expect("Failed to get local address for MQTT listener");
+        tx.send(local_addr).unwrap_or_else(|_| {
+            panic!("Failed to send the local address {local_addr:?} for MQTT listener")
+        });
+
+        let mappings = Arc::new(config.mappings);
+        let max_packet_size = config.max_packet_size.as_bytes_u64() as u32;
+        loop {
+            let (stream, address) = match listener.accept().await {
+                Ok(connection) => connection,
+                Err(error) => {
+                    error!("Unable to accept MQTT socket. {error}");
+                    continue;
+                }
+            };
+
+            info!("Accepted new MQTT connection: {address}");
+            let session = system
+                .read()
+                .await
+                .add_client(&address, Transport::Mqtt)
+                .await;
+            let client_id = session.client_id;
+            info!("Created new MQTT session: {session}");
+            let system = system.clone();
+            let mappings = mappings.clone();
+            tokio::spawn(async move {
+                if let Err(error) =
+                    handle_connection(stream, session, system.clone(), mappings, max_packet_size)
+                        .await
+                {
+                    handle_error(error);
+                }
+                system.read().await.delete_client(client_id).await;
+                info!("Closed MQTT connection for client: {client_id}, address: {address}.");
+            });
+        }
+    });
+    match rx.await {
+        Ok(addr) => {
+            info!("MQTT server has started on: {addr}");
+            addr
+        }
+        Err(_) => panic!("Failed to get the local address for MQTT listener."),
+    }
+}
@@ -0,0 +1,595 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::mqtt::codec::{MqttBufMut, MqttReader, variable_byte_integer_size};
+use crate::mqtt::protocol::{
+    ASSIGNED_CLIENT_IDENTIFIER, AUTHENTICATION_DATA, AUTHENTICATION_METHOD, CONNACK, CONTENT_TYPE,
+    CORRELATION_DATA, DISCONNECT, MAXIMUM_PACKET_SIZE, MAXIMUM_QOS, MESSAGE_EXPIRY_INTERVAL,
+    PAYLOAD_FORMAT_INDICATOR, PINGRESP, PROTOCOL_LEVEL_5, PROTOCOL_NAME, PUBACK, PUBLISH, PUBREL,
+    QOS_AT_MOST_ONCE, QOS_EXACTLY_ONCE, REASON_STRING, RECEIVE_MAXIMUM,
+    REQUEST_PROBLEM_INFORMATION, REQUEST_RESPONSE_INFORMATION, RESPONSE_INFORMATION,
+    RESPONSE_TOPIC, RETAIN_AVAILABLE, SERVER_KEEP_ALIVE, SERVER_REFERENCE, SESSION_EXPIRY_INTERVAL,
+    SHARED_SUBSCRIPTION_AVAILABLE, SUBACK, SUBSCRIBE, SUBSCRIPTION_IDENTIFIER,
+    SUBSCRIPTION_IDENTIFIER_AVAILABLE, SUCCESS, TOPIC_ALIAS, TOPIC_ALIAS_MAXIMUM, UNSUBACK,
+    UNSUBSCRIBE, USER_PROPERTY, WILDCARD_SUBSCRIPTION_AVAILABLE, WILL_DELAY_INTERVAL,
+};
+use bytes::{BufMut, Bytes, BytesMut};
+use iggy_common::IggyError;
+
+/// The protocol name used by MQTT 3.1, which is recognized only to reject its protocol level.
+const LEGACY_PROTOCOL_NAME: &str = "MQIsdp";
+const NO_PROPERTIES: u8 = 0;
+
+/// The first byte of the fixed header, followed by the length of the rest of the packet.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FixedHeader {
+    pub packet_type: u8,
+    pub flags: u8,
+    pub remaining_length: u32,
+}
+
+impl FixedHeader {
+    pub fn new(byte: u8, remaining_length: u32) -> Self {
+        Self {
+            packet_type: byte >> 4,
+            flags: byte & 0x0f,
+            remaining_length,
+        }
+    }
+
+    /// Only the PUBLISH packet carries the flags, while SUBSCRIBE, UNSUBSCRIBE and PUBREL require the fixed value.
+    pub fn has_valid_flags(&self) -> bool {
+        match self.packet_type {
+            PUBLISH => true,
+            SUBSCRIBE | UNSUBSCRIBE | PUBREL => self.flags == 0b0010,
+            _ => self.flags == 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PropertyValue {
+    Byte(u8),
+    TwoByteInteger(u16),
+    FourByteInteger(u32),
+    VariableByteInteger(u32),
+    String(String),
+    Binary(Bytes),
+    StringPair(String, String),
+}
+
+/// The properties of the MQTT 5 packets, kept in the order they were sent,
+/// as the user properties might be repeated (even with the same name).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Properties {
+    items: Vec<(u8, PropertyValue)>,
+}
+
+impl Properties {
+    pub fn read(reader: &mut MqttReader) -> Result<Self, IggyError> {
+        let length = reader.read_variable_byte_integer()?;
+        let mut reader = MqttReader::new(reader.read_raw(length as usize)?);
+        let mut items = Vec::new();
+        while reader.remaining() > 0 {
+            let identifier = reader.read_variable_byte_integer()?;
+            let identifier = u8::try_from(identifier).map_err(|_| IggyError::InvalidCommand)?;
+            let value = match identifier {
+                PAYLOAD_FORMAT_INDICATOR
+                | REQUEST_PROBLEM_INFORMATION
+                | REQUEST_RESPONSE_INFORMATION
+                | MAXIMUM_QOS
+                | RETAIN_AVAILABLE
+                | WILDCARD_SUBSCRIPTION_AVAILABLE
+                | SUBSCRIPTION_IDENTIFIER_AVAILABLE
+                | SHARED_SUBSCRIPTION_AVAILABLE => PropertyValue::Byte(reader.read_u8()?),
+                SERVER_KEEP_ALIVE | RECEIVE_MAXIMUM | TOPIC_ALIAS_MAXIMUM | TOPIC_ALIAS => {
+                    PropertyValue::TwoByteInteger(reader.read_u16()?)
+                }
+                MESSAGE_EXPIRY_INTERVAL
+                | SESSION_EXPIRY_INTERVAL
+                | WILL_DELAY_INTERVAL
+                | MAXIMUM_PACKET_SIZE => PropertyValue::FourByteInteger(reader.read_u32()?),
+                SUBSCRIPTION_IDENTIFIER => {
+                    PropertyValue::VariableByteInteger(reader.read_variable_byte_integer()?)
+                }
+                CONTENT_TYPE
+                | RESPONSE_TOPIC
+                | ASSIGNED_CLIENT_IDENTIFIER
+                | AUTHENTICATION_METHOD
+                | RESPONSE_INFORMATION
+                | SERVER_REFERENCE
+                | REASON_STRING => PropertyValue::String(reader.read_string()?),
+                CORRELATION_DATA | AUTHENTICATION_DATA => {
+                    PropertyValue::Binary(reader.read_binary()?)
+                }
+                USER_PROPERTY => {
+                    PropertyValue::StringPair(reader.read_string()?, reader.read_string()?)
+                }
+                _ => return Err(IggyError::InvalidCommand),
+            };
+            items.push((identifier, value));
+        }
+
+        Ok(Self { items })
+    }
+
+    pub fn write(&self, bytes: &mut BytesMut) {
+        let mut properties = BytesMut::new();
+        for (identifier, value) in &self.items {
+            properties.put_u8(*identifier);
+            match value {
+                PropertyValue::Byte(value) => properties.put_u8(*value),
+                PropertyValue::TwoByteInteger(value) => properties.put_u16(*value),
+                PropertyValue::FourByteInteger(value) => properties.put_u32(*value),
+                PropertyValue::VariableByteInteger(value) => {
+                    properties.put_variable_byte_integer(*value)
+                }
+                PropertyValue::String(value) => properties.put_mqtt_string(value),
+                PropertyValue::Binary(value) => properties.put_mqtt_binary(value),
+                PropertyValue::StringPair(name, value) => {
+                    properties.put_mqtt_string(name);
+                    properties.put_mqtt_string(value);
+                }
+            }
+        }
+        bytes.put_variable_byte_integer(properties.len() as u32);
+        bytes.put_slice(&properties);
+    }
+
+    pub fn push(&mut self, identifier: u8, value: PropertyValue) {
+        self.items.push((identifier, value));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn contains(&self, identifier: u8) -> bool {
+        self.items.iter().any(|(id, _)| *id == identifier)
+    }
+
+    pub fn get_u16(&self, identifier: u8) -> Option<u16> {
+        self.items.iter().find_map(|(id, value)| match value {
+            PropertyValue::TwoByteInteger(value) if *id == identifier => Some(*value),
+            _ => None,
+        })
+    }
+
+    pub fn get_u32(&self, identifier: u8) -> Option<u32> {
+        self.items.iter().find_map(|(id, value)| match value {
+            PropertyValue::FourByteInteger(value) if *id == identifier => Some(*value),
+            _ => None,
+        })
+    }
+
+    pub fn get_string(&self, identifier: u8) -> Option<&str> {
+        self.items.iter().find_map(|(id, value)| match value {
+            PropertyValue::String(value) if *id == identifier => Some(value.as_str()),
+            _ => None,
+        })
+    }
+
+    pub fn get_binary(&self, identifier: u8) -> Option<&Bytes> {
+        self.items.iter().find_map(|(id, value)| match value {
+            PropertyValue::Binary(value) if *id == identifier => Some(value),
+            _ => None,
+        })
+    }
+
+    pub fn user_properties(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.items.iter().filter_map(|(_, value)| match value {
+            PropertyValue::StringPair(name, value) => Some((name.as_str(), value.as_str())),
+            _ => None,
+        })
+    }
+}
+
+/// The message published by the server on behalf of the client, once its connection is closed unexpectedly.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Will {
+    pub topic: String,
+    pub payload: Bytes,
+    pub qos: u8,
+    pub retain: bool,
+    pub properties: Properties,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Connect {
+    pub protocol_level: u8,
+    pub clean_start: bool,
+    pub keep_alive: u16,
+    pub properties: Properties,
+    pub client_id: String,
+    pub will: Option<Will>,
+    pub username: Option<String>,
+    pub password: Option<Bytes>,
+}
+
+impl Connect {
+    /// Reads the protocol name and level, which must be checked before reading the rest of the packet,
+    /// as its format depends on the protocol level.
+    pub fn read_protocol_level(reader: &mut MqttReader) -> Result<u8, IggyError> {
+        let protocol_name = reader.read_string()?;
+        if protocol_name != PROTOCOL_NAME && protocol_name != LEGACY_PROTOCOL_NAME {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        reader.read_u8()
+    }
+
+    pub fn read(reader: &mut MqttReader, protocol_level: u8) -> Result<Self, IggyError> {
+        let flags = reader.read_u8()?;
+        if flags & 0b0000_0001 != 0 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let has_username = flags & 0b1000_0000 != 0;
+        let has_password = flags & 0b0100_0000 != 0;
+        let will_retain = flags & 0b0010_0000 != 0;
+        let will_qos = (flags >> 3) & 0b11;
+        let has_will = flags & 0b0000_0100 != 0;
+        let clean_start = flags & 0b0000_0010 != 0;
+        if will_qos > QOS_EXACTLY_ONCE
+            || (!has_will && (will_qos != QOS_AT_MOST_ONCE || will_retain))
+        {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let keep_alive = reader.read_u16()?;
+        let is_v5 = protocol_level == PROTOCOL_LEVEL_5;
+        let properties = read_properties(reader, is_v5)?;
+        let client_id = reader.read_string()?;
+        let will = if has_will {
+            let properties = read_properties(reader, is_v5)?;
+            Some(Will {
+                topic: reader.read_string()?,
+                payload: reader.read_binary()?,
+                qos: will_qos,
+                retain: will_retain,
+                properties,
+            })
+        } else {
+            None
+        };
+        let username = has_username.then(|| reader.read_string()).transpose()?;
+        let password = has_password.then(|| reader.read_binary()).transpose()?;
+        Ok(Self {
+            protocol_level,
+            clean_start,
+            keep_alive,
+            properties,
+            client_id,
+            will,
+            username,
+            password,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Publish {
+    pub dup: bool,
+    pub qos: u8,
+    pub retain: bool,
+    pub topic: String,
+    pub packet_id: Option<u16>,
+    pub properties: Properties,
+    pub payload: Bytes,
+}
+
+impl Publish {
+    pub fn read(flags: u8, reader: &mut MqttReader, protocol_level: u8) -> Result<Self, IggyError> {
+        let qos = (flags >> 1) & 0b11;
+        if qos > QOS_EXACTLY_ONCE {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let topic = reader.read_string()?;
+        let packet_id = if qos > QOS_AT_MOST_ONCE {
+            Some(read_packet_id(reader)?)
+        } else {
+            None
+        };
+        let properties = read_properties(reader, protocol_level == PROTOCOL_LEVEL_5)?;
+        Ok(Self {
+            dup: flags & 0b1000 != 0,
+            qos,
+            retain: flags & 0b0001 != 0,
+            topic,
+            packet_id,
+            properties,
+            payload: reader.read_remaining(),
+        })
+    }
+
+    pub fn encode(&self, protocol_level: u8) -> Bytes {
+        let mut body = BytesMut::with_capacity(self.topic.len() + self.payload.len() + 16);
+        body.put_mqtt_string(&self.topic);
+        if let Some(packet_id) = self.packet_id {
+            body.put_u16(packet_id);
+        }
+        if protocol_level == PROTOCOL_LEVEL_5 {
+            self.properties.write(&mut body);
+        }
+        body.put_slice(&self.payload);
+        let flags = ((self.dup as u8) << 3) | (self.qos << 1) | self.retain as u8;
+        encode_packet(PUBLISH, flags, &body)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Subscribe {
+    pub packet_id: u16,
+    pub properties: Properties,
+    /// The topic filters with their subscription options, the lowest two bits of which are the max QoS.
+    pub filters: Vec<(String, u8)>,
+}
+
+impl Subscribe {
+    pub fn read(reader: &mut MqttReader, protocol_level: u8) -> Result<Self, IggyError> {
+        let packet_id = read_packet_id(reader)?;
+        let properties = read_properties(reader, protocol_level == PROTOCOL_LEVEL_5)?;
+        let mut filters = Vec::new();
+        while reader.remaining() > 0 {
+            filters.push((reader.read_string()?, reader.read_u8()?));
+        }
+        if filters.is_empty() {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        Ok(Self {
+            packet_id,
+            properties,
+            filters,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Unsubscribe {
+    pub packet_id: u16,
+    pub filters: Vec<String>,
+}
+
+impl Unsubscribe {
+    pub fn read(reader: &mut MqttReader, protocol_level: u8) -> Result<Self, IggyError> {
+        let packet_id = read_packet_id(reader)?;
+        read_properties(reader, protocol_level == PROTOCOL_LEVEL_5)?;
+        let mut filters = Vec::new();
+        while reader.remaining() > 0 {
+            filters.push(reader.read_string()?);
+        }
+        if filters.is_empty() {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        Ok(Self { packet_id, filters })
+    }
+}
+
+/// Reads the non-zero packet identifier, e.g. of the PUBACK packet.
+pub fn read_packet_id(reader: &mut MqttReader) -> Result<u16, IggyError> {
+    match reader.read_u16()? {
+        0 => Err(IggyError::InvalidCommand),
+        packet_id => Ok(packet_id),
+    }
+}
+
+/// Reads the reason code of the DISCONNECT packet, which might be omitted in MQTT 5 (success) and is never sent in MQTT 3.1.1.
+pub fn read_disconnect_reason_code(reader: &mut MqttReader) -> Result<u8, IggyError> {
+    if reader.remaining() == 0 {
+        return Ok(SUCCESS);
+    }
+
+    reader.read_u8()
+}
+
+fn read_properties(reader: &mut MqttReader, is_v5: bool) -> Result<Properties, IggyError> {
+    if is_v5 {
+        Properties::read(reader)
+    } else {
+        Ok(Properties::default())
+    }
+}
+
+pub fn encode_packet(packet_type: u8, flags: u8, body: &[u8]) -> Bytes {
+    let remaining_length = body.len() as u32;
+    let mut packet =
+        BytesMut::with_capacity(1 + variable_byte_integer_size(remaining_length) + body.len());
+    packet.put_u8((packet_type << 4) | flags);
+    packet.put_variable_byte_integer(remaining_length);
+    packet.put_slice(body);
+    packet.freeze()
+}
+
+/// Encodes the CONNACK packet, the properties are sent only in MQTT 5.
+pub fn encode_connack(
+    protocol_level: u8,
+    session_present: bool,
+    code: u8,
+    properties: &Properties,
+) -> Bytes {
+    let mut body = BytesMut::new();
+    body.put_u8(session_present as u8);
+    body.put_u8(code);
+    if protocol_level == PROTOCOL_LEVEL_5 {
+        properties.write(&mut body);
+    }
+    encode_packet(CONNACK, 0, &body)
+}
+
+/// Encodes the PUBACK packet, the reason code is sent only in MQTT 5 and might be omitted on success.
+pub fn encode_puback(protocol_level: u8, packet_id: u16, reason_code: u8) -> Bytes {
+    let mut body = BytesMut::with_capacity(3);
+    body.put_u16(packet_id);
+    if protocol_level == PROTOCOL_LEVEL_5 && reason_code != SUCCESS {
+        body.put_u8(reason_code);
+    }
+    encode_packet(PUBACK, 0, &body)
+}
+
+/// Encodes the SUBACK packet with the granted QoS or the failure reason code of each of the topic filters.
+pub fn encode_suback(protocol_level: u8, packet_id: u16, codes: &[u8]) -> Bytes {
+    let mut body = BytesMut::with_capacity(3 + codes.len());
+    body.put_u16(packet_id);
+    if protocol_level == PROTOCOL_LEVEL_5 {
+        body.put_u8(NO_PROPERTIES);
+    }
+    body.put_slice(codes);
+    encode_packet(SUBACK, 0, &body)
+}
+
+/// Encodes the UNSUBACK packet, the reason codes of the topic filters are sent only in MQTT 5.
+pub fn encode_unsuback(protocol_level: u8, packet_id: u16, codes: &[u8]) -> Bytes {
+    let mut body = BytesMut::with_capacity(3 + codes.len());
+    body.put_u16(packet_id);
+    if protocol_level == PROTOCOL_LEVEL_5 {
+        body.put_u8(NO_PROPERTIES);
+        body.put_slice(codes);
+    }
+    encode_packet(UNSUBACK, 0, &body)
+}
+
+pub fn encode_pingresp() -> Bytes {
+    encode_packet(PINGRESP, 0, &[])
+}
+
+/// Encodes the DISCONNECT packet sent by the server, which is available in MQTT 5 only.
+pub fn encode_disconnect(reason_code: u8) -> Bytes {
+    encode_packet(DISCONNECT, 0, &[reason_code, NO_PROPERTIES])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mqtt::protocol::{PROTOCOL_LEVEL_3_1_1, QOS_AT_LEAST_ONCE, UNSPECIFIED_ERROR};
+
+    fn read_fixed_header(packet: &Bytes) -> (FixedHeader, MqttReader) {
+        let mut reader = MqttReader::new(packet.clone());
+        let byte = reader.read_u8().unwrap();
+        let remaining_length = reader.read_variable_byte_integer().unwrap();
+        assert_eq!(reader.remaining(), remaining_length as usize);
+        (FixedHeader::new(byte, remaining_length), reader)
+    }
+
+    #[test]
+    fn connect_should_be_read() {
+        let mut properties = Properties::default();
+        properties.push(RECEIVE_MAXIMUM, PropertyValue::TwoByteInteger(10));
+        let mut body = BytesMut::new();
+        body.put_mqtt_string(PROTOCOL_NAME);
+        body.put_u8(PROTOCOL_LEVEL_5);
+        body.put_u8(0b1100_1110);
+        body.put_u16(30);
+        properties.write(&mut body);
+        body.put_mqtt_string("device-1");
+        Properties::default().write(&mut body);
+        body.put_mqtt_string("devices/1/status");
+        body.put_mqtt_binary(b"offline");
+        body.put_mqtt_string("user");
+        body.put_mqtt_binary(b"secret");
+
+        let mut reader = MqttReader::new(body.freeze());
+        let protocol_level = Connect::read_protocol_level(&mut reader).unwrap();
+        let connect = Connect::read(&mut reader, protocol_level).unwrap();
+        assert_eq!(connect.protocol_level, PROTOCOL_LEVEL_5);
+        assert!(connect.clean_start);
+        assert_eq!(connect.keep_alive, 30);
+        assert_eq!(connect.properties.get_u16(RECEIVE_MAXIMUM), Some(10));
+        assert_eq!(connect.client_id, "device-1");
+        let will = connect.will.unwrap();
+        assert_eq!(will.topic, "devices/1/status");
+        assert_eq!(will.payload.as_ref(), b"offline");
+        assert_eq!(will.qos, QOS_AT_LEAST_ONCE);
+        assert_eq!(connect.username.as_deref(), Some("user"));
+        assert_eq!(connect.password.unwrap().as_ref(), b"secret");
+    }
+
+    #[test]
+    fn publish_should_be_encoded_and_read() {
+        for protocol_level in [PROTOCOL_LEVEL_3_1_1, PROTOCOL_LEVEL_5] {
+            let mut properties = Properties::default();
+            if protocol_level == PROTOCOL_LEVEL_5 {
+                properties.push(CONTENT_TYPE, PropertyValue::String("text/plain".to_owned()));
+                properties.push(
+                    USER_PROPERTY,
+                    PropertyValue::StringPair("unit".to_owned(), "celsius".to_owned()),
+                );
+            }
+            let publish = Publish {
+                dup: false,
+                qos: QOS_AT_LEAST_ONCE,
+                retain: false,
+                topic: "devices/1/telemetry".to_owned(),
+                packet_id: Some(7),
+                properties,
+                payload: Bytes::from_static(b"21.5"),
+            };
+
+            let packet = publish.encode(protocol_level);
+            let (header, mut reader) = read_fixed_header(&packet);
+            assert_eq!(header.packet_type, PUBLISH);
+            assert!(header.has_valid_flags());
+            let read_publish = Publish::read(header.flags, &mut reader, protocol_level).unwrap();
+            assert_eq!(read_publish, publish);
+        }
+    }
+
+    #[test]
+    fn subscribe_should_be_read() {
+        let mut body = BytesMut::new();
+        body.put_u16(3);
+        Properties::default().write(&mut body);
+        body.put_mqtt_string("devices/+/telemetry");
+        body.put_u8(QOS_AT_LEAST_ONCE);
+        body.put_mqtt_string("devices/#");
+        body.put_u8(QOS_EXACTLY_ONCE);
+
+        let mut reader = MqttReader::new(body.freeze());
+        let subscribe = Subscribe::read(&mut reader, PROTOCOL_LEVEL_5).unwrap();
+        assert_eq!(subscribe.packet_id, 3);
+        assert_eq!(
+            subscribe.filters,
+            vec![
+                ("devices/+/telemetry".to_owned(), QOS_AT_LEAST_ONCE),
+                ("devices/#".to_owned(), QOS_EXACTLY_ONCE)
+            ]
+        );
+    }
+
+    #[test]
+    fn acknowledgements_should_depend_on_protocol_level() {
+        assert_eq!(
+            encode_puback(PROTOCOL_LEVEL_3_1_1, 1, UNSPECIFIED_ERROR).as_ref(),
+            &[PUBACK << 4, 2, 0, 1]
+        );
+        assert_eq!(
+            encode_puback(PROTOCOL_LEVEL_5, 1, UNSPECIFIED_ERROR).as_ref(),
+            &[PUBACK << 4, 3, 0, 1, UNSPECIFIED_ERROR]
+        );
+        assert_eq!(
+            encode_unsuback(PROTOCOL_LEVEL_3_1_1, 1, &[SUCCESS]).as_ref(),
+            &[UNSUBACK << 4, 2, 0, 1]
+        );
+        assert_eq!(
+            encode_suback(PROTOCOL_LEVEL_5, 1, &[QOS_AT_LEAST_ONCE]).as_ref(),
+            &[SUBACK << 4, 4, 0, 1, 0, QOS_AT_LEAST_ONCE]
+        );
+    }
+}
@@ -0,0 +1,135 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::mqtt::codec::MAX_VARIABLE_BYTE_INTEGER;
+use iggy_common::IggyError;
+
+pub const CONNECT: u8 = 1;
+pub const CONNACK: u8 = 2;
+pub const PUBLISH: u8 = 3;
+pub const PUBACK: u8 = 4;
+pub const PUBREC: u8 = 5;
+pub const PUBREL: u8 = 6;
+pub const PUBCOMP: u8 = 7;
+pub const SUBSCRIBE: u8 = 8;
+pub const SUBACK: u8 = 9;
+pub const UNSUBSCRIBE: u8 = 10;
+pub const UNSUBACK: u8 = 11;
+pub const PINGREQ: u8 = 12;
+pub const PINGRESP: u8 = 13;
+pub const DISCONNECT: u8 = 14;
+pub const AUTH: u8 = 15;
+
+/// The max size of the whole packet: the fixed header byte, the 4 bytes of the remaining length and the remaining bytes.
+pub const MQTT_MAX_PACKET_SIZE: u32 = MAX_VARIABLE_BYTE_INTEGER + 5;
+
+pub const PROTOCOL_NAME: &str = "MQTT";
+pub const PROTOCOL_LEVEL_3_1_1: u8 = 4;
+pub const PROTOCOL_LEVEL_5: u8 = 5;
+
+pub const QOS_AT_MOST_ONCE: u8 = 0;
+pub const QOS_AT_LEAST_ONCE: u8 = 1;
+/// QoS 2 (exactly once) isn't supported, QoS 1 is granted instead for the subscriptions.
+pub const QOS_EXACTLY_ONCE: u8 = 2;
+pub const MAX_SUPPORTED_QOS: u8 = QOS_AT_LEAST_ONCE;
+
+/// The return codes of the CONNACK packet in MQTT 3.1.1.
+pub const CONNECTION_ACCEPTED: u8 = 0x00;
+pub const UNACCEPTABLE_PROTOCOL_VERSION: u8 = 0x01;
+pub const IDENTIFIER_REJECTED: u8 = 0x02;
+pub const BAD_USERNAME_OR_PASSWORD: u8 = 0x04;
+/// The return code of the SUBACK packet in MQTT 3.1.1 for the rejected subscription.
+pub const SUBSCRIPTION_FAILURE: u8 = 0x80;
+
+/// The reason codes of MQTT 5.
+pub const SUCCESS: u8 = 0x00;
+pub const NO_SUBSCRIPTION_EXISTED: u8 = 0x11;
+pub const UNSPECIFIED_ERROR: u8 = 0x80;
+pub const MALFORMED_PACKET: u8 = 0x81;
+pub const PROTOCOL_ERROR: u8 = 0x82;
+pub const UNSUPPORTED_PROTOCOL_VERSION: u8 = 0x84;
+pub const CLIENT_IDENTIFIER_NOT_VALID: u8 = 0x85;
+pub const BAD_USER_NAME_OR_PASSWORD: u8 = 0x86;
+pub const NOT_AUTHORIZED: u8 = 0x87;
+pub const BAD_AUTHENTICATION_METHOD: u8 = 0x8C;
+pub const KEEP_ALIVE_TIMEOUT: u8 = 0x8D;
+pub const TOPIC_FILTER_INVALID: u8 = 0x8F;
+pub const TOPIC_NAME_INVALID: u8 = 0x90;
+pub const TOPIC_ALIAS_INVALID: u8 = 0x94;
+pub const PACKET_TOO_LARGE: u8 = 0x95;
+pub const PAYLOAD_FORMAT_INVALID: u8 = 0x99;
+pub const RETAIN_NOT_SUPPORTED: u8 = 0x9A;
+pub const QOS_NOT_SUPPORTED: u8 = 0x9B;
+pub const SHARED_SUBSCRIPTIONS_NOT_SUPPORTED: u8 = 0x9E;
+pub const SUBSCRIPTION_IDENTIFIERS_NOT_SUPPORTED: u8 = 0xA1;
+/// The reason code of the DISCONNECT packet sent by the client, which requests the will message to be published.
+pub const DISCONNECT_WITH_WILL_MESSAGE: u8 = 0x04;
+
+/// The identifiers of the MQTT 5 properties.
+pub const PAYLOAD_FORMAT_INDICATOR: u8 = 0x01;
+pub const MESSAGE_EXPIRY_INTERVAL: u8 = 0x02;
+pub const CONTENT_TYPE: u8 = 0x03;
+pub const RESPONSE_TOPIC: u8 = 0x08;
+pub const CORRELATION_DATA: u8 = 0x09;
+pub const SUBSCRIPTION_IDENTIFIER: u8 = 0x0B;
+pub const SESSION_EXPIRY_INTERVAL: u8 = 0x11;
+pub const ASSIGNED_CLIENT_IDENTIFIER: u8 = 0x12;
+pub const SERVER_KEEP_ALIVE: u8 = 0x13;
+pub const AUTHENTICATION_METHOD: u8 = 0x15;
+pub const AUTHENTICATION_DATA: u8 = 0x16;
+pub const REQUEST_PROBLEM_INFORMATION: u8 = 0x17;
+pub const WILL_DELAY_INTERVAL: u8 = 0x18;
+pub const REQUEST_RESPONSE_INFORMATION: u8 = 0x19;
+pub const RESPONSE_INFORMATION: u8 = 0x1A;
+pub const SERVER_REFERENCE: u8 = 0x1C;
+pub const REASON_STRING: u8 = 0x1F;
+pub const RECEIVE_MAXIMUM: u8 = 0x21;
+pub const TOPIC_ALIAS_MAXIMUM: u8 = 0x22;
+pub const TOPIC_ALIAS: u8 = 0x23;
+pub const MAXIMUM_QOS: u8 = 0x24;
+pub const RETAIN_AVAILABLE: u8 = 0x25;
+pub const USER_PROPERTY: u8 = 0x26;
+pub const MAXIMUM_PACKET_SIZE: u8 = 0x27;
+pub const WILDCARD_SUBSCRIPTION_AVAILABLE: u8 = 0x28;
+pub const SUBSCRIPTION_IDENTIFIER_AVAILABLE: u8 = 0x29;
+pub const SHARED_SUBSCRIPTION_AVAILABLE: u8 = 0x2A;
+
+/// Returns whether the protocol level sent in the CONNECT packet is supported (MQTT 3.1.1 or 5).
+pub fn is_protocol_level_supported(protocol_level: u8) -> bool {
+    protocol_level == PROTOCOL_LEVEL_3_1_1 || protocol_level == PROTOCOL_LEVEL_5
+}
+
+/// Maps the Iggy error onto the closest MQTT 5 reason code.
+pub fn map_error(error: &IggyError) -> u8 {
+    match error {
+        IggyError::StreamIdNotFound(_)
+        | IggyError::StreamNameNotFound(_)
+        | IggyError::TopicIdNotFound(_, _)
+        | IggyError::TopicNameNotFound(_, _)
+        | IggyError::PartitionNotFound(_, _, _)
+        | IggyError::NoPartitions(_, _)
+        | IggyError::ResourceNotFound(_) => TOPIC_NAME_INVALID,
+        IggyError::Unauthorized | IggyError::Unauthenticated => NOT_AUTHORIZED,
+        IggyError::InvalidMessagePayloadLength
+        | IggyError::TooBigMessagePayload
+        | IggyError::TooBigUserHeaders
+        | IggyError::InvalidHeaderKey
+        | IggyError::InvalidHeaderValue => PAYLOAD_FORMAT_INVALID,
+        _ => UNSPECIFIED_ERROR,
+    }
+}
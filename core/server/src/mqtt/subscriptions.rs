@@ -0,0 +1,389 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::configs::mqtt::MqttTopicMapping;
+use crate::mqtt::COMPONENT;
+use crate::mqtt::messages::{get_topic_name, to_publish};
+use crate::mqtt::protocol::{MAX_SUPPORTED_QOS, QOS_AT_LEAST_ONCE};
+use crate::mqtt::topics::{matches_filter, resolve_subscription_topics};
+use crate::streaming::session::Session;
+use crate::streaming::systems::messages::PollingArgs;
+use crate::streaming::systems::system::SharedSystem;
+use ahash::AHashMap;
+use bytes::Bytes;
+use error_set::ErrContext;
+use iggy_common::locking::IggySharedMutFn;
+use iggy_common::{Consumer, Identifier, IggyError, IggyMessage, PollingStrategy};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore, mpsc};
+use tokio::task::JoinHandle;
+use tracing::{debug, warn};
+
+const POLL_MESSAGES_COUNT: u32 = 100;
+/// Interval at which the idle subscription polls the partitions again, even if no new messages were appended,
+/// e.g. to pick up the partitions created in the meantime.
+const IDLE_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The QoS 1 messages sent to the client and not acknowledged yet, limited by the receive maximum of the client.
+#[derive(Debug)]
+pub struct InflightMessages {
+    permits: Arc<Semaphore>,
+    state: Mutex<InflightState>,
+}
+
+#[derive(Debug)]
+struct InflightState {
+    next_packet_id: u16,
+    pending: AHashMap<u16, OwnedSemaphorePermit>,
+}
+
+impl InflightMessages {
+    pub fn new(receive_maximum: u16) -> Self {
+        Self {
+            permits: Arc::new(Semaphore::new(receive_maximum as usize)),
+            state: Mutex::new(InflightState {
+                next_packet_id: 1,
+                pending: AHashMap::new(),
+            }),
+        }
+    }
+
+    /// Waits until another message can be sent, and returns its packet identifier.
+    pub async fn acquire(&self) -> Result<u16, IggyError> {
+        let permit = self
+            .permits
+            .clone()
+            .acquire_owned()
+            .await
+            .map_err(|_| IggyError::Disconnected)?;
+        let mut state = self
+            .state
+            .lock()
+            .expect("Failed to lock MQTT inflight messages");
+        // There are at most 65535 permits, so there's always an identifier not used by the pending messages.
+        loop {
+            let packet_id = state.next_packet_id;
+            state.next_packet_id = state.next_packet_id.checked_add(1).unwrap_or(1);
+            if !state.pending.contains_key(&packet_id) {
+                state.pending.insert(packet_id, permit);
+                return Ok(packet_id);
+            }
+        }
+    }
+
+    /// Releases the packet identifier once the message has been acknowledged, returns whether it was pending.
+    pub fn complete(&self, packet_id: u16) -> bool {
+        self.state
+            .lock()
+            .expect("Failed to lock MQTT inflight messages")
+            .pending
+            .remove(&packet_id)
+            .is_some()
+    }
+
+    fn close(&self) {
+        self.permits.close();
+    }
+}
+
+/// The connected client receiving the messages matched by its subscriptions.
+#[derive(Debug)]
+pub struct Subscriber {
+    pub protocol_level: u8,
+    /// The max size of the packet accepted by the client, the larger messages are not sent at all.
+    pub max_packet_size: usize,
+    pub inflight: InflightMessages,
+    pub outgoing: mpsc::Sender<Bytes>,
+}
+
+/// The Iggy topic matched by the subscription, along with the offsets of the messages to be sent next.
+#[derive(Debug)]
+struct SubscribedTopic {
+    stream_id: Identifier,
+    topic_id: Identifier,
+    stream_name: String,
+    topic_name: String,
+    next_offsets: AHashMap<u32, u64>,
+}
+
+/// Topic filters subscribed by a single MQTT connection.
+///
+/// The subscription receives the messages appended after it was created, to all the existing Iggy topics
+/// which might contain the messages matched by the filter. Every Iggy topic is served by its own task,
+/// which polls all the partitions whenever new messages are appended and sends the matching ones to the client.
+/// All the tasks are aborted once the subscriptions are dropped (the connection is closed).
+#[derive(Debug)]
+pub struct MqttSubscriptions {
+    session: Arc<Session>,
+    system: SharedSystem,
+    mappings: Arc<Vec<MqttTopicMapping>>,
+    subscriber: Arc<Subscriber>,
+    tasks: AHashMap<String, Vec<JoinHandle<()>>>,
+}
+
+impl MqttSubscriptions {
+    pub fn new(
+        session: Arc<Session>,
+        system: SharedSystem,
+        mappings: Arc<Vec<MqttTopicMapping>>,
+        subscriber: Arc<Subscriber>,
+    ) -> Self {
+        Self {
+            session,
+            system,
+            mappings,
+            subscriber,
+            tasks: AHashMap::new(),
+        }
+    }
+
+    pub fn subscriber(&self) -> &Subscriber {
+        &self.subscriber
+    }
+
+    /// Subscribes to the topic filter (replacing the existing subscription, if any) and returns the granted QoS.
+    pub async fn subscribe(&mut self, filter: &str, qos: u8) -> Result<u8, IggyError> {
+        let topics = self.resolve_topics(filter).await.with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to subscribe to topic filter: {filter}, session: {}",
+                self.session
+            )
+        })?;
+        if topics.is_empty() {
+            return Err(IggyError::ResourceNotFound(format!(
+                "Iggy topic for MQTT topic filter: {filter}"
+            )));
+        }
+
+        let granted_qos = qos.min(MAX_SUPPORTED_QOS);
+        let tasks = topics
+            .into_iter()
+            .map(|topic| {
+                tokio::spawn(serve_subscription(
+                    filter.to_owned(),
+                    granted_qos,
+                    topic,
+                    self.session.clone(),
+                    self.system.clone(),
+                    self.subscriber.clone(),
+                ))
+            })
+            .collect();
+        if let Some(tasks) = self.tasks.insert(filter.to_owned(), tasks) {
+            tasks.iter().for_each(JoinHandle::abort);
+        }
+        debug!(
+            "Added MQTT subscription to topic filter: {filter} with QoS: {granted_qos} for session: {}",
+            self.session
+        );
+        Ok(granted_qos)
+    }
+
+    /// Removes the subscription to the topic filter, returns whether it existed.
+    pub fn unsubscribe(&mut self, filter: &str) -> bool {
+        let Some(tasks) = self.tasks.remove(filter) else {
+            return false;
+        };
+
+        tasks.iter().for_each(JoinHandle::abort);
+        debug!(
+            "Removed MQTT subscription to topic filter: {filter} for session: {}",
+            self.session
+        );
+        true
+    }
+
+    async fn resolve_topics(&self, filter: &str) -> Result<Vec<SubscribedTopic>, IggyError> {
+        let system = self.system.read().await;
+        let mut topics = Vec::new();
+        for name in resolve_subscription_topics(&self.mappings, filter) {
+            let (Ok(stream_id), Ok(topic_id)) = (
+                Identifier::named(&name.stream),
+                Identifier::named(&name.topic),
+            ) else {
+                continue;
+            };
+
+            let Some(topic) = system.try_find_topic(&self.session, &stream_id, &topic_id)? else {
+                continue;
+            };
+
+            system.permissioner.poll_messages(
+                self.session.get_user_id(),
+                topic.stream_id,
+                topic.topic_id,
+            )?;
+            let mut next_offsets = AHashMap::new();
+            for (partition_id, partition) in &topic.partitions {
+                let partition = partition.read().await;
+                if partition.should_increment_offset {
+                    next_offsets.insert(*partition_id, partition.current_offset + 1);
+                }
+            }
+            topics.push(SubscribedTopic {
+                stream_id: Identifier::numeric(topic.stream_id)?,
+                topic_id: Identifier::numeric(topic.topic_id)?,
+                stream_name: name.stream,
+                topic_name: name.topic,
+                next_offsets,
+            });
+        }
+        Ok(topics)
+    }
+}
+
+impl Drop for MqttSubscriptions {
+    fn drop(&mut self) {
+        self.subscriber.inflight.close();
+        for task in self.tasks.values().flatten() {
+            task.abort();
+        }
+    }
+}
+
+async fn serve_subscription(
+    filter: String,
+    qos: u8,
+    mut topic: SubscribedTopic,
+    session: Arc<Session>,
+    system: SharedSystem,
+    subscriber: Arc<Subscriber>,
+) {
+    let notifier =
+        system
+            .read()
+            .await
+            .get_new_messages_notifier(&session, &topic.stream_id, &topic.topic_id);
+    let notifier = match notifier {
+        Ok(notifier) => notifier,
+        Err(error) => {
+            warn!("MQTT subscription to topic filter: {filter} has failed, error: {error}");
+            return;
+        }
+    };
+
+    loop {
+        // Register the interest before polling, so that the messages appended in between are not missed.
+        let notified = notifier.notified();
+        tokio::pin!(notified);
+        notified.as_mut().enable();
+
+        let messages = match poll_new_messages(&session, &system, &mut topic).await {
+            Ok(messages) => messages,
+            Err(error) => {
+                warn!(
+                    "MQTT subscription to topic filter: {filter} has failed, session: {session}, error: {error}"
+                );
+                return;
+            }
+        };
+
+        if messages.is_empty() {
+            tokio::select! {
+                _ = notified => {}
+                _ = tokio::time::sleep(IDLE_POLL_INTERVAL) => {}
+                _ = subscriber.outgoing.closed() => return,
+            }
+            continue;
+        }
+
+        for message in messages {
+            let name = get_topic_name(&message, &topic.stream_name, &topic.topic_name);
+            if !matches_filter(&filter, &name) {
+                continue;
+            }
+
+            if deliver(&subscriber, &message, name, qos).await.is_err() {
+                debug!("MQTT connection for subscription to topic filter: {filter} is closed.");
+                return;
+            }
+        }
+    }
+}
+
+async fn poll_new_messages(
+    session: &Session,
+    system: &SharedSystem,
+    topic: &mut SubscribedTopic,
+) -> Result<Vec<IggyMessage>, IggyError> {
+    let system = system.read().await;
+    let mut partition_ids = system
+        .find_topic(session, &topic.stream_id, &topic.topic_id)?
+        .partitions
+        .keys()
+        .copied()
+        .collect::<Vec<_>>();
+    partition_ids.sort_unstable();
+
+    let consumer = Consumer::default();
+    let mut messages = Vec::new();
+    for partition_id in partition_ids {
+        let offset = topic.next_offsets.get(&partition_id).copied().unwrap_or(0);
+        let (metadata, batches) = system
+            .poll_messages(
+                session,
+                &consumer,
+                &topic.stream_id,
+                &topic.topic_id,
+                Some(partition_id),
+                PollingArgs::new(PollingStrategy::offset(offset), POLL_MESSAGES_COUNT, false),
+            )
+            .await?;
+        let polled_messages = batches.into_polled_messages(metadata);
+        if let Some(message) = polled_messages.messages.last() {
+            topic
+                .next_offsets
+                .insert(partition_id, message.header.offset + 1);
+        }
+        messages.extend(polled_messages.messages);
+    }
+    Ok(messages)
+}
+
+async fn deliver(
+    subscriber: &Subscriber,
+    message: &IggyMessage,
+    topic: String,
+    qos: u8,
+) -> Result<(), IggyError> {
+    let packet_id = if qos == QOS_AT_LEAST_ONCE {
+        Some(subscriber.inflight.acquire().await?)
+    } else {
+        None
+    };
+
+    let protocol_level = subscriber.protocol_level;
+    let packet = to_publish(message, topic, qos, packet_id, protocol_level).encode(protocol_level);
+    if packet.len() > subscriber.max_packet_size {
+        debug!(
+            "MQTT message with offset: {} exceeds the max packet size of the client and won't be sent.",
+            message.header.offset
+        );
+        if let Some(packet_id) = packet_id {
+            subscriber.inflight.complete(packet_id);
+        }
+        return Ok(());
+    }
+
+    subscriber
+        .outgoing
+        .send(packet)
+        .await
+        .map_err(|_| IggyError::Disconnected)
+}
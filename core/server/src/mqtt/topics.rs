@@ -0,0 +1,272 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::configs::mqtt::MqttTopicMapping;
+
+const LEVEL_SEPARATOR: char = '/';
+const SINGLE_LEVEL_WILDCARD: &str = "+";
+const MULTI_LEVEL_WILDCARD: &str = "#";
+const WILDCARDS: [char; 2] = ['+', '#'];
+/// The prefix of the shared subscriptions (MQTT 5), which aren't supported.
+pub const SHARED_SUBSCRIPTION_PREFIX: &str = "$share/";
+
+/// The names of the stream and the topic in Iggy, onto which the MQTT topic is mapped.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IggyTopicName {
+    pub stream: String,
+    pub topic: String,
+}
+
+impl IggyTopicName {
+    fn new(stream: &str, topic: &str) -> Self {
+        Self {
+            stream: stream.to_owned(),
+            topic: topic.to_owned(),
+        }
+    }
+}
+
+/// The topic name used to publish the message must not contain the wildcards.
+pub fn is_valid_topic_name(name: &str) -> bool {
+    !name.is_empty() && name.len() <= u16::MAX as usize && !name.contains(WILDCARDS)
+}
+
+/// The wildcards of the topic filter must occupy the entire level,
+/// and the multi-level wildcard is allowed only as the last level.
+pub fn is_valid_topic_filter(filter: &str) -> bool {
+    if filter.is_empty() || filter.len() > u16::MAX as usize {
+        return false;
+    }
+
+    let mut levels = filter.split(LEVEL_SEPARATOR).peekable();
+    while let Some(level) = levels.next() {
+        if level == MULTI_LEVEL_WILDCARD {
+            return levels.peek().is_none();
+        }
+
+        if level != SINGLE_LEVEL_WILDCARD && level.contains(WILDCARDS) {
+            return false;
+        }
+    }
+
+    true
+}
+
+/// Returns whether the topic name matches the topic filter. The topic names starting with `$`
+/// are not matched by the filters starting with a wildcard, as required by the specification.
+pub fn matches_filter(filter: &str, name: &str) -> bool {
+    if name.starts_with('$') && filter.starts_with(WILDCARDS) {
+        return false;
+    }
+
+    let mut name_levels = name.split(LEVEL_SEPARATOR);
+    for filter_level in filter.split(LEVEL_SEPARATOR) {
+        if filter_level == MULTI_LEVEL_WILDCARD {
+            return true;
+        }
+
+        match name_levels.next() {
+            Some(name_level)
+                if filter_level == SINGLE_LEVEL_WILDCARD || filter_level == name_level => {}
+            _ => return false,
+        }
+    }
+
+    name_levels.next().is_none()
+}
+
+/// Returns whether there's any topic name matched by both of the topic filters.
+pub fn filters_intersect(first: &str, second: &str) -> bool {
+    let mut first_levels = first.split(LEVEL_SEPARATOR);
+    let mut second_levels = second.split(LEVEL_SEPARATOR);
+    loop {
+        match (first_levels.next(), second_levels.next()) {
+            (Some(MULTI_LEVEL_WILDCARD), _) | (_, Some(MULTI_LEVEL_WILDCARD)) | (None, None) => {
+                return true;
+            }
+            (Some(first_level), Some(second_level)) => {
+                if first_level != SINGLE_LEVEL_WILDCARD
+                    && second_level != SINGLE_LEVEL_WILDCARD
+                    && first_level != second_level
+                {
+                    return false;
+                }
+            }
+            _ => return false,
+        }
+    }
+}
+
+/// Returns the Iggy topic onto which the MQTT topic is mapped by the first matching mapping,
+/// or otherwise by its first two levels (the names of the stream and the topic), if there are any.
+pub fn resolve_publish_topic(mappings: &[MqttTopicMapping], name: &str) -> Option<IggyTopicName> {
+    if let Some(mapping) = mappings
+        .iter()
+        .find(|mapping| matches_filter(&mapping.filter, name))
+    {
+        return Some(IggyTopicName::new(&mapping.stream, &mapping.topic));
+    }
+
+    let mut levels = name.split(LEVEL_SEPARATOR);
+    match (levels.next(), levels.next()) {
+        (Some(stream), Some(topic)) if !stream.is_empty() && !topic.is_empty() => {
+            Some(IggyTopicName::new(stream, topic))
+        }
+        _ => None,
+    }
+}
+
+/// Returns the Iggy topics which might contain the messages matched by the topic filter, i.e. the topics
+/// of all the mappings intersecting with the filter, and the topic named after the first two levels of the filter,
+/// unless they contain the wildcards.
+pub fn resolve_subscription_topics(
+    mappings: &[MqttTopicMapping],
+    filter: &str,
+) -> Vec<IggyTopicName> {
+    let mut topics: Vec<IggyTopicName> = Vec::new();
+    let mut add_topic = |topic: IggyTopicName| {
+        if !topics.contains(&topic) {
+            topics.push(topic);
+        }
+    };
+
+    for mapping in mappings {
+        if filters_intersect(&mapping.filter, filter) {
+            add_topic(IggyTopicName::new(&mapping.stream, &mapping.topic));
+        }
+    }
+
+    let mut levels = filter.split(LEVEL_SEPARATOR);
+    if let (Some(stream), Some(topic)) = (levels.next(), levels.next()) {
+        let is_literal = |level: &str| {
+            !level.is_empty() && level != SINGLE_LEVEL_WILDCARD && level != MULTI_LEVEL_WILDCARD
+        };
+        if is_literal(stream) && is_literal(topic) {
+            add_topic(IggyTopicName::new(stream, topic));
+        }
+    }
+
+    topics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mappings() -> Vec<MqttTopicMapping> {
+        vec![
+            "devices/+/telemetry=iot/telemetry".parse().unwrap(),
+            "devices/#=iot/events".parse().unwrap(),
+        ]
+    }
+
+    #[test]
+    fn topic_filters_should_be_validated() {
+        for filter in ["a", "a/b", "+", "#", "a/+/c", "a/#", "+/+", "/", "a//b"] {
+            assert!(is_valid_topic_filter(filter), "{filter}");
+        }
+        for filter in ["", "a/#/c", "a+", "a/b#", "#/a"] {
+            assert!(!is_valid_topic_filter(filter), "{filter}");
+        }
+        assert!(is_valid_topic_name("a/b"));
+        assert!(!is_valid_topic_name("a/+"));
+        assert!(!is_valid_topic_name(""));
+    }
+
+    #[test]
+    fn topic_names_should_match_filters() {
+        assert!(matches_filter("a/b", "a/b"));
+        assert!(matches_filter("a/+", "a/b"));
+        assert!(matches_filter("a/#", "a"));
+        assert!(matches_filter("a/#", "a/b/c"));
+        assert!(matches_filter("#", "a/b"));
+        assert!(matches_filter("+/+", "/b"));
+        assert!(!matches_filter("a/+", "a/b/c"));
+        assert!(!matches_filter("a/b", "a"));
+        assert!(!matches_filter("a/b/c", "a/b"));
+        assert!(!matches_filter("#", "$SYS/uptime"));
+        assert!(matches_filter("$SYS/#", "$SYS/uptime"));
+    }
+
+    #[test]
+    fn topic_filters_should_intersect() {
+        assert!(filters_intersect("devices/+/telemetry", "devices/1/+"));
+        assert!(filters_intersect("devices/#", "devices"));
+        assert!(filters_intersect("#", "a/b"));
+        assert!(!filters_intersect(
+            "devices/+/telemetry",
+            "devices/1/status"
+        ));
+        assert!(!filters_intersect("devices/+", "devices/1/telemetry"));
+    }
+
+    #[test]
+    fn published_topics_should_be_resolved() {
+        let mappings = mappings();
+        assert_eq!(
+            resolve_publish_topic(&mappings, "devices/1/telemetry"),
+            Some(IggyTopicName::new("iot", "telemetry"))
+        );
+        assert_eq!(
+            resolve_publish_topic(&mappings, "devices/1/status"),
+            Some(IggyTopicName::new("iot", "events"))
+        );
+        assert_eq!(
+            resolve_publish_topic(&mappings, "orders/created/eu"),
+            Some(IggyTopicName::new("orders", "created"))
+        );
+        assert_eq!(resolve_publish_topic(&mappings, "orders"), None);
+        assert_eq!(resolve_publish_topic(&mappings, "/orders"), None);
+    }
+
+    #[test]
+    fn subscription_topics_should_be_resolved() {
+        let mappings = mappings();
+        assert_eq!(
+            resolve_subscription_topics(&mappings, "devices/1/+"),
+            vec![
+                IggyTopicName::new("iot", "telemetry"),
+                IggyTopicName::new("iot", "events"),
+                IggyTopicName::new("devices", "1"),
+            ]
+        );
+        assert_eq!(
+            resolve_subscription_topics(&mappings, "orders/created/#"),
+            vec![IggyTopicName::new("orders", "created")]
+        );
+        assert!(resolve_subscription_topics(&mappings, "orders/+").is_empty());
+    }
+
+    #[test]
+    fn topic_mappings_should_be_parsed() {
+        let mapping: MqttTopicMapping = " devices/+/telemetry = iot/telemetry ".parse().unwrap();
+        assert_eq!(mapping.filter, "devices/+/telemetry");
+        assert_eq!(mapping.stream, "iot");
+        assert_eq!(mapping.topic, "telemetry");
+        assert_eq!(mapping.to_string(), "devices/+/telemetry=iot/telemetry");
+        for mapping in [
+            "devices/#",
+            "devices/#=iot",
+            "devices/#/x=iot/t",
+            "=iot/t",
+            "a=/t",
+        ] {
+            assert!(mapping.parse::<MqttTopicMapping>().is_err(), "{mapping}");
+        }
+    }
+}
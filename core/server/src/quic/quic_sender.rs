@@ -22,6 +22,8 @@ use error_set::ErrContext;
 use iggy_common::IggyError;
 use quinn::{RecvStream, SendStream};
 use std::io::IoSlice;
+use tokio::fs::File;
+use tokio::io::AsyncReadExt;
 use tracing::{debug, error};
 
 const STATUS_OK: &[u8] = &[0; 4];
@@ -52,6 +54,41 @@ impl Sender for QuicSender {
         self.send_response(STATUS_OK, payload).await
     }
 
+    async fn send_ok_response_from_file(
+        &mut self,
+        file: &mut File,
+        length: u32,
+    ) -> Result<(), IggyError> {
+        debug!("Sending response of len: {length} from file with status: {STATUS_OK:?}...");
+        self.send
+            .write_all(&[STATUS_OK, &length.to_le_bytes()].concat())
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to write headers to stream")
+            })
+            .map_err(|_| IggyError::QuicError)?;
+        let sent_bytes = tokio::io::copy(&mut file.take(u64::from(length)), &mut self.send)
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to write file to stream")
+            })
+            .map_err(|_| IggyError::QuicError)?;
+        if sent_bytes != u64::from(length) {
+            error!(
+                "Failed to send response from file, expected: {length} bytes, sent: {sent_bytes}"
+            );
+            return Err(IggyError::CannotReadFile);
+        }
+        self.send
+            .finish()
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to finish send stream")
+            })
+            .map_err(|_| IggyError::QuicError)?;
+        debug!("Sent response from file with status: {STATUS_OK:?}");
+        Ok(())
+    }
+
     async fn send_error_response(&mut self, error: IggyError) -> Result<(), IggyError> {
         self.send_response(&error.as_code().to_le_bytes(), &[])
             .await
@@ -18,7 +18,7 @@
 
 use crate::state::models::{
     CreateConsumerGroupWithId, CreatePersonalAccessTokenWithHash, CreateStreamWithId,
-    CreateTopicWithId, CreateUserWithId,
+    CreateTopicWithId, CreateUserWithId, DeleteDrainedPartitions,
 };
 use bytes::{Buf, BufMut, Bytes, BytesMut};
 use iggy_common::BytesSerializable;
@@ -32,8 +32,10 @@ use iggy_common::delete_segments::DeleteSegments;
 use iggy_common::delete_stream::DeleteStream;
 use iggy_common::delete_topic::DeleteTopic;
 use iggy_common::delete_user::DeleteUser;
+use iggy_common::move_partition::MovePartition;
 use iggy_common::purge_stream::PurgeStream;
 use iggy_common::purge_topic::PurgeTopic;
+use iggy_common::shrink_partitions::ShrinkPartitions;
 use iggy_common::update_permissions::UpdatePermissions;
 use iggy_common::update_stream::UpdateStream;
 use iggy_common::update_topic::UpdateTopic;
@@ -41,8 +43,9 @@ use iggy_common::update_user::UpdateUser;
 use iggy_common::{
     CHANGE_PASSWORD_CODE, CREATE_CONSUMER_GROUP_CODE, CREATE_PARTITIONS_CODE,
     CREATE_PERSONAL_ACCESS_TOKEN_CODE, CREATE_STREAM_CODE, CREATE_TOPIC_CODE, CREATE_USER_CODE,
-    Command, DELETE_CONSUMER_GROUP_CODE, DELETE_PARTITIONS_CODE, DELETE_PERSONAL_ACCESS_TOKEN_CODE,
-    DELETE_STREAM_CODE, DELETE_TOPIC_CODE, DELETE_USER_CODE, PURGE_STREAM_CODE, PURGE_TOPIC_CODE,
+    Command, DELETE_CONSUMER_GROUP_CODE, DELETE_DRAINED_PARTITIONS_CODE, DELETE_PARTITIONS_CODE,
+    DELETE_PERSONAL_ACCESS_TOKEN_CODE, DELETE_STREAM_CODE, DELETE_TOPIC_CODE, DELETE_USER_CODE,
+    MOVE_PARTITION_CODE, PURGE_STREAM_CODE, PURGE_TOPIC_CODE, SHRINK_PARTITIONS_CODE,
     UPDATE_PERMISSIONS_CODE, UPDATE_STREAM_CODE, UPDATE_TOPIC_CODE, UPDATE_USER_CODE,
 };
 use std::fmt::{Display, Formatter};
@@ -59,6 +62,9 @@ pub enum EntryCommand {
     PurgeTopic(PurgeTopic),
     CreatePartitions(CreatePartitions),
     DeletePartitions(DeletePartitions),
+    MovePartition(MovePartition),
+    ShrinkPartitions(ShrinkPartitions),
+    DeleteDrainedPartitions(DeleteDrainedPartitions),
     DeleteSegments(DeleteSegments),
     CreateConsumerGroup(CreateConsumerGroupWithId),
     DeleteConsumerGroup(DeleteConsumerGroup),
@@ -84,6 +90,9 @@ impl BytesSerializable for EntryCommand {
             EntryCommand::PurgeTopic(command) => (command.code(), command.to_bytes()),
             EntryCommand::CreatePartitions(command) => (command.code(), command.to_bytes()),
             EntryCommand::DeletePartitions(command) => (command.code(), command.to_bytes()),
+            EntryCommand::MovePartition(command) => (command.code(), command.to_bytes()),
+            EntryCommand::ShrinkPartitions(command) => (command.code(), command.to_bytes()),
+            EntryCommand::DeleteDrainedPartitions(command) => (command.code(), command.to_bytes()),
             EntryCommand::DeleteSegments(command) => (command.code(), command.to_bytes()),
             EntryCommand::CreateConsumerGroup(command) => (command.code(), command.to_bytes()),
             EntryCommand::DeleteConsumerGroup(command) => (command.code(), command.to_bytes()),
@@ -137,6 +146,15 @@ impl BytesSerializable for EntryCommand {
             DELETE_PARTITIONS_CODE => Ok(EntryCommand::DeletePartitions(
                 DeletePartitions::from_bytes(payload)?,
             )),
+            MOVE_PARTITION_CODE => Ok(EntryCommand::MovePartition(MovePartition::from_bytes(
+                payload,
+            )?)),
+            SHRINK_PARTITIONS_CODE => Ok(EntryCommand::ShrinkPartitions(
+                ShrinkPartitions::from_bytes(payload)?,
+            )),
+            DELETE_DRAINED_PARTITIONS_CODE => Ok(EntryCommand::DeleteDrainedPartitions(
+                DeleteDrainedPartitions::from_bytes(payload)?,
+            )),
             CREATE_CONSUMER_GROUP_CODE => Ok(EntryCommand::CreateConsumerGroup(
                 CreateConsumerGroupWithId::from_bytes(payload)?,
             )),
@@ -178,6 +196,11 @@ impl Display for EntryCommand {
             EntryCommand::PurgeTopic(command) => write!(f, "PurgeTopic({command})"),
             EntryCommand::CreatePartitions(command) => write!(f, "CreatePartitions({command})"),
             EntryCommand::DeletePartitions(command) => write!(f, "DeletePartitions({command})"),
+            EntryCommand::MovePartition(command) => write!(f, "MovePartition({command})"),
+            EntryCommand::ShrinkPartitions(command) => write!(f, "ShrinkPartitions({command})"),
+            EntryCommand::DeleteDrainedPartitions(command) => {
+                write!(f, "DeleteDrainedPartitions({command})")
+            }
             EntryCommand::DeleteSegments(command) => write!(f, "DeleteSegments({command})"),
             EntryCommand::CreateConsumerGroup(command) => {
                 write!(f, "CreateConsumerGroup({command})")
@@ -21,6 +21,7 @@ use bytes::{BufMut, Bytes, BytesMut};
 use error_set::ErrContext;
 use iggy_common::BytesSerializable;
 use iggy_common::Command;
+use iggy_common::DELETE_DRAINED_PARTITIONS_CODE;
 use iggy_common::IggyError;
 use iggy_common::Validatable;
 use iggy_common::create_consumer_group::CreateConsumerGroup;
@@ -28,6 +29,7 @@ use iggy_common::create_personal_access_token::CreatePersonalAccessToken;
 use iggy_common::create_stream::CreateStream;
 use iggy_common::create_topic::CreateTopic;
 use iggy_common::create_user::CreateUser;
+use iggy_common::delete_partitions::DeletePartitions;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::fmt::{Display, Formatter};
@@ -63,6 +65,15 @@ pub struct CreatePersonalAccessTokenWithHash {
     pub command: CreatePersonalAccessToken,
 }
 
+/// Deletes the draining partitions once they have been consumed. Unlike the `DeletePartitions` issued by the clients,
+/// it also reduces the number of the draining partitions of the topic. The IDs of the deleted partitions are stored,
+/// so that applying the same entry more than once (e.g. when the deletion was retried) removes only the existing ones.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct DeleteDrainedPartitions {
+    pub command: DeletePartitions,
+    pub partition_ids: Vec<u32>,
+}
+
 impl Validatable<IggyError> for CreateStreamWithId {
     fn validate(&self) -> Result<(), IggyError> {
         self.command.validate()
@@ -123,6 +134,18 @@ impl Command for CreatePersonalAccessTokenWithHash {
     }
 }
 
+impl Validatable<IggyError> for DeleteDrainedPartitions {
+    fn validate(&self) -> Result<(), IggyError> {
+        self.command.validate()
+    }
+}
+
+impl Command for DeleteDrainedPartitions {
+    fn code(&self) -> u32 {
+        DELETE_DRAINED_PARTITIONS_CODE
+    }
+}
+
 impl Display for CreateStreamWithId {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         write!(
@@ -173,6 +196,16 @@ impl Display for CreatePersonalAccessTokenWithHash {
     }
 }
 
+impl Display for DeleteDrainedPartitions {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "DeleteDrainedPartitions {{ command: {}, partition_ids: {:?} }}",
+            self.command, self.partition_ids
+        )
+    }
+}
+
 impl BytesSerializable for CreateStreamWithId {
     fn to_bytes(&self) -> Bytes {
         let mut bytes = BytesMut::new();
@@ -387,3 +420,65 @@ impl BytesSerializable for CreatePersonalAccessTokenWithHash {
         Ok(Self { hash, command })
     }
 }
+
+impl BytesSerializable for DeleteDrainedPartitions {
+    fn to_bytes(&self) -> Bytes {
+        let command_bytes = self.command.to_bytes();
+        let mut bytes =
+            BytesMut::with_capacity(8 + command_bytes.len() + 4 * self.partition_ids.len());
+        bytes.put_u32_le(command_bytes.len() as u32);
+        bytes.put_slice(&command_bytes);
+        bytes.put_u32_le(self.partition_ids.len() as u32);
+        for partition_id in &self.partition_ids {
+            bytes.put_u32_le(*partition_id);
+        }
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<Self, IggyError>
+    where
+        Self: Sized,
+    {
+        if bytes.len() < 4 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let mut position = 0;
+        let command_length = u32::from_le_bytes(
+            bytes[position..position + 4]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        ) as usize;
+        position += 4;
+        if bytes.len() < position + command_length + 4 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let command =
+            DeletePartitions::from_bytes(bytes.slice(position..position + command_length))
+                .with_error_context(|error| {
+                    format!(
+                        "{COMPONENT} (error: {error}) - failed to parse delete partitions command"
+                    )
+                })?;
+        position += command_length;
+        let partitions_count = u32::from_le_bytes(
+            bytes[position..position + 4]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        ) as usize;
+        position += 4;
+        if bytes.len() != position + 4 * partitions_count {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let partition_ids = bytes[position..]
+            .chunks_exact(4)
+            .map(|chunk| u32::from_le_bytes(chunk.try_into().unwrap()))
+            .collect();
+        Ok(Self {
+            command,
+            partition_ids,
+        })
+    }
+}
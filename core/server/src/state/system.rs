@@ -16,6 +16,7 @@
  * under the License.
  */
 
+use crate::state::models::DeleteDrainedPartitions;
 use crate::state::{COMPONENT, EntryCommand, StateEntry};
 use crate::streaming::personal_access_tokens::personal_access_token::PersonalAccessToken;
 use ahash::AHashMap;
@@ -25,6 +26,7 @@ use iggy_common::IggyError;
 use iggy_common::IggyExpiry;
 use iggy_common::IggyTimestamp;
 use iggy_common::MaxTopicSize;
+use iggy_common::TopicOverrides;
 use iggy_common::{IdKind, Identifier, Permissions, UserStatus};
 use std::fmt::Display;
 use tracing::{debug, info};
@@ -48,17 +50,20 @@ pub struct TopicState {
     pub id: u32,
     pub name: String,
     pub partitions: AHashMap<u32, PartitionState>,
+    pub draining_partitions_count: u32,
     pub consumer_groups: AHashMap<u32, ConsumerGroupState>,
     pub compression_algorithm: CompressionAlgorithm,
     pub message_expiry: IggyExpiry,
     pub max_topic_size: MaxTopicSize,
     pub replication_factor: Option<u8>,
+    pub overrides: TopicOverrides,
     pub created_at: IggyTimestamp,
 }
 
 #[derive(Debug)]
 pub struct PartitionState {
     pub id: u32,
+    pub directory: Option<String>,
     pub created_at: IggyTimestamp,
 }
 
@@ -137,11 +142,13 @@ impl SystemState {
                     let topic = TopicState {
                         id: topic_id,
                         name: command.name,
+                        draining_partitions_count: 0,
                         consumer_groups: AHashMap::new(),
                         compression_algorithm: command.compression_algorithm,
                         message_expiry: command.message_expiry,
                         max_topic_size: command.max_topic_size,
                         replication_factor: command.replication_factor,
+                        overrides: command.overrides,
                         created_at: entry.timestamp,
                         partitions: if command.partitions_count > 0 {
                             let mut partitions = AHashMap::new();
@@ -150,6 +157,7 @@ impl SystemState {
                                     i,
                                     PartitionState {
                                         id: i,
+                                        directory: None,
                                         created_at: entry.timestamp,
                                     },
                                 );
@@ -176,6 +184,9 @@ impl SystemState {
                     topic.message_expiry = command.message_expiry;
                     topic.max_topic_size = command.max_topic_size;
                     topic.replication_factor = command.replication_factor;
+                    if let Some(overrides) = command.overrides {
+                        topic.overrides = overrides;
+                    }
                 }
                 EntryCommand::DeleteTopic(command) => {
                     let stream_id = find_stream_id(&streams, &command.stream_id);
@@ -222,6 +233,7 @@ impl SystemState {
                             last_partition_id + i,
                             PartitionState {
                                 id: last_partition_id + i,
+                                directory: None,
                                 created_at: entry.timestamp,
                             },
                         );
@@ -251,6 +263,65 @@ impl SystemState {
                         topic.partitions.remove(&(last_partition_id - i));
                     }
                 }
+                EntryCommand::DeleteDrainedPartitions(DeleteDrainedPartitions {
+                    command,
+                    partition_ids,
+                }) => {
+                    let stream_id = find_stream_id(&streams, &command.stream_id);
+                    let stream = streams
+                        .get_mut(&stream_id)
+                        .unwrap_or_else(|| panic!("{}", format!("Stream: {stream_id} not found")));
+                    let topic_id = find_topic_id(&stream.topics, &command.topic_id);
+                    let topic = stream
+                        .topics
+                        .get_mut(&topic_id)
+                        .unwrap_or_else(|| panic!("{}", format!("Topic: {topic_id} not found")));
+                    let mut deleted_partitions_count = 0;
+                    for partition_id in partition_ids {
+                        if topic.partitions.remove(&partition_id).is_some() {
+                            deleted_partitions_count += 1;
+                        }
+                    }
+                    topic.draining_partitions_count = topic
+                        .draining_partitions_count
+                        .saturating_sub(deleted_partitions_count);
+                }
+                EntryCommand::ShrinkPartitions(command) => {
+                    let stream_id = find_stream_id(&streams, &command.stream_id);
+                    let stream = streams
+                        .get_mut(&stream_id)
+                        .unwrap_or_else(|| panic!("{}", format!("Stream: {stream_id} not found")));
+                    let topic_id = find_topic_id(&stream.topics, &command.topic_id);
+                    let topic = stream
+                        .topics
+                        .get_mut(&topic_id)
+                        .unwrap_or_else(|| panic!("{}", format!("Topic: {topic_id} not found")));
+                    topic.draining_partitions_count += command.partitions_count;
+                    if !command.copy_unconsumed {
+                        continue;
+                    }
+
+                    // The unconsumed messages have been already copied, so all the draining partitions were deleted.
+                    let last_partition_id = topic.partitions.keys().max().copied().unwrap_or(0);
+                    for i in 0..topic.draining_partitions_count.min(last_partition_id) {
+                        topic.partitions.remove(&(last_partition_id - i));
+                    }
+                    topic.draining_partitions_count = 0;
+                }
+                EntryCommand::MovePartition(command) => {
+                    let stream_id = find_stream_id(&streams, &command.stream_id);
+                    let stream = streams
+                        .get_mut(&stream_id)
+                        .unwrap_or_else(|| panic!("{}", format!("Stream: {stream_id} not found")));
+                    let topic_id = find_topic_id(&stream.topics, &command.topic_id);
+                    let topic = stream
+                        .topics
+                        .get_mut(&topic_id)
+                        .unwrap_or_else(|| panic!("{}", format!("Topic: {topic_id} not found")));
+                    if let Some(partition) = topic.partitions.get_mut(&command.partition_id) {
+                        partition.directory = Some(command.directory);
+                    }
+                }
                 EntryCommand::DeleteSegments(command) => {
                     let stream_id = find_stream_id(&streams, &command.stream_id);
                     let stream = streams
@@ -539,7 +610,11 @@ impl Display for StreamState {
 
 impl Display for TopicState {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Topic -> ID: {}, Name: {}", self.id, self.name,)?;
+        write!(
+            f,
+            "Topic -> ID: {}, Name: {}, Draining Partitions: {}",
+            self.id, self.name, self.draining_partitions_count
+        )?;
         for partition in self.partitions.iter() {
             write!(f, "\n  {}", partition.1)?;
         }
@@ -555,8 +630,10 @@ impl Display for PartitionState {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "Partition -> ID: {}, Created At: {}",
-            self.id, self.created_at
+            "Partition -> ID: {}, Directory: {}, Created At: {}",
+            self.id,
+            self.directory.as_deref().unwrap_or("default"),
+            self.created_at
         )
     }
 }
@@ -53,6 +53,10 @@ pub struct ConsumerGroup {
 pub enum Transport {
     Tcp,
     Quic,
+    WebSocket,
+    Http,
+    Kafka,
+    Mqtt,
 }
 
 impl Display for Transport {
@@ -60,6 +64,10 @@ impl Display for Transport {
         match self {
             Transport::Tcp => write!(f, "TCP"),
             Transport::Quic => write!(f, "QUIC"),
+            Transport::WebSocket => write!(f, "WebSocket"),
+            Transport::Http => write!(f, "HTTP"),
+            Transport::Kafka => write!(f, "Kafka"),
+            Transport::Mqtt => write!(f, "MQTT"),
         }
     }
 }
@@ -17,29 +17,60 @@
  */
 
 use iggy_common::IggyDuration;
+use moka::Expiry;
 use moka::future::Cache;
+use std::time::{Duration, Instant};
 
 #[derive(Debug)]
 pub struct MessageDeduplicator {
-    cache: Cache<u128, bool>,
+    cache: Cache<u128, Option<Duration>>,
+    max_entries: Option<u64>,
+    ttl: Option<IggyDuration>,
+}
+
+/// Expires the ID after its own time to live (if set), or after the default one.
+struct MessageIdExpiry {
+    ttl: Option<Duration>,
+}
+
+impl Expiry<u128, Option<Duration>> for MessageIdExpiry {
+    fn expire_after_create(
+        &self,
+        _id: &u128,
+        ttl: &Option<Duration>,
+        _created_at: Instant,
+    ) -> Option<Duration> {
+        ttl.or(self.ttl)
+    }
 }
 
 impl MessageDeduplicator {
     /// Creates a new message deduplicator with the given max entries and time to live for each ID.
     pub fn new(max_entries: Option<u64>, ttl: Option<IggyDuration>) -> Self {
-        let mut cache = Cache::builder();
+        let mut cache = Cache::builder().expire_after(MessageIdExpiry {
+            ttl: ttl.map(|ttl| ttl.get_duration()),
+        });
         if let Some(max_entries) = max_entries {
             cache = cache.max_capacity(max_entries);
         }
-        if let Some(ttl) = ttl {
-            cache = cache.time_to_live(ttl.get_duration());
-        }
 
         Self {
             cache: cache.build(),
+            max_entries,
+            ttl,
         }
     }
 
+    /// Returns the max entries of the deduplicator, if limited.
+    pub fn max_entries(&self) -> Option<u64> {
+        self.max_entries
+    }
+
+    /// Returns the time to live of each ID, if limited.
+    pub fn ttl(&self) -> Option<IggyDuration> {
+        self.ttl
+    }
+
     /// Checks if the given ID exists.
     pub fn exists(&self, id: u128) -> bool {
         self.cache.contains_key(&id)
@@ -47,7 +78,12 @@ impl MessageDeduplicator {
 
     /// Inserts the given ID.
     pub async fn insert(&self, id: u128) {
-        self.cache.insert(id, true).await;
+        self.cache.insert(id, None).await;
+    }
+
+    /// Inserts the given ID with the custom time to live, e.g. the remaining one of the ID restored after the restart.
+    pub async fn insert_with_ttl(&self, id: u128, ttl: Duration) {
+        self.cache.insert(id, Some(ttl)).await;
     }
 
     /// Tries to insert the given ID, returns false if it already exists.
@@ -93,4 +129,18 @@ mod tests {
             assert!(deduplicator.try_insert(id).await);
         }
     }
+
+    #[tokio::test]
+    async fn message_deduplicator_should_evict_identifier_after_its_own_time_to_live() {
+        let ttl = "10s".parse::<IggyDuration>().unwrap();
+        let deduplicator = MessageDeduplicator::new(None, Some(ttl));
+        deduplicator
+            .insert_with_ttl(1, Duration::from_millis(100))
+            .await;
+        deduplicator.insert(2).await;
+        assert!(deduplicator.exists(1));
+        sleep(Duration::from_millis(200)).await;
+        assert!(!deduplicator.exists(1));
+        assert!(deduplicator.exists(2));
+    }
 }
@@ -0,0 +1,333 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::streaming::partitions::COMPONENT;
+use crate::streaming::partitions::partition::Partition;
+use crate::streaming::segments::IggyMessagesBatchMut;
+use ahash::AHashMap;
+use bytes::{BufMut, BytesMut};
+use error_set::ErrContext;
+use iggy_common::{IggyError, IggyTimestamp};
+use tracing::{trace, warn};
+
+const COPIED_OFFSET_SIZE: usize = 28;
+
+/// The last message copied into the partition from the draining partition, while shrinking the topic.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CopiedOffset {
+    pub source_partition_id: u32,
+    /// Tells apart the source partition created again with the same ID, e.g. after the topic was shrunk.
+    pub source_created_at: IggyTimestamp,
+    pub source_offset: u64,
+    pub last_offset: u64,
+}
+
+/// Offsets of the messages copied into the partition from the draining partitions, so that retrying the copy
+/// (also after the server restart) doesn't duplicate the messages copied already.
+///
+/// Just like the producer states, the offsets are persisted right before the buffered messages are saved on disk,
+/// together with the offsets covered by the previously saved messages. On startup, the offset whose copy hasn't
+/// reached the disk is replaced with the previous one, so that the offsets never get ahead of the copied messages.
+#[derive(Debug, Default)]
+pub struct CopiedOffsets {
+    offsets: AHashMap<u32, CopiedOffset>,
+    persisted_offsets: AHashMap<u32, CopiedOffset>,
+    has_unsaved_changes: bool,
+}
+
+impl CopiedOffsets {
+    /// Returns the last offset copied from the source partition, unless it's another partition with the same ID.
+    pub fn get(&self, source_partition_id: u32, source_created_at: IggyTimestamp) -> Option<u64> {
+        self.offsets
+            .get(&source_partition_id)
+            .filter(|offset| offset.source_created_at == source_created_at)
+            .map(|offset| offset.source_offset)
+    }
+
+    pub fn len(&self) -> usize {
+        self.offsets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+
+    /// Stores the offset of the last copied message, returns the previous one for the source partition.
+    pub fn update(&mut self, offset: CopiedOffset) -> Option<CopiedOffset> {
+        self.has_unsaved_changes = true;
+        self.offsets.insert(offset.source_partition_id, offset)
+    }
+
+    /// Restores the previous offset for the source partition, e.g. when the copied messages couldn't be appended.
+    pub fn restore(&mut self, source_partition_id: u32, previous_offset: Option<CopiedOffset>) {
+        match previous_offset {
+            Some(offset) => self.offsets.insert(source_partition_id, offset),
+            None => self.offsets.remove(&source_partition_id),
+        };
+    }
+
+    /// Removes the offsets of the source partition, once it has been deleted.
+    pub fn remove(&mut self, source_partition_id: u32) {
+        let removed = self.offsets.remove(&source_partition_id).is_some();
+        let removed_persisted = self
+            .persisted_offsets
+            .remove(&source_partition_id)
+            .is_some();
+        if removed || removed_persisted {
+            self.has_unsaved_changes = true;
+        }
+    }
+
+    pub fn clear(&mut self) {
+        if !self.offsets.is_empty() || !self.persisted_offsets.is_empty() {
+            self.has_unsaved_changes = true;
+        }
+        self.offsets.clear();
+        self.persisted_offsets.clear();
+    }
+
+    /// Marks the current offsets as covered by the messages saved on disk.
+    pub fn mark_persisted(&mut self) {
+        self.persisted_offsets.clone_from(&self.offsets);
+    }
+
+    /// Keeps only the offsets whose copied messages have been saved on disk, falling back to the previously persisted ones.
+    /// The `max_offset` is the offset of the last message loaded from disk, `None` if the partition has no messages.
+    pub fn retain_persisted(&mut self, max_offset: Option<u64>) {
+        let is_persisted =
+            |offset: &CopiedOffset| max_offset.is_some_and(|max| offset.last_offset <= max);
+        let mut offsets = AHashMap::with_capacity(self.offsets.len());
+        for (source_partition_id, offset) in &self.offsets {
+            if is_persisted(offset) {
+                offsets.insert(*source_partition_id, *offset);
+                continue;
+            }
+
+            if let Some(offset) = self
+                .persisted_offsets
+                .get(source_partition_id)
+                .filter(|offset| is_persisted(offset))
+            {
+                offsets.insert(*source_partition_id, *offset);
+            }
+        }
+
+        if offsets != self.offsets {
+            self.has_unsaved_changes = true;
+        }
+        self.offsets = offsets;
+        self.mark_persisted();
+    }
+
+    pub fn to_bytes(&self) -> BytesMut {
+        let mut bytes = BytesMut::with_capacity(
+            4 + (self.offsets.len() + self.persisted_offsets.len()) * COPIED_OFFSET_SIZE,
+        );
+        bytes.put_u32_le(self.offsets.len() as u32);
+        for offset in self.offsets.values().chain(self.persisted_offsets.values()) {
+            bytes.put_u32_le(offset.source_partition_id);
+            bytes.put_u64_le(offset.source_created_at.as_micros());
+            bytes.put_u64_le(offset.source_offset);
+            bytes.put_u64_le(offset.last_offset);
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, IggyError> {
+        if bytes.len() < 4 || !(bytes.len() - 4).is_multiple_of(COPIED_OFFSET_SIZE) {
+            return Err(IggyError::InvalidNumberEncoding);
+        }
+
+        let offsets_count = u32::from_le_bytes(bytes[..4].try_into().unwrap()) as usize;
+        let entries_count = (bytes.len() - 4) / COPIED_OFFSET_SIZE;
+        if offsets_count > entries_count {
+            return Err(IggyError::InvalidNumberEncoding);
+        }
+
+        let mut offsets = AHashMap::with_capacity(offsets_count);
+        let mut persisted_offsets = AHashMap::with_capacity(entries_count - offsets_count);
+        for (index, chunk) in bytes[4..].chunks_exact(COPIED_OFFSET_SIZE).enumerate() {
+            let source_partition_id = u32::from_le_bytes(chunk[..4].try_into().unwrap());
+            let offset = CopiedOffset {
+                source_partition_id,
+                source_created_at: u64::from_le_bytes(chunk[4..12].try_into().unwrap()).into(),
+                source_offset: u64::from_le_bytes(chunk[12..20].try_into().unwrap()),
+                last_offset: u64::from_le_bytes(chunk[20..28].try_into().unwrap()),
+            };
+            if index < offsets_count {
+                offsets.insert(source_partition_id, offset);
+            } else {
+                persisted_offsets.insert(source_partition_id, offset);
+            }
+        }
+
+        Ok(Self {
+            offsets,
+            persisted_offsets,
+            has_unsaved_changes: false,
+        })
+    }
+}
+
+impl Partition {
+    /// Appends the messages copied from the draining partition, together with the offset of the last one.
+    pub async fn append_copied_messages(
+        &mut self,
+        batch: IggyMessagesBatchMut,
+        source_partition_id: u32,
+        source_created_at: IggyTimestamp,
+        source_offset: u64,
+    ) -> Result<(), IggyError> {
+        let messages_count = batch.count();
+        if messages_count == 0 {
+            return Ok(());
+        }
+
+        let first_offset = if self.should_increment_offset {
+            self.current_offset + 1
+        } else {
+            0
+        };
+        let previous_offset = self.copied_offsets.update(CopiedOffset {
+            source_partition_id,
+            source_created_at,
+            source_offset,
+            last_offset: first_offset + messages_count as u64 - 1,
+        });
+        if let Err(error) = self.append_messages(batch, None).await {
+            self.copied_offsets
+                .restore(source_partition_id, previous_offset);
+            return Err(error);
+        }
+
+        Ok(())
+    }
+
+    pub async fn load_copied_offsets(&mut self) -> Result<(), IggyError> {
+        let mut copied_offsets = match self
+            .storage
+            .partition
+            .load_copied_offsets(&self.copied_offsets_path)
+            .await
+        {
+            Ok(copied_offsets) => copied_offsets,
+            Err(error) => {
+                warn!(
+                    "Failed to load copied offsets for partition with ID: {} for topic with ID: {} and stream with ID: {}, path: {}. {error}",
+                    self.partition_id, self.topic_id, self.stream_id, self.copied_offsets_path
+                );
+                CopiedOffsets::default()
+            }
+        };
+        let max_offset = self.should_increment_offset.then_some(self.current_offset);
+        copied_offsets.retain_persisted(max_offset);
+        trace!(
+            "Loaded {} copied offsets for partition with ID: {} for topic with ID: {} and stream with ID: {}.",
+            copied_offsets.len(),
+            self.partition_id,
+            self.topic_id,
+            self.stream_id
+        );
+        self.copied_offsets = copied_offsets;
+        Ok(())
+    }
+
+    pub async fn persist_copied_offsets(&mut self) -> Result<(), IggyError> {
+        if !self.copied_offsets.has_unsaved_changes {
+            return Ok(());
+        }
+
+        self.storage
+            .partition
+            .save_copied_offsets(&self.copied_offsets, &self.copied_offsets_path)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to save copied offsets, path: {}",
+                    self.copied_offsets_path
+                )
+            })?;
+        self.copied_offsets.has_unsaved_changes = false;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn copied_offset(source_offset: u64, last_offset: u64) -> CopiedOffset {
+        CopiedOffset {
+            source_partition_id: 3,
+            source_created_at: 100.into(),
+            source_offset,
+            last_offset,
+        }
+    }
+
+    #[test]
+    fn copied_offset_should_be_ignored_given_source_partition_created_again() {
+        let mut copied_offsets = CopiedOffsets::default();
+        copied_offsets.update(copied_offset(9, 4));
+        assert_eq!(copied_offsets.get(3, 100.into()), Some(9));
+        assert_eq!(copied_offsets.get(3, 200.into()), None);
+        assert_eq!(copied_offsets.get(4, 100.into()), None);
+    }
+
+    #[test]
+    fn copied_offset_should_fall_back_to_persisted_one_given_copy_not_saved_on_disk() {
+        let mut copied_offsets = CopiedOffsets::default();
+        copied_offsets.update(copied_offset(9, 4));
+        copied_offsets.mark_persisted();
+        copied_offsets.update(copied_offset(19, 14));
+
+        copied_offsets.retain_persisted(Some(10));
+        assert_eq!(copied_offsets.get(3, 100.into()), Some(9));
+        assert!(copied_offsets.has_unsaved_changes);
+
+        copied_offsets.retain_persisted(None);
+        assert!(copied_offsets.is_empty());
+    }
+
+    #[test]
+    fn copied_offsets_should_be_serialized_as_bytes_and_deserialized() {
+        let mut copied_offsets = CopiedOffsets::default();
+        copied_offsets.update(copied_offset(9, 4));
+        copied_offsets.mark_persisted();
+        copied_offsets.update(copied_offset(19, 14));
+
+        let deserialized = CopiedOffsets::from_bytes(&copied_offsets.to_bytes()).unwrap();
+        assert_eq!(deserialized.offsets, copied_offsets.offsets);
+        assert_eq!(
+            deserialized.persisted_offsets,
+            copied_offsets.persisted_offsets
+        );
+        assert!(!deserialized.has_unsaved_changes);
+    }
+
+    #[test]
+    fn removed_copied_offset_should_not_be_restored_from_persisted_ones() {
+        let mut copied_offsets = CopiedOffsets::default();
+        copied_offsets.update(copied_offset(9, 4));
+        copied_offsets.mark_persisted();
+        copied_offsets.remove(3);
+
+        copied_offsets.retain_persisted(Some(10));
+        assert!(copied_offsets.is_empty());
+    }
+}
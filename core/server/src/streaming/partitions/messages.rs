@@ -26,7 +26,7 @@ use std::sync::atomic::Ordering;
 use tracing::trace;
 
 impl Partition {
-    /// Retrieves messages by timestamp (up to a specified count).
+    /// Retrieves messages by timestamp (up to a specified count), skipping the messages which TTL has expired.
     pub async fn get_messages_by_timestamp(
         &self,
         timestamp: IggyTimestamp,
@@ -53,7 +53,7 @@ impl Partition {
         Self::get_messages_from_segments_by_timestamp(filtered_segments, query_ts, count).await
     }
 
-    // Retrieves messages by offset (up to a specified count).
+    // Retrieves messages by offset (up to a specified count), skipping the messages which TTL has expired.
     pub async fn get_messages_by_offset(
         &self,
         start_offset: u64,
@@ -150,6 +150,7 @@ impl Partition {
         let mut remaining_count = count;
         let mut current_offset = offset;
         let mut batches = IggyMessagesBatchSet::empty();
+        let now = IggyTimestamp::now().as_micros();
 
         for segment in segments {
             if remaining_count == 0 {
@@ -157,7 +158,7 @@ impl Partition {
             }
 
             let messages = segment
-            .get_messages_by_offset(current_offset, remaining_count)
+            .get_unexpired_messages_by_offset(current_offset, remaining_count, now)
             .await
             .with_error_context(|error| {
                 format!(
@@ -193,6 +194,7 @@ impl Partition {
     ) -> Result<IggyMessagesBatchSet, IggyError> {
         let mut remaining_count = count;
         let mut batches = IggyMessagesBatchSet::empty();
+        let now = IggyTimestamp::now().as_micros();
 
         for segment in segments {
             if remaining_count == 0 {
@@ -200,7 +202,7 @@ impl Partition {
             }
 
             let messages = segment
-                .get_messages_by_timestamp(timestamp, remaining_count)
+                .get_unexpired_messages_by_timestamp(timestamp, remaining_count, now)
                 .await
                 .with_error_context(|error| {
                     format!(
@@ -316,6 +318,9 @@ impl Partition {
                 }
             );
 
+            self.persist_producer_states().await?;
+            self.persist_copied_offsets().await?;
+            let last_segment = self.segments.last_mut().ok_or(IggyError::SegmentNotFound)?;
             last_segment.persist_messages(confirmation).await.with_error_context(|error| {
                 format!(
                     "{COMPONENT} (error: {error}) - failed to persist messages, partition id: {}, start offset: {}",
@@ -324,6 +329,8 @@ impl Partition {
             })?;
             self.unsaved_messages_count = 0;
             self.unsaved_messages_size = 0.into();
+            self.producers.mark_persisted();
+            self.copied_offsets.mark_persisted();
         }
 
         Ok(())
@@ -339,6 +346,8 @@ impl Partition {
             return Ok(());
         }
 
+        self.persist_producer_states().await?;
+        self.persist_copied_offsets().await?;
         let last_segment = self.segments.last_mut().ok_or(IggyError::SegmentNotFound)?;
         trace!(
             "Segment with start offset: {} for partition with ID: {} will be forcefully persisted on disk...",
@@ -355,6 +364,8 @@ impl Partition {
 
         self.unsaved_messages_count = 0;
         self.unsaved_messages_size = 0.into();
+        self.producers.mark_persisted();
+        self.copied_offsets.mark_persisted();
         Ok(())
     }
 }
@@ -363,11 +374,14 @@ impl Partition {
 mod tests {
     use super::*;
     use crate::configs::system::{MessageDeduplicationConfig, SystemConfig};
+    use crate::state::system::PartitionState;
     use crate::streaming::persistence::persister::{FileWithSyncPersister, PersisterKind};
     use crate::streaming::storage::SystemStorage;
+    use crate::streaming::topics::topic::Topic;
     use crate::streaming::utils::MemoryPool;
     use bytes::Bytes;
-    use iggy_common::{IggyExpiry, IggyMessage};
+    use iggy_common::{IggyDuration, IggyExpiry, IggyMessage, TopicOverrides};
+    use std::str::FromStr;
     use std::sync::Arc;
     use std::sync::atomic::{AtomicU32, AtomicU64};
     use tempfile::TempDir;
@@ -704,7 +718,202 @@ mod tests {
         assert_eq!(loaded_messages.count(), 4);
     }
 
+    #[tokio::test]
+    async fn given_enabled_message_deduplication_ids_should_be_restored_after_loading_partition() {
+        let (mut partition, _tempdir) = create_partition(true).await;
+        partition.persist().await.unwrap();
+        let messages = vec![
+            create_message(1, "message 1"),
+            create_message(2, "message 2"),
+            create_message(3, "message 3"),
+        ];
+        let messages_size = messages
+            .iter()
+            .map(|m| m.get_size_bytes().as_bytes_u32())
+            .sum();
+        let batch = IggyMessagesBatchMut::from_messages(&messages, messages_size);
+        partition.append_messages(batch, None).await.unwrap();
+        partition.flush_unsaved_buffer(true).await.unwrap();
+
+        let mut loaded_partition = Partition::create(
+            partition.stream_id,
+            partition.topic_id,
+            partition.partition_id,
+            false,
+            partition.config.clone(),
+            partition.storage.clone(),
+            IggyExpiry::NeverExpire,
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU32::new(0)),
+            partition.created_at,
+        )
+        .await;
+        loaded_partition
+            .load(PartitionState {
+                id: partition.partition_id,
+                directory: None,
+                created_at: partition.created_at,
+            })
+            .await
+            .unwrap();
+
+        let messages = vec![
+            create_message(1, "message 1.3"),
+            create_message(4, "message 4"),
+        ];
+        let messages_size = messages
+            .iter()
+            .map(|m| m.get_size_bytes().as_bytes_u32())
+            .sum();
+        let batch = IggyMessagesBatchMut::from_messages(&messages, messages_size);
+        loaded_partition.append_messages(batch, None).await.unwrap();
+
+        let loaded_messages = loaded_partition
+            .get_messages_by_offset(0, 10)
+            .await
+            .unwrap();
+        assert_eq!(loaded_messages.count(), 4);
+    }
+
+    #[tokio::test]
+    async fn given_copied_messages_only_offsets_of_saved_ones_should_be_restored_after_loading_partition()
+     {
+        let (mut partition, _tempdir) = create_partition(false).await;
+        partition.persist().await.unwrap();
+        let source_created_at = IggyTimestamp::from(100);
+        let messages = create_messages();
+        let messages_size = messages
+            .iter()
+            .map(|m| m.get_size_bytes().as_bytes_u32())
+            .sum();
+        let batch = IggyMessagesBatchMut::from_messages(&messages, messages_size);
+        partition
+            .append_copied_messages(batch, 4, source_created_at, 9)
+            .await
+            .unwrap();
+        partition.flush_unsaved_buffer(true).await.unwrap();
+        let batch = IggyMessagesBatchMut::from_messages(&messages, messages_size);
+        partition
+            .append_copied_messages(batch, 4, source_created_at, 19)
+            .await
+            .unwrap();
+        assert_eq!(partition.copied_offsets.get(4, source_created_at), Some(19));
+
+        // The server stops before the last copied messages are saved on disk.
+        partition.persist_copied_offsets().await.unwrap();
+        let mut loaded_partition = Partition::create(
+            partition.stream_id,
+            partition.topic_id,
+            partition.partition_id,
+            false,
+            partition.config.clone(),
+            partition.storage.clone(),
+            IggyExpiry::NeverExpire,
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU32::new(0)),
+            partition.created_at,
+        )
+        .await;
+        loaded_partition
+            .load(PartitionState {
+                id: partition.partition_id,
+                directory: None,
+                created_at: partition.created_at,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(loaded_partition.current_offset, messages.len() as u64 - 1);
+        assert_eq!(
+            loaded_partition.copied_offsets.get(4, source_created_at),
+            Some(9)
+        );
+    }
+
+    #[tokio::test]
+    async fn given_message_deduplication_disabled_for_topic_all_messages_should_be_appended() {
+        let config = Arc::new(SystemConfig {
+            message_deduplication: MessageDeduplicationConfig {
+                enabled: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let overrides = TopicOverrides {
+            deduplication_enabled: Some(false),
+            ..Default::default()
+        };
+        let topic_config = Topic::get_config(&config, &overrides).unwrap();
+        let (mut partition, _tempdir) =
+            create_partition_with_config(topic_config.message_deduplication.clone()).await;
+        let messages = create_messages();
+        let messages_count = messages.len() as u32;
+        let messages_size = messages
+            .iter()
+            .map(|m| m.get_size_bytes().as_bytes_u32())
+            .sum();
+        let batch = IggyMessagesBatchMut::from_messages(&messages, messages_size);
+
+        partition.append_messages(batch, None).await.unwrap();
+
+        let loaded_messages = partition
+            .get_messages_by_offset(0, messages_count)
+            .await
+            .unwrap();
+        assert_eq!(loaded_messages.count(), messages_count);
+    }
+
+    #[tokio::test]
+    async fn expired_messages_should_be_skipped_when_polling() {
+        let (mut partition, _tempdir) = create_partition(false).await;
+        let messages = vec![
+            create_message(1, "message 1"),
+            create_message_with_ttl(2, "message 2", IggyDuration::from(1)),
+            create_message_with_ttl(3, "message 3", IggyDuration::from_str("1h").unwrap()),
+            create_message_with_ttl(4, "message 4", IggyDuration::from(1)),
+            create_message(5, "message 5"),
+        ];
+        let messages_size = messages
+            .iter()
+            .map(|m| m.get_size_bytes().as_bytes_u32())
+            .sum();
+        let batch = IggyMessagesBatchMut::from_messages(&messages, messages_size);
+        partition.append_messages(batch, None).await.unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+
+        // The expired messages don't count towards the limit.
+        let loaded_messages = partition.get_messages_by_offset(0, 3).await.unwrap();
+        assert_eq!(loaded_messages.count(), 3);
+        let ids = (0..3)
+            .map(|index| loaded_messages.get(index).unwrap().header().id())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec![1, 3, 5]);
+
+        let timestamp = loaded_messages.first_timestamp().unwrap();
+        let loaded_messages = partition
+            .get_messages_by_timestamp(IggyTimestamp::from(timestamp), 10)
+            .await
+            .unwrap();
+        assert_eq!(loaded_messages.count(), 3);
+    }
+
     async fn create_partition(deduplication_enabled: bool) -> (Partition, TempDir) {
+        create_partition_with_config(MessageDeduplicationConfig {
+            enabled: deduplication_enabled,
+            ..Default::default()
+        })
+        .await
+    }
+
+    async fn create_partition_with_config(
+        message_deduplication: MessageDeduplicationConfig,
+    ) -> (Partition, TempDir) {
         let stream_id = 1;
         let topic_id = 2;
         let partition_id = 3;
@@ -712,10 +921,7 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         let config = Arc::new(SystemConfig {
             path: temp_dir.path().to_path_buf().to_str().unwrap().to_string(),
-            message_deduplication: MessageDeduplicationConfig {
-                enabled: deduplication_enabled,
-                ..Default::default()
-            },
+            message_deduplication,
             ..Default::default()
         });
         let storage = Arc::new(SystemStorage::new(
@@ -756,6 +962,15 @@ mod tests {
         ]
     }
 
+    fn create_message_with_ttl(id: u128, payload: &str, ttl: IggyDuration) -> IggyMessage {
+        IggyMessage::builder()
+            .id(id)
+            .payload(Bytes::from(payload.to_string()))
+            .ttl(ttl)
+            .build()
+            .expect("Failed to create message with TTL")
+    }
+
     fn create_message(id: u128, payload: &str) -> IggyMessage {
         IggyMessage::builder()
             .id(id)
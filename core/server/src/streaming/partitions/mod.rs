@@ -17,9 +17,12 @@
  */
 
 pub mod consumer_offsets;
+pub mod copied_offsets;
 pub mod messages;
 pub mod partition;
 pub mod persistence;
+pub mod producers;
+pub mod relocation;
 pub mod segments;
 pub mod storage;
 
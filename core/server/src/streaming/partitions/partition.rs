@@ -18,6 +18,8 @@
 
 use crate::configs::system::SystemConfig;
 use crate::streaming::deduplication::message_deduplicator::MessageDeduplicator;
+use crate::streaming::partitions::copied_offsets::CopiedOffsets;
+use crate::streaming::partitions::producers::ProducerStates;
 use crate::streaming::segments::*;
 use crate::streaming::storage::SystemStorage;
 use dashmap::DashMap;
@@ -40,6 +42,8 @@ pub struct Partition {
     pub offsets_path: String,
     pub consumer_offsets_path: String,
     pub consumer_group_offsets_path: String,
+    pub producers_path: String,
+    pub copied_offsets_path: String,
     pub current_offset: u64,
     pub message_deduplicator: Option<MessageDeduplicator>,
     pub unsaved_messages_count: u32,
@@ -57,6 +61,8 @@ pub struct Partition {
     pub(crate) message_expiry: IggyExpiry,
     pub(crate) consumer_offsets: DashMap<u32, ConsumerOffset>,
     pub(crate) consumer_group_offsets: DashMap<u32, ConsumerOffset>,
+    pub(crate) producers: ProducerStates,
+    pub(crate) copied_offsets: CopiedOffsets,
     pub(crate) segments: Vec<Segment>,
     pub(crate) config: Arc<SystemConfig>,
     pub(crate) storage: Arc<SystemStorage>,
@@ -104,24 +110,10 @@ impl Partition {
             config.get_consumer_offsets_path(stream_id, topic_id, partition_id);
         let consumer_group_offsets_path =
             config.get_consumer_group_offsets_path(stream_id, topic_id, partition_id);
+        let producers_path = config.get_producers_path(stream_id, topic_id, partition_id);
+        let copied_offsets_path = config.get_copied_offsets_path(stream_id, topic_id, partition_id);
 
-        let message_deduplicator = match config.message_deduplication.enabled {
-            true => Some(MessageDeduplicator::new(
-                if config.message_deduplication.max_entries > 0 {
-                    Some(config.message_deduplication.max_entries)
-                } else {
-                    None
-                },
-                {
-                    if config.message_deduplication.expiry.is_zero() {
-                        None
-                    } else {
-                        Some(config.message_deduplication.expiry)
-                    }
-                },
-            )),
-            false => None,
-        };
+        let message_deduplicator = Partition::create_message_deduplicator(&config);
 
         let mut partition = Partition {
             stream_id,
@@ -131,6 +123,8 @@ impl Partition {
             offsets_path,
             consumer_offsets_path,
             consumer_group_offsets_path,
+            producers_path,
+            copied_offsets_path,
             message_expiry,
             message_deduplicator,
             segments: vec![],
@@ -140,6 +134,8 @@ impl Partition {
             should_increment_offset: false,
             consumer_offsets: DashMap::new(),
             consumer_group_offsets: DashMap::new(),
+            producers: ProducerStates::default(),
+            copied_offsets: CopiedOffsets::default(),
             config,
             storage,
             created_at,
@@ -177,6 +173,27 @@ impl Partition {
 
         partition
     }
+
+    /// Creates the deduplicator from the message deduplication settings of the (topic) config, if enabled.
+    pub fn create_message_deduplicator(config: &SystemConfig) -> Option<MessageDeduplicator> {
+        let message_deduplication = &config.message_deduplication;
+        if !message_deduplication.enabled {
+            return None;
+        }
+
+        Some(MessageDeduplicator::new(
+            if message_deduplication.max_entries > 0 {
+                Some(message_deduplication.max_entries)
+            } else {
+                None
+            },
+            if message_deduplication.expiry.is_zero() {
+                None
+            } else {
+                Some(message_deduplication.expiry)
+            },
+        ))
+    }
 }
 
 impl Sizeable for Partition {
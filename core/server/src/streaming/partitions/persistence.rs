@@ -37,11 +37,14 @@ impl Partition {
         storage.partition.save(self).await
     }
 
+    /// Deletes the segments and the partition directory. The deleted segments are removed from the partition,
+    /// so if the deletion fails, it can be retried for the remaining ones.
     pub async fn delete(&mut self) -> Result<(), IggyError> {
-        for segment in &mut self.segments {
+        while let Some(segment) = self.segments.first_mut() {
             segment.delete().await.with_error_context(|error| {
                 format!("{COMPONENT} (error: {error}) - failed to delete segment: {segment}",)
             })?;
+            self.segments.remove(0);
             self.segments_count_of_parent_stream
                 .fetch_sub(1, Ordering::SeqCst);
         }
@@ -54,6 +57,7 @@ impl Partition {
         self.should_increment_offset = false;
         self.consumer_offsets.clear();
         self.consumer_group_offsets.clear();
+        self.copied_offsets.clear();
 
         for segment in &mut self.segments {
             segment.delete().await.with_error_context(|error| {
@@ -63,6 +67,12 @@ impl Partition {
                 .fetch_sub(1, Ordering::SeqCst);
         }
         self.segments.clear();
+        // The copied messages are gone, as well as the ones in the source partitions purged along.
+        self.persist_copied_offsets()
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to save copied offsets in partition: {self}")
+            })?;
         self.storage
             .partition
             .delete_consumer_offsets(&self.consumer_offsets_path)
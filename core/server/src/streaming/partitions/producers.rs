@@ -0,0 +1,441 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::streaming::partitions::COMPONENT;
+use crate::streaming::partitions::partition::Partition;
+use ahash::AHashMap;
+use bytes::{BufMut, BytesMut};
+use error_set::ErrContext;
+use iggy_common::{Confirmation, IggyDuration, IggyError, IggyTimestamp, ProducerSequence};
+use tracing::{trace, warn};
+
+use crate::streaming::segments::IggyMessagesBatchMut;
+
+const PRODUCER_STATE_SIZE: usize = 32;
+
+/// The last sequence number appended to the partition by the idempotent producer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProducerState {
+    pub producer_id: u64,
+    pub last_sequence: u64,
+    pub last_offset: u64,
+    pub last_appended_at: IggyTimestamp,
+}
+
+/// States of the idempotent producers appending the messages to the partition.
+///
+/// The states are persisted right before the buffered messages are saved on disk, together with the states
+/// covered by the previously saved messages. On startup, the state of the producer whose last offset
+/// hasn't reached the disk is replaced with the previous one, so that the sequence numbers never get ahead of the messages.
+#[derive(Debug, Default)]
+pub struct ProducerStates {
+    states: AHashMap<u64, ProducerState>,
+    persisted_states: AHashMap<u64, ProducerState>,
+    has_unsaved_changes: bool,
+}
+
+impl ProducerStates {
+    pub fn get(&self, producer_id: u64) -> Option<&ProducerState> {
+        self.states.get(&producer_id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.states.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.states.is_empty()
+    }
+
+    /// Checks whether the batch with the given sequence has been already appended to the partition.
+    /// The sequence of the unknown producer is always accepted, as its state might have already expired,
+    /// while the sequence of the known producer must directly follow the last appended one.
+    pub fn is_duplicate(
+        &self,
+        producer_sequence: &ProducerSequence,
+        messages_count: u32,
+    ) -> Result<bool, IggyError> {
+        let Some(state) = self.states.get(&producer_sequence.producer_id) else {
+            return Ok(false);
+        };
+
+        if producer_sequence.last_sequence(messages_count) <= state.last_sequence {
+            return Ok(true);
+        }
+
+        let expected_sequence = state.last_sequence + 1;
+        if producer_sequence.base_sequence != expected_sequence {
+            return Err(IggyError::ProducerSequenceOutOfOrder(
+                producer_sequence.producer_id,
+                producer_sequence.base_sequence,
+                expected_sequence,
+            ));
+        }
+
+        Ok(false)
+    }
+
+    /// Stores the last sequence of the appended batch, returns the previous state of the producer.
+    pub fn update(
+        &mut self,
+        producer_sequence: &ProducerSequence,
+        messages_count: u32,
+        last_offset: u64,
+        now: IggyTimestamp,
+    ) -> Option<ProducerState> {
+        self.has_unsaved_changes = true;
+        self.states.insert(
+            producer_sequence.producer_id,
+            ProducerState {
+                producer_id: producer_sequence.producer_id,
+                last_sequence: producer_sequence.last_sequence(messages_count),
+                last_offset,
+                last_appended_at: now,
+            },
+        )
+    }
+
+    /// Restores the previous state of the producer, e.g. when the batch couldn't be appended.
+    pub fn restore(&mut self, producer_id: u64, previous_state: Option<ProducerState>) {
+        match previous_state {
+            Some(state) => self.states.insert(producer_id, state),
+            None => self.states.remove(&producer_id),
+        };
+    }
+
+    /// Removes the states of the producers which haven't appended any messages within the given expiry.
+    pub fn remove_expired(&mut self, now: IggyTimestamp, expiry: IggyDuration) {
+        if expiry.is_zero() {
+            return;
+        }
+
+        let expiry = expiry.as_micros();
+        let is_alive = |state: &ProducerState| {
+            now.as_micros()
+                .saturating_sub(state.last_appended_at.as_micros())
+                < expiry
+        };
+        let states_count = self.states.len();
+        self.states.retain(|_, state| is_alive(state));
+        self.persisted_states.retain(|_, state| is_alive(state));
+        if self.states.len() != states_count {
+            self.has_unsaved_changes = true;
+        }
+    }
+
+    /// Marks the current states as covered by the messages saved on disk.
+    pub fn mark_persisted(&mut self) {
+        self.persisted_states.clone_from(&self.states);
+    }
+
+    /// Keeps only the states whose last offset has been saved on disk, falling back to the previously persisted ones.
+    /// The `max_offset` is the offset of the last message loaded from disk, `None` if the partition has no messages.
+    pub fn retain_persisted(&mut self, max_offset: Option<u64>) {
+        let is_persisted =
+            |state: &ProducerState| max_offset.is_some_and(|offset| state.last_offset <= offset);
+        let mut states = AHashMap::with_capacity(self.states.len());
+        for (producer_id, state) in &self.states {
+            if is_persisted(state) {
+                states.insert(*producer_id, *state);
+                continue;
+            }
+
+            if let Some(state) = self
+                .persisted_states
+                .get(producer_id)
+                .filter(|state| is_persisted(state))
+            {
+                states.insert(*producer_id, *state);
+            }
+        }
+
+        if states != self.states {
+            self.has_unsaved_changes = true;
+        }
+        self.states = states;
+        self.mark_persisted();
+    }
+
+    pub fn to_bytes(&self) -> BytesMut {
+        let mut bytes = BytesMut::with_capacity(
+            4 + (self.states.len() + self.persisted_states.len()) * PRODUCER_STATE_SIZE,
+        );
+        bytes.put_u32_le(self.states.len() as u32);
+        for state in self.states.values().chain(self.persisted_states.values()) {
+            bytes.put_u64_le(state.producer_id);
+            bytes.put_u64_le(state.last_sequence);
+            bytes.put_u64_le(state.last_offset);
+            bytes.put_u64_le(state.last_appended_at.as_micros());
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, IggyError> {
+        if bytes.len() < 4 || !(bytes.len() - 4).is_multiple_of(PRODUCER_STATE_SIZE) {
+            return Err(IggyError::InvalidNumberEncoding);
+        }
+
+        let states_count = u32::from_le_bytes(bytes[..4].try_into().unwrap()) as usize;
+        let entries_count = (bytes.len() - 4) / PRODUCER_STATE_SIZE;
+        if states_count > entries_count {
+            return Err(IggyError::InvalidNumberEncoding);
+        }
+
+        let mut states = AHashMap::with_capacity(states_count);
+        let mut persisted_states = AHashMap::with_capacity(entries_count - states_count);
+        for (index, chunk) in bytes[4..].chunks_exact(PRODUCER_STATE_SIZE).enumerate() {
+            let producer_id = u64::from_le_bytes(chunk[..8].try_into().unwrap());
+            let state = ProducerState {
+                producer_id,
+                last_sequence: u64::from_le_bytes(chunk[8..16].try_into().unwrap()),
+                last_offset: u64::from_le_bytes(chunk[16..24].try_into().unwrap()),
+                last_appended_at: u64::from_le_bytes(chunk[24..32].try_into().unwrap()).into(),
+            };
+            if index < states_count {
+                states.insert(producer_id, state);
+            } else {
+                persisted_states.insert(producer_id, state);
+            }
+        }
+
+        Ok(Self {
+            states,
+            persisted_states,
+            has_unsaved_changes: false,
+        })
+    }
+}
+
+impl Partition {
+    /// Appends the batch sent by the idempotent producer, unless it has been already appended before.
+    pub async fn append_messages_with_sequence(
+        &mut self,
+        batch: IggyMessagesBatchMut,
+        producer_sequence: &ProducerSequence,
+        confirmation: Option<Confirmation>,
+    ) -> Result<(), IggyError> {
+        let messages_count = batch.count();
+        if messages_count == 0 {
+            return Ok(());
+        }
+
+        if self
+            .producers
+            .is_duplicate(producer_sequence, messages_count)
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - invalid producer sequence: {producer_sequence}, partition: {self}"
+                )
+            })?
+        {
+            warn!(
+                "Detected duplicate batch of {messages_count} messages for producer sequence: {producer_sequence}, partition ID: {}, skipping...",
+                self.partition_id
+            );
+            return Ok(());
+        }
+
+        // The state is updated upfront, so that it's persisted before the appended messages.
+        let first_offset = if self.should_increment_offset {
+            self.current_offset + 1
+        } else {
+            0
+        };
+        let last_offset = first_offset + messages_count as u64 - 1;
+        let previous_state = self.producers.update(
+            producer_sequence,
+            messages_count,
+            last_offset,
+            IggyTimestamp::now(),
+        );
+        if let Err(error) = self.append_messages(batch, confirmation).await {
+            self.producers
+                .restore(producer_sequence.producer_id, previous_state);
+            return Err(error);
+        }
+
+        Ok(())
+    }
+
+    /// Loads the producer states, which must be called after the segments are loaded.
+    /// The missing or corrupted states file is not fatal, as the states are only used for the deduplication.
+    pub async fn load_producer_states(&mut self) -> Result<(), IggyError> {
+        let mut producers = match self
+            .storage
+            .partition
+            .load_producer_states(&self.producers_path)
+            .await
+        {
+            Ok(producers) => producers,
+            Err(error) => {
+                warn!(
+                    "Failed to load producer states for partition with ID: {} for topic with ID: {} and stream with ID: {}, path: {}. {error}",
+                    self.partition_id, self.topic_id, self.stream_id, self.producers_path
+                );
+                ProducerStates::default()
+            }
+        };
+        let max_offset = self.should_increment_offset.then_some(self.current_offset);
+        producers.retain_persisted(max_offset);
+        producers.remove_expired(
+            IggyTimestamp::now(),
+            self.config.partition.producer_state_expiry,
+        );
+        trace!(
+            "Loaded {} producer states for partition with ID: {} for topic with ID: {} and stream with ID: {}.",
+            producers.len(),
+            self.partition_id,
+            self.topic_id,
+            self.stream_id
+        );
+        self.producers = producers;
+        Ok(())
+    }
+
+    /// Saves the producer states, which must be called right before the buffered messages are persisted.
+    pub async fn persist_producer_states(&mut self) -> Result<(), IggyError> {
+        self.producers.remove_expired(
+            IggyTimestamp::now(),
+            self.config.partition.producer_state_expiry,
+        );
+        if !self.producers.has_unsaved_changes {
+            return Ok(());
+        }
+
+        self.storage
+            .partition
+            .save_producer_states(&self.producers, &self.producers_path)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to save producer states, path: {}",
+                    self.producers_path
+                )
+            })?;
+        self.producers.has_unsaved_changes = false;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_producer_sequence_should_be_accepted() {
+        let producers = ProducerStates::default();
+        let sequence = ProducerSequence::new(1, 10);
+        assert!(!producers.is_duplicate(&sequence, 5).unwrap());
+    }
+
+    #[test]
+    fn already_appended_sequence_should_be_duplicate() {
+        let mut producers = ProducerStates::default();
+        let sequence = ProducerSequence::new(1, 0);
+        producers.update(&sequence, 5, 4, IggyTimestamp::now());
+        assert_eq!(producers.get(1).unwrap().last_sequence, 4);
+        assert!(producers.is_duplicate(&sequence, 5).unwrap());
+        assert!(
+            producers
+                .is_duplicate(&ProducerSequence::new(1, 2), 3)
+                .unwrap()
+        );
+        assert!(
+            !producers
+                .is_duplicate(&ProducerSequence::new(1, 5), 1)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn sequence_with_gap_or_overlap_should_be_out_of_order() {
+        let mut producers = ProducerStates::default();
+        producers.update(&ProducerSequence::new(1, 0), 5, 4, IggyTimestamp::now());
+        assert!(matches!(
+            producers.is_duplicate(&ProducerSequence::new(1, 6), 1),
+            Err(IggyError::ProducerSequenceOutOfOrder(1, 6, 5))
+        ));
+        assert!(matches!(
+            producers.is_duplicate(&ProducerSequence::new(1, 3), 5),
+            Err(IggyError::ProducerSequenceOutOfOrder(1, 3, 5))
+        ));
+    }
+
+    #[test]
+    fn expired_producer_states_should_be_removed() {
+        let mut producers = ProducerStates::default();
+        let now = IggyTimestamp::now();
+        producers.update(&ProducerSequence::new(1, 0), 1, 0, 0.into());
+        producers.update(&ProducerSequence::new(2, 0), 1, 1, now);
+        producers.remove_expired(now, IggyDuration::new_from_secs(60));
+        assert!(producers.get(1).is_none());
+        assert!(producers.get(2).is_some());
+    }
+
+    #[test]
+    fn producer_states_should_be_serialized_as_bytes_and_deserialized() {
+        let mut producers = ProducerStates::default();
+        producers.update(&ProducerSequence::new(1, 0), 3, 2, 100.into());
+        producers.mark_persisted();
+        producers.update(&ProducerSequence::new(2, 10), 1, 3, 200.into());
+
+        let deserialized = ProducerStates::from_bytes(&producers.to_bytes()).unwrap();
+        assert_eq!(deserialized.len(), 2);
+        assert_eq!(deserialized.get(1), producers.get(1));
+        assert_eq!(deserialized.get(2), producers.get(2));
+        assert_eq!(deserialized.persisted_states, producers.persisted_states);
+        assert!(!deserialized.has_unsaved_changes);
+    }
+
+    #[test]
+    fn truncated_producer_states_bytes_should_fail_to_deserialize() {
+        let mut producers = ProducerStates::default();
+        producers.update(&ProducerSequence::new(1, 0), 3, 2, 100.into());
+        let bytes = producers.to_bytes();
+        assert!(ProducerStates::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert!(ProducerStates::from_bytes(&[]).is_err());
+    }
+
+    #[test]
+    fn producer_state_not_saved_on_disk_should_fall_back_to_persisted_one() {
+        let mut producers = ProducerStates::default();
+        producers.update(&ProducerSequence::new(1, 0), 5, 4, 100.into());
+        producers.update(&ProducerSequence::new(2, 0), 5, 9, 100.into());
+        producers.mark_persisted();
+        producers.update(&ProducerSequence::new(1, 5), 5, 14, 200.into());
+        producers.update(&ProducerSequence::new(3, 0), 5, 19, 200.into());
+
+        let mut loaded = ProducerStates::from_bytes(&producers.to_bytes()).unwrap();
+        loaded.retain_persisted(Some(9));
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded.get(1).unwrap().last_sequence, 4);
+        assert_eq!(loaded.get(2).unwrap().last_sequence, 4);
+        assert!(loaded.get(3).is_none());
+        assert!(loaded.has_unsaved_changes);
+
+        let mut loaded = ProducerStates::from_bytes(&producers.to_bytes()).unwrap();
+        loaded.retain_persisted(Some(19));
+        assert_eq!(loaded.len(), 3);
+        assert_eq!(loaded.get(1).unwrap().last_sequence, 9);
+        assert!(!loaded.has_unsaved_changes);
+
+        let mut loaded = ProducerStates::from_bytes(&producers.to_bytes()).unwrap();
+        loaded.retain_persisted(None);
+        assert!(loaded.is_empty());
+    }
+}
@@ -0,0 +1,445 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::state::system::PartitionState;
+use crate::streaming::partitions::COMPONENT;
+use crate::streaming::partitions::partition::Partition;
+use crate::streaming::topics::topic::Topic;
+use ahash::{AHashMap, AHashSet};
+use error_set::ErrContext;
+use iggy_common::IggyError;
+use iggy_common::locking::{IggySharedMut, IggySharedMutFn};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
+use tokio::fs;
+use tracing::{error, info, warn};
+
+/// Suffix of the partition directory which is still being copied to the target data directory.
+pub const MOVING_PARTITION_SUFFIX: &str = ".moving";
+
+impl Partition {
+    /// Returns the data directory in which the partition is placed.
+    pub fn get_directory(&self) -> &str {
+        &self.config.path
+    }
+}
+
+/// Moves the partition to another data directory, without losing any messages or offsets.
+///
+/// The closed segments are copied first, while the partition keeps serving the requests.
+/// Then the partition is locked for writing, the unsaved messages are flushed, the remaining files
+/// are copied and the partition is reloaded from the new directory. The move is persisted (e.g. applied
+/// to the state log) before the partition is switched to the new directory and the old one is removed,
+/// so that the complete copy, which is used after the restart, is always the one pointed to by the state.
+pub async fn move_partition(
+    partition: &IggySharedMut<Partition>,
+    directory: &str,
+    persist_move: impl Future<Output = Result<(), IggyError>>,
+) -> Result<(), IggyError> {
+    let (stream_id, topic_id, partition_id, source_path, target_path) = {
+        let partition = partition.read().await;
+        if partition.get_directory() == directory {
+            info!(
+                "Partition with ID: {} for stream with ID: {} and topic with ID: {} is already placed in directory: {directory}.",
+                partition.partition_id, partition.stream_id, partition.topic_id
+            );
+            return Ok(());
+        }
+
+        let target_path = partition
+            .config
+            .with_data_directory(directory)
+            .get_partition_path(
+                partition.stream_id,
+                partition.topic_id,
+                partition.partition_id,
+            );
+        (
+            partition.stream_id,
+            partition.topic_id,
+            partition.partition_id,
+            partition.partition_path.clone(),
+            target_path,
+        )
+    };
+
+    let cannot_move_partition =
+        || IggyError::CannotMovePartition(partition_id, topic_id, stream_id, directory.to_owned());
+    if fs::try_exists(&target_path).await.unwrap_or(true) {
+        error!(
+            "Cannot move partition with ID: {partition_id} for stream with ID: {stream_id} and topic with ID: {topic_id}, path: {target_path} already exists."
+        );
+        return Err(cannot_move_partition());
+    }
+
+    // Creating the temporary directory fails if another move of the same partition is in progress.
+    let moving_path = PathBuf::from(format!("{target_path}{MOVING_PARTITION_SUFFIX}"));
+    if let Some(parent) = moving_path.parent() {
+        fs::create_dir_all(parent).await.map_err(|error| {
+            error!(
+                "Cannot create directory: {}, error: {error}",
+                parent.display()
+            );
+            cannot_move_partition()
+        })?;
+    }
+    fs::create_dir(&moving_path).await.map_err(|error| {
+        error!(
+            "Cannot create directory: {}, error: {error}",
+            moving_path.display()
+        );
+        cannot_move_partition()
+    })?;
+
+    info!(
+        "Moving partition with ID: {partition_id} for stream with ID: {stream_id} and topic with ID: {topic_id} from: {source_path} to: {target_path}..."
+    );
+    let result = copy_and_reload_partition(
+        partition,
+        directory,
+        Path::new(&source_path),
+        &moving_path,
+        Path::new(&target_path),
+        persist_move,
+    )
+    .await;
+    if let Err(error) = result {
+        error!(
+            "Failed to move partition with ID: {partition_id} for stream with ID: {stream_id} and topic with ID: {topic_id} to: {target_path}, error: {error}"
+        );
+        let _ = fs::remove_dir_all(&moving_path).await;
+        return Err(cannot_move_partition());
+    }
+
+    if let Err(error) = fs::remove_dir_all(&source_path).await {
+        warn!("Cannot remove the previous partition directory: {source_path}, error: {error}");
+    }
+    info!(
+        "Moved partition with ID: {partition_id} for stream with ID: {stream_id} and topic with ID: {topic_id} to: {target_path}."
+    );
+    Ok(())
+}
+
+async fn copy_and_reload_partition(
+    partition: &IggySharedMut<Partition>,
+    directory: &str,
+    source_path: &Path,
+    moving_path: &Path,
+    target_path: &Path,
+    persist_move: impl Future<Output = Result<(), IggyError>>,
+) -> Result<(), IggyError> {
+    // The closed segments are immutable, so they can be copied without blocking the partition.
+    let closed_segment_files = {
+        let partition = partition.read().await;
+        partition
+            .segments
+            .iter()
+            .filter(|segment| segment.is_closed())
+            .flat_map(|segment| {
+                [
+                    PathBuf::from(segment.messages_file_path()),
+                    PathBuf::from(segment.index_file_path()),
+                ]
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let mut copied_files = AHashMap::new();
+    for file in closed_segment_files {
+        let Ok(relative_path) = file.strip_prefix(source_path) else {
+            continue;
+        };
+        match copy_file(&file, &moving_path.join(relative_path)).await {
+            Ok(size) => {
+                copied_files.insert(relative_path.to_path_buf(), size);
+            }
+            // The segment might have been deleted in the meantime, e.g. due to the message expiry.
+            Err(error) if error.kind() == io::ErrorKind::NotFound => continue,
+            Err(error) => {
+                error!("Cannot copy file: {}, error: {error}", file.display());
+                return Err(IggyError::CannotReadFile);
+            }
+        }
+    }
+
+    let mut partition = partition.write().await;
+    partition
+        .flush_unsaved_buffer(true)
+        .await
+        .with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to flush unsaved messages, partition: {partition}")
+        })?;
+    let mut closed_writers = Vec::new();
+    for (index, segment) in partition.segments.iter_mut().enumerate() {
+        if segment.close_writing().await {
+            closed_writers.push(index);
+        }
+    }
+
+    let result = copy_remaining_files(source_path, moving_path, &copied_files).await;
+    let result = match result {
+        Ok(()) => fs::rename(moving_path, target_path).await.map_err(|error| {
+            error!(
+                "Cannot rename directory: {} to: {}, error: {error}",
+                moving_path.display(),
+                target_path.display()
+            );
+            IggyError::CannotCreatePartitionDirectory(
+                partition.partition_id,
+                partition.stream_id,
+                partition.topic_id,
+            )
+        }),
+        Err(error) => Err(error),
+    };
+    let result = match result {
+        Ok(()) => reload_partition(&partition, directory).await,
+        Err(error) => Err(error),
+    };
+    // If the server stops before the move is persisted, the partition is loaded from the previous directory
+    // (which is still complete) on the next start, otherwise from the new one, and the other copy is removed.
+    let result = match result {
+        Ok(mut moved_partition) => match persist_move.await {
+            Ok(()) => Ok(moved_partition),
+            Err(error) => {
+                for segment in moved_partition.segments.iter_mut() {
+                    segment.shutdown_reading().await;
+                }
+                subtract_partition_counters(&moved_partition);
+                Err(error)
+            }
+        },
+        Err(error) => Err(error),
+    };
+
+    match result {
+        Ok(moved_partition) => {
+            let mut previous_partition = std::mem::replace(&mut *partition, moved_partition);
+            for segment in previous_partition.segments.iter_mut() {
+                segment.shutdown_reading().await;
+            }
+            subtract_partition_counters(&previous_partition);
+            Ok(())
+        }
+        Err(error) => {
+            let _ = fs::remove_dir_all(target_path).await;
+            for index in closed_writers {
+                partition.segments[index]
+                    .initialize_writing(true)
+                    .await
+                    .with_error_context(|error| {
+                        format!("{COMPONENT} (error: {error}) - failed to reopen segment writers after the failed move, partition: {partition}")
+                    })?;
+            }
+            Err(error)
+        }
+    }
+}
+
+/// Replaces all the files of the partition with the ones from the given directory, e.g. unpacked from the data snapshot,
+/// and reloads the partition, which must not be used by anyone else, as all its messages and offsets are replaced.
+pub async fn restore_partition(
+    partition: &IggySharedMut<Partition>,
+    source_path: &Path,
+) -> Result<(), IggyError> {
+    let mut partition = partition.write().await;
+    for segment in partition.segments.iter_mut() {
+        segment.close_writing().await;
+        segment.shutdown_reading().await;
+    }
+
+    let cannot_restore_partition = || {
+        IggyError::CannotCreatePartitionDirectory(
+            partition.partition_id,
+            partition.stream_id,
+            partition.topic_id,
+        )
+    };
+    let partition_path = PathBuf::from(&partition.partition_path);
+    if let Err(error) = fs::remove_dir_all(&partition_path).await {
+        error!(
+            "Cannot remove directory: {}, error: {error}",
+            partition_path.display()
+        );
+        return Err(cannot_restore_partition());
+    }
+    if let Err(error) = fs::create_dir_all(&partition_path).await {
+        error!(
+            "Cannot create directory: {}, error: {error}",
+            partition_path.display()
+        );
+        return Err(cannot_restore_partition());
+    }
+
+    copy_remaining_files(source_path, &partition_path, &AHashMap::new())
+        .await
+        .with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to copy the restored files, partition: {partition}")
+        })?;
+    let directory = partition.get_directory().to_owned();
+    let restored_partition = reload_partition(&partition, &directory).await?;
+    let previous_partition = std::mem::replace(&mut *partition, restored_partition);
+    subtract_partition_counters(&previous_partition);
+    info!(
+        "Restored partition with ID: {} for stream with ID: {} and topic with ID: {} from: {}.",
+        partition.partition_id,
+        partition.stream_id,
+        partition.topic_id,
+        source_path.display()
+    );
+    Ok(())
+}
+
+/// Loads the partition from the files placed in the given data directory, as a replacement for the existing one.
+async fn reload_partition(partition: &Partition, directory: &str) -> Result<Partition, IggyError> {
+    let mut moved_partition = Partition::create(
+        partition.stream_id,
+        partition.topic_id,
+        partition.partition_id,
+        false,
+        Topic::get_partition_config(&partition.config, directory),
+        partition.storage.clone(),
+        partition.message_expiry,
+        partition.messages_count_of_parent_stream.clone(),
+        partition.messages_count_of_parent_topic.clone(),
+        partition.size_of_parent_stream.clone(),
+        partition.size_of_parent_topic.clone(),
+        partition.segments_count_of_parent_stream.clone(),
+        partition.created_at,
+    )
+    .await;
+    let state = PartitionState {
+        id: partition.partition_id,
+        directory: Some(directory.to_owned()),
+        created_at: partition.created_at,
+    };
+    if let Err(error) = moved_partition.load(state).await {
+        subtract_partition_counters(&moved_partition);
+        return Err(error);
+    }
+
+    Ok(moved_partition)
+}
+
+/// Subtracts the counters of the partition from its parents, as the moved partition is loaded with its own.
+fn subtract_partition_counters(partition: &Partition) {
+    let size_bytes = partition.size_bytes.load(Ordering::SeqCst);
+    let messages_count = partition.messages_count.load(Ordering::SeqCst);
+    partition
+        .size_of_parent_stream
+        .fetch_sub(size_bytes, Ordering::SeqCst);
+    partition
+        .size_of_parent_topic
+        .fetch_sub(size_bytes, Ordering::SeqCst);
+    partition
+        .messages_count_of_parent_stream
+        .fetch_sub(messages_count, Ordering::SeqCst);
+    partition
+        .messages_count_of_parent_topic
+        .fetch_sub(messages_count, Ordering::SeqCst);
+    partition
+        .segments_count_of_parent_stream
+        .fetch_sub(partition.get_segments_count(), Ordering::SeqCst);
+}
+
+/// Copies the files which weren't copied yet (or have changed since), and removes the copies of the deleted ones.
+async fn copy_remaining_files(
+    source_path: &Path,
+    moving_path: &Path,
+    copied_files: &AHashMap<PathBuf, u64>,
+) -> Result<(), IggyError> {
+    let (source_files, source_directories) = collect_files(source_path).await.map_err(|error| {
+        error!(
+            "Cannot read directory: {}, error: {error}",
+            source_path.display()
+        );
+        IggyError::CannotReadPartitions
+    })?;
+
+    for relative_path in &source_directories {
+        let directory = moving_path.join(relative_path);
+        fs::create_dir_all(&directory).await.map_err(|error| {
+            error!(
+                "Cannot create directory: {}, error: {error}",
+                directory.display()
+            );
+            IggyError::CannotWriteToFile
+        })?;
+    }
+
+    for (relative_path, size) in &source_files {
+        if copied_files.get(relative_path) == Some(size) {
+            continue;
+        }
+
+        let file = source_path.join(relative_path);
+        copy_file(&file, &moving_path.join(relative_path))
+            .await
+            .map_err(|error| {
+                error!("Cannot copy file: {}, error: {error}", file.display());
+                IggyError::CannotReadFile
+            })?;
+    }
+
+    let source_files = source_files.into_keys().collect::<AHashSet<_>>();
+    for relative_path in copied_files.keys() {
+        if !source_files.contains(relative_path) {
+            let _ = fs::remove_file(moving_path.join(relative_path)).await;
+        }
+    }
+    Ok(())
+}
+
+async fn copy_file(source: &Path, target: &Path) -> io::Result<u64> {
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+    let size = fs::copy(source, target).await?;
+    fs::File::open(target).await?.sync_all().await?;
+    Ok(size)
+}
+
+/// Returns the paths (relative to the given directory) and sizes of all the files in the directory tree,
+/// along with the relative paths of all the nested directories.
+pub(crate) async fn collect_files(
+    path: &Path,
+) -> io::Result<(AHashMap<PathBuf, u64>, Vec<PathBuf>)> {
+    let mut files = AHashMap::new();
+    let mut nested_directories = Vec::new();
+    let mut directories = vec![path.to_path_buf()];
+    while let Some(directory) = directories.pop() {
+        let mut entries = fs::read_dir(&directory).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let metadata = entry.metadata().await?;
+            let entry_path = entry.path();
+            let Ok(relative_path) = entry_path.strip_prefix(path) else {
+                continue;
+            };
+
+            if metadata.is_dir() {
+                nested_directories.push(relative_path.to_path_buf());
+                directories.push(entry_path);
+                continue;
+            }
+
+            files.insert(relative_path.to_path_buf(), metadata.len());
+        }
+    }
+    Ok((files, nested_directories))
+}
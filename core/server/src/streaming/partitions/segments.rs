@@ -57,11 +57,14 @@ impl Partition {
     }
 
     pub async fn get_expired_segments_start_offsets(&self, now: IggyTimestamp) -> Vec<u64> {
+        // Only the leading expired segments are returned, so the deleted segments never leave a gap in offsets,
+        // which might happen for the segments containing only the messages with TTL.
         let mut expired_segments = Vec::new();
         for segment in &self.segments {
-            if segment.is_expired(now).await {
-                expired_segments.push(segment.start_offset());
+            if !segment.is_expired(now).await {
+                break;
             }
+            expired_segments.push(segment.start_offset());
         }
 
         expired_segments.sort();
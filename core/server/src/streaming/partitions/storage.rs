@@ -20,7 +20,9 @@ use crate::compat::index_rebuilding::index_rebuilder::IndexRebuilder;
 use crate::configs::cache_indexes::CacheIndexesConfig;
 use crate::state::system::PartitionState;
 use crate::streaming::partitions::COMPONENT;
+use crate::streaming::partitions::copied_offsets::CopiedOffsets;
 use crate::streaming::partitions::partition::{ConsumerOffset, Partition};
+use crate::streaming::partitions::producers::ProducerStates;
 use crate::streaming::persistence::persister::PersisterKind;
 use crate::streaming::segments::*;
 use crate::streaming::storage::PartitionStorage;
@@ -28,12 +30,14 @@ use crate::streaming::utils::file;
 use error_set::ErrContext;
 use iggy_common::ConsumerKind;
 use iggy_common::IggyError;
+use iggy_common::IggyTimestamp;
 use std::path::Path;
 use std::sync::Arc;
 use std::sync::atomic::Ordering;
+use std::time::Duration;
 use tokio::fs;
 use tokio::fs::create_dir_all;
-use tokio::io::AsyncReadExt;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tracing::{error, info, trace, warn};
 
 #[derive(Debug)]
@@ -178,38 +182,6 @@ impl PartitionStorage for FilePartitionStorage {
                 );
             }
 
-            // Load the unique message IDs for the partition if the deduplication feature is enabled.
-            let mut unique_message_ids_count = 0;
-            if let Some(message_deduplicator) = &partition.message_deduplicator {
-                let max_entries = partition.config.message_deduplication.max_entries as u32;
-                info!(
-                    "Loading {max_entries} unique message IDs for partition with ID: {} and segment with start offset: {}...",
-                    partition.partition_id,
-                    segment.start_offset()
-                );
-                let message_ids = segment.load_message_ids(max_entries).await.with_error_context(|error| {
-                    format!("{COMPONENT} (error: {error}) - failed to load message ids, segment: {segment}",)
-                })?;
-                for message_id in message_ids {
-                    if message_deduplicator.try_insert(message_id).await {
-                        unique_message_ids_count += 1;
-                    } else {
-                        warn!(
-                            "Duplicated message ID: {} for partition with ID: {} and segment with start offset: {}.",
-                            message_id,
-                            partition.partition_id,
-                            segment.start_offset()
-                        );
-                    }
-                }
-                info!(
-                    "Loaded: {} unique message IDs for partition with ID: {} and segment with start offset: {}...",
-                    unique_message_ids_count,
-                    partition.partition_id,
-                    segment.start_offset()
-                );
-            }
-
             if CacheIndexesConfig::None == partition.config.segment.cache_indexes {
                 segment.drop_indexes();
             }
@@ -225,6 +197,74 @@ impl PartitionStorage for FilePartitionStorage {
             partition.current_offset = last_segment.end_offset();
         }
 
+        // Rebuild the deduplication cache from the newest messages which haven't expired yet.
+        if let Some(message_deduplicator) = &partition.message_deduplicator {
+            let max_entries = message_deduplicator
+                .max_entries()
+                .map(|max_entries| max_entries.min(u32::MAX as u64) as u32)
+                .unwrap_or(u32::MAX);
+            let now = IggyTimestamp::now().as_micros();
+            let min_timestamp = message_deduplicator
+                .ttl()
+                .map(|ttl| now.saturating_sub(ttl.as_micros()))
+                .unwrap_or(0);
+            info!(
+                "Loading up to {max_entries} message IDs not older than {} for partition with ID: {}...",
+                IggyTimestamp::from(min_timestamp),
+                partition.partition_id
+            );
+
+            let mut message_ids = Vec::new();
+            for segment in partition.segments.iter().rev() {
+                let remaining_count = max_entries - message_ids.len() as u32;
+                if remaining_count == 0 || segment.end_timestamp() < min_timestamp {
+                    break;
+                }
+
+                let segment_message_ids = segment.load_message_ids(remaining_count).await.with_error_context(|error| {
+                    format!("{COMPONENT} (error: {error}) - failed to load message ids, segment: {segment}",)
+                })?;
+                let segment_message_ids_count = segment_message_ids.len();
+                let previous_message_ids_count = message_ids.len();
+                message_ids.extend(
+                    segment_message_ids
+                        .into_iter()
+                        .rev()
+                        .take_while(|(_, timestamp)| *timestamp >= min_timestamp),
+                );
+                if message_ids.len() - previous_message_ids_count < segment_message_ids_count {
+                    break;
+                }
+            }
+
+            let mut unique_message_ids_count = 0;
+            for (message_id, timestamp) in message_ids.into_iter().rev() {
+                if message_deduplicator.exists(message_id) {
+                    warn!(
+                        "Duplicated message ID: {} for partition with ID: {}.",
+                        message_id, partition.partition_id
+                    );
+                    continue;
+                }
+
+                match message_deduplicator.ttl() {
+                    Some(ttl) => {
+                        let elapsed = now.saturating_sub(timestamp);
+                        let remaining_ttl = ttl.as_micros().saturating_sub(elapsed);
+                        message_deduplicator
+                            .insert_with_ttl(message_id, Duration::from_micros(remaining_ttl))
+                            .await;
+                    }
+                    None => message_deduplicator.insert(message_id).await,
+                }
+                unique_message_ids_count += 1;
+            }
+            info!(
+                "Loaded: {} unique message IDs for partition with ID: {}.",
+                unique_message_ids_count, partition.partition_id
+            );
+        }
+
         // If cache_indexes is OpenSegment, clear all segment indexes except the last one
         if matches!(
             partition.config.segment.cache_indexes,
@@ -243,6 +283,18 @@ impl PartitionStorage for FilePartitionStorage {
             .with_error_context(|error| {
                 format!("{COMPONENT} (error: {error}) - failed to load consumer offsets, partition: {partition}",)
             })?;
+        partition
+            .load_producer_states()
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to load producer states, partition: {partition}",)
+            })?;
+        partition
+            .load_copied_offsets()
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to load copied offsets, partition: {partition}",)
+            })?;
         info!(
             "Loaded partition with ID: {} for stream with ID: {} and topic with ID: {}, current offset: {}.",
             partition.partition_id,
@@ -368,7 +420,9 @@ impl PartitionStorage for FilePartitionStorage {
             ));
         }
 
-        if fs::remove_dir_all(&partition.partition_path).await.is_err() {
+        if Path::new(&partition.partition_path).exists()
+            && fs::remove_dir_all(&partition.partition_path).await.is_err()
+        {
             error!(
                 "Cannot delete partition directory: {} for partition with ID: {} for topic with ID: {} for stream with ID: {}.",
                 partition.partition_path,
@@ -494,4 +548,77 @@ impl PartitionStorage for FilePartitionStorage {
         }
         Ok(())
     }
+
+    async fn save_producer_states(
+        &self,
+        producers: &ProducerStates,
+        path: &str,
+    ) -> Result<(), IggyError> {
+        overwrite_file(path, &producers.to_bytes(), "producer states").await?;
+        trace!("Stored {} producer states, path: {}", producers.len(), path);
+        Ok(())
+    }
+
+    async fn load_producer_states(&self, path: &str) -> Result<ProducerStates, IggyError> {
+        if !Path::new(path).exists() {
+            trace!("Producer states file does not exist: {path}.");
+            return Ok(ProducerStates::default());
+        }
+
+        trace!("Loading producer states from path: {path}...");
+        let bytes = fs::read(path)
+            .await
+            .map_err(|_| IggyError::CannotReadProducerStates(path.to_owned()))?;
+        ProducerStates::from_bytes(&bytes)
+            .map_err(|_| IggyError::CannotReadProducerStates(path.to_owned()))
+    }
+
+    async fn save_copied_offsets(
+        &self,
+        copied_offsets: &CopiedOffsets,
+        path: &str,
+    ) -> Result<(), IggyError> {
+        overwrite_file(path, &copied_offsets.to_bytes(), "copied offsets").await?;
+        trace!(
+            "Stored {} copied offsets, path: {}",
+            copied_offsets.len(),
+            path
+        );
+        Ok(())
+    }
+
+    async fn load_copied_offsets(&self, path: &str) -> Result<CopiedOffsets, IggyError> {
+        if !Path::new(path).exists() {
+            trace!("Copied offsets file does not exist: {path}.");
+            return Ok(CopiedOffsets::default());
+        }
+
+        trace!("Loading copied offsets from path: {path}...");
+        let bytes = fs::read(path)
+            .await
+            .map_err(|_| IggyError::CannotReadCopiedOffsets(path.to_owned()))?;
+        CopiedOffsets::from_bytes(&bytes)
+            .map_err(|_| IggyError::CannotReadCopiedOffsets(path.to_owned()))
+    }
+}
+
+/// Writes the bytes into the temporary file renamed afterwards, so that the file is never torn.
+async fn overwrite_file(path: &str, bytes: &[u8], description: &str) -> Result<(), IggyError> {
+    let temp_path = format!("{path}.tmp");
+    let mut temp_file = fs::File::create(&temp_path).await.map_err(|error| {
+        error!("Cannot create {description} file: {temp_path}. {error}");
+        IggyError::CannotOverwriteFile
+    })?;
+    temp_file.write_all(bytes).await.map_err(|error| {
+        error!("Cannot write {description} file: {temp_path}. {error}");
+        IggyError::CannotWriteToFile
+    })?;
+    temp_file.sync_all().await.map_err(|error| {
+        error!("Cannot sync {description} file: {temp_path}. {error}");
+        IggyError::CannotSyncFile
+    })?;
+    file::rename(&temp_path, path).await.map_err(|error| {
+        error!("Cannot rename {description} file: {temp_path} to: {path}. {error}");
+        IggyError::CannotOverwriteFile
+    })
 }
@@ -194,8 +194,60 @@ impl Segment {
         Ok(combined_batch_set)
     }
 
-    /// Loads and returns `count` newest message IDs from the log file.
-    pub async fn load_message_ids(&self, count: u32) -> Result<Vec<u128>, IggyError> {
+    /// Retrieves up to `count` messages by offset, skipping the messages which TTL has expired at the provided timestamp.
+    /// The expired messages don't count towards the limit, so the reading continues until enough messages are found
+    /// or the end of the segment is reached.
+    pub async fn get_unexpired_messages_by_offset(
+        &self,
+        offset: u64,
+        count: u32,
+        now: u64,
+    ) -> Result<IggyMessagesBatchSet, IggyError> {
+        let messages = self.get_messages_by_offset(offset, count).await?;
+        self.remove_expired_messages(messages, count, now).await
+    }
+
+    /// Retrieves up to `count` messages by timestamp, skipping the messages which TTL has expired at the provided timestamp.
+    pub async fn get_unexpired_messages_by_timestamp(
+        &self,
+        timestamp: u64,
+        count: u32,
+        now: u64,
+    ) -> Result<IggyMessagesBatchSet, IggyError> {
+        let messages = self.get_messages_by_timestamp(timestamp, count).await?;
+        self.remove_expired_messages(messages, count, now).await
+    }
+
+    /// Removes the expired messages from the batch set and tops it up with the following messages of the segment.
+    async fn remove_expired_messages(
+        &self,
+        mut messages: IggyMessagesBatchSet,
+        count: u32,
+        now: u64,
+    ) -> Result<IggyMessagesBatchSet, IggyError> {
+        let mut unexpired_messages = IggyMessagesBatchSet::empty();
+        while let Some(last_offset) = messages.last_offset() {
+            messages.remove_expired_messages(now);
+            unexpired_messages.add_batch_set(messages);
+            let remaining_count = count - unexpired_messages.count();
+            if remaining_count == 0 || last_offset >= self.end_offset {
+                break;
+            }
+
+            trace!(
+                "Skipped expired messages, loading {remaining_count} more messages from offset: {}...",
+                last_offset + 1
+            );
+            messages = self
+                .get_messages_by_offset(last_offset + 1, remaining_count)
+                .await?;
+        }
+
+        Ok(unexpired_messages)
+    }
+
+    /// Loads and returns `count` newest message IDs with their timestamps from the log file, ordered from the oldest one.
+    pub async fn load_message_ids(&self, count: u32) -> Result<Vec<(u128, u64)>, IggyError> {
         let messages_count = self.get_messages_count();
         trace!(
             "Loading message IDs for {messages_count} messages from log file: {}",
@@ -219,15 +271,19 @@ impl Segment {
 
         let indexes = indexes.unwrap();
 
-        let ids = self
+        let messages = self
             .messages_reader
             .as_ref()
             .unwrap()
-            .load_all_message_ids_from_disk(indexes, messages_count)
+            .load_messages_from_disk(indexes)
             .await
             .with_error_context(|error| {
                 format!("Failed to load message IDs, error: {error} for {self}")
             })?;
+        let ids = messages
+            .iter()
+            .map(|message| (message.header().id(), message.header().timestamp()))
+            .collect::<Vec<_>>();
 
         trace!(
             "Loaded {} message IDs from log file: {}",
@@ -30,9 +30,11 @@ use iggy_common::IggyTimestamp;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
 use tokio::fs::remove_file;
+use tokio::sync::OnceCell;
 use tracing::{info, warn};
 
 const SIZE_16MB: usize = 16 * 1024 * 1024;
+const EXPIRY_SCAN_BATCH_COUNT: u32 = 10000;
 
 #[derive(Debug)]
 pub struct Segment {
@@ -64,6 +66,8 @@ pub struct Segment {
     pub(super) indexes: IggyIndexesMut,
     pub(super) messages_size: Arc<AtomicU64>,
     pub(super) indexes_size: Arc<AtomicU64>,
+    /// The latest expiry timestamp of the messages, if every message in the closed segment has the TTL set.
+    pub(super) messages_expiry_timestamp: OnceCell<Option<u64>>,
 }
 
 impl Segment {
@@ -124,6 +128,7 @@ impl Segment {
             config,
             messages_size: Arc::new(AtomicU64::new(0)),
             indexes_size: Arc::new(AtomicU64::new(0)),
+            messages_expiry_timestamp: OnceCell::new(),
         }
     }
 
@@ -247,6 +252,36 @@ impl Segment {
         Ok(())
     }
 
+    /// Updates the config of the segment, e.g. after the topic overrides have changed.
+    /// The writers of the open segment are reopened if the fsync or confirmation settings differ.
+    pub async fn update_config(&mut self, config: Arc<SystemConfig>) -> Result<(), IggyError> {
+        let reinitialize_writing = self.messages_writer.is_some()
+            && (self.config.partition.enforce_fsync != config.partition.enforce_fsync
+                || self.config.segment.server_confirmation != config.segment.server_confirmation);
+        self.max_size_bytes = config.segment.size;
+        self.config = config;
+        if !reinitialize_writing {
+            return Ok(());
+        }
+
+        self.close_writing().await;
+        self.initialize_writing(true).await
+    }
+
+    /// Waits until the pending writes are persisted and closes the writers, if the segment has any.
+    /// Returns whether the writers were open.
+    pub async fn close_writing(&mut self) -> bool {
+        let was_writing = self.messages_writer.is_some() || self.index_writer.is_some();
+        if let Some(messages_writer) = self.messages_writer.take() {
+            let _ = messages_writer.fsync().await;
+            messages_writer.shutdown_persister_task().await;
+        }
+        if let Some(index_writer) = self.index_writer.take() {
+            let _ = index_writer.fsync().await;
+        }
+        was_writing
+    }
+
     pub async fn initialize_reading(&mut self) -> Result<(), IggyError> {
         let messages_reader =
             MessagesReader::new(&self.messages_path, self.messages_size.clone()).await?;
@@ -271,7 +306,7 @@ impl Segment {
             return false;
         }
 
-        match self.message_expiry {
+        let is_expired = match self.message_expiry {
             IggyExpiry::NeverExpire => false,
             IggyExpiry::ServerDefault => false,
             IggyExpiry::ExpireDuration(expiry) => {
@@ -289,9 +324,51 @@ impl Segment {
                 let last_message_timestamp = last_message.header().timestamp();
                 last_message_timestamp + expiry.as_micros() <= now.as_micros()
             }
+        };
+        if is_expired {
+            return true;
+        }
+
+        // The segment might also contain only the messages with TTL, regardless of the topic message expiry.
+        // The closed segment doesn't change anymore, so it's scanned only once.
+        match self
+            .messages_expiry_timestamp
+            .get_or_try_init(|| self.calculate_messages_expiry_timestamp())
+            .await
+        {
+            Ok(Some(expiry_timestamp)) => *expiry_timestamp <= now.as_micros(),
+            Ok(None) => false,
+            Err(error) => {
+                warn!("Failed to calculate the messages expiry for segment: {self}. {error}");
+                false
+            }
         }
     }
 
+    /// Returns the latest expiry timestamp of the messages, or `None` if any of the messages has no TTL set.
+    async fn calculate_messages_expiry_timestamp(&self) -> Result<Option<u64>, IggyError> {
+        let mut messages_expiry_timestamp = 0;
+        let mut offset = self.start_offset;
+        while offset <= self.end_offset {
+            let messages = self
+                .get_messages_by_offset(offset, EXPIRY_SCAN_BATCH_COUNT)
+                .await?;
+            let Some(last_offset) = messages.last_offset() else {
+                return Ok(None);
+            };
+
+            for message in messages.iter().flat_map(|batch| batch.iter()) {
+                let Some(expiry_timestamp) = message.expiry_timestamp() else {
+                    return Ok(None);
+                };
+                messages_expiry_timestamp = messages_expiry_timestamp.max(expiry_timestamp);
+            }
+            offset = last_offset + 1;
+        }
+
+        Ok(Some(messages_expiry_timestamp))
+    }
+
     pub async fn shutdown_reading(&mut self) {
         if let Some(log_reader) = self.messages_reader.take() {
             drop(log_reader);
@@ -606,6 +606,26 @@ impl IggyMessagesBatchMut {
         self.indexes = new_indexes;
     }
 
+    /// Removes the messages which TTL (set with the `MESSAGE_TTL_HEADER` user header) has expired at the provided timestamp.
+    pub fn remove_expired_messages(&mut self, now: u64) {
+        let expired_messages_indexes = self
+            .iter()
+            .enumerate()
+            .filter(|(_, message)| {
+                message
+                    .expiry_timestamp()
+                    .is_some_and(|expiry_timestamp| expiry_timestamp <= now)
+            })
+            .map(|(index, _)| index as u32)
+            .collect::<Vec<_>>();
+        if expired_messages_indexes.is_empty() {
+            return;
+        }
+
+        let base_position = self.indexes.base_position();
+        self.remove_messages(&expired_messages_indexes, base_position);
+    }
+
     /// Validates that all messages in batch have correct checksums.
     pub fn validate_checksums(&self) -> Result<(), IggyError> {
         for message in self.iter() {
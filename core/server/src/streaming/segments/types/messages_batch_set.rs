@@ -79,6 +79,16 @@ impl IggyMessagesBatchSet {
         self.batches.extend(other_batches);
     }
 
+    /// Removes the messages which TTL has expired at the provided timestamp, along with the batches left empty.
+    pub fn remove_expired_messages(&mut self, now: u64) {
+        for batch in self.batches.iter_mut() {
+            batch.remove_expired_messages(now);
+        }
+        self.batches.retain(|batch| !batch.is_empty());
+        self.count = self.batches.iter().map(|batch| batch.count()).sum();
+        self.size = self.batches.iter().map(|batch| batch.size()).sum();
+    }
+
     /// Extract indexes from all batches in the set
     pub fn append_indexes_to(&self, target: &mut IggyIndexesMut) {
         for batch in self.iter() {
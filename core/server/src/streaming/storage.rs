@@ -19,7 +19,9 @@
 use super::persistence::persister::PersisterKind;
 use crate::configs::system::SystemConfig;
 use crate::state::system::{PartitionState, StreamState, TopicState};
+use crate::streaming::partitions::copied_offsets::CopiedOffsets;
 use crate::streaming::partitions::partition::{ConsumerOffset, Partition};
+use crate::streaming::partitions::producers::ProducerStates;
 use crate::streaming::partitions::storage::FilePartitionStorage;
 use crate::streaming::streams::storage::FileStreamStorage;
 use crate::streaming::streams::stream::Stream;
@@ -139,6 +141,24 @@ pub trait PartitionStorage: Send {
         &self,
         path: &str,
     ) -> impl Future<Output = Result<(), IggyError>> + Send;
+    fn save_producer_states(
+        &self,
+        producers: &ProducerStates,
+        path: &str,
+    ) -> impl Future<Output = Result<(), IggyError>> + Send;
+    fn load_producer_states(
+        &self,
+        path: &str,
+    ) -> impl Future<Output = Result<ProducerStates, IggyError>> + Send;
+    fn save_copied_offsets(
+        &self,
+        copied_offsets: &CopiedOffsets,
+        path: &str,
+    ) -> impl Future<Output = Result<(), IggyError>> + Send;
+    fn load_copied_offsets(
+        &self,
+        path: &str,
+    ) -> impl Future<Output = Result<CopiedOffsets, IggyError>> + Send;
 }
 
 #[derive(Debug)]
@@ -204,5 +224,17 @@ impl PartitionStorageKind {
         ) -> Result<Vec<ConsumerOffset>, IggyError>;
         async fn delete_consumer_offsets(&self, path: &str) -> Result<(), IggyError>;
         async fn delete_consumer_offset(&self, path: &str) -> Result<(), IggyError>;
+        async fn save_producer_states(
+            &self,
+            producers: &ProducerStates,
+            path: &str
+        ) -> Result<(), IggyError>;
+        async fn load_producer_states(&self, path: &str) -> Result<ProducerStates, IggyError>;
+        async fn save_copied_offsets(
+            &self,
+            copied_offsets: &CopiedOffsets,
+            path: &str
+        ) -> Result<(), IggyError>;
+        async fn load_copied_offsets(&self, path: &str) -> Result<CopiedOffsets, IggyError>;
     }
 }
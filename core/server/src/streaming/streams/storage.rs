@@ -225,6 +225,15 @@ impl StreamStorage for FileStreamStorage {
         if fs::remove_dir_all(&stream.path).await.is_err() {
             return Err(IggyError::CannotDeleteStreamDirectory(stream.stream_id));
         }
+        for directory in &stream.config.partition.directories {
+            let path = stream
+                .config
+                .with_data_directory(directory)
+                .get_stream_path(stream.stream_id);
+            if Path::new(&path).exists() && fs::remove_dir_all(&path).await.is_err() {
+                return Err(IggyError::CannotDeleteStreamDirectory(stream.stream_id));
+            }
+        }
         info!("Deleted stream with ID: {}.", stream.stream_id);
         Ok(())
     }
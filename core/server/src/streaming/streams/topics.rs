@@ -16,6 +16,7 @@
  * under the License.
  */
 
+use crate::streaming::partitions::partition::Partition;
 use crate::streaming::streams::COMPONENT;
 use crate::streaming::streams::stream::Stream;
 use crate::streaming::topics::topic::Topic;
@@ -24,6 +25,7 @@ use iggy_common::CompressionAlgorithm;
 use iggy_common::IggyError;
 use iggy_common::IggyExpiry;
 use iggy_common::MaxTopicSize;
+use iggy_common::TopicOverrides;
 use iggy_common::locking::IggySharedMutFn;
 use iggy_common::{IdKind, Identifier};
 use std::sync::atomic::Ordering;
@@ -44,8 +46,10 @@ impl Stream {
         compression_algorithm: CompressionAlgorithm,
         max_topic_size: MaxTopicSize,
         replication_factor: u8,
+        overrides: TopicOverrides,
     ) -> Result<u32, IggyError> {
-        let max_topic_size = Topic::get_max_topic_size(max_topic_size, &self.config)?;
+        let topic_config = Topic::get_config(&self.config, &overrides)?;
+        let max_topic_size = Topic::get_max_topic_size(max_topic_size, &topic_config)?;
         if self.topics_ids.contains_key(name) {
             return Err(IggyError::TopicNameAlreadyExists(
                 name.to_owned(),
@@ -88,6 +92,7 @@ impl Stream {
             compression_algorithm,
             max_topic_size,
             replication_factor,
+            overrides,
         )
         .await?;
         topic.persist().await.with_error_context(|error| {
@@ -99,6 +104,7 @@ impl Stream {
         Ok(id)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn update_topic(
         &mut self,
         id: &Identifier,
@@ -107,16 +113,22 @@ impl Stream {
         compression_algorithm: CompressionAlgorithm,
         max_topic_size: MaxTopicSize,
         replication_factor: u8,
+        overrides: Option<TopicOverrides>,
     ) -> Result<(), IggyError> {
         let message_expiry = Topic::get_message_expiry(message_expiry, &self.config);
-        let max_topic_size = Topic::get_max_topic_size(max_topic_size, &self.config)?;
         let topic_id;
+        let topic_config;
         {
             let topic = self.get_topic(id).with_error_context(|error| {
                 format!("{COMPONENT} (error: {error}) - failed to get topic with id: {id}")
             })?;
             topic_id = topic.topic_id;
+            topic_config = match &overrides {
+                Some(overrides) => Topic::get_config(&self.config, overrides)?,
+                None => topic.config.clone(),
+            };
         }
+        let max_topic_size = Topic::get_max_topic_size(max_topic_size, &topic_config)?;
 
         {
             if let Some(topic_id_by_name) = self.topics_ids.get(name) {
@@ -146,11 +158,31 @@ impl Stream {
             topic.name = name.to_owned();
             topic.message_expiry = message_expiry;
             topic.compression_algorithm = compression_algorithm;
+            if let Some(overrides) = overrides {
+                topic.overrides = overrides;
+                topic.config = topic_config.clone();
+            }
             for partition in topic.partitions.values_mut() {
                 let mut partition = partition.write().await;
                 partition.message_expiry = message_expiry;
+                let previous_deduplication = &partition.config.message_deduplication;
+                let deduplication = &topic_config.message_deduplication;
+                if previous_deduplication.enabled != deduplication.enabled
+                    || previous_deduplication.max_entries != deduplication.max_entries
+                    || previous_deduplication.expiry != deduplication.expiry
+                {
+                    // The cache of the new deduplicator starts empty, it's rebuilt from the messages on the next startup.
+                    partition.message_deduplicator =
+                        Partition::create_message_deduplicator(&topic_config);
+                }
+                partition.config =
+                    Topic::get_partition_config(&topic_config, partition.get_directory());
+                let partition_config = partition.config.clone();
                 for segment in partition.segments.iter_mut() {
                     segment.update_message_expiry(message_expiry);
+                    segment.update_config(partition_config.clone()).await.with_error_context(|error| {
+                        format!("{COMPONENT} (error: {error}) - failed to update config of segment: {segment}")
+                    })?;
                 }
             }
             topic.max_topic_size = max_topic_size;
@@ -314,6 +346,7 @@ mod tests {
                 compression_algorithm,
                 max_topic_size,
                 1,
+                TopicOverrides::default(),
             )
             .await
             .unwrap();
@@ -25,8 +25,10 @@ use crate::streaming::utils::PooledBuffer;
 use error_set::ErrContext;
 use iggy_common::{
     BytesSerializable, Confirmation, Consumer, EncryptorKind, IGGY_MESSAGE_HEADER_SIZE, Identifier,
-    IggyError, Partitioning, PollingStrategy,
+    IggyError, Partitioning, PollingStrategy, ProducerSequence,
 };
+use std::sync::Arc;
+use tokio::sync::Notify;
 use tracing::{error, trace};
 
 impl System {
@@ -93,6 +95,19 @@ impl System {
         Ok((metadata, batch_set))
     }
 
+    /// Returns the notifier which is triggered whenever new messages are appended to any partition of the topic.
+    pub fn get_new_messages_notifier(
+        &self,
+        session: &Session,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+    ) -> Result<Arc<Notify>, IggyError> {
+        self.ensure_authenticated(session)?;
+        let topic = self.find_topic(session, stream_id, topic_id).with_error_context(|error| format!("{COMPONENT} (error: {error}) - topic not found for stream ID: {stream_id}, topic_id: {topic_id}"))?;
+        Ok(topic.new_messages.clone())
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub async fn append_messages(
         &self,
         session: &Session,
@@ -100,6 +115,7 @@ impl System {
         topic_id: &Identifier,
         partitioning: &Partitioning,
         messages: IggyMessagesBatchMut,
+        producer_sequence: Option<ProducerSequence>,
         confirmation: Option<Confirmation>,
     ) -> Result<(), IggyError> {
         self.ensure_authenticated(session)?;
@@ -124,7 +140,7 @@ impl System {
         };
 
         topic
-            .append_messages(partitioning, messages, confirmation)
+            .append_messages(partitioning, messages, producer_sequence, confirmation)
             .await?;
 
         self.metrics.increment_messages(messages_count as u64);
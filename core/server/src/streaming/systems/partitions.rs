@@ -16,12 +16,17 @@
  * under the License.
  */
 
+use crate::streaming::partitions::partition::Partition;
 use crate::streaming::session::Session;
 use crate::streaming::systems::COMPONENT;
 use crate::streaming::systems::system::System;
+use crate::streaming::topics::partitions::UnconsumedMessagesCopy;
 use error_set::ErrContext;
 use iggy_common::Identifier;
+use iggy_common::IggyDuration;
 use iggy_common::IggyError;
+use iggy_common::locking::IggySharedMut;
+use tracing::{error, info, warn};
 
 impl System {
     pub async fn create_partitions(
@@ -96,6 +101,14 @@ impl System {
                     "{COMPONENT} (error: {error}) - failed to get mutable reference to stream with id: {stream_id}"
                 )
             })?;
+        // The draining partitions are the last ones, and they are deleted only once drained (or copied).
+        if topic.get_draining_partitions_count() > 0 {
+            return Err(IggyError::PartitionsDraining(
+                topic.topic_id,
+                topic.stream_id,
+            ));
+        }
+
         let partitions = topic
             .delete_persisted_partitions(partitions_count)
             .await
@@ -110,4 +123,232 @@ impl System {
         }
         Ok(())
     }
+
+    /// Shrinks the topic by the given number of partitions. The partitions are either marked as draining,
+    /// to be deleted once consumed by all the consumer groups, or their unconsumed messages are going to be copied
+    /// into the remaining partitions. In the latter case, the returned copy must be run without the system lock,
+    /// and the shrinking finished with its result, which deletes the draining partitions.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn shrink_partitions(
+        &mut self,
+        session: &Session,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partitions_count: u32,
+        copy_unconsumed: bool,
+        key_header: Option<&str>,
+        consistent_hashing: bool,
+    ) -> Result<Option<UnconsumedMessagesCopy>, IggyError> {
+        self.ensure_authenticated(session)?;
+        {
+            let topic = self.find_topic(session, stream_id, topic_id).with_error_context(|error| format!("{COMPONENT} (error: {error}) - topic not found for stream ID: {stream_id}, topic_id: {topic_id}"))?;
+            self.permissioner.shrink_partitions(
+                session.get_user_id(),
+                topic.stream_id,
+                topic.topic_id,
+            ).with_error_context(|error| format!(
+                "{COMPONENT} (error: {error}) - permission denied to shrink partitions for user {} on stream ID: {}, topic ID: {}",
+                session.get_user_id(),
+                topic.stream_id,
+                topic.topic_id
+            ))?;
+        }
+
+        let topic = self
+            .get_stream_mut(stream_id)?
+            .get_topic_mut(topic_id)
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to get mutable reference to stream with id: {stream_id}"
+                )
+            })?;
+        if topic.is_copying_partitions() {
+            return Err(IggyError::PartitionsDraining(
+                topic.topic_id,
+                topic.stream_id,
+            ));
+        }
+
+        topic
+            .drain_partitions(partitions_count)
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to drain partitions for topic: {topic}"
+                )
+            })?;
+        if !copy_unconsumed {
+            info!("Draining {partitions_count} partition(s) for topic: {topic}");
+            return Ok(None);
+        }
+
+        match topic.start_unconsumed_messages_copy(partitions_count, key_header, consistent_hashing)
+        {
+            Ok(copy) => {
+                info!(
+                    "Copying unconsumed messages of {} draining partition(s) for topic: {topic}...",
+                    topic.get_draining_partitions_count()
+                );
+                Ok(Some(copy))
+            }
+            Err(error) => {
+                topic.draining_partitions_count -= partitions_count;
+                Err(error)
+            }
+        }
+    }
+
+    /// Finishes shrinking the partitions with the result of the unconsumed messages copy. The draining partitions
+    /// are deleted once copied, otherwise the shrunk partitions become active again, so the shrinking can be retried.
+    pub async fn finish_shrinking_partitions(
+        &mut self,
+        copy: &UnconsumedMessagesCopy,
+        copied_messages_count: Result<u64, IggyError>,
+    ) -> Result<(), IggyError> {
+        let stream_id = Identifier::numeric(copy.stream_id)?;
+        let topic_id = Identifier::numeric(copy.topic_id)?;
+        let topic = self
+            .get_stream_mut(&stream_id)?
+            .get_topic_mut(&topic_id)
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to get mutable reference to stream with id: {stream_id}"
+                )
+            })?;
+        // The topic might have been deleted and created again in the meantime.
+        if !topic.is_copying_partitions() {
+            return Err(IggyError::TopicIdNotFound(copy.topic_id, copy.stream_id));
+        }
+
+        topic.finish_unconsumed_messages_copy();
+        let copied_messages_count = match copied_messages_count {
+            Ok(copied_messages_count) => copied_messages_count,
+            Err(error) => {
+                topic.draining_partitions_count -= copy.partitions_count;
+                error!(
+                    "{COMPONENT} (error: {error}) - failed to copy unconsumed messages for topic: {topic}"
+                );
+                return Err(error);
+            }
+        };
+
+        let draining_partitions_count = topic.get_draining_partitions_count();
+        let partitions = topic
+            .delete_persisted_partitions(draining_partitions_count)
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to delete drained partitions for topic: {topic}")
+            })?;
+        topic.reassign_consumer_groups().await;
+        // The copied offsets of the deleted partitions are ignored anyway, once another partition is created with the same ID.
+        if let Err(error) = copy.remove_copied_offsets().await {
+            warn!(
+                "Failed to remove the copied offsets of the deleted partitions for topic: {topic}. {error}"
+            );
+        }
+        info!(
+            "Copied {copied_messages_count} unconsumed message(s) and deleted {draining_partitions_count} partition(s) for topic: {topic}"
+        );
+        self.metrics.increment_messages(copied_messages_count);
+        if let Some(partitions) = partitions {
+            self.metrics.decrement_partitions(draining_partitions_count);
+            self.metrics.decrement_segments(partitions.segments_count);
+            self.metrics.decrement_messages(partitions.messages_count);
+        }
+        Ok(())
+    }
+
+    /// Returns the IDs of the last draining partitions of the topic, which have been consumed,
+    /// or retained long enough when they have no consumers.
+    pub async fn get_drained_partition_ids(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        retention: IggyDuration,
+    ) -> Result<Vec<u32>, IggyError> {
+        let topic = self.get_stream(stream_id)?.get_topic(topic_id)?;
+        Ok(topic.get_drained_partition_ids(retention).await)
+    }
+
+    /// Deletes the given draining partitions of the topic, which have been drained, the already deleted ones are skipped.
+    pub async fn delete_drained_partitions(
+        &mut self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_ids: &[u32],
+    ) -> Result<(), IggyError> {
+        let topic = self
+            .get_stream_mut(stream_id)?
+            .get_topic_mut(topic_id)
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to get mutable reference to stream with id: {stream_id}"
+                )
+            })?;
+        let mut result = Ok(());
+        let mut deleted_partitions_count = 0;
+        let mut deleted_segments_count = 0;
+        let mut deleted_messages_count = 0;
+        for partition_id in partition_ids {
+            match topic.delete_drained_partition(*partition_id).await {
+                Ok(Some(partitions)) => {
+                    deleted_partitions_count += 1;
+                    deleted_segments_count += partitions.segments_count;
+                    deleted_messages_count += partitions.messages_count;
+                }
+                Ok(None) => {}
+                Err(error) => {
+                    result = Err(error).with_error_context(|error| {
+                        format!("{COMPONENT} (error: {error}) - failed to delete drained partitions for topic: {topic}")
+                    });
+                    break;
+                }
+            }
+        }
+        if deleted_partitions_count > 0 {
+            topic.reassign_consumer_groups().await;
+            self.metrics.decrement_partitions(deleted_partitions_count);
+            self.metrics.decrement_segments(deleted_segments_count);
+            self.metrics.decrement_messages(deleted_messages_count);
+        }
+        result
+    }
+
+    /// Returns the partition which is going to be moved to the given data directory.
+    /// The move itself doesn't require the system to be locked, so the partition is returned to the caller.
+    pub fn find_partition_to_move(
+        &self,
+        session: &Session,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: u32,
+        directory: &str,
+    ) -> Result<IggySharedMut<Partition>, IggyError> {
+        self.ensure_authenticated(session)?;
+        let topic = self.find_topic(session, stream_id, topic_id).with_error_context(|error| format!("{COMPONENT} (error: {error}) - topic not found for stream ID: {stream_id}, topic_id: {topic_id}"))?;
+        self.permissioner.move_partition(
+            session.get_user_id(),
+            topic.stream_id,
+            topic.topic_id,
+        ).with_error_context(|error| format!(
+            "{COMPONENT} (error: {error}) - permission denied to move partition for user {} on stream ID: {}, topic ID: {}",
+            session.get_user_id(),
+            topic.stream_id,
+            topic.topic_id
+        ))?;
+
+        if !self
+            .config
+            .get_data_directories()
+            .iter()
+            .any(|data_directory| data_directory == directory)
+        {
+            return Err(IggyError::InvalidPartitionDirectory(directory.to_owned()));
+        }
+
+        topic.get_partition(partition_id).with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - partition with ID: {partition_id} not found, topic: {topic}"
+            )
+        })
+    }
 }
@@ -0,0 +1,876 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::configs::system::SystemConfig;
+use crate::state::command::EntryCommand;
+use crate::state::models::{CreateConsumerGroupWithId, CreateStreamWithId, CreateTopicWithId};
+use crate::streaming::partitions::relocation::{collect_files, restore_partition};
+use crate::streaming::segments::{INDEX_EXTENSION, LOG_EXTENSION};
+use crate::streaming::session::Session;
+use crate::streaming::systems::COMPONENT;
+use crate::streaming::systems::system::System;
+use crate::streaming::topics::topic::Topic;
+use ahash::AHashSet;
+use bytes::{BufMut, Bytes, BytesMut};
+use error_set::ErrContext;
+use iggy_common::create_consumer_group::CreateConsumerGroup;
+use iggy_common::create_stream::CreateStream;
+use iggy_common::create_topic::CreateTopic;
+use iggy_common::locking::IggySharedMutFn;
+use iggy_common::{BytesSerializable, DataSnapshotTarget, IggyDuration, IggyError, IggyTimestamp};
+use iggy_common::{Identifier, IggyByteSize};
+use std::io::{self, Read, Seek};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use tempfile::TempDir;
+use tokio::fs::{self, File};
+use tokio::io::AsyncWriteExt;
+use tracing::{error, info};
+
+/// Name of the archive entry holding the state entries required to recreate the streams, topics and consumer groups.
+const STATE_ENTRY: &str = "state";
+/// Name of the archive directory holding the partitions files, placed under `{stream_id}/{topic_id}/{partition_id}`.
+const PARTITIONS_DIRECTORY: &str = "partitions";
+/// Size of the chunks in which the data snapshot archive is streamed to and from the clients.
+pub const DATA_SNAPSHOT_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Data snapshot archive written to an unnamed temporary file in the runtime directory,
+/// so it can be streamed to the client without keeping it in memory, and it's removed once the file is dropped.
+#[derive(Debug)]
+pub struct DataSnapshot {
+    pub file: File,
+    pub size: u64,
+}
+
+/// Data snapshot archive uploaded for the restore, streamed in chunks to an unnamed temporary file in the runtime directory,
+/// so it's never kept in memory as a whole.
+#[derive(Debug)]
+pub struct DataSnapshotUpload {
+    file: File,
+    size: u64,
+}
+
+/// Data snapshot archive unpacked into a temporary directory in the runtime directory, which is removed once dropped.
+/// The archive is unpacked before the system is locked, so the restore only needs the lock to recreate the resources.
+#[derive(Debug)]
+pub struct UnpackedDataSnapshot {
+    directory: TempDir,
+    entries: Vec<EntryCommand>,
+}
+
+impl DataSnapshotUpload {
+    pub async fn create(config: &SystemConfig) -> Result<Self, IggyError> {
+        let runtime_path = create_runtime_directory(config).await?;
+        let file = tempfile::tempfile_in(&runtime_path).map_err(|error| {
+            error!("Cannot create temporary data snapshot file in: {runtime_path}, error: {error}");
+            IggyError::CannotCreateBaseDirectory(runtime_path.clone())
+        })?;
+        Ok(Self {
+            file: File::from_std(file),
+            size: 0,
+        })
+    }
+
+    pub async fn write(&mut self, chunk: &[u8]) -> Result<(), IggyError> {
+        self.file.write_all(chunk).await.map_err(|error| {
+            error!("Cannot write the uploaded data snapshot chunk, error: {error}");
+            IggyError::CannotWriteToFile
+        })?;
+        self.size += chunk.len() as u64;
+        Ok(())
+    }
+
+    /// Unpacks the uploaded archive and reads its state entries, which doesn't require the system to be locked.
+    pub async fn unpack(self, config: &SystemConfig) -> Result<UnpackedDataSnapshot, IggyError> {
+        if self.size == 0 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let runtime_path = create_runtime_directory(config).await?;
+        let directory = tempfile::Builder::new()
+            .prefix("data_snapshot_")
+            .tempdir_in(&runtime_path)
+            .map_err(|error| {
+                error!("Cannot create temporary directory in: {runtime_path}, error: {error}");
+                IggyError::CannotCreateBaseDirectory(runtime_path.clone())
+            })?;
+        let mut archive = self.file.into_std().await;
+        let unpack_path = directory.path().to_path_buf();
+        tokio::task::spawn_blocking(move || {
+            archive.rewind()?;
+            unpack_archive(archive, &unpack_path)
+        })
+        .await
+        .map_err(|error| {
+            error!("Failed to join the data snapshot task, error: {error}");
+            IggyError::InvalidDataSnapshot
+        })?
+        .map_err(|error| {
+            error!("Failed to unpack the data snapshot archive, error: {error}");
+            IggyError::InvalidDataSnapshot
+        })?;
+
+        let state = fs::read(directory.path().join(STATE_ENTRY))
+            .await
+            .map_err(|error| {
+                error!("Cannot read the data snapshot state entries, error: {error}");
+                IggyError::InvalidDataSnapshot
+            })?;
+        let entries = read_state_entries(Bytes::from(state))?;
+        Ok(UnpackedDataSnapshot { directory, entries })
+    }
+}
+
+/// File of the partition to be included in the snapshot.
+/// The segments are append-only, so it's enough to read their size captured at the snapshot point in time,
+/// and the opened file can still be read, even if the segment is deleted in the meantime.
+/// All the other files (e.g. consumer offsets) are read right away, and the directories are kept even if empty.
+enum SnapshotFile {
+    Segment {
+        path: String,
+        file: std::fs::File,
+        size: u64,
+    },
+    Read {
+        path: String,
+        data: Vec<u8>,
+    },
+    Directory {
+        path: String,
+    },
+}
+
+/// Reader of the segment file yielding exactly the captured size, failing if the file turns out to be shorter,
+/// as otherwise the archive entry wouldn't match the size declared in its header.
+struct SegmentReader {
+    file: io::Take<std::fs::File>,
+}
+
+impl Read for SegmentReader {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        let read_bytes = self.file.read(buffer)?;
+        if read_bytes == 0 && !buffer.is_empty() && self.file.limit() > 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                format!(
+                    "segment file is shorter than expected, missing bytes: {}",
+                    self.file.limit()
+                ),
+            ));
+        }
+        Ok(read_bytes)
+    }
+}
+
+impl System {
+    /// Creates a point-in-time snapshot of the selected streams and topics, without blocking them longer than needed
+    /// to flush the unsaved messages and capture the current size of the segments.
+    pub async fn get_data_snapshot(
+        &self,
+        session: &Session,
+        targets: &[DataSnapshotTarget],
+    ) -> Result<DataSnapshot, IggyError> {
+        self.ensure_authenticated(session)?;
+        let topics = self.find_data_snapshot_topics(session, targets)?;
+        info!(
+            "Creating data snapshot of {} topic(s) for targets: {targets:?}...",
+            topics.len()
+        );
+        let now = Instant::now();
+
+        let mut state = BytesMut::new();
+        let mut streams = AHashSet::new();
+        let mut files = Vec::new();
+        for topic in topics {
+            if streams.insert(topic.stream_id) {
+                let stream = self.get_stream(&Identifier::numeric(topic.stream_id)?)?;
+                state.put_slice(
+                    &EntryCommand::CreateStream(CreateStreamWithId {
+                        stream_id: stream.stream_id,
+                        command: CreateStream {
+                            stream_id: Some(stream.stream_id),
+                            name: stream.name.clone(),
+                        },
+                    })
+                    .to_bytes(),
+                );
+            }
+
+            state.put_slice(
+                &EntryCommand::CreateTopic(CreateTopicWithId {
+                    topic_id: topic.topic_id,
+                    command: CreateTopic {
+                        stream_id: Identifier::numeric(topic.stream_id)?,
+                        topic_id: Some(topic.topic_id),
+                        partitions_count: topic.get_partitions_count(),
+                        compression_algorithm: topic.compression_algorithm,
+                        message_expiry: topic.message_expiry,
+                        max_topic_size: topic.max_topic_size,
+                        replication_factor: Some(topic.replication_factor),
+                        name: topic.name.clone(),
+                        overrides: topic.overrides,
+                    },
+                })
+                .to_bytes(),
+            );
+            for consumer_group in topic.get_consumer_groups() {
+                let consumer_group = consumer_group.read().await;
+                state.put_slice(
+                    &EntryCommand::CreateConsumerGroup(CreateConsumerGroupWithId {
+                        group_id: consumer_group.group_id,
+                        command: CreateConsumerGroup {
+                            stream_id: Identifier::numeric(topic.stream_id)?,
+                            topic_id: Identifier::numeric(topic.topic_id)?,
+                            group_id: Some(consumer_group.group_id),
+                            name: consumer_group.name.clone(),
+                        },
+                    })
+                    .to_bytes(),
+                );
+            }
+
+            files.extend(capture_topic_files(topic).await.with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to capture files for data snapshot, topic: {topic}"
+                )
+            })?);
+        }
+
+        files.insert(
+            0,
+            SnapshotFile::Read {
+                path: STATE_ENTRY.to_owned(),
+                data: state.to_vec(),
+            },
+        );
+
+        let runtime_path = create_runtime_directory(&self.config).await?;
+        let archive = tempfile::tempfile_in(&runtime_path).map_err(|error| {
+            error!("Cannot create temporary data snapshot file in: {runtime_path}, error: {error}");
+            IggyError::SnapshotFileCompletionFailed
+        })?;
+        let (archive, size) = tokio::task::spawn_blocking(move || build_archive(files, archive))
+            .await
+            .map_err(|error| {
+                error!("Failed to join the data snapshot task, error: {error}");
+                IggyError::SnapshotFileCompletionFailed
+            })?
+            .map_err(|error| {
+                error!("Failed to build the data snapshot archive, error: {error}");
+                IggyError::SnapshotFileCompletionFailed
+            })?;
+        info!(
+            "Created data snapshot of size: {} in {}",
+            IggyByteSize::from(size),
+            IggyDuration::new(now.elapsed())
+        );
+        Ok(DataSnapshot {
+            file: File::from_std(archive),
+            size,
+        })
+    }
+
+    /// Restores the streams and topics from the unpacked data snapshot.
+    /// The streams are reused if they already exist with the same ID and name, while the topics must not exist yet.
+    /// Each state entry is applied right after its stream, topic or consumer group is created,
+    /// so the state stays in sync with the restored resources, even if the restore fails midway.
+    pub async fn restore_data_snapshot(
+        &mut self,
+        session: &Session,
+        snapshot: UnpackedDataSnapshot,
+    ) -> Result<(), IggyError> {
+        self.ensure_authenticated(session)?;
+        let now = Instant::now();
+        let UnpackedDataSnapshot { directory, entries } = snapshot;
+        self.validate_data_snapshot_entries(&entries)?;
+
+        let mut applied_entries = 0;
+        for entry in entries {
+            match entry {
+                EntryCommand::CreateStream(command) => {
+                    if self.streams.contains_key(&command.stream_id) {
+                        continue;
+                    }
+
+                    self.create_stream(session, Some(command.stream_id), &command.command.name)
+                        .await
+                        .with_error_context(|error| {
+                            format!(
+                                "{COMPONENT} (error: {error}) - failed to restore stream with ID: {}",
+                                command.stream_id
+                            )
+                        })?;
+                    self.apply_restored_entry(session, EntryCommand::CreateStream(command))
+                        .await?;
+                    applied_entries += 1;
+                }
+                EntryCommand::CreateTopic(mut command) => {
+                    let topic = self
+                        .create_topic(
+                            session,
+                            &command.command.stream_id,
+                            Some(command.topic_id),
+                            &command.command.name,
+                            command.command.partitions_count,
+                            command.command.message_expiry,
+                            command.command.compression_algorithm,
+                            command.command.max_topic_size,
+                            command.command.replication_factor,
+                            command.command.overrides,
+                        )
+                        .await
+                        .with_error_context(|error| {
+                            format!(
+                                "{COMPONENT} (error: {error}) - failed to restore topic with ID: {} for stream with ID: {}",
+                                command.topic_id, command.command.stream_id
+                            )
+                        })?;
+                    command.command.message_expiry = topic.message_expiry;
+                    command.command.max_topic_size = topic.max_topic_size;
+                    let stream_id = command.command.stream_id.clone();
+                    let topic_id = Identifier::numeric(command.topic_id)?;
+                    self.apply_restored_entry(session, EntryCommand::CreateTopic(command))
+                        .await?;
+                    applied_entries += 1;
+                    let topic = self.get_stream(&stream_id)?.get_topic(&topic_id)?;
+                    restore_topic_files(topic, directory.path()).await?;
+                }
+                EntryCommand::CreateConsumerGroup(command) => {
+                    self.create_consumer_group(
+                        session,
+                        &command.command.stream_id,
+                        &command.command.topic_id,
+                        Some(command.group_id),
+                        &command.command.name,
+                    )
+                    .await
+                    .with_error_context(|error| {
+                        format!(
+                            "{COMPONENT} (error: {error}) - failed to restore consumer group with ID: {} for stream with ID: {} and topic with ID: {}",
+                            command.group_id, command.command.stream_id, command.command.topic_id
+                        )
+                    })?;
+                    self.apply_restored_entry(session, EntryCommand::CreateConsumerGroup(command))
+                        .await?;
+                    applied_entries += 1;
+                }
+                _ => return Err(IggyError::InvalidDataSnapshot),
+            }
+        }
+
+        info!(
+            "Restored data snapshot with {applied_entries} state entries in {}",
+            IggyDuration::new(now.elapsed())
+        );
+        Ok(())
+    }
+
+    async fn apply_restored_entry(
+        &self,
+        session: &Session,
+        entry: EntryCommand,
+    ) -> Result<(), IggyError> {
+        self.state
+            .apply(session.get_user_id(), &entry)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to apply restored state entry: {entry}"
+                )
+            })
+    }
+
+    fn find_data_snapshot_topics(
+        &self,
+        session: &Session,
+        targets: &[DataSnapshotTarget],
+    ) -> Result<Vec<&Topic>, IggyError> {
+        let mut topics = Vec::new();
+        if targets.is_empty() {
+            for stream in self.find_streams(session)? {
+                topics.extend(stream.get_topics());
+            }
+        }
+
+        for target in targets {
+            match &target.topic_id {
+                Some(topic_id) => {
+                    topics.push(self.find_topic(session, &target.stream_id, topic_id)?);
+                }
+                None => topics.extend(self.find_topics(session, &target.stream_id)?),
+            }
+        }
+
+        let mut included_topics = AHashSet::new();
+        topics.retain(|topic| included_topics.insert((topic.stream_id, topic.topic_id)));
+        topics.sort_by_key(|topic| (topic.stream_id, topic.topic_id));
+        for topic in &topics {
+            self.permissioner
+                .poll_messages(session.get_user_id(), topic.stream_id, topic.topic_id)
+                .with_error_context(|error| {
+                    format!(
+                        "{COMPONENT} (error: {error}) - permission denied to create data snapshot of topic with ID: {} in stream with ID: {} for user with ID: {}",
+                        topic.topic_id,
+                        topic.stream_id,
+                        session.get_user_id(),
+                    )
+                })?;
+        }
+        Ok(topics)
+    }
+
+    /// Ensures that the snapshot contains only the supported state entries,
+    /// and that none of them conflicts with the existing streams and topics, before anything gets restored.
+    fn validate_data_snapshot_entries(&self, entries: &[EntryCommand]) -> Result<(), IggyError> {
+        let mut restored_streams = AHashSet::new();
+        for entry in entries {
+            match entry {
+                EntryCommand::CreateStream(command) => {
+                    let name = &command.command.name;
+                    match self.streams.get(&command.stream_id) {
+                        Some(stream) if &stream.name == name => continue,
+                        Some(_) => return Err(IggyError::StreamIdAlreadyExists(command.stream_id)),
+                        None => {}
+                    }
+                    if self.streams_ids.contains_key(name) {
+                        return Err(IggyError::StreamNameAlreadyExists(name.to_owned()));
+                    }
+                    restored_streams.insert(command.stream_id);
+                }
+                EntryCommand::CreateTopic(command) => {
+                    let stream_id = command.command.stream_id.get_u32_value()?;
+                    let Some(stream) = self.streams.get(&stream_id) else {
+                        if restored_streams.contains(&stream_id) {
+                            continue;
+                        }
+                        return Err(IggyError::StreamIdNotFound(stream_id));
+                    };
+                    if stream.topics.contains_key(&command.topic_id) {
+                        return Err(IggyError::TopicIdAlreadyExists(command.topic_id, stream_id));
+                    }
+                    if stream.topics_ids.contains_key(&command.command.name) {
+                        return Err(IggyError::TopicNameAlreadyExists(
+                            command.command.name.to_owned(),
+                            stream_id,
+                        ));
+                    }
+                }
+                EntryCommand::CreateConsumerGroup(_) => {}
+                _ => return Err(IggyError::InvalidDataSnapshot),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Flushes the unsaved messages of all the topic partitions and captures their files,
+/// while all the partitions are locked, so the snapshot is consistent across the whole topic.
+async fn capture_topic_files(topic: &Topic) -> Result<Vec<SnapshotFile>, IggyError> {
+    let mut partitions = topic.get_partitions();
+    let mut partition_guards = Vec::with_capacity(partitions.len());
+    for partition in partitions.iter_mut() {
+        partition_guards.push(partition.write().await);
+    }
+    partition_guards.sort_by_key(|partition| partition.partition_id);
+
+    let mut files = Vec::new();
+    for partition in partition_guards.iter_mut() {
+        partition
+            .flush_unsaved_buffer(true)
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to flush unsaved messages, partition: {partition}")
+            })?;
+
+        let partition_path = PathBuf::from(&partition.partition_path);
+        let (partition_files, partition_directories) =
+            collect_files(&partition_path).await.map_err(|error| {
+                error!(
+                    "Cannot read directory: {}, error: {error}",
+                    partition_path.display()
+                );
+                IggyError::CannotReadPartitions
+            })?;
+        let archive_path = get_partition_archive_path(
+            partition.stream_id,
+            partition.topic_id,
+            partition.partition_id,
+        );
+        files.push(SnapshotFile::Directory {
+            path: archive_path.to_string_lossy().into_owned(),
+        });
+        for relative_path in partition_directories {
+            let path = archive_path
+                .join(&relative_path)
+                .to_string_lossy()
+                .into_owned();
+            files.push(SnapshotFile::Directory { path });
+        }
+        for (relative_path, size) in partition_files {
+            let file_path = partition_path.join(&relative_path);
+            let path = archive_path
+                .join(&relative_path)
+                .to_string_lossy()
+                .into_owned();
+            let cannot_read_file = |error: io::Error| {
+                error!("Cannot read file: {}, error: {error}", file_path.display());
+                IggyError::CannotReadFile
+            };
+            let is_segment_file = matches!(
+                relative_path
+                    .extension()
+                    .and_then(|extension| extension.to_str()),
+                Some(LOG_EXTENSION | INDEX_EXTENSION)
+            );
+            if is_segment_file {
+                let file = File::open(&file_path)
+                    .await
+                    .map_err(cannot_read_file)?
+                    .into_std()
+                    .await;
+                files.push(SnapshotFile::Segment { path, file, size });
+            } else {
+                let data = fs::read(&file_path).await.map_err(cannot_read_file)?;
+                files.push(SnapshotFile::Read { path, data });
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// Replaces the data of all the topic partitions with the files unpacked from the snapshot.
+async fn restore_topic_files(topic: &Topic, unpack_path: &Path) -> Result<(), IggyError> {
+    for partition in topic.get_partitions() {
+        let source_path = {
+            let partition = partition.read().await;
+            unpack_path.join(get_partition_archive_path(
+                partition.stream_id,
+                partition.topic_id,
+                partition.partition_id,
+            ))
+        };
+        if !fs::try_exists(&source_path).await.unwrap_or(false) {
+            error!(
+                "Data snapshot doesn't contain the partition directory: {}",
+                source_path.display()
+            );
+            return Err(IggyError::InvalidDataSnapshot);
+        }
+
+        restore_partition(&partition, &source_path).await?;
+    }
+    Ok(())
+}
+
+async fn create_runtime_directory(config: &SystemConfig) -> Result<String, IggyError> {
+    let runtime_path = config.get_runtime_path();
+    fs::create_dir_all(&runtime_path).await.map_err(|error| {
+        error!("Cannot create directory: {runtime_path}, error: {error}");
+        IggyError::CannotCreateBaseDirectory(runtime_path.clone())
+    })?;
+    Ok(runtime_path)
+}
+
+fn get_partition_archive_path(stream_id: u32, topic_id: u32, partition_id: u32) -> PathBuf {
+    PathBuf::from(format!(
+        "{PARTITIONS_DIRECTORY}/{stream_id}/{topic_id}/{partition_id}"
+    ))
+}
+
+fn read_state_entries(bytes: Bytes) -> Result<Vec<EntryCommand>, IggyError> {
+    let mut entries = Vec::new();
+    let mut position = 0;
+    while position < bytes.len() {
+        if bytes.len() < position + 8 {
+            return Err(IggyError::InvalidDataSnapshot);
+        }
+
+        let length = u32::from_le_bytes(
+            bytes[position + 4..position + 8]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        ) as usize;
+        let end = position + 8 + length;
+        if bytes.len() < end {
+            return Err(IggyError::InvalidDataSnapshot);
+        }
+
+        entries.push(EntryCommand::from_bytes(bytes.slice(position..end))?);
+        position = end;
+    }
+    Ok(entries)
+}
+
+/// Writes the compressed archive to the given file, and returns it rewound to the beginning along with its size.
+fn build_archive(
+    files: Vec<SnapshotFile>,
+    archive: std::fs::File,
+) -> io::Result<(std::fs::File, u64)> {
+    let modified_at = IggyTimestamp::now().to_secs();
+    let encoder = zstd::Encoder::new(archive, zstd::DEFAULT_COMPRESSION_LEVEL)?;
+    let mut builder = tar::Builder::new(encoder);
+    for file in files {
+        let mut header = tar::Header::new_gnu();
+        header.set_mtime(modified_at);
+        match file {
+            SnapshotFile::Segment { path, file, size } => {
+                header.set_size(size);
+                header.set_mode(0o644);
+                let reader = SegmentReader {
+                    file: file.take(size),
+                };
+                builder.append_data(&mut header, path, reader)?;
+            }
+            SnapshotFile::Read { path, data } => {
+                header.set_size(data.len() as u64);
+                header.set_mode(0o644);
+                builder.append_data(&mut header, path, data.as_slice())?;
+            }
+            SnapshotFile::Directory { path } => {
+                header.set_entry_type(tar::EntryType::Directory);
+                header.set_size(0);
+                header.set_mode(0o755);
+                builder.append_data(&mut header, path, io::empty())?;
+            }
+        }
+    }
+    let mut archive = builder.into_inner()?.finish()?;
+    archive.sync_all()?;
+    let size = archive.stream_position()?;
+    archive.rewind()?;
+    Ok((archive, size))
+}
+
+fn unpack_archive(archive: impl Read, path: &Path) -> io::Result<()> {
+    let decoder = zstd::Decoder::new(archive)?;
+    tar::Archive::new(decoder).unpack(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::server::{DataMaintenanceConfig, PersonalAccessTokenConfig};
+    use crate::state::{MockState, StateKind};
+    use crate::streaming::persistence::persister::{FileWithSyncPersister, PersisterKind};
+    use crate::streaming::storage::SystemStorage;
+    use crate::streaming::users::user::User;
+    use iggy_common::defaults::{DEFAULT_ROOT_PASSWORD, DEFAULT_ROOT_USERNAME};
+    use iggy_common::delete_stream::DeleteStream;
+    use std::io::Write;
+    use std::net::{Ipv4Addr, SocketAddr};
+    use std::sync::Arc;
+
+    #[test]
+    fn should_read_state_entries_given_valid_bytes() {
+        let entries = vec![
+            create_stream_entry(1, "stream"),
+            create_topic_entry(1, 2, "topic"),
+        ];
+        let mut bytes = BytesMut::new();
+        for entry in &entries {
+            bytes.put_slice(&entry.to_bytes());
+        }
+
+        let read_entries = read_state_entries(bytes.freeze()).unwrap();
+
+        assert_eq!(read_entries, entries);
+    }
+
+    #[test]
+    fn should_fail_to_read_state_entries_given_truncated_bytes() {
+        let bytes = create_stream_entry(1, "stream").to_bytes();
+
+        for length in [4, bytes.len() - 1] {
+            let result = read_state_entries(bytes.slice(..length));
+            assert!(matches!(result, Err(IggyError::InvalidDataSnapshot)));
+        }
+    }
+
+    #[test]
+    fn segment_reader_should_yield_only_the_captured_size() {
+        let mut file = tempfile::tempfile().unwrap();
+        file.write_all(b"0123456789").unwrap();
+        file.rewind().unwrap();
+        let mut reader = SegmentReader { file: file.take(4) };
+
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data).unwrap();
+
+        assert_eq!(data, b"0123");
+    }
+
+    #[test]
+    fn segment_reader_should_fail_given_file_shorter_than_captured_size() {
+        let mut file = tempfile::tempfile().unwrap();
+        file.write_all(b"0123").unwrap();
+        file.rewind().unwrap();
+        let mut reader = SegmentReader {
+            file: file.take(10),
+        };
+
+        let mut data = Vec::new();
+        let error = reader.read_to_end(&mut data).unwrap_err();
+
+        assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[tokio::test]
+    async fn should_unpack_uploaded_archive_given_valid_data_snapshot() {
+        let tempdir = TempDir::new().unwrap();
+        let config = SystemConfig {
+            path: tempdir.path().to_str().unwrap().to_string(),
+            ..Default::default()
+        };
+        let entries = vec![create_stream_entry(1, "stream")];
+        let files = vec![
+            SnapshotFile::Read {
+                path: STATE_ENTRY.to_owned(),
+                data: entries[0].to_bytes().to_vec(),
+            },
+            SnapshotFile::Directory {
+                path: get_partition_archive_path(1, 1, 1)
+                    .to_string_lossy()
+                    .into_owned(),
+            },
+        ];
+        let (mut archive, _) = build_archive(files, tempfile::tempfile().unwrap()).unwrap();
+        let mut archive_bytes = Vec::new();
+        archive.read_to_end(&mut archive_bytes).unwrap();
+
+        let mut upload = DataSnapshotUpload::create(&config).await.unwrap();
+        for chunk in archive_bytes.chunks(16) {
+            upload.write(chunk).await.unwrap();
+        }
+        let snapshot = upload.unpack(&config).await.unwrap();
+
+        assert_eq!(snapshot.entries, entries);
+        assert!(
+            snapshot
+                .directory
+                .path()
+                .join(get_partition_archive_path(1, 1, 1))
+                .is_dir()
+        );
+    }
+
+    #[tokio::test]
+    async fn should_fail_to_unpack_given_empty_or_invalid_upload() {
+        let tempdir = TempDir::new().unwrap();
+        let config = SystemConfig {
+            path: tempdir.path().to_str().unwrap().to_string(),
+            ..Default::default()
+        };
+
+        let upload = DataSnapshotUpload::create(&config).await.unwrap();
+        let result = upload.unpack(&config).await;
+        assert!(matches!(result, Err(IggyError::InvalidCommand)));
+
+        let mut upload = DataSnapshotUpload::create(&config).await.unwrap();
+        upload.write(b"invalid").await.unwrap();
+        let result = upload.unpack(&config).await;
+        assert!(matches!(result, Err(IggyError::InvalidDataSnapshot)));
+    }
+
+    #[tokio::test]
+    async fn should_validate_data_snapshot_entries_against_existing_streams() {
+        let tempdir = TempDir::new().unwrap();
+        let system = create_system_with_stream(&tempdir, 1, "stream").await;
+
+        let entries = vec![
+            create_stream_entry(1, "stream"),
+            create_topic_entry(1, 1, "topic"),
+            create_stream_entry(2, "other"),
+            create_topic_entry(2, 1, "topic"),
+        ];
+        assert!(system.validate_data_snapshot_entries(&entries).is_ok());
+
+        let entries = vec![create_stream_entry(1, "other")];
+        let result = system.validate_data_snapshot_entries(&entries);
+        assert!(matches!(result, Err(IggyError::StreamIdAlreadyExists(1))));
+
+        let entries = vec![create_stream_entry(2, "stream")];
+        let result = system.validate_data_snapshot_entries(&entries);
+        assert!(matches!(result, Err(IggyError::StreamNameAlreadyExists(_))));
+
+        let entries = vec![create_topic_entry(3, 1, "topic")];
+        let result = system.validate_data_snapshot_entries(&entries);
+        assert!(matches!(result, Err(IggyError::StreamIdNotFound(3))));
+
+        let entries = vec![EntryCommand::DeleteStream(DeleteStream {
+            stream_id: Identifier::numeric(1).unwrap(),
+        })];
+        let result = system.validate_data_snapshot_entries(&entries);
+        assert!(matches!(result, Err(IggyError::InvalidDataSnapshot)));
+    }
+
+    async fn create_system_with_stream(tempdir: &TempDir, stream_id: u32, name: &str) -> System {
+        let config = Arc::new(SystemConfig {
+            path: tempdir.path().to_str().unwrap().to_string(),
+            ..Default::default()
+        });
+        let storage = SystemStorage::new(
+            config.clone(),
+            Arc::new(PersisterKind::FileWithSync(FileWithSyncPersister {})),
+        );
+        let mut system = System::create(
+            config,
+            storage,
+            Arc::new(StateKind::Mock(MockState::new())),
+            None,
+            DataMaintenanceConfig::default(),
+            PersonalAccessTokenConfig::default(),
+        );
+        let root = User::root(DEFAULT_ROOT_USERNAME, DEFAULT_ROOT_PASSWORD);
+        let permissions = root.permissions.clone();
+        let session = Session::new(
+            1,
+            root.id,
+            SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 1234),
+        );
+        system
+            .permissioner
+            .init_permissions_for_user(root.id, permissions);
+        system
+            .create_stream(&session, Some(stream_id), name)
+            .await
+            .unwrap();
+        system
+    }
+
+    fn create_stream_entry(stream_id: u32, name: &str) -> EntryCommand {
+        EntryCommand::CreateStream(CreateStreamWithId {
+            stream_id,
+            command: CreateStream {
+                stream_id: Some(stream_id),
+                name: name.to_owned(),
+            },
+        })
+    }
+
+    fn create_topic_entry(stream_id: u32, topic_id: u32, name: &str) -> EntryCommand {
+        EntryCommand::CreateTopic(CreateTopicWithId {
+            topic_id,
+            command: CreateTopic {
+                stream_id: Identifier::numeric(stream_id).unwrap(),
+                topic_id: Some(topic_id),
+                name: name.to_owned(),
+                ..Default::default()
+            },
+        })
+    }
+}
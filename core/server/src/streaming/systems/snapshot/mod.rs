@@ -16,8 +16,11 @@
  * under the License.
  */
 
+mod data;
 mod procdump;
 
+pub use data::{DATA_SNAPSHOT_CHUNK_SIZE, DataSnapshotUpload, UnpackedDataSnapshot};
+
 use crate::configs::system::SystemConfig;
 use crate::streaming::session::Session;
 use crate::streaming::systems::system::System;
@@ -333,6 +333,7 @@ impl System {
         self.streams_ids.insert(name.to_owned(), stream.stream_id);
         self.streams.insert(stream.stream_id, stream);
         self.metrics.increment_streams(1);
+        self.notify_topics_changed();
         self.get_stream_by_id(id)
     }
 
@@ -383,6 +384,7 @@ impl System {
             self.streams_ids.remove(&old_name);
             self.streams_ids.insert(name.to_owned(), stream_id);
         }
+        self.notify_topics_changed();
 
         info!("Stream with ID '{id}' updated. Old name: '{old_name}' changed to: '{name}'.");
         Ok(())
@@ -420,6 +422,7 @@ impl System {
         self.metrics.decrement_segments(stream.get_segments_count());
         self.streams.remove(&stream_id);
         self.streams_ids.remove(&stream_name);
+        self.notify_topics_changed();
         let current_stream_id = CURRENT_STREAM_ID.load(Ordering::SeqCst);
         if current_stream_id > stream_id {
             CURRENT_STREAM_ID.store(stream_id, Ordering::SeqCst);
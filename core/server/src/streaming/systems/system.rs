@@ -41,7 +41,7 @@ use iggy_common::{Aes256GcmEncryptor, EncryptorKind, IggyError, UserId};
 use std::path::Path;
 use std::sync::Arc;
 use tokio::fs::{create_dir_all, remove_dir_all};
-use tokio::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+use tokio::sync::{Notify, RwLock, RwLockReadGuard, RwLockWriteGuard};
 use tokio::time::Instant;
 use tracing::{error, info, instrument, trace};
 
@@ -87,6 +87,8 @@ pub struct System {
     pub(crate) metrics: Metrics,
     pub(crate) state: Arc<StateKind>,
     pub(crate) archiver: Option<Arc<ArchiverKind>>,
+    pub(crate) topics_version: u64,
+    pub(crate) topics_changed: Arc<Notify>,
     pub personal_access_token: PersonalAccessTokenConfig,
 }
 
@@ -181,6 +183,8 @@ impl System {
             state,
             personal_access_token: pat_config,
             archiver,
+            topics_version: 1,
+            topics_changed: Arc::new(Notify::new()),
         }
     }
 
@@ -22,7 +22,11 @@ use crate::streaming::systems::system::System;
 use crate::streaming::topics::topic::Topic;
 use error_set::ErrContext;
 use iggy_common::locking::IggySharedMutFn;
-use iggy_common::{CompressionAlgorithm, Identifier, IggyError, IggyExpiry, MaxTopicSize};
+use iggy_common::{
+    CompressionAlgorithm, Identifier, IggyError, IggyExpiry, MaxTopicSize, TopicOverrides,
+};
+use std::sync::Arc;
+use tokio::sync::Notify;
 
 impl System {
     pub fn find_topic(
@@ -104,6 +108,18 @@ impl System {
         Ok(Some(topic))
     }
 
+    /// Returns the current topics version along with the notifier which is triggered whenever it changes,
+    /// i.e. when any topic or stream is created, renamed or deleted.
+    pub fn get_topics_version(&self, session: &Session) -> Result<(u64, Arc<Notify>), IggyError> {
+        self.ensure_authenticated(session)?;
+        Ok((self.topics_version, self.topics_changed.clone()))
+    }
+
+    pub(crate) fn notify_topics_changed(&mut self) {
+        self.topics_version += 1;
+        self.topics_changed.notify_waiters();
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub async fn create_topic(
         &mut self,
@@ -116,6 +132,7 @@ impl System {
         compression_algorithm: CompressionAlgorithm,
         max_topic_size: MaxTopicSize,
         replication_factor: Option<u8>,
+        overrides: TopicOverrides,
     ) -> Result<&Topic, IggyError> {
         self.ensure_authenticated(session)?;
         {
@@ -142,6 +159,7 @@ impl System {
                 compression_algorithm,
                 max_topic_size,
                 replication_factor.unwrap_or(1),
+                overrides,
             )
             .await
             .with_error_context(|error| {
@@ -151,6 +169,7 @@ impl System {
         self.metrics.increment_topics(1);
         self.metrics.increment_partitions(partitions_count);
         self.metrics.increment_segments(partitions_count);
+        self.notify_topics_changed();
 
         self.get_stream(stream_id)
             .with_error_context(|error| {
@@ -175,6 +194,7 @@ impl System {
         compression_algorithm: CompressionAlgorithm,
         max_topic_size: MaxTopicSize,
         replication_factor: Option<u8>,
+        overrides: Option<TopicOverrides>,
     ) -> Result<&Topic, IggyError> {
         self.ensure_authenticated(session)?;
         {
@@ -207,6 +227,7 @@ impl System {
                 compression_algorithm,
                 max_topic_size,
                 replication_factor.unwrap_or(1),
+                overrides,
             )
             .await
             .with_error_context(|error| {
@@ -214,6 +235,7 @@ impl System {
                     "{COMPONENT} (error: {error}) - failed to update topic with ID: {topic_id} in stream with ID: {stream_id}",
                 )
             })?;
+        self.notify_topics_changed();
 
         // TODO: if message_expiry is changed, we need to check if we need to purge messages based on the new expiry
         // TODO: if max_size_bytes is changed, we need to check if we need to purge messages based on the new size
@@ -267,6 +289,7 @@ impl System {
         self.metrics.decrement_messages(topic.get_messages_count());
         self.metrics
             .decrement_segments(topic.get_segments_count().await);
+        self.notify_topics_changed();
         let client_manager = self.client_manager.read().await;
         client_manager
             .delete_consumer_groups_for_topic(stream_id_value, topic.topic_id)
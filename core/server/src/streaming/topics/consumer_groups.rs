@@ -237,7 +237,7 @@ mod tests {
     use crate::streaming::persistence::persister::{FileWithSyncPersister, PersisterKind};
     use crate::streaming::storage::SystemStorage;
     use crate::streaming::utils::MemoryPool;
-    use iggy_common::{CompressionAlgorithm, IggyExpiry, MaxTopicSize};
+    use iggy_common::{CompressionAlgorithm, IggyExpiry, MaxTopicSize, TopicOverrides};
     use std::sync::Arc;
     use std::sync::atomic::{AtomicU32, AtomicU64};
 
@@ -419,6 +419,7 @@ mod tests {
             compression_algorithm,
             MaxTopicSize::ServerDefault,
             1,
+            TopicOverrides::default(),
         )
         .await
         .unwrap()
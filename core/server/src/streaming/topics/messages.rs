@@ -25,8 +25,11 @@ use crate::streaming::utils::hash;
 use ahash::AHashMap;
 use error_set::ErrContext;
 use iggy_common::locking::IggySharedMutFn;
-use iggy_common::{Confirmation, IggyTimestamp, PollingStrategy};
-use iggy_common::{IggyError, IggyExpiry, Partitioning, PartitioningKind, PollingKind};
+use iggy_common::{
+    Confirmation, IggyTimestamp, PollingStrategy, ProducerSequence,
+    calculate_partition_id_by_consistent_hash,
+};
+use iggy_common::{IggyError, Partitioning, PartitioningKind, PollingKind};
 use std::sync::atomic::Ordering;
 use tracing::trace;
 
@@ -80,6 +83,7 @@ impl Topic {
         &self,
         partitioning: &Partitioning,
         messages: IggyMessagesBatchMut,
+        producer_sequence: Option<ProducerSequence>,
         confirmation: Option<Confirmation>,
     ) -> Result<(), IggyError> {
         if !self.has_partitions() {
@@ -96,6 +100,11 @@ impl Topic {
             return Ok(());
         }
 
+        // The copied messages must precede the new ones in the partitions they're copied to.
+        if self.copying_partitions {
+            return Err(IggyError::PartitionsDraining(self.topic_id, self.stream_id));
+        }
+
         let partition_id = match partitioning.kind {
             PartitioningKind::Balanced => self.get_next_partition_id(),
             PartitioningKind::PartitionId => u32::from_le_bytes(
@@ -106,9 +115,22 @@ impl Topic {
             PartitioningKind::MessagesKey => {
                 self.calculate_partition_id_by_messages_key_hash(&partitioning.value)
             }
+            PartitioningKind::ConsistentMessagesKey => {
+                self.calculate_partition_id_by_messages_key_consistent_hash(&partitioning.value)
+            }
         };
 
-        self.append_messages_to_partition(messages, partition_id, confirmation)
+        if partition_id > self.get_active_partitions_count()
+            && self.partitions.contains_key(&partition_id)
+        {
+            return Err(IggyError::PartitionDraining(
+                partition_id,
+                self.topic_id,
+                self.stream_id,
+            ));
+        }
+
+        self.append_messages_to_partition(messages, partition_id, producer_sequence, confirmation)
             .await
     }
 
@@ -134,29 +156,38 @@ impl Topic {
         &self,
         messages: IggyMessagesBatchMut,
         partition_id: u32,
+        producer_sequence: Option<ProducerSequence>,
         confirmation: Option<Confirmation>,
     ) -> Result<(), IggyError> {
         let partition = self.partitions.get(&partition_id);
-        partition
+        let mut partition = partition
             .ok_or(IggyError::PartitionNotFound(
                 partition_id,
                 self.topic_id,
                 self.stream_id,
             ))?
             .write()
-            .await
-            .append_messages(messages, confirmation)
-            .await
-            .with_error_context(|error| {
-                format!("{COMPONENT} (error: {error}) - failed to append messages")
-            })?;
+            .await;
+        match producer_sequence {
+            Some(producer_sequence) => {
+                partition
+                    .append_messages_with_sequence(messages, &producer_sequence, confirmation)
+                    .await
+            }
+            None => partition.append_messages(messages, confirmation).await,
+        }
+        .with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to append messages")
+        })?;
+        drop(partition);
 
+        self.new_messages.notify_waiters();
         Ok(())
     }
 
     fn get_next_partition_id(&self) -> u32 {
         let mut partition_id = self.current_partition_id.fetch_add(1, Ordering::SeqCst);
-        let partitions_count = self.partitions.len() as u32;
+        let partitions_count = self.get_active_partitions_count();
         if partition_id > partitions_count {
             partition_id = 1;
             self.current_partition_id
@@ -166,13 +197,10 @@ impl Topic {
         partition_id
     }
 
-    fn calculate_partition_id_by_messages_key_hash(&self, messages_key: &[u8]) -> u32 {
+    pub(crate) fn calculate_partition_id_by_messages_key_hash(&self, messages_key: &[u8]) -> u32 {
         let messages_key_hash = hash::calculate_32(messages_key);
-        let partitions_count = self.get_partitions_count();
-        let mut partition_id = messages_key_hash % partitions_count;
-        if partition_id == 0 {
-            partition_id = partitions_count;
-        }
+        let partition_id =
+            calculate_partition_id_by_hash(messages_key_hash, self.get_active_partitions_count());
         trace!(
             "Calculated partition ID: {} for messages key: {:?}, hash: {}",
             partition_id, messages_key, messages_key_hash
@@ -180,24 +208,46 @@ impl Topic {
         partition_id
     }
 
+    fn calculate_partition_id_by_messages_key_consistent_hash(&self, messages_key: &[u8]) -> u32 {
+        let messages_key_hash = hash::calculate_32(messages_key);
+        let partition_id = calculate_partition_id_by_consistent_hash(
+            messages_key_hash as u64,
+            self.get_active_partitions_count(),
+        );
+        trace!(
+            "Calculated partition ID: {} for messages key: {:?} using consistent hash, hash: {}",
+            partition_id, messages_key, messages_key_hash
+        );
+        partition_id
+    }
+
     pub async fn get_expired_segments_start_offsets_per_partition(
         &self,
         now: IggyTimestamp,
     ) -> AHashMap<u32, Vec<u64>> {
+        // Even if the topic messages never expire, the segments might contain only the messages with TTL.
         let mut expired_segments = AHashMap::new();
-        if let IggyExpiry::ExpireDuration(_) = self.message_expiry {
-            for (_, partition) in self.partitions.iter() {
-                let partition = partition.read().await;
-                let segments = partition.get_expired_segments_start_offsets(now).await;
-                if !segments.is_empty() {
-                    expired_segments.insert(partition.partition_id, segments);
-                }
+        for (_, partition) in self.partitions.iter() {
+            let partition = partition.read().await;
+            let segments = partition.get_expired_segments_start_offsets(now).await;
+            if !segments.is_empty() {
+                expired_segments.insert(partition.partition_id, segments);
             }
         }
         expired_segments
     }
 }
 
+/// Returns the partition ID (starting from 1) for the hash of the messages key, by the modulo of the partitions count.
+pub(crate) fn calculate_partition_id_by_hash(messages_key_hash: u32, partitions_count: u32) -> u32 {
+    let partition_id = messages_key_hash % partitions_count;
+    if partition_id == 0 {
+        partitions_count
+    } else {
+        partition_id
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -208,7 +258,7 @@ mod tests {
     use crate::streaming::utils::MemoryPool;
     use bytes::Bytes;
     use iggy_common::CompressionAlgorithm;
-    use iggy_common::{IggyMessage, MaxTopicSize};
+    use iggy_common::{IggyExpiry, IggyMessage, MaxTopicSize, TopicOverrides};
     use std::sync::Arc;
     use std::sync::atomic::AtomicU32;
     use std::sync::atomic::AtomicU64;
@@ -229,7 +279,7 @@ mod tests {
                 .expect("Failed to create message with valid payload and headers");
             let messages = IggyMessagesBatchMut::from_messages(&[message], 1);
             topic
-                .append_messages(&partitioning, messages, None)
+                .append_messages(&partitioning, messages, None, None)
                 .await
                 .unwrap();
         }
@@ -262,7 +312,7 @@ mod tests {
                 .expect("Failed to create message with valid payload and headers");
             let messages = IggyMessagesBatchMut::from_messages(&[message], 1);
             topic
-                .append_messages(&partitioning, messages, None)
+                .append_messages(&partitioning, messages, None, None)
                 .await
                 .unwrap();
         }
@@ -319,6 +369,26 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn given_multiple_partitions_calculate_partition_id_by_consistent_hash_should_return_jump_hash_partition_id()
+     {
+        let partitions_count = 3;
+        let messages_count = 1000;
+        let topic = init_topic(partitions_count).await;
+
+        for entity_id in 1..=messages_count {
+            let key = Partitioning::consistent_messages_key_u64(entity_id);
+            let partition_id =
+                topic.calculate_partition_id_by_messages_key_consistent_hash(&key.value);
+            let entity_id_hash = hash::calculate_32(&key.value);
+            let expected_partition_id =
+                calculate_partition_id_by_consistent_hash(entity_id_hash as u64, partitions_count);
+
+            assert!((1..=partitions_count).contains(&partition_id));
+            assert_eq!(partition_id, expected_partition_id);
+        }
+    }
+
     async fn init_topic(partitions_count: u32) -> Topic {
         let tempdir = tempfile::TempDir::new().unwrap();
         let config = Arc::new(SystemConfig {
@@ -352,6 +422,7 @@ mod tests {
             compression_algorithm,
             MaxTopicSize::ServerDefault,
             1,
+            TopicOverrides::default(),
         )
         .await
         .unwrap();
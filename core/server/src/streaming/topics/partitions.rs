@@ -16,14 +16,29 @@
  * under the License.
  */
 
+use crate::binary::handlers::messages::poll_messages_handler::IggyPollMetadata;
+use crate::configs::partition_placement::PartitionPlacement;
+use crate::configs::system::SystemConfig;
 use crate::streaming::partitions::partition::Partition;
+use crate::streaming::segments::IggyMessagesBatchMut;
 use crate::streaming::topics::COMPONENT;
+use crate::streaming::topics::messages::calculate_partition_id_by_hash;
 use crate::streaming::topics::topic::Topic;
+use crate::streaming::utils::hash;
+use ahash::{AHashMap, AHashSet};
 use error_set::ErrContext;
 use iggy_common::locking::{IggySharedMut, IggySharedMutFn};
-use iggy_common::{IggyError, IggyTimestamp};
+use iggy_common::{
+    HeaderKey, IggyDuration, IggyError, IggyMessage, IggyTimestamp, Sizeable,
+    calculate_partition_id_by_consistent_hash,
+};
+use nix::sys::statvfs::statvfs;
+use std::sync::Arc;
+use tokio::sync::Notify;
+use tracing::warn;
 
 const MAX_PARTITIONS_COUNT: u32 = 100_000;
+const COPIED_MESSAGES_BATCH_SIZE: u32 = 1000;
 
 impl Topic {
     pub fn has_partitions(&self) -> bool {
@@ -34,11 +49,64 @@ impl Topic {
         self.partitions.len() as u32
     }
 
+    /// Returns the number of partitions accepting new messages, i.e. excluding the ones being drained.
+    pub fn get_active_partitions_count(&self) -> u32 {
+        self.get_partitions_count() - self.draining_partitions_count
+    }
+
+    pub fn get_draining_partitions_count(&self) -> u32 {
+        self.draining_partitions_count
+    }
+
+    /// Returns the config of the partition placed in the given data directory, based on the topic config.
+    pub fn get_partition_config(
+        topic_config: &Arc<SystemConfig>,
+        directory: &str,
+    ) -> Arc<SystemConfig> {
+        if topic_config.path == directory {
+            return topic_config.clone();
+        }
+
+        Arc::new(topic_config.with_data_directory(directory))
+    }
+
+    /// Returns the data directory for the new partition, based on the configured placement policy.
+    // The types of the filesystem statistics differ between the platforms.
+    #[allow(clippy::unnecessary_cast)]
+    pub fn place_partition(&self, partition_id: u32) -> String {
+        let mut directories = self.config.get_data_directories();
+        if directories.len() == 1 {
+            return directories.remove(0);
+        }
+
+        match self.config.partition.placement {
+            PartitionPlacement::RoundRobin => {
+                let index = (partition_id.saturating_sub(1) as usize) % directories.len();
+                directories.swap_remove(index)
+            }
+            PartitionPlacement::MostFreeSpace => directories
+                .into_iter()
+                .max_by_key(|directory| match statvfs(directory.as_str()) {
+                    Ok(stats) => stats.blocks_available() as u64 * stats.fragment_size() as u64,
+                    Err(error) => {
+                        warn!("Cannot get the available space of data directory: {directory}, error: {error}");
+                        0
+                    }
+                })
+                .unwrap_or_else(|| self.config.get_system_path()),
+        }
+    }
+
     pub async fn add_partitions(&mut self, count: u32) -> Result<Vec<u32>, IggyError> {
         if count == 0 {
             return Ok(vec![]);
         }
 
+        // The partitions being drained must remain the last ones, until they are deleted.
+        if self.draining_partitions_count > 0 {
+            return Err(IggyError::PartitionsDraining(self.topic_id, self.stream_id));
+        }
+
         let current_partitions_count = self.partitions.len() as u32;
         if current_partitions_count + count > MAX_PARTITIONS_COUNT {
             return Err(IggyError::TooManyPartitions);
@@ -51,7 +119,7 @@ impl Topic {
                 self.topic_id,
                 partition_id,
                 true,
-                self.config.clone(),
+                Topic::get_partition_config(&self.config, &self.place_partition(partition_id)),
                 self.storage.clone(),
                 self.message_expiry,
                 self.messages_count_of_parent_stream.clone(),
@@ -103,6 +171,7 @@ impl Topic {
             count = current_partitions_count;
         }
 
+        self.draining_partitions_count = self.draining_partitions_count.saturating_sub(count);
         let mut segments_count = 0;
         let mut messages_count = 0;
         for partition_id in current_partitions_count - count + 1..=current_partitions_count {
@@ -123,6 +192,332 @@ impl Topic {
             messages_count,
         }))
     }
+
+    /// Marks the last active partitions as draining, so they no longer accept new messages,
+    /// but can still be consumed until they are deleted.
+    pub fn drain_partitions(&mut self, count: u32) -> Result<(), IggyError> {
+        if count >= self.get_active_partitions_count() {
+            return Err(IggyError::CannotShrinkPartitions(
+                count,
+                self.topic_id,
+                self.stream_id,
+            ));
+        }
+
+        self.draining_partitions_count += count;
+        self.draining_started_at = IggyTimestamp::now();
+        Ok(())
+    }
+
+    /// Returns the IDs (starting from the last one) of the last draining partitions which have been consumed by all the consumer groups
+    /// and all the consumers with the stored offsets. The partitions without any of them are considered drained
+    /// only once the retention since the draining started elapses, as it's unknown whether they were consumed.
+    /// The partitions can only be deleted from the end, thus the search stops at the first partition which is not drained yet.
+    pub async fn get_drained_partition_ids(&self, retention: IggyDuration) -> Vec<u32> {
+        // The copied partitions are deleted once the copy is finished.
+        if self.copying_partitions {
+            return Vec::new();
+        }
+
+        let consumer_group_ids = self.consumer_groups.keys().copied().collect::<Vec<_>>();
+        let retention_elapsed = self.draining_started_at.as_micros() + retention.as_micros()
+            <= IggyTimestamp::now().as_micros();
+        let mut drained_partition_ids = Vec::new();
+        for partition_id in
+            (self.get_active_partitions_count() + 1..=self.get_partitions_count()).rev()
+        {
+            let Some(partition) = self.partitions.get(&partition_id) else {
+                break;
+            };
+
+            let partition = partition.read().await;
+            let drained = partition.get_messages_count() == 0
+                || match get_unconsumed_offset(&partition, &consumer_group_ids) {
+                    Some(offset) => offset > partition.current_offset,
+                    None => retention_elapsed,
+                };
+            if !drained {
+                break;
+            }
+
+            drained_partition_ids.push(partition_id);
+        }
+        drained_partition_ids
+    }
+
+    /// Deletes the drained partition, which is removed from the topic only once its data has been deleted,
+    /// so the failed deletion can be retried. Returns `None` if the partition has been already deleted.
+    pub async fn delete_drained_partition(
+        &mut self,
+        partition_id: u32,
+    ) -> Result<Option<DeletedPartitions>, IggyError> {
+        let Some(partition) = self.partitions.get(&partition_id).cloned() else {
+            return Ok(None);
+        };
+
+        let mut partition = partition.write().await;
+        let deleted_partitions = DeletedPartitions {
+            segments_count: partition.get_segments_count(),
+            messages_count: partition.get_messages_count(),
+        };
+        partition.delete().await.with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to delete drained partition with ID: {partition_id} in topic with ID: {}",
+                self.topic_id
+            )
+        })?;
+        self.partitions.remove(&partition_id);
+        self.draining_partitions_count = self.draining_partitions_count.saturating_sub(1);
+        Ok(Some(deleted_partitions))
+    }
+
+    /// Returns whether the unconsumed messages of the draining partitions are being copied.
+    pub fn is_copying_partitions(&self) -> bool {
+        self.copying_partitions
+    }
+
+    /// Starts copying the unconsumed messages of the draining partitions into the active ones.
+    /// Until the copy is finished, the topic doesn't accept new messages, so the copied messages
+    /// precede the ones sent afterwards, and its partitions can't be drained or deleted.
+    pub fn start_unconsumed_messages_copy(
+        &mut self,
+        partitions_count: u32,
+        key_header: Option<&str>,
+        consistent_hashing: bool,
+    ) -> Result<UnconsumedMessagesCopy, IggyError> {
+        let key_header = key_header.map(HeaderKey::new).transpose()?;
+        let active_partitions_count = self.get_active_partitions_count();
+        let mut source_partitions = Vec::new();
+        for partition_id in active_partitions_count + 1..=self.get_partitions_count() {
+            source_partitions.push((partition_id, self.get_partition(partition_id)?));
+        }
+        let mut target_partitions = AHashMap::new();
+        for partition_id in 1..=active_partitions_count {
+            target_partitions.insert(partition_id, self.get_partition(partition_id)?);
+        }
+
+        self.copying_partitions = true;
+        Ok(UnconsumedMessagesCopy {
+            stream_id: self.stream_id,
+            topic_id: self.topic_id,
+            partitions_count,
+            source_partitions,
+            target_partitions,
+            consumer_group_ids: self.consumer_groups.keys().copied().collect(),
+            key_header,
+            consistent_hashing,
+            new_messages: self.new_messages.clone(),
+        })
+    }
+
+    /// Finishes the copy of the unconsumed messages, so the topic accepts new messages again.
+    pub fn finish_unconsumed_messages_copy(&mut self) {
+        self.copying_partitions = false;
+    }
+}
+
+/// The copy of the messages of the draining partitions, which haven't been consumed by all the consumer groups
+/// and the consumers yet, into the active partitions. It runs without the system lock, each partition is locked
+/// only for reading or appending a single batch of messages.
+pub struct UnconsumedMessagesCopy {
+    pub stream_id: u32,
+    pub topic_id: u32,
+    /// The number of partitions drained by the shrinking, which started the copy.
+    pub partitions_count: u32,
+    source_partitions: Vec<(u32, IggySharedMut<Partition>)>,
+    target_partitions: AHashMap<u32, IggySharedMut<Partition>>,
+    consumer_group_ids: Vec<u32>,
+    key_header: Option<HeaderKey>,
+    consistent_hashing: bool,
+    new_messages: Arc<Notify>,
+}
+
+impl UnconsumedMessagesCopy {
+    /// Copies the unconsumed messages and returns the number of copied messages.
+    /// The messages are copied in order, either to the partition calculated from the value of the key header,
+    /// the same way as for the (consistent) messages key partitioning, or to a single active partition for each draining one,
+    /// when the key header is not provided or the message doesn't contain it.
+    /// The offsets of the copied messages are stored by each target partition and persisted together with its messages,
+    /// so when the copy is retried after the failure or the server restart, the messages copied already are skipped.
+    pub async fn run(&self) -> Result<u64, IggyError> {
+        let active_partitions_count = self.target_partitions.len() as u32;
+        let mut target_partition_ids = AHashSet::new();
+        let mut copied_messages_count = 0;
+        for (partition_id, partition) in &self.source_partitions {
+            let partition_id = *partition_id;
+            // The draining partition doesn't accept new messages, so its current offset doesn't change anymore.
+            let (mut offset, current_offset, created_at) = {
+                let partition = partition.read().await;
+                if partition.get_messages_count() == 0 {
+                    continue;
+                }
+
+                let mut offset =
+                    get_unconsumed_offset(&partition, &self.consumer_group_ids).unwrap_or(0);
+                if let Some(segment) = partition.segments.first() {
+                    offset = offset.max(segment.start_offset());
+                }
+                (offset, partition.current_offset, partition.created_at)
+            };
+            let mut copied_offsets = AHashMap::new();
+            for (target_partition_id, target_partition) in &self.target_partitions {
+                if let Some(copied_offset) = target_partition
+                    .read()
+                    .await
+                    .copied_offsets
+                    .get(partition_id, created_at)
+                {
+                    copied_offsets.insert(*target_partition_id, copied_offset);
+                }
+            }
+
+            let default_partition_id = (partition_id - 1) % active_partitions_count + 1;
+            while offset <= current_offset {
+                let (messages, last_offset) = {
+                    let partition = partition.read().await;
+                    let batches = partition
+                        .get_messages_by_offset(offset, COPIED_MESSAGES_BATCH_SIZE)
+                        .await
+                        .with_error_context(|error| {
+                            format!(
+                                "{COMPONENT} (error: {error}) - failed to get messages to copy from partition with ID: {partition_id}, offset: {offset}"
+                            )
+                        })?;
+                    let Some(last_offset) = batches.last_offset() else {
+                        break;
+                    };
+
+                    let messages = batches
+                        .into_polled_messages(IggyPollMetadata::new(partition_id, current_offset))
+                        .messages;
+                    (messages, last_offset)
+                };
+
+                let mut partitioned_messages: AHashMap<u32, Vec<IggyMessage>> = AHashMap::new();
+                for message in messages {
+                    let target_partition_id = self
+                        .key_header
+                        .as_ref()
+                        .and_then(|key_header| {
+                            message
+                                .user_headers_map()
+                                .ok()
+                                .flatten()
+                                .and_then(|headers| headers.get(key_header).cloned())
+                        })
+                        .map(|key| self.calculate_target_partition_id(&key.value))
+                        .unwrap_or(default_partition_id);
+                    if copied_offsets
+                        .get(&target_partition_id)
+                        .is_some_and(|copied_offset| message.header.offset <= *copied_offset)
+                    {
+                        continue;
+                    }
+
+                    partitioned_messages
+                        .entry(target_partition_id)
+                        .or_default()
+                        .push(message);
+                }
+
+                for (target_partition_id, messages) in partitioned_messages {
+                    let Some(last_copied_offset) =
+                        messages.last().map(|message| message.header.offset)
+                    else {
+                        continue;
+                    };
+                    let messages_count = messages.len() as u64;
+                    let messages_size = messages
+                        .iter()
+                        .map(|message| message.get_size_bytes().as_bytes_u32())
+                        .sum();
+                    let batch = IggyMessagesBatchMut::from_messages(&messages, messages_size);
+                    let target_partition = self.get_target_partition(target_partition_id)?;
+                    target_partition
+                        .write()
+                        .await
+                        .append_copied_messages(batch, partition_id, created_at, last_copied_offset)
+                        .await
+                        .with_error_context(|error| {
+                            format!(
+                                "{COMPONENT} (error: {error}) - failed to copy messages from partition with ID: {partition_id} to partition with ID: {target_partition_id}"
+                            )
+                        })?;
+                    copied_offsets.insert(target_partition_id, last_copied_offset);
+                    copied_messages_count += messages_count;
+                    target_partition_ids.insert(target_partition_id);
+                }
+                offset = last_offset + 1;
+            }
+        }
+
+        // The source partitions are deleted right after, so the copied messages must not remain only in memory.
+        for partition_id in target_partition_ids {
+            self.get_target_partition(partition_id)?
+                .write()
+                .await
+                .flush_unsaved_buffer(true)
+                .await?;
+        }
+
+        if copied_messages_count > 0 {
+            self.new_messages.notify_waiters();
+        }
+        Ok(copied_messages_count)
+    }
+
+    /// Removes the offsets of the messages copied from the draining partitions, once they have been deleted.
+    pub async fn remove_copied_offsets(&self) -> Result<(), IggyError> {
+        for target_partition in self.target_partitions.values() {
+            let mut target_partition = target_partition.write().await;
+            for (partition_id, _) in &self.source_partitions {
+                target_partition.copied_offsets.remove(*partition_id);
+            }
+            target_partition.persist_copied_offsets().await?;
+        }
+        Ok(())
+    }
+
+    fn calculate_target_partition_id(&self, messages_key: &[u8]) -> u32 {
+        let messages_key_hash = hash::calculate_32(messages_key);
+        let partitions_count = self.target_partitions.len() as u32;
+        if self.consistent_hashing {
+            calculate_partition_id_by_consistent_hash(messages_key_hash as u64, partitions_count)
+        } else {
+            calculate_partition_id_by_hash(messages_key_hash, partitions_count)
+        }
+    }
+
+    fn get_target_partition(
+        &self,
+        partition_id: u32,
+    ) -> Result<&IggySharedMut<Partition>, IggyError> {
+        self.target_partitions
+            .get(&partition_id)
+            .ok_or(IggyError::PartitionNotFound(
+                partition_id,
+                self.topic_id,
+                self.stream_id,
+            ))
+    }
+}
+
+/// Returns the lowest offset which hasn't been consumed yet by all the consumer groups and the consumers
+/// with the stored offsets, or `None`, when there are neither of them, so it's unknown what was consumed.
+fn get_unconsumed_offset(partition: &Partition, consumer_group_ids: &[u32]) -> Option<u64> {
+    let consumer_group_offsets = consumer_group_ids.iter().map(|group_id| {
+        partition
+            .consumer_group_offsets
+            .get(group_id)
+            .map(|offset| offset.offset + 1)
+            .unwrap_or(0)
+    });
+    let consumer_offsets = partition
+        .consumer_offsets
+        .iter()
+        .map(|offset| offset.value().offset + 1)
+        .collect::<Vec<_>>();
+    consumer_group_offsets.chain(consumer_offsets).min()
 }
 
 pub struct DeletedPartitions {
@@ -53,15 +53,24 @@ impl Topic {
         for partition in self.get_partitions() {
             let mut partition = partition.write().await;
             let partition_id = partition.partition_id;
+            partition.persist_producer_states().await?;
+            partition.persist_copied_offsets().await?;
             for segment in partition.get_segments_mut() {
                 saved_messages_number += segment.persist_messages(None).await.with_error_context(|error| format!("{COMPONENT} (error: {error}) - failed to persist messages in segment, partition ID: {partition_id}"))?;
             }
+            partition.producers.mark_persisted();
+            partition.copied_offsets.mark_persisted();
         }
 
         Ok(saved_messages_number)
     }
 
     pub async fn purge(&self) -> Result<(), IggyError> {
+        // The copied messages must not be purged only partially.
+        if self.copying_partitions {
+            return Err(IggyError::PartitionsDraining(self.topic_id, self.stream_id));
+        }
+
         for partition in self.get_partitions() {
             let mut partition = partition.write().await;
             partition.purge().await?;
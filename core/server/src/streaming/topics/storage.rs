@@ -18,11 +18,12 @@
 
 use crate::state::system::TopicState;
 use crate::streaming::partitions::partition::Partition;
+use crate::streaming::partitions::relocation::MOVING_PARTITION_SUFFIX;
 use crate::streaming::storage::TopicStorage;
 use crate::streaming::topics::COMPONENT;
 use crate::streaming::topics::consumer_group::ConsumerGroup;
 use crate::streaming::topics::topic::Topic;
-use ahash::AHashSet;
+use ahash::{AHashMap, AHashSet};
 use anyhow::Context;
 use error_set::ErrContext;
 use futures::future::join_all;
@@ -53,6 +54,8 @@ impl TopicStorage for FileTopicStorage {
             return Err(IggyError::TopicIdNotFound(topic.topic_id, topic.stream_id));
         }
 
+        topic.config = Topic::get_config(&topic.config, &state.overrides)?;
+        topic.overrides = state.overrides;
         let message_expiry = Topic::get_message_expiry(state.message_expiry, &topic.config);
         let max_topic_size = Topic::get_max_topic_size(state.max_topic_size, &topic.config)?;
         topic.created_at = state.created_at;
@@ -61,50 +64,118 @@ impl TopicStorage for FileTopicStorage {
         topic.compression_algorithm = state.compression_algorithm;
         topic.replication_factor = state.replication_factor.unwrap_or(1);
 
-        let mut dir_entries = fs::read_dir(&topic.partitions_path).await
-            .with_context(|| format!("Failed to read partition with ID: {} for stream with ID: {} for topic with ID: {} and path: {}",
-                                     topic.topic_id, topic.stream_id, topic.topic_id, &topic.partitions_path))
-            .map_err(|_| IggyError::CannotReadPartitions)?;
+        // The partitions might be placed in any of the data directories, including the ones which
+        // are no longer configured, but still contain the partitions moved there before.
+        let mut data_directories = topic.config.get_data_directories();
+        let partition_state_directories = state.partitions.values().filter_map(|partition| {
+            partition
+                .directory
+                .as_ref()
+                .map(|directory| (partition.id, directory))
+        });
+        for (partition_id, directory) in partition_state_directories {
+            if !data_directories.contains(directory) {
+                warn!(
+                    "Partition with ID: '{partition_id}' for topic with ID: '{}' for stream with ID: '{}' is placed in directory: '{directory}', which is not configured.",
+                    topic.topic_id, topic.stream_id
+                );
+                data_directories.push(directory.clone());
+            }
+        }
 
-        let mut unloaded_partitions = Vec::new();
-        while let Some(dir_entry) = dir_entries.next_entry().await.unwrap_or(None) {
-            let metadata = dir_entry.metadata().await;
-            if metadata.is_err() || metadata.unwrap().is_file() {
+        let mut partition_directories = AHashMap::<u32, Vec<String>>::new();
+        for directory in &data_directories {
+            let partitions_path = topic
+                .config
+                .with_data_directory(directory)
+                .get_partitions_path(topic.stream_id, topic.topic_id);
+            if directory != &topic.config.path && !Path::new(&partitions_path).exists() {
                 continue;
             }
 
-            let name = dir_entry.file_name().into_string().unwrap();
-            let partition_id = name.parse::<u32>();
-            if partition_id.is_err() {
-                error!("Invalid partition ID file with name: '{}'.", name);
-                continue;
+            let mut dir_entries = fs::read_dir(&partitions_path).await
+                .with_context(|| format!("Failed to read partition with ID: {} for stream with ID: {} for topic with ID: {} and path: {}",
+                                         topic.topic_id, topic.stream_id, topic.topic_id, &partitions_path))
+                .map_err(|_| IggyError::CannotReadPartitions)?;
+
+            while let Some(dir_entry) = dir_entries.next_entry().await.unwrap_or(None) {
+                let metadata = dir_entry.metadata().await;
+                if metadata.is_err() || metadata.unwrap().is_file() {
+                    continue;
+                }
+
+                let name = dir_entry.file_name().into_string().unwrap();
+                if name.ends_with(MOVING_PARTITION_SUFFIX) {
+                    warn!(
+                        "Partition directory: '{}' was not fully moved and will be removed.",
+                        dir_entry.path().display()
+                    );
+                    if let Err(error) = fs::remove_dir_all(&dir_entry.path()).await {
+                        error!("Cannot remove partition directory: {error}");
+                    }
+                    continue;
+                }
+
+                let partition_id = name.parse::<u32>();
+                if partition_id.is_err() {
+                    error!("Invalid partition ID file with name: '{}'.", name);
+                    continue;
+                }
+
+                let partition_id = partition_id.unwrap();
+                if !state.partitions.contains_key(&partition_id) {
+                    let stream_id = topic.stream_id;
+                    let topic_id = topic.topic_id;
+                    error!(
+                        "Partition with ID: '{partition_id}' for stream with ID: '{stream_id}' and topic with ID: '{topic_id}' was not found in state, but exists on disk and will be removed."
+                    );
+                    if let Err(error) = fs::remove_dir_all(&dir_entry.path()).await {
+                        error!("Cannot remove partition directory: {error}");
+                    } else {
+                        warn!(
+                            "Partition with ID: '{partition_id}' for stream with ID: '{stream_id}' and topic with ID: '{topic_id}' was removed."
+                        );
+                    }
+                    continue;
+                }
+
+                partition_directories
+                    .entry(partition_id)
+                    .or_default()
+                    .push(directory.clone());
             }
+        }
 
-            let partition_id = partition_id.unwrap();
-            let partition_state = state.partitions.get(&partition_id);
-            if partition_state.is_none() {
-                let stream_id = topic.stream_id;
-                let topic_id = topic.topic_id;
-                error!(
-                    "Partition with ID: '{partition_id}' for stream with ID: '{stream_id}' and topic with ID: '{topic_id}' was not found in state, but exists on disk and will be removed."
+        let mut unloaded_partitions = Vec::new();
+        for (partition_id, directories) in partition_directories {
+            let partition_state = state.partitions.get(&partition_id).unwrap();
+            // The directory stored in the state wins, the other copies are leftovers of the interrupted moves.
+            let directory = partition_state
+                .directory
+                .as_ref()
+                .filter(|directory| directories.contains(directory))
+                .unwrap_or(&directories[0])
+                .clone();
+            for stale_directory in directories.iter().filter(|stale| **stale != directory) {
+                let stale_path = topic
+                    .config
+                    .with_data_directory(stale_directory)
+                    .get_partition_path(topic.stream_id, topic.topic_id, partition_id);
+                warn!(
+                    "Partition with ID: '{partition_id}' for stream with ID: '{}' and topic with ID: '{}' is placed in directory: '{directory}', the stale copy: '{stale_path}' will be removed.",
+                    topic.stream_id, topic.topic_id
                 );
-                if let Err(error) = fs::remove_dir_all(&dir_entry.path()).await {
+                if let Err(error) = fs::remove_dir_all(&stale_path).await {
                     error!("Cannot remove partition directory: {error}");
-                } else {
-                    warn!(
-                        "Partition with ID: '{partition_id}' for stream with ID: '{stream_id}' and topic with ID: '{topic_id}' was removed."
-                    );
                 }
-                continue;
             }
 
-            let partition_state = partition_state.unwrap();
             let partition = Partition::create(
                 topic.stream_id,
                 topic.topic_id,
                 partition_id,
                 false,
-                topic.config.clone(),
+                Topic::get_partition_config(&topic.config, &directory),
                 topic.storage.clone(),
                 message_expiry,
                 topic.messages_count_of_parent_stream.clone(),
@@ -151,7 +222,10 @@ impl TopicStorage for FileTopicStorage {
                         topic.topic_id,
                         partition_id,
                         true,
-                        topic.config.clone(),
+                        Topic::get_partition_config(
+                            &topic.config,
+                            &topic.place_partition(partition_id),
+                        ),
                         topic.storage.clone(),
                         message_expiry,
                         topic.messages_count_of_parent_stream.clone(),
@@ -211,6 +285,9 @@ impl TopicStorage for FileTopicStorage {
                 .partitions
                 .insert(partition.partition_id, IggySharedMut::new(partition));
         }
+        topic.draining_partitions_count = state
+            .draining_partitions_count
+            .min(topic.get_partitions_count());
 
         for consumer_group in state.consumer_groups.into_values() {
             let consumer_group = ConsumerGroup::new(
@@ -278,6 +355,20 @@ impl TopicStorage for FileTopicStorage {
             ));
         }
 
+        for directory in &topic.config.partition.directories {
+            let path = topic
+                .config
+                .with_data_directory(directory)
+                .get_topic_path(topic.stream_id, topic.topic_id);
+            if Path::new(&path).exists() && fs::remove_dir_all(&path).await.is_err() {
+                return Err(IggyError::CannotDeleteTopicDirectory(
+                    topic.topic_id,
+                    topic.stream_id,
+                    path,
+                ));
+            }
+        }
+
         info!(
             "Deleted topic with ID: {} for stream with ID: {}.",
             topic.topic_id, topic.stream_id
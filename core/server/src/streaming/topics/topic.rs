@@ -19,6 +19,7 @@
 use crate::configs::system::SystemConfig;
 use crate::streaming::partitions::partition::Partition;
 use crate::streaming::polling_consumer::PollingConsumer;
+use crate::streaming::segments::SEGMENT_MAX_SIZE_BYTES;
 use crate::streaming::storage::SystemStorage;
 use crate::streaming::topics::consumer_group::ConsumerGroup;
 use ahash::AHashMap;
@@ -26,12 +27,12 @@ use core::fmt;
 use iggy_common::locking::IggySharedMut;
 use iggy_common::{
     CompressionAlgorithm, Consumer, ConsumerKind, IggyByteSize, IggyError, IggyExpiry,
-    IggyTimestamp, MaxTopicSize, Sizeable,
+    IggyTimestamp, MaxTopicSize, Sizeable, TopicOverrides,
 };
 
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
-use tokio::sync::RwLock;
+use tokio::sync::{Notify, RwLock};
 use tracing::info;
 
 const ALMOST_FULL_THRESHOLD: f64 = 0.9;
@@ -50,15 +51,23 @@ pub struct Topic {
     pub(crate) segments_count_of_parent_stream: Arc<AtomicU32>,
     pub(crate) config: Arc<SystemConfig>,
     pub(crate) partitions: AHashMap<u32, IggySharedMut<Partition>>,
+    /// The number of the last partitions which are being drained and no longer accept new messages.
+    pub(crate) draining_partitions_count: u32,
+    /// The time when the last partitions started draining, after which the ones without any consumers are deleted.
+    pub(crate) draining_started_at: IggyTimestamp,
+    /// Whether the unconsumed messages of the draining partitions are being copied into the active ones.
+    pub(crate) copying_partitions: bool,
     pub(crate) storage: Arc<SystemStorage>,
     pub(crate) consumer_groups: AHashMap<u32, RwLock<ConsumerGroup>>,
     pub(crate) consumer_groups_ids: AHashMap<String, u32>,
     pub(crate) current_consumer_group_id: AtomicU32,
     pub(crate) current_partition_id: AtomicU32,
+    pub(crate) new_messages: Arc<Notify>,
     pub message_expiry: IggyExpiry,
     pub compression_algorithm: CompressionAlgorithm,
     pub max_topic_size: MaxTopicSize,
     pub replication_factor: u8,
+    pub overrides: TopicOverrides,
     pub created_at: IggyTimestamp,
 }
 
@@ -88,6 +97,7 @@ impl Topic {
             Default::default(),
             MaxTopicSize::ServerDefault,
             1,
+            TopicOverrides::default(),
         )
         .await
         .unwrap()
@@ -108,7 +118,9 @@ impl Topic {
         compression_algorithm: CompressionAlgorithm,
         max_topic_size: MaxTopicSize,
         replication_factor: u8,
+        overrides: TopicOverrides,
     ) -> Result<Topic, IggyError> {
+        let config = Topic::get_config(&config, &overrides)?;
         let path = config.get_topic_path(stream_id, topic_id);
         let partitions_path = config.get_partitions_path(stream_id, topic_id);
         let mut topic = Topic {
@@ -116,6 +128,9 @@ impl Topic {
             topic_id,
             name: name.to_string(),
             partitions: AHashMap::new(),
+            draining_partitions_count: 0,
+            draining_started_at: IggyTimestamp::now(),
+            copying_partitions: false,
             path,
             partitions_path,
             storage,
@@ -128,10 +143,12 @@ impl Topic {
             consumer_groups_ids: AHashMap::new(),
             current_consumer_group_id: AtomicU32::new(1),
             current_partition_id: AtomicU32::new(1),
+            new_messages: Arc::new(Notify::new()),
             message_expiry: Topic::get_message_expiry(message_expiry, &config),
             max_topic_size: Topic::get_max_topic_size(max_topic_size, &config)?,
             compression_algorithm,
             replication_factor,
+            overrides,
             config,
             created_at: IggyTimestamp::now(),
         };
@@ -245,6 +262,48 @@ impl Topic {
         }
     }
 
+    /// Returns the config of the topic, which is the server config with the topic overrides applied.
+    pub fn get_config(
+        config: &Arc<SystemConfig>,
+        overrides: &TopicOverrides,
+    ) -> Result<Arc<SystemConfig>, IggyError> {
+        if overrides.is_empty() {
+            return Ok(config.clone());
+        }
+
+        let mut topic_config = SystemConfig::clone(config);
+        if let Some(segment_size) = overrides.segment_size {
+            if segment_size.as_bytes_u64() > SEGMENT_MAX_SIZE_BYTES
+                || segment_size.as_bytes_u64() % 512 != 0
+            {
+                return Err(IggyError::InvalidTopicOverrides);
+            }
+            topic_config.segment.size = segment_size;
+        }
+        if let Some(enforce_fsync) = overrides.enforce_fsync {
+            topic_config.partition.enforce_fsync = enforce_fsync;
+        }
+        if let Some(messages_required_to_save) = overrides.messages_required_to_save {
+            if messages_required_to_save < 32 || messages_required_to_save % 32 != 0 {
+                return Err(IggyError::InvalidTopicOverrides);
+            }
+            topic_config.partition.messages_required_to_save = messages_required_to_save;
+        }
+        if let Some(server_confirmation) = overrides.server_confirmation {
+            topic_config.segment.server_confirmation = server_confirmation;
+        }
+        if let Some(enabled) = overrides.deduplication_enabled {
+            topic_config.message_deduplication.enabled = enabled;
+        }
+        if let Some(max_entries) = overrides.deduplication_max_entries {
+            topic_config.message_deduplication.max_entries = max_entries;
+        }
+        if let Some(expiry) = overrides.deduplication_expiry {
+            topic_config.message_deduplication.expiry = expiry;
+        }
+        Ok(Arc::new(topic_config))
+    }
+
     pub fn get_message_expiry(message_expiry: IggyExpiry, config: &SystemConfig) -> IggyExpiry {
         match message_expiry {
             IggyExpiry::ServerDefault => config.segment.message_expiry,
@@ -263,7 +322,7 @@ impl fmt::Display for Topic {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "Topic {{ id: {}, stream ID: {}, name: {}, path: {}, partitions: {}, message_expiry: {}, max_topic_size: {}, replication_factor: {} }}",
+            "Topic {{ id: {}, stream ID: {}, name: {}, path: {}, partitions: {}, message_expiry: {}, max_topic_size: {}, replication_factor: {}, overrides: {{ {} }} }}",
             self.topic_id,
             self.stream_id,
             self.name,
@@ -272,6 +331,7 @@ impl fmt::Display for Topic {
             self.message_expiry,
             self.max_topic_size,
             self.replication_factor,
+            self.overrides,
         )
     }
 }
@@ -326,6 +386,7 @@ mod tests {
             compression_algorithm,
             max_topic_size,
             replication_factor,
+            TopicOverrides::default(),
         )
         .await
         .unwrap();
@@ -37,4 +37,22 @@ impl Permissioner {
     ) -> Result<(), IggyError> {
         self.update_topic(user_id, stream_id, topic_id)
     }
+
+    pub fn move_partition(
+        &self,
+        user_id: u32,
+        stream_id: u32,
+        topic_id: u32,
+    ) -> Result<(), IggyError> {
+        self.update_topic(user_id, stream_id, topic_id)
+    }
+
+    pub fn shrink_partitions(
+        &self,
+        user_id: u32,
+        stream_id: u32,
+        topic_id: u32,
+    ) -> Result<(), IggyError> {
+        self.delete_partitions(user_id, stream_id, topic_id)
+    }
 }
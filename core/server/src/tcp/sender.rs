@@ -18,8 +18,9 @@
 
 use iggy_common::IggyError;
 use std::io::IoSlice;
+use tokio::fs::File;
 use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
-use tracing::debug;
+use tracing::{debug, error};
 
 const STATUS_OK: &[u8] = &[0; 4];
 
@@ -65,6 +66,33 @@ where
     send_response_vectored(stream, STATUS_OK, length, slices).await
 }
 
+pub(crate) async fn send_ok_response_from_file<T>(
+    stream: &mut T,
+    file: &mut File,
+    length: u32,
+) -> Result<(), IggyError>
+where
+    T: AsyncRead + AsyncWrite + Unpin,
+{
+    debug!("Sending response of len: {length} from file with status: {STATUS_OK:?}...");
+    stream
+        .write_all(&[STATUS_OK, &length.to_le_bytes()].concat())
+        .await
+        .map_err(|_| IggyError::TcpError)?;
+    let sent_bytes = tokio::io::copy(&mut file.take(u64::from(length)), stream)
+        .await
+        .map_err(|error| {
+            error!("Failed to send response from file, error: {error}");
+            IggyError::TcpError
+        })?;
+    if sent_bytes != u64::from(length) {
+        error!("Failed to send response from file, expected: {length} bytes, sent: {sent_bytes}");
+        return Err(IggyError::CannotReadFile);
+    }
+    debug!("Sent response from file with status: {STATUS_OK:?}");
+    Ok(())
+}
+
 pub(crate) async fn send_error_response<T>(
     stream: &mut T,
     error: IggyError,
@@ -21,6 +21,7 @@ use crate::tcp::COMPONENT;
 use crate::{server_error::ServerError, tcp::sender};
 use error_set::ErrContext;
 use iggy_common::IggyError;
+use tokio::fs::File;
 use tokio::io::AsyncWriteExt;
 use tokio::net::TcpStream;
 use tokio_rustls::server::TlsStream;
@@ -43,6 +44,14 @@ impl Sender for TcpTlsSender {
         sender::send_ok_response(&mut self.stream, payload).await
     }
 
+    async fn send_ok_response_from_file(
+        &mut self,
+        file: &mut File,
+        length: u32,
+    ) -> Result<(), IggyError> {
+        sender::send_ok_response_from_file(&mut self.stream, file, length).await
+    }
+
     async fn send_error_response(&mut self, error: IggyError) -> Result<(), IggyError> {
         sender::send_error_response(&mut self.stream, error).await
     }
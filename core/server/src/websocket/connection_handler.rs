@@ -0,0 +1,237 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::binary::command::{ServerCommand, ServerCommandHandler};
+use crate::binary::sender::SenderKind;
+use crate::configs::http::HttpWebSocketConfig;
+use crate::server_error::ConnectionError;
+use crate::streaming::clients::client_manager::Transport;
+use crate::streaming::session::Session;
+use crate::streaming::systems::system::SharedSystem;
+use crate::websocket::subscriptions::{WebSocketSubscriptions, error_frame, send_frame};
+use axum::extract::ws::{Message, Utf8Bytes, WebSocket};
+use bytes::{Buf, Bytes};
+use futures::{SinkExt, StreamExt};
+use iggy_common::locking::IggySharedMutFn;
+use iggy_common::{IggyError, UserId, WebSocketClientFrame, WebSocketServerFrame};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tracing::{debug, error, info};
+
+const INITIAL_BYTES_LENGTH: usize = 4;
+const OUTGOING_MESSAGES_CAPACITY: usize = 1024;
+
+/// Handles the upgraded WebSocket connection until it's closed by either side.
+///
+/// Binary messages carry the regular binary protocol requests (`[length][code][payload]`),
+/// while text messages carry JSON frames used to manage the push-based message subscriptions.
+/// If the upgrade request was authenticated with JWT, the session is authenticated upfront,
+/// otherwise the client is expected to log in using the binary protocol.
+pub async fn handle_connection(
+    socket: WebSocket,
+    address: SocketAddr,
+    user_id: Option<UserId>,
+    system: SharedSystem,
+    config: HttpWebSocketConfig,
+) {
+    info!("WebSocket client has connected: {address}");
+    let session = system
+        .read()
+        .await
+        .add_client(&address, Transport::WebSocket)
+        .await;
+
+    let authentication = match user_id {
+        Some(user_id) => authenticate(&session, user_id, &system).await,
+        None => Ok(()),
+    };
+    if let Err(error) = authentication {
+        error!("Failed to authenticate WebSocket session: {session}, error: {error}");
+        system.read().await.delete_client(session.client_id).await;
+        return;
+    }
+
+    let client_id = session.client_id;
+    let (mut socket_sender, mut socket_receiver) = socket.split();
+    let (outgoing, mut outgoing_receiver) = mpsc::channel::<Message>(OUTGOING_MESSAGES_CAPACITY);
+    let writer = tokio::spawn(async move {
+        while let Some(message) = outgoing_receiver.recv().await {
+            let is_close = matches!(message, Message::Close(_));
+            if socket_sender.send(message).await.is_err() || is_close {
+                break;
+            }
+        }
+    });
+
+    let mut subscriptions = WebSocketSubscriptions::new(
+        session.clone(),
+        system.clone(),
+        outgoing.clone(),
+        config.poll_interval,
+        config.max_subscriptions,
+    );
+
+    while let Some(message) = socket_receiver.next().await {
+        let result = match message {
+            Ok(Message::Binary(request)) => {
+                handle_request(request, &session, &system, &outgoing).await
+            }
+            Ok(Message::Text(frame)) => {
+                handle_frame(frame, &mut subscriptions, &outgoing).await;
+                Ok(())
+            }
+            Ok(Message::Close(_)) => break,
+            Ok(Message::Ping(_)) | Ok(Message::Pong(_)) => Ok(()),
+            Err(error) => {
+                debug!(
+                    "Failed to receive WebSocket message from client: {address}, error: {error}"
+                );
+                break;
+            }
+        };
+
+        if let Err(error) = result {
+            match error {
+                ConnectionError::SdkError(IggyError::ClientNotFound(_)) => {
+                    error!("Session: {session} will be deleted.");
+                }
+                _ => {
+                    error!("WebSocket connection has failed: {error}");
+                }
+            }
+            break;
+        }
+    }
+
+    drop(subscriptions);
+    let _ = outgoing.send(Message::Close(None)).await;
+    drop(outgoing);
+    let _ = writer.await;
+    info!("WebSocket client with ID: {client_id} has disconnected.");
+    system.read().await.delete_client(client_id).await;
+}
+
+async fn authenticate(
+    session: &Session,
+    user_id: UserId,
+    system: &SharedSystem,
+) -> Result<(), IggyError> {
+    let system = system.read().await;
+    system
+        .client_manager
+        .write()
+        .await
+        .set_user_id(session.client_id, user_id)
+        .await?;
+    session.set_user_id(user_id);
+    Ok(())
+}
+
+async fn handle_request(
+    mut request: Bytes,
+    session: &Arc<Session>,
+    system: &SharedSystem,
+    outgoing: &mpsc::Sender<Message>,
+) -> Result<(), ConnectionError> {
+    let mut sender = SenderKind::get_websocket_sender(Bytes::new(), outgoing.clone());
+    if request.remaining() < 2 * INITIAL_BYTES_LENGTH {
+        sender
+            .send_error_response(IggyError::CommandLengthError(format!(
+                "Unable to read the WebSocket request length and code, expected: {} bytes, received: {} bytes.",
+                2 * INITIAL_BYTES_LENGTH,
+                request.remaining()
+            )))
+            .await?;
+        return Ok(());
+    }
+
+    let length = request.get_u32_le();
+    let code = request.get_u32_le();
+    if length < INITIAL_BYTES_LENGTH as u32 || request.remaining() != (length - 4) as usize {
+        sender
+            .send_error_response(IggyError::CommandLengthError(format!(
+                "Invalid WebSocket request length: {length}, payload size: {} bytes.",
+                request.remaining()
+            )))
+            .await?;
+        return Ok(());
+    }
+
+    debug!("Received a WebSocket request, length: {length}, code: {code}");
+    let mut sender = SenderKind::get_websocket_sender(request, outgoing.clone());
+    let command = match ServerCommand::from_code_and_reader(code, &mut sender, length - 4).await {
+        Ok(command) => command,
+        Err(error) => {
+            sender.send_error_response(error).await?;
+            return Ok(());
+        }
+    };
+
+    debug!("Received a WebSocket command: {command}, payload size: {length}");
+    match command.handle(&mut sender, length, session, system).await {
+        Ok(_) => {
+            debug!(
+                "Command was handled successfully, session: {session}. WebSocket response was sent."
+            );
+        }
+        Err(error) => {
+            error!("Command was not handled successfully, session: {session}, error: {error}.");
+            if let IggyError::ClientNotFound(_) = error {
+                sender.send_error_response(error).await?;
+                return Err(ConnectionError::from(IggyError::ClientNotFound(
+                    session.client_id,
+                )));
+            }
+
+            sender.send_error_response(error).await?;
+            debug!("WebSocket error response was sent to: {session}.");
+        }
+    }
+    Ok(())
+}
+
+async fn handle_frame(
+    frame: Utf8Bytes,
+    subscriptions: &mut WebSocketSubscriptions,
+    outgoing: &mpsc::Sender<Message>,
+) {
+    let response = match serde_json::from_str::<WebSocketClientFrame>(frame.as_str()) {
+        Ok(WebSocketClientFrame::Subscribe(subscription)) => {
+            let subscription_id = subscription.subscription_id;
+            match subscriptions.subscribe(subscription) {
+                Ok(()) => WebSocketServerFrame::Subscribed { subscription_id },
+                Err(error) => error_frame(Some(subscription_id), error),
+            }
+        }
+        Ok(WebSocketClientFrame::Unsubscribe { subscription_id }) => {
+            match subscriptions.unsubscribe(subscription_id) {
+                Ok(()) => WebSocketServerFrame::Unsubscribed { subscription_id },
+                Err(error) => error_frame(Some(subscription_id), error),
+            }
+        }
+        Err(error) => {
+            debug!("Received an invalid WebSocket frame, error: {error}");
+            error_frame(None, IggyError::InvalidCommand)
+        }
+    };
+
+    if let Err(error) = send_frame(outgoing, &response).await {
+        debug!("Failed to send WebSocket frame, error: {error}");
+    }
+}
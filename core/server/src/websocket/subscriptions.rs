@@ -0,0 +1,213 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::streaming::session::Session;
+use crate::streaming::systems::messages::PollingArgs;
+use crate::streaming::systems::system::SharedSystem;
+use crate::websocket::COMPONENT;
+use ahash::AHashMap;
+use axum::extract::ws::Message;
+use error_set::ErrContext;
+use iggy_common::{
+    IggyDuration, IggyError, MessagesSubscription, PolledMessages, PollingKind, PollingStrategy,
+    Validatable, WebSocketServerFrame,
+};
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::{debug, error};
+
+/// Message subscriptions registered by a single WebSocket connection.
+///
+/// Every subscription is served by its own task, which keeps polling the partition
+/// and pushes the messages to the client as soon as they are available.
+/// All the tasks are aborted once the subscriptions are dropped (the connection is closed).
+#[derive(Debug)]
+pub struct WebSocketSubscriptions {
+    session: Arc<Session>,
+    system: SharedSystem,
+    outgoing: mpsc::Sender<Message>,
+    poll_interval: IggyDuration,
+    max_subscriptions: u32,
+    tasks: AHashMap<u32, JoinHandle<()>>,
+}
+
+impl WebSocketSubscriptions {
+    pub fn new(
+        session: Arc<Session>,
+        system: SharedSystem,
+        outgoing: mpsc::Sender<Message>,
+        poll_interval: IggyDuration,
+        max_subscriptions: u32,
+    ) -> Self {
+        Self {
+            session,
+            system,
+            outgoing,
+            poll_interval,
+            max_subscriptions,
+            tasks: AHashMap::new(),
+        }
+    }
+
+    pub fn subscribe(&mut self, subscription: MessagesSubscription) -> Result<(), IggyError> {
+        subscription.validate()?;
+        if !self.session.is_authenticated() {
+            return Err(IggyError::Unauthenticated);
+        }
+
+        self.tasks.retain(|_, task| !task.is_finished());
+        if self.tasks.contains_key(&subscription.subscription_id) {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        if self.tasks.len() >= self.max_subscriptions as usize {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let subscription_id = subscription.subscription_id;
+        let task = tokio::spawn(serve_subscription(
+            subscription,
+            self.session.clone(),
+            self.system.clone(),
+            self.outgoing.clone(),
+            self.poll_interval,
+        ));
+        self.tasks.insert(subscription_id, task);
+        debug!(
+            "Added WebSocket subscription with ID: {subscription_id} for session: {}",
+            self.session
+        );
+        Ok(())
+    }
+
+    pub fn unsubscribe(&mut self, subscription_id: u32) -> Result<(), IggyError> {
+        let Some(task) = self.tasks.remove(&subscription_id) else {
+            return Err(IggyError::ResourceNotFound(format!(
+                "subscription with ID: {subscription_id}"
+            )));
+        };
+
+        task.abort();
+        debug!(
+            "Removed WebSocket subscription with ID: {subscription_id} for session: {}",
+            self.session
+        );
+        Ok(())
+    }
+}
+
+impl Drop for WebSocketSubscriptions {
+    fn drop(&mut self) {
+        for task in self.tasks.values() {
+            task.abort();
+        }
+    }
+}
+
+async fn serve_subscription(
+    subscription: MessagesSubscription,
+    session: Arc<Session>,
+    system: SharedSystem,
+    outgoing: mpsc::Sender<Message>,
+    poll_interval: IggyDuration,
+) {
+    let subscription_id = subscription.subscription_id;
+    let consumer = subscription.consumer();
+    let mut strategy = subscription.strategy;
+    loop {
+        let polled_messages = {
+            let system = system.read().await;
+            system
+                .poll_messages(
+                    &session,
+                    &consumer,
+                    &subscription.stream_id,
+                    &subscription.topic_id,
+                    subscription.partition_id,
+                    PollingArgs::new(strategy, subscription.count, subscription.auto_commit),
+                )
+                .await
+                .map(|(metadata, messages)| messages.into_polled_messages(metadata))
+                .with_error_context(|error| {
+                    format!(
+                        "{COMPONENT} (error: {error}) - failed to poll messages for subscription with ID: {subscription_id}, session: {session}"
+                    )
+                })
+        };
+
+        let polled_messages = match polled_messages {
+            Ok(polled_messages) => polled_messages,
+            Err(error) => {
+                error!("Subscription with ID: {subscription_id} has failed, error: {error}");
+                let _ = send_frame(&outgoing, &error_frame(Some(subscription_id), error)).await;
+                return;
+            }
+        };
+
+        if polled_messages.messages.is_empty() {
+            tokio::time::sleep(poll_interval.get_duration()).await;
+            continue;
+        }
+
+        strategy = next_strategy(strategy, &polled_messages);
+        let frame = WebSocketServerFrame::Messages {
+            subscription_id,
+            messages: polled_messages,
+        };
+        if send_frame(&outgoing, &frame).await.is_err() {
+            debug!("WebSocket connection for subscription with ID: {subscription_id} is closed.");
+            return;
+        }
+    }
+}
+
+/// The `Next` strategy is tracked by the server using the stored consumer offset,
+/// any other strategy continues from the offset following the last pushed message.
+pub(crate) fn next_strategy(
+    strategy: PollingStrategy,
+    polled_messages: &PolledMessages,
+) -> PollingStrategy {
+    if strategy.kind == PollingKind::Next {
+        return strategy;
+    }
+
+    match polled_messages.messages.last() {
+        Some(message) => PollingStrategy::offset(message.header.offset + 1),
+        None => strategy,
+    }
+}
+
+pub(crate) async fn send_frame(
+    outgoing: &mpsc::Sender<Message>,
+    frame: &WebSocketServerFrame,
+) -> Result<(), IggyError> {
+    let json = serde_json::to_string(frame).map_err(|_| IggyError::CannotSerializeResource)?;
+    outgoing
+        .send(Message::Text(json.into()))
+        .await
+        .map_err(|_| IggyError::WebSocketError)
+}
+
+pub(crate) fn error_frame(subscription_id: Option<u32>, error: IggyError) -> WebSocketServerFrame {
+    WebSocketServerFrame::Error {
+        subscription_id,
+        code: error.as_code(),
+        reason: error.to_string(),
+    }
+}
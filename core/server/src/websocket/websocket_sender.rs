@@ -0,0 +1,140 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::binary::sender::Sender;
+use crate::server_error::ServerError;
+use axum::extract::ws::Message;
+use bytes::{Buf, Bytes, BytesMut};
+use iggy_common::IggyError;
+use std::io::IoSlice;
+use tokio::fs::File;
+use tokio::io::AsyncReadExt;
+use tokio::sync::mpsc;
+use tracing::{debug, error};
+
+const STATUS_OK: &[u8] = &[0; 4];
+
+/// Sender used by the WebSocket transport.
+///
+/// Each binary WebSocket message carries exactly one request in the same format as TCP,
+/// so the sender is created per request and the response is written back as a single
+/// binary message through the connection's outgoing channel.
+#[derive(Debug)]
+pub struct WebSocketSender {
+    pub(crate) request: Bytes,
+    pub(crate) outgoing: mpsc::Sender<Message>,
+}
+
+impl WebSocketSender {
+    async fn send_response(&mut self, status: &[u8], payload: &[u8]) -> Result<(), IggyError> {
+        debug!(
+            "Sending WebSocket response of len: {} with status: {:?}...",
+            payload.len(),
+            status
+        );
+        let length = (payload.len() as u32).to_le_bytes();
+        let response = [status, &length, payload].as_slice().concat();
+        self.outgoing
+            .send(Message::Binary(response.into()))
+            .await
+            .map_err(|_| IggyError::WebSocketError)?;
+        debug!("Sent WebSocket response with status: {:?}", status);
+        Ok(())
+    }
+}
+
+impl Sender for WebSocketSender {
+    async fn read(&mut self, buffer: &mut [u8]) -> Result<usize, IggyError> {
+        if self.request.remaining() < buffer.len() {
+            return Err(IggyError::CommandLengthError(format!(
+                "Unable to read the WebSocket request, expected: {} bytes, remaining: {} bytes.",
+                buffer.len(),
+                self.request.remaining()
+            )));
+        }
+
+        self.request.copy_to_slice(buffer);
+        Ok(buffer.len())
+    }
+
+    async fn send_empty_ok_response(&mut self) -> Result<(), IggyError> {
+        self.send_ok_response(&[]).await
+    }
+
+    async fn send_ok_response(&mut self, payload: &[u8]) -> Result<(), IggyError> {
+        self.send_response(STATUS_OK, payload).await
+    }
+
+    async fn send_ok_response_vectored(
+        &mut self,
+        length: &[u8],
+        slices: Vec<IoSlice<'_>>,
+    ) -> Result<(), IggyError> {
+        let payload_length = slices.iter().map(|slice| slice.len()).sum::<usize>();
+        let mut response = BytesMut::with_capacity(STATUS_OK.len() + length.len() + payload_length);
+        response.extend_from_slice(STATUS_OK);
+        response.extend_from_slice(length);
+        for slice in slices {
+            response.extend_from_slice(&slice);
+        }
+        self.outgoing
+            .send(Message::Binary(response.freeze()))
+            .await
+            .map_err(|_| IggyError::WebSocketError)?;
+        debug!(
+            "Sent vectored WebSocket response with status: {:?}",
+            STATUS_OK
+        );
+        Ok(())
+    }
+
+    /// The response has to be sent as a single WebSocket message, so the file is read as a whole.
+    async fn send_ok_response_from_file(
+        &mut self,
+        file: &mut File,
+        length: u32,
+    ) -> Result<(), IggyError> {
+        let mut payload = Vec::with_capacity(length as usize);
+        file.take(u64::from(length))
+            .read_to_end(&mut payload)
+            .await
+            .map_err(|error| {
+                error!("Failed to read response from file, error: {error}");
+                IggyError::CannotReadFile
+            })?;
+        if payload.len() != length as usize {
+            error!(
+                "Failed to read response from file, expected: {length} bytes, read: {}",
+                payload.len()
+            );
+            return Err(IggyError::CannotReadFile);
+        }
+        self.send_ok_response(&payload).await
+    }
+
+    async fn send_error_response(&mut self, error: IggyError) -> Result<(), IggyError> {
+        self.send_response(&error.as_code().to_le_bytes(), &[])
+            .await
+    }
+
+    async fn shutdown(&mut self) -> Result<(), ServerError> {
+        // The receiver might be already gone if the connection was closed by the client.
+        let _ = self.outgoing.send(Message::Close(None)).await;
+        Ok(())
+    }
+}
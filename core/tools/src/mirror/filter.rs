@@ -0,0 +1,149 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use anyhow::{Result, bail};
+
+/// Decides which topics are mirrored, based on the `<stream>/<topic>` patterns,
+/// in which `*` matches any (also empty) sequence of characters.
+/// The topic is mirrored if it matches any of the included patterns and none of the excluded ones.
+#[derive(Debug, Clone)]
+pub struct TopicFilter {
+    include: Vec<Pattern>,
+    exclude: Vec<Pattern>,
+}
+
+#[derive(Debug, Clone)]
+struct Pattern {
+    stream: String,
+    topic: String,
+}
+
+impl TopicFilter {
+    pub fn new(include: &[String], exclude: &[String]) -> Result<Self> {
+        Ok(Self {
+            include: include
+                .iter()
+                .map(|pattern| Pattern::parse(pattern))
+                .collect::<Result<_>>()?,
+            exclude: exclude
+                .iter()
+                .map(|pattern| Pattern::parse(pattern))
+                .collect::<Result<_>>()?,
+        })
+    }
+
+    pub fn is_stream_included(&self, stream: &str) -> bool {
+        self.include
+            .iter()
+            .any(|pattern| matches(&pattern.stream, stream))
+    }
+
+    pub fn is_topic_included(&self, stream: &str, topic: &str) -> bool {
+        self.include
+            .iter()
+            .any(|pattern| pattern.matches(stream, topic))
+            && !self
+                .exclude
+                .iter()
+                .any(|pattern| pattern.matches(stream, topic))
+    }
+}
+
+impl Pattern {
+    fn parse(pattern: &str) -> Result<Self> {
+        let Some((stream, topic)) = pattern.trim().split_once('/') else {
+            bail!("Invalid pattern: '{pattern}', expected '<stream>/<topic>'.");
+        };
+
+        if stream.is_empty() || topic.is_empty() || topic.contains('/') {
+            bail!("Invalid pattern: '{pattern}', expected '<stream>/<topic>'.");
+        }
+
+        Ok(Self {
+            stream: stream.to_owned(),
+            topic: topic.to_owned(),
+        })
+    }
+
+    fn matches(&self, stream: &str, topic: &str) -> bool {
+        matches(&self.stream, stream) && matches(&self.topic, topic)
+    }
+}
+
+fn matches(pattern: &str, value: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = value.strip_prefix(first) else {
+        return false;
+    };
+
+    let parts = parts.collect::<Vec<_>>();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+
+    for part in middle {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wildcards_should_match_any_sequence_of_characters() {
+        assert!(matches("*", ""));
+        assert!(matches("*", "orders"));
+        assert!(matches("orders", "orders"));
+        assert!(!matches("orders", "orders-eu"));
+        assert!(matches("orders-*", "orders-eu"));
+        assert!(matches("*-eu", "orders-eu"));
+        assert!(matches("o*-*u", "orders-eu"));
+        assert!(!matches("o*-*s", "orders-eu"));
+        assert!(!matches("ab*ba", "aba"));
+    }
+
+    #[test]
+    fn excluded_topics_should_not_be_included() {
+        let filter = TopicFilter::new(
+            &["prod/*".to_owned(), "dev/orders".to_owned()],
+            &["prod/*-internal".to_owned()],
+        )
+        .unwrap();
+        assert!(filter.is_stream_included("prod"));
+        assert!(filter.is_stream_included("dev"));
+        assert!(!filter.is_stream_included("test"));
+        assert!(filter.is_topic_included("prod", "orders"));
+        assert!(!filter.is_topic_included("prod", "users-internal"));
+        assert!(filter.is_topic_included("dev", "orders"));
+        assert!(!filter.is_topic_included("dev", "users"));
+    }
+
+    #[test]
+    fn invalid_patterns_should_be_rejected() {
+        assert!(TopicFilter::new(&["prod".to_owned()], &[]).is_err());
+        assert!(TopicFilter::new(&["prod/".to_owned()], &[]).is_err());
+        assert!(TopicFilter::new(&["prod/a/b".to_owned()], &[]).is_err());
+    }
+}
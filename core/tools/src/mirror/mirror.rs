@@ -0,0 +1,342 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::filter::TopicFilter;
+use anyhow::Result;
+use iggy::prelude::*;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::task::JoinHandle;
+use tokio::time::sleep;
+use tracing::{error, info, warn};
+
+/// The header added to every mirrored message, holding its offset in the source partition,
+/// which allows translating the target offsets back to the source ones (and resuming the mirroring).
+pub const SOURCE_OFFSET_HEADER: &str = "iggy_mirror_source_offset";
+const RETRY_INTERVAL: IggyDuration = IggyDuration::ONE_SECOND;
+
+#[derive(Debug)]
+pub struct MirrorConfig {
+    pub filter: TopicFilter,
+    pub consumer: String,
+    pub batch_length: u32,
+    pub poll_timeout: IggyDuration,
+    pub discovery_interval: IggyDuration,
+    pub create_topics: bool,
+}
+
+/// Mirrors the messages of the matching topics from the source server to the target one.
+/// Every source partition is mirrored to the partition with the same ID in the topic with the same name
+/// on the target server, keeping the message IDs, headers and origin timestamps.
+pub struct Mirror {
+    source: Arc<IggyClient>,
+    target: Arc<IggyClient>,
+    config: Arc<MirrorConfig>,
+    partitions: HashMap<MirroredPartition, JoinHandle<()>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct MirroredPartition {
+    stream: String,
+    topic: String,
+    partition_id: u32,
+}
+
+impl Mirror {
+    pub fn new(source: IggyClient, target: IggyClient, config: MirrorConfig) -> Self {
+        Self {
+            source: Arc::new(source),
+            target: Arc::new(target),
+            config: Arc::new(config),
+            partitions: HashMap::new(),
+        }
+    }
+
+    /// Periodically discovers the topics to mirror and starts mirroring each of their partitions in the background.
+    pub async fn run(mut self) -> Result<()> {
+        loop {
+            self.partitions.retain(|_, task| !task.is_finished());
+            for partition in self.discover().await? {
+                if self.partitions.contains_key(&partition) {
+                    continue;
+                }
+
+                let task = tokio::spawn(mirror_partition(
+                    self.source.clone(),
+                    self.target.clone(),
+                    self.config.clone(),
+                    partition.clone(),
+                ));
+                self.partitions.insert(partition, task);
+            }
+            sleep(self.config.discovery_interval.get_duration()).await;
+        }
+    }
+
+    async fn discover(&self) -> Result<Vec<MirroredPartition>> {
+        let mut partitions = Vec::new();
+        for stream in self.source.get_streams().await? {
+            if !self.config.filter.is_stream_included(&stream.name) {
+                continue;
+            }
+
+            let stream_id = Identifier::numeric(stream.id)?;
+            for topic in self.source.get_topics(&stream_id).await? {
+                if !self
+                    .config
+                    .filter
+                    .is_topic_included(&stream.name, &topic.name)
+                {
+                    continue;
+                }
+
+                let partitions_count = match self.prepare_target_topic(&stream.name, &topic).await {
+                    Ok(partitions_count) => partitions_count,
+                    Err(error) => {
+                        warn!(
+                            "Cannot mirror topic: {}/{} to the target server. {error}",
+                            stream.name, topic.name
+                        );
+                        continue;
+                    }
+                };
+
+                for partition_id in 1..=topic.partitions_count.min(partitions_count) {
+                    partitions.push(MirroredPartition {
+                        stream: stream.name.clone(),
+                        topic: topic.name.clone(),
+                        partition_id,
+                    });
+                }
+            }
+        }
+        Ok(partitions)
+    }
+
+    /// Creates the missing stream, topic and partitions on the target server (unless disabled),
+    /// and returns the number of partitions of the target topic.
+    async fn prepare_target_topic(&self, stream: &str, topic: &Topic) -> Result<u32, IggyError> {
+        let stream_id = Identifier::named(stream)?;
+        let topic_id = Identifier::named(&topic.name)?;
+        if self.target.get_stream(&stream_id).await?.is_none() {
+            if !self.config.create_topics {
+                return Err(IggyError::StreamNameNotFound(stream.to_owned()));
+            }
+
+            self.target.create_stream(stream, None).await?;
+            info!("Created stream: {stream} on the target server.");
+        }
+
+        let Some(target_topic) = self.target.get_topic(&stream_id, &topic_id).await? else {
+            if !self.config.create_topics {
+                return Err(IggyError::TopicNameNotFound(
+                    topic.name.clone(),
+                    stream.to_owned(),
+                ));
+            }
+
+            self.target
+                .create_topic(
+                    &stream_id,
+                    &topic.name,
+                    topic.partitions_count,
+                    topic.compression_algorithm,
+                    None,
+                    None,
+                    topic.message_expiry,
+                    topic.max_topic_size,
+                )
+                .await?;
+            info!(
+                "Created topic: {stream}/{} with {} partition(s) on the target server.",
+                topic.name, topic.partitions_count
+            );
+            return Ok(topic.partitions_count);
+        };
+
+        if target_topic.partitions_count >= topic.partitions_count || !self.config.create_topics {
+            return Ok(target_topic.partitions_count);
+        }
+
+        let missing_partitions = topic.partitions_count - target_topic.partitions_count;
+        self.target
+            .create_partitions(&stream_id, &topic_id, missing_partitions)
+            .await?;
+        info!(
+            "Created {missing_partitions} partition(s) for topic: {stream}/{} on the target server.",
+            topic.name
+        );
+        Ok(topic.partitions_count)
+    }
+}
+
+/// Mirrors the partition until it's deleted on either server, retrying on any other error.
+async fn mirror_partition(
+    source: Arc<IggyClient>,
+    target: Arc<IggyClient>,
+    config: Arc<MirrorConfig>,
+    partition: MirroredPartition,
+) {
+    loop {
+        let Err(error) = mirror_messages(&source, &target, &config, &partition).await else {
+            return;
+        };
+
+        if is_not_found(&error) {
+            warn!(
+                "Stopped mirroring partition: {} of topic: {}/{}. {error}",
+                partition.partition_id, partition.stream, partition.topic
+            );
+            return;
+        }
+
+        error!(
+            "Failed to mirror partition: {} of topic: {}/{}, retrying... {error}",
+            partition.partition_id, partition.stream, partition.topic
+        );
+        sleep(RETRY_INTERVAL.get_duration()).await;
+    }
+}
+
+async fn mirror_messages(
+    source: &IggyClient,
+    target: &IggyClient,
+    config: &MirrorConfig,
+    partition: &MirroredPartition,
+) -> Result<(), IggyError> {
+    let stream_id = Identifier::named(&partition.stream)?;
+    let topic_id = Identifier::named(&partition.topic)?;
+    let partition_id = partition.partition_id;
+    let consumer = Consumer::new(Identifier::named(&config.consumer)?);
+    let mut offset = get_start_offset(source, target, &consumer, partition).await?;
+    info!(
+        "Mirroring partition: {partition_id} of topic: {}/{} from offset: {offset}...",
+        partition.stream, partition.topic
+    );
+
+    let partitioning = Partitioning::partition_id(partition_id);
+    loop {
+        let polled_messages = source
+            .long_poll_messages(
+                &stream_id,
+                &topic_id,
+                Some(partition_id),
+                &consumer,
+                &PollingStrategy::offset(offset),
+                config.batch_length,
+                false,
+                &config.poll_timeout,
+            )
+            .await?;
+        let Some(last_message) = polled_messages.messages.last() else {
+            continue;
+        };
+
+        let last_offset = last_message.header.offset;
+        let mut messages = polled_messages
+            .messages
+            .into_iter()
+            .map(to_mirrored_message)
+            .collect::<Result<Vec<_>, _>>()?;
+        target
+            .send_messages(&stream_id, &topic_id, &partitioning, &mut messages)
+            .await?;
+        source
+            .store_consumer_offset(
+                &consumer,
+                &stream_id,
+                &topic_id,
+                Some(partition_id),
+                last_offset,
+            )
+            .await?;
+        offset = last_offset + 1;
+    }
+}
+
+/// Returns the source offset to resume the mirroring from, which is the one following the offset
+/// of the last mirrored message found on the target partition or the checkpoint stored on the source server,
+/// whichever is greater, as the checkpoint might not have been stored after the messages were sent.
+async fn get_start_offset(
+    source: &IggyClient,
+    target: &IggyClient,
+    consumer: &Consumer,
+    partition: &MirroredPartition,
+) -> Result<u64, IggyError> {
+    let stream_id = Identifier::named(&partition.stream)?;
+    let topic_id = Identifier::named(&partition.topic)?;
+    let partition_id = Some(partition.partition_id);
+    let checkpoint = source
+        .get_consumer_offset(consumer, &stream_id, &topic_id, partition_id)
+        .await?
+        .map(|offset| offset.stored_offset);
+    let last_mirrored_offset = target
+        .poll_messages(
+            &stream_id,
+            &topic_id,
+            partition_id,
+            &Consumer::default(),
+            &PollingStrategy::last(),
+            1,
+            false,
+        )
+        .await?
+        .messages
+        .first()
+        .and_then(get_source_offset);
+
+    Ok(checkpoint
+        .max(last_mirrored_offset)
+        .map_or(0, |offset| offset + 1))
+}
+
+fn get_source_offset(message: &IggyMessage) -> Option<u64> {
+    message
+        .user_headers_map()
+        .ok()??
+        .get(&HeaderKey::new(SOURCE_OFFSET_HEADER).ok()?)?
+        .as_uint64()
+        .ok()
+}
+
+fn to_mirrored_message(message: IggyMessage) -> Result<IggyMessage, IggyError> {
+    let mut headers = message.user_headers_map()?.unwrap_or_default();
+    headers.insert(
+        HeaderKey::new(SOURCE_OFFSET_HEADER)?,
+        HeaderValue::from_uint64(message.header.offset)?,
+    );
+    let mut mirrored_message = IggyMessage::builder()
+        .id(message.header.id)
+        .payload(message.payload)
+        .user_headers(headers)
+        .build()?;
+    mirrored_message.header.origin_timestamp = message.header.origin_timestamp;
+    Ok(mirrored_message)
+}
+
+fn is_not_found(error: &IggyError) -> bool {
+    matches!(
+        error,
+        IggyError::StreamIdNotFound(_)
+            | IggyError::StreamNameNotFound(_)
+            | IggyError::TopicIdNotFound(_, _)
+            | IggyError::TopicNameNotFound(_, _)
+            | IggyError::PartitionNotFound(_, _, _)
+            | IggyError::ResourceNotFound(_)
+    )
+}